@@ -0,0 +1,110 @@
+// AI 相关 Tauri Commands
+use crate::state::AppState;
+use aumate_application::dto::conversation::ConversationDTO;
+use aumate_core_shared::{ApiError, UseCaseError};
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, State};
+
+/// AI 请求内容
+#[derive(Debug, Deserialize)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+pub enum AiChatRequest {
+    /// 表达润色
+    Polish { input: String },
+    /// AI 对话，历史从对话记录中读取。未指定 `conversation_id` 时新建对话
+    Dialogue { conversation_id: Option<i64>, content: String },
+}
+
+/// `ai-chat-chunk` 事件
+#[derive(Debug, Clone, Serialize)]
+struct AiChatChunkEvent<'a> {
+    request_id: &'a str,
+    delta: &'a str,
+}
+
+/// `ai-chat-conversation` 事件，回复开始前发送
+#[derive(Debug, Clone, Serialize)]
+struct AiChatConversationEvent<'a> {
+    request_id: &'a str,
+    conversation: &'a ConversationDTO,
+}
+
+/// 直接展示给用户的错误信息
+fn error_message(e: UseCaseError) -> String {
+    match e {
+        UseCaseError::InvalidRequest(message) => message,
+        UseCaseError::Infrastructure(e) => e.to_string(),
+        e => {
+            let api_error: ApiError = e.into();
+            api_error.to_string()
+        }
+    }
+}
+
+/// 发送 AI 请求，返回完整回复
+///
+/// 回复同时以 `ai-chat-chunk` 事件逐段发送。`request_id` 由前端生成，
+/// 用于区分事件以及通过 `cancel_ai_chat` 取消请求。对话模式下先发送
+/// `ai-chat-conversation` 事件告知消息所在的对话，完整的回复会保存到对话中
+#[tauri::command]
+pub async fn ai_chat(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    request_id: String,
+    request: AiChatRequest,
+) -> Result<String, String> {
+    log::info!("API: ai_chat called, request_id={}", request_id);
+
+    let polish_expression = state.polish_expression.clone();
+    let send_conversation_message = state.send_conversation_message.clone();
+    let id = request_id.clone();
+    let task = tokio::spawn(async move {
+        let mut stream = match request {
+            AiChatRequest::Polish { input } => polish_expression.execute(&input).await,
+            AiChatRequest::Dialogue { conversation_id, content } => {
+                send_conversation_message.execute(conversation_id, &content).await.map(|reply| {
+                    let event = AiChatConversationEvent {
+                        request_id: &id,
+                        conversation: &reply.conversation,
+                    };
+                    let _ = app.emit("ai-chat-conversation", event);
+                    reply.stream
+                })
+            }
+        }
+        .map_err(error_message)?;
+
+        let mut content = String::new();
+        while let Some(delta) = stream.next().await {
+            let delta = delta.map_err(|e| e.to_string())?;
+            let _ = app.emit("ai-chat-chunk", AiChatChunkEvent { request_id: &id, delta: &delta });
+            content.push_str(&delta);
+        }
+        Ok(content)
+    });
+
+    state.ai_requests().insert(request_id.clone(), task.abort_handle());
+    let result = task.await;
+    state.ai_requests().remove(&request_id);
+
+    match result {
+        Ok(result) => result,
+        Err(e) if e.is_cancelled() => Err("Request cancelled".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// 取消进行中的 AI 请求，请求不存在时返回 `false`
+#[tauri::command]
+pub async fn cancel_ai_chat(
+    state: State<'_, AppState>,
+    request_id: String,
+) -> Result<bool, String> {
+    log::info!("API: cancel_ai_chat called, request_id={}", request_id);
+
+    let Some(task) = state.ai_requests().remove(&request_id) else {
+        return Ok(false);
+    };
+    task.abort();
+    Ok(true)
+}
@@ -0,0 +1,30 @@
+// 截图标注相关 Tauri Commands
+use crate::state::AppState;
+use aumate_application::dto::annotation::RenderAnnotationsRequest;
+use aumate_core_domain::annotation::AnnotationDocument;
+use aumate_core_shared::ApiError;
+use tauri::State;
+
+/// 把标注文档绘制到图像上
+///
+/// 输入和输出都是 base64 编码的图像，输出格式默认 PNG
+#[tauri::command]
+pub async fn render_annotations(
+    state: State<'_, AppState>,
+    image_base64: String,
+    document: AnnotationDocument,
+    format: Option<String>,
+) -> Result<String, String> {
+    use base64::{Engine as _, engine::general_purpose::STANDARD};
+
+    log::info!("API: render_annotations called, {} annotations", document.annotations.len());
+
+    let image =
+        STANDARD.decode(&image_base64).map_err(|e| format!("Failed to decode base64: {}", e))?;
+    let request = RenderAnnotationsRequest { image, document, format };
+    let response = state.render_annotations.execute(request).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })?;
+    Ok(STANDARD.encode(response.data))
+}
@@ -0,0 +1,79 @@
+// 条码相关 Tauri Commands
+use crate::state::AppState;
+use aumate_application::dto::barcode::{GenerateQrCodeRequest, ScanBarcodesRequest};
+use aumate_core_domain::barcode::{DecodedBarcode, QrCodeOptions};
+use aumate_core_shared::{ApiError, ApplicationError, Rectangle};
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use serde::Serialize;
+use tauri::State;
+
+fn api_error(e: ApplicationError) -> String {
+    let api_error: ApiError = e.into();
+    api_error.to_string()
+}
+
+/// 生成的 QR 码
+#[derive(Debug, Clone, Serialize)]
+pub struct QrCodeResult {
+    /// base64 编码的图像
+    pub image_base64: String,
+    pub width: u32,
+    pub height: u32,
+    pub format: String,
+}
+
+/// 识别截图中的 QR 码、DataMatrix 和一维码
+///
+/// `region` 为图像像素，返回的位置相对于整张图像
+#[tauri::command]
+pub fn scan_barcodes(
+    state: State<'_, AppState>,
+    image_base64: String,
+    region: Option<Rectangle>,
+) -> Result<Vec<DecodedBarcode>, String> {
+    log::info!("API: scan_barcodes called, region: {:?}", region);
+
+    let image =
+        STANDARD.decode(&image_base64).map_err(|e| format!("Failed to decode base64: {}", e))?;
+    state.scan_barcodes.scan_image(ScanBarcodesRequest { image, region }).map_err(api_error)
+}
+
+/// 截取屏幕区域并识别条码，返回屏幕坐标
+///
+/// 未指定区域时识别光标所在的显示器
+#[tauri::command]
+pub async fn scan_screen_barcodes(
+    state: State<'_, AppState>,
+    region: Option<Rectangle>,
+) -> Result<Vec<DecodedBarcode>, String> {
+    log::info!("API: scan_screen_barcodes called, region: {:?}", region);
+
+    state.scan_barcodes.scan_screen(region).await.map_err(api_error)
+}
+
+/// 把文本生成为 QR 码，可同时复制到剪贴板
+#[tauri::command]
+pub async fn generate_qr_code(
+    state: State<'_, AppState>,
+    text: String,
+    options: Option<QrCodeOptions>,
+    copy_to_clipboard: Option<bool>,
+    format: Option<String>,
+) -> Result<QrCodeResult, String> {
+    log::info!("API: generate_qr_code called, {} bytes", text.len());
+
+    let request = GenerateQrCodeRequest {
+        text,
+        options: options.unwrap_or_default(),
+        copy_to_clipboard: copy_to_clipboard.unwrap_or(false),
+        format,
+    };
+    let response = state.generate_qr_code.execute(request).await.map_err(api_error)?;
+
+    Ok(QrCodeResult {
+        image_base64: STANDARD.encode(response.data),
+        width: response.width,
+        height: response.height,
+        format: response.format,
+    })
+}
@@ -17,6 +17,22 @@ pub async fn read_clipboard(state: State<'_, AppState>) -> Result<ReadClipboardR
     })
 }
 
+/// 以指定格式读取剪贴板内容
+///
+/// `format` 为 `text`、`html`、`rtf`、`image`、`files` 或任意 MIME 类型
+#[tauri::command]
+pub async fn read_clipboard_format(
+    state: State<'_, AppState>,
+    format: String,
+) -> Result<ReadClipboardResponse, String> {
+    log::info!("API: read_clipboard_format called, format={}", format);
+
+    state.read_clipboard.execute_format(&format).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
 /// 写入剪贴板内容
 #[tauri::command]
 pub async fn write_clipboard(
@@ -45,6 +61,8 @@ pub async fn clear_clipboard(state: State<'_, AppState>) -> Result<(), String> {
 }
 
 /// 获取剪贴板可用类型
+///
+/// 返回 `text`、`html`、`rtf`、`image`、`files`，其他格式以 MIME 类型返回
 #[tauri::command]
 pub async fn get_clipboard_types(state: State<'_, AppState>) -> Result<Vec<String>, String> {
     log::info!("API: get_clipboard_types called");
@@ -56,7 +74,7 @@ pub async fn get_clipboard_types(state: State<'_, AppState>) -> Result<Vec<Strin
         api_error.to_string()
     })?;
 
-    let type_strings = types.into_iter().map(|t| format!("{:?}", t)).collect();
+    let type_strings = types.into_iter().map(|t| t.to_string()).collect();
 
     Ok(type_strings)
 }
@@ -84,12 +102,10 @@ pub async fn write_clipboard_image(
 ) -> Result<(), String> {
     log::info!("API: write_clipboard_image called, size={}x{}", width, height);
 
-    // 直接调用 ClipboardAdapter 的 write_image_rgba 方法
-    state
-        .clipboard
-        .write_image_rgba(data, width as usize, height as usize)
-        .await
-        .map_err(|e| format!("Failed to write image to clipboard: {}", e))
+    state.write_clipboard_image.execute(data, width, height).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
 }
 
 /// 从 PNG base64 写入图像到剪贴板 (优化版)
@@ -101,26 +117,14 @@ pub async fn write_clipboard_image_png(
     log::info!("API: write_clipboard_image_png called, base64 length={}", png_base64.len());
 
     use base64::{Engine as _, engine::general_purpose::STANDARD};
-    use image::GenericImageView;
 
     // Decode base64 to PNG bytes
     let png_data =
         STANDARD.decode(&png_base64).map_err(|e| format!("Failed to decode base64: {}", e))?;
 
-    // Decode PNG to get RGBA pixels
-    let img =
-        image::load_from_memory(&png_data).map_err(|e| format!("Failed to decode PNG: {}", e))?;
-
-    let (width, height) = img.dimensions();
-    let rgba = img.to_rgba8();
-    let data = rgba.into_raw();
-
-    log::info!("API: Decoded PNG to {}x{} RGBA image", width, height);
-
-    // 直接调用 ClipboardAdapter 的 write_image_rgba 方法（绕过不支持图像的通用 write 方法）
-    state
-        .clipboard
-        .write_image_rgba(data, width as usize, height as usize)
-        .await
-        .map_err(|e| format!("Failed to write image to clipboard: {}", e))
+    // PNG 由剪贴板适配器解码，尺寸取自 PNG 头
+    state.write_clipboard_image.execute_png(png_data).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
 }
@@ -0,0 +1,87 @@
+// 剪贴板历史相关 Tauri Commands
+use crate::state::AppState;
+use aumate_application::dto::clipboard::ClipboardContentDTO;
+use aumate_application::dto::clipboard_history::{
+    ListClipboardHistoryRequest, ListClipboardHistoryResponse,
+};
+use aumate_core_shared::ApiError;
+use tauri::State;
+
+/// 列出或搜索剪贴板历史，置顶条目在前
+#[tauri::command]
+pub async fn list_clipboard_history(
+    state: State<'_, AppState>,
+    request: ListClipboardHistoryRequest,
+) -> Result<ListClipboardHistoryResponse, String> {
+    log::info!("API: list_clipboard_history called");
+
+    state.list_clipboard_history.execute(request).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 读取历史条目的完整内容
+#[tauri::command]
+pub async fn get_clipboard_history_content(
+    state: State<'_, AppState>,
+    id: i64,
+) -> Result<ClipboardContentDTO, String> {
+    log::info!("API: get_clipboard_history_content called, id={}", id);
+
+    state.get_clipboard_history_content.execute(id).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 将历史条目重新写入剪贴板
+#[tauri::command]
+pub async fn paste_clipboard_history(state: State<'_, AppState>, id: i64) -> Result<(), String> {
+    log::info!("API: paste_clipboard_history called, id={}", id);
+
+    state.paste_clipboard_history.execute(id).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 置顶或取消置顶历史条目
+#[tauri::command]
+pub async fn pin_clipboard_history(
+    state: State<'_, AppState>,
+    id: i64,
+    pinned: bool,
+) -> Result<(), String> {
+    log::info!("API: pin_clipboard_history called, id={}, pinned={}", id, pinned);
+
+    state.pin_clipboard_history.execute(id, pinned).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 删除历史条目
+#[tauri::command]
+pub async fn delete_clipboard_history(state: State<'_, AppState>, id: i64) -> Result<(), String> {
+    log::info!("API: delete_clipboard_history called, id={}", id);
+
+    state.delete_clipboard_history.execute(id).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 清空剪贴板历史，返回删除的条目数
+#[tauri::command]
+pub async fn clear_clipboard_history(
+    state: State<'_, AppState>,
+    keep_pinned: bool,
+) -> Result<usize, String> {
+    log::info!("API: clear_clipboard_history called, keep_pinned={}", keep_pinned);
+
+    state.delete_clipboard_history.execute_clear(keep_pinned).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
@@ -0,0 +1,95 @@
+// 取色器相关 Tauri Commands
+use crate::state::AppState;
+use aumate_application::dto::color_picker::PickedColor;
+use aumate_core_domain::color::ColorSample;
+use aumate_core_shared::{ApiError, Point, UseCaseError};
+use tauri::{AppHandle, Emitter, State};
+
+/// 光标移动时发送的取样事件，内容为 `ColorSample`
+const COLOR_PICKER_SAMPLE: &str = "color-picker-sample";
+
+fn api_error(e: UseCaseError) -> String {
+    let api_error: ApiError = e.into();
+    api_error.to_string()
+}
+
+/// 开始取色
+///
+/// 每台显示器截图一次，之后光标每次移动都从截图中取样，
+/// 通过 `color-picker-sample` 事件发送颜色和放大镜网格
+#[tauri::command]
+pub async fn start_color_picker(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    radius: Option<u32>,
+) -> Result<(), String> {
+    log::info!("API: start_color_picker called, radius: {:?}", radius);
+
+    state.color_picker.start_session().await.map_err(api_error)?;
+
+    let color_picker = state.color_picker.clone();
+    let guard = state.color_picker_events().on_mouse_move(move |&(x, y)| {
+        // 光标在所有显示器之外时没有可取的颜色，忽略即可
+        let Ok(sample) = color_picker.sample(Point::new(x, y), radius) else {
+            return;
+        };
+        if let Err(e) = app.emit(COLOR_PICKER_SAMPLE, sample) {
+            log::error!("Failed to emit {}: {}", COLOR_PICKER_SAMPLE, e);
+        }
+    })?;
+    *state.color_picker_guard() = Some(Box::new(guard));
+
+    Ok(())
+}
+
+/// 取指定屏幕坐标处的颜色和放大镜网格，不加入最近颜色
+#[tauri::command]
+pub fn sample_color(
+    state: State<'_, AppState>,
+    x: i32,
+    y: i32,
+    radius: Option<u32>,
+) -> Result<ColorSample, String> {
+    state.color_picker.sample(Point::new(x, y), radius).map_err(api_error)
+}
+
+/// 确认取色并加入最近颜色
+///
+/// 未指定坐标时使用最近一次取样的位置
+#[tauri::command]
+pub fn pick_color(
+    state: State<'_, AppState>,
+    x: Option<i32>,
+    y: Option<i32>,
+) -> Result<PickedColor, String> {
+    log::info!("API: pick_color called");
+
+    let position = x.zip(y).map(|(x, y)| Point::new(x, y));
+    state.color_picker.pick(position).map_err(api_error)
+}
+
+/// 结束取色，停止发送取样事件并释放截图
+#[tauri::command]
+pub fn stop_color_picker(state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("API: stop_color_picker called");
+
+    state.color_picker_guard().take();
+    state.color_picker_events().release();
+    state.color_picker.end_session();
+    Ok(())
+}
+
+/// 获取最近取过的颜色，最新的在前
+#[tauri::command]
+pub fn get_color_history(state: State<'_, AppState>) -> Result<Vec<PickedColor>, String> {
+    Ok(state.color_picker.history())
+}
+
+/// 清空最近颜色
+#[tauri::command]
+pub fn clear_color_history(state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("API: clear_color_history called");
+
+    state.color_picker.clear_history();
+    Ok(())
+}
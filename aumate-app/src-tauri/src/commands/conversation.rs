@@ -0,0 +1,98 @@
+// AI 对话记录相关 Tauri Commands
+//
+// 发送消息通过 `ai_chat` 的 `dialogue` 模式
+use crate::state::AppState;
+use aumate_application::dto::conversation::{
+    ConversationDetailDTO, ListConversationsRequest, ListConversationsResponse,
+};
+use aumate_core_shared::ApiError;
+use tauri::State;
+
+/// 列出或搜索对话，最近更新的在前
+#[tauri::command]
+pub async fn list_conversations(
+    state: State<'_, AppState>,
+    request: ListConversationsRequest,
+) -> Result<ListConversationsResponse, String> {
+    log::info!("API: list_conversations called");
+
+    state.list_conversations.execute(request).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 读取对话及其全部消息，用于恢复对话
+#[tauri::command]
+pub async fn get_conversation(
+    state: State<'_, AppState>,
+    id: i64,
+) -> Result<ConversationDetailDTO, String> {
+    log::info!("API: get_conversation called, id={}", id);
+
+    state.get_conversation.execute(id).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 重命名对话
+#[tauri::command]
+pub async fn rename_conversation(
+    state: State<'_, AppState>,
+    id: i64,
+    title: String,
+) -> Result<(), String> {
+    log::info!("API: rename_conversation called, id={}", id);
+
+    state.rename_conversation.execute(id, &title).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 删除对话
+#[tauri::command]
+pub async fn delete_conversation(state: State<'_, AppState>, id: i64) -> Result<(), String> {
+    log::info!("API: delete_conversation called, id={}", id);
+
+    state.delete_conversation.execute(id).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 删除全部对话，返回删除的数量
+#[tauri::command]
+pub async fn clear_conversations(state: State<'_, AppState>) -> Result<usize, String> {
+    log::info!("API: clear_conversations called");
+
+    state.delete_conversation.execute_clear().await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 导出对话为 Markdown
+///
+/// 指定 `path` 时写入文件，始终返回导出的内容
+#[tauri::command]
+pub async fn export_conversation_markdown(
+    state: State<'_, AppState>,
+    id: i64,
+    path: Option<String>,
+) -> Result<String, String> {
+    log::info!("API: export_conversation_markdown called, id={}", id);
+
+    let markdown = state.export_conversation.execute(id).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })?;
+
+    if let Some(path) = path {
+        tokio::fs::write(&path, &markdown)
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+    Ok(markdown)
+}
@@ -0,0 +1,58 @@
+// 应用和文件启动器相关 Tauri Commands
+use crate::state::AppState;
+use aumate_application::dto::launcher::{
+    LauncherIndexStatsDTO, SearchLauncherRequest, SearchLauncherResponse,
+};
+use aumate_core_shared::ApiError;
+use tauri::State;
+
+/// 搜索应用和文件，按匹配程度和使用频率排序
+#[tauri::command]
+pub async fn search_launcher(
+    state: State<'_, AppState>,
+    request: SearchLauncherRequest,
+) -> Result<SearchLauncherResponse, String> {
+    state.search_launcher.execute(request).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 增量刷新启动器索引
+#[tauri::command]
+pub async fn refresh_launcher_index(
+    state: State<'_, AppState>,
+) -> Result<LauncherIndexStatsDTO, String> {
+    log::info!("API: refresh_launcher_index called");
+
+    state.refresh_launcher_index.execute().await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 启动应用或打开文件
+#[tauri::command]
+pub async fn launch_launcher_item(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    log::info!("API: launch_launcher_item called, id={}", id);
+
+    state.launch_item.execute(&id).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 读取启动项图标，返回 data URL，没有图标时返回 None
+#[tauri::command]
+pub async fn get_launcher_icon(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Option<String>, String> {
+    use base64::{Engine as _, engine::general_purpose::STANDARD};
+
+    let icon = state.get_launcher_icon.execute(&id).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })?;
+    Ok(icon.map(|icon| format!("data:{};base64,{}", icon.mime_type, STANDARD.encode(icon.data))))
+}
@@ -0,0 +1,45 @@
+// 测量相关 Tauri Commands
+use crate::state::AppState;
+use aumate_application::dto::measurement::MeasurementSessionInfo;
+use aumate_core_domain::measurement::Measurement;
+use aumate_core_shared::{ApiError, MonitorId, Point, UseCaseError};
+use tauri::State;
+
+fn api_error(e: UseCaseError) -> String {
+    let api_error: ApiError = e.into();
+    api_error.to_string()
+}
+
+/// 开始测量
+///
+/// 截取显示器并做一次边缘检测，未指定显示器时使用光标所在的显示器。
+/// 返回截图尺寸和缩放比例，前端据此把 CSS 像素换算为截图像素
+#[tauri::command]
+pub async fn start_measurement(
+    state: State<'_, AppState>,
+    monitor_id: Option<u32>,
+) -> Result<MeasurementSessionInfo, String> {
+    log::info!("API: start_measurement called, monitor_id: {:?}", monitor_id);
+
+    // UI 自动化不可用时仍可测量间距，只是没有元素边界
+    if let Err(e) = state.ui_automation.init().await {
+        log::warn!("Failed to init UI automation for measurement: {}", e);
+    }
+
+    state.measurement.start(monitor_id.map(MonitorId::new)).await.map_err(api_error)
+}
+
+/// 测量截图像素 `(x, y)` 处的间距和 UI 元素，光标每次移动时调用
+#[tauri::command]
+pub async fn measure_at(state: State<'_, AppState>, x: i32, y: i32) -> Result<Measurement, String> {
+    state.measurement.measure(Point::new(x, y)).await.map_err(api_error)
+}
+
+/// 结束测量，释放边缘图
+#[tauri::command]
+pub fn stop_measurement(state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("API: stop_measurement called");
+
+    state.measurement.stop();
+    Ok(())
+}
@@ -2,15 +2,25 @@
 //
 // 每个模块对应一个功能域
 
+pub mod ai;
+pub mod annotation;
+pub mod barcode;
 pub mod clipboard;
+pub mod clipboard_history;
+pub mod color_picker;
+pub mod conversation;
 pub mod draw;
 pub mod element_scanner;
 pub mod frontend_log;
 pub mod hotkey;
+pub mod launcher;
+pub mod measurement;
 pub mod monitor;
 pub mod page;
 pub mod permissions;
+pub mod redaction;
 pub mod screenshot;
+pub mod screenshot_library;
 pub mod scroll;
 pub mod settings;
 pub mod ui;
@@ -18,14 +28,24 @@ pub mod ui_control;
 pub mod window;
 
 // Re-export all commands
+pub use ai::*;
+pub use annotation::*;
+pub use barcode::*;
 pub use clipboard::*;
+pub use clipboard_history::*;
+pub use color_picker::*;
+pub use conversation::*;
 pub use draw::*;
 pub use element_scanner::*;
 pub use frontend_log::*;
 pub use hotkey::*;
+pub use launcher::*;
+pub use measurement::*;
 pub use monitor::*;
 pub use page::*;
+pub use redaction::*;
 pub use screenshot::*;
+pub use screenshot_library::*;
 pub use scroll::*;
 pub use settings::*;
 pub use ui::*;
@@ -0,0 +1,73 @@
+// 截图隐私遮挡相关 Tauri Commands
+use crate::state::AppState;
+use aumate_application::dto::redaction::RedactScreenshotRequest;
+use aumate_core_domain::redaction::{RedactionMethod, RedactionReport, SensitiveKind};
+use aumate_core_shared::{ApiError, Rectangle};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+/// 遮挡选项
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct RedactOptions {
+    pub method: RedactionMethod,
+    /// 手动指定的区域 (图像像素)
+    pub regions: Vec<Rectangle>,
+    /// 先 OCR 再检测邮箱、令牌、IP、银行卡号，需要 `ocr` feature
+    pub auto: bool,
+    pub detectors: Option<Vec<SensitiveKind>>,
+    pub redact_all_text: bool,
+    pub format: Option<String>,
+}
+
+/// 遮挡结果
+#[derive(Debug, Clone, Serialize)]
+pub struct RedactResult {
+    /// base64 编码的图像
+    pub image_base64: String,
+    pub width: u32,
+    pub height: u32,
+    pub format: String,
+    pub report: RedactionReport,
+}
+
+/// 遮挡截图中的敏感区域，返回新图像和遮挡报告
+#[tauri::command]
+pub async fn redact_screenshot(
+    state: State<'_, AppState>,
+    image_base64: String,
+    options: Option<RedactOptions>,
+) -> Result<RedactResult, String> {
+    use base64::{Engine as _, engine::general_purpose::STANDARD};
+
+    let options = options.unwrap_or_default();
+    log::info!(
+        "API: redact_screenshot called, {} regions, auto: {}",
+        options.regions.len(),
+        options.auto
+    );
+
+    let image =
+        STANDARD.decode(&image_base64).map_err(|e| format!("Failed to decode base64: {}", e))?;
+    let request = RedactScreenshotRequest {
+        image,
+        method: options.method,
+        regions: options.regions,
+        auto: options.auto,
+        detectors: options.detectors,
+        redact_all_text: options.redact_all_text,
+        format: options.format,
+    };
+    let response = state.redact_screenshot.execute(request).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })?;
+
+    Ok(RedactResult {
+        image_base64: STANDARD.encode(response.data),
+        width: response.width,
+        height: response.height,
+        format: response.format,
+        report: response.report,
+    })
+}
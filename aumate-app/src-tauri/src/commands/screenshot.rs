@@ -1,9 +1,13 @@
 // 截图相关 Tauri Commands
 use crate::state::AppState;
-use aumate_application::dto::{CaptureRegionRequest, CaptureResponse, CaptureScreenRequest};
+use aumate_application::dto::{
+    CaptureRegionRequest, CaptureResponse, CaptureScreenRequest, SaveScreenshotRequest,
+    SaveScreenshotResponse,
+};
+use aumate_core_domain::hdr::ToneMappingOperator;
 use aumate_core_shared::{ApiError, DomainError, MonitorId, Rectangle};
 use image::DynamicImage;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, State};
 
 /// 捕获当前监视器
@@ -13,6 +17,7 @@ pub async fn capture_current_monitor(
     format: String,
     quality: Option<u8>,
     hdr_correction: Option<bool>,
+    tone_mapping: Option<ToneMappingOperator>,
 ) -> Result<CaptureResponse, String> {
     log::info!("API: capture_current_monitor called");
 
@@ -21,6 +26,7 @@ pub async fn capture_current_monitor(
         format,
         quality,
         hdr_correction: hdr_correction.unwrap_or(false),
+        tone_mapping,
     };
 
     state.capture_screen.execute(request).await.map_err(|e| {
@@ -37,6 +43,7 @@ pub async fn capture_monitor(
     format: String,
     quality: Option<u8>,
     hdr_correction: Option<bool>,
+    tone_mapping: Option<ToneMappingOperator>,
 ) -> Result<CaptureResponse, String> {
     log::info!("API: capture_monitor called, monitor_id={}", monitor_id);
 
@@ -45,6 +52,7 @@ pub async fn capture_monitor(
         format,
         quality,
         hdr_correction: hdr_correction.unwrap_or(false),
+        tone_mapping,
     };
 
     state.capture_screen.execute(request).await.map_err(|e| {
@@ -64,6 +72,8 @@ pub async fn capture_region(
     monitor_id: Option<String>,
     format: String,
     quality: Option<u8>,
+    hdr_correction: Option<bool>,
+    tone_mapping: Option<ToneMappingOperator>,
 ) -> Result<CaptureResponse, String> {
     log::info!("API: capture_region called, region=({},{},{}x{})", x, y, width, height);
 
@@ -75,6 +85,8 @@ pub async fn capture_region(
         monitor_id: monitor_id.map(|id| MonitorId::new(id.parse().unwrap_or(0))),
         format,
         quality,
+        hdr_correction: hdr_correction.unwrap_or(false),
+        tone_mapping,
     };
 
     state.capture_region.execute(request).await.map_err(|e| {
@@ -83,6 +95,53 @@ pub async fn capture_region(
     })
 }
 
+/// 保存选项，未指定的项使用设置中的 `screenshot`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SaveScreenshotOptions {
+    /// 截图时的前台窗口标题和应用名，用于文件名模板
+    pub window_title: Option<String>,
+    pub app_name: Option<String>,
+    pub monitor_id: Option<String>,
+    pub folder: Option<String>,
+    pub filename_pattern: Option<String>,
+    pub format: Option<String>,
+    pub quality: Option<u8>,
+    pub copy_to_clipboard: Option<bool>,
+}
+
+/// 按设置保存截图，返回保存路径
+#[tauri::command]
+pub async fn save_screenshot(
+    state: State<'_, AppState>,
+    image_base64: String,
+    options: Option<SaveScreenshotOptions>,
+) -> Result<SaveScreenshotResponse, String> {
+    use base64::{Engine as _, engine::general_purpose::STANDARD};
+
+    log::info!("API: save_screenshot called");
+
+    let options = options.unwrap_or_default();
+    let image =
+        STANDARD.decode(&image_base64).map_err(|e| format!("Failed to decode base64: {}", e))?;
+    let request = SaveScreenshotRequest {
+        image,
+        window_title: options.window_title,
+        app_name: options.app_name,
+        monitor_id: options.monitor_id,
+        folder: options.folder,
+        filename_pattern: options.filename_pattern,
+        format: options.format,
+        quality: options.quality,
+        copy_to_clipboard: options.copy_to_clipboard,
+    };
+
+    state.save_screenshot.execute(request).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
 // ============= 截图编辑器专用命令 =============
 
 #[derive(Debug, Clone, Serialize)]
@@ -369,6 +428,7 @@ mod tests {
             format: "png".to_string(),
             quality: Some(95),
             hdr_correction: false,
+            tone_mapping: None,
         };
         assert_eq!(request.format, "png");
         assert_eq!(request.quality, Some(95));
@@ -0,0 +1,97 @@
+// 截图库相关 Tauri Commands
+use crate::state::AppState;
+use aumate_application::dto::screenshot_library::{
+    ListScreenshotsRequest, ListScreenshotsResponse, ScreenshotRecordDTO, UpdateScreenshotRequest,
+};
+use aumate_core_shared::ApiError;
+use tauri::State;
+
+/// 按关键字和标签列出截图，最新的在前
+#[tauri::command]
+pub async fn list_screenshots(
+    state: State<'_, AppState>,
+    request: ListScreenshotsRequest,
+) -> Result<ListScreenshotsResponse, String> {
+    state.list_screenshots.execute(request).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 读取截图缩略图，返回 PNG data URL
+#[tauri::command]
+pub async fn get_screenshot_thumbnail(
+    state: State<'_, AppState>,
+    id: i64,
+) -> Result<String, String> {
+    use base64::{Engine as _, engine::general_purpose::STANDARD};
+
+    let thumbnail = state.get_screenshot_thumbnail.execute(id).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })?;
+    Ok(format!("data:image/png;base64,{}", STANDARD.encode(thumbnail)))
+}
+
+/// 用系统默认程序打开截图
+#[tauri::command]
+pub async fn open_screenshot(state: State<'_, AppState>, id: i64) -> Result<(), String> {
+    log::info!("API: open_screenshot called, id={}", id);
+
+    state.open_screenshot.execute(id).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 把截图复制到剪贴板
+#[tauri::command]
+pub async fn copy_screenshot(state: State<'_, AppState>, id: i64) -> Result<(), String> {
+    log::info!("API: copy_screenshot called, id={}", id);
+
+    state.copy_screenshot.execute(id).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 修改截图的标签或备注
+#[tauri::command]
+pub async fn update_screenshot(
+    state: State<'_, AppState>,
+    request: UpdateScreenshotRequest,
+) -> Result<ScreenshotRecordDTO, String> {
+    log::info!("API: update_screenshot called, id={}", request.id);
+
+    state.update_screenshot.execute(request).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 删除截图及其文件
+#[tauri::command]
+pub async fn delete_screenshot(state: State<'_, AppState>, id: i64) -> Result<(), String> {
+    log::info!("API: delete_screenshot called, id={}", id);
+
+    state.delete_screenshot.execute(id).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
+
+/// 识别截图中的文本并保存，之后可以按文本搜索
+///
+/// 需要启用 `ocr` feature 并下载 OCR 模型
+#[tauri::command]
+pub async fn index_screenshot_text(state: State<'_, AppState>, id: i64) -> Result<String, String> {
+    log::info!("API: index_screenshot_text called, id={}", id);
+
+    let Some(index_screenshot_text) = &state.index_screenshot_text else {
+        return Err("Text recognition is not available in this build".to_string());
+    };
+    index_screenshot_text.execute(id).await.map_err(|e| {
+        let api_error: ApiError = e.into();
+        api_error.to_string()
+    })
+}
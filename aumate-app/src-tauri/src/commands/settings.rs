@@ -1,36 +1,94 @@
 // Settings Commands
 use crate::state::AppState;
 use aumate_core_domain::settings::Settings;
-use aumate_core_shared::ApiError;
+use aumate_core_shared::{ApiError, ApplicationError, DomainError, FieldError, UseCaseError};
+use serde::Serialize;
 use tauri::{Emitter, State};
 
+/// Error returned by the settings commands
+///
+/// `errors` lists the invalid fields so the settings window can show them inline.
+#[derive(Debug, Serialize)]
+pub struct SettingsError {
+    pub message: String,
+    pub errors: Vec<FieldError>,
+}
+
+impl From<ApplicationError> for SettingsError {
+    fn from(e: ApplicationError) -> Self {
+        match e {
+            UseCaseError::Domain(DomainError::InvalidFields(errors)) => {
+                Self { message: "Invalid settings".to_string(), errors }
+            }
+            e => {
+                let api_error: ApiError = e.into();
+                Self { message: api_error.to_string(), errors: Vec::new() }
+            }
+        }
+    }
+}
+
+/// Payload of the `settings-recovered` event
+#[derive(Debug, Clone, Serialize)]
+struct SettingsRecoveredEvent {
+    migrated_from: Option<u32>,
+    /// Fields that were reset to their defaults
+    errors: Vec<FieldError>,
+    backup_path: Option<String>,
+}
+
 /// Get application settings
+///
+/// API keys are masked; the plaintext keys never reach the webview. If the
+/// settings file had to be migrated or repaired, a `settings-recovered` event is
+/// emitted as well.
 #[tauri::command]
-pub async fn get_settings(state: State<'_, AppState>) -> Result<Settings, String> {
+pub async fn get_settings(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<Settings, SettingsError> {
     log::info!("API: get_settings called");
 
-    state.get_settings.execute().await.map_err(|e| {
-        let api_error: ApiError = e.into();
-        api_error.to_string()
-    })
+    let mut report = state.get_settings.execute_with_report().await?;
+    state.settings_secrets.mask(&mut report.settings).await?;
+    if !report.is_clean() {
+        let event = SettingsRecoveredEvent {
+            migrated_from: report.migrated_from,
+            errors: report.errors,
+            backup_path: report.backup_path,
+        };
+        let _ = app.emit("settings-recovered", &event);
+    }
+
+    Ok(report.settings)
 }
 
 /// Save application settings
+///
+/// Masked API keys are kept as they are, anything else replaces the stored key.
 #[tauri::command]
 pub async fn save_settings(
     state: State<'_, AppState>,
     app: tauri::AppHandle,
     settings: Settings,
-) -> Result<(), String> {
+) -> Result<(), SettingsError> {
     log::info!("API: save_settings called");
 
-    state.save_settings.execute(settings.clone()).await.map_err(|e| {
-        let api_error: ApiError = e.into();
-        api_error.to_string()
-    })?;
+    let mut saved = state.save_settings.execute(settings).await?;
+
+    state.clipboard_watcher.update_settings(saved.clipboard_history.clone());
+
+    // 启动器的文件夹可能有变化，后台重新索引
+    let refresh_launcher_index = state.refresh_launcher_index.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = refresh_launcher_index.execute().await {
+            log::warn!("Failed to refresh launcher index: {}", e);
+        }
+    });
 
     // Emit settings-changed event to all windows
-    let _ = app.emit("settings-changed", &settings);
+    state.settings_secrets.mask(&mut saved).await?;
+    let _ = app.emit("settings-changed", &saved);
 
     Ok(())
 }
@@ -1,11 +1,34 @@
 // 窗口管理相关 Tauri Commands
 use crate::state::AppState;
 use aumate_application::dto::{
-    CreateWindowRequest, CreateWindowResponse, DragWindowRequest, ResizeWindowRequest,
-    WindowElementDto,
+    CreateWindowRequest, CreateWindowResponse, DragWindowRequest, PinImageRequest,
+    ResizeWindowRequest, WindowElementDto,
 };
-use aumate_core_shared::{ApiError, Point, WindowId};
-use tauri::State;
+use aumate_core_domain::pin::PinnedWindow;
+use aumate_core_shared::{ApiError, Point, UseCaseError, WindowId};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// 贴图状态变化时发送给贴图窗口的事件
+const PINNED_WINDOW_UPDATED: &str = "pinned-window-updated";
+
+/// 贴图窗口加载的内容
+#[derive(Debug, Clone, Serialize)]
+pub struct PinnedWindowContent {
+    pub window: PinnedWindow,
+    /// base64 编码的 PNG
+    pub image_base64: String,
+}
+
+fn to_api_error(e: UseCaseError) -> String {
+    let api_error: ApiError = e.into();
+    api_error.to_string()
+}
+
+/// 把最新状态通知贴图窗口 (缩放、透明度等由页面渲染)
+fn notify_pinned(app: &AppHandle, window: &PinnedWindow) {
+    let _ = app.emit_to(window.id.as_str(), PINNED_WINDOW_UPDATED, window);
+}
 
 /// 创建窗口
 #[tauri::command]
@@ -66,26 +89,185 @@ pub async fn resize_window(
     })
 }
 
-/// 固定窗口（置顶）
+/// 把图像贴到屏幕上 (无边框置顶窗口)
+///
+/// `x`、`y` 为屏幕物理像素，未指定时居中显示
 #[tauri::command]
-pub async fn pin_window(state: State<'_, AppState>, window_id: String) -> Result<(), String> {
-    log::info!("API: pin_window called, window_id={}", window_id);
+pub async fn pin_window(
+    state: State<'_, AppState>,
+    image_base64: String,
+    x: Option<i32>,
+    y: Option<i32>,
+) -> Result<PinnedWindow, String> {
+    use base64::{Engine as _, engine::general_purpose::STANDARD};
+
+    log::info!("API: pin_window called");
+
+    let image =
+        STANDARD.decode(&image_base64).map_err(|e| format!("Failed to decode base64: {}", e))?;
+    let position = match (x, y) {
+        (Some(x), Some(y)) => Some(Point::new(x, y)),
+        _ => None,
+    };
 
-    state.window_management.set_always_on_top(WindowId::new(window_id), true).await.map_err(|e| {
-        let api_error: ApiError = e.into();
-        api_error.to_string()
-    })
+    state
+        .window_management
+        .pin_image(PinImageRequest { image, position })
+        .await
+        .map_err(to_api_error)
 }
 
-/// 取消固定窗口
+/// 关闭贴图窗口
 #[tauri::command]
 pub async fn unpin_window(state: State<'_, AppState>, window_id: String) -> Result<(), String> {
     log::info!("API: unpin_window called, window_id={}", window_id);
 
-    state.window_management.set_always_on_top(WindowId::new(window_id), false).await.map_err(|e| {
-        let api_error: ApiError = e.into();
-        api_error.to_string()
-    })
+    state.window_management.unpin(&WindowId::new(window_id)).await.map_err(to_api_error)
+}
+
+/// 关闭所有贴图
+#[tauri::command]
+pub async fn unpin_all_windows(state: State<'_, AppState>) -> Result<(), String> {
+    log::info!("API: unpin_all_windows called");
+
+    state.window_management.unpin_all().await.map_err(to_api_error)
+}
+
+/// 取消所有贴图的鼠标穿透 (托盘菜单调用)
+pub async fn release_pinned_windows(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let windows =
+        state.window_management.set_all_pinned_click_through(false).await.map_err(to_api_error)?;
+    for window in &windows {
+        notify_pinned(app, window);
+    }
+    Ok(())
+}
+
+/// 关闭所有贴图 (托盘菜单调用)
+pub async fn close_pinned_windows(app: &AppHandle) -> Result<(), String> {
+    app.state::<AppState>().window_management.unpin_all().await.map_err(to_api_error)
+}
+
+/// 列出所有贴图 (包括隐藏的)
+#[tauri::command]
+pub async fn list_pinned_windows(state: State<'_, AppState>) -> Result<Vec<PinnedWindow>, String> {
+    Ok(state.window_management.list_pinned_windows())
+}
+
+/// 获取贴图的状态和图像，供贴图页面加载
+#[tauri::command]
+pub async fn get_pinned_window(
+    state: State<'_, AppState>,
+    window_id: String,
+) -> Result<PinnedWindowContent, String> {
+    use base64::{Engine as _, engine::general_purpose::STANDARD};
+
+    let window_id = WindowId::new(window_id);
+    let window = state.window_management.get_pinned_window(&window_id).map_err(to_api_error)?;
+    let image = state.window_management.get_pinned_image(&window_id).map_err(to_api_error)?;
+
+    Ok(PinnedWindowContent { window, image_base64: STANDARD.encode(image) })
+}
+
+/// 缩放贴图
+#[tauri::command]
+pub async fn zoom_pinned_window(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    window_id: String,
+    zoom: f64,
+) -> Result<PinnedWindow, String> {
+    let window = state
+        .window_management
+        .zoom_pinned_window(&WindowId::new(window_id), zoom)
+        .await
+        .map_err(to_api_error)?;
+    notify_pinned(&app, &window);
+    Ok(window)
+}
+
+/// 设置贴图不透明度
+#[tauri::command]
+pub async fn set_pinned_opacity(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    window_id: String,
+    opacity: f64,
+) -> Result<PinnedWindow, String> {
+    let window = state
+        .window_management
+        .set_pinned_opacity(&WindowId::new(window_id), opacity)
+        .map_err(to_api_error)?;
+    notify_pinned(&app, &window);
+    Ok(window)
+}
+
+/// 切换贴图的鼠标穿透
+#[tauri::command]
+pub async fn set_pinned_click_through(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    window_id: String,
+    enabled: bool,
+) -> Result<PinnedWindow, String> {
+    log::info!(
+        "API: set_pinned_click_through called, window_id={}, enabled={}",
+        window_id,
+        enabled
+    );
+
+    let window = state
+        .window_management
+        .set_pinned_click_through(&WindowId::new(window_id), enabled)
+        .await
+        .map_err(to_api_error)?;
+    notify_pinned(&app, &window);
+    Ok(window)
+}
+
+/// 移动贴图，`snap` 默认开启，吸附到屏幕边缘和其他贴图
+#[tauri::command]
+pub async fn move_pinned_window(
+    state: State<'_, AppState>,
+    window_id: String,
+    x: i32,
+    y: i32,
+    snap: Option<bool>,
+) -> Result<PinnedWindow, String> {
+    state
+        .window_management
+        .move_pinned_window(&WindowId::new(window_id), Point::new(x, y), snap.unwrap_or(true))
+        .await
+        .map_err(to_api_error)
+}
+
+/// 显示或隐藏贴图，`window_id` 为空时作用于所有贴图
+#[tauri::command]
+pub async fn set_pinned_visible(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    window_id: Option<String>,
+    visible: bool,
+) -> Result<Vec<PinnedWindow>, String> {
+    log::info!("API: set_pinned_visible called, window_id={:?}, visible={}", window_id, visible);
+
+    let windows = match window_id {
+        Some(window_id) => vec![
+            state
+                .window_management
+                .set_pinned_visible(&WindowId::new(window_id), visible)
+                .await
+                .map_err(to_api_error)?,
+        ],
+        None => {
+            state.window_management.set_all_pinned_visible(visible).await.map_err(to_api_error)?
+        }
+    };
+    for window in &windows {
+        notify_pinned(&app, window);
+    }
+    Ok(windows)
 }
 
 /// 关闭窗口
@@ -235,11 +417,7 @@ pub async fn set_window_vibrancy(
     window_label: String,
     enabled: bool,
 ) -> Result<(), String> {
-    log::info!(
-        "API: set_window_vibrancy called, window={}, enabled={}",
-        window_label,
-        enabled
-    );
+    log::info!("API: set_window_vibrancy called, window={}, enabled={}", window_label, enabled);
 
     // 获取窗口 ID
     let window_id = aumate_core_shared::WindowId::new(window_label.clone());
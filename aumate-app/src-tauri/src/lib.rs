@@ -166,8 +166,19 @@ pub fn run() {
             // Create system tray menu
             let about = MenuItem::with_id(app, "about", "About Aumate", true, None::<&str>)?;
             let settings = MenuItem::with_id(app, "settings", "Settings", true, None::<&str>)?;
+            // 鼠标穿透的贴图收不到输入，只能从托盘恢复或关闭
+            let release_pins = MenuItem::with_id(
+                app,
+                "release_pins",
+                "Release Pinned Images",
+                true,
+                None::<&str>,
+            )?;
+            let close_pins =
+                MenuItem::with_id(app, "close_pins", "Close Pinned Images", true, None::<&str>)?;
             let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&about, &settings, &quit])?;
+            let menu =
+                Menu::with_items(app, &[&about, &settings, &release_pins, &close_pins, &quit])?;
 
             // Create system tray
             let _tray = TrayIconBuilder::new()
@@ -201,6 +212,22 @@ pub fn run() {
                     "settings" => {
                         show_settings_window(app);
                     }
+                    "release_pins" => {
+                        let app = app.clone();
+                        tauri::async_runtime::spawn(async move {
+                            if let Err(e) = release_pinned_windows(&app).await {
+                                log::error!("Failed to release pinned windows: {}", e);
+                            }
+                        });
+                    }
+                    "close_pins" => {
+                        let app = app.clone();
+                        tauri::async_runtime::spawn(async move {
+                            if let Err(e) = close_pinned_windows(&app).await {
+                                log::error!("Failed to close pinned windows: {}", e);
+                            }
+                        });
+                    }
                     "quit" => {
                         app.exit(0);
                     }
@@ -358,6 +385,15 @@ pub fn run() {
 
             Ok(())
         })
+        .on_window_event(|window, event| {
+            // 贴图窗口可能被系统直接关闭，同步清理注册表
+            if let tauri::WindowEvent::Destroyed = event {
+                let window_id = aumate_core_shared::WindowId::new(window.label().to_string());
+                if window.state::<state::AppState>().window_management.forget_pinned(&window_id) {
+                    log::info!("Pinned window {} destroyed", window.label());
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             // UI control commands
             show_command_palette,
@@ -368,10 +404,50 @@ pub fn run() {
             // Settings commands
             get_settings,
             save_settings,
+            // AI commands
+            ai_chat,
+            cancel_ai_chat,
+            // AI dialogue conversation commands
+            list_conversations,
+            get_conversation,
+            rename_conversation,
+            delete_conversation,
+            clear_conversations,
+            export_conversation_markdown,
+            // Launcher commands
+            search_launcher,
+            refresh_launcher_index,
+            launch_launcher_item,
+            get_launcher_icon,
+            list_screenshots,
+            get_screenshot_thumbnail,
+            open_screenshot,
+            copy_screenshot,
+            update_screenshot,
+            delete_screenshot,
+            index_screenshot_text,
+            render_annotations,
+            redact_screenshot,
+            // Color picker commands
+            start_color_picker,
+            sample_color,
+            pick_color,
+            stop_color_picker,
+            get_color_history,
+            clear_color_history,
+            // Measurement commands
+            start_measurement,
+            measure_at,
+            stop_measurement,
+            // Barcode commands
+            scan_barcodes,
+            scan_screen_barcodes,
+            generate_qr_code,
             // Screenshot commands
             capture_current_monitor,
             capture_monitor,
             capture_region,
+            save_screenshot,
             capture_all_monitors,
             get_screenshot_window_elements,
             // Draw window commands
@@ -382,18 +458,34 @@ pub fn run() {
             get_current_monitor,
             // Clipboard commands
             read_clipboard,
+            read_clipboard_format,
             write_clipboard,
             clear_clipboard,
             get_clipboard_types,
             read_clipboard_image,
             write_clipboard_image,
             write_clipboard_image_png,
+            // Clipboard history commands
+            list_clipboard_history,
+            get_clipboard_history_content,
+            paste_clipboard_history,
+            pin_clipboard_history,
+            delete_clipboard_history,
+            clear_clipboard_history,
             // Window management commands
             create_window,
             drag_window,
             resize_window,
             pin_window,
             unpin_window,
+            unpin_all_windows,
+            list_pinned_windows,
+            get_pinned_window,
+            zoom_pinned_window,
+            set_pinned_opacity,
+            set_pinned_click_through,
+            move_pinned_window,
+            set_pinned_visible,
             close_window,
             get_window_elements,
             switch_to_window,
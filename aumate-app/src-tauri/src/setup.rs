@@ -3,23 +3,64 @@ use crate::state::AppState;
 use aumate_application::use_cases::{
     CaptureRegionUseCase, CaptureScreenUseCase, CheckGlobalShortcutAvailabilityUseCase,
     ClickElementUseCase, CloseDesktopWindowUseCase, FocusElementUseCase, GetWindowElementsUseCase,
-    RegisterGlobalShortcutUseCase, ScanElementsUseCase, ScrollScreenshotUseCase,
-    SetWindowVibrancyUseCase, SwitchToWindowUseCase, UnregisterGlobalShortcutUseCase,
-    WindowManagementUseCase,
+    RegisterGlobalShortcutUseCase, SaveScreenshotUseCase, ScanElementsUseCase,
+    ScrollScreenshotUseCase, SetWindowVibrancyUseCase, SwitchToWindowUseCase,
+    UnregisterGlobalShortcutUseCase, WindowManagementUseCase,
+    annotation::RenderAnnotationsUseCase,
+    barcode::{GenerateQrCodeUseCase, ScanBarcodesUseCase},
     clipboard::{
         ReadClipboardImageUseCase, ReadClipboardUseCase, WriteClipboardImageUseCase,
         WriteClipboardUseCase,
     },
+    clipboard_history::{
+        DeleteClipboardHistoryUseCase, GetClipboardHistoryContentUseCase,
+        ListClipboardHistoryUseCase, PasteClipboardHistoryUseCase, PinClipboardHistoryUseCase,
+    },
+    color_picker::ColorPickerUseCase,
+    conversation::{
+        DeleteConversationUseCase, ExportConversationUseCase, GetConversationUseCase,
+        ListConversationsUseCase, RenameConversationUseCase, SendConversationMessageUseCase,
+    },
+    launcher::{
+        GetLauncherIconUseCase, LaunchItemUseCase, RefreshLauncherIndexUseCase,
+        SearchLauncherUseCase,
+    },
+    llm::PolishExpressionUseCase,
+    measurement::MeasurementUseCase,
     monitor::{GetCurrentMonitorUseCase, GetMonitorsUseCase},
-    settings::{GetSettingsUseCase, SaveSettingsUseCase},
+    redaction::RedactScreenshotUseCase,
+    screenshot_library::{
+        CopyScreenshotUseCase, DeleteScreenshotUseCase, GetScreenshotThumbnailUseCase,
+        IndexScreenshotTextUseCase, ListScreenshotsUseCase, OpenScreenshotUseCase,
+        RecordScreenshotUseCase, UpdateScreenshotUseCase,
+    },
+    settings::{GetSettingsUseCase, SaveSettingsUseCase, SettingsSecrets},
 };
+use aumate_core_traits::clipboard::ClipboardPort;
+use aumate_core_traits::settings::SettingsStoragePort;
+use aumate_core_traits::text_recognition::TextRecognitionPort;
+use aumate_infrastructure::ClipboardWatcherService;
 use aumate_infrastructure::adapters::{
-    ClipboardAdapter, ElementScannerAdapter, FileSystemSettingsAdapter, GlobalShortcutAdapter,
-    HotkeyListenerAdapter, ImageProcessingAdapter, PageManagementAdapter, ScreenCaptureAdapter,
-    ScrollCaptureAdapter, UIAutomationAdapter, WindowListAdapter, WindowManagementAdapter,
+    BarcodeAdapter, CannyEdgeDetectionAdapter, ClipboardAdapter, ElementScannerAdapter,
+    EncryptedFileSecretStore, FileSystemAdapter, FileSystemLauncherIndexAdapter,
+    FileSystemSettingsAdapter, GlobalShortcutAdapter, HotkeyListenerAdapter,
+    ImageProcessingAdapter, JsonLaunchUsageAdapter, OpenAiCompatibleLlmAdapter,
+    PageManagementAdapter, ScreenCaptureAdapter, ScrollCaptureAdapter,
+    SqliteClipboardHistoryAdapter, SqliteConversationAdapter, SqliteScreenshotLibraryAdapter,
+    SystemLauncherAdapter, UIAutomationAdapter, WindowListAdapter, WindowManagementAdapter,
     WindowVibrancyAdapter,
 };
+use serde::Serialize;
 use std::sync::Arc;
+use tauri::Emitter;
+
+/// `clipboard-changed` 事件内容
+#[derive(Debug, Clone, Serialize)]
+struct ClipboardChangedEvent {
+    sequence: u64,
+    /// 内容类型名 (`text`、`image` 等) 或 MIME 类型
+    types: Vec<String>,
+}
 
 /// 设置应用程序
 ///
@@ -34,12 +75,14 @@ pub fn setup_application(app_handle: tauri::AppHandle) -> AppState {
     let screen_capture = Arc::new(ScreenCaptureAdapter::new());
     let image_processing = Arc::new(ImageProcessingAdapter::new());
     let scroll_capture = Arc::new(ScrollCaptureAdapter::new());
-    let window_management = Arc::new(WindowManagementAdapter::new());
+    let window_management =
+        Arc::new(WindowManagementAdapter::new().with_app_handle(app_handle.clone()));
     let window_list = Arc::new(WindowListAdapter::new());
     let ui_automation = Arc::new(UIAutomationAdapter::new());
     let hotkey_listener = Arc::new(HotkeyListenerAdapter::new());
     let page_management = Arc::new(PageManagementAdapter::new());
     let settings_storage = Arc::new(FileSystemSettingsAdapter::new());
+    let clipboard_history = Arc::new(open_clipboard_history());
 
     // 2. 创建 Use Cases
     log::info!("Creating use cases...");
@@ -50,15 +93,121 @@ pub fn setup_application(app_handle: tauri::AppHandle) -> AppState {
     let read_clipboard_image = Arc::new(ReadClipboardImageUseCase::new(clipboard.clone()));
     let write_clipboard_image = Arc::new(WriteClipboardImageUseCase::new(clipboard.clone()));
 
-    let capture_screen =
-        Arc::new(CaptureScreenUseCase::new(screen_capture.clone(), image_processing.clone()));
+    // Clipboard History Use Cases
+    let list_clipboard_history =
+        Arc::new(ListClipboardHistoryUseCase::new(clipboard_history.clone()));
+    let get_clipboard_history_content =
+        Arc::new(GetClipboardHistoryContentUseCase::new(clipboard_history.clone()));
+    let paste_clipboard_history =
+        Arc::new(PasteClipboardHistoryUseCase::new(clipboard_history.clone(), clipboard.clone()));
+    let pin_clipboard_history =
+        Arc::new(PinClipboardHistoryUseCase::new(clipboard_history.clone()));
+    let delete_clipboard_history =
+        Arc::new(DeleteClipboardHistoryUseCase::new(clipboard_history.clone()));
+
+    // Clipboard Watcher，读取设置后开始记录
+    let clipboard_watcher = Arc::new(
+        ClipboardWatcherService::new(clipboard.clone(), clipboard_history.clone())
+            .with_window_list(window_list.clone()),
+    );
+    {
+        let watcher = clipboard_watcher.clone();
+        let settings_storage = settings_storage.clone();
+        tauri::async_runtime::spawn(async move {
+            match settings_storage.load().await {
+                Ok(settings) => watcher.update_settings(settings.clipboard_history),
+                Err(e) => log::warn!("Failed to load clipboard history settings: {}", e),
+            }
+            watcher.run().await;
+        });
+    }
+    tauri::async_runtime::spawn(emit_clipboard_changes(clipboard.clone(), app_handle.clone()));
+
+    // OCR 模型较大，截图库索引和自动遮挡共用一个实例
+    #[cfg(feature = "ocr")]
+    let text_recognition: Option<Arc<dyn TextRecognitionPort>> =
+        Some(Arc::new(aumate_infrastructure::adapters::TrOcrTextRecognitionAdapter::new()));
+    #[cfg(not(feature = "ocr"))]
+    let text_recognition: Option<Arc<dyn TextRecognitionPort>> = None;
+
+    let render_annotations = Arc::new(RenderAnnotationsUseCase::new(image_processing.clone()));
+    let mut redact_screenshot = RedactScreenshotUseCase::new(image_processing.clone());
+    if let Some(text_recognition) = text_recognition.clone() {
+        redact_screenshot = redact_screenshot.with_text_recognition(text_recognition);
+    }
+    let redact_screenshot = Arc::new(redact_screenshot);
+
+    // 取色器
+    let color_picker = Arc::new(ColorPickerUseCase::new(screen_capture.clone()));
+
+    // 测量，间距来自截图边缘检测，元素边界来自 UI 自动化
+    let measurement = Arc::new(
+        MeasurementUseCase::new(screen_capture.clone(), Arc::new(CannyEdgeDetectionAdapter::new()))
+            .with_ui_automation(ui_automation.clone()),
+    );
+
+    // 条码识别与 QR 码生成
+    let barcode = Arc::new(BarcodeAdapter::new());
+    let scan_barcodes = Arc::new(ScanBarcodesUseCase::new(
+        barcode.clone(),
+        image_processing.clone(),
+        screen_capture.clone(),
+    ));
+    let generate_qr_code =
+        Arc::new(GenerateQrCodeUseCase::new(barcode, image_processing.clone(), clipboard.clone()));
+
+    // Screenshot Library，每次截图自动加入
+    let screenshot_library = Arc::new(open_screenshot_library());
+    let record_screenshot = Arc::new(RecordScreenshotUseCase::new(
+        screenshot_library.clone(),
+        image_processing.clone(),
+    ));
+    let list_screenshots = Arc::new(ListScreenshotsUseCase::new(screenshot_library.clone()));
+    let get_screenshot_thumbnail =
+        Arc::new(GetScreenshotThumbnailUseCase::new(screenshot_library.clone()));
+    let open_screenshot = Arc::new(OpenScreenshotUseCase::new(
+        screenshot_library.clone(),
+        Arc::new(SystemLauncherAdapter::new()),
+    ));
+    let copy_screenshot = Arc::new(CopyScreenshotUseCase::new(
+        screenshot_library.clone(),
+        image_processing.clone(),
+        clipboard.clone(),
+    ));
+    let update_screenshot = Arc::new(UpdateScreenshotUseCase::new(screenshot_library.clone()));
+    let delete_screenshot = Arc::new(DeleteScreenshotUseCase::new(screenshot_library.clone()));
+    let index_screenshot_text = text_recognition.map(|text_recognition| {
+        Arc::new(IndexScreenshotTextUseCase::new(
+            screenshot_library.clone(),
+            image_processing.clone(),
+            text_recognition,
+        ))
+    });
 
-    let capture_region =
-        Arc::new(CaptureRegionUseCase::new(screen_capture.clone(), image_processing.clone()));
+    let capture_screen = Arc::new(
+        CaptureScreenUseCase::new(screen_capture.clone(), image_processing.clone())
+            .with_library(record_screenshot.clone()),
+    );
+
+    let capture_region = Arc::new(
+        CaptureRegionUseCase::new(screen_capture.clone(), image_processing.clone())
+            .with_library(record_screenshot),
+    );
+
+    // 按设置中的目录、文件名模板和格式保存截图
+    let save_screenshot = Arc::new(
+        SaveScreenshotUseCase::new(
+            settings_storage.clone(),
+            image_processing.clone(),
+            Arc::new(FileSystemAdapter::new()),
+        )
+        .with_clipboard(clipboard.clone()),
+    );
 
     let scroll_screenshot = Arc::new(ScrollScreenshotUseCase::new(scroll_capture));
 
-    let window_management_use_case = Arc::new(WindowManagementUseCase::new(window_management));
+    let window_management_use_case =
+        Arc::new(WindowManagementUseCase::new(window_management, image_processing.clone()));
 
     // Window List Use Cases
     let get_window_elements = Arc::new(GetWindowElementsUseCase::new(window_list.clone()));
@@ -79,9 +228,66 @@ pub fn setup_application(app_handle: tauri::AppHandle) -> AppState {
     let get_monitors = Arc::new(GetMonitorsUseCase::new(screen_capture.clone()));
     let get_current_monitor = Arc::new(GetCurrentMonitorUseCase::new(screen_capture.clone()));
 
-    // Settings Use Cases
-    let get_settings = Arc::new(GetSettingsUseCase::new(settings_storage.clone()));
-    let save_settings = Arc::new(SaveSettingsUseCase::new(settings_storage.clone()));
+    // Settings Use Cases，API Key 保存在加密的密钥存储中
+    let settings_secrets =
+        Arc::new(SettingsSecrets::new(Arc::new(EncryptedFileSecretStore::open_default())));
+    let get_settings = Arc::new(
+        GetSettingsUseCase::new(settings_storage.clone()).with_secrets(settings_secrets.clone()),
+    );
+    let save_settings = Arc::new(
+        SaveSettingsUseCase::new(settings_storage.clone())
+            .with_hotkey_validator(Arc::new(|hotkey: &str| {
+                crate::shortcut_parser::parse_shortcut(hotkey).map(|_| ())
+            }))
+            .with_secrets(settings_secrets.clone()),
+    );
+
+    // AI Use Cases
+    let llm = Arc::new(OpenAiCompatibleLlmAdapter::new());
+    let polish_expression = Arc::new(PolishExpressionUseCase::new(
+        settings_storage.clone(),
+        settings_secrets.clone(),
+        llm.clone(),
+    ));
+
+    // AI Dialogue Conversations
+    let conversations = Arc::new(open_conversations());
+    let send_conversation_message = Arc::new(SendConversationMessageUseCase::new(
+        settings_storage.clone(),
+        settings_secrets.clone(),
+        llm,
+        conversations.clone(),
+    ));
+    let list_conversations = Arc::new(ListConversationsUseCase::new(conversations.clone()));
+    let get_conversation = Arc::new(GetConversationUseCase::new(conversations.clone()));
+    let rename_conversation = Arc::new(RenameConversationUseCase::new(conversations.clone()));
+    let delete_conversation = Arc::new(DeleteConversationUseCase::new(conversations.clone()));
+    let export_conversation = Arc::new(ExportConversationUseCase::new(conversations));
+
+    // Launcher，启动时在后台建立索引
+    let launcher_index = Arc::new(FileSystemLauncherIndexAdapter::new());
+    let launch_usage =
+        Arc::new(JsonLaunchUsageAdapter::new(JsonLaunchUsageAdapter::default_path()));
+    let refresh_launcher_index = Arc::new(RefreshLauncherIndexUseCase::new(
+        settings_storage.clone(),
+        launcher_index.clone(),
+    ));
+    let search_launcher =
+        Arc::new(SearchLauncherUseCase::new(launcher_index.clone(), launch_usage.clone()));
+    let launch_item = Arc::new(LaunchItemUseCase::new(
+        launcher_index.clone(),
+        launch_usage,
+        Arc::new(SystemLauncherAdapter::new()),
+    ));
+    let get_launcher_icon = Arc::new(GetLauncherIconUseCase::new(launcher_index.clone()));
+    {
+        let refresh_launcher_index = refresh_launcher_index.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = refresh_launcher_index.execute().await {
+                log::warn!("Failed to build launcher index: {}", e);
+            }
+        });
+    }
 
     // Global Shortcut Adapter and Use Cases
     let global_shortcut = Arc::new(GlobalShortcutAdapter::new(app_handle));
@@ -109,8 +315,32 @@ pub fn setup_application(app_handle: tauri::AppHandle) -> AppState {
         write_clipboard,
         read_clipboard_image,
         write_clipboard_image,
+        clipboard_history,
+        clipboard_watcher,
+        list_clipboard_history,
+        get_clipboard_history_content,
+        paste_clipboard_history,
+        pin_clipboard_history,
+        delete_clipboard_history,
         capture_screen,
         capture_region,
+        save_screenshot,
+        render_annotations,
+        redact_screenshot,
+        color_picker,
+        color_picker_events: Default::default(),
+        color_picker_guard: Default::default(),
+        measurement,
+        scan_barcodes,
+        generate_qr_code,
+        screenshot_library,
+        list_screenshots,
+        get_screenshot_thumbnail,
+        open_screenshot,
+        copy_screenshot,
+        update_screenshot,
+        delete_screenshot,
+        index_screenshot_text,
         scroll_screenshot,
         window_management: window_management_use_case,
         window_list,
@@ -130,6 +360,20 @@ pub fn setup_application(app_handle: tauri::AppHandle) -> AppState {
         settings_storage,
         get_settings,
         save_settings,
+        settings_secrets,
+        polish_expression,
+        send_conversation_message,
+        ai_requests: Default::default(),
+        list_conversations,
+        get_conversation,
+        rename_conversation,
+        delete_conversation,
+        export_conversation,
+        launcher_index,
+        refresh_launcher_index,
+        search_launcher,
+        launch_item,
+        get_launcher_icon,
         global_shortcut,
         register_global_shortcut,
         unregister_global_shortcut,
@@ -141,3 +385,50 @@ pub fn setup_application(app_handle: tauri::AppHandle) -> AppState {
         trigger_element_action_use_case,
     }
 }
+
+/// 剪贴板变化时向前端发送 `clipboard-changed` 事件
+async fn emit_clipboard_changes(clipboard: Arc<ClipboardAdapter>, app_handle: tauri::AppHandle) {
+    let mut changes = match clipboard.watch().await {
+        Ok(changes) => changes,
+        Err(e) => {
+            log::warn!("Failed to watch clipboard: {}", e);
+            return;
+        }
+    };
+    while let Some(change) = changes.next().await {
+        let event = ClipboardChangedEvent {
+            sequence: change.sequence,
+            types: change.types.iter().map(|ty| ty.to_string()).collect(),
+        };
+        let _ = app_handle.emit("clipboard-changed", &event);
+    }
+}
+
+/// 打开剪贴板历史数据库，失败时退回内存数据库 (历史不会保存到磁盘)
+fn open_clipboard_history() -> SqliteClipboardHistoryAdapter {
+    let path = SqliteClipboardHistoryAdapter::default_path();
+    SqliteClipboardHistoryAdapter::open(&path).unwrap_or_else(|e| {
+        log::error!("Failed to open clipboard history at {:?}: {}", path, e);
+        SqliteClipboardHistoryAdapter::in_memory().expect("Failed to create in-memory database")
+    })
+}
+
+/// 打开对话数据库，失败时退回内存数据库 (对话不会保存到磁盘)
+fn open_conversations() -> SqliteConversationAdapter {
+    let path = SqliteConversationAdapter::default_path();
+    SqliteConversationAdapter::open(&path).unwrap_or_else(|e| {
+        log::error!("Failed to open conversations at {:?}: {}", path, e);
+        SqliteConversationAdapter::in_memory().expect("Failed to create in-memory database")
+    })
+}
+
+/// 打开截图库数据库，失败时退回内存数据库 (截图文件仍写入磁盘，但索引不会保存)
+fn open_screenshot_library() -> SqliteScreenshotLibraryAdapter {
+    let path = SqliteScreenshotLibraryAdapter::default_path();
+    let images_dir = SqliteScreenshotLibraryAdapter::default_images_dir();
+    SqliteScreenshotLibraryAdapter::open(&path, images_dir.clone()).unwrap_or_else(|e| {
+        log::error!("Failed to open screenshot library at {:?}: {}", path, e);
+        SqliteScreenshotLibraryAdapter::in_memory(images_dir)
+            .expect("Failed to create in-memory database")
+    })
+}
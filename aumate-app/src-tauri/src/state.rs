@@ -2,22 +2,52 @@
 use aumate_application::use_cases::{
     CaptureRegionUseCase, CaptureScreenUseCase, CheckGlobalShortcutAvailabilityUseCase,
     ClickElementUseCase, CloseDesktopWindowUseCase, FocusElementUseCase, GetWindowElementsUseCase,
-    RegisterGlobalShortcutUseCase, ScanElementsUseCase, ScrollScreenshotUseCase,
-    SetWindowVibrancyUseCase, SwitchToWindowUseCase, UnregisterGlobalShortcutUseCase,
-    WindowManagementUseCase,
+    RegisterGlobalShortcutUseCase, SaveScreenshotUseCase, ScanElementsUseCase,
+    ScrollScreenshotUseCase, SetWindowVibrancyUseCase, SwitchToWindowUseCase,
+    UnregisterGlobalShortcutUseCase, WindowManagementUseCase,
+    annotation::RenderAnnotationsUseCase,
+    barcode::{GenerateQrCodeUseCase, ScanBarcodesUseCase},
     clipboard::{
         ReadClipboardImageUseCase, ReadClipboardUseCase, WriteClipboardImageUseCase,
         WriteClipboardUseCase,
     },
+    clipboard_history::{
+        DeleteClipboardHistoryUseCase, GetClipboardHistoryContentUseCase,
+        ListClipboardHistoryUseCase, PasteClipboardHistoryUseCase, PinClipboardHistoryUseCase,
+    },
+    color_picker::ColorPickerUseCase,
+    conversation::{
+        DeleteConversationUseCase, ExportConversationUseCase, GetConversationUseCase,
+        ListConversationsUseCase, RenameConversationUseCase, SendConversationMessageUseCase,
+    },
+    launcher::{
+        GetLauncherIconUseCase, LaunchItemUseCase, RefreshLauncherIndexUseCase,
+        SearchLauncherUseCase,
+    },
+    llm::PolishExpressionUseCase,
+    measurement::MeasurementUseCase,
     monitor::{GetCurrentMonitorUseCase, GetMonitorsUseCase},
-    settings::{GetSettingsUseCase, SaveSettingsUseCase},
+    redaction::RedactScreenshotUseCase,
+    screenshot_library::{
+        CopyScreenshotUseCase, DeleteScreenshotUseCase, GetScreenshotThumbnailUseCase,
+        IndexScreenshotTextUseCase, ListScreenshotsUseCase, OpenScreenshotUseCase,
+        UpdateScreenshotUseCase,
+    },
+    settings::{GetSettingsUseCase, SaveSettingsUseCase, SettingsSecrets},
 };
 use aumate_infrastructure::adapters::{
-    ClipboardAdapter, ElementScannerAdapter, FileSystemSettingsAdapter, GlobalShortcutAdapter,
-    HotkeyListenerAdapter, PageManagementAdapter, ScreenCaptureAdapter, UIAutomationAdapter,
-    WindowListAdapter, WindowVibrancyAdapter,
+    ClipboardAdapter, ElementScannerAdapter, FileSystemLauncherIndexAdapter,
+    FileSystemSettingsAdapter, GlobalShortcutAdapter, HotkeyListenerAdapter,
+    JsonLaunchUsageAdapter, OpenAiCompatibleLlmAdapter, PageManagementAdapter,
+    ScreenCaptureAdapter, SqliteClipboardHistoryAdapter, SqliteConversationAdapter,
+    SqliteScreenshotLibraryAdapter, SystemLauncherAdapter, UIAutomationAdapter, WindowListAdapter,
+    WindowVibrancyAdapter,
 };
-use std::sync::Arc;
+use aumate_infrastructure::{ClipboardWatcherService, DeviceEventHandlerService};
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::task::AbortHandle;
 
 /// 应用状态
 ///
@@ -30,9 +60,52 @@ pub struct AppState {
     pub read_clipboard_image: Arc<ReadClipboardImageUseCase<ClipboardAdapter>>,
     pub write_clipboard_image: Arc<WriteClipboardImageUseCase<ClipboardAdapter>>,
 
+    // Clipboard History
+    pub clipboard_history: Arc<SqliteClipboardHistoryAdapter>,
+    pub clipboard_watcher: Arc<ClipboardWatcherService>,
+    pub list_clipboard_history: Arc<ListClipboardHistoryUseCase<SqliteClipboardHistoryAdapter>>,
+    pub get_clipboard_history_content:
+        Arc<GetClipboardHistoryContentUseCase<SqliteClipboardHistoryAdapter>>,
+    pub paste_clipboard_history:
+        Arc<PasteClipboardHistoryUseCase<SqliteClipboardHistoryAdapter, ClipboardAdapter>>,
+    pub pin_clipboard_history: Arc<PinClipboardHistoryUseCase<SqliteClipboardHistoryAdapter>>,
+    pub delete_clipboard_history: Arc<DeleteClipboardHistoryUseCase<SqliteClipboardHistoryAdapter>>,
+
     // Screenshot Use Cases
     pub capture_screen: Arc<CaptureScreenUseCase>,
     pub capture_region: Arc<CaptureRegionUseCase>,
+    pub save_screenshot: Arc<SaveScreenshotUseCase<FileSystemSettingsAdapter>>,
+
+    // Annotation
+    pub render_annotations: Arc<RenderAnnotationsUseCase>,
+
+    // Redaction
+    pub redact_screenshot: Arc<RedactScreenshotUseCase>,
+
+    // Color Picker
+    pub color_picker: Arc<ColorPickerUseCase>,
+    /// 取色时监听光标移动
+    pub color_picker_events: Mutex<DeviceEventHandlerService>,
+    /// 光标移动回调的 guard，drop 后停止发送取样事件
+    pub color_picker_guard: Mutex<Option<Box<dyn Any + Send>>>,
+
+    // Measurement
+    pub measurement: Arc<MeasurementUseCase>,
+
+    // Barcode
+    pub scan_barcodes: Arc<ScanBarcodesUseCase>,
+    pub generate_qr_code: Arc<GenerateQrCodeUseCase>,
+
+    // Screenshot Library
+    pub screenshot_library: Arc<SqliteScreenshotLibraryAdapter>,
+    pub list_screenshots: Arc<ListScreenshotsUseCase>,
+    pub get_screenshot_thumbnail: Arc<GetScreenshotThumbnailUseCase>,
+    pub open_screenshot: Arc<OpenScreenshotUseCase>,
+    pub copy_screenshot: Arc<CopyScreenshotUseCase>,
+    pub update_screenshot: Arc<UpdateScreenshotUseCase>,
+    pub delete_screenshot: Arc<DeleteScreenshotUseCase>,
+    /// 未启用 `ocr` feature 时为 None
+    pub index_screenshot_text: Option<Arc<IndexScreenshotTextUseCase>>,
 
     // Scroll Screenshot Use Case
     pub scroll_screenshot: Arc<ScrollScreenshotUseCase>,
@@ -72,6 +145,42 @@ pub struct AppState {
     pub settings_storage: Arc<FileSystemSettingsAdapter>,
     pub get_settings: Arc<GetSettingsUseCase<FileSystemSettingsAdapter>>,
     pub save_settings: Arc<SaveSettingsUseCase<FileSystemSettingsAdapter>>,
+    pub settings_secrets: Arc<SettingsSecrets>,
+
+    // AI
+    pub polish_expression:
+        Arc<PolishExpressionUseCase<FileSystemSettingsAdapter, OpenAiCompatibleLlmAdapter>>,
+    pub send_conversation_message: Arc<
+        SendConversationMessageUseCase<
+            FileSystemSettingsAdapter,
+            OpenAiCompatibleLlmAdapter,
+            SqliteConversationAdapter,
+        >,
+    >,
+    /// 进行中的 AI 请求，按前端生成的 request_id 索引
+    pub ai_requests: Mutex<HashMap<String, AbortHandle>>,
+
+    // AI Dialogue Conversations
+    pub list_conversations: Arc<ListConversationsUseCase<SqliteConversationAdapter>>,
+    pub get_conversation: Arc<GetConversationUseCase<SqliteConversationAdapter>>,
+    pub rename_conversation: Arc<RenameConversationUseCase<SqliteConversationAdapter>>,
+    pub delete_conversation: Arc<DeleteConversationUseCase<SqliteConversationAdapter>>,
+    pub export_conversation: Arc<ExportConversationUseCase<SqliteConversationAdapter>>,
+
+    // Launcher
+    pub launcher_index: Arc<FileSystemLauncherIndexAdapter>,
+    pub refresh_launcher_index:
+        Arc<RefreshLauncherIndexUseCase<FileSystemSettingsAdapter, FileSystemLauncherIndexAdapter>>,
+    pub search_launcher:
+        Arc<SearchLauncherUseCase<FileSystemLauncherIndexAdapter, JsonLaunchUsageAdapter>>,
+    pub launch_item: Arc<
+        LaunchItemUseCase<
+            FileSystemLauncherIndexAdapter,
+            JsonLaunchUsageAdapter,
+            SystemLauncherAdapter,
+        >,
+    >,
+    pub get_launcher_icon: Arc<GetLauncherIconUseCase<FileSystemLauncherIndexAdapter>>,
 
     // Global Shortcut
     pub global_shortcut: Arc<GlobalShortcutAdapter>,
@@ -86,3 +195,17 @@ pub struct AppState {
     pub focus_element_use_case: Arc<FocusElementUseCase>,
     pub trigger_element_action_use_case: Arc<aumate_application::TriggerElementActionUseCase>,
 }
+
+impl AppState {
+    pub fn ai_requests(&self) -> MutexGuard<'_, HashMap<String, AbortHandle>> {
+        self.ai_requests.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn color_picker_events(&self) -> MutexGuard<'_, DeviceEventHandlerService> {
+        self.color_picker_events.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn color_picker_guard(&self) -> MutexGuard<'_, Option<Box<dyn Any + Send>>> {
+        self.color_picker_guard.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -66,7 +66,7 @@ export function ChatPanel({
   const displayMessages = [...messages];
   if (isStreaming && streamingContent) {
     displayMessages.push({
-      id: "streaming",
+      id: -1,
       role: "assistant",
       content: streamingContent,
       timestamp: Date.now(),
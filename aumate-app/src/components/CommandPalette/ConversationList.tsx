@@ -1,13 +1,25 @@
-import { MessageSquare, Plus, Trash2 } from "lucide-react";
+import {
+  Download,
+  MessageSquare,
+  Pencil,
+  Plus,
+  Search,
+  Trash2,
+} from "lucide-react";
+import { useEffect, useState } from "react";
 import { cn } from "@/lib/utils";
 import type { Conversation } from "@/types/dialogue";
 
 interface ConversationListProps {
   conversations: Conversation[];
-  activeConversationId: string | null;
-  onSelectConversation: (id: string) => void;
+  activeConversationId: number | null;
+  searchQuery: string;
+  onSearch: (query: string) => void;
+  onSelectConversation: (id: number) => void;
   onNewConversation: () => void;
-  onDeleteConversation: (id: string) => void;
+  onRenameConversation: (id: number, title: string) => void;
+  onExportConversation: (id: number) => void;
+  onDeleteConversation: (id: number) => void;
 }
 
 // Group conversations by date
@@ -57,11 +69,37 @@ function groupByDate(
 export function ConversationList({
   conversations,
   activeConversationId,
+  searchQuery,
+  onSearch,
   onSelectConversation,
   onNewConversation,
+  onRenameConversation,
+  onExportConversation,
   onDeleteConversation,
 }: ConversationListProps) {
   const grouped = groupByDate(conversations);
+  const [search, setSearch] = useState(searchQuery);
+  const [editingId, setEditingId] = useState<number | null>(null);
+  const [editingTitle, setEditingTitle] = useState("");
+
+  // Debounce search input
+  useEffect(() => {
+    if (search === searchQuery) return;
+    const timer = setTimeout(() => onSearch(search), 200);
+    return () => clearTimeout(timer);
+  }, [search, searchQuery, onSearch]);
+
+  const startRename = (conv: Conversation) => {
+    setEditingId(conv.id);
+    setEditingTitle(conv.title);
+  };
+
+  const commitRename = () => {
+    if (editingId !== null && editingTitle.trim()) {
+      onRenameConversation(editingId, editingTitle.trim());
+    }
+    setEditingId(null);
+  };
 
   return (
     <div className="w-52 border-r border-white/10 flex flex-col h-full">
@@ -75,6 +113,16 @@ export function ConversationList({
           <Plus className="w-4 h-4" />
           <span>New Chat</span>
         </button>
+        <div className="mt-2 flex items-center gap-2 px-3 py-1.5 bg-white/5 rounded-lg">
+          <Search className="w-3.5 h-3.5 text-muted-foreground shrink-0" />
+          <input
+            type="text"
+            value={search}
+            onChange={(e) => setSearch(e.target.value)}
+            placeholder="Search chats"
+            className="w-full bg-transparent text-xs text-foreground placeholder:text-muted-foreground outline-none"
+          />
+        </div>
       </div>
 
       {/* Conversation List */}
@@ -82,7 +130,9 @@ export function ConversationList({
         {conversations.length === 0 ? (
           <div className="px-3 py-8 text-center text-muted-foreground text-xs">
             <MessageSquare className="w-6 h-6 mx-auto mb-2 opacity-50" />
-            <p>No conversations yet</p>
+            <p>
+              {searchQuery ? "No matching conversations" : "No conversations yet"}
+            </p>
           </div>
         ) : (
           Array.from(grouped.entries()).map(([label, convs]) => (
@@ -100,36 +150,80 @@ export function ConversationList({
                       : "hover:bg-white/5",
                   )}
                 >
-                  <button
-                    type="button"
-                    onClick={() => onSelectConversation(conv.id)}
-                    className="w-full text-left px-3 py-2"
-                  >
-                    <div
-                      className={cn(
-                        "text-sm truncate",
-                        activeConversationId === conv.id
-                          ? "text-accent-foreground"
-                          : "text-foreground",
-                      )}
+                  {editingId === conv.id ? (
+                    <input
+                      type="text"
+                      value={editingTitle}
+                      onChange={(e) => setEditingTitle(e.target.value)}
+                      onBlur={commitRename}
+                      onKeyDown={(e) => {
+                        if (e.key === "Enter") commitRename();
+                        if (e.key === "Escape") setEditingId(null);
+                      }}
+                      // biome-ignore lint/a11y/noAutofocus: focus the field being renamed
+                      autoFocus
+                      className="w-full px-3 py-2 bg-transparent text-sm text-foreground outline-none"
+                    />
+                  ) : (
+                    <button
+                      type="button"
+                      onClick={() => onSelectConversation(conv.id)}
+                      onDoubleClick={() => startRename(conv)}
+                      className="w-full text-left px-3 py-2"
                     >
-                      {conv.title}
-                    </div>
-                    <div className="text-xs text-muted-foreground truncate">
-                      {conv.messages.length} messages
+                      <div
+                        className={cn(
+                          "text-sm truncate pr-14",
+                          activeConversationId === conv.id
+                            ? "text-accent-foreground"
+                            : "text-foreground",
+                        )}
+                      >
+                        {conv.title}
+                      </div>
+                      <div className="text-xs text-muted-foreground truncate">
+                        {conv.messageCount} messages
+                        {conv.model ? ` · ${conv.model}` : ""}
+                      </div>
+                    </button>
+                  )}
+                  {editingId !== conv.id && (
+                    <div className="absolute right-2 top-1/2 -translate-y-1/2 flex opacity-0 group-hover:opacity-100 transition-opacity">
+                      <button
+                        type="button"
+                        onClick={(e) => {
+                          e.stopPropagation();
+                          startRename(conv);
+                        }}
+                        className="p-1 text-muted-foreground hover:text-foreground"
+                        title="Rename conversation"
+                      >
+                        <Pencil className="w-3.5 h-3.5" />
+                      </button>
+                      <button
+                        type="button"
+                        onClick={(e) => {
+                          e.stopPropagation();
+                          onExportConversation(conv.id);
+                        }}
+                        className="p-1 text-muted-foreground hover:text-foreground"
+                        title="Export as Markdown"
+                      >
+                        <Download className="w-3.5 h-3.5" />
+                      </button>
+                      <button
+                        type="button"
+                        onClick={(e) => {
+                          e.stopPropagation();
+                          onDeleteConversation(conv.id);
+                        }}
+                        className="p-1 text-muted-foreground hover:text-red-400"
+                        title="Delete conversation"
+                      >
+                        <Trash2 className="w-3.5 h-3.5" />
+                      </button>
                     </div>
-                  </button>
-                  <button
-                    type="button"
-                    onClick={(e) => {
-                      e.stopPropagation();
-                      onDeleteConversation(conv.id);
-                    }}
-                    className="absolute right-2 top-1/2 -translate-y-1/2 p-1 text-muted-foreground hover:text-red-400 opacity-0 group-hover:opacity-100 transition-opacity"
-                    title="Delete conversation"
-                  >
-                    <Trash2 className="w-3.5 h-3.5" />
-                  </button>
+                  )}
                 </div>
               ))}
             </div>
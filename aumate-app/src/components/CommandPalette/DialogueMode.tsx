@@ -1,7 +1,6 @@
 import { useCallback, useEffect, useRef, useState } from "react";
-import { type ChatMessage, sendDialogueMessage } from "@/lib/dialogue";
+import { sendDialogueMessage } from "@/lib/dialogue";
 import { useDialogueStore } from "@/stores/dialogueStore";
-import { useSettingsStore } from "@/stores/settingsStore";
 import { ChatPanel } from "./ChatPanel";
 import { ConversationList } from "./ConversationList";
 
@@ -9,128 +8,111 @@ export function DialogueMode() {
   const {
     conversations,
     activeConversationId,
+    messages,
+    searchQuery,
     isLoading,
-    createConversation,
-    setActiveConversation,
-    addMessage,
-    updateLastMessage,
-    deleteConversation,
     loadConversations,
-    getActiveConversation,
+    selectConversation,
+    receiveConversation,
+    addLocalMessage,
+    renameConversation,
+    deleteConversation,
+    exportConversation,
   } = useDialogueStore();
 
-  const { settings } = useSettingsStore();
-  const { ai_dialogue } = settings;
-
   const [isStreaming, setIsStreaming] = useState(false);
   const [streamingContent, setStreamingContent] = useState("");
   const abortControllerRef = useRef<AbortController | null>(null);
 
-  // Load conversations on mount
+  // Load conversations on mount and resume the most recent one
   useEffect(() => {
-    loadConversations();
-  }, [loadConversations]);
-
-  // Get active conversation
-  const activeConversation = getActiveConversation();
+    loadConversations().then(() => {
+      const { conversations, activeConversationId } =
+        useDialogueStore.getState();
+      selectConversation(activeConversationId ?? conversations[0]?.id ?? null);
+    });
+  }, [loadConversations, selectConversation]);
 
   // Handle sending a message
   const handleSendMessage = useCallback(
     async (content: string) => {
-      // Create new conversation if none exists
-      let conversationId = activeConversationId;
-      if (!conversationId) {
-        conversationId = createConversation();
-      }
-
-      // Add user message
-      addMessage(conversationId, {
-        role: "user",
-        content,
-        timestamp: Date.now(),
-      });
-
-      // Add empty assistant message (will be updated via streaming)
-      addMessage(conversationId, {
-        role: "assistant",
-        content: "",
-        timestamp: Date.now(),
-      });
-
-      // Prepare messages for API (limit to max_history_messages)
-      const conversation = useDialogueStore.getState().getActiveConversation();
-      if (!conversation) return;
-
-      const recentMessages = conversation.messages
-        .slice(-(ai_dialogue.max_history_messages * 2)) // Keep pairs of user/assistant
-        .filter((m) => m.content) // Filter out empty messages
-        .map((m) => ({
-          role: m.role as "user" | "assistant",
-          content: m.content,
-        }));
-
-      // Add the new user message if not included
-      if (recentMessages[recentMessages.length - 1]?.content !== content) {
-        recentMessages.push({ role: "user", content });
-      }
+      addLocalMessage({ role: "user", content, timestamp: Date.now() });
 
       // Start streaming
       setIsStreaming(true);
       setStreamingContent("");
-      abortControllerRef.current = new AbortController();
+      const controller = new AbortController();
+      abortControllerRef.current = controller;
 
       const result = await sendDialogueMessage({
-        apiUrl: ai_dialogue.api_url,
-        apiKey: ai_dialogue.api_key,
-        model: ai_dialogue.model,
-        systemPrompt: ai_dialogue.system_prompt,
-        messages: recentMessages as ChatMessage[],
-        signal: abortControllerRef.current.signal,
+        conversationId: activeConversationId,
+        content,
+        signal: controller.signal,
+        onConversation: receiveConversation,
         onChunk: (chunk) => {
           setStreamingContent((prev) => prev + chunk);
         },
       });
 
       setIsStreaming(false);
-      abortControllerRef.current = null;
+      setStreamingContent("");
+      if (abortControllerRef.current === controller) {
+        abortControllerRef.current = null;
+      }
 
-      // Update the last assistant message with the full content
-      if (result.content) {
-        updateLastMessage(conversationId, result.content);
-        setStreamingContent("");
-      } else if (result.error) {
-        updateLastMessage(conversationId, `Error: ${result.error}`);
-        setStreamingContent("");
+      // The reply is saved on the Rust side; reload to pick up ids and metadata
+      const conversationId = useDialogueStore.getState().activeConversationId;
+      if (conversationId !== null) {
+        await selectConversation(conversationId);
+      }
+      loadConversations();
+
+      if (result.error && !controller.signal.aborted) {
+        addLocalMessage({
+          role: "assistant",
+          content: `Error: ${result.error}`,
+          timestamp: Date.now(),
+        });
       }
     },
     [
       activeConversationId,
-      createConversation,
-      addMessage,
-      updateLastMessage,
-      ai_dialogue,
+      addLocalMessage,
+      receiveConversation,
+      selectConversation,
+      loadConversations,
     ],
   );
 
-  // Handle stopping the stream
+  // Handle stopping the stream; unfinished replies are not saved
   const handleStopStreaming = useCallback(() => {
-    if (abortControllerRef.current) {
-      abortControllerRef.current.abort();
-      abortControllerRef.current = null;
-    }
-    setIsStreaming(false);
+    abortControllerRef.current?.abort();
+  }, []);
 
-    // Keep whatever content we have so far
-    if (streamingContent && activeConversationId) {
-      updateLastMessage(activeConversationId, streamingContent);
-    }
-    setStreamingContent("");
-  }, [activeConversationId, streamingContent, updateLastMessage]);
-
-  // Handle new conversation
+  // Handle new conversation; it is created when the first message is sent
   const handleNewConversation = useCallback(() => {
-    createConversation();
-  }, [createConversation]);
+    selectConversation(null);
+  }, [selectConversation]);
+
+  // Handle exporting a conversation to a Markdown file
+  const handleExportConversation = useCallback(
+    async (id: number) => {
+      const title = conversations.find((c) => c.id === id)?.title ?? "chat";
+      const { save } = await import("@tauri-apps/plugin-dialog");
+      const path = await save({
+        defaultPath: `${title.replace(/[\\/:*?"<>|]/g, "_")}.md`,
+        filters: [{ name: "Markdown", extensions: ["md"] }],
+      });
+      if (!path) return;
+
+      try {
+        await exportConversation(id, path);
+      } catch (error) {
+        console.error("Failed to export conversation:", error);
+      }
+    },
+    [conversations, exportConversation],
+  );
 
   if (isLoading) {
     return (
@@ -145,12 +127,16 @@ export function DialogueMode() {
       <ConversationList
         conversations={conversations}
         activeConversationId={activeConversationId}
-        onSelectConversation={setActiveConversation}
+        searchQuery={searchQuery}
+        onSearch={loadConversations}
+        onSelectConversation={selectConversation}
         onNewConversation={handleNewConversation}
+        onRenameConversation={renameConversation}
+        onExportConversation={handleExportConversation}
         onDeleteConversation={deleteConversation}
       />
       <ChatPanel
-        messages={activeConversation?.messages ?? []}
+        messages={messages}
         isStreaming={isStreaming}
         streamingContent={streamingContent}
         onSendMessage={handleSendMessage}
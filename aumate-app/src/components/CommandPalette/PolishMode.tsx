@@ -2,7 +2,6 @@ import { Copy, Sparkles, Square, X } from "lucide-react";
 import { forwardRef, useCallback, useEffect, useRef, useState } from "react";
 import Markdown from "react-markdown";
 import { polishExpression } from "@/lib/openai";
-import { useSettingsStore } from "@/stores/settingsStore";
 
 interface PolishModeProps {
   query: string;
@@ -16,7 +15,6 @@ export const PolishMode = forwardRef<HTMLDivElement, PolishModeProps>(
     const [isPolishing, setIsPolishing] = useState(false);
     const abortControllerRef = useRef<AbortController | null>(null);
     const polishScrollRef = useRef<HTMLDivElement>(null);
-    const { settings } = useSettingsStore();
 
     // Cancel polishing request
     const cancelPolishing = useCallback(() => {
@@ -31,8 +29,6 @@ export const PolishMode = forwardRef<HTMLDivElement, PolishModeProps>(
     const doPolish = useCallback(async () => {
       if (!query.trim() || isPolishing) return;
 
-      const { expression_polishing } = settings;
-
       setIsPolishing(true);
       setPolishResult("");
       setPolishError("");
@@ -40,10 +36,6 @@ export const PolishMode = forwardRef<HTMLDivElement, PolishModeProps>(
       abortControllerRef.current = new AbortController();
 
       const result = await polishExpression({
-        apiUrl: expression_polishing.api_url,
-        apiKey: expression_polishing.api_key,
-        model: expression_polishing.model,
-        systemPrompt: expression_polishing.system_prompt,
         userInput: query,
         signal: abortControllerRef.current.signal,
         onChunk: (chunk) => {
@@ -57,7 +49,7 @@ export const PolishMode = forwardRef<HTMLDivElement, PolishModeProps>(
       if (result.error) {
         setPolishError(result.error);
       }
-    }, [query, settings, isPolishing]);
+    }, [query, isPolishing]);
 
     // Clear polish results
     const clearPolishResults = useCallback(() => {
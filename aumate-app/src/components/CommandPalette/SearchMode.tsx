@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
 import {
+  AppWindow,
   Camera,
   Command,
   FileText,
@@ -7,7 +8,7 @@ import {
   Settings as SettingsIcon,
   Terminal,
 } from "lucide-react";
-import { useCallback, useEffect, useRef, useState } from "react";
+import { useCallback, useEffect, useMemo, useRef, useState } from "react";
 import { cn } from "@/lib/utils";
 
 // Command item interface
@@ -92,6 +93,42 @@ export const commands: CommandItem[] = [
   },
 ];
 
+// Ranked application/file result returned by `search_launcher`
+interface LaunchResult {
+  id: string;
+  kind: "application" | "file" | "folder";
+  name: string;
+  description: string | null;
+  path: string;
+  has_icon: boolean;
+  score: number;
+}
+
+const LAUNCHER_SEARCH_DEBOUNCE_MS = 80;
+const LAUNCHER_RESULT_LIMIT = 20;
+
+const launchItem = async (id: string) => {
+  try {
+    await invoke("launch_launcher_item", { id });
+  } catch (error) {
+    console.error("Failed to launch item:", error);
+  }
+};
+
+function launchResultIcon(result: LaunchResult, iconUrl?: string) {
+  if (iconUrl) {
+    return <img src={iconUrl} alt="" className="w-4 h-4 object-contain" />;
+  }
+  switch (result.kind) {
+    case "application":
+      return <AppWindow className="w-4 h-4" />;
+    case "folder":
+      return <Folder className="w-4 h-4" />;
+    default:
+      return <FileText className="w-4 h-4" />;
+  }
+}
+
 interface SearchModeProps {
   query: string;
   onHide: () => void;
@@ -100,14 +137,65 @@ interface SearchModeProps {
 
 export function SearchMode({ query, onHide, isActive }: SearchModeProps) {
   const [selectedIndex, setSelectedIndex] = useState(0);
+  const [launchResults, setLaunchResults] = useState<LaunchResult[]>([]);
+  const [icons, setIcons] = useState<Record<string, string | null>>({});
+  const [indexVersion, setIndexVersion] = useState(0);
   const listRef = useRef<HTMLDivElement>(null);
 
-  // Filter commands based on search query
-  const filteredCommands = commands.filter(
-    (cmd) =>
-      cmd.title.toLowerCase().includes(query.toLowerCase()) ||
-      cmd.description?.toLowerCase().includes(query.toLowerCase()),
-  );
+  // Pick up newly installed applications whenever the palette is shown
+  useEffect(() => {
+    if (!isActive) return;
+    invoke("refresh_launcher_index")
+      .then(() => setIndexVersion((version) => version + 1))
+      .catch((error) =>
+        console.error("Failed to refresh launcher index:", error),
+      );
+  }, [isActive]);
+
+  // Search applications and files in the backend index
+  // biome-ignore lint/correctness/useExhaustiveDependencies: re-run the search after the index is refreshed
+  useEffect(() => {
+    let cancelled = false;
+    const timer = setTimeout(async () => {
+      try {
+        const response = await invoke<{ results: LaunchResult[] }>(
+          "search_launcher",
+          { request: { query, limit: LAUNCHER_RESULT_LIMIT } },
+        );
+        if (!cancelled) setLaunchResults(response.results);
+      } catch (error) {
+        console.error("Failed to search launcher:", error);
+      }
+    }, LAUNCHER_SEARCH_DEBOUNCE_MS);
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [query, indexVersion]);
+
+  // Lazily load icons for visible results
+  useEffect(() => {
+    for (const result of launchResults) {
+      if (!result.has_icon || result.id in icons) continue;
+      setIcons((prev) => ({ ...prev, [result.id]: null }));
+      invoke<string | null>("get_launcher_icon", { id: result.id })
+        .then((url) => setIcons((prev) => ({ ...prev, [result.id]: url })))
+        .catch((error) => console.error("Failed to load icon:", error));
+    }
+  }, [launchResults, icons]);
+
+  // Filter commands based on search query, then append launcher results
+  const filteredCommands = useMemo(() => {
+    const builtin = getFilteredCommands(query);
+    const launchable: CommandItem[] = launchResults.map((result) => ({
+      id: result.id,
+      title: result.name,
+      description: result.description ?? result.path,
+      icon: launchResultIcon(result, icons[result.id] ?? undefined),
+      action: () => launchItem(result.id),
+    }));
+    return [...builtin, ...launchable];
+  }, [query, launchResults, icons]);
 
   // Execute command
   const executeCommand = useCallback(
@@ -184,7 +272,7 @@ export function SearchMode({ query, onHide, isActive }: SearchModeProps) {
       <div ref={listRef} className="flex-1 overflow-y-auto command-list py-2">
         {filteredCommands.length === 0 ? (
           <div className="px-4 py-8 text-center text-muted-foreground">
-            No commands found
+            No results found
           </div>
         ) : (
           filteredCommands.map((command, index) => (
@@ -234,7 +322,7 @@ export function SearchMode({ query, onHide, isActive }: SearchModeProps) {
             <span>Execute</span>
           </span>
         </div>
-        <span>{filteredCommands.length} commands</span>
+        <span>{filteredCommands.length} results</span>
       </div>
     </>
   );
@@ -0,0 +1,220 @@
+/**
+ * ColorPicker - 取色器
+ * 跟随光标显示放大镜网格和 HEX/RGB/HSL/OKLCH，点击取色并复制 HEX
+ *
+ * 取样在后端完成：开始时每台显示器截图一次，光标移动时通过
+ * `color-picker-sample` 事件推送放大镜网格
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type React from "react";
+import {
+  forwardRef,
+  useCallback,
+  useEffect,
+  useImperativeHandle,
+  useRef,
+  useState,
+} from "react";
+import { log } from "@/utils/logger";
+import { zIndexs } from "./extra";
+import type { ColorPickerActionType, MousePosition } from "./types";
+
+/** 放大镜半径，网格为 (2 * 半径 + 1) 个像素见方 */
+const MAGNIFIER_RADIUS = 7;
+/** 每个像素在放大镜中的尺寸 (CSS 像素) */
+const MAGNIFIER_CELL = 8;
+/** 放大镜与光标的距离 */
+const MAGNIFIER_OFFSET = 20;
+
+interface ColorFormats {
+  hex: string;
+  rgb: string;
+  hsl: string;
+  oklch: string;
+}
+
+interface ColorSample {
+  position: { x: number; y: number };
+  color: { r: number; g: number; b: number; a: number };
+  formats: ColorFormats;
+  magnifier: { radius: number; size: number; pixels: number[] };
+}
+
+interface PickedColor {
+  formats: ColorFormats;
+}
+
+export const ColorPicker = forwardRef<
+  ColorPickerActionType | undefined,
+  object
+>((_props, ref) => {
+  const [enabled, setEnabled] = useState(false);
+  const [sample, setSample] = useState<ColorSample>();
+  const [cursor, setCursor] = useState<{ x: number; y: number }>();
+  const [history, setHistory] = useState<PickedColor[]>([]);
+  const canvasRef = useRef<HTMLCanvasElement>(null);
+  const currentColorRef = useRef<string | undefined>(undefined);
+
+  // 开启时启动后端取色会话并订阅取样事件
+  useEffect(() => {
+    if (!enabled) return;
+
+    let disposed = false;
+    const unlisten = listen<ColorSample>("color-picker-sample", (event) => {
+      if (disposed) return;
+      currentColorRef.current = event.payload.formats.hex;
+      setSample(event.payload);
+    });
+    invoke("start_color_picker", { radius: MAGNIFIER_RADIUS }).catch(
+      (error) => {
+        log.error("[ColorPicker] Failed to start color picker:", error);
+        setEnabled(false);
+      },
+    );
+    invoke<PickedColor[]>("get_color_history")
+      .then(setHistory)
+      .catch(() => {});
+
+    return () => {
+      disposed = true;
+      unlisten.then((fn) => fn());
+      invoke("stop_color_picker").catch(() => {});
+      setSample(undefined);
+      currentColorRef.current = undefined;
+    };
+  }, [enabled]);
+
+  // 绘制放大镜网格
+  useEffect(() => {
+    const canvas = canvasRef.current;
+    const context = canvas?.getContext("2d");
+    if (!canvas || !context || !sample) return;
+
+    const { size, pixels } = sample.magnifier;
+    canvas.width = size * MAGNIFIER_CELL;
+    canvas.height = size * MAGNIFIER_CELL;
+    for (let row = 0; row < size; row++) {
+      for (let column = 0; column < size; column++) {
+        const offset = (row * size + column) * 4;
+        const alpha = pixels[offset + 3] / 255;
+        context.fillStyle = `rgba(${pixels[offset]}, ${pixels[offset + 1]}, ${pixels[offset + 2]}, ${alpha})`;
+        context.fillRect(
+          column * MAGNIFIER_CELL,
+          row * MAGNIFIER_CELL,
+          MAGNIFIER_CELL,
+          MAGNIFIER_CELL,
+        );
+      }
+    }
+
+    // 中心像素
+    const center = Math.floor(size / 2) * MAGNIFIER_CELL;
+    context.strokeStyle = "#ffffff";
+    context.lineWidth = 2;
+    context.strokeRect(center, center, MAGNIFIER_CELL, MAGNIFIER_CELL);
+  }, [sample]);
+
+  const pick = useCallback(async () => {
+    try {
+      const picked = await invoke<PickedColor>("pick_color");
+      await navigator.clipboard.writeText(picked.formats.hex);
+      setHistory(await invoke<PickedColor[]>("get_color_history"));
+      return picked.formats.hex;
+    } catch (error) {
+      log.error("[ColorPicker] Pick color error:", error);
+      return undefined;
+    }
+  }, []);
+
+  // ESC 只退出取色，不退出截图
+  useEffect(() => {
+    if (!enabled) return;
+
+    const handleKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") {
+        e.stopPropagation();
+        setEnabled(false);
+      }
+    };
+    window.addEventListener("keydown", handleKeyDown, true);
+    return () => window.removeEventListener("keydown", handleKeyDown, true);
+  }, [enabled]);
+
+  useImperativeHandle(
+    ref,
+    () => ({
+      // 坐标以后端最近一次取样为准，与放大镜显示的一致
+      async pickColor(_mousePosition: MousePosition) {
+        return pick();
+      },
+      setForceEnable(enable: boolean) {
+        setEnabled(enable);
+      },
+      getCurrentColor() {
+        return currentColorRef.current;
+      },
+    }),
+    [pick],
+  );
+
+  if (!enabled) return null;
+
+  return (
+    <div
+      className="color-picker-layer"
+      style={{
+        position: "fixed",
+        inset: 0,
+        cursor: "crosshair",
+        zIndex: zIndexs.Draw_ColorPicker,
+      }}
+      onMouseMove={(e) => setCursor({ x: e.clientX, y: e.clientY })}
+      onClick={async () => {
+        if (await pick()) setEnabled(false);
+      }}
+    >
+      {sample && cursor && (
+        <div
+          style={{
+            position: "fixed",
+            left: cursor.x + MAGNIFIER_OFFSET,
+            top: cursor.y + MAGNIFIER_OFFSET,
+            pointerEvents: "none",
+          }}
+          className="rounded-md bg-black/80 p-1 text-xs text-white shadow-lg"
+        >
+          <canvas
+            ref={canvasRef}
+            className="block rounded-sm"
+            style={{ imageRendering: "pixelated" }}
+          />
+          <div className="mt-1 flex items-center gap-1">
+            <span
+              className="inline-block h-3 w-3 rounded-sm border border-white/50"
+              style={{ backgroundColor: sample.formats.hex }}
+            />
+            <span className="font-mono">{sample.formats.hex}</span>
+          </div>
+          <div className="font-mono">{sample.formats.rgb}</div>
+          <div className="font-mono">{sample.formats.hsl}</div>
+          <div className="font-mono">{sample.formats.oklch}</div>
+          {history.length > 0 && (
+            <div className="mt-1 flex gap-0.5">
+              {history.slice(0, 8).map((color) => (
+                <span
+                  key={color.formats.hex}
+                  className="inline-block h-3 w-3 rounded-sm border border-white/30"
+                  style={{ backgroundColor: color.formats.hex }}
+                />
+              ))}
+            </div>
+          )}
+        </div>
+      )}
+    </div>
+  );
+});
+
+ColorPicker.displayName = "ColorPicker";
@@ -6,6 +6,8 @@ import {
   withStatePublisher,
 } from "@/hooks/useStatePublisher";
 import { log } from "@/utils/logger";
+import { toAnnotationDocument } from "./annotationDocument";
+import { ColorPicker } from "./ColorPicker";
 import { DrawLayer } from "./DrawLayer";
 import {
   ArrowEndPublisher,
@@ -474,6 +476,35 @@ const DrawPageCore: React.FC = () => {
 
     // 获取 DrawLayer 的绘图内容
     const drawCoreAction = drawLayerActionRef.current?.getDrawCoreAction?.();
+
+    // 优先交给后端渲染器绘制标注，与自动化流程输出一致
+    const annotationDocument = drawCoreAction?.hasDrawings?.()
+      ? toAnnotationDocument(drawCoreAction.getElements(), cropX, cropY, dpr)
+      : null;
+    if (annotationDocument) {
+      try {
+        const rendered = await invoke<string>("render_annotations", {
+          imageBase64: canvas.toDataURL("image/png").split(",")[1],
+          document: annotationDocument,
+        });
+        const renderedImage = new Image();
+        await new Promise<void>((resolve, reject) => {
+          renderedImage.onload = () => resolve();
+          renderedImage.onerror = reject;
+          renderedImage.src = `data:image/png;base64,${rendered}`;
+        });
+        ctx.clearRect(0, 0, cropWidth, cropHeight);
+        ctx.drawImage(renderedImage, 0, 0);
+
+        const blob = await new Promise<Blob | null>((resolve) => {
+          canvas.toBlob(resolve, "image/png");
+        });
+        return { blob, canvas };
+      } catch (error) {
+        log.warn("[DrawPage] render_annotations failed, falling back:", error);
+      }
+    }
+
     if (drawCoreAction?.hasDrawings?.()) {
       log.info("[DrawPage] Compositing drawings...");
       const exportResult = await drawCoreAction.exportToBlob?.({
@@ -623,6 +654,82 @@ const DrawPageCore: React.FC = () => {
     }
   }, [finishCapture, compositeImage]);
 
+  /**
+   * 贴图：把选区贴在屏幕原位置上
+   */
+  const handlePin = useCallback(async () => {
+    try {
+      const result = await compositeImage();
+      if (!result?.canvas) {
+        log.error("[DrawPage] Failed to create composite image");
+        return;
+      }
+
+      // 选区是 CSS 像素，贴图窗口位置是屏幕物理像素
+      const dpr = window.devicePixelRatio || 1;
+      const windowPosition = await getCurrentWindow().outerPosition();
+      const selectRect = selectLayerActionRef.current?.getSelectRect?.();
+      const base64Data = result.canvas.toDataURL("image/png").split(",")[1];
+      await invoke("pin_window", {
+        imageBase64: base64Data,
+        x: windowPosition.x + Math.round((selectRect?.min_x ?? 0) * dpr),
+        y: windowPosition.y + Math.round((selectRect?.min_y ?? 0) * dpr),
+      });
+
+      await finishCapture();
+    } catch (error) {
+      log.error("[DrawPage] Pin error:", error);
+    }
+  }, [finishCapture, compositeImage]);
+
+  /**
+   * 隐私遮挡：OCR 识别截图文本，遮挡邮箱、令牌、IP 和银行卡号
+   */
+  const handleRedact = useCallback(async () => {
+    const loadedImage = loadedImageRef.current;
+    const boundingBox = captureBoundingBoxInfoRef.current;
+    if (!loadedImage || !boundingBox) return;
+
+    try {
+      const sourceBlob = await fetch(loadedImage.src).then((r) => r.blob());
+      const sourceDataUrl = await new Promise<string>((resolve, reject) => {
+        const reader = new FileReader();
+        reader.onload = () => resolve(reader.result as string);
+        reader.onerror = reject;
+        reader.readAsDataURL(sourceBlob);
+      });
+
+      const result = await invoke<{
+        image_base64: string;
+        report: { items: { reason: string; text: string | null }[] };
+      }>("redact_screenshot", {
+        imageBase64: sourceDataUrl.split(",")[1],
+        options: { auto: true },
+      });
+      log.info(
+        "[DrawPage] Redacted regions:",
+        result.report.items.map((item) => `${item.reason}: ${item.text ?? ""}`),
+      );
+      if (result.report.items.length === 0) return;
+
+      const bytes = Uint8Array.from(atob(result.image_base64), (c) =>
+        c.charCodeAt(0),
+      );
+      const imageSrc = URL.createObjectURL(
+        new Blob([bytes], { type: "image/png" }),
+      );
+      URL.revokeObjectURL(loadedImage.src);
+      loadedImageRef.current = { ...loadedImage, src: imageSrc };
+      await imageLayerActionRef.current?.onCaptureLoad(
+        imageSrc,
+        undefined,
+        boundingBox,
+      );
+    } catch (error) {
+      log.error("[DrawPage] Redact error:", error);
+    }
+  }, []);
+
   /**
    * 键盘快捷键处理
    * - ESC: 退出截图
@@ -687,10 +794,18 @@ const DrawPageCore: React.FC = () => {
         {/* 选择层 - z-index 最高 */}
         <SelectLayer ref={selectLayerActionRef} />
 
+        {/* 取色器 */}
+        <ColorPicker ref={colorPickerActionRef} />
+
         {/* 工具栏 */}
         <Toolbar
           onSave={handleSave}
           onCopy={handleCopy}
+          onRedact={handleRedact}
+          onPin={handlePin}
+          onColorPicker={() =>
+            colorPickerActionRef.current?.setForceEnable(true)
+          }
           onClose={finishCapture}
           onUndo={() =>
             drawLayerActionRef.current?.getDrawCoreAction()?.undo?.()
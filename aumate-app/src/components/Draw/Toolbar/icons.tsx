@@ -1,9 +1,12 @@
 import {
   Eraser,
+  EyeOff,
   Hand,
   Image as ImageIcon,
   Lock,
   MoreHorizontal,
+  Pin,
+  Pipette,
 } from "lucide-react";
 import type React from "react";
 
@@ -35,6 +38,21 @@ export const EraserIcon: React.FC<IconProps> = ({
   className,
 }) => <Eraser size={size} className={className} />;
 
+export const RedactIcon: React.FC<IconProps> = ({
+  size = defaultSize,
+  className,
+}) => <EyeOff size={size} className={className} />;
+
+export const PinIcon: React.FC<IconProps> = ({
+  size = defaultSize,
+  className,
+}) => <Pin size={size} className={className} />;
+
+export const ColorPickerIcon: React.FC<IconProps> = ({
+  size = defaultSize,
+  className,
+}) => <Pipette size={size} className={className} />;
+
 export const MoreIcon: React.FC<IconProps> = ({
   size = defaultSize,
   className,
@@ -10,6 +10,7 @@ import { CaptureStep, DrawState, type ElementRect } from "../types";
 import {
   ArrowIcon,
   CloseIcon,
+  ColorPickerIcon,
   CopyIcon,
   DiamondIcon,
   EllipseIcon,
@@ -19,7 +20,9 @@ import {
   LineIcon,
   LockIcon,
   PenIcon,
+  PinIcon,
   RectIcon,
+  RedactIcon,
   RedoIcon,
   SaveIcon,
   SelectIcon,
@@ -32,6 +35,12 @@ import { ToolDivider } from "./ToolDivider";
 export interface ToolbarProps {
   onSave: () => void;
   onCopy: () => void;
+  /** 自动遮挡敏感信息 */
+  onRedact?: () => void;
+  /** 贴到屏幕上 */
+  onPin?: () => void;
+  /** 屏幕取色 */
+  onColorPicker?: () => void;
   onClose: () => void;
   onUndo?: () => void;
   onRedo?: () => void;
@@ -48,6 +57,9 @@ export interface ToolbarProps {
 export const Toolbar: React.FC<ToolbarProps> = ({
   onSave,
   onCopy,
+  onRedact,
+  onPin,
+  onColorPicker,
   onClose,
   onUndo,
   onRedo,
@@ -286,6 +298,23 @@ export const Toolbar: React.FC<ToolbarProps> = ({
 
         {/* 操作按钮 */}
         <div className="flex items-center gap-0.5">
+          {onRedact && (
+            <ToolButton
+              icon={<RedactIcon />}
+              onClick={onRedact}
+              tooltip="隐私遮挡"
+            />
+          )}
+          {onPin && (
+            <ToolButton icon={<PinIcon />} onClick={onPin} tooltip="贴图" />
+          )}
+          {onColorPicker && (
+            <ToolButton
+              icon={<ColorPickerIcon />}
+              onClick={onColorPicker}
+              tooltip="取色"
+            />
+          )}
           <ToolButton
             icon={<SaveIcon />}
             onClick={onSave}
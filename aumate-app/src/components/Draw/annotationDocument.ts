@@ -0,0 +1,191 @@
+import type { ExcalidrawElement } from "@excalidraw/excalidraw/element/types";
+
+// ============ 标注文档 (与 aumate_core_domain::annotation 的 JSON 结构一致) ============
+
+export interface AnnotationPoint {
+  x: number;
+  y: number;
+}
+
+export interface AnnotationStyle {
+  /** "#RRGGBB" 或 "#RRGGBBAA" */
+  stroke: string;
+  stroke_width: number;
+  fill: string | null;
+  /** 0.0 - 1.0 */
+  opacity: number;
+}
+
+export type AnnotationShape =
+  | {
+      type: "rectangle";
+      x: number;
+      y: number;
+      width: number;
+      height: number;
+      corner_radius: number;
+    }
+  | { type: "ellipse"; x: number; y: number; width: number; height: number }
+  | { type: "line"; from: AnnotationPoint; to: AnnotationPoint }
+  | { type: "arrow"; from: AnnotationPoint; to: AnnotationPoint }
+  | { type: "freehand"; points: AnnotationPoint[] }
+  | {
+      type: "text";
+      x: number;
+      y: number;
+      content: string;
+      font_size: number;
+      background: string | null;
+    }
+  | { type: "step"; x: number; y: number; number: number; radius: number }
+  | {
+      type: "mosaic";
+      x: number;
+      y: number;
+      width: number;
+      height: number;
+      block_size: number;
+    }
+  | {
+      type: "blur";
+      x: number;
+      y: number;
+      width: number;
+      height: number;
+      radius: number;
+    };
+
+export type Annotation = AnnotationShape & {
+  id: string;
+  z_index: number;
+  style: AnnotationStyle;
+};
+
+export interface AnnotationDocument {
+  annotations: Annotation[];
+}
+
+const HEX_COLOR = /^#([0-9a-f]{3}|[0-9a-f]{6}|[0-9a-f]{8})$/i;
+
+/**
+ * 把 Excalidraw 颜色转换为标注颜色
+ *
+ * 返回 undefined 表示颜色无法识别 (例如 CSS 颜色名)
+ */
+function toColor(color: string): string | null | undefined {
+  if (color === "transparent") return null;
+  return HEX_COLOR.test(color) ? color : undefined;
+}
+
+/**
+ * 把 Excalidraw 元素转换为标注文档
+ *
+ * 元素坐标是 CSS 像素，转换后的坐标是裁剪后截图的物理像素。
+ * 只要有一个元素无法准确表达 (旋转、图片、虚线填充等) 就返回 null，
+ * 调用方应回退到前端合成。
+ */
+export function toAnnotationDocument(
+  elements: readonly ExcalidrawElement[],
+  cropX: number,
+  cropY: number,
+  dpr: number,
+): AnnotationDocument | null {
+  const px = (value: number) => value * dpr;
+  const point = (x: number, y: number): AnnotationPoint => ({
+    x: x * dpr - cropX,
+    y: y * dpr - cropY,
+  });
+
+  const annotations: Annotation[] = [];
+
+  for (const [index, element] of elements.entries()) {
+    if (element.isDeleted) continue;
+    if (element.angle !== 0) return null;
+
+    const stroke = toColor(element.strokeColor);
+    const background = toColor(element.backgroundColor);
+    if (stroke === undefined || background === undefined) return null;
+    if (background !== null && element.fillStyle !== "solid") return null;
+
+    const style: AnnotationStyle = {
+      stroke: stroke ?? "#00000000",
+      stroke_width: px(element.strokeWidth),
+      fill: background,
+      opacity: element.opacity / 100,
+    };
+    const origin = point(element.x, element.y);
+    let shape: AnnotationShape;
+
+    switch (element.type) {
+      case "rectangle": {
+        const width = px(element.width);
+        const height = px(element.height);
+        shape = {
+          type: "rectangle",
+          ...origin,
+          width,
+          height,
+          corner_radius: element.roundness ? Math.min(width, height) * 0.25 : 0,
+        };
+        break;
+      }
+      case "ellipse":
+        shape = {
+          type: "ellipse",
+          ...origin,
+          width: px(element.width),
+          height: px(element.height),
+        };
+        break;
+      case "arrow":
+      case "line": {
+        const points = element.points.map(([x, y]) =>
+          point(element.x + x, element.y + y),
+        );
+        if (points.length < 2) continue;
+        if (element.type === "arrow") {
+          // 折线箭头无法表达
+          if (points.length !== 2 || element.startArrowhead) return null;
+          shape = element.endArrowhead
+            ? { type: "arrow", from: points[0], to: points[1] }
+            : { type: "line", from: points[0], to: points[1] };
+        } else if (points.length === 2) {
+          shape = { type: "line", from: points[0], to: points[1] };
+        } else {
+          shape = { type: "freehand", points };
+        }
+        break;
+      }
+      case "freedraw":
+        shape = {
+          type: "freehand",
+          points: element.points.map(([x, y]) =>
+            point(element.x + x, element.y + y),
+          ),
+        };
+        break;
+      case "text":
+        if (element.containerId) return null;
+        shape = {
+          type: "text",
+          ...origin,
+          content: element.text,
+          font_size: px(element.fontSize),
+          background: null,
+        };
+        style.fill = null;
+        break;
+      default:
+        return null;
+    }
+
+    annotations.push({
+      ...shape,
+      id: element.id,
+      z_index: index,
+      style,
+    });
+  }
+
+  return { annotations };
+}
@@ -0,0 +1,190 @@
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import type React from "react";
+import { useCallback, useEffect, useRef, useState } from "react";
+import { log } from "@/utils/logger";
+
+/** 与 Rust `PinnedWindow` 对应，坐标为屏幕物理像素 */
+interface PinnedWindow {
+  id: string;
+  x: number;
+  y: number;
+  image_width: number;
+  image_height: number;
+  zoom: number;
+  opacity: number;
+  click_through: boolean;
+  visible: boolean;
+}
+
+interface PinnedWindowContent {
+  window: PinnedWindow;
+  image_base64: string;
+}
+
+const ZOOM_STEP = 1.1;
+const OPACITY_STEP = 0.1;
+
+/**
+ * 贴图窗口
+ *
+ * - 拖动移动，松开前持续吸附到屏幕边缘和其他贴图
+ * - 滚轮缩放，Alt + 滚轮调整透明度
+ * - `0` 恢复原始大小，`T` 切换鼠标穿透，`Esc` 或双击关闭
+ * - 开启鼠标穿透后窗口收不到输入，需从托盘菜单 "Release Pinned Images" 恢复
+ */
+export function PinWindow() {
+  const windowId = useRef(getCurrentWebviewWindow().label).current;
+  const [pinned, setPinned] = useState<PinnedWindow | null>(null);
+  const [image, setImage] = useState<string | null>(null);
+  const dragRef = useRef<{
+    pointerX: number;
+    pointerY: number;
+    windowX: number;
+    windowY: number;
+  } | null>(null);
+  const pendingMoveRef = useRef<number | null>(null);
+
+  useEffect(() => {
+    invoke<PinnedWindowContent>("get_pinned_window", { windowId })
+      .then((content) => {
+        setPinned(content.window);
+        setImage(`data:image/png;base64,${content.image_base64}`);
+      })
+      .catch((error) => log.error("[PinWindow] Failed to load:", error));
+
+    const unlisten = getCurrentWebviewWindow().listen<PinnedWindow>(
+      "pinned-window-updated",
+      (event) => setPinned(event.payload),
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [windowId]);
+
+  const unpin = useCallback(() => {
+    invoke("unpin_window", { windowId }).catch((error) =>
+      log.error("[PinWindow] Failed to unpin:", error),
+    );
+  }, [windowId]);
+
+  const zoomTo = useCallback(
+    (zoom: number) => {
+      invoke<PinnedWindow>("zoom_pinned_window", { windowId, zoom })
+        .then(setPinned)
+        .catch((error) => log.error("[PinWindow] Failed to zoom:", error));
+    },
+    [windowId],
+  );
+
+  const setOpacity = useCallback(
+    (opacity: number) => {
+      invoke<PinnedWindow>("set_pinned_opacity", { windowId, opacity })
+        .then(setPinned)
+        .catch((error) =>
+          log.error("[PinWindow] Failed to set opacity:", error),
+        );
+    },
+    [windowId],
+  );
+
+  const handleWheel = useCallback(
+    (event: React.WheelEvent) => {
+      if (!pinned) return;
+      const up = event.deltaY < 0;
+      if (event.altKey) {
+        setOpacity(pinned.opacity + (up ? OPACITY_STEP : -OPACITY_STEP));
+      } else {
+        zoomTo(up ? pinned.zoom * ZOOM_STEP : pinned.zoom / ZOOM_STEP);
+      }
+    },
+    [pinned, setOpacity, zoomTo],
+  );
+
+  const handlePointerDown = useCallback(
+    (event: React.PointerEvent) => {
+      if (!pinned || event.button !== 0) return;
+      event.currentTarget.setPointerCapture(event.pointerId);
+      dragRef.current = {
+        pointerX: event.screenX * window.devicePixelRatio,
+        pointerY: event.screenY * window.devicePixelRatio,
+        windowX: pinned.x,
+        windowY: pinned.y,
+      };
+    },
+    [pinned],
+  );
+
+  const handlePointerMove = useCallback(
+    (event: React.PointerEvent) => {
+      const drag = dragRef.current;
+      if (!drag) return;
+      const x = Math.round(
+        drag.windowX + event.screenX * window.devicePixelRatio - drag.pointerX,
+      );
+      const y = Math.round(
+        drag.windowY + event.screenY * window.devicePixelRatio - drag.pointerY,
+      );
+
+      // 每帧最多移动一次
+      if (pendingMoveRef.current !== null) {
+        cancelAnimationFrame(pendingMoveRef.current);
+      }
+      pendingMoveRef.current = requestAnimationFrame(() => {
+        pendingMoveRef.current = null;
+        invoke<PinnedWindow>("move_pinned_window", { windowId, x, y })
+          .then(setPinned)
+          .catch((error) => log.error("[PinWindow] Failed to move:", error));
+      });
+    },
+    [windowId],
+  );
+
+  const handlePointerUp = useCallback((event: React.PointerEvent) => {
+    event.currentTarget.releasePointerCapture(event.pointerId);
+    dragRef.current = null;
+  }, []);
+
+  useEffect(() => {
+    const handleKeyDown = (event: KeyboardEvent) => {
+      if (event.key === "Escape") {
+        unpin();
+      } else if (event.key === "0") {
+        zoomTo(1);
+      } else if (event.key.toLowerCase() === "t" && pinned) {
+        invoke<PinnedWindow>("set_pinned_click_through", {
+          windowId,
+          enabled: !pinned.click_through,
+        })
+          .then(setPinned)
+          .catch((error) =>
+            log.error("[PinWindow] Failed to toggle click-through:", error),
+          );
+      }
+    };
+    window.addEventListener("keydown", handleKeyDown);
+    return () => window.removeEventListener("keydown", handleKeyDown);
+  }, [pinned, unpin, windowId, zoomTo]);
+
+  if (!pinned || !image) return null;
+
+  return (
+    <div
+      className="w-screen h-screen cursor-move select-none"
+      style={{ opacity: pinned.opacity }}
+      onWheel={handleWheel}
+      onPointerDown={handlePointerDown}
+      onPointerMove={handlePointerMove}
+      onPointerUp={handlePointerUp}
+      onDoubleClick={unpin}
+    >
+      <img
+        src={image}
+        alt=""
+        draggable={false}
+        className="block w-full h-full"
+        style={{ imageRendering: pinned.zoom > 1 ? "pixelated" : "auto" }}
+      />
+    </div>
+  );
+}
@@ -16,6 +16,14 @@ interface WindowElement {
   app_name: string;
 }
 
+interface MonitorInfo {
+  id: string;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
 interface SelectionState {
   isSelecting: boolean;
   startX: number;
@@ -41,6 +49,7 @@ export function ScreenshotMode() {
     null,
   );
   const [windows, setWindows] = useState<WindowElement[]>([]);
+  const [monitors, setMonitors] = useState<MonitorInfo[]>([]);
   const [mousePos, setMousePos] = useState({ x: 0, y: 0 });
   // Screen bounds can be used for coordinate mapping in multi-monitor setups
   const [_screenBounds, setScreenBounds] = useState<ElementRect | null>(null);
@@ -62,19 +71,19 @@ export function ScreenshotMode() {
         });
         setScreenshotData(`data:image/png;base64,${base64Image}`);
 
-        // Get screen bounds
-        const monitorInfo = await invoke<{
-          x: number;
-          y: number;
-          width: number;
-          height: number;
-        }>("get_monitor_info", { x: 0, y: 0 });
-        setScreenBounds({
-          min_x: monitorInfo.x,
-          min_y: monitorInfo.y,
-          max_x: monitorInfo.x + monitorInfo.width,
-          max_y: monitorInfo.y + monitorInfo.height,
-        });
+        // Get screen bounds from the monitor layout
+        const { monitors: monitorList } = await invoke<{
+          monitors: MonitorInfo[];
+        }>("get_monitors");
+        setMonitors(monitorList);
+        if (monitorList.length > 0) {
+          setScreenBounds({
+            min_x: Math.min(...monitorList.map((m) => m.x)),
+            min_y: Math.min(...monitorList.map((m) => m.y)),
+            max_x: Math.max(...monitorList.map((m) => m.x + m.width)),
+            max_y: Math.max(...monitorList.map((m) => m.y + m.height)),
+          });
+        }
       } catch (error) {
         console.error("Failed to capture screen:", error);
       }
@@ -301,16 +310,26 @@ export function ScreenshotMode() {
         format: "png",
       });
 
-      // Get save path from settings or use default
-      const timestamp = new Date().toISOString().replace(/[:.]/g, "-");
-      const filename = `screenshot_${timestamp}.png`;
-
-      // For now, save to Pictures folder
-      const savePath = `${await getDownloadsPath()}/${filename}`;
+      // Window and monitor under the selection fill in {title}, {app} and {monitor}
+      const centerX = x + width / 2;
+      const centerY = y + height / 2;
+      const win = findWindowAtPoint(centerX, centerY);
+      const monitor = monitors.find(
+        (m) =>
+          centerX >= m.x &&
+          centerX < m.x + m.width &&
+          centerY >= m.y &&
+          centerY < m.y + m.height,
+      );
+
+      // Folder, file name and format come from the screenshot settings
       await invoke("save_screenshot", {
-        imageData: base64Image,
-        filePath: savePath,
-        format: "png",
+        imageBase64: base64Image,
+        options: {
+          window_title: win?.title,
+          app_name: win?.app_name,
+          monitor_id: monitor?.id,
+        },
       });
 
       // Close the window after saving
@@ -318,7 +337,7 @@ export function ScreenshotMode() {
     } catch (error) {
       console.error("Failed to save screenshot:", error);
     }
-  }, [selection]);
+  }, [selection, findWindowAtPoint, monitors]);
 
   const handleKeyDown = useCallback(
     async (e: KeyboardEvent) => {
@@ -446,10 +465,3 @@ export function ScreenshotMode() {
     </div>
   );
 }
-
-// Helper to get downloads path - simplified version
-async function getDownloadsPath(): Promise<string> {
-  // This is a placeholder - in a real app, you'd use Tauri's path API
-  // or get it from settings
-  return "C:/Users/Public/Pictures";
-}
@@ -1,18 +1,23 @@
 import { Eye, EyeOff, Trash2 } from "lucide-react";
-import { useState } from "react";
+import { useEffect, useState } from "react";
 import { useDialogueStore } from "@/stores/dialogueStore";
 import { useSettingsStore } from "@/stores/settingsStore";
 
 export function AIDialogueSettings() {
   const { settings, updateAIDialogue } = useSettingsStore();
   const { ai_dialogue } = settings;
-  const { conversations, clearAllConversations } = useDialogueStore();
+  const { conversations, loadConversations, clearAllConversations } =
+    useDialogueStore();
   const [showApiKey, setShowApiKey] = useState(false);
 
+  useEffect(() => {
+    loadConversations("");
+  }, [loadConversations]);
+
   const handleClearConversations = () => {
     if (
       window.confirm(
-        `Are you sure you want to delete all ${conversations.length} conversations? This cannot be undone.`,
+        "Are you sure you want to delete all conversations? This cannot be undone.",
       )
     ) {
       clearAllConversations();
@@ -159,8 +164,7 @@ export function AIDialogueSettings() {
                   Clear All Conversations
                 </div>
                 <p className="text-xs text-gray-500 mt-0.5">
-                  Delete all {conversations.length} conversation
-                  {conversations.length === 1 ? "" : "s"}
+                  Delete all saved conversations and their messages
                 </p>
               </div>
               <button
@@ -1,11 +1,21 @@
 import { AppWindow, MessageSquare, Search, Sparkles } from "lucide-react";
+import { useEffect, useState } from "react";
 import { SettingRow, Toggle } from "@/components/ui";
 import { cn } from "@/lib/utils";
 import { useSettingsStore } from "@/stores/settingsStore";
 
 export function GeneralSettings() {
-  const { settings, updateGeneral, updateEnabledModes } = useSettingsStore();
-  const { general, enabled_modes } = settings;
+  const { settings, updateGeneral, updateEnabledModes, updateLauncher } =
+    useSettingsStore();
+  const { general, enabled_modes, launcher } = settings;
+
+  // Edit folders as text and only commit complete lines on blur
+  const [foldersDraft, setFoldersDraft] = useState(
+    launcher.search_folders.join("\n"),
+  );
+  useEffect(() => {
+    setFoldersDraft(launcher.search_folders.join("\n"));
+  }, [launcher.search_folders]);
 
   return (
     <div className="space-y-6">
@@ -145,6 +155,50 @@ export function GeneralSettings() {
           At least one mode will always remain enabled
         </p>
       </div>
+
+      {/* Launcher */}
+      <div className="pt-4 space-y-4">
+        <h3 className="text-sm font-medium text-white">Launcher</h3>
+        <SettingRow
+          label="Index Applications"
+          description="Show installed applications in search results"
+        >
+          <Toggle
+            checked={launcher.index_applications}
+            onChange={(checked) =>
+              updateLauncher({ index_applications: checked })
+            }
+          />
+        </SettingRow>
+        <div>
+          <label
+            htmlFor="launcher-folders"
+            className="block text-sm font-medium text-gray-300 mb-2"
+          >
+            Search Folders
+          </label>
+          <textarea
+            id="launcher-folders"
+            value={foldersDraft}
+            onChange={(e) => setFoldersDraft(e.target.value)}
+            onBlur={() =>
+              updateLauncher({
+                search_folders: foldersDraft
+                  .split("\n")
+                  .map((line) => line.trim())
+                  .filter(Boolean),
+              })
+            }
+            rows={3}
+            placeholder="/home/user/Documents"
+            className="w-full bg-gray-800 border border-gray-700 rounded-lg px-3 py-2 text-sm text-white placeholder-gray-500 focus:outline-none focus:ring-2 focus:ring-blue-500 focus:border-transparent resize-none"
+          />
+          <p className="text-xs text-gray-500 mt-1">
+            One absolute path per line. Files and folders up to{" "}
+            {launcher.max_depth} levels deep are searchable.
+          </p>
+        </div>
+      </div>
     </div>
   );
 }
@@ -1,6 +1,9 @@
 import { FolderOpen } from "lucide-react";
 import { Toggle } from "@/components/ui/Toggle";
-import { useSettingsStore } from "@/stores/settingsStore";
+import {
+  type ScreenshotImageFormat,
+  useSettingsStore,
+} from "@/stores/settingsStore";
 
 export function ScreenshotSettings() {
   const { settings, updateScreenshot } = useSettingsStore();
@@ -74,7 +77,10 @@ export function ScreenshotSettings() {
           />
           <p className="text-xs text-gray-500 mt-1">
             Use %Y (year), %m (month), %d (day), %H (hour), %M (minute), %S
-            (second)
+            (second), <code>{"{title}"}</code> (window title),{" "}
+            <code>{"{app}"}</code> (application), <code>{"{monitor}"}</code>{" "}
+            and <code>{"{counter}"}</code> (or <code>{"{counter:3}"}</code> to
+            pad with zeros)
           </p>
         </div>
 
@@ -91,7 +97,7 @@ export function ScreenshotSettings() {
             value={screenshot.image_format}
             onChange={(e) =>
               updateScreenshot({
-                image_format: e.target.value as "png" | "webp" | "jpeg",
+                image_format: e.target.value as ScreenshotImageFormat,
               })
             }
             className="w-full bg-gray-800 border border-gray-700 rounded-lg px-3 py-2 text-sm text-white focus:outline-none focus:ring-2 focus:ring-blue-500 focus:border-transparent"
@@ -99,9 +105,35 @@ export function ScreenshotSettings() {
             <option value="png">PNG (Best quality, larger file)</option>
             <option value="webp">WebP (Good quality, smaller file)</option>
             <option value="jpeg">JPEG (Smaller file, lossy)</option>
+            <option value="avif">AVIF (Smallest file, lossy)</option>
           </select>
         </div>
 
+        {/* Image Quality */}
+        {(screenshot.image_format === "jpeg" ||
+          screenshot.image_format === "webp" ||
+          screenshot.image_format === "avif") && (
+          <div>
+            <label
+              htmlFor="image-quality"
+              className="block text-sm font-medium text-gray-300 mb-2"
+            >
+              Image Quality: {screenshot.image_quality}
+            </label>
+            <input
+              id="image-quality"
+              type="range"
+              min={1}
+              max={100}
+              value={screenshot.image_quality}
+              onChange={(e) =>
+                updateScreenshot({ image_quality: Number(e.target.value) })
+              }
+              className="w-full accent-blue-500"
+            />
+          </div>
+        )}
+
         {/* Auto Copy to Clipboard */}
         <div className="flex items-center justify-between">
           <div>
@@ -8,8 +8,10 @@ export const DEFAULT_MODEL = "gpt-4";
 export const DEFAULT_HOTKEY = "F3";
 export const DEFAULT_MAX_HISTORY = 20;
 export const DEFAULT_FILENAME_PATTERN = "screenshot_%Y%m%d_%H%M%S";
+export const DEFAULT_IMAGE_QUALITY = 90;
 
 export const defaultSettings: Settings = {
+  schema_version: 2,
   general: {
     follow_system_appearance: true,
     open_at_login: false,
@@ -36,6 +38,7 @@ export const defaultSettings: Settings = {
     save_folder: "",
     filename_pattern: DEFAULT_FILENAME_PATTERN,
     image_format: "png",
+    image_quality: DEFAULT_IMAGE_QUALITY,
     auto_copy_clipboard: true,
   },
   ai_dialogue: {
@@ -51,4 +54,29 @@ export const defaultSettings: Settings = {
     dialogue: true,
     switcher: true,
   },
+  clipboard_history: {
+    enabled: true,
+    max_entries: 500,
+    max_age_days: 30,
+    excluded_apps: [
+      "1Password",
+      "1Password 7",
+      "Bitwarden",
+      "KeePass",
+      "KeePassXC",
+      "KeePassX",
+      "LastPass",
+      "Dashlane",
+      "Enpass",
+      "Keeper Password Manager",
+      "Keychain Access",
+      "Passwords",
+      "seahorse",
+    ],
+  },
+  launcher: {
+    index_applications: true,
+    search_folders: [],
+    max_depth: 3,
+  },
 };
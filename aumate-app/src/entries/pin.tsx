@@ -0,0 +1,10 @@
+import React from "react";
+import ReactDOM from "react-dom/client";
+import { PinWindow } from "../components/Pin/PinWindow";
+import "../index.css";
+
+ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
+  <React.StrictMode>
+    <PinWindow />
+  </React.StrictMode>,
+);
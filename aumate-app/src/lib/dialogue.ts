@@ -1,47 +1,40 @@
 import { type StreamingResult, streamChatCompletion } from "./streaming";
 
-export interface ChatMessage {
-  role: "system" | "user" | "assistant";
-  content: string;
+export interface ConversationPayload {
+  id: number;
+  title: string;
+  model: string | null;
+  message_count: number;
+  created_at: number;
+  updated_at: number;
 }
 
 export interface DialogueOptions {
-  apiUrl: string;
-  apiKey: string;
-  model: string;
-  systemPrompt: string;
-  messages: ChatMessage[];
+  /** Conversation to continue; a new one is created when null */
+  conversationId: number | null;
+  /** The new user message */
+  content: string;
+  /** Called before the reply starts with the conversation the message belongs to */
+  onConversation?: (conversation: ConversationPayload) => void;
   onChunk?: (chunk: string) => void;
   signal?: AbortSignal;
 }
 
 export type DialogueResult = StreamingResult;
 
+/**
+ * Send a dialogue message; history is loaded from the saved conversation and
+ * the reply is saved once it completes
+ */
 export async function sendDialogueMessage(
   options: DialogueOptions,
 ): Promise<DialogueResult> {
-  const { apiUrl, apiKey, model, systemPrompt, messages, onChunk, signal } =
-    options;
-
-  // Build messages array with system prompt
-  const chatMessages = [{ role: "system", content: systemPrompt }, ...messages];
+  const { conversationId, content, onConversation, onChunk, signal } = options;
 
-  const result = await streamChatCompletion({
-    apiUrl,
-    apiKey,
-    model,
-    messages: chatMessages,
+  return streamChatCompletion({
+    request: { mode: "dialogue", conversation_id: conversationId, content },
+    onConversation,
     onChunk,
     signal,
   });
-
-  if (result.error === "API key not configured.") {
-    return {
-      content: "",
-      error:
-        "API key not configured. Please set your API key in Settings > AI Dialogue.",
-    };
-  }
-
-  return result;
 }
@@ -1,127 +1,80 @@
-// Shared streaming utilities for OpenAI-compatible APIs
+// Streaming chat completions through the Rust LLM gateway
+//
+// Requests are sent by the `ai_chat` command, which reads the endpoint, model
+// and API key from settings. Tokens arrive as `ai-chat-chunk` events.
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type { ConversationPayload } from "./dialogue";
 
 export interface StreamingResult {
   content: string;
   error?: string;
 }
 
+export type ChatRequest =
+  | { mode: "polish"; input: string }
+  | { mode: "dialogue"; conversation_id: number | null; content: string };
+
 export interface StreamingOptions {
-  apiUrl: string;
-  apiKey: string;
-  model: string;
-  messages: Array<{ role: string; content: string }>;
+  request: ChatRequest;
+  onConversation?: (conversation: ConversationPayload) => void;
   onChunk?: (chunk: string) => void;
   signal?: AbortSignal;
 }
 
-/**
- * Parse SSE streaming response from OpenAI-compatible APIs
- */
-async function parseSSEStream(
-  reader: ReadableStreamDefaultReader<Uint8Array>,
-  onChunk?: (chunk: string) => void,
-): Promise<string> {
-  const decoder = new TextDecoder();
-  let fullContent = "";
-
-  while (true) {
-    const { done, value } = await reader.read();
-    if (done) break;
-
-    const chunk = decoder.decode(value, { stream: true });
-    const lines = chunk.split("\n");
-
-    for (const line of lines) {
-      if (line.startsWith("data: ")) {
-        const data = line.slice(6);
-        if (data === "[DONE]") continue;
-
-        try {
-          const parsed = JSON.parse(data);
-          const content = parsed.choices?.[0]?.delta?.content;
-          if (content) {
-            fullContent += content;
-            onChunk?.(content);
-          }
-        } catch {
-          // Skip invalid JSON lines
-        }
-      }
-    }
-  }
-
-  return fullContent;
+interface ChatChunkEvent {
+  request_id: string;
+  delta: string;
 }
 
-/**
- * Parse API error response
- */
-async function parseApiError(response: Response): Promise<string> {
-  const errorText = await response.text();
-  let errorMessage = `API error: ${response.status}`;
-  try {
-    const errorJson = JSON.parse(errorText);
-    errorMessage = errorJson.error?.message || errorMessage;
-  } catch {
-    // Keep the default error message
-  }
-  return errorMessage;
+interface ChatConversationEvent {
+  request_id: string;
+  conversation: ConversationPayload;
 }
 
 /**
- * Send a streaming request to an OpenAI-compatible API
+ * Send a streaming chat request; aborting the signal cancels it on the Rust side
  */
 export async function streamChatCompletion(
   options: StreamingOptions,
 ): Promise<StreamingResult> {
-  const { apiUrl, apiKey, model, messages, onChunk, signal } = options;
+  const { request, onConversation, onChunk, signal } = options;
 
-  if (!apiKey) {
-    return {
-      content: "",
-      error: "API key not configured.",
-    };
-  }
-
-  if (messages.length === 0) {
-    return { content: "", error: "No messages to send." };
+  if (signal?.aborted) {
+    return { content: "", error: "Request cancelled" };
   }
 
-  try {
-    const response = await fetch(`${apiUrl}/chat/completions`, {
-      method: "POST",
-      headers: {
-        "Content-Type": "application/json",
-        Authorization: `Bearer ${apiKey}`,
-      },
-      body: JSON.stringify({
-        model,
-        stream: true,
-        messages,
-      }),
-      signal,
-    });
-
-    if (!response.ok) {
-      const errorMessage = await parseApiError(response);
-      return { content: "", error: errorMessage };
-    }
-
-    if (!response.body) {
-      return { content: "", error: "No response body received" };
+  const requestId = crypto.randomUUID();
+  const unlisten = await listen<ChatChunkEvent>("ai-chat-chunk", (event) => {
+    if (event.payload.request_id === requestId) {
+      onChunk?.(event.payload.delta);
     }
+  });
+  const unlistenConversation = await listen<ChatConversationEvent>(
+    "ai-chat-conversation",
+    (event) => {
+      if (event.payload.request_id === requestId) {
+        onConversation?.(event.payload.conversation);
+      }
+    },
+  );
+  const cancel = () => {
+    invoke("cancel_ai_chat", { requestId }).catch(() => {});
+  };
+  signal?.addEventListener("abort", cancel);
 
-    const reader = response.body.getReader();
-    const content = await parseSSEStream(reader, onChunk);
-
+  try {
+    const content = await invoke<string>("ai_chat", { requestId, request });
     return { content };
   } catch (error) {
-    if (error instanceof Error) {
-      if (error.name === "AbortError") {
-        return { content: "", error: "Request cancelled" };
-      }
-      return { content: "", error: error.message };
-    }
-    return { content: "", error: "An unknown error occurred" };
+    return {
+      content: "",
+      error: typeof error === "string" ? error : "An unknown error occurred",
+    };
+  } finally {
+    signal?.removeEventListener("abort", cancel);
+    unlisten();
+    unlistenConversation();
   }
 }
@@ -1,186 +1,151 @@
+import { invoke } from "@tauri-apps/api/core";
 import { create } from "zustand";
+import type { ConversationPayload } from "@/lib/dialogue";
 import type { Conversation, DialogueMessage } from "@/types/dialogue";
 
+// Conversations are stored in a SQLite database on the Rust side; this store
+// caches the conversation list and the messages of the active conversation.
+
+interface ConversationMessagePayload {
+  id: number;
+  role: "user" | "assistant";
+  content: string;
+  model: string | null;
+  created_at: number;
+  duration_ms: number | null;
+}
+
+interface ConversationDetailPayload {
+  conversation: ConversationPayload;
+  messages: ConversationMessagePayload[];
+}
+
 interface DialogueStore {
   conversations: Conversation[];
-  activeConversationId: string | null;
+  activeConversationId: number | null;
+  /** Messages of the active conversation */
+  messages: DialogueMessage[];
+  searchQuery: string;
   isLoading: boolean;
 
   // Actions
-  createConversation: () => string;
-  setActiveConversation: (id: string | null) => void;
-  addMessage: (
-    conversationId: string,
-    message: Omit<DialogueMessage, "id">,
-  ) => void;
-  updateLastMessage: (conversationId: string, content: string) => void;
-  deleteConversation: (id: string) => void;
-  clearAllConversations: () => void;
-
-  // Persistence
-  loadConversations: () => Promise<void>;
-  saveConversations: () => Promise<void>;
-
-  // Helpers
-  getActiveConversation: () => Conversation | null;
+  loadConversations: (search?: string) => Promise<void>;
+  selectConversation: (id: number | null) => Promise<void>;
+  /** Called when the backend reports the conversation a message was sent to */
+  receiveConversation: (conversation: ConversationPayload) => void;
+  addLocalMessage: (message: Omit<DialogueMessage, "id">) => void;
+  renameConversation: (id: number, title: string) => Promise<void>;
+  deleteConversation: (id: number) => Promise<void>;
+  clearAllConversations: () => Promise<void>;
+  exportConversation: (id: number, path?: string) => Promise<string>;
 }
 
-const STORAGE_KEY = "dialogue_conversations";
-
-// Generate unique ID
-function generateId(): string {
-  return `${Date.now()}-${Math.random().toString(36).substr(2, 9)}`;
+function toConversation(payload: ConversationPayload): Conversation {
+  return {
+    id: payload.id,
+    title: payload.title,
+    model: payload.model,
+    messageCount: payload.message_count,
+    createdAt: payload.created_at,
+    updatedAt: payload.updated_at,
+  };
 }
 
-// Generate conversation title from first message
-function generateTitle(content: string): string {
-  const maxLength = 30;
-  const trimmed = content.trim().replace(/\n/g, " ");
-  if (trimmed.length <= maxLength) return trimmed;
-  return `${trimmed.substring(0, maxLength)}...`;
+function toMessage(payload: ConversationMessagePayload): DialogueMessage {
+  return {
+    id: payload.id,
+    role: payload.role,
+    content: payload.content,
+    timestamp: payload.created_at,
+    model: payload.model,
+    durationMs: payload.duration_ms,
+  };
 }
 
+// Ids for messages that have not been saved yet
+let nextLocalId = -2;
+
 export const useDialogueStore = create<DialogueStore>((set, get) => ({
   conversations: [],
   activeConversationId: null,
+  messages: [],
+  searchQuery: "",
   isLoading: true,
 
-  createConversation: () => {
-    const id = generateId();
-    const now = Date.now();
-    const newConversation: Conversation = {
-      id,
-      title: "New Chat",
-      messages: [],
-      createdAt: now,
-      updatedAt: now,
-    };
-
-    set((state) => ({
-      conversations: [newConversation, ...state.conversations],
-      activeConversationId: id,
-    }));
-
-    get().saveConversations();
-    return id;
+  loadConversations: async (search) => {
+    const searchQuery = search ?? get().searchQuery;
+    set({ searchQuery });
+    try {
+      const response = await invoke<{ conversations: ConversationPayload[] }>(
+        "list_conversations",
+        { request: { search: searchQuery || null } },
+      );
+      set({
+        conversations: response.conversations.map(toConversation),
+        isLoading: false,
+      });
+    } catch (error) {
+      console.error("Failed to load conversations:", error);
+      set({ isLoading: false });
+    }
   },
 
-  setActiveConversation: (id) => {
+  selectConversation: async (id) => {
     set({ activeConversationId: id });
-  },
-
-  addMessage: (conversationId, message) => {
-    const id = generateId();
-    const fullMessage: DialogueMessage = { ...message, id };
-
-    set((state) => {
-      const conversations = state.conversations.map((conv) => {
-        if (conv.id !== conversationId) return conv;
-
-        const updatedMessages = [...conv.messages, fullMessage];
-        // Update title from first user message
-        const title =
-          conv.messages.length === 0 && message.role === "user"
-            ? generateTitle(message.content)
-            : conv.title;
-
-        return {
-          ...conv,
-          messages: updatedMessages,
-          title,
-          updatedAt: Date.now(),
-        };
-      });
-
-      // Move updated conversation to top
-      const updatedConv = conversations.find((c) => c.id === conversationId);
-      const otherConvs = conversations.filter((c) => c.id !== conversationId);
-
-      return {
-        conversations: updatedConv
-          ? [updatedConv, ...otherConvs]
-          : conversations,
-      };
-    });
+    if (id === null) {
+      set({ messages: [] });
+      return;
+    }
 
-    get().saveConversations();
+    try {
+      const detail = await invoke<ConversationDetailPayload>(
+        "get_conversation",
+        { id },
+      );
+      // Ignore stale responses after switching conversations
+      if (get().activeConversationId === id) {
+        set({ messages: detail.messages.map(toMessage) });
+      }
+    } catch (error) {
+      console.error("Failed to load conversation:", error);
+      set({ messages: [] });
+    }
   },
 
-  updateLastMessage: (conversationId, content) => {
+  receiveConversation: (payload) => {
+    const conversation = toConversation(payload);
     set((state) => ({
-      conversations: state.conversations.map((conv) => {
-        if (conv.id !== conversationId) return conv;
-        if (conv.messages.length === 0) return conv;
-
-        const messages = [...conv.messages];
-        const lastIndex = messages.length - 1;
-        messages[lastIndex] = {
-          ...messages[lastIndex],
-          content,
-        };
-
-        return {
-          ...conv,
-          messages,
-          updatedAt: Date.now(),
-        };
-      }),
+      activeConversationId: conversation.id,
+      conversations: [
+        conversation,
+        ...state.conversations.filter((c) => c.id !== conversation.id),
+      ],
     }));
   },
 
-  deleteConversation: (id) => {
-    set((state) => {
-      const conversations = state.conversations.filter((c) => c.id !== id);
-      const activeConversationId =
-        state.activeConversationId === id
-          ? (conversations[0]?.id ?? null)
-          : state.activeConversationId;
-
-      return { conversations, activeConversationId };
-    });
-
-    get().saveConversations();
+  addLocalMessage: (message) => {
+    const id = nextLocalId--;
+    set((state) => ({ messages: [...state.messages, { ...message, id }] }));
   },
 
-  clearAllConversations: () => {
-    set({
-      conversations: [],
-      activeConversationId: null,
-    });
-    get().saveConversations();
+  renameConversation: async (id, title) => {
+    await invoke("rename_conversation", { id, title });
+    await get().loadConversations();
   },
 
-  loadConversations: async () => {
-    try {
-      set({ isLoading: true });
-      const stored = localStorage.getItem(STORAGE_KEY);
-      if (stored) {
-        const conversations = JSON.parse(stored) as Conversation[];
-        set({
-          conversations,
-          activeConversationId: conversations[0]?.id ?? null,
-          isLoading: false,
-        });
-      } else {
-        set({ isLoading: false });
-      }
-    } catch (error) {
-      console.error("Failed to load conversations:", error);
-      set({ isLoading: false });
+  deleteConversation: async (id) => {
+    await invoke("delete_conversation", { id });
+    await get().loadConversations();
+    if (get().activeConversationId === id) {
+      await get().selectConversation(get().conversations[0]?.id ?? null);
     }
   },
 
-  saveConversations: async () => {
-    try {
-      const { conversations } = get();
-      localStorage.setItem(STORAGE_KEY, JSON.stringify(conversations));
-    } catch (error) {
-      console.error("Failed to save conversations:", error);
-    }
+  clearAllConversations: async () => {
+    await invoke<number>("clear_conversations");
+    set({ conversations: [], activeConversationId: null, messages: [] });
   },
 
-  getActiveConversation: () => {
-    const { conversations, activeConversationId } = get();
-    return conversations.find((c) => c.id === activeConversationId) ?? null;
-  },
+  exportConversation: (id, path) =>
+    invoke<string>("export_conversation_markdown", { id, path: path ?? null }),
 }));
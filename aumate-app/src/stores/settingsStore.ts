@@ -28,10 +28,14 @@ export interface ExpressionPolishingSettings {
   system_prompt: string;
 }
 
+export type ScreenshotImageFormat = "png" | "webp" | "jpeg" | "avif";
+
 export interface ScreenshotSettings {
   save_folder: string;
   filename_pattern: string;
-  image_format: "png" | "webp" | "jpeg";
+  image_format: ScreenshotImageFormat;
+  /** Encoder quality (1-100) for JPEG and AVIF */
+  image_quality: number;
   auto_copy_clipboard: boolean;
 }
 
@@ -50,7 +54,21 @@ export interface EnabledModes {
   switcher: boolean;
 }
 
+export interface ClipboardHistorySettings {
+  enabled: boolean;
+  max_entries: number;
+  max_age_days: number;
+  excluded_apps: string[];
+}
+
+export interface LauncherSettings {
+  index_applications: boolean;
+  search_folders: string[];
+  max_depth: number;
+}
+
 export interface Settings {
+  schema_version: number;
   general: GeneralSettings;
   shortcuts: ShortcutSettings;
   advanced: AdvancedSettings;
@@ -58,11 +76,26 @@ export interface Settings {
   screenshot: ScreenshotSettings;
   ai_dialogue: AIDialogueSettings;
   enabled_modes: EnabledModes;
+  clipboard_history: ClipboardHistorySettings;
+  launcher: LauncherSettings;
+}
+
+/** A setting rejected by the backend, e.g. `shortcuts.screenshot` */
+export interface FieldError {
+  field: string;
+  message: string;
+}
+
+/** Error returned by the `get_settings` and `save_settings` commands */
+export interface SettingsError {
+  message: string;
+  errors: FieldError[];
 }
 
 interface SettingsState {
   settings: Settings;
   isLoading: boolean;
+  fieldErrors: FieldError[];
   activeSection: string;
   setActiveSection: (section: string) => void;
   setSettings: (settings: Settings) => void;
@@ -77,6 +110,7 @@ interface SettingsState {
   updateScreenshot: (updates: Partial<ScreenshotSettings>) => void;
   updateAIDialogue: (updates: Partial<AIDialogueSettings>) => void;
   updateEnabledModes: (updates: Partial<EnabledModes>) => void;
+  updateLauncher: (updates: Partial<LauncherSettings>) => void;
 }
 
 // Debounce timer for auto-save
@@ -108,6 +142,7 @@ function createSectionUpdater<K extends keyof Settings>(
 const createSettingsStore: StateCreator<SettingsState> = (set, get) => ({
   settings: defaultSettings,
   isLoading: true,
+  fieldErrors: [],
   activeSection: "general",
 
   setActiveSection: (section) => set({ activeSection: section }),
@@ -120,6 +155,7 @@ const createSettingsStore: StateCreator<SettingsState> = (set, get) => ({
       const loaded = await invoke<Partial<Settings>>("get_settings");
       // Merge with defaults to handle missing fields from old settings files
       const settings: Settings = {
+        schema_version: loaded.schema_version ?? defaultSettings.schema_version,
         general: { ...defaultSettings.general, ...loaded.general },
         shortcuts: { ...defaultSettings.shortcuts, ...loaded.shortcuts },
         advanced: { ...defaultSettings.advanced, ...loaded.advanced },
@@ -133,6 +169,11 @@ const createSettingsStore: StateCreator<SettingsState> = (set, get) => ({
           ...defaultSettings.enabled_modes,
           ...loaded.enabled_modes,
         },
+        clipboard_history: {
+          ...defaultSettings.clipboard_history,
+          ...loaded.clipboard_history,
+        },
+        launcher: { ...defaultSettings.launcher, ...loaded.launcher },
       };
       set({ settings, isLoading: false });
     } catch (error) {
@@ -145,8 +186,10 @@ const createSettingsStore: StateCreator<SettingsState> = (set, get) => ({
     try {
       const { settings } = get();
       await invoke("save_settings", { settings });
+      set({ fieldErrors: [] });
     } catch (error) {
       console.error("Failed to save settings:", error);
+      set({ fieldErrors: (error as SettingsError).errors ?? [] });
     }
   },
 
@@ -161,6 +204,7 @@ const createSettingsStore: StateCreator<SettingsState> = (set, get) => ({
   updateScreenshot: createSectionUpdater("screenshot", set, get),
   updateAIDialogue: createSectionUpdater("ai_dialogue", set, get),
   updateEnabledModes: createSectionUpdater("enabled_modes", set, get),
+  updateLauncher: createSectionUpdater("launcher", set, get),
 });
 
 export const useSettingsStore = create<SettingsState>(createSettingsStore);
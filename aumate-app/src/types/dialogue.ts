@@ -1,14 +1,21 @@
 export interface DialogueMessage {
-  id: string;
+  /** Database id; messages not saved yet use negative ids */
+  id: number;
   role: "user" | "assistant";
   content: string;
   timestamp: number;
+  /** Model that generated the reply */
+  model?: string | null;
+  /** Time taken to generate the reply */
+  durationMs?: number | null;
 }
 
 export interface Conversation {
-  id: string;
+  id: number;
   title: string;
-  messages: DialogueMessage[];
+  /** Model used for the latest reply */
+  model: string | null;
+  messageCount: number;
   createdAt: number;
   updatedAt: number;
 }
@@ -24,6 +24,7 @@ export default defineConfig(async () => ({
         screenshot: path.resolve(__dirname, "pages/screenshot.html"),
         draw: path.resolve(__dirname, "pages/draw.html"),
         elementscan: path.resolve(__dirname, "pages/elementscan.html"),
+        pin: path.resolve(__dirname, "pages/pin.html"),
       },
     },
   },
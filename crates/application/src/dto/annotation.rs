@@ -0,0 +1,24 @@
+// 截图标注 DTOs
+use aumate_core_domain::annotation::AnnotationDocument;
+use serde::{Deserialize, Serialize};
+
+/// 绘制标注请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderAnnotationsRequest {
+    /// 原图 (PNG、JPEG 或 WebP 编码)
+    pub image: Vec<u8>,
+    pub document: AnnotationDocument,
+    /// 输出格式 ("png", "jpeg", "webp")，默认 PNG
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// 绘制标注响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderAnnotationsResponse {
+    /// 编码后的图像
+    pub data: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    pub format: String,
+}
@@ -0,0 +1,38 @@
+// 条码 DTOs
+use aumate_core_domain::barcode::QrCodeOptions;
+use aumate_core_shared::Rectangle;
+use serde::{Deserialize, Serialize};
+
+/// 识别图像中的条码请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanBarcodesRequest {
+    /// 图像 (PNG、JPEG 或 WebP 编码)
+    pub image: Vec<u8>,
+    /// 只识别该区域 (图像像素)，返回的位置仍相对于整张图像
+    #[serde(default)]
+    pub region: Option<Rectangle>,
+}
+
+/// 生成 QR 码请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerateQrCodeRequest {
+    pub text: String,
+    #[serde(default)]
+    pub options: QrCodeOptions,
+    /// 同时复制到剪贴板
+    #[serde(default)]
+    pub copy_to_clipboard: bool,
+    /// 输出格式 ("png", "jpeg", "webp")，默认 PNG
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// 生成 QR 码响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerateQrCodeResponse {
+    /// 编码后的图像
+    pub data: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    pub format: String,
+}
@@ -10,6 +10,12 @@ pub enum ClipboardContentDTO {
     Image { data: Vec<u8>, width: u32, height: u32, format: String },
     #[serde(rename = "files")]
     Files(Vec<String>),
+    #[serde(rename = "html")]
+    Html { html: String, alt_text: Option<String> },
+    #[serde(rename = "rtf")]
+    Rtf { rtf: String, alt_text: Option<String> },
+    #[serde(rename = "custom")]
+    Custom { mime: String, data: Vec<u8> },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,40 @@
+/// 剪贴板历史相关 DTO
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListClipboardHistoryRequest {
+    /// 全文搜索关键字
+    #[serde(default)]
+    pub search: Option<String>,
+    /// 条目类型 (`text`、`image`、`files`、`html`、`rtf`)
+    #[serde(default)]
+    pub kind: Option<String>,
+    #[serde(default)]
+    pub pinned_only: bool,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardHistoryEntryDTO {
+    pub id: i64,
+    pub kind: String,
+    pub preview: String,
+    pub source_app: Option<String>,
+    pub pinned: bool,
+    pub use_count: u32,
+    pub byte_size: u64,
+    pub image_width: Option<u32>,
+    pub image_height: Option<u32>,
+    /// Unix 毫秒
+    pub created_at: i64,
+    /// Unix 毫秒
+    pub last_used_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListClipboardHistoryResponse {
+    pub entries: Vec<ClipboardHistoryEntryDTO>,
+}
@@ -0,0 +1,16 @@
+// 取色器 DTOs
+use aumate_core_domain::color::{ColorFormats, PixelColor};
+use serde::{Deserialize, Serialize};
+
+/// 取到的颜色及其各种格式
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PickedColor {
+    pub color: PixelColor,
+    pub formats: ColorFormats,
+}
+
+impl From<PixelColor> for PickedColor {
+    fn from(color: PixelColor) -> Self {
+        Self { color, formats: color.formats() }
+    }
+}
@@ -0,0 +1,51 @@
+/// AI 对话相关 DTO
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListConversationsRequest {
+    /// 匹配标题或消息内容的关键字
+    #[serde(default)]
+    pub search: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationDTO {
+    pub id: i64,
+    pub title: String,
+    /// 最近一次回复使用的模型
+    pub model: Option<String>,
+    pub message_count: u32,
+    /// Unix 毫秒
+    pub created_at: i64,
+    /// Unix 毫秒
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationMessageDTO {
+    pub id: i64,
+    /// `user` 或 `assistant`
+    pub role: String,
+    pub content: String,
+    pub model: Option<String>,
+    /// Unix 毫秒
+    pub created_at: i64,
+    /// 生成回复的耗时 (毫秒)
+    pub duration_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListConversationsResponse {
+    pub conversations: Vec<ConversationDTO>,
+}
+
+/// 恢复对话所需的全部内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationDetailDTO {
+    pub conversation: ConversationDTO,
+    pub messages: Vec<ConversationMessageDTO>,
+}
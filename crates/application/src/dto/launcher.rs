@@ -0,0 +1,35 @@
+/// 启动器相关 DTO
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchLauncherRequest {
+    #[serde(default)]
+    pub query: String,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchResultDTO {
+    pub id: String,
+    /// `application`、`file` 或 `folder`
+    pub kind: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub path: String,
+    /// 是否有图标，图标通过 `get_launcher_icon` 读取
+    pub has_icon: bool,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchLauncherResponse {
+    pub results: Vec<LaunchResultDTO>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LauncherIndexStatsDTO {
+    pub total: usize,
+    pub updated: usize,
+    pub removed: usize,
+}
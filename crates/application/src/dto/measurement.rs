@@ -0,0 +1,23 @@
+// 测量 DTOs
+use aumate_core_domain::measurement::MeasurementSession;
+use aumate_core_shared::Rectangle;
+use serde::{Deserialize, Serialize};
+
+/// 测量会话信息，前端据此把 CSS 像素换算为截图像素
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeasurementSessionInfo {
+    /// 截图对应的屏幕区域
+    pub bounds: Rectangle,
+    /// 截图宽度 (物理像素)
+    pub width: u32,
+    /// 截图高度 (物理像素)
+    pub height: u32,
+    pub scale_factor: f64,
+}
+
+impl From<&MeasurementSession> for MeasurementSessionInfo {
+    fn from(session: &MeasurementSession) -> Self {
+        let (width, height) = session.image_size();
+        Self { bounds: session.bounds(), width, height, scale_factor: session.scale_factor() }
+    }
+}
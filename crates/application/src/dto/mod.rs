@@ -2,19 +2,37 @@
 //
 // 定义用例的请求和响应数据结构
 
+pub mod annotation;
+pub mod barcode;
 pub mod clipboard;
+pub mod clipboard_history;
+pub mod color_picker;
+pub mod conversation;
 pub mod element_scanner;
+pub mod launcher;
+pub mod measurement;
 pub mod monitor;
+pub mod redaction;
 pub mod screenshot;
+pub mod screenshot_library;
 pub mod scroll;
 pub mod storage;
 pub mod window;
 pub mod window_list;
 
+pub use annotation::*;
+pub use barcode::*;
 pub use clipboard::*;
+pub use clipboard_history::*;
+pub use color_picker::*;
+pub use conversation::*;
 pub use element_scanner::*;
+pub use launcher::*;
+pub use measurement::*;
 pub use monitor::*;
+pub use redaction::*;
 pub use screenshot::*;
+pub use screenshot_library::*;
 pub use scroll::*;
 pub use storage::*;
 pub use window::*;
@@ -1,4 +1,5 @@
 /// 监视器信息相关 DTO
+use aumate_core_domain::hdr::HdrInfo;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +12,9 @@ pub struct MonitorInfo {
     pub height: u32,
     pub scale_factor: f64,
     pub is_primary: bool,
+    /// 平台能报告时的 HDR 信息
+    #[serde(default)]
+    pub hdr_info: Option<HdrInfo>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
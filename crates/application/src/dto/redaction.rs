@@ -0,0 +1,40 @@
+// 截图隐私遮挡 DTOs
+use aumate_core_domain::redaction::{RedactionMethod, RedactionReport, SensitiveKind};
+use aumate_core_shared::Rectangle;
+use serde::{Deserialize, Serialize};
+
+/// 遮挡截图请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactScreenshotRequest {
+    /// 原图 (PNG、JPEG 或 WebP 编码)
+    pub image: Vec<u8>,
+    #[serde(default)]
+    pub method: RedactionMethod,
+    /// 手动指定的区域
+    #[serde(default)]
+    pub regions: Vec<Rectangle>,
+    /// 自动模式：先 OCR，再检测敏感文本
+    #[serde(default)]
+    pub auto: bool,
+    /// 自动模式使用的检测器，默认全部
+    #[serde(default)]
+    pub detectors: Option<Vec<SensitiveKind>>,
+    /// 自动模式下遮挡所有文本
+    #[serde(default)]
+    pub redact_all_text: bool,
+    /// 输出格式 ("png", "jpeg", "webp")，默认 PNG
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// 遮挡截图响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactScreenshotResponse {
+    /// 编码后的图像
+    pub data: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    pub format: String,
+    /// 被遮挡的内容和位置
+    pub report: RedactionReport,
+}
@@ -1,4 +1,5 @@
 // 截图相关 DTOs
+use aumate_core_domain::hdr::ToneMappingOperator;
 use aumate_core_shared::{MonitorId, Point, Rectangle, ScreenshotId};
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +14,9 @@ pub struct CaptureScreenRequest {
     pub quality: Option<u8>,
     /// 是否启用 HDR 校正
     pub hdr_correction: bool,
+    /// HDR 显示器上浮点截图的色调映射算子 (None 使用默认的 BT.2390)
+    #[serde(default)]
+    pub tone_mapping: Option<ToneMappingOperator>,
 }
 
 /// 捕获区域请求
@@ -26,6 +30,12 @@ pub struct CaptureRegionRequest {
     pub format: String,
     /// JPEG 质量
     pub quality: Option<u8>,
+    /// 是否启用 HDR 校正
+    #[serde(default)]
+    pub hdr_correction: bool,
+    /// HDR 显示器上浮点截图的色调映射算子 (None 使用默认的 BT.2390)
+    #[serde(default)]
+    pub tone_mapping: Option<ToneMappingOperator>,
 }
 
 /// 截图响应
@@ -44,12 +54,36 @@ pub struct CaptureResponse {
 }
 
 /// 保存截图请求
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// 未指定的选项使用设置中的 `screenshot`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SaveScreenshotRequest {
-    /// 截图 ID
-    pub id: ScreenshotId,
-    /// 保存路径
-    pub path: String,
+    /// 编码后的图像 (PNG、JPEG 等)
+    pub image: Vec<u8>,
+    /// 截图时的前台窗口标题，用于 `{title}`
+    #[serde(default)]
+    pub window_title: Option<String>,
+    /// 截图时的前台应用名，用于 `{app}`
+    #[serde(default)]
+    pub app_name: Option<String>,
+    /// 监视器 ID，用于 `{monitor}`
+    #[serde(default)]
+    pub monitor_id: Option<String>,
+    /// 保存目录
+    #[serde(default)]
+    pub folder: Option<String>,
+    /// 文件名模板
+    #[serde(default)]
+    pub filename_pattern: Option<String>,
+    /// 图像格式 ("png", "jpg", "webp", "avif", "bmp")
+    #[serde(default)]
+    pub format: Option<String>,
+    /// 有损格式的质量 (1-100)
+    #[serde(default)]
+    pub quality: Option<u8>,
+    /// 保存后复制到剪贴板
+    #[serde(default)]
+    pub copy_to_clipboard: Option<bool>,
 }
 
 /// 保存截图响应
@@ -59,4 +93,8 @@ pub struct SaveScreenshotResponse {
     pub path: String,
     /// 文件大小（字节）
     pub size: u64,
+    /// 图像格式
+    pub format: String,
+    /// 是否已复制到剪贴板
+    pub copied_to_clipboard: bool,
 }
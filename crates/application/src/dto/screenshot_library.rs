@@ -0,0 +1,52 @@
+/// 截图库相关 DTO
+use aumate_core_domain::screenshot::CaptureRegion;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListScreenshotsRequest {
+    /// 按窗口标题、应用名、标签、备注和 OCR 文本搜索
+    #[serde(default)]
+    pub search: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenshotRecordDTO {
+    pub id: i64,
+    pub file_path: String,
+    pub format: String,
+    pub width: u32,
+    pub height: u32,
+    pub byte_size: u64,
+    pub monitor_id: Option<String>,
+    pub window_title: Option<String>,
+    pub app_name: Option<String>,
+    pub region: Option<CaptureRegion>,
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+    /// 是否已有 OCR 文本
+    pub has_ocr_text: bool,
+    /// Unix 毫秒
+    pub captured_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListScreenshotsResponse {
+    pub screenshots: Vec<ScreenshotRecordDTO>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateScreenshotRequest {
+    pub id: i64,
+    /// 替换全部标签，`None` 表示不修改
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// 新备注，空字符串表示清除，`None` 表示不修改
+    #[serde(default)]
+    pub note: Option<String>,
+}
@@ -45,3 +45,13 @@ pub struct ResizeWindowRequest {
     /// 增量
     pub delta: i32,
 }
+
+/// 贴图请求
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PinImageRequest {
+    /// 编码后的图像 (PNG、JPEG 等)
+    pub image: Vec<u8>,
+    /// 窗口左上角 (物理像素)，未指定时居中于第一个显示器的工作区
+    #[serde(default)]
+    pub position: Option<Point>,
+}
@@ -5,6 +5,9 @@
 pub mod dto;
 pub mod use_cases;
 
+#[cfg(test)]
+mod test_support;
+
 // Re-export for convenience
 pub use dto::*;
 pub use use_cases::*;
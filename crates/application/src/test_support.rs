@@ -0,0 +1,315 @@
+// 用例测试共用的 Port mock
+
+use async_trait::async_trait;
+use aumate_core_domain::annotation::AnnotationDocument;
+use aumate_core_domain::hdr::{HdrImage, HdrInfo, HdrScreenshot, ToneMapper, ToneMappingOperator};
+use aumate_core_domain::image::{ColorFormat, Image};
+use aumate_core_domain::redaction::{
+    RedactedItem, RedactionReason, RedactionReport, RedactionRequest,
+};
+use aumate_core_shared::{InfrastructureError, MonitorId, Point, Rectangle, WindowId};
+use aumate_core_traits::clipboard::{ClipboardContent, ClipboardPort, ClipboardType};
+use aumate_core_traits::screenshot::{
+    CaptureMetadata, CaptureOptions, CaptureTarget, HdrCorrectionAlgorithm, ImageFormat, Monitor,
+    Screenshot,
+};
+use aumate_core_traits::{ImageProcessingPort, ScreenCapturePort};
+use std::sync::Mutex;
+
+pub type InfraResult<T> = Result<T, InfrastructureError>;
+
+/// 填满 `width` x `height` 的 RGBA 图像
+pub fn solid_image(width: u32, height: u32, pixel: [u8; 4]) -> Image {
+    let data = pixel.repeat((width * height) as usize);
+    Image::new(data, width, height, ColorFormat::RGBA).unwrap()
+}
+
+/// `MockImageProcessing` 的编码输出
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MockEncoding {
+    /// 原始像素
+    #[default]
+    Pixels,
+    /// "格式:质量"，未指定质量时为 0
+    Quality,
+    /// "格式:宽x高"，PNG 使用真实的 IHDR 头以便写入剪贴板
+    Header,
+}
+
+/// 图像处理 mock
+///
+/// - 解码默认把数据的前两个字节当作宽和高生成白图，`with_decoded` 固定解码结果
+/// - 缩放输出目标尺寸的黑图，绘制标注时把第一个像素涂黑
+/// - 打码时每个手动区域和每行文本各报告一项
+/// - 色调映射使用领域层的实现
+#[derive(Default)]
+pub struct MockImageProcessing {
+    encoding: MockEncoding,
+    decoded: Option<Image>,
+}
+
+impl MockImageProcessing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_encoding(mut self, encoding: MockEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn with_decoded(mut self, image: Image) -> Self {
+        self.decoded = Some(image);
+        self
+    }
+}
+
+impl ImageProcessingPort for MockImageProcessing {
+    fn encode(&self, image: &Image, format: ImageFormat) -> InfraResult<Vec<u8>> {
+        self.encode_with_quality(image, format, None)
+    }
+
+    fn encode_with_quality(
+        &self,
+        image: &Image,
+        format: ImageFormat,
+        quality: Option<u8>,
+    ) -> InfraResult<Vec<u8>> {
+        let data = match self.encoding {
+            MockEncoding::Pixels => image.data.clone(),
+            MockEncoding::Quality => {
+                format!("{}:{}", format.extension(), quality.unwrap_or(0)).into_bytes()
+            }
+            MockEncoding::Header if format == ImageFormat::Png => {
+                let mut png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n', 0, 0, 0, 13];
+                png.extend_from_slice(b"IHDR");
+                png.extend_from_slice(&image.width.to_be_bytes());
+                png.extend_from_slice(&image.height.to_be_bytes());
+                png
+            }
+            MockEncoding::Header => {
+                format!("{}:{}x{}", format.extension(), image.width, image.height).into_bytes()
+            }
+        };
+        Ok(data)
+    }
+
+    fn decode(&self, data: &[u8]) -> InfraResult<Image> {
+        if let Some(image) = &self.decoded {
+            return Ok(image.clone());
+        }
+        match data {
+            [width, height, ..] => Ok(solid_image(*width as u32, *height as u32, [255; 4])),
+            _ => Err(InfrastructureError::ImageProcessingFailed("empty".to_string())),
+        }
+    }
+
+    fn overlay(&self, base: &Image, _: &Image, _: Point) -> InfraResult<Image> {
+        Ok(base.clone())
+    }
+
+    fn correct_hdr(&self, image: &Image, _: HdrCorrectionAlgorithm) -> InfraResult<Image> {
+        Ok(image.clone())
+    }
+
+    fn tone_map(
+        &self,
+        image: &HdrImage,
+        operator: ToneMappingOperator,
+        hdr_info: &HdrInfo,
+    ) -> InfraResult<Image> {
+        image
+            .tone_map(&ToneMapper::new(operator, hdr_info))
+            .map_err(InfrastructureError::ImageProcessingFailed)
+    }
+
+    fn resize(&self, _: &Image, width: u32, height: u32) -> InfraResult<Image> {
+        Ok(solid_image(width, height, [0; 4]))
+    }
+
+    fn render_annotations(&self, image: &Image, _: &AnnotationDocument) -> InfraResult<Image> {
+        let mut image = image.clone();
+        if let Some(pixel) = image.data.get_mut(..4) {
+            pixel.copy_from_slice(&[0, 0, 0, 255]);
+        }
+        Ok(image)
+    }
+
+    fn redact(
+        &self,
+        image: &Image,
+        request: &RedactionRequest,
+    ) -> InfraResult<(Image, RedactionReport)> {
+        let manual = request.regions.iter().map(|&bounds| RedactedItem {
+            reason: RedactionReason::Manual,
+            bounds,
+            text: None,
+        });
+        let text = request.text.iter().map(|line| RedactedItem {
+            reason: RedactionReason::Email,
+            bounds: line.bounds,
+            text: Some(line.text.clone()),
+        });
+        let items = manual.chain(text).collect();
+        Ok((image.clone(), RedactionReport { items }))
+    }
+}
+
+/// `id` 号显示器，非主显示器且没有 HDR 信息
+pub fn monitor(id: u32, rect: Rectangle, scale_factor: f64) -> Monitor {
+    Monitor {
+        id: MonitorId::new(id),
+        name: format!("Monitor {}", id),
+        rect,
+        scale_factor,
+        is_primary: false,
+        hdr_info: None,
+    }
+}
+
+/// 屏幕捕获 mock
+///
+/// 截图大小为显示器或区域的逻辑尺寸乘以缩放比例，区域按左上角所在的显示器换算。
+/// 像素由 `with_pixels` 按显示器和物理像素坐标决定，默认全白。浮点捕获返回
+/// `with_hdr` 设置的图像，未设置时返回 `PlatformNotSupported`。每次 8 位捕获的
+/// 目标和选项都会被记录
+pub struct MockScreenCapture {
+    monitors: Vec<Monitor>,
+    hdr: Option<HdrImage>,
+    pixels: fn(&MonitorId, u32, u32) -> [u8; 4],
+    captures: Mutex<Vec<(CaptureTarget, CaptureOptions)>>,
+}
+
+impl MockScreenCapture {
+    pub fn new(monitors: Vec<Monitor>) -> Self {
+        Self { monitors, hdr: None, pixels: |_, _, _| [255; 4], captures: Mutex::default() }
+    }
+
+    pub fn with_hdr(mut self, image: HdrImage) -> Self {
+        self.hdr = Some(image);
+        self
+    }
+
+    pub fn with_pixels(mut self, pixels: fn(&MonitorId, u32, u32) -> [u8; 4]) -> Self {
+        self.pixels = pixels;
+        self
+    }
+
+    /// 已执行的 8 位捕获
+    pub fn captures(&self) -> Vec<(CaptureTarget, CaptureOptions)> {
+        self.captures.lock().unwrap().clone()
+    }
+
+    fn monitor(&self, id: &MonitorId) -> InfraResult<Monitor> {
+        self.monitors
+            .iter()
+            .find(|monitor| &monitor.id == id)
+            .cloned()
+            .ok_or_else(|| InfrastructureError::CaptureFailed(format!("No monitor {:?}", id)))
+    }
+
+    fn primary(&self) -> InfraResult<Monitor> {
+        self.monitors
+            .iter()
+            .find(|monitor| monitor.is_primary)
+            .or(self.monitors.first())
+            .cloned()
+            .ok_or_else(|| InfrastructureError::CaptureFailed("No monitor".to_string()))
+    }
+}
+
+#[async_trait]
+impl ScreenCapturePort for MockScreenCapture {
+    async fn capture(
+        &self,
+        target: CaptureTarget,
+        options: CaptureOptions,
+    ) -> InfraResult<Screenshot> {
+        let (monitor, width, height) = match &target {
+            CaptureTarget::Region(region) => {
+                let corner = Point::new(region.min_x(), region.min_y());
+                let monitor = match self.monitors.iter().find(|m| m.rect.contains_point(&corner)) {
+                    Some(monitor) => monitor.clone(),
+                    None => self.primary()?,
+                };
+                (monitor, region.width(), region.height())
+            }
+            CaptureTarget::Monitor(id) => {
+                let monitor = self.monitor(id)?;
+                let (width, height) = (monitor.rect.width(), monitor.rect.height());
+                (monitor, width, height)
+            }
+            _ => {
+                let monitor = self.primary()?;
+                let (width, height) = (monitor.rect.width(), monitor.rect.height());
+                (monitor, width, height)
+            }
+        };
+        self.captures.lock().unwrap().push((target, options));
+
+        let scale = |v: u32| (v as f64 * monitor.scale_factor).round() as u32;
+        let (width, height) = (scale(width), scale(height));
+        let mut data = Vec::with_capacity((width * height * 4) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                data.extend_from_slice(&(self.pixels)(&monitor.id, x, y));
+            }
+        }
+        let image = Image::new(data, width, height, ColorFormat::RGBA)
+            .map_err(|e| InfrastructureError::CaptureFailed(e.to_string()))?;
+        Ok(Screenshot::new(image, CaptureMetadata::default()))
+    }
+
+    async fn capture_hdr(&self, _: CaptureTarget, _: CaptureOptions) -> InfraResult<HdrScreenshot> {
+        let image = self.hdr.clone().ok_or(InfrastructureError::PlatformNotSupported)?;
+        Ok(HdrScreenshot::new(image, CaptureMetadata::default()))
+    }
+
+    async fn get_monitors(&self) -> InfraResult<Vec<Monitor>> {
+        Ok(self.monitors.clone())
+    }
+
+    async fn get_current_monitor(&self) -> InfraResult<Monitor> {
+        self.primary()
+    }
+
+    async fn get_focused_window(&self) -> InfraResult<WindowId> {
+        Err(InfrastructureError::PlatformNotSupported)
+    }
+}
+
+/// 只能写入的剪贴板，记录写入的内容
+#[derive(Default)]
+pub struct MockClipboard {
+    written: Mutex<Vec<ClipboardContent>>,
+}
+
+impl MockClipboard {
+    pub fn written(&self) -> Vec<ClipboardContent> {
+        self.written.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl ClipboardPort for MockClipboard {
+    async fn read(&self) -> InfraResult<ClipboardContent> {
+        Err(InfrastructureError::ClipboardFailed("Empty".to_string()))
+    }
+
+    async fn read_format(&self, _format: ClipboardType) -> InfraResult<ClipboardContent> {
+        self.read().await
+    }
+
+    async fn write(&self, content: ClipboardContent) -> InfraResult<()> {
+        self.written.lock().unwrap().push(content);
+        Ok(())
+    }
+
+    async fn clear(&self) -> InfraResult<()> {
+        Ok(())
+    }
+
+    async fn get_available_types(&self) -> InfraResult<Vec<ClipboardType>> {
+        Ok(Vec::new())
+    }
+}
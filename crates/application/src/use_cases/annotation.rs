@@ -0,0 +1,87 @@
+// 截图标注 Use Cases
+//
+// 在 Rust 侧把标注文档绘制到截图上，编辑器保存和自动化流程共用
+use crate::dto::annotation::{RenderAnnotationsRequest, RenderAnnotationsResponse};
+use aumate_core_shared::ApplicationError;
+use aumate_core_traits::ImageProcessingPort;
+use aumate_core_traits::screenshot::ImageFormat;
+use std::sync::Arc;
+
+/// 绘制标注 Use Case
+pub struct RenderAnnotationsUseCase {
+    image_processing: Arc<dyn ImageProcessingPort>,
+}
+
+impl RenderAnnotationsUseCase {
+    pub fn new(image_processing: Arc<dyn ImageProcessingPort>) -> Self {
+        Self { image_processing }
+    }
+
+    pub async fn execute(
+        &self,
+        request: RenderAnnotationsRequest,
+    ) -> Result<RenderAnnotationsResponse, ApplicationError> {
+        let format = ImageFormat::from_str(request.format.as_deref().unwrap_or("png"))?;
+        request.document.validate()?;
+        log::info!(
+            "RenderAnnotationsUseCase: rendering {} annotations",
+            request.document.annotations.len()
+        );
+
+        let image = self.image_processing.decode(&request.image)?;
+        let image = if request.document.is_empty() {
+            image
+        } else {
+            self.image_processing.render_annotations(&image, &request.document)?
+        };
+        let data = self.image_processing.encode(&image, format)?;
+
+        Ok(RenderAnnotationsResponse {
+            data,
+            width: image.width,
+            height: image.height,
+            format: format.extension().to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{MockImageProcessing, solid_image};
+    use aumate_core_domain::annotation::{Annotation, AnnotationDocument, AnnotationShape};
+
+    fn request(document: AnnotationDocument, format: Option<&str>) -> RenderAnnotationsRequest {
+        RenderAnnotationsRequest { image: Vec::new(), document, format: format.map(str::to_string) }
+    }
+
+    #[tokio::test]
+    async fn test_render_annotations() {
+        // 解码得到 2x1 白图，绘制标注时第一个像素被涂黑
+        let image_processing = MockImageProcessing::new().with_decoded(solid_image(2, 1, [255; 4]));
+        let use_case = RenderAnnotationsUseCase::new(Arc::new(image_processing));
+        let step =
+            Annotation::new(AnnotationShape::Step { x: 1.0, y: 1.0, number: 1, radius: 8.0 });
+
+        let response =
+            use_case.execute(request(AnnotationDocument::new(vec![step]), None)).await.unwrap();
+        assert_eq!(response.format, "png");
+        assert_eq!((response.width, response.height), (2, 1));
+        assert_eq!(response.data, [0, 0, 0, 255, 255, 255, 255, 255]);
+
+        // 空文档原样输出
+        let response =
+            use_case.execute(request(AnnotationDocument::default(), Some("jpeg"))).await.unwrap();
+        assert_eq!(response.format, "jpg");
+        assert_eq!(response.data, [255; 8]);
+
+        let invalid =
+            Annotation::new(AnnotationShape::Step { x: 1.0, y: 1.0, number: 1, radius: 0.0 });
+        assert!(
+            use_case.execute(request(AnnotationDocument::new(vec![invalid]), None)).await.is_err()
+        );
+        assert!(
+            use_case.execute(request(AnnotationDocument::default(), Some("tiff"))).await.is_err()
+        );
+    }
+}
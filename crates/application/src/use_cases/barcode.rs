@@ -0,0 +1,225 @@
+// 条码 Use Cases
+//
+// 识别截图或屏幕区域中的 QR 码、DataMatrix 和一维码，
+// 以及把文本生成为 QR 码并复制到剪贴板
+use crate::dto::barcode::{GenerateQrCodeRequest, GenerateQrCodeResponse, ScanBarcodesRequest};
+use aumate_core_domain::barcode::DecodedBarcode;
+use aumate_core_shared::{ApplicationError, Rectangle};
+use aumate_core_traits::clipboard::{ClipboardContent, ClipboardImage};
+use aumate_core_traits::screenshot::{CaptureOptions, CaptureTarget, ImageFormat};
+use aumate_core_traits::{BarcodePort, ClipboardPort, ImageProcessingPort, ScreenCapturePort};
+use std::sync::Arc;
+
+/// 识别条码 Use Case
+pub struct ScanBarcodesUseCase {
+    barcode: Arc<dyn BarcodePort>,
+    image_processing: Arc<dyn ImageProcessingPort>,
+    screen_capture: Arc<dyn ScreenCapturePort + Send + Sync>,
+}
+
+impl ScanBarcodesUseCase {
+    pub fn new(
+        barcode: Arc<dyn BarcodePort>,
+        image_processing: Arc<dyn ImageProcessingPort>,
+        screen_capture: Arc<dyn ScreenCapturePort + Send + Sync>,
+    ) -> Self {
+        Self { barcode, image_processing, screen_capture }
+    }
+
+    /// 识别已编码图像中的条码，位置为图像像素
+    pub fn scan_image(
+        &self,
+        request: ScanBarcodesRequest,
+    ) -> Result<Vec<DecodedBarcode>, ApplicationError> {
+        let image = self.image_processing.decode(&request.image)?;
+        let barcodes = self.barcode.decode(&image, request.region)?;
+        log::info!("ScanBarcodesUseCase: found {} barcodes in image", barcodes.len());
+        Ok(barcodes)
+    }
+
+    /// 截取屏幕区域并识别条码，位置为屏幕坐标
+    ///
+    /// `region` 为空时截取光标所在的显示器
+    pub async fn scan_screen(
+        &self,
+        region: Option<Rectangle>,
+    ) -> Result<Vec<DecodedBarcode>, ApplicationError> {
+        let (target, bounds) = match region {
+            Some(region) => (CaptureTarget::Region(region), region),
+            None => {
+                let monitor = self.screen_capture.get_current_monitor().await?;
+                (CaptureTarget::Monitor(monitor.id), monitor.rect)
+            }
+        };
+
+        let options = CaptureOptions { cursor_visible: false, ..Default::default() };
+        let image = self.screen_capture.capture(target, options).await?.image;
+        let barcodes: Vec<DecodedBarcode> = self
+            .barcode
+            .decode(&image, None)?
+            .into_iter()
+            .map(|barcode| barcode.map_to(image.width, image.height, &bounds))
+            .collect();
+        log::info!("ScanBarcodesUseCase: found {} barcodes on screen", barcodes.len());
+        Ok(barcodes)
+    }
+}
+
+/// 生成 QR 码 Use Case
+pub struct GenerateQrCodeUseCase {
+    barcode: Arc<dyn BarcodePort>,
+    image_processing: Arc<dyn ImageProcessingPort>,
+    clipboard: Arc<dyn ClipboardPort>,
+}
+
+impl GenerateQrCodeUseCase {
+    pub fn new(
+        barcode: Arc<dyn BarcodePort>,
+        image_processing: Arc<dyn ImageProcessingPort>,
+        clipboard: Arc<dyn ClipboardPort>,
+    ) -> Self {
+        Self { barcode, image_processing, clipboard }
+    }
+
+    pub async fn execute(
+        &self,
+        request: GenerateQrCodeRequest,
+    ) -> Result<GenerateQrCodeResponse, ApplicationError> {
+        if request.text.is_empty() {
+            return Err(ApplicationError::InvalidRequest("QR code text is empty".to_string()));
+        }
+        let format = ImageFormat::from_str(request.format.as_deref().unwrap_or("png"))?;
+        request.options.validate()?;
+
+        let image = self.barcode.generate_qr(&request.text, &request.options)?;
+        if request.copy_to_clipboard {
+            let rgba = image.to_rgba().map_err(ApplicationError::ProcessingFailed)?;
+            let clipboard_image = ClipboardImage::rgba(rgba.data, rgba.width, rgba.height)
+                .map_err(ApplicationError::ClipboardFailed)?;
+            self.clipboard.write(ClipboardContent::Image(clipboard_image)).await?;
+        }
+
+        log::info!(
+            "GenerateQrCodeUseCase: generated {}x{} QR code, copied: {}",
+            image.width,
+            image.height,
+            request.copy_to_clipboard
+        );
+        Ok(GenerateQrCodeResponse {
+            data: self.image_processing.encode(&image, format)?,
+            width: image.width,
+            height: image.height,
+            format: format.extension().to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{
+        InfraResult, MockClipboard, MockImageProcessing, MockScreenCapture, monitor, solid_image,
+    };
+    use aumate_core_domain::barcode::{BarcodeFormat, QrCodeOptions};
+    use aumate_core_domain::image::Image;
+    use aumate_core_shared::Point;
+    use std::sync::Mutex;
+
+    fn image(width: u32, height: u32) -> Image {
+        solid_image(width, height, [255; 4])
+    }
+
+    /// 每张图像报告一个 QR 码，位于图像中央四分之一
+    #[derive(Default)]
+    struct MockBarcode {
+        regions: Mutex<Vec<Option<Rectangle>>>,
+    }
+
+    impl BarcodePort for MockBarcode {
+        fn decode(
+            &self,
+            image: &Image,
+            region: Option<Rectangle>,
+        ) -> InfraResult<Vec<DecodedBarcode>> {
+            self.regions.lock().unwrap().push(region);
+            let (width, height) = (image.width as i32, image.height as i32);
+            let bounds =
+                Rectangle::from_bounds(width / 4, height / 4, width * 3 / 4, height * 3 / 4);
+            Ok(vec![DecodedBarcode {
+                format: BarcodeFormat::QrCode,
+                text: "otpauth://totp/Aumate?secret=JBSWY3DP".to_string(),
+                bounds,
+                points: vec![Point::new(bounds.min_x(), bounds.min_y())],
+            }])
+        }
+
+        fn generate_qr(&self, _: &str, options: &QrCodeOptions) -> InfraResult<Image> {
+            Ok(image(21 * options.module_size, 21 * options.module_size))
+        }
+    }
+
+    fn image_processing() -> MockImageProcessing {
+        MockImageProcessing::new().with_decoded(image(40, 20))
+    }
+
+    #[tokio::test]
+    async fn test_scan_image_and_screen() {
+        let barcode = Arc::new(MockBarcode::default());
+        // 逻辑尺寸 100x50 的显示器，截图为 2 倍分辨率
+        let screen_capture = Arc::new(MockScreenCapture::new(vec![monitor(
+            1,
+            Rectangle::from_bounds(-100, 0, 0, 50),
+            2.0,
+        )]));
+        let scan = ScanBarcodesUseCase::new(
+            barcode.clone(),
+            Arc::new(image_processing()),
+            screen_capture.clone(),
+        );
+
+        let region = Rectangle::from_bounds(0, 0, 20, 10);
+        let found = scan
+            .scan_image(ScanBarcodesRequest { image: vec![1, 2, 3], region: Some(region) })
+            .unwrap();
+        assert_eq!(found[0].bounds, Rectangle::from_bounds(10, 5, 30, 15));
+        assert_eq!(*barcode.regions.lock().unwrap(), [Some(region)]);
+
+        // 截图像素按 2 倍分辨率换算回屏幕坐标
+        let found = scan.scan_screen(Some(Rectangle::from_bounds(10, 10, 50, 30))).await.unwrap();
+        assert_eq!(found[0].bounds, Rectangle::from_bounds(20, 15, 40, 25));
+        assert_eq!(found[0].points, [Point::new(20, 15)]);
+
+        let found = scan.scan_screen(None).await.unwrap();
+        assert_eq!(found[0].bounds, Rectangle::from_bounds(-75, 13, -25, 38));
+        assert!(screen_capture.captures().iter().all(|(_, options)| !options.cursor_visible));
+    }
+
+    #[tokio::test]
+    async fn test_generate_qr_code() {
+        let clipboard = Arc::new(MockClipboard::default());
+        let generate = GenerateQrCodeUseCase::new(
+            Arc::new(MockBarcode::default()),
+            Arc::new(image_processing()),
+            clipboard.clone(),
+        );
+        let request =
+            |text: &str, module_size: u32, copy_to_clipboard: bool| GenerateQrCodeRequest {
+                text: text.to_string(),
+                options: QrCodeOptions { module_size, ..Default::default() },
+                copy_to_clipboard,
+                format: None,
+            };
+
+        let response = generate.execute(request("hello", 2, false)).await.unwrap();
+        assert_eq!((response.width, response.height), (42, 42));
+        assert_eq!(response.format, "png");
+        assert!(clipboard.written().is_empty());
+
+        generate.execute(request("hello", 2, true)).await.unwrap();
+        let copied = clipboard.written();
+        assert!(matches!(&copied[..], [ClipboardContent::Image(image)] if image.width == 42));
+
+        assert!(generate.execute(request("", 2, false)).await.is_err());
+        assert!(generate.execute(request("hello", 0, false)).await.is_err());
+    }
+}
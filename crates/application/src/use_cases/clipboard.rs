@@ -1,11 +1,70 @@
 /// 剪贴板管理 Use Cases
 use crate::dto::clipboard::{ClipboardContentDTO, ClipboardImageResponse, ReadClipboardResponse};
-use aumate_core_domain::image::{ColorFormat, Image, ImageMetadata, ImageSource};
+use aumate_core_domain::clipboard::paths_from_uri_list;
 use aumate_core_shared::{DomainError, Result};
-use aumate_core_traits::clipboard::ClipboardContent;
+use aumate_core_traits::clipboard::{ClipboardContent, ClipboardImage, ClipboardImageFormat};
 use aumate_core_traits::clipboard::{ClipboardPort, ClipboardType};
+use std::path::PathBuf;
 use std::sync::Arc;
 
+/// 剪贴板图像转为 DTO
+fn image_to_dto(image: ClipboardImage) -> ClipboardImageResponse {
+    ClipboardImageResponse {
+        data: image.data,
+        width: image.width,
+        height: image.height,
+        format: image.format.as_str().to_string(),
+    }
+}
+
+/// DTO 转为剪贴板图像
+///
+/// PNG 的宽高从数据中读取，RGBA 数据必须与给定宽高一致
+fn image_from_dto(data: Vec<u8>, width: u32, height: u32, format: &str) -> Result<ClipboardImage> {
+    let format = ClipboardImageFormat::parse(format)
+        .ok_or_else(|| DomainError::UnsupportedImageFormat(format.to_string()))?;
+    let image = match format {
+        ClipboardImageFormat::Rgba8 => ClipboardImage::rgba(data, width, height),
+        ClipboardImageFormat::Png => ClipboardImage::png(data),
+    };
+    image.map_err(DomainError::ValidationFailed)
+}
+
+/// 解析文件列表，每项可以是路径或 `file://` URI
+fn paths_from_dto(entries: Vec<String>) -> Result<Vec<PathBuf>> {
+    let paths: Vec<PathBuf> = entries
+        .into_iter()
+        .flat_map(|entry| {
+            if entry.starts_with("file://") {
+                paths_from_uri_list(&entry)
+            } else {
+                vec![PathBuf::from(entry)]
+            }
+        })
+        .collect();
+    if paths.is_empty() {
+        return Err(DomainError::ValidationFailed("File list is empty".to_string()));
+    }
+    Ok(paths)
+}
+
+/// 剪贴板内容转为 DTO
+pub(crate) fn content_to_dto(content: ClipboardContent) -> ClipboardContentDTO {
+    match content {
+        ClipboardContent::Text(text) => ClipboardContentDTO::Text(text),
+        ClipboardContent::Image(image) => {
+            let ClipboardImageResponse { data, width, height, format } = image_to_dto(image);
+            ClipboardContentDTO::Image { data, width, height, format }
+        }
+        ClipboardContent::Files(paths) => ClipboardContentDTO::Files(
+            paths.into_iter().map(|p| p.to_string_lossy().to_string()).collect(),
+        ),
+        ClipboardContent::Html { html, alt_text } => ClipboardContentDTO::Html { html, alt_text },
+        ClipboardContent::Rtf { rtf, alt_text } => ClipboardContentDTO::Rtf { rtf, alt_text },
+        ClipboardContent::Custom { mime, data } => ClipboardContentDTO::Custom { mime, data },
+    }
+}
+
 /// 读取剪贴板 Use Case
 pub struct ReadClipboardUseCase<P: ClipboardPort> {
     clipboard: Arc<P>,
@@ -25,24 +84,20 @@ impl<P: ClipboardPort> ReadClipboardUseCase<P> {
             .await
             .map_err(|e| DomainError::ValidationFailed(e.to_string()))?;
 
-        let dto = match content {
-            ClipboardContent::Text(text) => ClipboardContentDTO::Text(text),
-            ClipboardContent::Image(data) => {
-                // 假设图像是 RGBA 格式，需要根据实际情况解析
-                // TODO: 从图像数据中提取宽高和格式
-                ClipboardContentDTO::Image {
-                    data,
-                    width: 0, // Placeholder
-                    height: 0,
-                    format: "rgba".to_string(),
-                }
-            }
-            ClipboardContent::Files(paths) => ClipboardContentDTO::Files(
-                paths.into_iter().map(|p| p.to_string_lossy().to_string()).collect(),
-            ),
-        };
+        Ok(ReadClipboardResponse { content: content_to_dto(content) })
+    }
+
+    /// 以指定格式读取，`format` 为类型名 (`text`、`html`、`rtf` 等) 或 MIME 类型
+    pub async fn execute_format(&self, format: &str) -> Result<ReadClipboardResponse> {
+        log::info!("ReadClipboardUseCase: reading clipboard as {}", format);
+
+        let content = self
+            .clipboard
+            .read_format(ClipboardType::parse(format))
+            .await
+            .map_err(|e| DomainError::ValidationFailed(e.to_string()))?;
 
-        Ok(ReadClipboardResponse { content: dto })
+        Ok(ReadClipboardResponse { content: content_to_dto(content) })
     }
 }
 
@@ -66,10 +121,7 @@ impl<P: ClipboardPort> ReadClipboardImageUseCase<P> {
             .map_err(|e| DomainError::ValidationFailed(e.to_string()))?;
 
         match content {
-            ClipboardContent::Image(data) => {
-                // TODO: 解析图像数据获取宽高
-                Ok(ClipboardImageResponse { data, width: 0, height: 0, format: "rgba".to_string() })
-            }
+            ClipboardContent::Image(image) => Ok(image_to_dto(image)),
             _ => Err(DomainError::ValidationFailed("Clipboard does not contain image".to_string())),
         }
     }
@@ -91,12 +143,18 @@ impl<P: ClipboardPort> WriteClipboardUseCase<P> {
         let domain_content = match content {
             ClipboardContentDTO::Text(text) => ClipboardContent::Text(text),
             ClipboardContentDTO::Image { data, width, height, format } => {
-                // 将图像数据包装为 ClipboardContent::Image
-                // 简化：直接传递原始数据
-                ClipboardContent::Image(data)
+                ClipboardContent::Image(image_from_dto(data, width, height, &format)?)
             }
-            ClipboardContentDTO::Files(paths) => {
-                ClipboardContent::Files(paths.into_iter().map(std::path::PathBuf::from).collect())
+            ClipboardContentDTO::Files(paths) => ClipboardContent::Files(paths_from_dto(paths)?),
+            ClipboardContentDTO::Html { html, alt_text } => {
+                ClipboardContent::Html { html, alt_text }
+            }
+            ClipboardContentDTO::Rtf { rtf, alt_text } => ClipboardContent::Rtf { rtf, alt_text },
+            ClipboardContentDTO::Custom { mime, data } => {
+                if mime.trim().is_empty() {
+                    return Err(DomainError::ValidationFailed("MIME type is empty".to_string()));
+                }
+                ClipboardContent::Custom { mime, data }
             }
         };
 
@@ -119,26 +177,31 @@ impl<P: ClipboardPort> WriteClipboardImageUseCase<P> {
         Self { clipboard }
     }
 
+    /// 写入 RGBA 图像
     pub async fn execute(&self, data: Vec<u8>, width: u32, height: u32) -> Result<()> {
         log::info!("WriteClipboardImageUseCase: writing image {}x{} to clipboard", width, height);
 
-        // 创建 Image 对象
-        let image = Image::with_metadata(
-            data.clone(),
-            width,
-            height,
-            ColorFormat::RGBA,
-            ImageMetadata::new(ImageSource::Clipboard),
-        )
-        .map_err(|e| DomainError::InvalidImageData)?;
-
-        // 写入剪贴板
+        let image = ClipboardImage::rgba(data, width, height)
+            .map_err(|_| DomainError::InvalidDimensions(width, height))?;
+        self.write(image).await
+    }
+
+    /// 写入 PNG 图像
+    pub async fn execute_png(&self, data: Vec<u8>) -> Result<()> {
+        let image = ClipboardImage::png(data).map_err(|_| DomainError::InvalidImageData)?;
+        log::info!(
+            "WriteClipboardImageUseCase: writing PNG {}x{} to clipboard",
+            image.width,
+            image.height
+        );
+        self.write(image).await
+    }
+
+    async fn write(&self, image: ClipboardImage) -> Result<()> {
         self.clipboard
-            .write(ClipboardContent::Image(data))
+            .write(ClipboardContent::Image(image))
             .await
-            .map_err(|e| DomainError::ValidationFailed(e.to_string()))?;
-
-        Ok(())
+            .map_err(|e| DomainError::ValidationFailed(e.to_string()))
     }
 }
 
@@ -148,8 +211,23 @@ mod tests {
     use async_trait::async_trait;
     use aumate_core_shared::InfrastructureError;
 
+    use std::sync::Mutex;
+
+    #[derive(Default)]
     struct MockClipboard {
         content: Option<ClipboardContent>,
+        written: Mutex<Vec<ClipboardContent>>,
+        requested: Mutex<Vec<ClipboardType>>,
+    }
+
+    impl MockClipboard {
+        fn with_content(content: ClipboardContent) -> Self {
+            Self { content: Some(content), ..Default::default() }
+        }
+
+        fn last_written(&self) -> ClipboardContent {
+            self.written.lock().unwrap().last().cloned().expect("nothing written")
+        }
     }
 
     #[async_trait]
@@ -160,10 +238,19 @@ mod tests {
                 .ok_or_else(|| InfrastructureError::ClipboardFailed("Empty".to_string()))
         }
 
+        async fn read_format(
+            &self,
+            format: ClipboardType,
+        ) -> std::result::Result<ClipboardContent, InfrastructureError> {
+            self.requested.lock().unwrap().push(format);
+            self.read().await
+        }
+
         async fn write(
             &self,
-            _content: ClipboardContent,
+            content: ClipboardContent,
         ) -> std::result::Result<(), InfrastructureError> {
+            self.written.lock().unwrap().push(content);
             Ok(())
         }
 
@@ -180,8 +267,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_read_text_from_clipboard() {
-        let mock =
-            Arc::new(MockClipboard { content: Some(ClipboardContent::Text("Hello".to_string())) });
+        let mock = Arc::new(MockClipboard::with_content(ClipboardContent::Text("Hello".into())));
         let use_case = ReadClipboardUseCase::new(mock);
 
         let result = use_case.execute().await.unwrap();
@@ -190,4 +276,133 @@ mod tests {
             _ => panic!("Expected text content"),
         }
     }
+
+    #[tokio::test]
+    async fn test_read_image_reports_dimensions() {
+        let image = ClipboardImage::rgba(vec![9; 4 * 2 * 4], 4, 2).unwrap();
+        let mock = Arc::new(MockClipboard::with_content(ClipboardContent::Image(image)));
+
+        let response = ReadClipboardImageUseCase::new(mock.clone()).execute().await.unwrap();
+        assert_eq!((response.width, response.height), (4, 2));
+        assert_eq!(response.format, "rgba");
+
+        let result = ReadClipboardUseCase::new(mock).execute().await.unwrap();
+        match result.content {
+            ClipboardContentDTO::Image { width, height, .. } => assert_eq!((width, height), (4, 2)),
+            _ => panic!("Expected image content"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_image_roundtrip() {
+        let mock = Arc::new(MockClipboard::default());
+        let use_case = WriteClipboardUseCase::new(mock.clone());
+
+        let dto = ClipboardContentDTO::Image {
+            data: vec![1; 3 * 3 * 4],
+            width: 3,
+            height: 3,
+            format: "rgba".to_string(),
+        };
+        use_case.execute(dto).await.unwrap();
+        let written = mock.last_written();
+        assert_eq!(written.as_image().map(|i| (i.width, i.height)), Some((3, 3)));
+
+        // 写入的内容可以原样读回
+        let mock_read = Arc::new(MockClipboard::with_content(written));
+        let read = ReadClipboardUseCase::new(mock_read).execute().await.unwrap();
+        match read.content {
+            ClipboardContentDTO::Image { data, width, height, format } => {
+                assert_eq!(data, vec![1; 3 * 3 * 4]);
+                assert_eq!((width, height, format.as_str()), (3, 3, "rgba"));
+            }
+            _ => panic!("Expected image content"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_image_rejects_bad_data() {
+        let mock = Arc::new(MockClipboard::default());
+        let use_case = WriteClipboardUseCase::new(mock.clone());
+
+        let wrong_size = ClipboardContentDTO::Image {
+            data: vec![0; 5],
+            width: 3,
+            height: 3,
+            format: "rgba".into(),
+        };
+        assert!(use_case.execute(wrong_size).await.is_err());
+
+        let unknown = ClipboardContentDTO::Image {
+            data: vec![0; 4],
+            width: 1,
+            height: 1,
+            format: "bmp".into(),
+        };
+        assert!(matches!(
+            use_case.execute(unknown).await,
+            Err(DomainError::UnsupportedImageFormat(_))
+        ));
+
+        let image_use_case = WriteClipboardImageUseCase::new(mock.clone());
+        assert!(image_use_case.execute_png(vec![0; 32]).await.is_err());
+        assert!(mock.written.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_write_files_accepts_paths_and_uris() {
+        let mock = Arc::new(MockClipboard::default());
+        let use_case = WriteClipboardUseCase::new(mock.clone());
+
+        let files = ClipboardContentDTO::Files(vec![
+            "/tmp/report.pdf".to_string(),
+            "file:///tmp/shot%201.png".to_string(),
+        ]);
+        use_case.execute(files).await.unwrap();
+        assert_eq!(
+            mock.last_written().as_files().unwrap(),
+            &vec![PathBuf::from("/tmp/report.pdf"), PathBuf::from("/tmp/shot 1.png")]
+        );
+
+        assert!(use_case.execute(ClipboardContentDTO::Files(Vec::new())).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_format_and_rich_content() {
+        let html = ClipboardContent::html("<i>x</i>", Some("x".to_string()));
+        let mock = Arc::new(MockClipboard::with_content(html));
+        let use_case = ReadClipboardUseCase::new(mock.clone());
+
+        let result = use_case.execute_format("html").await.unwrap();
+        match result.content {
+            ClipboardContentDTO::Html { html, alt_text } => {
+                assert_eq!(html, "<i>x</i>");
+                assert_eq!(alt_text.as_deref(), Some("x"));
+            }
+            _ => panic!("Expected HTML content"),
+        }
+        use_case.execute_format("application/x-test").await.unwrap();
+        assert_eq!(
+            *mock.requested.lock().unwrap(),
+            vec![ClipboardType::Html, ClipboardType::Custom("application/x-test".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_rich_content() {
+        let mock = Arc::new(MockClipboard::default());
+        let use_case = WriteClipboardUseCase::new(mock.clone());
+
+        let rtf = ClipboardContentDTO::Rtf { rtf: r"{\rtf1 x}".to_string(), alt_text: None };
+        use_case.execute(rtf).await.unwrap();
+        assert!(matches!(mock.last_written(), ClipboardContent::Rtf { .. }));
+
+        let custom =
+            ClipboardContentDTO::Custom { mime: "application/x-test".into(), data: vec![7] };
+        use_case.execute(custom).await.unwrap();
+        assert_eq!(mock.last_written().plain_text(), None);
+
+        let unnamed = ClipboardContentDTO::Custom { mime: " ".into(), data: vec![7] };
+        assert!(use_case.execute(unnamed).await.is_err());
+    }
 }
@@ -0,0 +1,396 @@
+/// 剪贴板历史 Use Cases
+use crate::dto::clipboard::ClipboardContentDTO;
+use crate::dto::clipboard_history::{
+    ClipboardHistoryEntryDTO, ListClipboardHistoryRequest, ListClipboardHistoryResponse,
+};
+use crate::use_cases::clipboard::content_to_dto;
+use aumate_core_domain::clipboard_history::{
+    ClipboardHistoryEntry, ClipboardHistoryKind, ClipboardHistoryQuery,
+};
+use aumate_core_shared::{DomainError, Result};
+use aumate_core_traits::clipboard::{ClipboardContent, ClipboardPort};
+use aumate_core_traits::clipboard_history::ClipboardHistoryPort;
+use std::sync::Arc;
+
+/// 单次查询的最大条目数
+const MAX_LIST_LIMIT: usize = 500;
+
+fn entry_to_dto(entry: ClipboardHistoryEntry) -> ClipboardHistoryEntryDTO {
+    ClipboardHistoryEntryDTO {
+        id: entry.id,
+        kind: entry.kind.as_str().to_string(),
+        preview: entry.preview,
+        source_app: entry.source_app,
+        pinned: entry.pinned,
+        use_count: entry.use_count,
+        byte_size: entry.byte_size,
+        image_width: entry.image_width,
+        image_height: entry.image_height,
+        created_at: entry.created_at,
+        last_used_at: entry.last_used_at,
+    }
+}
+
+fn not_found(id: i64) -> DomainError {
+    DomainError::ValidationFailed(format!("Clipboard history entry {} not found", id))
+}
+
+/// 读取条目内容，条目不存在时报错
+async fn entry_content<H: ClipboardHistoryPort>(history: &H, id: i64) -> Result<ClipboardContent> {
+    history
+        .get_content(id)
+        .await
+        .map_err(|e| DomainError::ValidationFailed(e.to_string()))?
+        .ok_or_else(|| not_found(id))
+}
+
+/// 列出 / 搜索剪贴板历史 Use Case
+pub struct ListClipboardHistoryUseCase<H: ClipboardHistoryPort> {
+    history: Arc<H>,
+}
+
+impl<H: ClipboardHistoryPort> ListClipboardHistoryUseCase<H> {
+    pub fn new(history: Arc<H>) -> Self {
+        Self { history }
+    }
+
+    pub async fn execute(
+        &self,
+        request: ListClipboardHistoryRequest,
+    ) -> Result<ListClipboardHistoryResponse> {
+        log::info!("ListClipboardHistoryUseCase: search={:?}", request.search);
+
+        let mut query = ClipboardHistoryQuery::default().with_pinned_only(request.pinned_only);
+        if let Some(search) = request.search {
+            query = query.with_search(search);
+        }
+        if let Some(kind) = request.kind {
+            let kind = ClipboardHistoryKind::parse(&kind).ok_or_else(|| {
+                DomainError::ValidationFailed(format!("Unknown clipboard history kind: {}", kind))
+            })?;
+            query = query.with_kind(kind);
+        }
+        if let Some(limit) = request.limit {
+            query = query.with_limit(limit.min(MAX_LIST_LIMIT));
+        }
+        if let Some(offset) = request.offset {
+            query = query.with_offset(offset);
+        }
+
+        let entries = self
+            .history
+            .list(&query)
+            .await
+            .map_err(|e| DomainError::ValidationFailed(e.to_string()))?;
+
+        Ok(ListClipboardHistoryResponse {
+            entries: entries.into_iter().map(entry_to_dto).collect(),
+        })
+    }
+}
+
+/// 读取历史条目内容 Use Case
+pub struct GetClipboardHistoryContentUseCase<H: ClipboardHistoryPort> {
+    history: Arc<H>,
+}
+
+impl<H: ClipboardHistoryPort> GetClipboardHistoryContentUseCase<H> {
+    pub fn new(history: Arc<H>) -> Self {
+        Self { history }
+    }
+
+    pub async fn execute(&self, id: i64) -> Result<ClipboardContentDTO> {
+        Ok(content_to_dto(entry_content(&*self.history, id).await?))
+    }
+}
+
+/// 将历史条目重新写入剪贴板 Use Case
+pub struct PasteClipboardHistoryUseCase<H: ClipboardHistoryPort, C: ClipboardPort> {
+    history: Arc<H>,
+    clipboard: Arc<C>,
+}
+
+impl<H: ClipboardHistoryPort, C: ClipboardPort> PasteClipboardHistoryUseCase<H, C> {
+    pub fn new(history: Arc<H>, clipboard: Arc<C>) -> Self {
+        Self { history, clipboard }
+    }
+
+    pub async fn execute(&self, id: i64) -> Result<()> {
+        log::info!("PasteClipboardHistoryUseCase: pasting entry {}", id);
+
+        let content = entry_content(&*self.history, id).await?;
+        self.clipboard
+            .write(content)
+            .await
+            .map_err(|e| DomainError::ValidationFailed(e.to_string()))?;
+        self.history.touch(id).await.map_err(|e| DomainError::ValidationFailed(e.to_string()))
+    }
+}
+
+/// 置顶 / 取消置顶历史条目 Use Case
+pub struct PinClipboardHistoryUseCase<H: ClipboardHistoryPort> {
+    history: Arc<H>,
+}
+
+impl<H: ClipboardHistoryPort> PinClipboardHistoryUseCase<H> {
+    pub fn new(history: Arc<H>) -> Self {
+        Self { history }
+    }
+
+    pub async fn execute(&self, id: i64, pinned: bool) -> Result<()> {
+        log::info!("PinClipboardHistoryUseCase: entry {} pinned={}", id, pinned);
+
+        let updated = self
+            .history
+            .set_pinned(id, pinned)
+            .await
+            .map_err(|e| DomainError::ValidationFailed(e.to_string()))?;
+        if !updated {
+            return Err(not_found(id));
+        }
+        Ok(())
+    }
+}
+
+/// 删除历史条目 Use Case
+pub struct DeleteClipboardHistoryUseCase<H: ClipboardHistoryPort> {
+    history: Arc<H>,
+}
+
+impl<H: ClipboardHistoryPort> DeleteClipboardHistoryUseCase<H> {
+    pub fn new(history: Arc<H>) -> Self {
+        Self { history }
+    }
+
+    pub async fn execute(&self, id: i64) -> Result<()> {
+        log::info!("DeleteClipboardHistoryUseCase: deleting entry {}", id);
+
+        let deleted = self
+            .history
+            .delete(id)
+            .await
+            .map_err(|e| DomainError::ValidationFailed(e.to_string()))?;
+        if !deleted {
+            return Err(not_found(id));
+        }
+        Ok(())
+    }
+
+    /// 清空历史，返回删除的条目数
+    pub async fn execute_clear(&self, keep_pinned: bool) -> Result<usize> {
+        log::info!("DeleteClipboardHistoryUseCase: clearing history (keep_pinned={})", keep_pinned);
+
+        self.history
+            .clear(keep_pinned)
+            .await
+            .map_err(|e| DomainError::ValidationFailed(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use aumate_core_domain::clipboard_history::{ClipboardHistoryRetention, clipboard_preview};
+    use aumate_core_shared::InfrastructureError;
+    use aumate_core_traits::clipboard::ClipboardType;
+    use std::sync::Mutex;
+
+    type InfraResult<T> = std::result::Result<T, InfrastructureError>;
+
+    #[derive(Default)]
+    struct MockHistory {
+        entries: Mutex<Vec<(ClipboardHistoryEntry, ClipboardContent)>>,
+    }
+
+    impl MockHistory {
+        fn with_texts(texts: &[&str]) -> Self {
+            let history = Self::default();
+            for (i, text) in texts.iter().enumerate() {
+                let content = ClipboardContent::text(*text);
+                let entry = ClipboardHistoryEntry {
+                    id: i as i64 + 1,
+                    kind: ClipboardHistoryKind::Text,
+                    preview: clipboard_preview(&content),
+                    content_hash: text.to_string(),
+                    source_app: None,
+                    pinned: false,
+                    use_count: 1,
+                    byte_size: text.len() as u64,
+                    image_width: None,
+                    image_height: None,
+                    created_at: 0,
+                    last_used_at: 0,
+                };
+                history.entries.lock().unwrap().push((entry, content));
+            }
+            history
+        }
+
+        fn entry(&self, id: i64) -> Option<ClipboardHistoryEntry> {
+            let entries = self.entries.lock().unwrap();
+            entries.iter().find(|(entry, _)| entry.id == id).map(|(entry, _)| entry.clone())
+        }
+    }
+
+    #[async_trait]
+    impl ClipboardHistoryPort for MockHistory {
+        async fn record(
+            &self,
+            _content: &ClipboardContent,
+            _source_app: Option<&str>,
+        ) -> InfraResult<Option<ClipboardHistoryEntry>> {
+            Ok(None)
+        }
+
+        async fn list(
+            &self,
+            query: &ClipboardHistoryQuery,
+        ) -> InfraResult<Vec<ClipboardHistoryEntry>> {
+            let entries = self.entries.lock().unwrap();
+            Ok(entries
+                .iter()
+                .map(|(entry, _)| entry)
+                .filter(|entry| query.kind.is_none_or(|kind| entry.kind == kind))
+                .filter(|entry| !query.pinned_only || entry.pinned)
+                .filter(|entry| {
+                    query.search.as_deref().is_none_or(|search| entry.preview.contains(search))
+                })
+                .skip(query.offset)
+                .take(query.limit)
+                .cloned()
+                .collect())
+        }
+
+        async fn get(&self, id: i64) -> InfraResult<Option<ClipboardHistoryEntry>> {
+            Ok(self.entry(id))
+        }
+
+        async fn get_content(&self, id: i64) -> InfraResult<Option<ClipboardContent>> {
+            let entries = self.entries.lock().unwrap();
+            Ok(entries.iter().find(|(entry, _)| entry.id == id).map(|(_, c)| c.clone()))
+        }
+
+        async fn touch(&self, id: i64) -> InfraResult<()> {
+            let mut entries = self.entries.lock().unwrap();
+            if let Some((entry, _)) = entries.iter_mut().find(|(entry, _)| entry.id == id) {
+                entry.use_count += 1;
+            }
+            Ok(())
+        }
+
+        async fn set_pinned(&self, id: i64, pinned: bool) -> InfraResult<bool> {
+            let mut entries = self.entries.lock().unwrap();
+            let entry = entries.iter_mut().find(|(entry, _)| entry.id == id);
+            Ok(entry.map(|(entry, _)| entry.pinned = pinned).is_some())
+        }
+
+        async fn delete(&self, id: i64) -> InfraResult<bool> {
+            let mut entries = self.entries.lock().unwrap();
+            let before = entries.len();
+            entries.retain(|(entry, _)| entry.id != id);
+            Ok(entries.len() != before)
+        }
+
+        async fn clear(&self, keep_pinned: bool) -> InfraResult<usize> {
+            let mut entries = self.entries.lock().unwrap();
+            let before = entries.len();
+            entries.retain(|(entry, _)| keep_pinned && entry.pinned);
+            Ok(before - entries.len())
+        }
+
+        async fn apply_retention(
+            &self,
+            _retention: &ClipboardHistoryRetention,
+        ) -> InfraResult<usize> {
+            Ok(0)
+        }
+    }
+
+    #[derive(Default)]
+    struct MockClipboard {
+        written: Mutex<Vec<ClipboardContent>>,
+    }
+
+    #[async_trait]
+    impl ClipboardPort for MockClipboard {
+        async fn read(&self) -> InfraResult<ClipboardContent> {
+            Err(InfrastructureError::ClipboardFailed("Empty".to_string()))
+        }
+
+        async fn read_format(&self, _format: ClipboardType) -> InfraResult<ClipboardContent> {
+            self.read().await
+        }
+
+        async fn write(&self, content: ClipboardContent) -> InfraResult<()> {
+            self.written.lock().unwrap().push(content);
+            Ok(())
+        }
+
+        async fn clear(&self) -> InfraResult<()> {
+            Ok(())
+        }
+
+        async fn get_available_types(&self) -> InfraResult<Vec<ClipboardType>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_and_search() {
+        let history = Arc::new(MockHistory::with_texts(&["alpha", "beta", "alphabet"]));
+        let use_case = ListClipboardHistoryUseCase::new(history);
+
+        let all = use_case.execute(ListClipboardHistoryRequest::default()).await.unwrap();
+        assert_eq!(all.entries.len(), 3);
+        assert_eq!(all.entries[0].kind, "text");
+
+        let request = ListClipboardHistoryRequest {
+            search: Some("alpha".to_string()),
+            limit: Some(1),
+            ..Default::default()
+        };
+        let found = use_case.execute(request).await.unwrap();
+        assert_eq!(
+            found.entries.iter().map(|e| e.preview.as_str()).collect::<Vec<_>>(),
+            vec!["alpha"]
+        );
+
+        let request =
+            ListClipboardHistoryRequest { kind: Some("video".to_string()), ..Default::default() };
+        assert!(use_case.execute(request).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_paste_writes_clipboard_and_touches_entry() {
+        let history = Arc::new(MockHistory::with_texts(&["hello"]));
+        let clipboard = Arc::new(MockClipboard::default());
+        let use_case = PasteClipboardHistoryUseCase::new(history.clone(), clipboard.clone());
+
+        use_case.execute(1).await.unwrap();
+        assert_eq!(clipboard.written.lock().unwrap()[0].as_text().unwrap(), "hello");
+        assert_eq!(history.entry(1).unwrap().use_count, 2);
+
+        assert!(use_case.execute(42).await.is_err());
+        assert_eq!(clipboard.written.lock().unwrap().len(), 1);
+
+        let content = GetClipboardHistoryContentUseCase::new(history).execute(1).await.unwrap();
+        assert!(matches!(content, ClipboardContentDTO::Text(text) if text == "hello"));
+    }
+
+    #[tokio::test]
+    async fn test_pin_and_delete() {
+        let history = Arc::new(MockHistory::with_texts(&["a", "b", "c"]));
+
+        let pin = PinClipboardHistoryUseCase::new(history.clone());
+        pin.execute(2, true).await.unwrap();
+        assert!(history.entry(2).unwrap().pinned);
+        assert!(pin.execute(42, true).await.is_err());
+
+        let delete = DeleteClipboardHistoryUseCase::new(history.clone());
+        delete.execute(1).await.unwrap();
+        assert!(delete.execute(1).await.is_err());
+        assert_eq!(delete.execute_clear(true).await.unwrap(), 1);
+        assert!(history.entry(2).is_some());
+    }
+}
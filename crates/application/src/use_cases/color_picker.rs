@@ -0,0 +1,187 @@
+// 取色器 Use Cases
+//
+// 开始取色时每台显示器截图一次，之后光标移动只读截图，
+// 可以按帧率取样放大镜网格而不反复截屏
+use crate::dto::color_picker::PickedColor;
+use aumate_core_domain::color::{
+    ColorHistory, ColorPickerSession, ColorSample, DEFAULT_MAGNIFIER_RADIUS,
+};
+use aumate_core_shared::{Point, UseCaseError};
+use aumate_core_traits::ScreenCapturePort;
+use aumate_core_traits::screenshot::{CaptureOptions, CaptureTarget};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// 取色器 Use Case
+pub struct ColorPickerUseCase {
+    screen_capture: Arc<dyn ScreenCapturePort + Send + Sync>,
+    session: Mutex<Option<ColorPickerSession>>,
+    /// 最近一次取样的位置，点击取色时默认使用
+    last_position: Mutex<Option<Point>>,
+    history: Mutex<ColorHistory>,
+}
+
+impl ColorPickerUseCase {
+    pub fn new(screen_capture: Arc<dyn ScreenCapturePort + Send + Sync>) -> Self {
+        Self {
+            screen_capture,
+            session: Mutex::new(None),
+            last_position: Mutex::new(None),
+            history: Mutex::new(ColorHistory::default()),
+        }
+    }
+
+    /// 截取所有显示器，开始新的取色会话
+    ///
+    /// 已有会话时替换为新的截图
+    pub async fn start_session(&self) -> Result<(), UseCaseError> {
+        let options = CaptureOptions { cursor_visible: false, ..Default::default() };
+        let mut session = ColorPickerSession::new();
+        for monitor in self.screen_capture.get_monitors().await? {
+            let screenshot = self
+                .screen_capture
+                .capture(CaptureTarget::Monitor(monitor.id.clone()), options.clone())
+                .await?;
+            session = session.with_capture(monitor.rect, screenshot.image);
+        }
+        if session.is_empty() {
+            return Err(UseCaseError::CaptureFailed("No monitors available".to_string()));
+        }
+
+        log::info!("ColorPickerUseCase: session started");
+        *lock(&self.session) = Some(session);
+        *lock(&self.last_position) = None;
+        Ok(())
+    }
+
+    pub fn end_session(&self) {
+        *lock(&self.session) = None;
+        *lock(&self.last_position) = None;
+    }
+
+    pub fn is_active(&self) -> bool {
+        lock(&self.session).is_some()
+    }
+
+    /// 取 `position` 处的颜色和放大镜网格
+    ///
+    /// 只读会话截图，可以在每次光标移动时调用
+    pub fn sample(
+        &self,
+        position: Point,
+        radius: Option<u32>,
+    ) -> Result<ColorSample, UseCaseError> {
+        let session = lock(&self.session);
+        let session = session.as_ref().ok_or_else(no_session)?;
+        let sample = session.sample(position, radius.unwrap_or(DEFAULT_MAGNIFIER_RADIUS))?;
+        *lock(&self.last_position) = Some(position);
+        Ok(sample)
+    }
+
+    /// 确认取色并加入最近颜色
+    ///
+    /// `position` 为空时使用最近一次取样的位置
+    pub fn pick(&self, position: Option<Point>) -> Result<PickedColor, UseCaseError> {
+        let position = position.or(*lock(&self.last_position)).ok_or_else(|| {
+            UseCaseError::InvalidRequest("No position has been sampled yet".to_string())
+        })?;
+        let session = lock(&self.session);
+        let session = session.as_ref().ok_or_else(no_session)?;
+        let color = session.sample(position, 0)?.color;
+
+        lock(&self.history).push(color);
+        Ok(color.into())
+    }
+
+    /// 最近取过的颜色，最新的在前
+    pub fn history(&self) -> Vec<PickedColor> {
+        lock(&self.history).colors().iter().map(|&color| color.into()).collect()
+    }
+
+    pub fn clear_history(&self) {
+        lock(&self.history).clear();
+    }
+}
+
+fn no_session() -> UseCaseError {
+    UseCaseError::InvalidRequest("Color picker session is not started".to_string())
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{MockScreenCapture, monitor};
+    use aumate_core_domain::color::PixelColor;
+    use aumate_core_shared::{MonitorId, Rectangle};
+
+    /// 两台 2x2 逻辑尺寸的显示器，截图为 4x4 物理像素，
+    /// 左边显示器左半红右半绿，右边显示器全蓝
+    fn screen_capture() -> Arc<MockScreenCapture> {
+        let mut left = monitor(0, Rectangle::from_xywh(0, 0, 2, 2).unwrap(), 2.0);
+        left.is_primary = true;
+        let right = monitor(1, Rectangle::from_xywh(2, 0, 2, 2).unwrap(), 2.0);
+        let screen_capture =
+            MockScreenCapture::new(vec![left, right]).with_pixels(|id, x, _| {
+                match (*id == MonitorId::new(0), x < 2) {
+                    (true, true) => [255, 0, 0, 255],
+                    (true, false) => [0, 255, 0, 255],
+                    (false, _) => [0, 0, 255, 255],
+                }
+            });
+        Arc::new(screen_capture)
+    }
+
+    const RED: PixelColor = PixelColor::new(255, 0, 0, 255);
+    const GREEN: PixelColor = PixelColor::new(0, 255, 0, 255);
+    const BLUE: PixelColor = PixelColor::new(0, 0, 255, 255);
+
+    #[tokio::test]
+    async fn test_sample_reuses_session_capture() {
+        let screen_capture = screen_capture();
+        let picker = ColorPickerUseCase::new(screen_capture.clone());
+        assert!(picker.sample(Point::new(0, 0), None).is_err());
+
+        picker.start_session().await.unwrap();
+        assert!(picker.is_active());
+
+        let sample = picker.sample(Point::new(0, 0), Some(1)).unwrap();
+        assert_eq!(sample.color, RED);
+        assert_eq!(sample.formats.hex, "#FF0000");
+        assert_eq!(sample.magnifier.size, 3);
+        assert_eq!(picker.sample(Point::new(1, 1), None).unwrap().color, GREEN);
+        assert_eq!(picker.sample(Point::new(3, 0), None).unwrap().color, BLUE);
+        assert!(picker.sample(Point::new(4, 0), None).is_err());
+
+        // 每台显示器只截图一次
+        let captures = screen_capture.captures();
+        assert_eq!(captures.len(), 2);
+        assert!(captures.iter().all(|(target, options)| {
+            matches!(target, CaptureTarget::Monitor(_)) && !options.cursor_visible
+        }));
+
+        picker.end_session();
+        assert!(!picker.is_active());
+        assert!(picker.pick(Some(Point::new(0, 0))).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pick_updates_history() {
+        let picker = ColorPickerUseCase::new(screen_capture());
+        picker.start_session().await.unwrap();
+        assert!(picker.pick(None).is_err());
+
+        picker.sample(Point::new(3, 1), None).unwrap();
+        assert_eq!(picker.pick(None).unwrap().color, BLUE);
+        assert_eq!(picker.pick(Some(Point::new(0, 0))).unwrap().formats.rgb, "rgb(255, 0, 0)");
+        assert_eq!(picker.pick(Some(Point::new(2, 0))).unwrap().color, BLUE);
+
+        let history: Vec<_> = picker.history().into_iter().map(|c| c.color).collect();
+        assert_eq!(history, [BLUE, RED]);
+
+        picker.clear_history();
+        assert!(picker.history().is_empty());
+    }
+}
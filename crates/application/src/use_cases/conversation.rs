@@ -0,0 +1,504 @@
+// AI 对话 Use Cases
+//
+// 对话保存在本地数据库中，发送消息时历史从数据库读取，回复结束后自动保存
+use crate::dto::conversation::{
+    ConversationDTO, ConversationDetailDTO, ConversationMessageDTO, ListConversationsRequest,
+    ListConversationsResponse,
+};
+use crate::use_cases::llm::resolve_api_key;
+use crate::use_cases::settings::SettingsSecrets;
+use async_trait::async_trait;
+use aumate_core_domain::conversation::{
+    DEFAULT_CONVERSATION_TITLE, NewConversationMessage, conversation_markdown,
+};
+use aumate_core_domain::llm::ChatMessage;
+use aumate_core_shared::{ApplicationError, DomainError, InfrastructureError, UseCaseError};
+use aumate_core_traits::conversation::{
+    Conversation, ConversationMessage, ConversationQuery, ConversationRepositoryPort,
+};
+use aumate_core_traits::llm::{ChatCompletionRequest, ChatCompletionStream, LlmPort};
+use aumate_core_traits::settings::SettingsStoragePort;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// 单次查询的最大对话数
+const MAX_LIST_LIMIT: usize = 200;
+
+fn conversation_to_dto(conversation: Conversation) -> ConversationDTO {
+    ConversationDTO {
+        id: conversation.id,
+        title: conversation.title,
+        model: conversation.model,
+        message_count: conversation.message_count,
+        created_at: conversation.created_at,
+        updated_at: conversation.updated_at,
+    }
+}
+
+fn message_to_dto(message: ConversationMessage) -> ConversationMessageDTO {
+    ConversationMessageDTO {
+        id: message.id,
+        role: message.role.as_str().to_string(),
+        content: message.content,
+        model: message.model,
+        created_at: message.created_at,
+        duration_ms: message.duration_ms,
+    }
+}
+
+fn not_found(id: i64) -> ApplicationError {
+    DomainError::ValidationFailed(format!("Conversation {} not found", id)).into()
+}
+
+/// 读取对话，不存在时报错
+async fn get_conversation<C: ConversationRepositoryPort>(
+    conversations: &C,
+    id: i64,
+) -> Result<Conversation, ApplicationError> {
+    conversations.get(id).await?.ok_or_else(|| not_found(id))
+}
+
+/// 列出 / 搜索对话 Use Case
+pub struct ListConversationsUseCase<C: ConversationRepositoryPort> {
+    conversations: Arc<C>,
+}
+
+impl<C: ConversationRepositoryPort> ListConversationsUseCase<C> {
+    pub fn new(conversations: Arc<C>) -> Self {
+        Self { conversations }
+    }
+
+    pub async fn execute(
+        &self,
+        request: ListConversationsRequest,
+    ) -> Result<ListConversationsResponse, ApplicationError> {
+        log::info!("ListConversationsUseCase: search={:?}", request.search);
+
+        let mut query = ConversationQuery::default();
+        if let Some(search) = request.search {
+            query = query.with_search(search);
+        }
+        if let Some(limit) = request.limit {
+            query = query.with_limit(limit.min(MAX_LIST_LIMIT));
+        }
+        if let Some(offset) = request.offset {
+            query = query.with_offset(offset);
+        }
+
+        let conversations = self.conversations.list(&query).await?;
+        Ok(ListConversationsResponse {
+            conversations: conversations.into_iter().map(conversation_to_dto).collect(),
+        })
+    }
+}
+
+/// 恢复对话 Use Case
+pub struct GetConversationUseCase<C: ConversationRepositoryPort> {
+    conversations: Arc<C>,
+}
+
+impl<C: ConversationRepositoryPort> GetConversationUseCase<C> {
+    pub fn new(conversations: Arc<C>) -> Self {
+        Self { conversations }
+    }
+
+    pub async fn execute(&self, id: i64) -> Result<ConversationDetailDTO, ApplicationError> {
+        let conversation = get_conversation(&*self.conversations, id).await?;
+        let messages = self.conversations.messages(id).await?;
+        Ok(ConversationDetailDTO {
+            conversation: conversation_to_dto(conversation),
+            messages: messages.into_iter().map(message_to_dto).collect(),
+        })
+    }
+}
+
+/// 重命名对话 Use Case
+pub struct RenameConversationUseCase<C: ConversationRepositoryPort> {
+    conversations: Arc<C>,
+}
+
+impl<C: ConversationRepositoryPort> RenameConversationUseCase<C> {
+    pub fn new(conversations: Arc<C>) -> Self {
+        Self { conversations }
+    }
+
+    pub async fn execute(&self, id: i64, title: &str) -> Result<(), ApplicationError> {
+        log::info!("RenameConversationUseCase: renaming conversation {}", id);
+
+        let title = title.trim();
+        if title.is_empty() {
+            return Err(DomainError::ValidationFailed("Title must not be empty".to_string()).into());
+        }
+        if !self.conversations.rename(id, title).await? {
+            return Err(not_found(id));
+        }
+        Ok(())
+    }
+}
+
+/// 删除对话 Use Case
+pub struct DeleteConversationUseCase<C: ConversationRepositoryPort> {
+    conversations: Arc<C>,
+}
+
+impl<C: ConversationRepositoryPort> DeleteConversationUseCase<C> {
+    pub fn new(conversations: Arc<C>) -> Self {
+        Self { conversations }
+    }
+
+    pub async fn execute(&self, id: i64) -> Result<(), ApplicationError> {
+        log::info!("DeleteConversationUseCase: deleting conversation {}", id);
+
+        if !self.conversations.delete(id).await? {
+            return Err(not_found(id));
+        }
+        Ok(())
+    }
+
+    /// 删除全部对话，返回删除的数量
+    pub async fn execute_clear(&self) -> Result<usize, ApplicationError> {
+        log::info!("DeleteConversationUseCase: clearing conversations");
+
+        Ok(self.conversations.clear().await?)
+    }
+}
+
+/// 导出对话为 Markdown Use Case
+pub struct ExportConversationUseCase<C: ConversationRepositoryPort> {
+    conversations: Arc<C>,
+}
+
+impl<C: ConversationRepositoryPort> ExportConversationUseCase<C> {
+    pub fn new(conversations: Arc<C>) -> Self {
+        Self { conversations }
+    }
+
+    pub async fn execute(&self, id: i64) -> Result<String, ApplicationError> {
+        log::info!("ExportConversationUseCase: exporting conversation {}", id);
+
+        let conversation = get_conversation(&*self.conversations, id).await?;
+        let messages = self.conversations.messages(id).await?;
+        Ok(conversation_markdown(&conversation, &messages))
+    }
+}
+
+/// 发送对话消息的结果
+pub struct ConversationReply {
+    /// 消息所在的对话，未指定对话时为新建的对话
+    pub conversation: ConversationDTO,
+    /// 回复的增量文本流，结束时回复会保存到对话中
+    pub stream: Box<dyn ChatCompletionStream>,
+}
+
+/// 在对话中发送消息 Use Case
+///
+/// 模型接受请求后才保存用户消息 (以及新建的对话)，请求失败时不留下记录。
+/// 历史按 `max_history_messages` 截断后发送给模型
+pub struct SendConversationMessageUseCase<
+    P: SettingsStoragePort,
+    L: LlmPort,
+    C: ConversationRepositoryPort,
+> {
+    storage: Arc<P>,
+    secrets: Arc<SettingsSecrets>,
+    llm: Arc<L>,
+    conversations: Arc<C>,
+}
+
+impl<P, L, C> SendConversationMessageUseCase<P, L, C>
+where
+    P: SettingsStoragePort,
+    L: LlmPort,
+    C: ConversationRepositoryPort + 'static,
+{
+    pub fn new(
+        storage: Arc<P>,
+        secrets: Arc<SettingsSecrets>,
+        llm: Arc<L>,
+        conversations: Arc<C>,
+    ) -> Self {
+        Self { storage, secrets, llm, conversations }
+    }
+
+    pub async fn execute(
+        &self,
+        conversation_id: Option<i64>,
+        content: &str,
+    ) -> Result<ConversationReply, ApplicationError> {
+        log::info!("SendConversationMessageUseCase: conversation={:?}", conversation_id);
+
+        if content.trim().is_empty() {
+            return Err(UseCaseError::InvalidRequest("No messages to send.".to_string()));
+        }
+
+        let settings = self.storage.load().await?.ai_dialogue;
+        let api_key = resolve_api_key(&self.secrets, &settings.api_key, "AI Dialogue").await?;
+
+        let mut history = match conversation_id {
+            Some(id) => {
+                let id = get_conversation(&*self.conversations, id).await?.id;
+                self.conversations
+                    .messages(id)
+                    .await?
+                    .iter()
+                    .map(ConversationMessage::to_chat_message)
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+        history.push(ChatMessage::user(content));
+        let request = ChatCompletionRequest::dialogue(&settings, api_key, &history);
+        let model = request.endpoint.model.clone();
+        let stream = self.llm.stream_chat(request).await?;
+
+        let conversation_id = match conversation_id {
+            Some(id) => id,
+            None => self.conversations.create(DEFAULT_CONVERSATION_TITLE).await?.id,
+        };
+        self.conversations
+            .append(conversation_id, NewConversationMessage::user(content))
+            .await?
+            .ok_or_else(|| not_found(conversation_id))?;
+
+        let conversation = get_conversation(&*self.conversations, conversation_id).await?;
+        Ok(ConversationReply {
+            conversation: conversation_to_dto(conversation),
+            stream: Box::new(RecordingStream {
+                inner: stream,
+                conversations: self.conversations.clone(),
+                conversation_id,
+                model,
+                started: Instant::now(),
+                content: String::new(),
+            }),
+        })
+    }
+}
+
+/// 回复正常结束时保存到对话中；出错或被取消的回复不保存
+struct RecordingStream<C: ConversationRepositoryPort> {
+    inner: Box<dyn ChatCompletionStream>,
+    conversations: Arc<C>,
+    conversation_id: i64,
+    model: String,
+    started: Instant,
+    content: String,
+}
+
+#[async_trait]
+impl<C: ConversationRepositoryPort + 'static> ChatCompletionStream for RecordingStream<C> {
+    async fn next(&mut self) -> Option<Result<String, InfrastructureError>> {
+        let item = self.inner.next().await;
+        match &item {
+            Some(Ok(delta)) => self.content.push_str(delta),
+            Some(Err(_)) => self.content.clear(),
+            None if !self.content.is_empty() => {
+                let content = std::mem::take(&mut self.content);
+                let duration_ms = self.started.elapsed().as_millis() as u64;
+                let message = NewConversationMessage::assistant(content, &self.model, duration_ms);
+                if let Err(e) = self.conversations.append(self.conversation_id, message).await {
+                    return Some(Err(e));
+                }
+            }
+            None => {}
+        }
+        item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aumate_core_domain::llm::ChatRole;
+    use aumate_core_domain::settings::Settings;
+    use aumate_core_traits::secret::SecretStorePort;
+    use std::sync::Mutex;
+
+    type InfraResult<T> = std::result::Result<T, InfrastructureError>;
+
+    #[derive(Default)]
+    struct MockConversations {
+        conversations: Mutex<Vec<Conversation>>,
+        messages: Mutex<Vec<ConversationMessage>>,
+    }
+
+    #[async_trait]
+    impl ConversationRepositoryPort for MockConversations {
+        async fn create(&self, title: &str) -> InfraResult<Conversation> {
+            let mut conversations = self.conversations.lock().unwrap();
+            let conversation = Conversation {
+                id: conversations.len() as i64 + 1,
+                title: title.to_string(),
+                model: None,
+                message_count: 0,
+                created_at: 0,
+                updated_at: 0,
+            };
+            conversations.push(conversation.clone());
+            Ok(conversation)
+        }
+
+        async fn list(&self, _: &ConversationQuery) -> InfraResult<Vec<Conversation>> {
+            Ok(self.conversations.lock().unwrap().clone())
+        }
+
+        async fn get(&self, id: i64) -> InfraResult<Option<Conversation>> {
+            Ok(self.conversations.lock().unwrap().iter().find(|c| c.id == id).cloned())
+        }
+
+        async fn messages(&self, id: i64) -> InfraResult<Vec<ConversationMessage>> {
+            let messages = self.messages.lock().unwrap();
+            Ok(messages.iter().filter(|m| m.conversation_id == id).cloned().collect())
+        }
+
+        async fn append(
+            &self,
+            conversation_id: i64,
+            message: NewConversationMessage,
+        ) -> InfraResult<Option<ConversationMessage>> {
+            let mut messages = self.messages.lock().unwrap();
+            let stored = ConversationMessage {
+                id: messages.len() as i64 + 1,
+                conversation_id,
+                role: message.role,
+                content: message.content,
+                model: message.model,
+                created_at: 0,
+                duration_ms: message.duration_ms,
+            };
+            messages.push(stored.clone());
+            Ok(Some(stored))
+        }
+
+        async fn rename(&self, _: i64, _: &str) -> InfraResult<bool> {
+            Ok(false)
+        }
+
+        async fn delete(&self, _: i64) -> InfraResult<bool> {
+            Ok(false)
+        }
+
+        async fn clear(&self) -> InfraResult<usize> {
+            Ok(0)
+        }
+    }
+
+    struct MockSecrets;
+
+    #[async_trait]
+    impl SecretStorePort for MockSecrets {
+        async fn get(&self, _: &str) -> InfraResult<Option<String>> {
+            Ok(Some("sk-test".to_string()))
+        }
+
+        async fn set(&self, _: &str, _: &str) -> InfraResult<()> {
+            Ok(())
+        }
+
+        async fn delete(&self, _: &str) -> InfraResult<bool> {
+            Ok(false)
+        }
+    }
+
+    struct MockStorage(Settings);
+
+    #[async_trait]
+    impl SettingsStoragePort for MockStorage {
+        async fn load(&self) -> InfraResult<Settings> {
+            Ok(self.0.clone())
+        }
+
+        async fn save(&self, _: Settings) -> InfraResult<()> {
+            Ok(())
+        }
+    }
+
+    struct TokenStream(Vec<&'static str>);
+
+    #[async_trait]
+    impl ChatCompletionStream for TokenStream {
+        async fn next(&mut self) -> Option<InfraResult<String>> {
+            (!self.0.is_empty()).then(|| Ok(self.0.remove(0).to_string()))
+        }
+    }
+
+    /// 回复固定内容，记录每次请求的消息；`fail` 时请求失败
+    #[derive(Default)]
+    struct MockLlm {
+        requests: Mutex<Vec<ChatCompletionRequest>>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl LlmPort for MockLlm {
+        async fn stream_chat(
+            &self,
+            request: ChatCompletionRequest,
+        ) -> InfraResult<Box<dyn ChatCompletionStream>> {
+            self.requests.lock().unwrap().push(request);
+            if self.fail {
+                return Err(InfrastructureError::NetworkFailed("connection refused".to_string()));
+            }
+            Ok(Box::new(TokenStream(vec!["Hel", "lo"])))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replies_are_recorded_and_resumed() {
+        let mut settings = Settings::default();
+        settings.ai_dialogue.api_key = "secret:ai_dialogue.api_key".to_string();
+        settings.ai_dialogue.model = "test-model".to_string();
+        let conversations = Arc::new(MockConversations::default());
+        let llm = Arc::new(MockLlm::default());
+        let use_case = SendConversationMessageUseCase::new(
+            Arc::new(MockStorage(settings)),
+            Arc::new(SettingsSecrets::new(Arc::new(MockSecrets))),
+            llm.clone(),
+            conversations.clone(),
+        );
+
+        let mut reply = use_case.execute(None, "Hi").await.unwrap();
+        let id = reply.conversation.id;
+        assert_eq!(reply.conversation.title, DEFAULT_CONVERSATION_TITLE);
+        while reply.stream.next().await.is_some() {}
+
+        // 继续对话时带上保存的历史
+        let mut reply = use_case.execute(Some(id), "Again").await.unwrap();
+        while reply.stream.next().await.is_some() {}
+        let request = llm.requests.lock().unwrap().pop().unwrap();
+        let roles: Vec<_> = request.messages.iter().map(|m| m.role).collect();
+        assert_eq!(
+            roles,
+            vec![ChatRole::System, ChatRole::User, ChatRole::Assistant, ChatRole::User]
+        );
+
+        let detail = GetConversationUseCase::new(conversations.clone()).execute(id).await.unwrap();
+        let reply = &detail.messages[1];
+        assert_eq!((reply.role.as_str(), reply.content.as_str()), ("assistant", "Hello"));
+        assert_eq!(reply.model.as_deref(), Some("test-model"));
+        assert!(reply.duration_ms.is_some());
+        assert_eq!(detail.messages.len(), 4);
+
+        assert!(use_case.execute(Some(99), "x").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_failed_request_leaves_no_messages() {
+        let mut settings = Settings::default();
+        settings.ai_dialogue.api_key = "secret:ai_dialogue.api_key".to_string();
+        let conversations = Arc::new(MockConversations::default());
+        let use_case = SendConversationMessageUseCase::new(
+            Arc::new(MockStorage(settings)),
+            Arc::new(SettingsSecrets::new(Arc::new(MockSecrets))),
+            Arc::new(MockLlm { fail: true, ..Default::default() }),
+            conversations.clone(),
+        );
+
+        assert!(use_case.execute(None, "Hi").await.is_err());
+        assert!(conversations.conversations.lock().unwrap().is_empty());
+
+        let id = conversations.create("Existing").await.unwrap().id;
+        assert!(use_case.execute(Some(id), "Hi").await.is_err());
+        assert!(conversations.messages.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,228 @@
+// 应用和文件启动器 Use Cases
+//
+// 搜索结果按模糊匹配分数和 frecency 排序
+use crate::dto::launcher::{
+    LaunchResultDTO, LauncherIndexStatsDTO, SearchLauncherRequest, SearchLauncherResponse,
+};
+use aumate_core_domain::launcher::{LaunchItemKind, rank_launch_items};
+use aumate_core_shared::{ApplicationError, DomainError, now_ms};
+use aumate_core_traits::launcher::{LaunchIcon, LaunchUsagePort, LauncherIndexPort, LauncherPort};
+use aumate_core_traits::settings::SettingsStoragePort;
+use std::sync::Arc;
+
+/// 默认返回的结果数
+const DEFAULT_RESULT_LIMIT: usize = 20;
+
+/// 单次搜索的最大结果数
+const MAX_RESULT_LIMIT: usize = 100;
+
+/// 刷新启动器索引 Use Case
+///
+/// 索引范围来自设置中的 `launcher`
+pub struct RefreshLauncherIndexUseCase<P: SettingsStoragePort, I: LauncherIndexPort> {
+    storage: Arc<P>,
+    index: Arc<I>,
+}
+
+impl<P: SettingsStoragePort, I: LauncherIndexPort> RefreshLauncherIndexUseCase<P, I> {
+    pub fn new(storage: Arc<P>, index: Arc<I>) -> Self {
+        Self { storage, index }
+    }
+
+    pub async fn execute(&self) -> Result<LauncherIndexStatsDTO, ApplicationError> {
+        let settings = self.storage.load().await?.launcher;
+        let stats = self.index.refresh(&settings).await?;
+        Ok(LauncherIndexStatsDTO {
+            total: stats.total,
+            updated: stats.updated,
+            removed: stats.removed,
+        })
+    }
+}
+
+/// 搜索启动器 Use Case
+pub struct SearchLauncherUseCase<I: LauncherIndexPort, U: LaunchUsagePort> {
+    index: Arc<I>,
+    usage: Arc<U>,
+}
+
+impl<I: LauncherIndexPort, U: LaunchUsagePort> SearchLauncherUseCase<I, U> {
+    pub fn new(index: Arc<I>, usage: Arc<U>) -> Self {
+        Self { index, usage }
+    }
+
+    pub async fn execute(
+        &self,
+        request: SearchLauncherRequest,
+    ) -> Result<SearchLauncherResponse, ApplicationError> {
+        let limit = request.limit.unwrap_or(DEFAULT_RESULT_LIMIT).min(MAX_RESULT_LIMIT);
+        let items = self.index.items().await?;
+        let usage = self.usage.usage().await?;
+
+        let results = rank_launch_items(&items, &usage, &request.query, now_ms(), limit)
+            .into_iter()
+            .map(|ranked| LaunchResultDTO {
+                kind: match ranked.item.kind {
+                    LaunchItemKind::Application => "application",
+                    LaunchItemKind::File => "file",
+                    LaunchItemKind::Folder => "folder",
+                }
+                .to_string(),
+                has_icon: ranked.item.icon.is_some(),
+                id: ranked.item.id,
+                name: ranked.item.name,
+                description: ranked.item.description,
+                path: ranked.item.path,
+                score: ranked.score,
+            })
+            .collect();
+        Ok(SearchLauncherResponse { results })
+    }
+}
+
+/// 启动应用或打开文件 Use Case，同时记录使用频率
+pub struct LaunchItemUseCase<I: LauncherIndexPort, U: LaunchUsagePort, L: LauncherPort> {
+    index: Arc<I>,
+    usage: Arc<U>,
+    launcher: Arc<L>,
+}
+
+impl<I, U, L> LaunchItemUseCase<I, U, L>
+where
+    I: LauncherIndexPort,
+    U: LaunchUsagePort,
+    L: LauncherPort,
+{
+    pub fn new(index: Arc<I>, usage: Arc<U>, launcher: Arc<L>) -> Self {
+        Self { index, usage, launcher }
+    }
+
+    pub async fn execute(&self, id: &str) -> Result<(), ApplicationError> {
+        log::info!("LaunchItemUseCase: launching {}", id);
+
+        let item = self.index.get(id).await?.ok_or_else(|| {
+            DomainError::ValidationFailed(format!("Launcher item {} not found", id))
+        })?;
+        self.launcher.launch(&item).await?;
+
+        // 记录失败不影响启动
+        if let Err(e) = self.usage.record(id, now_ms()).await {
+            log::warn!("Failed to record launcher usage for {}: {}", id, e);
+        }
+        Ok(())
+    }
+}
+
+/// 读取启动项图标 Use Case
+pub struct GetLauncherIconUseCase<I: LauncherIndexPort> {
+    index: Arc<I>,
+}
+
+impl<I: LauncherIndexPort> GetLauncherIconUseCase<I> {
+    pub fn new(index: Arc<I>) -> Self {
+        Self { index }
+    }
+
+    pub async fn execute(&self, id: &str) -> Result<Option<LaunchIcon>, ApplicationError> {
+        Ok(self.index.icon(id).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use aumate_core_shared::InfrastructureError;
+    use aumate_core_traits::launcher::{
+        LaunchItem, LaunchUsage, LauncherIndexStats, LauncherSettings,
+    };
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    type InfraResult<T> = std::result::Result<T, InfrastructureError>;
+
+    struct MockIndex(Vec<LaunchItem>);
+
+    #[async_trait]
+    impl LauncherIndexPort for MockIndex {
+        async fn refresh(&self, _: &LauncherSettings) -> InfraResult<LauncherIndexStats> {
+            Ok(LauncherIndexStats::default())
+        }
+
+        async fn items(&self) -> InfraResult<Vec<LaunchItem>> {
+            Ok(self.0.clone())
+        }
+
+        async fn get(&self, id: &str) -> InfraResult<Option<LaunchItem>> {
+            Ok(self.0.iter().find(|item| item.id == id).cloned())
+        }
+
+        async fn icon(&self, _: &str) -> InfraResult<Option<LaunchIcon>> {
+            Ok(None)
+        }
+    }
+
+    #[derive(Default)]
+    struct MockUsage(Mutex<HashMap<String, LaunchUsage>>);
+
+    #[async_trait]
+    impl LaunchUsagePort for MockUsage {
+        async fn usage(&self) -> InfraResult<HashMap<String, LaunchUsage>> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+
+        async fn record(&self, id: &str, now: i64) -> InfraResult<()> {
+            self.0.lock().unwrap().entry(id.to_string()).or_default().record(now);
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockLauncher(Mutex<Vec<String>>);
+
+    #[async_trait]
+    impl LauncherPort for MockLauncher {
+        async fn launch(&self, item: &LaunchItem) -> InfraResult<()> {
+            self.0.lock().unwrap().push(item.id.clone());
+            Ok(())
+        }
+    }
+
+    fn item(id: &str, name: &str) -> LaunchItem {
+        LaunchItem {
+            id: id.to_string(),
+            kind: LaunchItemKind::Application,
+            name: name.to_string(),
+            description: None,
+            keywords: Vec::new(),
+            path: String::new(),
+            command: Vec::new(),
+            terminal: false,
+            icon: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_launch_updates_ranking() {
+        let index = Arc::new(MockIndex(vec![item("a", "Calendar"), item("b", "Calculator")]));
+        let usage = Arc::new(MockUsage::default());
+        let launcher = Arc::new(MockLauncher::default());
+        let search = SearchLauncherUseCase::new(index.clone(), usage.clone());
+        let launch = LaunchItemUseCase::new(index, usage, launcher.clone());
+
+        let request = SearchLauncherRequest { query: "cal".to_string(), limit: None };
+        let names = |response: SearchLauncherResponse| -> Vec<String> {
+            response.results.into_iter().map(|result| result.name).collect()
+        };
+        assert_eq!(
+            names(search.execute(request.clone()).await.unwrap()),
+            ["Calculator", "Calendar"]
+        );
+
+        launch.execute("a").await.unwrap();
+        assert_eq!(*launcher.0.lock().unwrap(), ["a"]);
+        assert_eq!(names(search.execute(request).await.unwrap()), ["Calendar", "Calculator"]);
+
+        assert!(launch.execute("missing").await.is_err());
+    }
+}
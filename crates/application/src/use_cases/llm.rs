@@ -0,0 +1,184 @@
+// AI 功能 Use Cases
+//
+// 请求参数来自设置，API Key 从密钥存储中读取，不经过 webview
+use crate::use_cases::settings::SettingsSecrets;
+use aumate_core_shared::{ApplicationError, UseCaseError};
+use aumate_core_traits::llm::{ChatCompletionRequest, ChatCompletionStream, ChatMessage, LlmPort};
+use aumate_core_traits::settings::SettingsStoragePort;
+use std::sync::Arc;
+
+/// 读取 API Key 明文，未配置时提示到对应的设置页
+pub(crate) async fn resolve_api_key(
+    secrets: &SettingsSecrets,
+    value: &str,
+    section: &str,
+) -> Result<String, ApplicationError> {
+    let api_key = secrets.resolve(value).await?;
+    if api_key.trim().is_empty() {
+        return Err(UseCaseError::InvalidRequest(format!(
+            "API key not configured. Please set your API key in Settings > {}.",
+            section
+        )));
+    }
+    Ok(api_key)
+}
+
+/// 表达润色 Use Case
+pub struct PolishExpressionUseCase<P: SettingsStoragePort, L: LlmPort> {
+    storage: Arc<P>,
+    secrets: Arc<SettingsSecrets>,
+    llm: Arc<L>,
+}
+
+impl<P: SettingsStoragePort, L: LlmPort> PolishExpressionUseCase<P, L> {
+    pub fn new(storage: Arc<P>, secrets: Arc<SettingsSecrets>, llm: Arc<L>) -> Self {
+        Self { storage, secrets, llm }
+    }
+
+    /// 润色输入的文本，返回增量文本流
+    pub async fn execute(
+        &self,
+        input: &str,
+    ) -> Result<Box<dyn ChatCompletionStream>, ApplicationError> {
+        log::info!("PolishExpressionUseCase: execute");
+
+        if input.trim().is_empty() {
+            return Err(UseCaseError::InvalidRequest(
+                "Please enter some text to polish.".to_string(),
+            ));
+        }
+
+        let settings = self.storage.load().await?.expression_polishing;
+        let api_key =
+            resolve_api_key(&self.secrets, &settings.api_key, "Expression Polishing").await?;
+        let request = ChatCompletionRequest::polish(&settings, api_key, input);
+        Ok(self.llm.stream_chat(request).await?)
+    }
+}
+
+/// AI 对话 Use Case
+pub struct SendDialogueMessageUseCase<P: SettingsStoragePort, L: LlmPort> {
+    storage: Arc<P>,
+    secrets: Arc<SettingsSecrets>,
+    llm: Arc<L>,
+}
+
+impl<P: SettingsStoragePort, L: LlmPort> SendDialogueMessageUseCase<P, L> {
+    pub fn new(storage: Arc<P>, secrets: Arc<SettingsSecrets>, llm: Arc<L>) -> Self {
+        Self { storage, secrets, llm }
+    }
+
+    /// 发送对话，`history` 的最后一条为用户的新消息
+    ///
+    /// 历史消息按设置中的 `max_history_messages` 截断
+    pub async fn execute(
+        &self,
+        history: &[ChatMessage],
+    ) -> Result<Box<dyn ChatCompletionStream>, ApplicationError> {
+        log::info!("SendDialogueMessageUseCase: execute, {} messages", history.len());
+
+        if history.iter().all(|m| m.content.trim().is_empty()) {
+            return Err(UseCaseError::InvalidRequest("No messages to send.".to_string()));
+        }
+
+        let settings = self.storage.load().await?.ai_dialogue;
+        let api_key = resolve_api_key(&self.secrets, &settings.api_key, "AI Dialogue").await?;
+        let request = ChatCompletionRequest::dialogue(&settings, api_key, history);
+        Ok(self.llm.stream_chat(request).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use aumate_core_domain::settings::Settings;
+    use aumate_core_shared::InfrastructureError;
+    use aumate_core_traits::secret::SecretStorePort;
+    use std::sync::Mutex;
+
+    struct MockSecrets;
+
+    #[async_trait]
+    impl SecretStorePort for MockSecrets {
+        async fn get(&self, name: &str) -> Result<Option<String>, InfrastructureError> {
+            Ok((name == "ai_dialogue.api_key").then(|| "sk-dialogue".to_string()))
+        }
+
+        async fn set(&self, _: &str, _: &str) -> Result<(), InfrastructureError> {
+            Ok(())
+        }
+
+        async fn delete(&self, _: &str) -> Result<bool, InfrastructureError> {
+            Ok(false)
+        }
+    }
+
+    struct MockStorage(Settings);
+
+    #[async_trait]
+    impl SettingsStoragePort for MockStorage {
+        async fn load(&self) -> Result<Settings, InfrastructureError> {
+            Ok(self.0.clone())
+        }
+
+        async fn save(&self, _: Settings) -> Result<(), InfrastructureError> {
+            Ok(())
+        }
+    }
+
+    struct EmptyStream;
+
+    #[async_trait]
+    impl ChatCompletionStream for EmptyStream {
+        async fn next(&mut self) -> Option<Result<String, InfrastructureError>> {
+            None
+        }
+    }
+
+    #[derive(Default)]
+    struct MockLlm {
+        requests: Mutex<Vec<ChatCompletionRequest>>,
+    }
+
+    #[async_trait]
+    impl LlmPort for MockLlm {
+        async fn stream_chat(
+            &self,
+            request: ChatCompletionRequest,
+        ) -> Result<Box<dyn ChatCompletionStream>, InfrastructureError> {
+            self.requests.lock().unwrap().push(request);
+            Ok(Box::new(EmptyStream))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_requests_use_settings_and_secrets() {
+        let mut settings = Settings::default();
+        settings.ai_dialogue.api_key = "secret:ai_dialogue.api_key".to_string();
+        settings.ai_dialogue.max_history_messages = 3;
+        let storage = Arc::new(MockStorage(settings));
+        let secrets = Arc::new(SettingsSecrets::new(Arc::new(MockSecrets)));
+        let llm = Arc::new(MockLlm::default());
+
+        let history: Vec<_> = (0..5)
+            .map(|i| match i % 2 {
+                0 => ChatMessage::user(i.to_string()),
+                _ => ChatMessage::assistant(i.to_string()),
+            })
+            .collect();
+        let dialogue =
+            SendDialogueMessageUseCase::new(storage.clone(), secrets.clone(), llm.clone());
+        dialogue.execute(&history).await.unwrap();
+
+        let request = llm.requests.lock().unwrap().pop().unwrap();
+        assert_eq!(request.endpoint.api_key, "sk-dialogue");
+        let contents: Vec<_> = request.messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["You are a helpful assistant.", "2", "3", "4"]);
+
+        // 未配置 API Key 时不发送请求
+        let polish = PolishExpressionUseCase::new(storage, secrets, llm.clone());
+        assert!(matches!(polish.execute("hello").await, Err(UseCaseError::InvalidRequest(_))));
+        assert!(llm.requests.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,225 @@
+// 测量 Use Cases
+//
+// 开始测量时截图并做一次边缘检测，之后光标每次移动只扫描边缘图，
+// 再从 UI 自动化取光标下的元素边界
+use crate::dto::measurement::MeasurementSessionInfo;
+use aumate_core_domain::measurement::{Measurement, MeasurementSession};
+use aumate_core_shared::{MonitorId, Point, UseCaseError};
+use aumate_core_traits::screenshot::{CaptureOptions, CaptureTarget};
+use aumate_core_traits::{EdgeDetectionPort, ScreenCapturePort, UIAutomationPort};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// 测量 Use Case
+pub struct MeasurementUseCase {
+    screen_capture: Arc<dyn ScreenCapturePort + Send + Sync>,
+    edge_detection: Arc<dyn EdgeDetectionPort>,
+    /// 未设置时测量结果不包含 UI 元素
+    ui_automation: Option<Arc<dyn UIAutomationPort>>,
+    session: Mutex<Option<MeasurementSession>>,
+}
+
+impl MeasurementUseCase {
+    pub fn new(
+        screen_capture: Arc<dyn ScreenCapturePort + Send + Sync>,
+        edge_detection: Arc<dyn EdgeDetectionPort>,
+    ) -> Self {
+        Self { screen_capture, edge_detection, ui_automation: None, session: Mutex::new(None) }
+    }
+
+    pub fn with_ui_automation(mut self, ui_automation: Arc<dyn UIAutomationPort>) -> Self {
+        self.ui_automation = Some(ui_automation);
+        self
+    }
+
+    /// 截取显示器并检测边缘，开始新的测量会话
+    ///
+    /// `monitor_id` 为空时使用光标所在的显示器，已有会话时替换
+    pub async fn start(
+        &self,
+        monitor_id: Option<MonitorId>,
+    ) -> Result<MeasurementSessionInfo, UseCaseError> {
+        let monitor = match monitor_id {
+            Some(id) => self
+                .screen_capture
+                .get_monitors()
+                .await?
+                .into_iter()
+                .find(|monitor| monitor.id == id)
+                .ok_or_else(|| {
+                    UseCaseError::InvalidRequest(format!("Monitor {:?} not found", id))
+                })?,
+            None => self.screen_capture.get_current_monitor().await?,
+        };
+
+        let options = CaptureOptions { cursor_visible: false, ..Default::default() };
+        let screenshot = self
+            .screen_capture
+            .capture(CaptureTarget::Monitor(monitor.id.clone()), options)
+            .await?;
+        let edges = self.edge_detection.detect_edges(&screenshot.image)?;
+        let session = MeasurementSession::new(monitor.rect, monitor.scale_factor, edges)?;
+
+        log::info!(
+            "MeasurementUseCase: session started on monitor {:?}, image {:?}",
+            monitor.id,
+            session.image_size()
+        );
+        let info = MeasurementSessionInfo::from(&session);
+        *lock(&self.session) = Some(session);
+        Ok(info)
+    }
+
+    pub fn stop(&self) {
+        *lock(&self.session) = None;
+    }
+
+    pub fn is_active(&self) -> bool {
+        lock(&self.session).is_some()
+    }
+
+    /// 测量截图像素 `position` 处的间距和 UI 元素
+    ///
+    /// 可以在每次光标移动时调用；UI 自动化失败时结果不包含元素
+    pub async fn measure(&self, position: Point) -> Result<Measurement, UseCaseError> {
+        let (mut measurement, screen_position) = {
+            let session = lock(&self.session);
+            let session = session.as_ref().ok_or_else(no_session)?;
+            (session.measure(position)?, session.to_screen(position))
+        };
+
+        let Some(ui_automation) = &self.ui_automation else {
+            return Ok(measurement);
+        };
+        let elements = match ui_automation.get_element_from_position(screen_position).await {
+            Ok(elements) => elements,
+            Err(e) => {
+                log::debug!("MeasurementUseCase: no UI element at {:?}: {}", screen_position, e);
+                return Ok(measurement);
+            }
+        };
+
+        // 嵌套元素中取包含光标的最小一个
+        let element = elements
+            .into_iter()
+            .filter(|element| element.bounds.contains_point(&screen_position))
+            .min_by_key(|element| element.bounds.width() as u64 * element.bounds.height() as u64);
+        if let Some(element) = element {
+            let session = lock(&self.session);
+            let session = session.as_ref().ok_or_else(no_session)?;
+            measurement.element =
+                session.measure_element(&element.bounds, element.role, element.title);
+        }
+        Ok(measurement)
+    }
+}
+
+fn no_session() -> UseCaseError {
+    UseCaseError::InvalidRequest("Measurement session is not started".to_string())
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{InfraResult, MockScreenCapture, monitor};
+    use async_trait::async_trait;
+    use aumate_core_domain::image::Image;
+    use aumate_core_domain::measurement::EdgeMap;
+    use aumate_core_shared::{InfrastructureError, Rectangle};
+    use aumate_core_traits::window::UIElement;
+
+    /// 逻辑尺寸 10x5 的显示器，截图为 20x10 物理像素
+    fn screen_capture() -> Arc<MockScreenCapture> {
+        Arc::new(MockScreenCapture::new(vec![monitor(
+            1,
+            Rectangle::from_xywh(100, 0, 10, 5).unwrap(),
+            2.0,
+        )]))
+    }
+
+    /// x=4 和 x=15 两条竖线
+    struct MockEdgeDetection;
+
+    impl EdgeDetectionPort for MockEdgeDetection {
+        fn detect_edges(&self, image: &Image) -> InfraResult<EdgeMap> {
+            let (width, height) = (image.width, image.height);
+            let edges = (0..width * height).map(|i| matches!(i % width, 4 | 15)).collect();
+            Ok(EdgeMap::new(width, height, edges).unwrap())
+        }
+    }
+
+    /// 窗口内嵌套一个按钮
+    struct MockUIAutomation;
+
+    #[async_trait]
+    impl UIAutomationPort for MockUIAutomation {
+        async fn init_ui_elements(&mut self) -> InfraResult<()> {
+            Ok(())
+        }
+
+        async fn init_ui_elements_cache(&mut self) -> InfraResult<()> {
+            Ok(())
+        }
+
+        async fn get_element_from_position(&self, position: Point) -> InfraResult<Vec<UIElement>> {
+            let element = |bounds, role: &str| UIElement {
+                bounds,
+                role: Some(role.to_string()),
+                title: None,
+                value: None,
+            };
+            if position.y >= 4 {
+                return Err(InfrastructureError::PlatformOperationFailed("no element".into()));
+            }
+            Ok(vec![
+                element(Rectangle::from_xywh(100, 0, 10, 5).unwrap(), "Window"),
+                element(Rectangle::from_xywh(102, 1, 3, 2).unwrap(), "Button"),
+            ])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_measure_spacing_and_element() {
+        let screen_capture = screen_capture();
+        let measurement =
+            MeasurementUseCase::new(screen_capture.clone(), Arc::new(MockEdgeDetection))
+                .with_ui_automation(Arc::new(MockUIAutomation));
+        assert!(measurement.measure(Point::new(8, 2)).await.is_err());
+
+        let info = measurement.start(None).await.unwrap();
+        assert_eq!((info.width, info.height, info.scale_factor), (20, 10, 2.0));
+
+        let result = measurement.measure(Point::new(8, 2)).await.unwrap();
+        assert_eq!(result.spacing.bounds, Rectangle::from_bounds(5, 0, 15, 10));
+        assert_eq!(result.spacing.width.logical, 5.0);
+        let element = result.element.unwrap();
+        assert_eq!(element.role.as_deref(), Some("Button"));
+        assert_eq!(element.bounds, Rectangle::from_bounds(4, 2, 10, 6));
+        assert_eq!(element.width.physical, 6);
+        assert_eq!(element.width.logical, 3.0);
+
+        // UI 自动化失败时只返回间距
+        let result = measurement.measure(Point::new(8, 9)).await.unwrap();
+        assert!(result.element.is_none());
+
+        let captures = screen_capture.captures();
+        assert!(matches!(&captures[..], [(CaptureTarget::Monitor(id), options)]
+            if *id == MonitorId::new(1) && !options.cursor_visible));
+
+        measurement.stop();
+        assert!(!measurement.is_active());
+    }
+
+    #[tokio::test]
+    async fn test_start_unknown_monitor() {
+        let measurement = MeasurementUseCase::new(screen_capture(), Arc::new(MockEdgeDetection));
+        assert!(measurement.start(Some(MonitorId::new(7))).await.is_err());
+
+        measurement.start(Some(MonitorId::new(1))).await.unwrap();
+        let result = measurement.measure(Point::new(0, 0)).await.unwrap();
+        assert!(result.element.is_none());
+    }
+}
@@ -2,11 +2,21 @@
 //
 // 应用层用例实现
 
+pub mod annotation;
+pub mod barcode;
 pub mod clipboard;
+pub mod clipboard_history;
+pub mod color_picker;
+pub mod conversation;
 pub mod element_scanner;
 pub mod global_shortcut;
+pub mod launcher;
+pub mod llm;
+pub mod measurement;
 pub mod monitor;
+pub mod redaction;
 pub mod screenshot;
+pub mod screenshot_library;
 pub mod scroll;
 pub mod settings;
 pub mod window;
@@ -14,10 +24,20 @@ pub mod window_layout;
 pub mod window_list;
 pub mod window_vibrancy;
 
+pub use annotation::*;
+pub use barcode::*;
 pub use clipboard::*;
+pub use clipboard_history::*;
+pub use color_picker::*;
+pub use conversation::*;
 pub use element_scanner::*;
 pub use global_shortcut::*;
+pub use launcher::*;
+pub use llm::*;
+pub use measurement::*;
+pub use redaction::*;
 pub use screenshot::*;
+pub use screenshot_library::*;
 pub use scroll::*;
 pub use settings::*;
 pub use window::*;
@@ -49,6 +49,7 @@ impl<P: ScreenCapturePort> GetMonitorsUseCase<P> {
                 height: m.rect.height(),
                 scale_factor: m.scale_factor,
                 is_primary: m.is_primary,
+                hdr_info: m.hdr_info,
             })
             .collect();
 
@@ -95,6 +96,7 @@ impl<P: ScreenCapturePort> GetCurrentMonitorUseCase<P> {
             height: current.rect.height(),
             scale_factor: current.scale_factor,
             is_primary: current.is_primary,
+            hdr_info: current.hdr_info,
         })
     }
 }
@@ -102,42 +104,10 @@ impl<P: ScreenCapturePort> GetCurrentMonitorUseCase<P> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use async_trait::async_trait;
-    use aumate_core_shared::{InfrastructureError, MonitorId, Rectangle};
+    use crate::test_support::MockScreenCapture;
+    use aumate_core_shared::{MonitorId, Rectangle};
     use aumate_core_traits::screenshot::Monitor;
 
-    struct MockScreenCapture {
-        monitors: Vec<Monitor>,
-    }
-
-    #[async_trait]
-    impl ScreenCapturePort for MockScreenCapture {
-        async fn capture(
-            &self,
-            _: aumate_core_traits::screenshot::CaptureTarget,
-            _: aumate_core_traits::screenshot::CaptureOptions,
-        ) -> std::result::Result<aumate_core_traits::screenshot::Screenshot, InfrastructureError>
-        {
-            unimplemented!()
-        }
-
-        async fn get_monitors(&self) -> std::result::Result<Vec<Monitor>, InfrastructureError> {
-            Ok(self.monitors.clone())
-        }
-
-        async fn get_current_monitor(&self) -> std::result::Result<Monitor, InfrastructureError> {
-            self.monitors
-                .iter()
-                .find(|m| m.is_primary)
-                .cloned()
-                .ok_or_else(|| InfrastructureError::CaptureFailed("No primary monitor".to_string()))
-        }
-
-        async fn get_focused_window(&self) -> std::result::Result<WindowId, InfrastructureError> {
-            unimplemented!()
-        }
-    }
-
     #[tokio::test]
     async fn test_get_monitors() {
         let monitors = vec![Monitor {
@@ -146,9 +116,10 @@ mod tests {
             rect: Rectangle::from_bounds(0, 0, 1920, 1080),
             scale_factor: 1.0,
             is_primary: true,
+            hdr_info: None,
         }];
 
-        let mock = Arc::new(MockScreenCapture { monitors: monitors.clone() });
+        let mock = Arc::new(MockScreenCapture::new(monitors.clone()));
         let use_case = GetMonitorsUseCase::new(mock);
 
         let result = use_case.execute().await.unwrap();
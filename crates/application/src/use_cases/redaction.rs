@@ -0,0 +1,124 @@
+// 截图隐私遮挡 Use Cases
+//
+// 截图进入 bug 报告之前遮挡手动区域，或自动识别并遮挡邮箱、令牌、IP、银行卡号
+use crate::dto::redaction::{RedactScreenshotRequest, RedactScreenshotResponse};
+use aumate_core_domain::redaction::RedactionRequest;
+use aumate_core_shared::ApplicationError;
+use aumate_core_traits::screenshot::ImageFormat;
+use aumate_core_traits::{ImageProcessingPort, TextRecognitionPort};
+use std::sync::Arc;
+
+/// 遮挡截图 Use Case
+pub struct RedactScreenshotUseCase {
+    image_processing: Arc<dyn ImageProcessingPort>,
+    /// 自动模式需要 OCR，未配置时只支持手动区域
+    text_recognition: Option<Arc<dyn TextRecognitionPort>>,
+}
+
+impl RedactScreenshotUseCase {
+    pub fn new(image_processing: Arc<dyn ImageProcessingPort>) -> Self {
+        Self { image_processing, text_recognition: None }
+    }
+
+    pub fn with_text_recognition(mut self, text_recognition: Arc<dyn TextRecognitionPort>) -> Self {
+        self.text_recognition = Some(text_recognition);
+        self
+    }
+
+    pub async fn execute(
+        &self,
+        request: RedactScreenshotRequest,
+    ) -> Result<RedactScreenshotResponse, ApplicationError> {
+        let format = ImageFormat::from_str(request.format.as_deref().unwrap_or("png"))?;
+        request.method.validate()?;
+
+        let image = self.image_processing.decode(&request.image)?;
+        let mut redaction = RedactionRequest::new(request.method)
+            .with_regions(request.regions)
+            .with_redact_all_text(request.redact_all_text);
+        if let Some(detectors) = request.detectors {
+            redaction = redaction.with_detectors(detectors);
+        }
+        if request.auto {
+            let text_recognition = self.text_recognition.as_ref().ok_or_else(|| {
+                ApplicationError::InvalidRequest(
+                    "Automatic redaction requires OCR, which is not available".to_string(),
+                )
+            })?;
+            redaction = redaction.with_text(text_recognition.recognize(&image).await?);
+        }
+
+        let (image, report) = self.image_processing.redact(&image, &redaction)?;
+        log::info!("RedactScreenshotUseCase: redacted {} regions", report.items.len());
+        let data = self.image_processing.encode(&image, format)?;
+
+        Ok(RedactScreenshotResponse {
+            data,
+            width: image.width,
+            height: image.height,
+            format: format.extension().to_string(),
+            report,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{InfraResult, MockImageProcessing, solid_image};
+    use async_trait::async_trait;
+    use aumate_core_domain::image::Image;
+    use aumate_core_domain::redaction::{RedactionMethod, RedactionReason};
+    use aumate_core_domain::text_recognition::RecognizedText;
+    use aumate_core_shared::Rectangle;
+
+    /// 解码得到 1x1 白图，每个手动区域和每行文本各报告一项
+    fn image_processing() -> Arc<MockImageProcessing> {
+        Arc::new(MockImageProcessing::new().with_decoded(solid_image(1, 1, [255; 4])))
+    }
+
+    struct MockTextRecognition;
+
+    #[async_trait]
+    impl TextRecognitionPort for MockTextRecognition {
+        async fn recognize(&self, _: &Image) -> InfraResult<Vec<RecognizedText>> {
+            Ok(vec![RecognizedText {
+                text: "a@b.io".to_string(),
+                bounds: Rectangle::from_xywh(0, 0, 1, 1).unwrap(),
+                confidence: 1.0,
+            }])
+        }
+    }
+
+    fn request(auto: bool) -> RedactScreenshotRequest {
+        RedactScreenshotRequest {
+            image: Vec::new(),
+            method: RedactionMethod::default(),
+            regions: vec![Rectangle::from_xywh(0, 0, 1, 1).unwrap()],
+            auto,
+            detectors: None,
+            redact_all_text: false,
+            format: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_redact_screenshot() {
+        let manual_only = RedactScreenshotUseCase::new(image_processing());
+        let response = manual_only.execute(request(false)).await.unwrap();
+        assert_eq!(response.format, "png");
+        assert_eq!(response.report.count(RedactionReason::Manual), 1);
+        // 没有 OCR 时不能使用自动模式
+        assert!(manual_only.execute(request(true)).await.is_err());
+
+        let use_case = RedactScreenshotUseCase::new(image_processing())
+            .with_text_recognition(Arc::new(MockTextRecognition));
+        let response = use_case.execute(request(true)).await.unwrap();
+        assert_eq!(response.report.items.len(), 2);
+        assert_eq!(response.report.count(RedactionReason::Email), 1);
+
+        let mut invalid = request(false);
+        invalid.method = RedactionMethod::Pixelate { block_size: 0 };
+        assert!(use_case.execute(invalid).await.is_err());
+    }
+}
@@ -1,12 +1,103 @@
 // 截图用例
-use aumate_core_shared::UseCaseError;
+use aumate_core_domain::clipboard::ClipboardImage;
+use aumate_core_domain::screenshot_filename::{FilenameContext, FilenamePattern};
+use aumate_core_shared::{InfrastructureError, Point, UseCaseError};
+use aumate_core_traits::clipboard::{ClipboardContent, ClipboardPort};
 use aumate_core_traits::screenshot::{
-    CaptureOptions, CaptureTarget, HdrCorrectionAlgorithm, Image, ImageFormat, Screenshot,
+    CaptureMetadata, CaptureOptions, CaptureTarget, HdrCorrectionAlgorithm, HdrInfo, Image,
+    ImageFormat, Screenshot, ToneMappingOperator,
 };
-use aumate_core_traits::{ImageProcessingPort, ScreenCapturePort};
+use aumate_core_traits::settings::SettingsStoragePort;
+use aumate_core_traits::{FileSystemPort, ImageProcessingPort, ScreenCapturePort};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use crate::dto::{CaptureRegionRequest, CaptureResponse, CaptureScreenRequest};
+use crate::dto::{
+    CaptureRegionRequest, CaptureResponse, CaptureScreenRequest, SaveScreenshotRequest,
+    SaveScreenshotResponse,
+};
+use crate::use_cases::screenshot_library::RecordScreenshotUseCase;
+
+/// 重名时最多尝试的文件名数
+const MAX_FILENAME_ATTEMPTS: u32 = 10_000;
+
+/// 将截图加入截图库，失败只记录日志，不影响截图结果
+async fn record_to_library(
+    library: Option<&RecordScreenshotUseCase>,
+    screenshot: &Screenshot,
+    format: ImageFormat,
+    data: &[u8],
+) {
+    let Some(library) = library else {
+        return;
+    };
+    if let Err(e) = library.execute_encoded(screenshot, format, data).await {
+        log::warn!("Failed to record screenshot in library: {}", e);
+    }
+}
+
+/// 目标所在监视器开启了 HDR 时，以浮点格式捕获并按其峰值亮度和 SDR 白色调映射
+///
+/// 监视器不是 HDR 或平台不支持浮点捕获时返回 None，由调用方走 8 位捕获
+async fn capture_tone_mapped(
+    screen_capture: &dyn ScreenCapturePort,
+    image_processing: &dyn ImageProcessingPort,
+    operator: ToneMappingOperator,
+    target: CaptureTarget,
+    options: CaptureOptions,
+) -> Result<Option<Screenshot>, UseCaseError> {
+    let Some(hdr_info) = monitor_hdr_info(screen_capture, &target).await else {
+        return Ok(None);
+    };
+
+    let hdr = match screen_capture.capture_hdr(target, options).await {
+        Ok(hdr) => hdr,
+        Err(InfrastructureError::PlatformNotSupported) => {
+            log::debug!("平台不支持浮点捕获，使用 8 位捕获");
+            return Ok(None);
+        }
+        Err(e) => {
+            return Err(UseCaseError::CaptureFailed(format!("HDR 屏幕捕获失败: {:?}", e)));
+        }
+    };
+
+    log::info!(
+        "色调映射 {:?}，峰值 {} nits，SDR 白 {} nits",
+        operator,
+        hdr_info.peak_luminance,
+        hdr_info.sdr_white_level
+    );
+    let image = image_processing
+        .tone_map(&hdr.image, operator, &hdr_info)
+        .map_err(|e| UseCaseError::CaptureFailed(format!("色调映射失败: {:?}", e)))?;
+    Ok(Some(Screenshot::new(image, hdr.capture_metadata.with_hdr_corrected(true))))
+}
+
+/// 捕获目标所在监视器的 HDR 信息，未开启 HDR 时返回 None
+///
+/// 区域捕获取包含区域左上角的监视器，其他目标未指定监视器时取主监视器
+async fn monitor_hdr_info(
+    screen_capture: &dyn ScreenCapturePort,
+    target: &CaptureTarget,
+) -> Option<HdrInfo> {
+    let monitors = match screen_capture.get_monitors().await {
+        Ok(monitors) => monitors,
+        Err(e) => {
+            log::warn!("获取监视器失败: {:?}", e);
+            return None;
+        }
+    };
+    let primary = || monitors.iter().find(|m| m.is_primary).or_else(|| monitors.first());
+    let monitor = match target {
+        CaptureTarget::Monitor(id) => monitors.iter().find(|m| &m.id == id),
+        CaptureTarget::Region(region) => {
+            let corner = Point::new(region.min_x(), region.min_y());
+            monitors.iter().find(|m| m.rect.contains_point(&corner)).or_else(primary)
+        }
+        _ => primary(),
+    }?;
+    monitor.hdr_info.filter(|info| info.is_hdr_enabled)
+}
 
 /// 捕获屏幕用例
 ///
@@ -14,6 +105,7 @@ use crate::dto::{CaptureRegionRequest, CaptureResponse, CaptureScreenRequest};
 pub struct CaptureScreenUseCase {
     screen_capture: Arc<dyn ScreenCapturePort + Send + Sync>,
     image_processing: Arc<dyn ImageProcessingPort + Send + Sync>,
+    library: Option<Arc<RecordScreenshotUseCase>>,
 }
 
 impl CaptureScreenUseCase {
@@ -21,7 +113,13 @@ impl CaptureScreenUseCase {
         screen_capture: Arc<dyn ScreenCapturePort + Send + Sync>,
         image_processing: Arc<dyn ImageProcessingPort + Send + Sync>,
     ) -> Self {
-        Self { screen_capture, image_processing }
+        Self { screen_capture, image_processing, library: None }
+    }
+
+    /// 每次截图后加入截图库
+    pub fn with_library(mut self, library: Arc<RecordScreenshotUseCase>) -> Self {
+        self.library = Some(library);
+        self
     }
 
     /// 执行截图
@@ -52,23 +150,39 @@ impl CaptureScreenUseCase {
             CaptureTarget::CurrentMonitor
         };
 
-        // 4. 调用 Infrastructure 层捕获屏幕
-        let screenshot = self
-            .screen_capture
-            .capture(target, capture_options)
-            .await
-            .map_err(|e| UseCaseError::CaptureFailed(format!("屏幕捕获失败: {:?}", e)))?;
+        // 4. 调用 Infrastructure 层捕获屏幕，HDR 显示器优先使用浮点捕获并逐像素色调映射
+        let tone_mapped = if request.hdr_correction {
+            capture_tone_mapped(
+                self.screen_capture.as_ref(),
+                self.image_processing.as_ref(),
+                request.tone_mapping.unwrap_or_default(),
+                target.clone(),
+                capture_options.clone(),
+            )
+            .await?
+        } else {
+            None
+        };
+        let screenshot = match tone_mapped {
+            Some(screenshot) => screenshot,
+            None => self
+                .screen_capture
+                .capture(target, capture_options)
+                .await
+                .map_err(|e| UseCaseError::CaptureFailed(format!("屏幕捕获失败: {:?}", e)))?,
+        };
 
         // 5. 编码图像（同步方法）
         let encoded_data = self
             .image_processing
             .encode(&screenshot.image, image_format)
             .map_err(|e| UseCaseError::EncodingFailed(format!("图像编码失败: {:?}", e)))?;
+        record_to_library(self.library.as_deref(), &screenshot, image_format, &encoded_data).await;
 
         // 6. 构建响应
         let response = CaptureResponse {
             id: aumate_core_shared::ScreenshotId::generate(),
-            data: encoded_data.clone(),
+            data: encoded_data,
             width: screenshot.image.width,
             height: screenshot.image.height,
             format: request.format,
@@ -101,6 +215,7 @@ impl CaptureScreenUseCase {
 pub struct CaptureRegionUseCase {
     screen_capture: Arc<dyn ScreenCapturePort + Send + Sync>,
     image_processing: Arc<dyn ImageProcessingPort + Send + Sync>,
+    library: Option<Arc<RecordScreenshotUseCase>>,
 }
 
 impl CaptureRegionUseCase {
@@ -108,7 +223,13 @@ impl CaptureRegionUseCase {
         screen_capture: Arc<dyn ScreenCapturePort + Send + Sync>,
         image_processing: Arc<dyn ImageProcessingPort + Send + Sync>,
     ) -> Self {
-        Self { screen_capture, image_processing }
+        Self { screen_capture, image_processing, library: None }
+    }
+
+    /// 每次截图后加入截图库
+    pub fn with_library(mut self, library: Arc<RecordScreenshotUseCase>) -> Self {
+        self.library = Some(library);
+        self
     }
 
     /// 执行区域截图
@@ -131,24 +252,47 @@ impl CaptureRegionUseCase {
         let image_format = self.parse_image_format(&request.format)?;
 
         // 3. 构建捕获选项
-        let capture_options =
-            CaptureOptions { exclude_windows: vec![], cursor_visible: false, hdr_correction: None };
+        let capture_options = CaptureOptions {
+            exclude_windows: vec![],
+            cursor_visible: false,
+            hdr_correction: if request.hdr_correction {
+                Some(HdrCorrectionAlgorithm::Auto)
+            } else {
+                None
+            },
+        };
 
         // 4. 确定捕获目标
         let target = CaptureTarget::Region(request.region);
 
-        // 5. 调用 Infrastructure 层捕获区域
-        let screenshot = self
-            .screen_capture
-            .capture(target, capture_options)
-            .await
-            .map_err(|e| UseCaseError::CaptureFailed(format!("区域捕获失败: {:?}", e)))?;
+        // 5. 调用 Infrastructure 层捕获区域，与整屏捕获一样在 HDR 显示器上色调映射
+        let tone_mapped = if request.hdr_correction {
+            capture_tone_mapped(
+                self.screen_capture.as_ref(),
+                self.image_processing.as_ref(),
+                request.tone_mapping.unwrap_or_default(),
+                target.clone(),
+                capture_options.clone(),
+            )
+            .await?
+        } else {
+            None
+        };
+        let screenshot = match tone_mapped {
+            Some(screenshot) => screenshot,
+            None => self
+                .screen_capture
+                .capture(target, capture_options)
+                .await
+                .map_err(|e| UseCaseError::CaptureFailed(format!("区域捕获失败: {:?}", e)))?,
+        };
 
         // 6. 编码图像（同步方法）
         let encoded_data = self
             .image_processing
             .encode(&screenshot.image, image_format)
             .map_err(|e| UseCaseError::EncodingFailed(format!("图像编码失败: {:?}", e)))?;
+        record_to_library(self.library.as_deref(), &screenshot, image_format, &encoded_data).await;
 
         // 7. 构建响应
         let response = CaptureResponse {
@@ -179,12 +323,366 @@ impl CaptureRegionUseCase {
     }
 }
 
+/// 保存截图用例
+///
+/// 按设置中的 `screenshot` 展开文件名模板、选择目录和格式，
+/// 原子写入文件，并按需复制到剪贴板
+pub struct SaveScreenshotUseCase<P: SettingsStoragePort> {
+    storage: Arc<P>,
+    image_processing: Arc<dyn ImageProcessingPort>,
+    file_system: Arc<dyn FileSystemPort>,
+    clipboard: Option<Arc<dyn ClipboardPort>>,
+}
+
+impl<P: SettingsStoragePort> SaveScreenshotUseCase<P> {
+    pub fn new(
+        storage: Arc<P>,
+        image_processing: Arc<dyn ImageProcessingPort>,
+        file_system: Arc<dyn FileSystemPort>,
+    ) -> Self {
+        Self { storage, image_processing, file_system, clipboard: None }
+    }
+
+    /// 保存后可复制到剪贴板
+    pub fn with_clipboard(mut self, clipboard: Arc<dyn ClipboardPort>) -> Self {
+        self.clipboard = Some(clipboard);
+        self
+    }
+
+    pub async fn execute(
+        &self,
+        request: SaveScreenshotRequest,
+    ) -> Result<SaveScreenshotResponse, UseCaseError> {
+        let settings = self.storage.load().await?.screenshot;
+
+        let format = request.format.as_deref().unwrap_or(&settings.image_format);
+        let format = ImageFormat::from_str(format)
+            .map_err(|_| UseCaseError::InvalidRequest(format!("不支持的图像格式: {}", format)))?;
+        let quality = request.quality.unwrap_or(settings.image_quality);
+        if !(1..=100).contains(&quality) {
+            return Err(UseCaseError::InvalidRequest(format!(
+                "图像质量必须在 1-100 之间: {}",
+                quality
+            )));
+        }
+
+        let folder = match request.folder.as_deref().unwrap_or(&settings.save_folder).trim() {
+            "" => self.file_system.get_pictures_dir()?,
+            folder => PathBuf::from(folder),
+        };
+        let pattern =
+            FilenamePattern::new(request.filename_pattern.unwrap_or(settings.filename_pattern));
+        let metadata =
+            CaptureMetadata { monitor_id: request.monitor_id, ..CaptureMetadata::default() }
+                .with_window(request.window_title, request.app_name);
+
+        let image = self.image_processing.decode(&request.image)?;
+        let data = self
+            .image_processing
+            .encode_with_quality(&image, format, Some(quality))
+            .map_err(|e| UseCaseError::EncodingFailed(e.to_string()))?;
+
+        let path = self
+            .resolve_path(&folder, &pattern, FilenameContext::from_metadata(&metadata), format)
+            .await?;
+        self.file_system
+            .write_atomic(&path, &data)
+            .await
+            .map_err(|e| UseCaseError::SaveFailed(e.to_string()))?;
+        log::info!("SaveScreenshotUseCase: 截图已保存到 {:?}", path);
+
+        let copied_to_clipboard =
+            if request.copy_to_clipboard.unwrap_or(settings.auto_copy_clipboard) {
+                self.copy_to_clipboard(image).await
+            } else {
+                false
+            };
+
+        Ok(SaveScreenshotResponse {
+            path: path.to_string_lossy().into_owned(),
+            size: data.len() as u64,
+            format: format.extension().to_string(),
+            copied_to_clipboard,
+        })
+    }
+
+    /// 找到第一个不存在的文件名
+    ///
+    /// 模板包含 `{counter}` 时递增计数器，否则追加 `_1`、`_2`……
+    async fn resolve_path(
+        &self,
+        folder: &Path,
+        pattern: &FilenamePattern,
+        context: FilenameContext,
+        format: ImageFormat,
+    ) -> Result<PathBuf, UseCaseError> {
+        let base = pattern.expand(&context);
+        for attempt in 1..=MAX_FILENAME_ATTEMPTS {
+            let name = if pattern.has_counter() {
+                pattern.expand(&context.clone().with_counter(attempt))
+            } else if attempt == 1 {
+                base.clone()
+            } else {
+                format!("{}_{}", base, attempt - 1)
+            };
+            let path = folder.join(format!("{}.{}", name, format.extension()));
+            if !self.file_system.exists(&path).await {
+                return Ok(path);
+            }
+        }
+        Err(UseCaseError::SaveFailed(format!("{:?} 中没有可用的文件名: {}", folder, base)))
+    }
+
+    /// 复制失败只记录日志，文件已经保存
+    async fn copy_to_clipboard(&self, image: Image) -> bool {
+        let Some(clipboard) = &self.clipboard else {
+            return false;
+        };
+        let result = image
+            .to_rgba()
+            .and_then(|image| ClipboardImage::rgba(image.data, image.width, image.height));
+        let image = match result {
+            Ok(image) => image,
+            Err(e) => {
+                log::warn!("Failed to convert screenshot for clipboard: {}", e);
+                return false;
+            }
+        };
+        match clipboard.write(ClipboardContent::Image(image)).await {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Failed to copy screenshot to clipboard: {}", e);
+                false
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{
+        InfraResult, MockClipboard, MockEncoding, MockImageProcessing, MockScreenCapture, monitor,
+        solid_image,
+    };
+    use async_trait::async_trait;
+    use aumate_core_domain::hdr::HdrImage;
+    use aumate_core_domain::settings::Settings;
+    use aumate_core_shared::Rectangle;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
 
     #[test]
     fn test_parse_image_format() {
         // 测试需要创建用例实例，这里保持简单
     }
+
+    struct MockStorage(Settings);
+
+    #[async_trait]
+    impl SettingsStoragePort for MockStorage {
+        async fn load(&self) -> InfraResult<Settings> {
+            Ok(self.0.clone())
+        }
+
+        async fn save(&self, _: Settings) -> InfraResult<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockFileSystem(Mutex<HashMap<PathBuf, Vec<u8>>>);
+
+    #[async_trait]
+    impl FileSystemPort for MockFileSystem {
+        async fn read(&self, path: &Path) -> InfraResult<Vec<u8>> {
+            self.0.lock().unwrap().get(path).cloned().ok_or_else(|| {
+                InfrastructureError::FileOperationFailed(format!("{:?} not found", path))
+            })
+        }
+
+        async fn write(&self, path: &Path, content: &[u8]) -> InfraResult<()> {
+            self.0.lock().unwrap().insert(path.to_path_buf(), content.to_vec());
+            Ok(())
+        }
+
+        async fn write_atomic(&self, path: &Path, content: &[u8]) -> InfraResult<()> {
+            self.write(path, content).await
+        }
+
+        async fn delete(&self, path: &Path) -> InfraResult<()> {
+            self.0.lock().unwrap().remove(path);
+            Ok(())
+        }
+
+        async fn copy(&self, from: &Path, to: &Path) -> InfraResult<()> {
+            let content = self.read(from).await?;
+            self.write(to, &content).await
+        }
+
+        async fn move_file(&self, from: &Path, to: &Path) -> InfraResult<()> {
+            self.copy(from, to).await?;
+            self.delete(from).await
+        }
+
+        async fn exists(&self, path: &Path) -> bool {
+            self.0.lock().unwrap().contains_key(path)
+        }
+
+        async fn create_dir(&self, _: &Path) -> InfraResult<()> {
+            Ok(())
+        }
+
+        async fn remove_dir(&self, _: &Path) -> InfraResult<()> {
+            Ok(())
+        }
+
+        fn get_app_config_dir(&self) -> InfraResult<PathBuf> {
+            Ok(PathBuf::from("/config"))
+        }
+
+        fn get_pictures_dir(&self) -> InfraResult<PathBuf> {
+            Ok(PathBuf::from("/pictures"))
+        }
+    }
+
+    /// 编码结果为 "格式:质量"
+    fn image_processing() -> MockImageProcessing {
+        MockImageProcessing::new()
+            .with_encoding(MockEncoding::Quality)
+            .with_decoded(solid_image(1, 1, [0; 4]))
+    }
+
+    fn save_use_case(
+        settings: Settings,
+    ) -> (SaveScreenshotUseCase<MockStorage>, Arc<MockFileSystem>, Arc<MockClipboard>) {
+        let file_system = Arc::new(MockFileSystem::default());
+        let clipboard = Arc::new(MockClipboard::default());
+        let use_case = SaveScreenshotUseCase::new(
+            Arc::new(MockStorage(settings)),
+            Arc::new(image_processing()),
+            file_system.clone(),
+        )
+        .with_clipboard(clipboard.clone());
+        (use_case, file_system, clipboard)
+    }
+
+    #[tokio::test]
+    async fn test_save_screenshot_with_settings() {
+        let mut settings = Settings::default();
+        settings.screenshot.save_folder = "/shots".to_string();
+        settings.screenshot.filename_pattern = "{app}_{title}_{monitor}".to_string();
+        settings.screenshot.image_format = "jpg".to_string();
+        settings.screenshot.image_quality = 75;
+        let (use_case, file_system, clipboard) = save_use_case(settings);
+
+        let request = SaveScreenshotRequest {
+            window_title: Some("a/b".to_string()),
+            app_name: Some("Code".to_string()),
+            monitor_id: Some("2".to_string()),
+            ..Default::default()
+        };
+        let response = use_case.execute(request.clone()).await.unwrap();
+        assert_eq!(response.path, "/shots/Code_a_b_2.jpg");
+        assert_eq!(response.format, "jpg");
+        assert!(response.copied_to_clipboard);
+        assert_eq!(clipboard.written().len(), 1);
+        assert_eq!(file_system.read(Path::new(&response.path)).await.unwrap(), b"jpg:75");
+
+        // 重名时追加序号
+        let response = use_case.execute(request.clone()).await.unwrap();
+        assert_eq!(response.path, "/shots/Code_a_b_2_1.jpg");
+
+        // 请求中的选项覆盖设置
+        let response = use_case
+            .execute(SaveScreenshotRequest {
+                format: Some("avif".to_string()),
+                quality: Some(50),
+                copy_to_clipboard: Some(false),
+                ..request
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.path, "/shots/Code_a_b_2.avif");
+        assert!(!response.copied_to_clipboard);
+        assert_eq!(file_system.read(Path::new(&response.path)).await.unwrap(), b"avif:50");
+    }
+
+    #[tokio::test]
+    async fn test_save_screenshot_counter_and_errors() {
+        let mut settings = Settings::default();
+        settings.screenshot.filename_pattern = "shot_{counter:3}".to_string();
+        settings.screenshot.auto_copy_clipboard = false;
+        let (use_case, _, clipboard) = save_use_case(settings);
+
+        // 未设置目录时保存到图片目录，`{counter}` 递增
+        let first = use_case.execute(SaveScreenshotRequest::default()).await.unwrap();
+        let second = use_case.execute(SaveScreenshotRequest::default()).await.unwrap();
+        assert_eq!(first.path, "/pictures/shot_001.png");
+        assert_eq!(second.path, "/pictures/shot_002.png");
+        assert!(clipboard.written().is_empty());
+
+        let invalid_format =
+            SaveScreenshotRequest { format: Some("tiff".to_string()), ..Default::default() };
+        assert!(use_case.execute(invalid_format).await.is_err());
+        let invalid_quality = SaveScreenshotRequest { quality: Some(0), ..Default::default() };
+        assert!(use_case.execute(invalid_quality).await.is_err());
+    }
+
+    /// 8 位捕获返回显示器大小的 2x1 图像，浮点捕获返回 1x1 图像，用宽度区分走了哪条路径
+    fn screen_capture(hdr_info: Option<HdrInfo>, supports_hdr: bool) -> MockScreenCapture {
+        let mut hdr_monitor = monitor(1, Rectangle::from_xywh(0, 0, 2, 1).unwrap(), 1.0);
+        hdr_monitor.is_primary = true;
+        hdr_monitor.hdr_info = hdr_info;
+        let screen_capture = MockScreenCapture::new(vec![hdr_monitor]);
+        if supports_hdr {
+            screen_capture.with_hdr(HdrImage::new(vec![12.5, 12.5, 12.5, 1.0], 1, 1).unwrap())
+        } else {
+            screen_capture
+        }
+    }
+
+    async fn capture_width(hdr_info: Option<HdrInfo>, supports_hdr: bool) -> u32 {
+        let use_case = CaptureScreenUseCase::new(
+            Arc::new(screen_capture(hdr_info, supports_hdr)),
+            Arc::new(image_processing()),
+        );
+        let request = CaptureScreenRequest {
+            monitor_id: None,
+            format: "png".to_string(),
+            quality: None,
+            hdr_correction: true,
+            tone_mapping: Some(ToneMappingOperator::Hable),
+        };
+        use_case.execute(request).await.unwrap().width
+    }
+
+    #[tokio::test]
+    async fn test_capture_tone_mapped() {
+        let hdr = HdrInfo::hdr(1000.0, 200.0);
+        assert_eq!(capture_width(Some(hdr), true).await, 1);
+        // 平台不支持浮点捕获或显示器未开启 HDR 时走 8 位捕获
+        assert_eq!(capture_width(Some(hdr), false).await, 2);
+        assert_eq!(capture_width(Some(HdrInfo::default()), true).await, 2);
+        assert_eq!(capture_width(None, true).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_capture_region_tone_mapped() {
+        let use_case = CaptureRegionUseCase::new(
+            Arc::new(screen_capture(Some(HdrInfo::hdr(1000.0, 200.0)), true)),
+            Arc::new(image_processing()),
+        );
+        let request = |hdr_correction| CaptureRegionRequest {
+            region: Rectangle::from_xywh(0, 0, 2, 1).unwrap(),
+            monitor_id: None,
+            format: "png".to_string(),
+            quality: None,
+            hdr_correction,
+            tone_mapping: None,
+        };
+        // 区域所在的监视器开启了 HDR，走浮点捕获
+        assert_eq!(use_case.execute(request(true)).await.unwrap().width, 1);
+        assert_eq!(use_case.execute(request(false)).await.unwrap().width, 2);
+    }
 }
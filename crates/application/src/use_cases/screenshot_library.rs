@@ -0,0 +1,494 @@
+// 截图库 Use Cases
+//
+// 截图保存到截图库后可以搜索、打开、重新复制和删除
+use crate::dto::screenshot_library::{
+    ListScreenshotsRequest, ListScreenshotsResponse, ScreenshotRecordDTO, UpdateScreenshotRequest,
+};
+use aumate_core_domain::clipboard::ClipboardImage;
+use aumate_core_domain::launcher::{LaunchItem, LaunchItemKind};
+use aumate_core_domain::screenshot_library::{
+    NewScreenshotRecord, ScreenshotLibraryQuery, ScreenshotRecord, THUMBNAIL_MAX_EDGE,
+    normalize_tags, thumbnail_size,
+};
+use aumate_core_domain::text_recognition::recognized_plain_text;
+use aumate_core_shared::{ApplicationError, DomainError};
+use aumate_core_traits::clipboard::{ClipboardContent, ClipboardPort};
+use aumate_core_traits::launcher::LauncherPort;
+use aumate_core_traits::screenshot::{ImageFormat, Screenshot};
+use aumate_core_traits::{ImageProcessingPort, ScreenshotLibraryPort, TextRecognitionPort};
+use std::sync::Arc;
+
+/// 单次查询的最大条目数
+const MAX_LIST_LIMIT: usize = 500;
+
+fn record_to_dto(record: ScreenshotRecord) -> ScreenshotRecordDTO {
+    ScreenshotRecordDTO {
+        id: record.id,
+        file_path: record.file_path,
+        format: record.format,
+        width: record.width,
+        height: record.height,
+        byte_size: record.byte_size,
+        monitor_id: record.monitor_id,
+        window_title: record.window_title,
+        app_name: record.app_name,
+        region: record.region,
+        tags: record.tags,
+        note: record.note,
+        has_ocr_text: record.ocr_text.is_some_and(|text| !text.is_empty()),
+        captured_at: record.captured_at,
+    }
+}
+
+fn not_found(id: i64) -> DomainError {
+    DomainError::ValidationFailed(format!("Screenshot {} not found", id))
+}
+
+/// 获取条目，不存在时报错
+async fn find_record(
+    library: &dyn ScreenshotLibraryPort,
+    id: i64,
+) -> Result<ScreenshotRecord, ApplicationError> {
+    Ok(library.get(id).await?.ok_or_else(|| not_found(id))?)
+}
+
+/// 读取截图文件，不存在时报错
+async fn read_image(
+    library: &dyn ScreenshotLibraryPort,
+    id: i64,
+) -> Result<Vec<u8>, ApplicationError> {
+    Ok(library.read_image(id).await?.ok_or_else(|| not_found(id))?)
+}
+
+/// 将截图加入截图库 Use Case
+///
+/// 按指定格式保存原图，并生成 PNG 缩略图
+pub struct RecordScreenshotUseCase {
+    library: Arc<dyn ScreenshotLibraryPort>,
+    image_processing: Arc<dyn ImageProcessingPort>,
+}
+
+impl RecordScreenshotUseCase {
+    pub fn new(
+        library: Arc<dyn ScreenshotLibraryPort>,
+        image_processing: Arc<dyn ImageProcessingPort>,
+    ) -> Self {
+        Self { library, image_processing }
+    }
+
+    pub async fn execute(
+        &self,
+        screenshot: &Screenshot,
+        format: ImageFormat,
+    ) -> Result<ScreenshotRecordDTO, ApplicationError> {
+        let data = self.image_processing.encode(&screenshot.image, format)?;
+        self.execute_encoded(screenshot, format, &data).await
+    }
+
+    /// 使用已按 `format` 编码好的数据，避免重复编码
+    pub async fn execute_encoded(
+        &self,
+        screenshot: &Screenshot,
+        format: ImageFormat,
+        data: &[u8],
+    ) -> Result<ScreenshotRecordDTO, ApplicationError> {
+        let image = &screenshot.image;
+        log::info!(
+            "RecordScreenshotUseCase: recording {}x{} screenshot",
+            image.width,
+            image.height
+        );
+
+        let (width, height) = thumbnail_size(image.width, image.height, THUMBNAIL_MAX_EDGE);
+        let thumbnail = if (width, height) == (image.width, image.height) {
+            self.image_processing.encode(image, ImageFormat::Png)?
+        } else {
+            let resized = self.image_processing.resize(image, width, height)?;
+            self.image_processing.encode(&resized, ImageFormat::Png)?
+        };
+
+        let record = NewScreenshotRecord::new(
+            format.extension(),
+            image.width,
+            image.height,
+            screenshot.capture_metadata.clone(),
+        );
+        let record = self.library.add(&record, data, &thumbnail).await?;
+        Ok(record_to_dto(record))
+    }
+}
+
+/// 识别截图文本以便搜索 Use Case
+pub struct IndexScreenshotTextUseCase {
+    library: Arc<dyn ScreenshotLibraryPort>,
+    image_processing: Arc<dyn ImageProcessingPort>,
+    text_recognition: Arc<dyn TextRecognitionPort>,
+}
+
+impl IndexScreenshotTextUseCase {
+    pub fn new(
+        library: Arc<dyn ScreenshotLibraryPort>,
+        image_processing: Arc<dyn ImageProcessingPort>,
+        text_recognition: Arc<dyn TextRecognitionPort>,
+    ) -> Self {
+        Self { library, image_processing, text_recognition }
+    }
+
+    /// 返回识别出的文本
+    pub async fn execute(&self, id: i64) -> Result<String, ApplicationError> {
+        log::info!("IndexScreenshotTextUseCase: recognizing text in screenshot {}", id);
+
+        let data = read_image(&*self.library, id).await?;
+        let image = self.image_processing.decode(&data)?;
+        let lines = self.text_recognition.recognize(&image).await?;
+        let text = recognized_plain_text(&lines);
+        if !self.library.set_ocr_text(id, &text).await? {
+            return Err(not_found(id).into());
+        }
+        Ok(text)
+    }
+}
+
+/// 列出 / 搜索截图库 Use Case
+pub struct ListScreenshotsUseCase {
+    library: Arc<dyn ScreenshotLibraryPort>,
+}
+
+impl ListScreenshotsUseCase {
+    pub fn new(library: Arc<dyn ScreenshotLibraryPort>) -> Self {
+        Self { library }
+    }
+
+    pub async fn execute(
+        &self,
+        request: ListScreenshotsRequest,
+    ) -> Result<ListScreenshotsResponse, ApplicationError> {
+        let mut query = ScreenshotLibraryQuery::default();
+        if let Some(search) = request.search {
+            query = query.with_search(search);
+        }
+        if let Some(tag) = request.tag {
+            query = query.with_tag(tag);
+        }
+        if let Some(limit) = request.limit {
+            query = query.with_limit(limit.min(MAX_LIST_LIMIT));
+        }
+        if let Some(offset) = request.offset {
+            query = query.with_offset(offset);
+        }
+
+        let records = self.library.list(&query).await?;
+        Ok(ListScreenshotsResponse {
+            screenshots: records.into_iter().map(record_to_dto).collect(),
+        })
+    }
+}
+
+/// 读取截图缩略图 (PNG) Use Case
+pub struct GetScreenshotThumbnailUseCase {
+    library: Arc<dyn ScreenshotLibraryPort>,
+}
+
+impl GetScreenshotThumbnailUseCase {
+    pub fn new(library: Arc<dyn ScreenshotLibraryPort>) -> Self {
+        Self { library }
+    }
+
+    pub async fn execute(&self, id: i64) -> Result<Vec<u8>, ApplicationError> {
+        Ok(self.library.thumbnail(id).await?.ok_or_else(|| not_found(id))?)
+    }
+}
+
+/// 用系统默认程序打开截图 Use Case
+pub struct OpenScreenshotUseCase {
+    library: Arc<dyn ScreenshotLibraryPort>,
+    launcher: Arc<dyn LauncherPort>,
+}
+
+impl OpenScreenshotUseCase {
+    pub fn new(library: Arc<dyn ScreenshotLibraryPort>, launcher: Arc<dyn LauncherPort>) -> Self {
+        Self { library, launcher }
+    }
+
+    pub async fn execute(&self, id: i64) -> Result<(), ApplicationError> {
+        log::info!("OpenScreenshotUseCase: opening screenshot {}", id);
+
+        let record = find_record(&*self.library, id).await?;
+        let item = LaunchItem {
+            id: format!("screenshot:{}", record.id),
+            kind: LaunchItemKind::File,
+            name: record.file_path.clone(),
+            description: None,
+            keywords: Vec::new(),
+            path: record.file_path,
+            command: Vec::new(),
+            terminal: false,
+            icon: None,
+        };
+        Ok(self.launcher.launch(&item).await?)
+    }
+}
+
+/// 将截图重新复制到剪贴板 Use Case
+pub struct CopyScreenshotUseCase {
+    library: Arc<dyn ScreenshotLibraryPort>,
+    image_processing: Arc<dyn ImageProcessingPort>,
+    clipboard: Arc<dyn ClipboardPort>,
+}
+
+impl CopyScreenshotUseCase {
+    pub fn new(
+        library: Arc<dyn ScreenshotLibraryPort>,
+        image_processing: Arc<dyn ImageProcessingPort>,
+        clipboard: Arc<dyn ClipboardPort>,
+    ) -> Self {
+        Self { library, image_processing, clipboard }
+    }
+
+    pub async fn execute(&self, id: i64) -> Result<(), ApplicationError> {
+        log::info!("CopyScreenshotUseCase: copying screenshot {}", id);
+
+        let record = find_record(&*self.library, id).await?;
+        let data = read_image(&*self.library, id).await?;
+
+        // PNG 直接写入，其他格式先解码为 RGBA
+        let image = if record.format == ImageFormat::Png.extension() {
+            ClipboardImage::png(data)
+        } else {
+            let image = self.image_processing.decode(&data)?.to_rgba().map_err(|e| {
+                ApplicationError::ProcessingFailed(format!("Failed to convert screenshot: {}", e))
+            })?;
+            ClipboardImage::rgba(image.data, image.width, image.height)
+        }
+        .map_err(|e| ApplicationError::ClipboardFailed(e.to_string()))?;
+
+        Ok(self.clipboard.write(ClipboardContent::Image(image)).await?)
+    }
+}
+
+/// 修改截图标签和备注 Use Case
+pub struct UpdateScreenshotUseCase {
+    library: Arc<dyn ScreenshotLibraryPort>,
+}
+
+impl UpdateScreenshotUseCase {
+    pub fn new(library: Arc<dyn ScreenshotLibraryPort>) -> Self {
+        Self { library }
+    }
+
+    pub async fn execute(
+        &self,
+        request: UpdateScreenshotRequest,
+    ) -> Result<ScreenshotRecordDTO, ApplicationError> {
+        let id = request.id;
+        log::info!("UpdateScreenshotUseCase: updating screenshot {}", id);
+
+        if let Some(tags) = request.tags {
+            let updated = self.library.set_tags(id, &normalize_tags(tags)).await?;
+            if !updated {
+                return Err(not_found(id).into());
+            }
+        }
+        if let Some(note) = request.note {
+            let note = note.trim();
+            let note = if note.is_empty() { None } else { Some(note) };
+            if !self.library.set_note(id, note).await? {
+                return Err(not_found(id).into());
+            }
+        }
+        Ok(record_to_dto(find_record(&*self.library, id).await?))
+    }
+}
+
+/// 删除截图 Use Case
+pub struct DeleteScreenshotUseCase {
+    library: Arc<dyn ScreenshotLibraryPort>,
+}
+
+impl DeleteScreenshotUseCase {
+    pub fn new(library: Arc<dyn ScreenshotLibraryPort>) -> Self {
+        Self { library }
+    }
+
+    pub async fn execute(&self, id: i64) -> Result<(), ApplicationError> {
+        log::info!("DeleteScreenshotUseCase: deleting screenshot {}", id);
+
+        if !self.library.delete(id).await? {
+            return Err(not_found(id).into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{
+        InfraResult, MockClipboard, MockEncoding, MockImageProcessing, solid_image,
+    };
+    use async_trait::async_trait;
+    use aumate_core_domain::screenshot::{CaptureMetadata, CaptureTarget};
+    use aumate_core_domain::screenshot_library::ScreenshotLibraryQuery;
+    use std::sync::Mutex;
+
+    /// 截图库中的一条截图
+    struct MockEntry {
+        record: ScreenshotRecord,
+        data: Vec<u8>,
+        thumbnail: Vec<u8>,
+    }
+
+    #[derive(Default)]
+    struct MockLibrary {
+        entries: Mutex<Vec<MockEntry>>,
+    }
+
+    impl MockLibrary {
+        fn with_entry<T>(&self, id: i64, f: impl FnOnce(&mut MockEntry) -> T) -> Option<T> {
+            self.entries.lock().unwrap().iter_mut().find(|entry| entry.record.id == id).map(f)
+        }
+    }
+
+    #[async_trait]
+    impl ScreenshotLibraryPort for MockLibrary {
+        async fn add(
+            &self,
+            record: &NewScreenshotRecord,
+            data: &[u8],
+            thumbnail: &[u8],
+        ) -> InfraResult<ScreenshotRecord> {
+            let mut entries = self.entries.lock().unwrap();
+            let metadata = &record.capture_metadata;
+            let stored = ScreenshotRecord {
+                id: entries.len() as i64 + 1,
+                file_path: format!("/library/{}.{}", entries.len() + 1, record.format),
+                format: record.format.clone(),
+                width: record.width,
+                height: record.height,
+                byte_size: data.len() as u64,
+                monitor_id: metadata.monitor_id.clone(),
+                window_title: metadata.window_title.clone(),
+                app_name: metadata.app_name.clone(),
+                region: metadata.region.clone(),
+                tags: record.tags.clone(),
+                note: None,
+                ocr_text: None,
+                captured_at: record.captured_at(),
+            };
+            entries.push(MockEntry {
+                record: stored.clone(),
+                data: data.to_vec(),
+                thumbnail: thumbnail.to_vec(),
+            });
+            Ok(stored)
+        }
+
+        async fn list(&self, query: &ScreenshotLibraryQuery) -> InfraResult<Vec<ScreenshotRecord>> {
+            let entries = self.entries.lock().unwrap();
+            Ok(entries
+                .iter()
+                .map(|entry| entry.record.clone())
+                .filter(|record| query.tag.as_ref().is_none_or(|tag| record.tags.contains(tag)))
+                .collect())
+        }
+
+        async fn get(&self, id: i64) -> InfraResult<Option<ScreenshotRecord>> {
+            Ok(self.with_entry(id, |entry| entry.record.clone()))
+        }
+
+        async fn read_image(&self, id: i64) -> InfraResult<Option<Vec<u8>>> {
+            Ok(self.with_entry(id, |entry| entry.data.clone()))
+        }
+
+        async fn thumbnail(&self, id: i64) -> InfraResult<Option<Vec<u8>>> {
+            Ok(self.with_entry(id, |entry| entry.thumbnail.clone()))
+        }
+
+        async fn set_tags(&self, id: i64, tags: &[String]) -> InfraResult<bool> {
+            Ok(self.with_entry(id, |entry| entry.record.tags = tags.to_vec()).is_some())
+        }
+
+        async fn set_note(&self, id: i64, note: Option<&str>) -> InfraResult<bool> {
+            Ok(self.with_entry(id, |entry| entry.record.note = note.map(str::to_string)).is_some())
+        }
+
+        async fn set_ocr_text(&self, id: i64, text: &str) -> InfraResult<bool> {
+            Ok(self
+                .with_entry(id, |entry| entry.record.ocr_text = Some(text.to_string()))
+                .is_some())
+        }
+
+        async fn delete(&self, id: i64) -> InfraResult<bool> {
+            let mut entries = self.entries.lock().unwrap();
+            let before = entries.len();
+            entries.retain(|entry| entry.record.id != id);
+            Ok(entries.len() < before)
+        }
+    }
+
+    fn screenshot(width: u32, height: u32) -> Screenshot {
+        let image = solid_image(width, height, [0; 4]);
+        let metadata = CaptureMetadata::new(CaptureTarget::FocusedWindow)
+            .with_window(Some("Login - Browser".to_string()), Some("browser".to_string()));
+        Screenshot::new(image, metadata)
+    }
+
+    #[tokio::test]
+    async fn test_record_copy_update_and_delete() {
+        let library = Arc::new(MockLibrary::default());
+        let image_processing = Arc::new(
+            MockImageProcessing::new()
+                .with_encoding(MockEncoding::Header)
+                .with_decoded(solid_image(1, 1, [0; 4])),
+        );
+        let clipboard = Arc::new(MockClipboard::default());
+
+        let record = RecordScreenshotUseCase::new(library.clone(), image_processing.clone());
+        let png = record.execute(&screenshot(640, 480), ImageFormat::Png).await.unwrap();
+        let jpg = record.execute(&screenshot(100, 50), ImageFormat::Jpeg).await.unwrap();
+        assert_eq!(png.window_title.as_deref(), Some("Login - Browser"));
+        assert_eq!((jpg.format.as_str(), jpg.width, jpg.height), ("jpg", 100, 50));
+
+        // 缩略图按最长边缩放，小图保持原尺寸
+        let thumbnail = GetScreenshotThumbnailUseCase::new(library.clone());
+        let thumb = ClipboardImage::png(thumbnail.execute(png.id).await.unwrap()).unwrap();
+        assert_eq!((thumb.width, thumb.height), (THUMBNAIL_MAX_EDGE, 240));
+        let thumb = ClipboardImage::png(thumbnail.execute(jpg.id).await.unwrap()).unwrap();
+        assert_eq!((thumb.width, thumb.height), (100, 50));
+
+        let copy = CopyScreenshotUseCase::new(library.clone(), image_processing, clipboard.clone());
+        copy.execute(png.id).await.unwrap();
+        copy.execute(jpg.id).await.unwrap();
+        let formats: Vec<_> = clipboard
+            .written()
+            .iter()
+            .map(|content| match content {
+                ClipboardContent::Image(image) => image.is_png(),
+                _ => panic!("expected image"),
+            })
+            .collect();
+        assert_eq!(formats, [true, false]);
+
+        let update = UpdateScreenshotUseCase::new(library.clone());
+        let updated = update
+            .execute(UpdateScreenshotRequest {
+                id: jpg.id,
+                tags: Some(vec![" bug ".to_string(), "Bug".to_string()]),
+                note: Some("  ".to_string()),
+            })
+            .await
+            .unwrap();
+        assert_eq!(updated.tags, ["bug"]);
+        assert_eq!(updated.note, None);
+
+        let list = ListScreenshotsUseCase::new(library.clone());
+        let request = ListScreenshotsRequest { tag: Some("bug".to_string()), ..Default::default() };
+        let listed = list.execute(request).await.unwrap().screenshots;
+        assert_eq!(listed.iter().map(|s| s.id).collect::<Vec<_>>(), [jpg.id]);
+
+        let delete = DeleteScreenshotUseCase::new(library.clone());
+        delete.execute(jpg.id).await.unwrap();
+        assert!(delete.execute(jpg.id).await.is_err());
+        assert!(copy.execute(jpg.id).await.is_err());
+    }
+}
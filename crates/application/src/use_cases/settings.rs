@@ -1,39 +1,285 @@
 // Settings Use Cases
-use aumate_core_domain::settings::Settings;
+use aumate_core_domain::secret::{is_masked_secret, mask_secret, parse_secret_ref, secret_ref};
+use aumate_core_domain::settings::{Settings, SettingsLoadReport, check_hotkey_syntax};
 use aumate_core_shared::{ApplicationError, DomainError};
+use aumate_core_traits::secret::SecretStorePort;
 use aumate_core_traits::settings::SettingsStoragePort;
 use std::sync::Arc;
 
+/// Checks whether a shortcut string can be registered
+pub type HotkeyValidator = Arc<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+/// Moves API keys between settings and the secret store
+///
+/// The settings file only holds `secret:<field>` references. Settings sent to
+/// the webview carry masked keys; a masked key coming back means "unchanged".
+pub struct SettingsSecrets {
+    store: Arc<dyn SecretStorePort>,
+}
+
+impl SettingsSecrets {
+    pub fn new(store: Arc<dyn SecretStorePort>) -> Self {
+        Self { store }
+    }
+
+    /// Move plaintext keys into the secret store, returning whether anything changed
+    pub async fn extract(&self, settings: &mut Settings) -> Result<bool, ApplicationError> {
+        let mut changed = false;
+        for (name, value) in settings.api_keys_mut() {
+            if value.is_empty() || parse_secret_ref(value).is_some() || is_masked_secret(value) {
+                continue;
+            }
+            log::info!("SettingsSecrets: moving {} to the secret store", name);
+            self.store.set(name, value.trim()).await?;
+            *value = secret_ref(name);
+            changed = true;
+        }
+        Ok(changed)
+    }
+
+    /// Apply keys submitted by the webview
+    ///
+    /// Masked keys keep the reference from `current`, empty keys are deleted and
+    /// anything else is stored as the new key.
+    pub async fn apply(
+        &self,
+        settings: &mut Settings,
+        current: &Settings,
+    ) -> Result<(), ApplicationError> {
+        for (name, value) in settings.api_keys_mut() {
+            if is_masked_secret(value) || parse_secret_ref(value).is_some() {
+                *value = current.api_key(name).unwrap_or_default().to_string();
+            } else if value.trim().is_empty() {
+                self.store.delete(name).await?;
+                value.clear();
+            } else {
+                self.store.set(name, value.trim()).await?;
+                *value = secret_ref(name);
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace keys with their masked form before sending settings to the webview
+    pub async fn mask(&self, settings: &mut Settings) -> Result<(), ApplicationError> {
+        for (_, value) in settings.api_keys_mut() {
+            let secret = self.resolve(value).await?;
+            *value = if secret.is_empty() { String::new() } else { mask_secret(&secret) };
+        }
+        Ok(())
+    }
+
+    /// The plaintext key behind a settings value; values that are not references are returned as-is
+    pub async fn resolve(&self, value: &str) -> Result<String, ApplicationError> {
+        match parse_secret_ref(value) {
+            Some(name) => Ok(self.store.get(name).await?.unwrap_or_default()),
+            None => Ok(value.to_string()),
+        }
+    }
+}
+
 /// Get Settings Use Case
 pub struct GetSettingsUseCase<P: SettingsStoragePort> {
     storage: Arc<P>,
+    secrets: Option<Arc<SettingsSecrets>>,
 }
 
 impl<P: SettingsStoragePort> GetSettingsUseCase<P> {
     pub fn new(storage: Arc<P>) -> Self {
-        Self { storage }
+        Self { storage, secrets: None }
+    }
+
+    /// Move plaintext API keys found in the settings file into the secret store
+    pub fn with_secrets(mut self, secrets: Arc<SettingsSecrets>) -> Self {
+        self.secrets = Some(secrets);
+        self
     }
 
     pub async fn execute(&self) -> Result<Settings, ApplicationError> {
         log::info!("GetSettingsUseCase: execute");
 
-        self.storage.load().await.map_err(|e| e.into())
+        Ok(self.execute_with_report().await?.settings)
+    }
+
+    /// Load settings together with any migration or repair that was needed
+    pub async fn execute_with_report(&self) -> Result<SettingsLoadReport, ApplicationError> {
+        log::info!("GetSettingsUseCase: execute_with_report");
+
+        let mut report = self.storage.load_with_report().await?;
+        let Some(secrets) = &self.secrets else {
+            return Ok(report);
+        };
+        if secrets.extract(&mut report.settings).await? {
+            self.storage.save(report.settings.clone()).await?;
+            self.storage.scrub_backups().await?;
+        }
+        Ok(report)
     }
 }
 
 /// Save Settings Use Case
+///
+/// Settings are validated first; invalid fields are returned as
+/// [`DomainError::InvalidFields`] and nothing is written.
 pub struct SaveSettingsUseCase<P: SettingsStoragePort> {
     storage: Arc<P>,
+    hotkey_validator: HotkeyValidator,
+    secrets: Option<Arc<SettingsSecrets>>,
 }
 
 impl<P: SettingsStoragePort> SaveSettingsUseCase<P> {
     pub fn new(storage: Arc<P>) -> Self {
-        Self { storage }
+        Self { storage, hotkey_validator: Arc::new(check_hotkey_syntax), secrets: None }
     }
 
-    pub async fn execute(&self, settings: Settings) -> Result<(), ApplicationError> {
+    /// Use the platform shortcut parser instead of the syntax check
+    pub fn with_hotkey_validator(mut self, hotkey_validator: HotkeyValidator) -> Self {
+        self.hotkey_validator = hotkey_validator;
+        self
+    }
+
+    /// Keep API keys in the secret store, see [`SettingsSecrets::apply`]
+    pub fn with_secrets(mut self, secrets: Arc<SettingsSecrets>) -> Self {
+        self.secrets = Some(secrets);
+        self
+    }
+
+    /// Validate and save settings, returning them as stored
+    pub async fn execute(&self, mut settings: Settings) -> Result<Settings, ApplicationError> {
         log::info!("SaveSettingsUseCase: execute");
 
-        self.storage.save(settings).await.map_err(|e| e.into())
+        let errors = settings.validate(self.hotkey_validator.as_ref());
+        if !errors.is_empty() {
+            return Err(DomainError::InvalidFields(errors).into());
+        }
+
+        if let Some(secrets) = &self.secrets {
+            let current = self.storage.load().await?;
+            secrets.apply(&mut settings, &current).await?;
+        }
+
+        self.storage.save(settings.clone()).await?;
+        Ok(settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use aumate_core_shared::{InfrastructureError, UseCaseError};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockSecrets {
+        secrets: Mutex<HashMap<String, String>>,
+    }
+
+    #[async_trait]
+    impl SecretStorePort for MockSecrets {
+        async fn get(&self, name: &str) -> Result<Option<String>, InfrastructureError> {
+            Ok(self.secrets.lock().unwrap().get(name).cloned())
+        }
+
+        async fn set(&self, name: &str, value: &str) -> Result<(), InfrastructureError> {
+            self.secrets.lock().unwrap().insert(name.to_string(), value.to_string());
+            Ok(())
+        }
+
+        async fn delete(&self, name: &str) -> Result<bool, InfrastructureError> {
+            Ok(self.secrets.lock().unwrap().remove(name).is_some())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockStorage {
+        saved: Mutex<Option<Settings>>,
+        scrubbed: Mutex<bool>,
+    }
+
+    #[async_trait]
+    impl SettingsStoragePort for MockStorage {
+        async fn load(&self) -> Result<Settings, InfrastructureError> {
+            Ok(self.saved.lock().unwrap().clone().unwrap_or_default())
+        }
+
+        async fn save(&self, settings: Settings) -> Result<(), InfrastructureError> {
+            *self.saved.lock().unwrap() = Some(settings);
+            Ok(())
+        }
+
+        async fn scrub_backups(&self) -> Result<(), InfrastructureError> {
+            *self.scrubbed.lock().unwrap() = true;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_rejects_invalid_settings() {
+        let storage = Arc::new(MockStorage::default());
+        let use_case = SaveSettingsUseCase::new(storage.clone()).with_hotkey_validator(Arc::new(
+            |hotkey: &str| match hotkey {
+                "Hyper+X" => Err("Unknown modifier".to_string()),
+                _ => Ok(()),
+            },
+        ));
+
+        let mut settings = Settings::default();
+        settings.shortcuts.screenshot = "Hyper+X".to_string();
+        settings.expression_polishing.api_url = "not a url".to_string();
+
+        let Err(UseCaseError::Domain(DomainError::InvalidFields(errors))) =
+            use_case.execute(settings).await
+        else {
+            panic!("expected field errors");
+        };
+        let fields: Vec<_> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["shortcuts.screenshot", "expression_polishing.api_url"]);
+        assert!(storage.saved.lock().unwrap().is_none());
+
+        use_case.execute(Settings::default()).await.unwrap();
+        assert!(storage.saved.lock().unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_api_keys_live_in_secret_store() {
+        let storage = Arc::new(MockStorage::default());
+        let store = Arc::new(MockSecrets::default());
+        let secrets = Arc::new(SettingsSecrets::new(store.clone()));
+        let get = GetSettingsUseCase::new(storage.clone()).with_secrets(secrets.clone());
+        let save = SaveSettingsUseCase::new(storage.clone()).with_secrets(secrets.clone());
+
+        // Plaintext keys from older settings files move on load
+        let mut legacy = Settings::default();
+        legacy.ai_dialogue.api_key = "sk-legacy-0000001234".to_string();
+        *storage.saved.lock().unwrap() = Some(legacy);
+        let settings = get.execute().await.unwrap();
+        assert_eq!(settings.ai_dialogue.api_key, "secret:ai_dialogue.api_key");
+        assert_eq!(storage.load().await.unwrap().ai_dialogue.api_key, settings.ai_dialogue.api_key);
+        assert!(*storage.scrubbed.lock().unwrap());
+        assert_eq!(
+            secrets.resolve(&settings.ai_dialogue.api_key).await.unwrap(),
+            "sk-legacy-0000001234"
+        );
+
+        // The webview only sees masked keys; sending them back keeps them
+        let mut shown = settings.clone();
+        secrets.mask(&mut shown).await.unwrap();
+        assert_eq!(shown.ai_dialogue.api_key, "••••••••1234");
+        assert_eq!(shown.expression_polishing.api_key, "");
+        shown.expression_polishing.api_key = "sk-polish-key-5678".to_string();
+        let saved = save.execute(shown.clone()).await.unwrap();
+        assert_eq!(saved.ai_dialogue.api_key, "secret:ai_dialogue.api_key");
+        assert_eq!(saved.expression_polishing.api_key, "secret:expression_polishing.api_key");
+        assert_eq!(
+            store.get("expression_polishing.api_key").await.unwrap().as_deref(),
+            Some("sk-polish-key-5678")
+        );
+
+        // Clearing a key deletes the secret
+        shown.ai_dialogue.api_key = String::new();
+        save.execute(shown).await.unwrap();
+        assert_eq!(store.get("ai_dialogue.api_key").await.unwrap(), None);
     }
 }
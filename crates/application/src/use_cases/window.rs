@@ -1,25 +1,52 @@
 // 窗口管理用例
-use aumate_core_shared::{Point, UseCaseError, WindowId};
-use aumate_core_traits::WindowManagementPort;
+use aumate_core_domain::pin::{DEFAULT_SNAP_DISTANCE, PinnedWindow, snap_position};
+use aumate_core_shared::{DomainError, Point, UseCaseError, WindowId};
+use aumate_core_traits::screenshot::ImageFormat;
 use aumate_core_traits::window::{
     DragOperation, ResizeConstraints, ResizeOperation, ResizeSide, WindowConfig,
 };
-use std::sync::Arc;
+use aumate_core_traits::{ImageProcessingPort, WindowManagementPort};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
 use crate::dto::{
-    CreateWindowRequest, CreateWindowResponse, DragWindowRequest, ResizeWindowRequest,
+    CreateWindowRequest, CreateWindowResponse, DragWindowRequest, PinImageRequest,
+    ResizeWindowRequest,
 };
 
+/// 贴图窗口的前端页面
+const PIN_WINDOW_URL: &str = "pages/pin.html";
+
+/// 已贴出的图像及其窗口状态
+struct PinnedEntry {
+    window: PinnedWindow,
+    /// PNG 编码的图像，供贴图窗口加载
+    image: Vec<u8>,
+}
+
 /// 窗口管理用例
 ///
-/// 实现窗口管理相关功能
+/// 实现窗口管理相关功能，并维护贴图窗口注册表。隐藏的贴图仍保留在注册表中，
+/// 重新显示时恢复位置、缩放和鼠标穿透
 pub struct WindowManagementUseCase {
     window_management: Arc<dyn WindowManagementPort + Send + Sync>,
+    image_processing: Arc<dyn ImageProcessingPort + Send + Sync>,
+    pinned: Mutex<HashMap<WindowId, PinnedEntry>>,
+    next_pin_id: AtomicU64,
 }
 
 impl WindowManagementUseCase {
-    pub fn new(window_management: Arc<dyn WindowManagementPort + Send + Sync>) -> Self {
-        Self { window_management }
+    pub fn new(
+        window_management: Arc<dyn WindowManagementPort + Send + Sync>,
+        image_processing: Arc<dyn ImageProcessingPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            window_management,
+            image_processing,
+            pinned: Mutex::new(HashMap::new()),
+            next_pin_id: AtomicU64::new(1),
+        }
     }
 
     /// 创建窗口
@@ -31,6 +58,8 @@ impl WindowManagementUseCase {
 
         // 1. 构建窗口配置
         let config = WindowConfig {
+            label: request.label.clone(),
+            url: request.url.clone(),
             title: request.title.clone(),
             width: request.width.unwrap_or(800.0) as u32,
             height: request.height.unwrap_or(600.0) as u32,
@@ -38,20 +67,20 @@ impl WindowManagementUseCase {
             y: 0,
             resizable: true,
             decorations: true,
+            always_on_top: false,
+            transparent: false,
+            skip_taskbar: false,
         };
 
         // 2. 调用 Infrastructure 层创建窗口
-        let window = self
+        let window_id = self
             .window_management
             .create_fixed_content_window(config)
             .await
             .map_err(|e| UseCaseError::Infrastructure(e))?;
 
         // 3. 构建响应
-        let response = CreateWindowResponse {
-            window_id: WindowId::new(request.label.clone()),
-            label: request.label,
-        };
+        let response = CreateWindowResponse { window_id, label: request.label };
 
         log::info!("WindowManagementUseCase: 窗口创建成功");
         Ok(response)
@@ -134,10 +163,11 @@ impl WindowManagementUseCase {
         Ok(())
     }
 
-    /// 关闭窗口
+    /// 关闭窗口，贴图窗口同时从注册表移除
     pub async fn close_window(&self, window_id: WindowId) -> Result<(), UseCaseError> {
         log::info!("WindowManagementUseCase: 关闭窗口 {}", window_id.as_str());
 
+        self.pinned.lock().unwrap().remove(&window_id);
         self.window_management
             .close_window(window_id)
             .await
@@ -146,14 +176,453 @@ impl WindowManagementUseCase {
         log::info!("WindowManagementUseCase: 窗口关闭完成");
         Ok(())
     }
+
+    /// 把图像贴到屏幕上：无边框、置顶、按图像原始像素大小显示
+    pub async fn pin_image(&self, request: PinImageRequest) -> Result<PinnedWindow, UseCaseError> {
+        let image = self
+            .image_processing
+            .decode(&request.image)
+            .map_err(|e| UseCaseError::InvalidRequest(format!("无法解码图像: {}", e)))?;
+        let png = self
+            .image_processing
+            .encode(&image, ImageFormat::Png)
+            .map_err(|e| UseCaseError::EncodingFailed(format!("图像编码失败: {}", e)))?;
+
+        let position = match request.position {
+            Some(position) => position,
+            None => {
+                let work_areas = self
+                    .window_management
+                    .get_work_areas()
+                    .await
+                    .map_err(UseCaseError::Infrastructure)?;
+                work_areas
+                    .first()
+                    .map(|area| {
+                        Point::new(
+                            area.min_x() + (area.width() as i32 - image.width as i32) / 2,
+                            area.min_y() + (area.height() as i32 - image.height as i32) / 2,
+                        )
+                    })
+                    .unwrap_or(Point::new(0, 0))
+            }
+        };
+
+        let label = format!("pin-{}", self.next_pin_id.fetch_add(1, Ordering::Relaxed));
+        let window =
+            PinnedWindow::new(WindowId::new(label.clone()), position, image.width, image.height);
+        log::info!(
+            "WindowManagementUseCase: 贴图 {} {}x{} @ ({}, {})",
+            label,
+            image.width,
+            image.height,
+            position.x,
+            position.y
+        );
+
+        // 先注册，窗口页面加载时即可取到图像
+        self.pinned
+            .lock()
+            .unwrap()
+            .insert(window.id.clone(), PinnedEntry { window: window.clone(), image: png });
+
+        let config = WindowConfig {
+            label: label.clone(),
+            url: PIN_WINDOW_URL.to_string(),
+            title: "Pinned Screenshot".to_string(),
+            width: window.width(),
+            height: window.height(),
+            x: window.x,
+            y: window.y,
+            resizable: false,
+            decorations: false,
+            always_on_top: true,
+            transparent: true,
+            skip_taskbar: true,
+        };
+        if let Err(e) = self.window_management.create_fixed_content_window(config).await {
+            self.pinned.lock().unwrap().remove(&window.id);
+            return Err(UseCaseError::Infrastructure(e));
+        }
+
+        Ok(window)
+    }
+
+    /// 所有贴图窗口 (包括隐藏的)
+    pub fn list_pinned_windows(&self) -> Vec<PinnedWindow> {
+        let mut windows: Vec<PinnedWindow> =
+            self.pinned.lock().unwrap().values().map(|entry| entry.window.clone()).collect();
+        windows.sort_by(|a, b| a.id.as_str().cmp(b.id.as_str()));
+        windows
+    }
+
+    pub fn get_pinned_window(&self, window_id: &WindowId) -> Result<PinnedWindow, UseCaseError> {
+        self.with_pinned(window_id, |entry| entry.window.clone())
+    }
+
+    /// 贴图的 PNG 数据
+    pub fn get_pinned_image(&self, window_id: &WindowId) -> Result<Vec<u8>, UseCaseError> {
+        self.with_pinned(window_id, |entry| entry.image.clone())
+    }
+
+    /// 缩放贴图，窗口大小随之改变
+    pub async fn zoom_pinned_window(
+        &self,
+        window_id: &WindowId,
+        zoom: f64,
+    ) -> Result<PinnedWindow, UseCaseError> {
+        let window = self.update_pinned(window_id, |window| window.set_zoom(zoom))?;
+        self.window_management
+            .set_window_bounds(window_id.clone(), window.bounds())
+            .await
+            .map_err(UseCaseError::Infrastructure)?;
+        Ok(window)
+    }
+
+    /// 设置贴图不透明度 (由贴图页面渲染)
+    pub fn set_pinned_opacity(
+        &self,
+        window_id: &WindowId,
+        opacity: f64,
+    ) -> Result<PinnedWindow, UseCaseError> {
+        self.update_pinned(window_id, |window| window.set_opacity(opacity))
+    }
+
+    /// 切换鼠标穿透
+    pub async fn set_pinned_click_through(
+        &self,
+        window_id: &WindowId,
+        enable: bool,
+    ) -> Result<PinnedWindow, UseCaseError> {
+        self.get_pinned_window(window_id)?;
+        self.window_management
+            .set_click_through(window_id.clone(), enable)
+            .await
+            .map_err(UseCaseError::Infrastructure)?;
+        self.update_pinned(window_id, |window| {
+            window.click_through = enable;
+            Ok(())
+        })
+    }
+
+    /// 移动贴图，`snap` 为 true 时吸附到显示器边缘和其他可见贴图
+    pub async fn move_pinned_window(
+        &self,
+        window_id: &WindowId,
+        position: Point,
+        snap: bool,
+    ) -> Result<PinnedWindow, UseCaseError> {
+        let position = if snap {
+            let mut targets = self
+                .window_management
+                .get_work_areas()
+                .await
+                .map_err(UseCaseError::Infrastructure)?;
+            let mut moved = self.get_pinned_window(window_id)?;
+            moved.move_to(position);
+            targets.extend(
+                self.list_pinned_windows()
+                    .iter()
+                    .filter(|other| other.visible && &other.id != window_id)
+                    .map(PinnedWindow::bounds),
+            );
+            snap_position(&moved.bounds(), &targets, DEFAULT_SNAP_DISTANCE)
+        } else {
+            position
+        };
+
+        let window = self.update_pinned(window_id, |window| {
+            window.move_to(position);
+            Ok(())
+        })?;
+        self.window_management
+            .set_window_bounds(window_id.clone(), window.bounds())
+            .await
+            .map_err(UseCaseError::Infrastructure)?;
+        Ok(window)
+    }
+
+    /// 显示或隐藏贴图，显示时恢复注册表中的位置、大小和鼠标穿透
+    pub async fn set_pinned_visible(
+        &self,
+        window_id: &WindowId,
+        visible: bool,
+    ) -> Result<PinnedWindow, UseCaseError> {
+        let window = self.update_pinned(window_id, |window| {
+            window.visible = visible;
+            Ok(())
+        })?;
+
+        if visible {
+            self.window_management
+                .set_window_bounds(window_id.clone(), window.bounds())
+                .await
+                .map_err(UseCaseError::Infrastructure)?;
+            self.window_management
+                .set_click_through(window_id.clone(), window.click_through)
+                .await
+                .map_err(UseCaseError::Infrastructure)?;
+        }
+        self.window_management
+            .set_window_visible(window_id.clone(), visible)
+            .await
+            .map_err(UseCaseError::Infrastructure)?;
+        Ok(window)
+    }
+
+    /// 显示或隐藏所有贴图
+    pub async fn set_all_pinned_visible(
+        &self,
+        visible: bool,
+    ) -> Result<Vec<PinnedWindow>, UseCaseError> {
+        let mut windows = Vec::new();
+        for window in self.list_pinned_windows() {
+            windows.push(self.set_pinned_visible(&window.id, visible).await?);
+        }
+        Ok(windows)
+    }
+
+    /// 关闭贴图并从注册表移除
+    pub async fn unpin(&self, window_id: &WindowId) -> Result<(), UseCaseError> {
+        self.get_pinned_window(window_id)?;
+        self.close_window(window_id.clone()).await
+    }
+
+    /// 设置所有贴图的鼠标穿透，穿透状态下贴图收不到输入，只能由托盘菜单等外部入口恢复
+    pub async fn set_all_pinned_click_through(
+        &self,
+        enable: bool,
+    ) -> Result<Vec<PinnedWindow>, UseCaseError> {
+        let mut windows = Vec::new();
+        for window in self.list_pinned_windows() {
+            windows.push(self.set_pinned_click_through(&window.id, enable).await?);
+        }
+        Ok(windows)
+    }
+
+    /// 关闭所有贴图
+    pub async fn unpin_all(&self) -> Result<(), UseCaseError> {
+        for window in self.list_pinned_windows() {
+            self.close_window(window.id).await?;
+        }
+        Ok(())
+    }
+
+    /// 窗口已被销毁 (例如被系统关闭) 时从注册表移除，返回是否为贴图
+    pub fn forget_pinned(&self, window_id: &WindowId) -> bool {
+        self.pinned.lock().unwrap().remove(window_id).is_some()
+    }
+
+    fn with_pinned<T>(
+        &self,
+        window_id: &WindowId,
+        f: impl FnOnce(&PinnedEntry) -> T,
+    ) -> Result<T, UseCaseError> {
+        let pinned = self.pinned.lock().unwrap();
+        let entry = pinned
+            .get(window_id)
+            .ok_or_else(|| DomainError::WindowNotFound(window_id.as_str().to_string()))?;
+        Ok(f(entry))
+    }
+
+    fn update_pinned(
+        &self,
+        window_id: &WindowId,
+        f: impl FnOnce(&mut PinnedWindow) -> Result<(), DomainError>,
+    ) -> Result<PinnedWindow, UseCaseError> {
+        let mut pinned = self.pinned.lock().unwrap();
+        let entry = pinned
+            .get_mut(window_id)
+            .ok_or_else(|| DomainError::WindowNotFound(window_id.as_str().to_string()))?;
+        f(&mut entry.window)?;
+        Ok(entry.window.clone())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{InfraResult, MockEncoding, MockImageProcessing};
+    use async_trait::async_trait;
+    use aumate_core_shared::{InfrastructureError, Rectangle};
+    use aumate_core_traits::window::{DrawWindowStyle, Window};
+
+    /// 记录每个窗口的位置、可见性和鼠标穿透
+    #[derive(Default)]
+    struct MockWindowManagement {
+        windows: Mutex<HashMap<String, (Rectangle, bool, bool)>>,
+    }
+
+    impl MockWindowManagement {
+        fn window(&self, label: &str) -> Option<(Rectangle, bool, bool)> {
+            self.windows.lock().unwrap().get(label).copied()
+        }
+
+        fn update(
+            &self,
+            id: &WindowId,
+            f: impl FnOnce(&mut (Rectangle, bool, bool)),
+        ) -> InfraResult<()> {
+            let mut windows = self.windows.lock().unwrap();
+            let window = windows.get_mut(id.as_str()).ok_or_else(|| {
+                InfrastructureError::PlatformOperationFailed("not found".to_string())
+            })?;
+            f(window);
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl WindowManagementPort for MockWindowManagement {
+        async fn get_window(&self, _: WindowId) -> InfraResult<Window> {
+            Err(InfrastructureError::PlatformNotSupported)
+        }
+
+        async fn set_always_on_top(&self, _: WindowId, _: bool) -> InfraResult<()> {
+            Ok(())
+        }
+
+        async fn start_drag(&self, _: DragOperation) -> InfraResult<()> {
+            Ok(())
+        }
+
+        async fn start_resize(&self, _: ResizeOperation) -> InfraResult<()> {
+            Ok(())
+        }
+
+        async fn set_draw_style(&self, _: WindowId, _: DrawWindowStyle) -> InfraResult<()> {
+            Ok(())
+        }
+
+        async fn create_fixed_content_window(&self, config: WindowConfig) -> InfraResult<WindowId> {
+            let bounds = Rectangle::from_xywh(config.x, config.y, config.width, config.height)
+                .map_err(|e| InfrastructureError::PlatformOperationFailed(e.to_string()))?;
+            self.windows.lock().unwrap().insert(config.label.clone(), (bounds, true, false));
+            Ok(WindowId::new(config.label))
+        }
+
+        async fn close_window(&self, id: WindowId) -> InfraResult<()> {
+            self.windows.lock().unwrap().remove(id.as_str());
+            Ok(())
+        }
+
+        async fn set_window_bounds(&self, id: WindowId, bounds: Rectangle) -> InfraResult<()> {
+            self.update(&id, |window| window.0 = bounds)
+        }
+
+        async fn set_click_through(&self, id: WindowId, enable: bool) -> InfraResult<()> {
+            self.update(&id, |window| window.2 = enable)
+        }
+
+        async fn set_window_visible(&self, id: WindowId, visible: bool) -> InfraResult<()> {
+            self.update(&id, |window| window.1 = visible)
+        }
+
+        async fn get_work_areas(&self) -> InfraResult<Vec<Rectangle>> {
+            Ok(vec![Rectangle::from_bounds(0, 0, 1920, 1040)])
+        }
+    }
+
+    fn use_case() -> (WindowManagementUseCase, Arc<MockWindowManagement>) {
+        let windows = Arc::new(MockWindowManagement::default());
+        // 图像数据的前两个字节为宽和高
+        let image_processing = MockImageProcessing::new().with_encoding(MockEncoding::Quality);
+        (WindowManagementUseCase::new(windows.clone(), Arc::new(image_processing)), windows)
+    }
+
+    #[tokio::test]
+    async fn test_pin_image() {
+        let (use_case, windows) = use_case();
+
+        // 未指定位置时居中
+        let pin = use_case
+            .pin_image(PinImageRequest { image: vec![200, 100], position: None })
+            .await
+            .unwrap();
+        assert_eq!(pin.id.as_str(), "pin-1");
+        assert!(windows.window("pin-1").unwrap().0.equals(860, 470, 1060, 570));
+        assert_eq!(use_case.get_pinned_image(&pin.id).unwrap(), b"png:0");
+
+        let zoomed = use_case.zoom_pinned_window(&pin.id, 0.5).await.unwrap();
+        assert!(windows.window("pin-1").unwrap().0.equals(910, 495, 1010, 545));
+        assert_eq!(zoomed.width(), 100);
+        assert_eq!(use_case.set_pinned_opacity(&pin.id, 0.5).unwrap().opacity, 0.5);
+
+        // 无效图像不创建窗口
+        let invalid = PinImageRequest { image: vec![], position: None };
+        assert!(use_case.pin_image(invalid).await.is_err());
+        assert_eq!(use_case.list_pinned_windows().len(), 1);
+
+        use_case.unpin(&pin.id).await.unwrap();
+        assert!(windows.window("pin-1").is_none());
+        assert!(use_case.get_pinned_window(&pin.id).is_err());
+        assert!(use_case.unpin(&pin.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_move_and_hide_pinned_windows() {
+        let (use_case, windows) = use_case();
+        let first = use_case
+            .pin_image(PinImageRequest {
+                image: vec![100, 50],
+                position: Some(Point::new(500, 300)),
+            })
+            .await
+            .unwrap();
+        let second = use_case
+            .pin_image(PinImageRequest { image: vec![100, 50], position: Some(Point::new(0, 600)) })
+            .await
+            .unwrap();
+
+        // 吸附到第一个贴图右侧和屏幕不吸附时保持原位
+        let moved =
+            use_case.move_pinned_window(&second.id, Point::new(610, 305), true).await.unwrap();
+        assert_eq!((moved.x, moved.y), (600, 300));
+        let moved =
+            use_case.move_pinned_window(&second.id, Point::new(610, 305), false).await.unwrap();
+        assert_eq!((moved.x, moved.y), (610, 305));
+
+        // 隐藏后状态保留，显示时恢复
+        use_case.set_pinned_click_through(&first.id, true).await.unwrap();
+        let hidden = use_case.set_all_pinned_visible(false).await.unwrap();
+        assert!(hidden.iter().all(|window| !window.visible));
+        assert!(!windows.window("pin-1").unwrap().1);
+        windows
+            .update(&first.id, |window| {
+                *window = (Rectangle::from_bounds(0, 0, 1, 1), false, false)
+            })
+            .unwrap();
+
+        let shown = use_case.set_pinned_visible(&first.id, true).await.unwrap();
+        assert!(shown.click_through);
+        assert_eq!(windows.window("pin-1").unwrap(), (first.bounds(), true, true));
+        assert_eq!(use_case.list_pinned_windows().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_release_and_forget_pinned_windows() {
+        let (use_case, windows) = use_case();
+        for _ in 0..3 {
+            let pin = use_case
+                .pin_image(PinImageRequest { image: vec![10, 10], position: None })
+                .await
+                .unwrap();
+            use_case.set_pinned_click_through(&pin.id, true).await.unwrap();
+        }
+
+        let released = use_case.set_all_pinned_click_through(false).await.unwrap();
+        assert_eq!(released.len(), 3);
+        assert!(released.iter().all(|window| !window.click_through));
+        assert!(!windows.window("pin-2").unwrap().2);
+
+        // 窗口被外部销毁后只清理注册表
+        windows.close_window(WindowId::new("pin-1".to_string())).await.unwrap();
+        assert!(use_case.forget_pinned(&WindowId::new("pin-1".to_string())));
+        assert!(!use_case.forget_pinned(&WindowId::new("main".to_string())));
+        assert_eq!(use_case.list_pinned_windows().len(), 2);
 
-    #[test]
-    fn test_window_management_use_case_creation() {
-        // 测试需要 Mock WindowManagementPort
+        use_case.unpin_all().await.unwrap();
+        assert!(use_case.list_pinned_windows().is_empty());
+        assert!(windows.window("pin-2").is_none() && windows.window("pin-3").is_none());
     }
 }
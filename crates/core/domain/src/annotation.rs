@@ -0,0 +1,345 @@
+use aumate_core_shared::DomainError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 标注颜色，序列化为 `"#RRGGBB"` 或 `"#RRGGBBAA"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AnnotationColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl AnnotationColor {
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::rgba(r, g, b, 255)
+    }
+
+    /// 解析 `#RGB`、`#RRGGBB` 或 `#RRGGBBAA`，`#` 可省略
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim().trim_start_matches('#');
+        if !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        match hex.len() {
+            3 => {
+                let short = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|v| v * 17);
+                Some(Self::rgb(short(0)?, short(1)?, short(2)?))
+            }
+            6 => Some(Self::rgb(channel(0)?, channel(2)?, channel(4)?)),
+            8 => Some(Self::rgba(channel(0)?, channel(2)?, channel(4)?, channel(6)?)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AnnotationColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.a == 255 {
+            write!(f, "#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+        } else {
+            write!(f, "#{:02X}{:02X}{:02X}{:02X}", self.r, self.g, self.b, self.a)
+        }
+    }
+}
+
+impl TryFrom<String> for AnnotationColor {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::from_hex(&value).ok_or_else(|| format!("Invalid color: {}", value))
+    }
+}
+
+impl From<AnnotationColor> for String {
+    fn from(color: AnnotationColor) -> Self {
+        color.to_string()
+    }
+}
+
+/// 标注坐标点 (图像像素，可以是小数)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AnnotationPoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl AnnotationPoint {
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+}
+
+/// 标注样式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnnotationStyle {
+    /// 描边颜色，也用作文字颜色和序号徽章颜色
+    pub stroke: AnnotationColor,
+    pub stroke_width: f32,
+    /// 封闭图形的填充色
+    pub fill: Option<AnnotationColor>,
+    /// 整体不透明度 (0.0 - 1.0)
+    pub opacity: f32,
+}
+
+impl Default for AnnotationStyle {
+    fn default() -> Self {
+        Self {
+            stroke: AnnotationColor::rgb(0xE0, 0x31, 0x31),
+            stroke_width: 4.0,
+            fill: None,
+            opacity: 1.0,
+        }
+    }
+}
+
+/// 标注图形
+///
+/// 坐标都是相对于被标注图像左上角的像素坐标
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AnnotationShape {
+    Rectangle {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        #[serde(default)]
+        corner_radius: f32,
+    },
+    Ellipse {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    },
+    Line {
+        from: AnnotationPoint,
+        to: AnnotationPoint,
+    },
+    Arrow {
+        from: AnnotationPoint,
+        to: AnnotationPoint,
+    },
+    /// 画笔轨迹
+    Freehand {
+        points: Vec<AnnotationPoint>,
+    },
+    /// 文字，(x, y) 为左上角，`\n` 换行
+    Text {
+        x: f32,
+        y: f32,
+        content: String,
+        font_size: f32,
+        #[serde(default)]
+        background: Option<AnnotationColor>,
+    },
+    /// 序号徽章，(x, y) 为圆心
+    Step {
+        x: f32,
+        y: f32,
+        number: u32,
+        radius: f32,
+    },
+    /// 马赛克
+    Mosaic {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        block_size: u32,
+    },
+    /// 高斯模糊，`radius` 为标准差 (像素)
+    Blur {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        radius: f32,
+    },
+}
+
+impl AnnotationShape {
+    fn coordinates(&self) -> Vec<f32> {
+        match self {
+            Self::Rectangle { x, y, width, height, corner_radius } => {
+                vec![*x, *y, *width, *height, *corner_radius]
+            }
+            Self::Ellipse { x, y, width, height } | Self::Mosaic { x, y, width, height, .. } => {
+                vec![*x, *y, *width, *height]
+            }
+            Self::Blur { x, y, width, height, radius } => vec![*x, *y, *width, *height, *radius],
+            Self::Line { from, to } | Self::Arrow { from, to } => {
+                vec![from.x, from.y, to.x, to.y]
+            }
+            Self::Freehand { points } => points.iter().flat_map(|p| [p.x, p.y]).collect(),
+            Self::Text { x, y, font_size, .. } => vec![*x, *y, *font_size],
+            Self::Step { x, y, radius, .. } => vec![*x, *y, *radius],
+        }
+    }
+}
+
+/// 单个标注
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    #[serde(default)]
+    pub id: String,
+    /// 绘制层级，大的在上面；相同时按文档顺序
+    #[serde(default)]
+    pub z_index: i32,
+    #[serde(default)]
+    pub style: AnnotationStyle,
+    #[serde(flatten)]
+    pub shape: AnnotationShape,
+}
+
+impl Annotation {
+    pub fn new(shape: AnnotationShape) -> Self {
+        Self { id: String::new(), z_index: 0, style: AnnotationStyle::default(), shape }
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    pub fn with_style(mut self, style: AnnotationStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+}
+
+/// 标注文档，编辑器和自动化流程都用它描述要画在截图上的内容
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnnotationDocument {
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+}
+
+impl AnnotationDocument {
+    pub fn new(annotations: Vec<Annotation>) -> Self {
+        Self { annotations }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, DomainError> {
+        serde_json::from_str(json).map_err(|e| {
+            DomainError::ValidationFailed(format!("Invalid annotation document: {}", e))
+        })
+    }
+
+    pub fn to_json(&self) -> Result<String, DomainError> {
+        serde_json::to_string(self).map_err(|e| DomainError::ValidationFailed(e.to_string()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.annotations.is_empty()
+    }
+
+    /// 按绘制顺序 (从下到上) 返回标注
+    pub fn render_order(&self) -> Vec<&Annotation> {
+        let mut ordered: Vec<&Annotation> = self.annotations.iter().collect();
+        ordered.sort_by_key(|annotation| annotation.z_index);
+        ordered
+    }
+
+    /// 检查坐标和样式取值
+    pub fn validate(&self) -> Result<(), DomainError> {
+        for (index, annotation) in self.annotations.iter().enumerate() {
+            let invalid = |reason: &str| {
+                DomainError::ValidationFailed(format!("Annotation {}: {}", index, reason))
+            };
+            let style = &annotation.style;
+            if annotation.shape.coordinates().iter().any(|v| !v.is_finite()) {
+                return Err(invalid("coordinates must be finite numbers"));
+            }
+            if !style.stroke_width.is_finite() || style.stroke_width < 0.0 {
+                return Err(invalid("stroke_width must not be negative"));
+            }
+            if !(0.0..=1.0).contains(&style.opacity) {
+                return Err(invalid("opacity must be between 0 and 1"));
+            }
+            match &annotation.shape {
+                AnnotationShape::Text { font_size, .. } if *font_size <= 0.0 => {
+                    return Err(invalid("font_size must be positive"));
+                }
+                AnnotationShape::Step { radius, .. } if *radius <= 0.0 => {
+                    return Err(invalid("radius must be positive"));
+                }
+                AnnotationShape::Mosaic { block_size: 0, .. } => {
+                    return Err(invalid("block_size must be positive"));
+                }
+                AnnotationShape::Blur { radius, .. } if *radius < 0.0 => {
+                    return Err(invalid("radius must not be negative"));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_json() {
+        let json = r##"{
+            "annotations": [
+                {"type": "step", "x": 10, "y": 10, "number": 1, "radius": 12, "z_index": 5},
+                {"type": "rectangle", "id": "r1", "x": 0, "y": 0, "width": 10, "height": 10,
+                 "style": {"stroke": "#0f0", "fill": "#0000FF80"}}
+            ]
+        }"##;
+        let document = AnnotationDocument::from_json(json).unwrap();
+        assert!(document.validate().is_ok());
+
+        let rect = &document.annotations[1];
+        assert_eq!(rect.id, "r1");
+        assert_eq!(rect.style.stroke, AnnotationColor::rgb(0, 255, 0));
+        assert_eq!(rect.style.fill, Some(AnnotationColor::rgba(0, 0, 255, 128)));
+        assert_eq!(rect.style.opacity, 1.0);
+
+        let order: Vec<_> = document.render_order().iter().map(|a| a.z_index).collect();
+        assert_eq!(order, [0, 5]);
+
+        let round_trip = AnnotationDocument::from_json(&document.to_json().unwrap()).unwrap();
+        assert_eq!(round_trip, document);
+    }
+
+    #[test]
+    fn test_validate() {
+        let invalid = [
+            Annotation::new(AnnotationShape::Line {
+                from: AnnotationPoint::new(f32::NAN, 0.0),
+                to: AnnotationPoint::new(1.0, 1.0),
+            }),
+            Annotation::new(AnnotationShape::Mosaic {
+                x: 0.0,
+                y: 0.0,
+                width: 10.0,
+                height: 10.0,
+                block_size: 0,
+            }),
+            Annotation::new(AnnotationShape::Ellipse { x: 0.0, y: 0.0, width: 5.0, height: 5.0 })
+                .with_style(AnnotationStyle { opacity: 1.5, ..AnnotationStyle::default() }),
+        ];
+        for annotation in invalid {
+            assert!(AnnotationDocument::new(vec![annotation]).validate().is_err());
+        }
+        assert!(AnnotationDocument::from_json(r#"{"annotations": [{"type": "star"}]}"#).is_err());
+    }
+}
@@ -0,0 +1,152 @@
+use aumate_core_shared::{DomainError, Point, Rectangle};
+use serde::{Deserialize, Serialize};
+
+/// QR 码每个模块的最大像素数
+pub const MAX_QR_MODULE_SIZE: u32 = 64;
+
+/// 条码类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BarcodeFormat {
+    QrCode,
+    MicroQrCode,
+    DataMatrix,
+    Aztec,
+    Pdf417,
+    MaxiCode,
+    Ean8,
+    Ean13,
+    UpcA,
+    UpcE,
+    Code39,
+    Code93,
+    Code128,
+    Codabar,
+    Itf,
+    Rss14,
+    RssExpanded,
+    /// 识别器支持的其他类型
+    Other,
+}
+
+/// 识别出的条码
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecodedBarcode {
+    pub format: BarcodeFormat,
+    pub text: String,
+    /// `points` 的外接矩形
+    pub bounds: Rectangle,
+    /// 识别器给出的定位点：二维码为定位图案或角点，一维码为扫描线两端
+    pub points: Vec<Point>,
+}
+
+impl DecodedBarcode {
+    /// 把图像像素坐标换算到 `target` 区域
+    ///
+    /// 用于区域截图：`target` 为截图对应的屏幕区域，
+    /// 截图可以是更高的物理分辨率，按两者的比例换算
+    pub fn map_to(mut self, image_width: u32, image_height: u32, target: &Rectangle) -> Self {
+        let scale_x = target.width() as f64 / image_width.max(1) as f64;
+        let scale_y = target.height() as f64 / image_height.max(1) as f64;
+        let map = |point: Point| {
+            Point::new(
+                target.min_x() + (point.x as f64 * scale_x).round() as i32,
+                target.min_y() + (point.y as f64 * scale_y).round() as i32,
+            )
+        };
+
+        let min = map(Point::new(self.bounds.min_x(), self.bounds.min_y()));
+        let max = map(Point::new(self.bounds.max_x(), self.bounds.max_y()));
+        self.bounds =
+            Rectangle::from_bounds(min.x, min.y, max.x.max(min.x + 1), max.y.max(min.y + 1));
+        self.points = self.points.into_iter().map(map).collect();
+        self
+    }
+}
+
+/// QR 码纠错等级，等级越高越能容忍污损，但码更大
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QrErrorCorrection {
+    /// 约 7%
+    Low,
+    /// 约 15%
+    #[default]
+    Medium,
+    /// 约 25%
+    Quartile,
+    /// 约 30%
+    High,
+}
+
+/// QR 码生成选项
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QrCodeOptions {
+    /// 每个模块的像素数
+    pub module_size: u32,
+    /// 四周留白的模块数，扫码器一般要求 4 个
+    pub quiet_zone: u32,
+    pub error_correction: QrErrorCorrection,
+}
+
+impl Default for QrCodeOptions {
+    fn default() -> Self {
+        Self { module_size: 8, quiet_zone: 4, error_correction: QrErrorCorrection::default() }
+    }
+}
+
+impl QrCodeOptions {
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.module_size == 0 || self.module_size > MAX_QR_MODULE_SIZE {
+            return Err(DomainError::ValidationFailed(format!(
+                "QR module size must be between 1 and {}, got {}",
+                MAX_QR_MODULE_SIZE, self.module_size
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_to_screen_region() {
+        // 逻辑尺寸 100x50 的区域，截图为 2 倍分辨率
+        let barcode = DecodedBarcode {
+            format: BarcodeFormat::QrCode,
+            text: "hello".to_string(),
+            bounds: Rectangle::from_bounds(20, 10, 60, 50),
+            points: vec![Point::new(20, 10), Point::new(60, 50)],
+        };
+        let target = Rectangle::from_bounds(300, 200, 400, 250);
+
+        let mapped = barcode.map_to(200, 100, &target);
+        assert_eq!(mapped.bounds, Rectangle::from_bounds(310, 205, 330, 225));
+        assert_eq!(mapped.points, [Point::new(310, 205), Point::new(330, 225)]);
+
+        // 一维码扫描线只有一个像素高，换算后仍保留
+        let line = DecodedBarcode {
+            format: BarcodeFormat::Ean13,
+            text: "4006381333931".to_string(),
+            bounds: Rectangle::from_bounds(0, 40, 100, 41),
+            points: vec![Point::new(0, 40), Point::new(100, 40)],
+        };
+        assert_eq!(
+            line.map_to(200, 100, &target).bounds,
+            Rectangle::from_bounds(300, 220, 350, 221)
+        );
+    }
+
+    #[test]
+    fn test_qr_options_validate() {
+        assert!(QrCodeOptions::default().validate().is_ok());
+        assert!(QrCodeOptions { module_size: 0, ..Default::default() }.validate().is_err());
+        let json = serde_json::to_string(&QrCodeOptions::default()).unwrap();
+        assert!(json.contains("\"error_correction\":\"medium\""));
+        let options: QrCodeOptions = serde_json::from_str(r#"{"module_size":4}"#).unwrap();
+        assert_eq!(options.quiet_zone, 4);
+    }
+}
@@ -1,12 +1,153 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// PNG 文件签名
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// 剪贴板图像数据格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClipboardImageFormat {
+    /// 未压缩 RGBA (每通道 8 位)
+    Rgba8,
+    /// PNG 编码
+    Png,
+}
+
+impl ClipboardImageFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Rgba8 => "rgba",
+            Self::Png => "png",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "rgba" | "rgba8" => Some(Self::Rgba8),
+            "png" => Some(Self::Png),
+            _ => None,
+        }
+    }
+}
+
+/// 剪贴板图像
+///
+/// 宽高总是已知的：RGBA 数据由调用方提供，PNG 数据从 IHDR 头中读取
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClipboardImage {
+    pub width: u32,
+    pub height: u32,
+    pub format: ClipboardImageFormat,
+    pub data: Vec<u8>,
+}
+
+impl ClipboardImage {
+    /// 从 RGBA 像素创建，校验数据长度
+    pub fn rgba(data: Vec<u8>, width: u32, height: u32) -> Result<Self, String> {
+        let expected_size = (width as usize) * (height as usize) * 4;
+        if width == 0 || height == 0 || data.len() != expected_size {
+            return Err(format!(
+                "Invalid RGBA image data for {}x{}: expected {} bytes, got {}",
+                width,
+                height,
+                expected_size,
+                data.len()
+            ));
+        }
+        Ok(Self { width, height, format: ClipboardImageFormat::Rgba8, data })
+    }
+
+    /// 从 PNG 数据创建，宽高取自 IHDR 头
+    pub fn png(data: Vec<u8>) -> Result<Self, String> {
+        let (width, height) = png_dimensions(&data)?;
+        Ok(Self { width, height, format: ClipboardImageFormat::Png, data })
+    }
+
+    pub fn is_png(&self) -> bool {
+        self.format == ClipboardImageFormat::Png
+    }
+}
+
+/// 读取 PNG 的宽高
+fn png_dimensions(data: &[u8]) -> Result<(u32, u32), String> {
+    // 签名 (8) + 块长度 (4) + "IHDR" (4) + 宽 (4) + 高 (4)
+    if data.len() < 24 || data[..8] != PNG_SIGNATURE || &data[12..16] != b"IHDR" {
+        return Err("Invalid PNG data".to_string());
+    }
+    let width = u32::from_be_bytes([data[16], data[17], data[18], data[19]]);
+    let height = u32::from_be_bytes([data[20], data[21], data[22], data[23]]);
+    if width == 0 || height == 0 {
+        return Err(format!("Invalid PNG dimensions: {}x{}", width, height));
+    }
+    Ok((width, height))
+}
+
+/// 解析 `text/uri-list`，只保留 `file://` 条目
+///
+/// 也接受每行一个的普通绝对路径
+pub fn paths_from_uri_list(list: &str) -> Vec<PathBuf> {
+    list.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            if let Some(rest) = line.strip_prefix("file://") {
+                // 跳过主机名 (file://host/path)
+                let path = &rest[rest.find('/')?..];
+                Some(PathBuf::from(percent_decode(path)))
+            } else if Path::new(line).is_absolute() {
+                Some(PathBuf::from(line))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn percent_decode(s: &str) -> String {
+    let hex = |b: u8| (b as char).to_digit(16).map(|d| d as u8);
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = match bytes.get(i..i + 3) {
+            Some(&[b'%', hi, lo]) => hex(hi).zip(hex(lo)),
+            _ => None,
+        };
+        match escaped {
+            Some((hi, lo)) => {
+                out.push(hi << 4 | lo);
+                i += 3;
+            }
+            None => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
 
 /// 剪贴板内容
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ClipboardContent {
     Text(String),
-    Image(Vec<u8>),
+    Image(ClipboardImage),
     Files(Vec<PathBuf>),
+    /// HTML，附带供不支持 HTML 的应用使用的纯文本
+    Html {
+        html: String,
+        alt_text: Option<String>,
+    },
+    /// RTF，附带纯文本
+    Rtf {
+        rtf: String,
+        alt_text: Option<String>,
+    },
+    /// 任意 MIME 类型的原始数据
+    Custom {
+        mime: String,
+        data: Vec<u8>,
+    },
 }
 
 impl ClipboardContent {
@@ -14,14 +155,26 @@ impl ClipboardContent {
         Self::Text(s.into())
     }
 
-    pub fn image(data: Vec<u8>) -> Self {
-        Self::Image(data)
+    pub fn image(image: ClipboardImage) -> Self {
+        Self::Image(image)
     }
 
     pub fn files(paths: Vec<PathBuf>) -> Self {
         Self::Files(paths)
     }
 
+    pub fn html(html: impl Into<String>, alt_text: Option<String>) -> Self {
+        Self::Html { html: html.into(), alt_text }
+    }
+
+    pub fn rtf(rtf: impl Into<String>, alt_text: Option<String>) -> Self {
+        Self::Rtf { rtf: rtf.into(), alt_text }
+    }
+
+    pub fn custom(mime: impl Into<String>, data: Vec<u8>) -> Self {
+        Self::Custom { mime: mime.into(), data }
+    }
+
     pub fn is_text(&self) -> bool {
         matches!(self, Self::Text(_))
     }
@@ -38,13 +191,22 @@ impl ClipboardContent {
         if let Self::Text(s) = self { Some(s) } else { None }
     }
 
-    pub fn as_image(&self) -> Option<&Vec<u8>> {
-        if let Self::Image(data) = self { Some(data) } else { None }
+    pub fn as_image(&self) -> Option<&ClipboardImage> {
+        if let Self::Image(image) = self { Some(image) } else { None }
     }
 
     pub fn as_files(&self) -> Option<&Vec<PathBuf>> {
         if let Self::Files(paths) = self { Some(paths) } else { None }
     }
+
+    /// 纯文本形式 (文本本身，或富文本附带的纯文本)
+    pub fn plain_text(&self) -> Option<&str> {
+        match self {
+            Self::Text(text) => Some(text),
+            Self::Html { alt_text, .. } | Self::Rtf { alt_text, .. } => alt_text.as_deref(),
+            _ => None,
+        }
+    }
 }
 
 /// 剪贴板操作
@@ -84,11 +246,59 @@ mod tests {
 
     #[test]
     fn test_clipboard_content_image() {
-        let data = vec![1, 2, 3, 4, 5];
-        let content = ClipboardContent::image(data.clone());
+        let image = ClipboardImage::rgba(vec![0; 2 * 3 * 4], 2, 3).unwrap();
+        let content = ClipboardContent::image(image.clone());
         assert!(content.is_image());
         assert!(!content.is_text());
-        assert_eq!(content.as_image().unwrap(), &data);
+        assert_eq!(content.as_image().unwrap(), &image);
+        assert_eq!(image.format, ClipboardImageFormat::Rgba8);
+    }
+
+    #[test]
+    fn test_clipboard_content_rich_text() {
+        let html = ClipboardContent::html("<b>hi</b>", Some("hi".to_string()));
+        assert_eq!(html.plain_text(), Some("hi"));
+        assert_eq!(ClipboardContent::rtf(r"{\rtf1 hi}", None).plain_text(), None);
+        assert_eq!(ClipboardContent::text("plain").plain_text(), Some("plain"));
+        assert_eq!(ClipboardContent::custom("application/x-test", vec![1]).plain_text(), None);
+    }
+
+    #[test]
+    fn test_clipboard_image_rgba_validates_size() {
+        assert!(ClipboardImage::rgba(vec![0; 10], 2, 3).is_err());
+        assert!(ClipboardImage::rgba(Vec::new(), 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_clipboard_image_png_dimensions() {
+        let mut png = PNG_SIGNATURE.to_vec();
+        png.extend_from_slice(&13u32.to_be_bytes());
+        png.extend_from_slice(b"IHDR");
+        png.extend_from_slice(&640u32.to_be_bytes());
+        png.extend_from_slice(&480u32.to_be_bytes());
+        png.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+        let image = ClipboardImage::png(png).unwrap();
+        assert_eq!((image.width, image.height), (640, 480));
+        assert!(image.is_png());
+        assert!(ClipboardImage::png(vec![1, 2, 3]).is_err());
+        assert_eq!(ClipboardImageFormat::parse("PNG"), Some(ClipboardImageFormat::Png));
+    }
+
+    #[test]
+    fn test_paths_from_uri_list() {
+        let list = "# copied files\r\nfile:///home/me/My%20Doc.txt\r\nfile://host/tmp/a.png\r\n\
+                    https://example.com/x\r\n/plain/path\r\nfile:///bad%zz";
+        let paths = paths_from_uri_list(list);
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/home/me/My Doc.txt"),
+                PathBuf::from("/tmp/a.png"),
+                PathBuf::from("/plain/path"),
+                PathBuf::from("/bad%zz"),
+            ]
+        );
     }
 
     #[test]
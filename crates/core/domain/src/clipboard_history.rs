@@ -0,0 +1,335 @@
+use crate::clipboard::ClipboardContent;
+use serde::{Deserialize, Serialize};
+
+/// 摘要的最大字符数
+pub const PREVIEW_MAX_CHARS: usize = 200;
+
+/// 默认排除的密码管理器 (按应用名或进程名匹配，忽略大小写和 `.exe` 后缀)
+pub const DEFAULT_EXCLUDED_APPS: &[&str] = &[
+    "1Password",
+    "1Password 7",
+    "Bitwarden",
+    "KeePass",
+    "KeePassXC",
+    "KeePassX",
+    "LastPass",
+    "Dashlane",
+    "Enpass",
+    "Keeper Password Manager",
+    "Keychain Access",
+    "Passwords",
+    "seahorse",
+];
+
+/// 密码管理器等应用用来标记敏感内容的剪贴板格式
+pub const SENSITIVE_FORMATS: &[&str] = &[
+    // KDE / X11
+    "x-kde-passwordManagerHint",
+    // macOS (nspasteboard.org)
+    "org.nspasteboard.ConcealedType",
+    "org.nspasteboard.TransientType",
+    "org.nspasteboard.AutoGeneratedType",
+    // Windows
+    "ExcludeClipboardContentFromMonitorProcessing",
+    "Clipboard Viewer Ignore",
+];
+
+/// 历史记录条目类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipboardHistoryKind {
+    Text,
+    Image,
+    Files,
+    Html,
+    Rtf,
+}
+
+impl ClipboardHistoryKind {
+    /// 内容对应的条目类型，自定义格式不记录
+    pub fn of(content: &ClipboardContent) -> Option<Self> {
+        match content {
+            ClipboardContent::Text(_) => Some(Self::Text),
+            ClipboardContent::Image(_) => Some(Self::Image),
+            ClipboardContent::Files(_) => Some(Self::Files),
+            ClipboardContent::Html { .. } => Some(Self::Html),
+            ClipboardContent::Rtf { .. } => Some(Self::Rtf),
+            ClipboardContent::Custom { .. } => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Image => "image",
+            Self::Files => "files",
+            Self::Html => "html",
+            Self::Rtf => "rtf",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "text" => Some(Self::Text),
+            "image" => Some(Self::Image),
+            "files" => Some(Self::Files),
+            "html" => Some(Self::Html),
+            "rtf" => Some(Self::Rtf),
+            _ => None,
+        }
+    }
+}
+
+/// 剪贴板历史条目
+///
+/// 只包含元数据和摘要，内容本身按需读取
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClipboardHistoryEntry {
+    pub id: i64,
+    pub kind: ClipboardHistoryKind,
+    /// 列表中显示的摘要
+    pub preview: String,
+    /// 内容哈希，用于去重
+    pub content_hash: String,
+    /// 复制内容时处于前台的应用
+    pub source_app: Option<String>,
+    pub pinned: bool,
+    /// 被复制或粘贴的次数
+    pub use_count: u32,
+    /// 内容大小 (字节)
+    pub byte_size: u64,
+    pub image_width: Option<u32>,
+    pub image_height: Option<u32>,
+    /// 首次记录时间 (Unix 毫秒)
+    pub created_at: i64,
+    /// 最近一次复制或粘贴时间 (Unix 毫秒)
+    pub last_used_at: i64,
+}
+
+/// 历史记录查询
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardHistoryQuery {
+    /// 全文搜索关键字，为空时返回全部
+    pub search: Option<String>,
+    pub kind: Option<ClipboardHistoryKind>,
+    pub pinned_only: bool,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+impl Default for ClipboardHistoryQuery {
+    fn default() -> Self {
+        Self { search: None, kind: None, pinned_only: false, limit: 50, offset: 0 }
+    }
+}
+
+impl ClipboardHistoryQuery {
+    pub fn with_search(mut self, search: impl Into<String>) -> Self {
+        let search = search.into();
+        self.search = if search.trim().is_empty() { None } else { Some(search) };
+        self
+    }
+
+    pub fn with_kind(mut self, kind: ClipboardHistoryKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    pub fn with_pinned_only(mut self, pinned_only: bool) -> Self {
+        self.pinned_only = pinned_only;
+        self
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+/// 保留策略，置顶条目不受限制
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClipboardHistoryRetention {
+    /// 未置顶条目的最大数量，0 表示不限
+    pub max_entries: usize,
+    /// 未置顶条目的最长保留天数，0 表示不限
+    pub max_age_days: u32,
+}
+
+impl ClipboardHistoryRetention {
+    /// 早于该时间 (Unix 毫秒) 未使用的条目应删除
+    pub fn cutoff(&self, now_ms: i64) -> Option<i64> {
+        if self.max_age_days == 0 {
+            return None;
+        }
+        Some(now_ms - i64::from(self.max_age_days) * 24 * 60 * 60 * 1000)
+    }
+}
+
+/// 不记录到历史的来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardHistoryExclusions {
+    /// 应用名或进程名
+    pub apps: Vec<String>,
+    /// 出现即表示内容敏感的剪贴板格式
+    pub formats: Vec<String>,
+}
+
+impl Default for ClipboardHistoryExclusions {
+    fn default() -> Self {
+        Self {
+            apps: DEFAULT_EXCLUDED_APPS.iter().map(|s| s.to_string()).collect(),
+            formats: SENSITIVE_FORMATS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl ClipboardHistoryExclusions {
+    /// 应用是否被排除
+    pub fn is_excluded_app(&self, app: &str) -> bool {
+        let app = normalize_app_name(app);
+        !app.is_empty() && self.apps.iter().any(|excluded| normalize_app_name(excluded) == app)
+    }
+
+    /// 剪贴板格式中是否有敏感标记
+    pub fn has_sensitive_format<S: AsRef<str>>(&self, formats: &[S]) -> bool {
+        formats.iter().any(|format| {
+            self.formats.iter().any(|marker| marker.eq_ignore_ascii_case(format.as_ref().trim()))
+        })
+    }
+}
+
+fn normalize_app_name(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    let name = name.strip_suffix(".exe").or_else(|| name.strip_suffix(".app")).unwrap_or(&name);
+    name.to_string()
+}
+
+/// 生成列表中显示的摘要
+pub fn clipboard_preview(content: &ClipboardContent) -> String {
+    let preview = match content {
+        ClipboardContent::Text(text) => text.clone(),
+        ClipboardContent::Image(image) => format!("Image {}×{}", image.width, image.height),
+        ClipboardContent::Files(paths) => paths
+            .iter()
+            .map(|path| path.file_name().unwrap_or(path.as_os_str()).to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join(", "),
+        ClipboardContent::Html { html, alt_text } => {
+            alt_text.clone().unwrap_or_else(|| strip_tags(html))
+        }
+        ClipboardContent::Rtf { alt_text, .. } => {
+            alt_text.clone().unwrap_or_else(|| "RTF document".to_string())
+        }
+        ClipboardContent::Custom { mime, .. } => mime.clone(),
+    };
+    truncate_chars(&collapse_whitespace(&preview), PREVIEW_MAX_CHARS)
+}
+
+/// 用于全文搜索的文本
+pub fn clipboard_search_text(content: &ClipboardContent) -> String {
+    match content {
+        ClipboardContent::Text(text) => text.clone(),
+        ClipboardContent::Files(paths) => paths
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        ClipboardContent::Html { html, alt_text } => {
+            alt_text.clone().unwrap_or_else(|| strip_tags(html))
+        }
+        ClipboardContent::Rtf { alt_text, .. } => alt_text.clone().unwrap_or_default(),
+        ClipboardContent::Image(_) | ClipboardContent::Custom { .. } => String::new(),
+    }
+}
+
+/// 去掉 HTML 标签，只保留文本
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    collapse_whitespace(&text)
+}
+
+fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn truncate_chars(s: &str, max_chars: usize) -> String {
+    match s.char_indices().nth(max_chars) {
+        Some((index, _)) => format!("{}…", &s[..index]),
+        None => s.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::ClipboardImage;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_preview() {
+        assert_eq!(clipboard_preview(&ClipboardContent::text("  a\n\n b\tc ")), "a b c");
+
+        let long = "字".repeat(PREVIEW_MAX_CHARS + 10);
+        let preview = clipboard_preview(&ClipboardContent::text(long));
+        assert_eq!(preview.chars().count(), PREVIEW_MAX_CHARS + 1);
+        assert!(preview.ends_with('…'));
+
+        let image = ClipboardImage::rgba(vec![0; 3 * 2 * 4], 3, 2).unwrap();
+        assert_eq!(clipboard_preview(&ClipboardContent::image(image)), "Image 3×2");
+
+        let files = ClipboardContent::files(vec![
+            PathBuf::from("/tmp/report.pdf"),
+            PathBuf::from("/home/me/shot.png"),
+        ]);
+        assert_eq!(clipboard_preview(&files), "report.pdf, shot.png");
+        assert_eq!(clipboard_search_text(&files), "/tmp/report.pdf\n/home/me/shot.png");
+
+        let html = ClipboardContent::html("<p>Hello <b>world</b></p>", None);
+        assert_eq!(clipboard_preview(&html), "Hello world");
+    }
+
+    #[test]
+    fn test_exclusions() {
+        let exclusions = ClipboardHistoryExclusions::default();
+        assert!(exclusions.is_excluded_app("KeePassXC"));
+        assert!(exclusions.is_excluded_app("1password.exe"));
+        assert!(!exclusions.is_excluded_app("Firefox"));
+        assert!(!exclusions.is_excluded_app(""));
+
+        assert!(exclusions.has_sensitive_format(&["text/plain", "x-kde-passwordManagerHint"]));
+        assert!(!exclusions.has_sensitive_format(&["text/plain", "text/html"]));
+    }
+
+    #[test]
+    fn test_retention_cutoff() {
+        let day = 24 * 60 * 60 * 1000;
+        let retention = ClipboardHistoryRetention { max_entries: 10, max_age_days: 2 };
+        assert_eq!(retention.cutoff(10 * day), Some(8 * day));
+        assert_eq!(ClipboardHistoryRetention { max_age_days: 0, ..retention }.cutoff(day), None);
+    }
+
+    #[test]
+    fn test_query_builder() {
+        let query = ClipboardHistoryQuery::default().with_search("   ").with_limit(5);
+        assert_eq!(query.search, None);
+        assert_eq!(query.limit, 5);
+        assert_eq!(ClipboardHistoryKind::parse("Files").map(|kind| kind.as_str()), Some("files"));
+    }
+}
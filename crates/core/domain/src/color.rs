@@ -0,0 +1,374 @@
+use crate::image::{ColorFormat, Image};
+use aumate_core_shared::{DomainError, Point, Rectangle};
+use serde::{Deserialize, Serialize};
+
+/// 放大镜默认半径，网格为 (2 × 半径 + 1) 像素见方
+pub const DEFAULT_MAGNIFIER_RADIUS: u32 = 7;
+
+/// 放大镜最大半径
+pub const MAX_MAGNIFIER_RADIUS: u32 = 32;
+
+/// 最近颜色默认保留数量
+pub const DEFAULT_COLOR_HISTORY_CAPACITY: usize = 16;
+
+/// 像素颜色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PixelColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl PixelColor {
+    /// 超出屏幕范围的像素
+    pub const TRANSPARENT: PixelColor = PixelColor::new(0, 0, 0, 0);
+
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// `#RRGGBB`
+    pub fn to_hex(&self) -> String {
+        format!("#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+    }
+
+    /// `rgb(255, 0, 0)`
+    pub fn to_rgb_string(&self) -> String {
+        format!("rgb({}, {}, {})", self.r, self.g, self.b)
+    }
+
+    pub fn to_hsl(&self) -> Hsl {
+        let [r, g, b] = [self.r, self.g, self.b].map(|c| c as f64 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+        let l = (max + min) / 2.0;
+        if delta == 0.0 {
+            return Hsl { h: 0.0, s: 0.0, l };
+        }
+
+        let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        Hsl { h, s, l }
+    }
+
+    /// 转换到 OKLCH (经 OKLab)
+    pub fn to_oklch(&self) -> Oklch {
+        let [r, g, b] = [self.r, self.g, self.b].map(srgb_to_linear);
+
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+        let lightness = 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s;
+        let a = 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s;
+        let b = 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s;
+
+        let chroma = a.hypot(b);
+        // 无彩色时色相没有意义
+        let hue = if chroma < ACHROMATIC_CHROMA {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        };
+        Oklch { l: lightness, c: chroma, h: hue }
+    }
+
+    /// 可直接复制的各种文本格式
+    pub fn formats(&self) -> ColorFormats {
+        ColorFormats {
+            hex: self.to_hex(),
+            rgb: self.to_rgb_string(),
+            hsl: self.to_hsl().to_string(),
+            oklch: self.to_oklch().to_string(),
+        }
+    }
+}
+
+/// 低于此彩度视为灰色
+const ACHROMATIC_CHROMA: f64 = 1e-4;
+
+fn srgb_to_linear(c: u8) -> f64 {
+    let c = c as f64 / 255.0;
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+/// HSL，色相为角度，饱和度和亮度为 0~1
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Hsl {
+    pub h: f64,
+    pub s: f64,
+    pub l: f64,
+}
+
+impl std::fmt::Display for Hsl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "hsl({}, {}%, {}%)",
+            self.h.round() as u32 % 360,
+            (self.s * 100.0).round(),
+            (self.l * 100.0).round()
+        )
+    }
+}
+
+/// OKLCH，亮度 0~1，彩度通常不超过 0.4，色相为角度
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Oklch {
+    pub l: f64,
+    pub c: f64,
+    pub h: f64,
+}
+
+impl std::fmt::Display for Oklch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "oklch({:.1}% {:.3} {:.1})", self.l * 100.0, self.c, self.h)
+    }
+}
+
+/// 颜色的文本格式
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColorFormats {
+    pub hex: String,
+    pub rgb: String,
+    pub hsl: String,
+    pub oklch: String,
+}
+
+/// 读取图像中的一个像素，越界时返回 None
+pub fn pixel_at(image: &Image, x: i64, y: i64) -> Option<PixelColor> {
+    if x < 0 || y < 0 || x >= image.width as i64 || y >= image.height as i64 {
+        return None;
+    }
+    let bpp = image.color_format.bytes_per_pixel();
+    let offset = (y as usize * image.width as usize + x as usize) * bpp;
+    let pixel = image.data.get(offset..offset + bpp)?;
+    Some(match image.color_format {
+        ColorFormat::RGBA => PixelColor::new(pixel[0], pixel[1], pixel[2], pixel[3]),
+        ColorFormat::RGB => PixelColor::new(pixel[0], pixel[1], pixel[2], 255),
+        ColorFormat::Grayscale => PixelColor::new(pixel[0], pixel[0], pixel[0], 255),
+    })
+}
+
+/// 放大镜显示的像素网格
+///
+/// `pixels` 为逐行排列的 RGBA 数据，超出屏幕的像素为全透明
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MagnifierGrid {
+    pub radius: u32,
+    /// 边长，等于 2 × 半径 + 1
+    pub size: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl MagnifierGrid {
+    /// 以图像坐标 `center` 为中心取样
+    pub fn sample(image: &Image, center: Point, radius: u32) -> Result<Self, DomainError> {
+        if radius > MAX_MAGNIFIER_RADIUS {
+            return Err(DomainError::ValidationFailed(format!(
+                "magnifier radius must not exceed {}",
+                MAX_MAGNIFIER_RADIUS
+            )));
+        }
+
+        let size = radius * 2 + 1;
+        let radius_i = radius as i64;
+        let mut pixels = Vec::with_capacity((size * size * 4) as usize);
+        for dy in -radius_i..=radius_i {
+            for dx in -radius_i..=radius_i {
+                let color = pixel_at(image, center.x as i64 + dx, center.y as i64 + dy)
+                    .unwrap_or(PixelColor::TRANSPARENT);
+                pixels.extend_from_slice(&[color.r, color.g, color.b, color.a]);
+            }
+        }
+        Ok(Self { radius, size, pixels })
+    }
+
+    pub fn color_at(&self, column: u32, row: u32) -> Option<PixelColor> {
+        if column >= self.size || row >= self.size {
+            return None;
+        }
+        let offset = ((row * self.size + column) * 4) as usize;
+        let p = &self.pixels[offset..offset + 4];
+        Some(PixelColor::new(p[0], p[1], p[2], p[3]))
+    }
+}
+
+/// 一次取色的结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColorSample {
+    /// 屏幕坐标
+    pub position: Point,
+    pub color: PixelColor,
+    pub formats: ColorFormats,
+    pub magnifier: MagnifierGrid,
+}
+
+/// 取色会话
+///
+/// 开始时每台显示器截图一次，之后所有取样都读这些截图，不再重复截屏。
+/// `bounds` 与光标坐标使用同一坐标系，截图可以是更高的物理分辨率，
+/// 取样时按两者的比例换算
+#[derive(Debug, Clone, Default)]
+pub struct ColorPickerSession {
+    captures: Vec<(Rectangle, Image)>,
+}
+
+impl ColorPickerSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capture(mut self, bounds: Rectangle, image: Image) -> Self {
+        self.captures.push((bounds, image));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.captures.is_empty()
+    }
+
+    /// 取 `position` 处的颜色和周围的放大镜网格
+    pub fn sample(&self, position: Point, radius: u32) -> Result<ColorSample, DomainError> {
+        let (bounds, image) = self
+            .captures
+            .iter()
+            .find(|(bounds, _)| bounds.contains_point(&position))
+            .ok_or(DomainError::RegionOutOfBounds)?;
+
+        let scale_x = image.width as f64 / bounds.width() as f64;
+        let scale_y = image.height as f64 / bounds.height() as f64;
+        let center = Point::new(
+            ((position.x - bounds.min_x()) as f64 * scale_x).floor() as i32,
+            ((position.y - bounds.min_y()) as f64 * scale_y).floor() as i32,
+        );
+
+        let color = pixel_at(image, center.x as i64, center.y as i64)
+            .ok_or(DomainError::RegionOutOfBounds)?;
+        Ok(ColorSample {
+            position,
+            color,
+            formats: color.formats(),
+            magnifier: MagnifierGrid::sample(image, center, radius)?,
+        })
+    }
+}
+
+/// 最近取过的颜色，最新的在前，不重复
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColorHistory {
+    colors: Vec<PixelColor>,
+    capacity: usize,
+}
+
+impl ColorHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self { colors: Vec::new(), capacity: capacity.max(1) }
+    }
+
+    /// 加到最前面，已存在时移到最前面
+    pub fn push(&mut self, color: PixelColor) {
+        self.colors.retain(|c| *c != color);
+        self.colors.insert(0, color);
+        self.colors.truncate(self.capacity);
+    }
+
+    pub fn colors(&self) -> &[PixelColor] {
+        &self.colors
+    }
+
+    pub fn clear(&mut self) {
+        self.colors.clear();
+    }
+}
+
+impl Default for ColorHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_COLOR_HISTORY_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gradient_image(width: u32, height: u32) -> Image {
+        let mut data = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                data.extend_from_slice(&[x as u8, y as u8, 0, 255]);
+            }
+        }
+        Image::new(data, width, height, ColorFormat::RGBA).unwrap()
+    }
+
+    #[test]
+    fn test_color_formats() {
+        let red = PixelColor::new(255, 0, 0, 255).formats();
+        assert_eq!(red.hex, "#FF0000");
+        assert_eq!(red.rgb, "rgb(255, 0, 0)");
+        assert_eq!(red.hsl, "hsl(0, 100%, 50%)");
+        assert_eq!(red.oklch, "oklch(62.8% 0.258 29.2)");
+
+        let teal = PixelColor::new(0, 128, 128, 255);
+        assert_eq!(teal.to_hsl().to_string(), "hsl(180, 100%, 25%)");
+
+        let white = PixelColor::new(255, 255, 255, 255);
+        assert_eq!(white.to_hsl().to_string(), "hsl(0, 0%, 100%)");
+        assert_eq!(white.to_oklch().to_string(), "oklch(100.0% 0.000 0.0)");
+
+        let blue = PixelColor::new(0, 0, 255, 255).to_oklch();
+        assert!((blue.l - 0.452).abs() < 1e-3);
+        assert!((blue.c - 0.313).abs() < 1e-3);
+        assert!((blue.h - 264.05).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_session_sample_with_scale() {
+        // 逻辑坐标 100x50 的显示器，截图为 2 倍分辨率
+        let session = ColorPickerSession::new()
+            .with_capture(Rectangle::from_bounds(-100, 0, 0, 50), gradient_image(200, 100));
+
+        let sample = session.sample(Point::new(-90, 5), 1).unwrap();
+        assert_eq!(sample.color, PixelColor::new(20, 10, 0, 255));
+        assert_eq!(sample.formats.hex, "#140A00");
+        assert_eq!(sample.magnifier.size, 3);
+        assert_eq!(sample.magnifier.color_at(1, 1), Some(sample.color));
+        assert_eq!(sample.magnifier.color_at(0, 0), Some(PixelColor::new(19, 9, 0, 255)));
+
+        // 边缘外的像素为透明
+        let corner = session.sample(Point::new(-100, 0), 1).unwrap();
+        assert_eq!(corner.magnifier.color_at(0, 0), Some(PixelColor::TRANSPARENT));
+        assert_eq!(corner.magnifier.color_at(1, 1), Some(PixelColor::new(0, 0, 0, 255)));
+
+        assert!(session.sample(Point::new(0, 0), 1).is_err());
+        assert!(session.sample(Point::new(-90, 5), MAX_MAGNIFIER_RADIUS + 1).is_err());
+    }
+
+    #[test]
+    fn test_color_history() {
+        let red = PixelColor::new(255, 0, 0, 255);
+        let green = PixelColor::new(0, 255, 0, 255);
+        let blue = PixelColor::new(0, 0, 255, 255);
+
+        let mut history = ColorHistory::new(2);
+        history.push(red);
+        history.push(green);
+        history.push(red);
+        assert_eq!(history.colors(), &[red, green]);
+
+        history.push(blue);
+        assert_eq!(history.colors(), &[blue, red]);
+
+        history.clear();
+        assert!(history.colors().is_empty());
+    }
+}
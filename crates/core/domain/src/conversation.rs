@@ -0,0 +1,215 @@
+use crate::llm::{ChatMessage, ChatRole};
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+
+/// 新对话的默认标题，收到第一条用户消息后替换
+pub const DEFAULT_CONVERSATION_TITLE: &str = "New Chat";
+
+/// 自动生成的标题的最大字符数
+const TITLE_MAX_CHARS: usize = 30;
+
+/// AI 对话
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Conversation {
+    pub id: i64,
+    pub title: String,
+    /// 最近一次回复使用的模型
+    pub model: Option<String>,
+    pub message_count: u32,
+    /// 创建时间 (Unix 毫秒)
+    pub created_at: i64,
+    /// 最近一条消息的时间 (Unix 毫秒)
+    pub updated_at: i64,
+}
+
+/// 对话中的消息
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationMessage {
+    pub id: i64,
+    pub conversation_id: i64,
+    pub role: ChatRole,
+    pub content: String,
+    /// 生成回复的模型，用户消息为 None
+    pub model: Option<String>,
+    /// Unix 毫秒
+    pub created_at: i64,
+    /// 生成回复的耗时 (毫秒)
+    pub duration_ms: Option<u64>,
+}
+
+impl ConversationMessage {
+    pub fn to_chat_message(&self) -> ChatMessage {
+        ChatMessage::new(self.role, self.content.clone())
+    }
+}
+
+/// 待保存的消息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewConversationMessage {
+    pub role: ChatRole,
+    pub content: String,
+    pub model: Option<String>,
+    pub duration_ms: Option<u64>,
+}
+
+impl NewConversationMessage {
+    pub fn user(content: impl Into<String>) -> Self {
+        Self { role: ChatRole::User, content: content.into(), model: None, duration_ms: None }
+    }
+
+    pub fn assistant(
+        content: impl Into<String>,
+        model: impl Into<String>,
+        duration_ms: u64,
+    ) -> Self {
+        Self {
+            role: ChatRole::Assistant,
+            content: content.into(),
+            model: Some(model.into()),
+            duration_ms: Some(duration_ms),
+        }
+    }
+}
+
+/// 对话查询
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversationQuery {
+    /// 匹配标题或消息内容，为空时返回全部
+    pub search: Option<String>,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+impl Default for ConversationQuery {
+    fn default() -> Self {
+        Self { search: None, limit: 50, offset: 0 }
+    }
+}
+
+impl ConversationQuery {
+    pub fn with_search(mut self, search: impl Into<String>) -> Self {
+        let search = search.into();
+        self.search = if search.trim().is_empty() { None } else { Some(search) };
+        self
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+/// 根据第一条用户消息生成标题
+pub fn conversation_title(content: &str) -> String {
+    let line = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.is_empty() {
+        return DEFAULT_CONVERSATION_TITLE.to_string();
+    }
+    if line.chars().count() <= TITLE_MAX_CHARS {
+        return line;
+    }
+    let mut title: String = line.chars().take(TITLE_MAX_CHARS).collect();
+    title.push_str("...");
+    title
+}
+
+fn format_time(ms: i64) -> String {
+    Local
+        .timestamp_millis_opt(ms)
+        .single()
+        .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+/// 导出为 Markdown
+pub fn conversation_markdown(
+    conversation: &Conversation,
+    messages: &[ConversationMessage],
+) -> String {
+    let mut markdown = format!("# {}\n\n", conversation.title);
+    markdown.push_str(&format!("- Created: {}\n", format_time(conversation.created_at)));
+    markdown.push_str(&format!("- Updated: {}\n", format_time(conversation.updated_at)));
+    if let Some(model) = &conversation.model {
+        markdown.push_str(&format!("- Model: {}\n", model));
+    }
+
+    for message in messages {
+        let heading = match message.role {
+            ChatRole::System => "System",
+            ChatRole::User => "User",
+            ChatRole::Assistant => "Assistant",
+        };
+        let mut details = vec![format_time(message.created_at)];
+        if let Some(model) = &message.model {
+            details.push(model.clone());
+        }
+        if let Some(duration_ms) = message.duration_ms {
+            details.push(format!("{:.1}s", duration_ms as f64 / 1000.0));
+        }
+        markdown.push_str(&format!(
+            "\n## {}\n\n_{}_\n\n{}\n",
+            heading,
+            details.join(" · "),
+            message.content.trim_end()
+        ));
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversation_title() {
+        assert_eq!(conversation_title("  hello\n world "), "hello world");
+        assert_eq!(conversation_title(" \n"), DEFAULT_CONVERSATION_TITLE);
+        assert_eq!(
+            conversation_title(&"字".repeat(40)),
+            format!("{}...", "字".repeat(TITLE_MAX_CHARS))
+        );
+    }
+
+    #[test]
+    fn test_conversation_markdown() {
+        let conversation = Conversation {
+            id: 1,
+            title: "Rust lifetimes".to_string(),
+            model: Some("gpt-4".to_string()),
+            message_count: 2,
+            created_at: 0,
+            updated_at: 0,
+        };
+        let messages = vec![
+            ConversationMessage {
+                id: 1,
+                conversation_id: 1,
+                role: ChatRole::User,
+                content: "What is 'a?".to_string(),
+                model: None,
+                created_at: 0,
+                duration_ms: None,
+            },
+            ConversationMessage {
+                id: 2,
+                conversation_id: 1,
+                role: ChatRole::Assistant,
+                content: "A lifetime.\n".to_string(),
+                model: Some("gpt-4".to_string()),
+                created_at: 0,
+                duration_ms: Some(1500),
+            },
+        ];
+
+        let markdown = conversation_markdown(&conversation, &messages);
+        assert!(markdown.starts_with("# Rust lifetimes\n\n- Created: "));
+        assert!(markdown.contains("- Model: gpt-4\n"));
+        assert!(markdown.contains("\n## User\n\n_"));
+        assert!(markdown.contains(" · gpt-4 · 1.5s_\n\nA lifetime.\n"));
+    }
+}
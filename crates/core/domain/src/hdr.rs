@@ -0,0 +1,388 @@
+use crate::image::{ColorFormat, Image, ImageMetadata, ImageSource};
+use crate::screenshot::CaptureMetadata;
+use serde::{Deserialize, Serialize};
+
+/// scRGB 中 1.0 对应的亮度 (nits)
+pub const SCRGB_WHITE_NITS: f32 = 80.0;
+
+/// PQ (SMPTE ST 2084) 能表示的最大亮度 (nits)
+pub const PQ_MAX_NITS: f32 = 10000.0;
+
+/// 监视器 HDR 信息
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HdrInfo {
+    pub is_hdr_enabled: bool,
+    /// 峰值亮度 (nits)
+    pub peak_luminance: f32,
+    /// 最低亮度 (nits)
+    pub min_luminance: f32,
+    /// 全屏持续亮度 (nits)
+    pub max_full_frame_luminance: f32,
+    /// SDR 内容的白色亮度 (nits)，Windows 中由 "SDR 内容亮度" 滑块决定
+    pub sdr_white_level: f32,
+}
+
+impl Default for HdrInfo {
+    /// SDR 显示器：峰值等于 SDR 白
+    fn default() -> Self {
+        Self {
+            is_hdr_enabled: false,
+            peak_luminance: SCRGB_WHITE_NITS,
+            min_luminance: 0.0,
+            max_full_frame_luminance: SCRGB_WHITE_NITS,
+            sdr_white_level: SCRGB_WHITE_NITS,
+        }
+    }
+}
+
+impl HdrInfo {
+    /// HDR 显示器
+    pub fn hdr(peak_luminance: f32, sdr_white_level: f32) -> Self {
+        Self {
+            is_hdr_enabled: true,
+            peak_luminance,
+            min_luminance: 0.0,
+            max_full_frame_luminance: peak_luminance,
+            sdr_white_level,
+        }
+    }
+
+    /// 峰值亮度相对 SDR 白的倍数，至少为 1
+    pub fn headroom(&self) -> f32 {
+        let white = self.sdr_white_level.max(1.0);
+        (self.peak_luminance / white).max(1.0)
+    }
+}
+
+/// HDR 到 SDR 的色调映射算子
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToneMappingOperator {
+    /// ACES filmic 曲线 (Narkowicz 拟合)，逐通道
+    Aces,
+    /// Hable (Uncharted 2) filmic 曲线，逐通道
+    Hable,
+    /// ITU-R BT.2390 EETF，在 PQ 域压缩 max(R, G, B)，保持色相
+    #[default]
+    Bt2390,
+}
+
+/// 高位深线性图像
+///
+/// 每像素 4 个 f32 (RGBA)，RGB 为 scRGB 线性值 (BT.709 原色，1.0 = 80 nits)，
+/// 与 Windows FP16 桌面复制的格式一致
+#[derive(Debug, Clone, PartialEq)]
+pub struct HdrImage {
+    pub data: Vec<f32>,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl HdrImage {
+    pub fn new(data: Vec<f32>, width: u32, height: u32) -> Result<Self, String> {
+        let expected = (width as usize) * (height as usize) * 4;
+        if data.len() != expected {
+            return Err(format!(
+                "Invalid HDR image data size: expected {}, got {}",
+                expected,
+                data.len()
+            ));
+        }
+        Ok(Self { data, width, height })
+    }
+
+    /// 从 8 位 sRGB 图像创建，1.0 对应 `sdr_white_level`
+    pub fn from_sdr(image: &Image, sdr_white_level: f32) -> Result<Self, String> {
+        let rgba = image.to_rgba()?;
+        let scale = sdr_white_level / SCRGB_WHITE_NITS;
+        let data = rgba
+            .data
+            .as_chunks::<4>()
+            .0
+            .iter()
+            .flat_map(|p| {
+                [
+                    srgb_to_linear(p[0] as f32 / 255.0) * scale,
+                    srgb_to_linear(p[1] as f32 / 255.0) * scale,
+                    srgb_to_linear(p[2] as f32 / 255.0) * scale,
+                    p[3] as f32 / 255.0,
+                ]
+            })
+            .collect();
+        Self::new(data, rgba.width, rgba.height)
+    }
+
+    /// 裁剪出左上角为 `(x, y)` 的区域
+    pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Result<Self, String> {
+        let fits =
+            |start: u32, len: u32, max: u32| start.checked_add(len).is_some_and(|end| end <= max);
+        if !fits(x, width, self.width) || !fits(y, height, self.height) {
+            return Err("Crop region out of bounds".to_string());
+        }
+
+        let stride = self.width as usize * 4;
+        let (left, row_len) = (x as usize * 4, width as usize * 4);
+        let data = (y..y + height)
+            .flat_map(|row| {
+                let start = row as usize * stride + left;
+                self.data[start..start + row_len].iter().copied()
+            })
+            .collect();
+        Self::new(data, width, height)
+    }
+
+    /// 逐像素色调映射为 8 位 sRGB 图像
+    pub fn tone_map(&self, mapper: &ToneMapper) -> Result<Image, String> {
+        let data: Vec<u8> =
+            self.data.as_chunks::<4>().0.iter().flat_map(|src| mapper.map_pixel(src)).collect();
+        Image::with_metadata(
+            data,
+            self.width,
+            self.height,
+            ColorFormat::RGBA,
+            ImageMetadata::new(ImageSource::Screenshot),
+        )
+    }
+}
+
+/// 浮点截图及其捕获信息
+#[derive(Debug, Clone)]
+pub struct HdrScreenshot {
+    pub image: HdrImage,
+    pub capture_metadata: CaptureMetadata,
+}
+
+impl HdrScreenshot {
+    pub fn new(image: HdrImage, capture_metadata: CaptureMetadata) -> Self {
+        Self { image, capture_metadata }
+    }
+}
+
+/// 按监视器的峰值亮度和 SDR 白把 scRGB 像素映射到 SDR
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToneMapper {
+    operator: ToneMappingOperator,
+    /// scRGB 值到 "SDR 白 = 1.0" 的缩放
+    input_scale: f32,
+    /// 峰值亮度相对 SDR 白的倍数
+    headroom: f32,
+    sdr_white_level: f32,
+    peak_luminance: f32,
+}
+
+impl ToneMapper {
+    pub fn new(operator: ToneMappingOperator, hdr_info: &HdrInfo) -> Self {
+        let sdr_white_level = hdr_info.sdr_white_level.max(1.0);
+        let headroom = hdr_info.headroom();
+        Self {
+            operator,
+            input_scale: SCRGB_WHITE_NITS / sdr_white_level,
+            headroom,
+            sdr_white_level,
+            peak_luminance: sdr_white_level * headroom,
+        }
+    }
+
+    pub fn operator(&self) -> ToneMappingOperator {
+        self.operator
+    }
+
+    /// 映射线性 RGB (1.0 = SDR 白)，返回 [0, 1] 内的线性 RGB
+    pub fn map_linear(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let rgb = rgb.map(|c| if c.is_finite() { c.max(0.0) } else { 0.0 });
+        match self.operator {
+            ToneMappingOperator::Aces => {
+                let white = aces(self.headroom);
+                rgb.map(|c| (aces(c) / white).min(1.0))
+            }
+            ToneMappingOperator::Hable => {
+                let white = hable(self.headroom);
+                rgb.map(|c| (hable(c) / white).min(1.0))
+            }
+            ToneMappingOperator::Bt2390 => {
+                let max = rgb[0].max(rgb[1]).max(rgb[2]);
+                if max <= 0.0 {
+                    return [0.0; 3];
+                }
+                let mapped = self.bt2390(max * self.sdr_white_level) / self.sdr_white_level;
+                let ratio = mapped / max;
+                rgb.map(|c| (c * ratio).min(1.0))
+            }
+        }
+    }
+
+    /// 映射一个 scRGB RGBA 像素为 8 位 sRGB
+    pub fn map_pixel(&self, pixel: &[f32]) -> [u8; 4] {
+        let linear =
+            [pixel[0] * self.input_scale, pixel[1] * self.input_scale, pixel[2] * self.input_scale];
+        let [r, g, b] = self.map_linear(linear);
+        [
+            to_u8(linear_to_srgb(r)),
+            to_u8(linear_to_srgb(g)),
+            to_u8(linear_to_srgb(b)),
+            to_u8(pixel[3]),
+        ]
+    }
+
+    /// BT.2390 EETF：源范围 [0, 峰值]，目标范围 [0, SDR 白]
+    fn bt2390(&self, nits: f32) -> f32 {
+        let source_max = pq_encode(self.peak_luminance);
+        let target_max = pq_encode(self.sdr_white_level) / source_max;
+        let e1 = pq_encode(nits) / source_max;
+
+        let knee_start = 1.5 * target_max - 0.5;
+        let e2 = if knee_start >= 1.0 {
+            // 峰值不超过 SDR 白，无需压缩
+            e1.min(1.0)
+        } else if e1 < knee_start {
+            e1
+        } else {
+            // Hermite 样条压缩 [knee_start, 1] 到 [knee_start, target_max]
+            let t = ((e1 - knee_start) / (1.0 - knee_start)).min(1.0);
+            let t2 = t * t;
+            let t3 = t2 * t;
+            (2.0 * t3 - 3.0 * t2 + 1.0) * knee_start
+                + (t3 - 2.0 * t2 + t) * (1.0 - knee_start)
+                + (-2.0 * t3 + 3.0 * t2) * target_max
+        };
+        pq_decode(e2 * source_max)
+    }
+}
+
+/// ACES filmic 曲线 (Krzysztof Narkowicz 拟合)
+fn aces(x: f32) -> f32 {
+    (x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14)
+}
+
+/// Hable filmic 曲线
+fn hable(x: f32) -> f32 {
+    const A: f32 = 0.15;
+    const B: f32 = 0.50;
+    const C: f32 = 0.10;
+    const D: f32 = 0.20;
+    const E: f32 = 0.02;
+    const F: f32 = 0.30;
+    ((x * (A * x + C * B) + D * E) / (x * (A * x + B) + D * F)) - E / F
+}
+
+// SMPTE ST 2084 常量
+const PQ_M1: f32 = 2610.0 / 16384.0;
+const PQ_M2: f32 = 2523.0 / 4096.0 * 128.0;
+const PQ_C1: f32 = 3424.0 / 4096.0;
+const PQ_C2: f32 = 2413.0 / 4096.0 * 32.0;
+const PQ_C3: f32 = 2392.0 / 4096.0 * 32.0;
+
+/// 亮度 (nits) 编码为 PQ 信号值 [0, 1]
+pub fn pq_encode(nits: f32) -> f32 {
+    let y = (nits / PQ_MAX_NITS).clamp(0.0, 1.0).powf(PQ_M1);
+    ((PQ_C1 + PQ_C2 * y) / (1.0 + PQ_C3 * y)).powf(PQ_M2)
+}
+
+/// PQ 信号值解码为亮度 (nits)
+pub fn pq_decode(signal: f32) -> f32 {
+    let e = signal.clamp(0.0, 1.0).powf(1.0 / PQ_M2);
+    let y = ((e - PQ_C1).max(0.0) / (PQ_C2 - PQ_C3 * e)).powf(1.0 / PQ_M1);
+    y * PQ_MAX_NITS
+}
+
+pub fn srgb_to_linear(v: f32) -> f32 {
+    if v <= 0.04045 { v / 12.92 } else { ((v + 0.055) / 1.055).powf(2.4) }
+}
+
+pub fn linear_to_srgb(v: f32) -> f32 {
+    let v = v.clamp(0.0, 1.0);
+    if v <= 0.0031308 { v * 12.92 } else { 1.055 * v.powf(1.0 / 2.4) - 0.055 }
+}
+
+fn to_u8(v: f32) -> u8 {
+    (v.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    /// 参考输出由 `testdata/gen_tone_mapping.py` 按公开公式以双精度独立计算
+    const FIXTURES: &str = include_str!("testdata/tone_mapping.json");
+
+    #[derive(Deserialize)]
+    struct Fixture {
+        operator: ToneMappingOperator,
+        peak_luminance: f32,
+        sdr_white_level: f32,
+        /// scRGB RGBA
+        input: [f32; 4],
+        /// 8 位 sRGB RGBA
+        expected: [u8; 4],
+    }
+
+    #[test]
+    fn test_tone_mapping_fixtures() {
+        let fixtures: Vec<Fixture> = serde_json::from_str(FIXTURES).unwrap();
+        assert!(!fixtures.is_empty());
+        for fixture in fixtures {
+            let info = HdrInfo::hdr(fixture.peak_luminance, fixture.sdr_white_level);
+            let actual = ToneMapper::new(fixture.operator, &info).map_pixel(&fixture.input);
+            for (a, e) in actual.iter().zip(fixture.expected) {
+                assert!(
+                    a.abs_diff(e) <= 1,
+                    "{:?} peak={} white={} input={:?}: expected {:?}, got {:?}",
+                    fixture.operator,
+                    fixture.peak_luminance,
+                    fixture.sdr_white_level,
+                    fixture.input,
+                    fixture.expected,
+                    actual
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_tone_mapping_properties() {
+        let info = HdrInfo::hdr(1000.0, 200.0);
+        for operator in
+            [ToneMappingOperator::Aces, ToneMappingOperator::Hable, ToneMappingOperator::Bt2390]
+        {
+            let mapper = ToneMapper::new(operator, &info);
+            // 峰值映射到白，黑色保持黑色
+            let peak = 1000.0 / SCRGB_WHITE_NITS;
+            assert_eq!(mapper.map_pixel(&[peak, peak, peak, 1.0]), [255, 255, 255, 255]);
+            assert_eq!(mapper.map_pixel(&[0.0, 0.0, 0.0, 1.0]), [0, 0, 0, 255]);
+            // 单调
+            let mut last = 0;
+            for i in 0..=50 {
+                let v = peak * i as f32 / 50.0;
+                let [r, ..] = mapper.map_pixel(&[v, v, v, 1.0]);
+                assert!(r >= last, "{:?} is not monotonic at {}", operator, v);
+                last = r;
+            }
+        }
+
+        // PQ 往返
+        for nits in [0.0, 1.0, 80.0, 203.0, 1000.0, 10000.0] {
+            assert!((pq_decode(pq_encode(nits)) - nits).abs() <= nits * 1e-3 + 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_hdr_image() {
+        let sdr = Image::new(vec![255, 128, 0, 255], 1, 1, ColorFormat::RGBA).unwrap();
+        let hdr = HdrImage::from_sdr(&sdr, SCRGB_WHITE_NITS).unwrap();
+        assert!((hdr.data[0] - 1.0).abs() < 1e-6);
+
+        // BT.2390 在 SDR 显示器上不压缩，8 位往返不变
+        let mapper = ToneMapper::new(ToneMappingOperator::Bt2390, &HdrInfo::default());
+        assert_eq!(hdr.tone_map(&mapper).unwrap().data, sdr.data);
+
+        assert!(HdrImage::new(vec![0.0; 3], 1, 1).is_err());
+
+        let data: Vec<f32> = (0..3 * 2 * 4).map(|i| i as f32).collect();
+        let image = HdrImage::new(data, 3, 2).unwrap();
+        let cropped = image.crop(1, 1, 2, 1).unwrap();
+        assert_eq!(cropped.data, vec![16.0, 17.0, 18.0, 19.0, 20.0, 21.0, 22.0, 23.0]);
+        assert!(image.crop(2, 0, 2, 1).is_err());
+        assert!(image.crop(0, u32::MAX, 1, 2).is_err());
+    }
+}
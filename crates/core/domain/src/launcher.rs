@@ -0,0 +1,387 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// 启动项类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchItemKind {
+    Application,
+    File,
+    Folder,
+}
+
+/// 启动器索引中的条目
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchItem {
+    /// 稳定的标识，用于记录使用频率
+    pub id: String,
+    pub kind: LaunchItemKind,
+    pub name: String,
+    pub description: Option<String>,
+    pub keywords: Vec<String>,
+    /// 入口文件 (`.desktop` / `.app` / `.lnk`) 或文件路径
+    pub path: String,
+    /// 启动命令，为空时用系统默认方式打开 `path`
+    pub command: Vec<String>,
+    /// 在终端模拟器中运行 `command`
+    #[serde(default)]
+    pub terminal: bool,
+    /// 图标文件路径
+    pub icon: Option<String>,
+}
+
+/// 启动项的使用记录
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchUsage {
+    pub count: u32,
+    /// 最近一次启动的时间 (Unix 毫秒)
+    pub last_used: i64,
+}
+
+impl LaunchUsage {
+    /// 记录一次启动
+    pub fn record(&mut self, now: i64) {
+        self.count = self.count.saturating_add(1);
+        self.last_used = now;
+    }
+
+    /// Frecency 分数：启动次数按最近一次启动的时间加权
+    pub fn frecency(&self, now: i64) -> f64 {
+        let weight = match (now - self.last_used).max(0) / DAY_MS {
+            0..=3 => 100.0,
+            4..=14 => 70.0,
+            15..=31 => 50.0,
+            32..=90 => 30.0,
+            _ => 10.0,
+        };
+        weight * self.count as f64
+    }
+}
+
+/// 索引刷新结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LauncherIndexStats {
+    /// 索引中的条目数
+    pub total: usize,
+    /// 新增或重新解析的条目数
+    pub updated: usize,
+    /// 移除的条目数
+    pub removed: usize,
+}
+
+/// 启动项图标
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchIcon {
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
+/// 排序后的搜索结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankedLaunchItem {
+    pub item: LaunchItem,
+    pub score: f64,
+}
+
+/// XDG `.desktop` 文件中的 `[Desktop Entry]`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DesktopEntry {
+    pub name: String,
+    pub generic_name: Option<String>,
+    pub comment: Option<String>,
+    pub keywords: Vec<String>,
+    pub exec: String,
+    pub icon: Option<String>,
+    pub terminal: bool,
+}
+
+fn unescape_desktop_value(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => result.push(' '),
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some(other) => {
+                // `\;` 等保留给列表解析
+                if other != '\\' {
+                    result.push('\\');
+                }
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// 解析 `.desktop` 文件
+///
+/// 只返回可以显示的应用：`Type=Application`、有 `Name` 和 `Exec`，
+/// 且没有 `NoDisplay=true` / `Hidden=true`。本地化的键被忽略
+pub fn parse_desktop_entry(content: &str) -> Option<DesktopEntry> {
+    let mut entry = DesktopEntry::default();
+    let mut in_entry = false;
+    let mut is_application = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_entry {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = unescape_desktop_value(value.trim());
+        match key.trim() {
+            "Type" => is_application = value == "Application",
+            "Name" => entry.name = value,
+            "GenericName" => entry.generic_name = Some(value),
+            "Comment" => entry.comment = Some(value),
+            "Keywords" => {
+                entry.keywords = value
+                    .split(';')
+                    .map(|keyword| keyword.replace('\\', "").trim().to_string())
+                    .filter(|keyword| !keyword.is_empty())
+                    .collect();
+            }
+            "Exec" => entry.exec = value,
+            "Icon" => entry.icon = Some(value).filter(|icon| !icon.is_empty()),
+            "Terminal" => entry.terminal = value == "true",
+            "NoDisplay" | "Hidden" if value == "true" => return None,
+            _ => {}
+        }
+    }
+
+    let usable = is_application && !entry.name.is_empty() && !entry.exec.trim().is_empty();
+    usable.then_some(entry)
+}
+
+/// 将 `Exec` 拆分为命令行参数，去掉 `%f`、`%U` 等字段代码
+pub fn desktop_exec_args(exec: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = exec.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => quoted = !quoted,
+            '\\' if quoted => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            '%' => {
+                if chars.next() == Some('%') {
+                    current.push('%');
+                }
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    args.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        args.push(current);
+    }
+    args
+}
+
+/// 模糊匹配时只看文本的前若干个字符
+const FUZZY_MAX_CHARS: usize = 128;
+
+fn fold_char(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// 模糊匹配分数，`query` 的字符必须按顺序出现在 `text` 中
+///
+/// 单词开头、连续匹配和前缀匹配加分，跳过的字符减分，取分数最高的匹配方式。
+/// 不匹配时返回 None
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).map(fold_char).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let chars: Vec<char> = text.chars().take(FUZZY_MAX_CHARS).collect();
+    let folded: Vec<char> = chars.iter().copied().map(fold_char).collect();
+    let n = chars.len();
+
+    let char_score: Vec<i64> = (0..n)
+        .map(|j| {
+            let word_start = j == 0
+                || !chars[j - 1].is_alphanumeric()
+                || (chars[j - 1].is_lowercase() && chars[j].is_uppercase());
+            if word_start { 48 } else { 16 }
+        })
+        .collect();
+
+    // scores[j]: 当前查询字符匹配 text[j] 时的最高分
+    let mut scores: Vec<Option<i64>> = (0..n)
+        .map(|j| {
+            let lead = if j == 0 { 48 } else { -(j as i64).min(15) };
+            (folded[j] == query[0]).then(|| char_score[j] + lead)
+        })
+        .collect();
+    for q in &query[1..] {
+        let mut next = vec![None; n];
+        let mut best_before_gap = None;
+        for j in 1..n {
+            if j >= 2 {
+                best_before_gap = best_before_gap.max(scores[j - 2]);
+            }
+            if folded[j] != *q {
+                continue;
+            }
+            let consecutive = scores[j - 1].map(|score| score + 16);
+            let gap = best_before_gap.map(|score| score - 10);
+            next[j] = consecutive.max(gap).map(|score| score + char_score[j]);
+        }
+        scores = next;
+    }
+    let mut score = scores.into_iter().flatten().max()?;
+
+    let query: String = query.into_iter().collect();
+    let text: String = folded.into_iter().filter(|c| !c.is_whitespace()).collect();
+    if text.starts_with(&query) {
+        score += 100;
+    } else if text.contains(&query) {
+        score += 50;
+    }
+    Some(score.max(1) as u32)
+}
+
+/// 条目与查询的匹配分数，名称优先于关键词和描述
+fn match_score(item: &LaunchItem, query: &str) -> Option<f64> {
+    let name = fuzzy_score(query, &item.name).map(f64::from);
+    let keywords = item
+        .keywords
+        .iter()
+        .filter_map(|keyword| fuzzy_score(query, keyword))
+        .max()
+        .map(|score| f64::from(score) * 0.8);
+    let description = item
+        .description
+        .as_deref()
+        .and_then(|description| fuzzy_score(query, description))
+        .map(|score| f64::from(score) * 0.5);
+    [name, keywords, description].into_iter().flatten().reduce(f64::max)
+}
+
+/// 按匹配程度和使用频率排序
+///
+/// 查询为空时只返回用过的条目，按 frecency 排序
+pub fn rank_launch_items(
+    items: &[LaunchItem],
+    usage: &HashMap<String, LaunchUsage>,
+    query: &str,
+    now: i64,
+    limit: usize,
+) -> Vec<RankedLaunchItem> {
+    let query = query.trim();
+    let mut ranked: Vec<RankedLaunchItem> = items
+        .iter()
+        .filter_map(|item| {
+            let frecency = usage.get(&item.id).map(|usage| usage.frecency(now)).unwrap_or(0.0);
+            let score = if query.is_empty() {
+                if frecency <= 0.0 {
+                    return None;
+                }
+                frecency
+            } else {
+                let kind_bonus = if item.kind == LaunchItemKind::Application { 10.0 } else { 0.0 };
+                match_score(item, query)? + 20.0 * frecency.ln_1p() + kind_bonus
+            };
+            Some(RankedLaunchItem { item: item.clone(), score })
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.item.name.cmp(&b.item.name)));
+    ranked.truncate(limit);
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(id: &str, name: &str) -> LaunchItem {
+        LaunchItem {
+            id: id.to_string(),
+            kind: LaunchItemKind::Application,
+            name: name.to_string(),
+            description: None,
+            keywords: Vec::new(),
+            path: String::new(),
+            command: Vec::new(),
+            terminal: false,
+            icon: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_desktop_entry() {
+        let content = "# comment\n[Desktop Entry]\nType=Application\nName=Text Editor\n\
+            Name[de]=Texteditor\nGenericName=Editor\nKeywords=text;notes;\n\
+            Exec=\"/opt/my editor/bin\" --new-window %U\nIcon=editor\n\
+            [Desktop Action new]\nName=New Window\nExec=other\n";
+        let entry = parse_desktop_entry(content).unwrap();
+        assert_eq!(entry.name, "Text Editor");
+        assert_eq!(entry.keywords, vec!["text", "notes"]);
+        assert_eq!(entry.icon.as_deref(), Some("editor"));
+        assert_eq!(desktop_exec_args(&entry.exec), vec!["/opt/my editor/bin", "--new-window"]);
+
+        let hidden = "[Desktop Entry]\nType=Application\nName=Hidden\nExec=x\nNoDisplay=true\n";
+        assert_eq!(parse_desktop_entry(hidden), None);
+        let link = "[Desktop Entry]\nType=Link\nName=Site\nURL=https://example.com\n";
+        assert_eq!(parse_desktop_entry(link), None);
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("fx", "Firefox").is_some());
+        assert!(fuzzy_score("xf", "Firefox").is_none());
+        // 前缀和单词开头优先
+        assert!(fuzzy_score("code", "Visual Studio Code") > fuzzy_score("code", "Decoder"));
+        assert!(fuzzy_score("term", "Terminal") > fuzzy_score("term", "Alacritty Terminal"));
+        assert!(fuzzy_score("vsc", "Visual Studio Code") > fuzzy_score("vsc", "Vim Script"));
+    }
+
+    #[test]
+    fn test_rank_launch_items() {
+        let now = 100 * DAY_MS;
+        let items = vec![app("files", "Files"), app("firefox", "Firefox"), app("gimp", "GIMP")];
+        let mut usage = HashMap::new();
+        usage.insert("firefox".to_string(), LaunchUsage { count: 5, last_used: now });
+
+        let names = |ranked: Vec<RankedLaunchItem>| -> Vec<String> {
+            ranked.into_iter().map(|r| r.item.name).collect()
+        };
+        // 匹配程度相同时常用的在前
+        assert_eq!(names(rank_launch_items(&items, &usage, "fi", now, 10)), ["Firefox", "Files"]);
+        assert_eq!(names(rank_launch_items(&items, &usage, "", now, 10)), ["Firefox"]);
+
+        // 久未使用的分数降低
+        let old = LaunchUsage { count: 5, last_used: 0 };
+        assert!(old.frecency(now) < usage["firefox"].frecency(now));
+    }
+}
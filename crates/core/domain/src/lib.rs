@@ -1,20 +1,50 @@
 // 领域模型定义
 
+pub mod annotation;
+pub mod barcode;
 pub mod clipboard;
+pub mod clipboard_history;
+pub mod color;
+pub mod conversation;
+pub mod hdr;
 pub mod hotkey;
 pub mod image;
+pub mod launcher;
+pub mod llm;
+pub mod measurement;
 pub mod page;
+pub mod pin;
+pub mod redaction;
 pub mod screenshot;
+pub mod screenshot_filename;
+pub mod screenshot_library;
+pub mod secret;
 pub mod settings;
 pub mod storage;
+pub mod text_recognition;
 pub mod window;
 
 // Re-export for convenience
+pub use annotation::*;
+pub use barcode::*;
 pub use clipboard::*;
+pub use clipboard_history::*;
+pub use color::*;
+pub use conversation::*;
+pub use hdr::*;
 pub use hotkey::*;
 pub use image::*;
+pub use launcher::*;
+pub use llm::*;
+pub use measurement::*;
 pub use page::*;
+pub use pin::*;
+pub use redaction::*;
 pub use screenshot::*;
+pub use screenshot_filename::*;
+pub use screenshot_library::*;
+pub use secret::*;
 pub use settings::*;
 pub use storage::*;
+pub use text_recognition::*;
 pub use window::*;
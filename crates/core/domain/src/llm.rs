@@ -0,0 +1,178 @@
+use crate::settings::{AIDialogueSettings, ExpressionPolishingSettings};
+use serde::{Deserialize, Serialize};
+
+/// 对话消息角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatRole {
+    System,
+    User,
+    Assistant,
+}
+
+impl ChatRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::System => "system",
+            Self::User => "user",
+            Self::Assistant => "assistant",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "system" => Some(Self::System),
+            "user" => Some(Self::User),
+            "assistant" => Some(Self::Assistant),
+            _ => None,
+        }
+    }
+}
+
+/// 对话消息
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: ChatRole,
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn new(role: ChatRole, content: impl Into<String>) -> Self {
+        Self { role, content: content.into() }
+    }
+
+    pub fn system(content: impl Into<String>) -> Self {
+        Self::new(ChatRole::System, content)
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        Self::new(ChatRole::User, content)
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self::new(ChatRole::Assistant, content)
+    }
+}
+
+/// OpenAI 兼容接口的连接参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LlmEndpoint {
+    /// 接口根地址，如 `https://api.openai.com/v1`
+    pub api_url: String,
+    pub api_key: String,
+    pub model: String,
+}
+
+impl LlmEndpoint {
+    /// 聊天补全接口地址
+    pub fn chat_completions_url(&self) -> String {
+        format!("{}/chat/completions", self.api_url.trim_end_matches('/'))
+    }
+}
+
+/// 一次聊天补全请求
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatCompletionRequest {
+    pub endpoint: LlmEndpoint,
+    pub messages: Vec<ChatMessage>,
+}
+
+impl ChatCompletionRequest {
+    /// 表达润色请求
+    pub fn polish(settings: &ExpressionPolishingSettings, api_key: String, input: &str) -> Self {
+        Self {
+            endpoint: LlmEndpoint {
+                api_url: settings.api_url.clone(),
+                api_key,
+                model: settings.model.clone(),
+            },
+            messages: vec![
+                ChatMessage::system(settings.system_prompt.clone()),
+                ChatMessage::user(input),
+            ],
+        }
+    }
+
+    /// AI 对话请求，历史消息按 `max_history_messages` 截断
+    pub fn dialogue(
+        settings: &AIDialogueSettings,
+        api_key: String,
+        history: &[ChatMessage],
+    ) -> Self {
+        let mut messages = vec![ChatMessage::system(settings.system_prompt.clone())];
+        messages.extend(truncate_history(history, settings.max_history_messages));
+        Self {
+            endpoint: LlmEndpoint {
+                api_url: settings.api_url.clone(),
+                api_key,
+                model: settings.model.clone(),
+            },
+            messages,
+        }
+    }
+}
+
+/// 保留最近的 `max_messages` 条对话消息
+///
+/// 系统消息和空消息会被丢弃，截断后以用户消息开头。最后一条消息总是保留，
+/// 即使 `max_messages` 为 0
+pub fn truncate_history(history: &[ChatMessage], max_messages: i32) -> Vec<ChatMessage> {
+    let messages: Vec<&ChatMessage> = history
+        .iter()
+        .filter(|m| m.role != ChatRole::System && !m.content.trim().is_empty())
+        .collect();
+    let keep = (max_messages.max(1) as usize).min(messages.len());
+    let mut recent = &messages[messages.len() - keep..];
+    while recent.len() > 1 && recent[0].role == ChatRole::Assistant {
+        recent = &recent[1..];
+    }
+    recent.iter().map(|m| (*m).clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_history() {
+        let history = vec![
+            ChatMessage::user("1"),
+            ChatMessage::assistant("2"),
+            ChatMessage::system("ignored"),
+            ChatMessage::user("3"),
+            ChatMessage::assistant(""),
+            ChatMessage::assistant("4"),
+            ChatMessage::user("5"),
+        ];
+        let contents = |max| -> Vec<String> {
+            truncate_history(&history, max).into_iter().map(|m| m.content).collect()
+        };
+
+        assert_eq!(contents(10), vec!["1", "2", "3", "4", "5"]);
+        assert_eq!(contents(3), vec!["3", "4", "5"]);
+        // 不以助手消息开头
+        assert_eq!(contents(2), vec!["5"]);
+        assert_eq!(contents(0), vec!["5"]);
+    }
+
+    #[test]
+    fn test_dialogue_request() {
+        let settings = AIDialogueSettings {
+            api_url: "http://localhost:8080/v1/".to_string(),
+            max_history_messages: 2,
+            ..Default::default()
+        };
+        let history =
+            vec![ChatMessage::user("a"), ChatMessage::assistant("b"), ChatMessage::user("c")];
+        let request = ChatCompletionRequest::dialogue(&settings, "key".to_string(), &history);
+
+        assert_eq!(
+            request.endpoint.chat_completions_url(),
+            "http://localhost:8080/v1/chat/completions"
+        );
+        assert_eq!(
+            request.messages,
+            vec![ChatMessage::system(settings.system_prompt.clone()), ChatMessage::user("c")]
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use aumate_core_shared::{DomainError, Point, Rectangle};
+use serde::{Deserialize, Serialize};
+
+/// 长度，同时给出物理像素和逻辑像素
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MeasuredLength {
+    pub physical: u32,
+    /// 物理像素 / 缩放比例，保留两位小数
+    pub logical: f64,
+}
+
+impl MeasuredLength {
+    pub fn new(physical: u32, scale_factor: f64) -> Self {
+        let logical = (physical as f64 / scale_factor * 100.0).round() / 100.0;
+        Self { physical, logical }
+    }
+}
+
+/// 边缘图，逐像素标记是否为边缘
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeMap {
+    width: u32,
+    height: u32,
+    edges: Vec<bool>,
+}
+
+impl EdgeMap {
+    /// `edges` 按行排列，长度必须为 `width * height`
+    pub fn new(width: u32, height: u32, edges: Vec<bool>) -> Result<Self, DomainError> {
+        if width == 0 || height == 0 || edges.len() != width as usize * height as usize {
+            return Err(DomainError::InvalidDimensions(width, height));
+        }
+        Ok(Self { width, height, edges })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// 越界时返回 false
+    pub fn is_edge(&self, x: i32, y: i32) -> bool {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return false;
+        }
+        self.edges[y as usize * self.width as usize + x as usize]
+    }
+
+    /// 从 `point` 向四个方向找最近的边缘，返回它们之间的区域
+    ///
+    /// 区域不含边缘像素，`point` 本身是边缘时从相邻像素开始找。
+    /// 某个方向没有边缘时延伸到图像边界
+    pub fn spacing_bounds(&self, point: Point) -> Option<Rectangle> {
+        let (x, y) = (point.x, point.y);
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+
+        let min_x = (0..x).rev().find(|&i| self.is_edge(i, y)).map_or(0, |i| i + 1);
+        let max_x =
+            (x + 1..self.width as i32).find(|&i| self.is_edge(i, y)).unwrap_or(self.width as i32);
+        let min_y = (0..y).rev().find(|&i| self.is_edge(x, i)).map_or(0, |i| i + 1);
+        let max_y =
+            (y + 1..self.height as i32).find(|&i| self.is_edge(x, i)).unwrap_or(self.height as i32);
+        Some(Rectangle::from_bounds(min_x, min_y, max_x, max_y))
+    }
+}
+
+/// 光标周围的间距
+///
+/// 距离为光标与边缘之间的像素数，不含两端，
+/// 因此 `left + right + 1 == width`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Spacing {
+    /// 四个方向最近边缘之间的区域 (图像像素)
+    pub bounds: Rectangle,
+    pub left: MeasuredLength,
+    pub right: MeasuredLength,
+    pub top: MeasuredLength,
+    pub bottom: MeasuredLength,
+    pub width: MeasuredLength,
+    pub height: MeasuredLength,
+}
+
+/// 光标下的 UI 元素
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeasuredElement {
+    /// 元素边界 (图像像素)，已裁剪到截图内
+    pub bounds: Rectangle,
+    pub width: MeasuredLength,
+    pub height: MeasuredLength,
+    pub role: Option<String>,
+    pub title: Option<String>,
+}
+
+/// 一次测量的结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    /// 光标位置 (图像像素)
+    pub position: Point,
+    pub scale_factor: f64,
+    pub spacing: Spacing,
+    pub element: Option<MeasuredElement>,
+}
+
+/// 测量会话
+///
+/// 开始时对截图做一次边缘检测，之后每次光标移动只扫描边缘图。
+/// `bounds` 是截图对应的屏幕区域，与 UI 自动化使用同一坐标系；
+/// 截图可以是更高的物理分辨率，换算时按两者的比例。
+/// `scale_factor` 为显示器缩放比例，用于把物理像素换算为逻辑像素
+#[derive(Debug, Clone)]
+pub struct MeasurementSession {
+    bounds: Rectangle,
+    scale_factor: f64,
+    edges: EdgeMap,
+}
+
+impl MeasurementSession {
+    pub fn new(bounds: Rectangle, scale_factor: f64, edges: EdgeMap) -> Result<Self, DomainError> {
+        if scale_factor.is_nan() || scale_factor <= 0.0 {
+            return Err(DomainError::ValidationFailed(format!(
+                "scale factor must be positive, got {}",
+                scale_factor
+            )));
+        }
+        if bounds.width() == 0 || bounds.height() == 0 {
+            return Err(DomainError::InvalidDimensions(bounds.width(), bounds.height()));
+        }
+        Ok(Self { bounds, scale_factor, edges })
+    }
+
+    pub fn bounds(&self) -> Rectangle {
+        self.bounds
+    }
+
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// 截图尺寸 (物理像素)
+    pub fn image_size(&self) -> (u32, u32) {
+        (self.edges.width(), self.edges.height())
+    }
+
+    fn ratio(&self) -> (f64, f64) {
+        (
+            self.edges.width() as f64 / self.bounds.width() as f64,
+            self.edges.height() as f64 / self.bounds.height() as f64,
+        )
+    }
+
+    /// 图像像素转换为屏幕坐标
+    pub fn to_screen(&self, position: Point) -> Point {
+        let (ratio_x, ratio_y) = self.ratio();
+        Point::new(
+            self.bounds.min_x() + (position.x as f64 / ratio_x).floor() as i32,
+            self.bounds.min_y() + (position.y as f64 / ratio_y).floor() as i32,
+        )
+    }
+
+    /// 屏幕矩形转换为图像像素，裁剪到截图内，完全在截图外时返回 None
+    pub fn to_image(&self, rect: &Rectangle) -> Option<Rectangle> {
+        let (ratio_x, ratio_y) = self.ratio();
+        let x = |v: i32| ((v - self.bounds.min_x()) as f64 * ratio_x).round() as i32;
+        let y = |v: i32| ((v - self.bounds.min_y()) as f64 * ratio_y).round() as i32;
+        let (width, height) = (self.edges.width() as i32, self.edges.height() as i32);
+
+        let min_x = x(rect.min_x()).clamp(0, width);
+        let min_y = y(rect.min_y()).clamp(0, height);
+        let max_x = x(rect.max_x()).clamp(0, width);
+        let max_y = y(rect.max_y()).clamp(0, height);
+        if max_x <= min_x || max_y <= min_y {
+            return None;
+        }
+        Some(Rectangle::from_bounds(min_x, min_y, max_x, max_y))
+    }
+
+    fn length(&self, physical: u32) -> MeasuredLength {
+        MeasuredLength::new(physical, self.scale_factor)
+    }
+
+    /// 测量图像像素 `position` 周围的间距
+    pub fn measure(&self, position: Point) -> Result<Measurement, DomainError> {
+        let bounds = self.edges.spacing_bounds(position).ok_or(DomainError::RegionOutOfBounds)?;
+        let spacing = Spacing {
+            bounds,
+            left: self.length((position.x - bounds.min_x()) as u32),
+            right: self.length((bounds.max_x() - 1 - position.x) as u32),
+            top: self.length((position.y - bounds.min_y()) as u32),
+            bottom: self.length((bounds.max_y() - 1 - position.y) as u32),
+            width: self.length(bounds.width()),
+            height: self.length(bounds.height()),
+        };
+        Ok(Measurement { position, scale_factor: self.scale_factor, spacing, element: None })
+    }
+
+    /// 以屏幕坐标的元素边界构造测量结果
+    pub fn measure_element(
+        &self,
+        screen_bounds: &Rectangle,
+        role: Option<String>,
+        title: Option<String>,
+    ) -> Option<MeasuredElement> {
+        let bounds = self.to_image(screen_bounds)?;
+        Some(MeasuredElement {
+            bounds,
+            width: self.length(bounds.width()),
+            height: self.length(bounds.height()),
+            role,
+            title,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 20x10 的边缘图，x=4 和 x=15 两条竖线，y=7 一条横线
+    fn edge_map() -> EdgeMap {
+        let mut edges = vec![false; 200];
+        for y in 0..10 {
+            edges[y * 20 + 4] = true;
+            edges[y * 20 + 15] = true;
+        }
+        for x in 0..20 {
+            edges[7 * 20 + x] = true;
+        }
+        EdgeMap::new(20, 10, edges).unwrap()
+    }
+
+    #[test]
+    fn test_spacing_between_edges() {
+        let edges = edge_map();
+        assert_eq!(
+            edges.spacing_bounds(Point::new(8, 2)),
+            Some(Rectangle::from_bounds(5, 0, 15, 7))
+        );
+        // 光标在竖线上：横向越过这条线继续找，纵向沿线紧贴光标
+        assert_eq!(
+            edges.spacing_bounds(Point::new(4, 2)),
+            Some(Rectangle::from_bounds(0, 2, 15, 3))
+        );
+        assert_eq!(edges.spacing_bounds(Point::new(20, 2)), None);
+        assert!(EdgeMap::new(2, 2, vec![false; 3]).is_err());
+    }
+
+    #[test]
+    fn test_measure_reports_physical_and_logical() {
+        // 逻辑尺寸 10x5 的显示器，截图为 2 倍分辨率
+        let session =
+            MeasurementSession::new(Rectangle::from_bounds(100, 0, 110, 5), 2.0, edge_map())
+                .unwrap();
+
+        let measurement = session.measure(Point::new(8, 2)).unwrap();
+        let spacing = measurement.spacing;
+        assert_eq!(spacing.width, MeasuredLength { physical: 10, logical: 5.0 });
+        assert_eq!(spacing.height, MeasuredLength { physical: 7, logical: 3.5 });
+        assert_eq!(spacing.left.physical, 3);
+        assert_eq!(spacing.right.physical, 6);
+        assert_eq!(spacing.left.physical + spacing.right.physical + 1, spacing.width.physical);
+        assert_eq!(spacing.top.physical, 2);
+        assert_eq!(spacing.bottom.physical, 4);
+        assert!(session.measure(Point::new(-1, 0)).is_err());
+
+        assert_eq!(session.to_screen(Point::new(8, 3)), Point::new(104, 1));
+        let element = session
+            .measure_element(&Rectangle::from_bounds(98, 1, 103, 3), None, Some("OK".into()))
+            .unwrap();
+        assert_eq!(element.bounds, Rectangle::from_bounds(0, 2, 6, 6));
+        assert_eq!(element.width, MeasuredLength { physical: 6, logical: 3.0 });
+        assert!(
+            session.measure_element(&Rectangle::from_bounds(0, 0, 10, 10), None, None).is_none()
+        );
+
+        assert!(
+            MeasurementSession::new(Rectangle::from_bounds(0, 0, 10, 5), 0.0, edge_map()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_logical_length_rounding() {
+        assert_eq!(MeasuredLength::new(10, 1.5).logical, 6.67);
+        assert_eq!(MeasuredLength::new(10, 1.0).logical, 10.0);
+    }
+}
@@ -0,0 +1,180 @@
+use aumate_core_shared::{DomainError, Point, Rectangle, WindowId};
+use serde::{Deserialize, Serialize};
+
+/// 贴图最小缩放
+pub const MIN_PIN_ZOOM: f64 = 0.1;
+
+/// 贴图最大缩放
+pub const MAX_PIN_ZOOM: f64 = 8.0;
+
+/// 最低不透明度，避免窗口完全透明后找不回来
+pub const MIN_PIN_OPACITY: f64 = 0.1;
+
+/// 默认吸附距离 (物理像素)
+pub const DEFAULT_SNAP_DISTANCE: u32 = 16;
+
+/// 贴在屏幕上的截图窗口
+///
+/// 坐标和尺寸均为屏幕物理像素，窗口大小 = 图像大小 × 缩放
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PinnedWindow {
+    pub id: WindowId,
+    pub x: i32,
+    pub y: i32,
+    pub image_width: u32,
+    pub image_height: u32,
+    pub zoom: f64,
+    pub opacity: f64,
+    /// 鼠标穿透，开启后点击落到下面的窗口
+    pub click_through: bool,
+    pub visible: bool,
+}
+
+impl PinnedWindow {
+    pub fn new(id: WindowId, position: Point, image_width: u32, image_height: u32) -> Self {
+        Self {
+            id,
+            x: position.x,
+            y: position.y,
+            image_width,
+            image_height,
+            zoom: 1.0,
+            opacity: 1.0,
+            click_through: false,
+            visible: true,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        scale(self.image_width, self.zoom)
+    }
+
+    pub fn height(&self) -> u32 {
+        scale(self.image_height, self.zoom)
+    }
+
+    pub fn bounds(&self) -> Rectangle {
+        Rectangle::from_bounds(
+            self.x,
+            self.y,
+            self.x + self.width() as i32,
+            self.y + self.height() as i32,
+        )
+    }
+
+    pub fn move_to(&mut self, position: Point) {
+        self.x = position.x;
+        self.y = position.y;
+    }
+
+    /// 以窗口中心为锚点缩放，超出范围时截断
+    pub fn set_zoom(&mut self, zoom: f64) -> Result<(), DomainError> {
+        if !zoom.is_finite() {
+            return Err(DomainError::ValidationFailed("zoom must be finite".to_string()));
+        }
+        let center_x = self.x + self.width() as i32 / 2;
+        let center_y = self.y + self.height() as i32 / 2;
+        self.zoom = zoom.clamp(MIN_PIN_ZOOM, MAX_PIN_ZOOM);
+        self.x = center_x - self.width() as i32 / 2;
+        self.y = center_y - self.height() as i32 / 2;
+        Ok(())
+    }
+
+    pub fn set_opacity(&mut self, opacity: f64) -> Result<(), DomainError> {
+        if !opacity.is_finite() {
+            return Err(DomainError::ValidationFailed("opacity must be finite".to_string()));
+        }
+        self.opacity = opacity.clamp(MIN_PIN_OPACITY, 1.0);
+        Ok(())
+    }
+}
+
+fn scale(size: u32, zoom: f64) -> u32 {
+    ((size as f64 * zoom).round() as u32).max(1)
+}
+
+/// 把窗口吸附到附近的边
+///
+/// `targets` 为显示器工作区和其他贴图窗口。窗口的左右 (上下) 边在 `distance`
+/// 以内靠近目标的任一竖直 (水平) 边时对齐过去，且只在另一方向与目标相邻时生效。
+/// 返回吸附后的左上角
+pub fn snap_position(bounds: &Rectangle, targets: &[Rectangle], distance: u32) -> Point {
+    let dx = snap_offset(
+        (bounds.min_x(), bounds.max_x()),
+        targets
+            .iter()
+            .filter(|t| near((bounds.min_y(), bounds.max_y()), (t.min_y(), t.max_y()), distance))
+            .flat_map(|t| [t.min_x(), t.max_x()]),
+        distance,
+    );
+    let dy = snap_offset(
+        (bounds.min_y(), bounds.max_y()),
+        targets
+            .iter()
+            .filter(|t| near((bounds.min_x(), bounds.max_x()), (t.min_x(), t.max_x()), distance))
+            .flat_map(|t| [t.min_y(), t.max_y()]),
+        distance,
+    );
+    Point::new(bounds.min_x() + dx, bounds.min_y() + dy)
+}
+
+/// 把 `span` 的任一端对齐到最近的 `edge` 所需的位移，没有足够近的边时为 0
+fn snap_offset(span: (i32, i32), edges: impl Iterator<Item = i32>, distance: u32) -> i32 {
+    edges
+        .flat_map(|edge| [edge - span.0, edge - span.1])
+        .filter(|offset| offset.unsigned_abs() <= distance)
+        .min_by_key(|offset| offset.unsigned_abs())
+        .unwrap_or(0)
+}
+
+/// 两个区间是否重叠或相距不超过 `distance`
+fn near(a: (i32, i32), b: (i32, i32), distance: u32) -> bool {
+    let distance = distance as i32;
+    a.0 <= b.1 + distance && b.0 <= a.1 + distance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinned_window_zoom_and_opacity() {
+        let mut window =
+            PinnedWindow::new(WindowId::new("pin-1".to_string()), Point::new(100, 100), 200, 100);
+        assert!(window.bounds().equals(100, 100, 300, 200));
+
+        // 以中心缩放
+        window.set_zoom(2.0).unwrap();
+        assert!(window.bounds().equals(0, 50, 400, 250));
+        window.set_zoom(100.0).unwrap();
+        assert_eq!(window.zoom, MAX_PIN_ZOOM);
+        assert!(window.set_zoom(f64::NAN).is_err());
+
+        window.set_opacity(0.0).unwrap();
+        assert_eq!(window.opacity, MIN_PIN_OPACITY);
+        window.set_opacity(0.5).unwrap();
+        assert_eq!(window.opacity, 0.5);
+    }
+
+    #[test]
+    fn test_snap_position() {
+        let screen = Rectangle::from_bounds(0, 0, 1920, 1040);
+        let other = Rectangle::from_bounds(500, 300, 700, 400);
+
+        // 靠近屏幕左上角
+        let bounds = Rectangle::from_bounds(10, 12, 110, 62);
+        assert_eq!(snap_position(&bounds, &[screen], 16), Point::new(0, 0));
+
+        // 贴到另一个窗口右侧，并与其顶边对齐
+        let bounds = Rectangle::from_bounds(708, 295, 808, 345);
+        assert_eq!(snap_position(&bounds, &[screen, other], 16), Point::new(700, 300));
+
+        // 太远不吸附
+        let bounds = Rectangle::from_bounds(300, 500, 400, 550);
+        assert_eq!(snap_position(&bounds, &[screen, other], 16), Point::new(300, 500));
+
+        // 竖直方向不相邻时不吸附到另一个窗口的竖直边
+        let bounds = Rectangle::from_bounds(705, 600, 805, 650);
+        assert_eq!(snap_position(&bounds, &[other], 16), Point::new(705, 600));
+    }
+}
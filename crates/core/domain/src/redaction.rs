@@ -0,0 +1,193 @@
+use crate::annotation::AnnotationColor;
+use crate::text_recognition::RecognizedText;
+use aumate_core_shared::{DomainError, Rectangle};
+use serde::{Deserialize, Serialize};
+
+/// 敏感文本类型 (自动模式的检测器)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SensitiveKind {
+    Email,
+    /// API Key、访问令牌、密码等
+    Token,
+    IpAddress,
+    /// 通过 Luhn 校验的银行卡号
+    CreditCard,
+}
+
+impl SensitiveKind {
+    pub const ALL: [SensitiveKind; 4] = [
+        SensitiveKind::Email,
+        SensitiveKind::Token,
+        SensitiveKind::IpAddress,
+        SensitiveKind::CreditCard,
+    ];
+}
+
+/// 遮挡方式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum RedactionMethod {
+    /// 马赛克
+    Pixelate { block_size: u32 },
+    /// 高斯模糊，`radius` 为标准差 (像素)
+    Blur { radius: f32 },
+    /// 纯色覆盖
+    Fill { color: AnnotationColor },
+}
+
+impl Default for RedactionMethod {
+    fn default() -> Self {
+        RedactionMethod::Pixelate { block_size: 12 }
+    }
+}
+
+impl RedactionMethod {
+    pub fn validate(&self) -> Result<(), DomainError> {
+        match self {
+            RedactionMethod::Pixelate { block_size: 0 } => {
+                Err(DomainError::ValidationFailed("block_size must be positive".to_string()))
+            }
+            RedactionMethod::Blur { radius } if !radius.is_finite() || *radius < 0.0 => {
+                Err(DomainError::ValidationFailed("radius must not be negative".to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 区域被遮挡的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactionReason {
+    /// 调用方指定的矩形
+    Manual,
+    /// 识别到的任意文本 (`redact_all_text`)
+    Text,
+    Email,
+    Token,
+    IpAddress,
+    CreditCard,
+}
+
+impl From<SensitiveKind> for RedactionReason {
+    fn from(kind: SensitiveKind) -> Self {
+        match kind {
+            SensitiveKind::Email => RedactionReason::Email,
+            SensitiveKind::Token => RedactionReason::Token,
+            SensitiveKind::IpAddress => RedactionReason::IpAddress,
+            SensitiveKind::CreditCard => RedactionReason::CreditCard,
+        }
+    }
+}
+
+/// 遮挡请求
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RedactionRequest {
+    #[serde(default)]
+    pub method: RedactionMethod,
+    /// 手动指定的区域
+    #[serde(default)]
+    pub regions: Vec<Rectangle>,
+    /// OCR 识别结果，检测器在这些文本里查找敏感内容
+    #[serde(default)]
+    pub text: Vec<RecognizedText>,
+    #[serde(default = "default_detectors")]
+    pub detectors: Vec<SensitiveKind>,
+    /// 遮挡所有识别到的文本，而不只是敏感部分
+    #[serde(default)]
+    pub redact_all_text: bool,
+}
+
+fn default_detectors() -> Vec<SensitiveKind> {
+    SensitiveKind::ALL.to_vec()
+}
+
+impl Default for RedactionRequest {
+    fn default() -> Self {
+        Self {
+            method: RedactionMethod::default(),
+            regions: Vec::new(),
+            text: Vec::new(),
+            detectors: default_detectors(),
+            redact_all_text: false,
+        }
+    }
+}
+
+impl RedactionRequest {
+    pub fn new(method: RedactionMethod) -> Self {
+        Self { method, ..Self::default() }
+    }
+
+    pub fn with_regions(mut self, regions: Vec<Rectangle>) -> Self {
+        self.regions = regions;
+        self
+    }
+
+    pub fn with_text(mut self, text: Vec<RecognizedText>) -> Self {
+        self.text = text;
+        self
+    }
+
+    pub fn with_detectors(mut self, detectors: Vec<SensitiveKind>) -> Self {
+        self.detectors = detectors;
+        self
+    }
+
+    pub fn with_redact_all_text(mut self, redact_all_text: bool) -> Self {
+        self.redact_all_text = redact_all_text;
+        self
+    }
+}
+
+/// 一个被遮挡的区域
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RedactedItem {
+    pub reason: RedactionReason,
+    pub bounds: Rectangle,
+    /// 被遮挡文本的掩码预览 (如 `al*************om`)，不包含原文
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+/// 遮挡报告，按应用顺序列出被隐藏的内容和位置
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RedactionReport {
+    pub items: Vec<RedactedItem>,
+}
+
+impl RedactionReport {
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// 某种原因的遮挡数量
+    pub fn count(&self, reason: RedactionReason) -> usize {
+        self.items.iter().filter(|item| item.reason == reason).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_json() {
+        let json = r##"{
+            "method": {"method": "fill", "color": "#000000"},
+            "regions": [{"min_x": 0, "min_y": 0, "max_x": 10, "max_y": 10}]
+        }"##;
+        let request: RedactionRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.method, RedactionMethod::Fill { color: AnnotationColor::rgb(0, 0, 0) });
+        assert_eq!(request.regions.len(), 1);
+        assert_eq!(request.detectors, SensitiveKind::ALL);
+        assert!(!request.redact_all_text);
+
+        let request: RedactionRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(request, RedactionRequest::default());
+        assert!(request.method.validate().is_ok());
+        assert!(RedactionMethod::Pixelate { block_size: 0 }.validate().is_err());
+        assert!(RedactionMethod::Blur { radius: -1.0 }.validate().is_err());
+    }
+}
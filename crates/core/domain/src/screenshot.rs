@@ -28,6 +28,12 @@ pub struct CaptureMetadata {
     pub monitor_id: Option<String>,
     /// 捕获区域
     pub region: Option<CaptureRegion>,
+    /// 捕获时的前台窗口标题
+    #[serde(default)]
+    pub window_title: Option<String>,
+    /// 捕获时的前台应用名
+    #[serde(default)]
+    pub app_name: Option<String>,
     /// 光标是否可见
     pub cursor_visible: bool,
     /// 是否进行了 HDR 校正
@@ -50,6 +56,8 @@ impl Default for CaptureMetadata {
             capture_target: CaptureTarget::CurrentMonitor,
             monitor_id: None,
             region: None,
+            window_title: None,
+            app_name: None,
             cursor_visible: true,
             hdr_corrected: false,
         }
@@ -63,6 +71,8 @@ impl CaptureMetadata {
             capture_target,
             monitor_id: None,
             region: None,
+            window_title: None,
+            app_name: None,
             cursor_visible: true,
             hdr_corrected: false,
         }
@@ -78,6 +88,12 @@ impl CaptureMetadata {
         self
     }
 
+    pub fn with_window(mut self, title: Option<String>, app_name: Option<String>) -> Self {
+        self.window_title = title;
+        self.app_name = app_name;
+        self
+    }
+
     pub fn with_cursor_visible(mut self, visible: bool) -> Self {
         self.cursor_visible = visible;
         self
@@ -0,0 +1,224 @@
+use crate::screenshot::CaptureMetadata;
+use chrono::{DateTime, Local};
+
+/// 模板展开后为空时使用的文件名
+pub const DEFAULT_FILENAME: &str = "screenshot";
+
+/// 窗口标题、应用名等单个字段的最大字符数
+const MAX_FIELD_CHARS: usize = 64;
+
+/// 文件名 (不含扩展名) 的最大字符数
+const MAX_FILENAME_CHARS: usize = 200;
+
+/// 支持的 strftime 占位符，其他 `%x` 原样保留
+const DATE_SPECIFIERS: &[char] = &['Y', 'y', 'm', 'd', 'H', 'M', 'S', 'j', 'b', 'B', 'a', 'A'];
+
+/// Windows 保留的设备名
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// 文件名模板中可用的截图信息
+#[derive(Debug, Clone)]
+pub struct FilenameContext {
+    pub time: DateTime<Local>,
+    pub window_title: Option<String>,
+    pub app_name: Option<String>,
+    pub monitor: Option<String>,
+    /// `{counter}` 的值，从 1 开始
+    pub counter: u32,
+}
+
+impl FilenameContext {
+    pub fn new(time: DateTime<Local>) -> Self {
+        Self { time, window_title: None, app_name: None, monitor: None, counter: 1 }
+    }
+
+    pub fn from_metadata(metadata: &CaptureMetadata) -> Self {
+        Self {
+            time: DateTime::from(metadata.capture_time),
+            window_title: metadata.window_title.clone(),
+            app_name: metadata.app_name.clone(),
+            monitor: metadata.monitor_id.clone(),
+            counter: 1,
+        }
+    }
+
+    pub fn with_counter(mut self, counter: u32) -> Self {
+        self.counter = counter;
+        self
+    }
+}
+
+/// 截图文件名模板
+///
+/// 支持 strftime 风格的 `%Y %y %m %d %H %M %S %j %b %B %a %A` 和 `%%`，
+/// 以及 `{date}`、`{time}`、`{title}`、`{app}`、`{monitor}`、`{counter}`、
+/// `{counter:N}` (补零到 N 位)。未知占位符原样保留
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilenamePattern(String);
+
+impl FilenamePattern {
+    pub fn new(pattern: impl Into<String>) -> Self {
+        Self(pattern.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// 是否包含 `{counter}`，包含时通过递增计数器解决重名
+    pub fn has_counter(&self) -> bool {
+        self.0.contains("{counter}") || self.0.contains("{counter:")
+    }
+
+    /// 展开为可直接使用的文件名 (不含扩展名)
+    pub fn expand(&self, context: &FilenameContext) -> String {
+        let mut output = String::new();
+        let mut rest = self.0.as_str();
+
+        while let Some(c) = rest.chars().next() {
+            rest = &rest[c.len_utf8()..];
+            match c {
+                '%' => match rest.chars().next() {
+                    Some('%') => {
+                        rest = &rest[1..];
+                        output.push('%');
+                    }
+                    Some(spec) if DATE_SPECIFIERS.contains(&spec) => {
+                        rest = &rest[1..];
+                        output.push_str(&context.time.format(&format!("%{}", spec)).to_string());
+                    }
+                    _ => output.push('%'),
+                },
+                '{' => {
+                    let expanded = rest
+                        .split_once('}')
+                        .and_then(|(token, after)| Some((expand_token(token, context)?, after)));
+                    match expanded {
+                        Some((value, after)) => {
+                            output.push_str(&value);
+                            rest = after;
+                        }
+                        None => output.push('{'),
+                    }
+                }
+                _ => output.push(c),
+            }
+        }
+
+        sanitize_filename(&output)
+    }
+}
+
+impl Default for FilenamePattern {
+    fn default() -> Self {
+        Self::new("screenshot_%Y%m%d_%H%M%S")
+    }
+}
+
+fn expand_token(token: &str, context: &FilenameContext) -> Option<String> {
+    let value = match token {
+        "date" => context.time.format("%Y-%m-%d").to_string(),
+        "time" => context.time.format("%H-%M-%S").to_string(),
+        "title" => field(context.window_title.as_deref()),
+        "app" => field(context.app_name.as_deref()),
+        "monitor" => field(context.monitor.as_deref()),
+        "counter" => context.counter.to_string(),
+        _ => {
+            let width: usize = token.strip_prefix("counter:")?.parse().ok()?;
+            format!("{:0width$}", context.counter, width = width.min(10))
+        }
+    };
+    Some(value)
+}
+
+/// 替换字段中的非法字符并截断
+fn field(value: Option<&str>) -> String {
+    let value = value.unwrap_or("").trim();
+    replace_invalid_chars(value)
+        .chars()
+        .take(MAX_FIELD_CHARS)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+fn replace_invalid_chars(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+/// 把任意字符串变成在 Windows、macOS 和 Linux 上都合法的文件名
+///
+/// 非法字符替换为 `_`，去掉首尾空白和结尾的 `.`，避开 Windows 保留名，
+/// 结果为空时返回 [`DEFAULT_FILENAME`]
+pub fn sanitize_filename(name: &str) -> String {
+    let name: String = replace_invalid_chars(name).chars().take(MAX_FILENAME_CHARS).collect();
+    let name = name.trim().trim_end_matches(['.', ' ']);
+    if name.is_empty() {
+        return DEFAULT_FILENAME.to_string();
+    }
+
+    let stem = name.split('.').next().unwrap_or(name);
+    if RESERVED_NAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(stem)) {
+        return format!("{}_", name);
+    }
+    name.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn sample_context() -> FilenameContext {
+        let mut context =
+            FilenameContext::new(Local.with_ymd_and_hms(2024, 3, 5, 9, 7, 2).unwrap());
+        context.window_title = Some("Report: Q1/Q2 *draft*".to_string());
+        context.app_name = Some("Excel".to_string());
+        context.monitor = Some("1".to_string());
+        context
+    }
+
+    #[test]
+    fn test_expand_pattern() {
+        let context = sample_context();
+        assert_eq!(FilenamePattern::default().expand(&context), "screenshot_20240305_090702");
+        assert_eq!(
+            FilenamePattern::new("{app} - {title} {date} {time}").expand(&context),
+            "Excel - Report_ Q1_Q2 _draft_ 2024-03-05 09-07-02"
+        );
+        assert_eq!(
+            FilenamePattern::new("shot_{monitor}_{counter:3}").expand(&context.with_counter(7)),
+            "shot_1_007"
+        );
+        // 未知占位符和单独的 % 原样保留
+        assert_eq!(
+            FilenamePattern::new("100%% {nope} %q {").expand(&sample_context()),
+            "100% {nope} %q {"
+        );
+        assert!(FilenamePattern::new("a{counter}").has_counter());
+        assert!(!FilenamePattern::default().has_counter());
+    }
+
+    #[test]
+    fn test_sanitize_filename() {
+        let mut context = sample_context();
+        context.window_title = None;
+        assert_eq!(FilenamePattern::new("{title}").expand(&context), DEFAULT_FILENAME);
+        assert_eq!(sanitize_filename(" name. "), "name");
+        assert_eq!(sanitize_filename("con"), "con_");
+        assert_eq!(sanitize_filename("a\tb"), "a_b");
+
+        context.window_title = Some("x".repeat(500));
+        assert_eq!(FilenamePattern::new("{title}").expand(&context).len(), MAX_FIELD_CHARS);
+    }
+}
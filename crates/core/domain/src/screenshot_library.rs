@@ -0,0 +1,204 @@
+use crate::screenshot::{CaptureMetadata, CaptureRegion};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 缩略图最长边 (像素)
+pub const THUMBNAIL_MAX_EDGE: u32 = 320;
+
+/// 单个标签的最大字符数
+pub const MAX_TAG_CHARS: usize = 32;
+
+/// 截图库条目
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScreenshotRecord {
+    pub id: i64,
+    /// 截图文件路径
+    pub file_path: String,
+    /// 文件格式 ("png", "jpg", "webp" ...)
+    pub format: String,
+    pub width: u32,
+    pub height: u32,
+    /// 文件大小 (字节)
+    pub byte_size: u64,
+    pub monitor_id: Option<String>,
+    pub window_title: Option<String>,
+    pub app_name: Option<String>,
+    pub region: Option<CaptureRegion>,
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+    /// OCR 识别出的文本，用于搜索
+    pub ocr_text: Option<String>,
+    /// 捕获时间 (Unix 毫秒)
+    pub captured_at: i64,
+}
+
+/// 待加入截图库的截图
+#[derive(Debug, Clone)]
+pub struct NewScreenshotRecord {
+    /// 文件格式扩展名
+    pub format: String,
+    pub width: u32,
+    pub height: u32,
+    pub capture_metadata: CaptureMetadata,
+    pub tags: Vec<String>,
+}
+
+impl NewScreenshotRecord {
+    pub fn new(
+        format: impl Into<String>,
+        width: u32,
+        height: u32,
+        capture_metadata: CaptureMetadata,
+    ) -> Self {
+        Self { format: format.into(), width, height, capture_metadata, tags: Vec::new() }
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = normalize_tags(tags);
+        self
+    }
+
+    /// 捕获时间 (Unix 毫秒)
+    pub fn captured_at(&self) -> i64 {
+        self.capture_metadata
+            .capture_time
+            .duration_since(UNIX_EPOCH)
+            .or_else(|_| SystemTime::now().duration_since(UNIX_EPOCH))
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0)
+    }
+}
+
+/// 截图库查询
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreenshotLibraryQuery {
+    /// 按窗口标题、应用名、标签、备注和 OCR 文本搜索，为空时返回全部
+    pub search: Option<String>,
+    /// 只返回带有该标签的截图 (忽略大小写)
+    pub tag: Option<String>,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+impl Default for ScreenshotLibraryQuery {
+    fn default() -> Self {
+        Self { search: None, tag: None, limit: 50, offset: 0 }
+    }
+}
+
+impl ScreenshotLibraryQuery {
+    pub fn with_search(mut self, search: impl Into<String>) -> Self {
+        let search = search.into();
+        self.search = if search.trim().is_empty() { None } else { Some(search) };
+        self
+    }
+
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        let tag = tag.into();
+        self.tag = if tag.trim().is_empty() { None } else { Some(tag.trim().to_string()) };
+        self
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+/// 截图库保留策略，加入新截图时删除超出限制的最旧截图
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScreenshotLibraryRetention {
+    /// 最大截图数量，0 表示不限
+    pub max_entries: usize,
+    /// 最长保留天数，0 表示不限
+    pub max_age_days: u32,
+    /// 截图文件总大小上限 (字节)，0 表示不限
+    pub max_total_bytes: u64,
+}
+
+impl Default for ScreenshotLibraryRetention {
+    fn default() -> Self {
+        Self { max_entries: 1000, max_age_days: 0, max_total_bytes: 2 * 1024 * 1024 * 1024 }
+    }
+}
+
+impl ScreenshotLibraryRetention {
+    /// 不做任何限制
+    pub fn unlimited() -> Self {
+        Self { max_entries: 0, max_age_days: 0, max_total_bytes: 0 }
+    }
+
+    /// 早于该时间 (Unix 毫秒) 捕获的截图应删除
+    pub fn cutoff(&self, now_ms: i64) -> Option<i64> {
+        if self.max_age_days == 0 {
+            return None;
+        }
+        Some(now_ms - i64::from(self.max_age_days) * 24 * 60 * 60 * 1000)
+    }
+}
+
+/// 整理标签：去掉首尾空白和空标签，截断过长的标签，忽略大小写去重并保留首次出现的写法
+pub fn normalize_tags<I, S>(tags: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag: String = tag.as_ref().trim().chars().take(MAX_TAG_CHARS).collect();
+        let tag = tag.trim_end().to_string();
+        if tag.is_empty() || normalized.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+            continue;
+        }
+        normalized.push(tag);
+    }
+    normalized
+}
+
+/// 保持宽高比缩放到最长边不超过 `max_edge`，小图不放大
+pub fn thumbnail_size(width: u32, height: u32, max_edge: u32) -> (u32, u32) {
+    let longest = width.max(height);
+    if longest <= max_edge || longest == 0 {
+        return (width, height);
+    }
+    let scale = max_edge as f64 / longest as f64;
+    let scaled = |v: u32| ((v as f64 * scale).round() as u32).clamp(1, max_edge);
+    (scaled(width), scaled(height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_tags() {
+        let tags = normalize_tags(["  Bug ", "bug", "", "login flow", "   ", "UI"]);
+        assert_eq!(tags, ["Bug", "login flow", "UI"]);
+
+        let long = "x".repeat(MAX_TAG_CHARS + 10);
+        assert_eq!(normalize_tags([long])[0].chars().count(), MAX_TAG_CHARS);
+    }
+
+    #[test]
+    fn test_thumbnail_size() {
+        assert_eq!(thumbnail_size(1920, 1080, 320), (320, 180));
+        assert_eq!(thumbnail_size(1080, 1920, 320), (180, 320));
+        assert_eq!(thumbnail_size(200, 100, 320), (200, 100));
+        assert_eq!(thumbnail_size(10000, 1, 320), (320, 1));
+    }
+
+    #[test]
+    fn test_query_ignores_blank_filters() {
+        let query = ScreenshotLibraryQuery::default().with_search("  ").with_tag(" ");
+        assert_eq!(query.search, None);
+        assert_eq!(query.tag, None);
+
+        let query = ScreenshotLibraryQuery::default().with_tag(" bug ");
+        assert_eq!(query.tag.as_deref(), Some("bug"));
+    }
+}
@@ -0,0 +1,60 @@
+// 密钥引用
+
+/// 设置中的密钥引用前缀，例如 `secret:ai_dialogue.api_key`
+pub const SECRET_REF_PREFIX: &str = "secret:";
+
+/// 打码显示使用的字符
+pub const SECRET_MASK_CHAR: char = '•';
+
+/// 打码时保留的末尾字符数
+const SECRET_VISIBLE_CHARS: usize = 4;
+
+/// 生成密钥引用
+pub fn secret_ref(name: &str) -> String {
+    format!("{}{}", SECRET_REF_PREFIX, name)
+}
+
+/// 解析密钥引用，得到密钥名称
+pub fn parse_secret_ref(value: &str) -> Option<&str> {
+    value.strip_prefix(SECRET_REF_PREFIX).filter(|name| !name.is_empty())
+}
+
+/// 打码后的密钥，只保留末尾几个字符
+///
+/// 太短的密钥完全隐藏
+pub fn mask_secret(secret: &str) -> String {
+    let count = secret.chars().count();
+    let mask = SECRET_MASK_CHAR.to_string().repeat(8);
+    if count <= SECRET_VISIBLE_CHARS * 2 {
+        return mask;
+    }
+    let visible: String = secret.chars().skip(count - SECRET_VISIBLE_CHARS).collect();
+    format!("{}{}", mask, visible)
+}
+
+/// 是否为 [`mask_secret`] 生成的打码值 (前端未修改)
+pub fn is_masked_secret(value: &str) -> bool {
+    value.starts_with(SECRET_MASK_CHAR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_ref() {
+        let value = secret_ref("ai_dialogue.api_key");
+        assert_eq!(value, "secret:ai_dialogue.api_key");
+        assert_eq!(parse_secret_ref(&value), Some("ai_dialogue.api_key"));
+        assert_eq!(parse_secret_ref("sk-plain"), None);
+        assert_eq!(parse_secret_ref("secret:"), None);
+    }
+
+    #[test]
+    fn test_mask_secret() {
+        assert_eq!(mask_secret("sk-1234567890abcd"), "••••••••abcd");
+        assert_eq!(mask_secret("short"), "••••••••");
+        assert!(is_masked_secret(&mask_secret("sk-1234567890abcd")));
+        assert!(!is_masked_secret("sk-new"));
+    }
+}
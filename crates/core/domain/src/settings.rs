@@ -1,5 +1,21 @@
 // Settings Domain Models
+use crate::clipboard_history::{
+    ClipboardHistoryExclusions, ClipboardHistoryRetention, DEFAULT_EXCLUDED_APPS,
+};
+use crate::secret::{parse_secret_ref, secret_ref};
+use aumate_core_shared::FieldError;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Current version of the settings file layout
+///
+/// Bump it together with a new entry at the end of [`MIGRATIONS`] whenever
+/// older files can no longer be read as-is.
+pub const SETTINGS_SCHEMA_VERSION: u32 = 2;
+
+fn current_schema_version() -> u32 {
+    SETTINGS_SCHEMA_VERSION
+}
 
 /// General settings for the application
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -130,26 +146,89 @@ impl Default for EnabledModes {
 /// Screenshot settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScreenshotSettings {
+    /// Folder screenshots are saved to, empty for the pictures folder
     pub save_folder: String,
+    /// File name without extension, see `FilenamePattern` for the supported tokens
     pub filename_pattern: String,
+    /// "png", "jpeg", "webp", "avif" or "bmp"
     pub image_format: String,
+    /// Encoder quality (1-100) for lossy formats
+    #[serde(default = "default_image_quality")]
+    pub image_quality: u8,
     pub auto_copy_clipboard: bool,
 }
 
+fn default_image_quality() -> u8 {
+    90
+}
+
 impl Default for ScreenshotSettings {
     fn default() -> Self {
         Self {
             save_folder: String::new(),
             filename_pattern: "screenshot_%Y%m%d_%H%M%S".to_string(),
             image_format: "png".to_string(),
+            image_quality: default_image_quality(),
             auto_copy_clipboard: true,
         }
     }
 }
 
+/// Clipboard history settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardHistorySettings {
+    pub enabled: bool,
+    /// Maximum number of unpinned entries, 0 for unlimited
+    pub max_entries: usize,
+    /// Days to keep unpinned entries, 0 to keep forever
+    pub max_age_days: u32,
+    /// Applications whose copies are never recorded
+    pub excluded_apps: Vec<String>,
+}
+
+impl Default for ClipboardHistorySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_entries: 500,
+            max_age_days: 30,
+            excluded_apps: DEFAULT_EXCLUDED_APPS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl ClipboardHistorySettings {
+    pub fn retention(&self) -> ClipboardHistoryRetention {
+        ClipboardHistoryRetention { max_entries: self.max_entries, max_age_days: self.max_age_days }
+    }
+
+    pub fn exclusions(&self) -> ClipboardHistoryExclusions {
+        ClipboardHistoryExclusions { apps: self.excluded_apps.clone(), ..Default::default() }
+    }
+}
+
+/// Application and file launcher settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LauncherSettings {
+    /// Include installed applications in search results
+    pub index_applications: bool,
+    /// Folders whose files and subfolders are searchable
+    pub search_folders: Vec<String>,
+    /// How many levels below each search folder are indexed
+    pub max_depth: usize,
+}
+
+impl Default for LauncherSettings {
+    fn default() -> Self {
+        Self { index_applications: true, search_folders: Vec::new(), max_depth: 3 }
+    }
+}
+
 /// Complete application settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
+    #[serde(default = "current_schema_version")]
+    pub schema_version: u32,
     pub general: GeneralSettings,
     pub shortcuts: ShortcutSettings,
     pub advanced: AdvancedSettings,
@@ -159,11 +238,16 @@ pub struct Settings {
     pub ai_dialogue: AIDialogueSettings,
     #[serde(default)]
     pub enabled_modes: EnabledModes,
+    #[serde(default)]
+    pub clipboard_history: ClipboardHistorySettings,
+    #[serde(default)]
+    pub launcher: LauncherSettings,
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
             general: GeneralSettings::default(),
             shortcuts: ShortcutSettings::default(),
             advanced: AdvancedSettings::default(),
@@ -171,6 +255,489 @@ impl Default for Settings {
             screenshot: ScreenshotSettings::default(),
             ai_dialogue: AIDialogueSettings::default(),
             enabled_modes: EnabledModes::default(),
+            clipboard_history: ClipboardHistorySettings::default(),
+            launcher: LauncherSettings::default(),
+        }
+    }
+}
+
+/// Settings fields holding API keys, also used as their secret names
+pub const API_KEY_FIELDS: [&str; 2] = ["expression_polishing.api_key", "ai_dialogue.api_key"];
+
+impl Settings {
+    /// API key fields paired with their names from [`API_KEY_FIELDS`]
+    pub fn api_keys_mut(&mut self) -> [(&'static str, &mut String); 2] {
+        [
+            (API_KEY_FIELDS[0], &mut self.expression_polishing.api_key),
+            (API_KEY_FIELDS[1], &mut self.ai_dialogue.api_key),
+        ]
+    }
+
+    /// Value of an API key field, a secret reference once keys are migrated
+    pub fn api_key(&self, field: &str) -> Option<&str> {
+        match field {
+            "expression_polishing.api_key" => Some(&self.expression_polishing.api_key),
+            "ai_dialogue.api_key" => Some(&self.ai_dialogue.api_key),
+            _ => None,
+        }
+    }
+
+    /// Check field values, returning every problem found
+    ///
+    /// `check_hotkey` decides whether a shortcut string can be registered; the
+    /// app passes its shortcut parser, [`check_hotkey_syntax`] is a fallback.
+    pub fn validate(&self, check_hotkey: &dyn Fn(&str) -> Result<(), String>) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+
+        let hotkeys = [
+            ("general.hotkey", &self.general.hotkey),
+            ("shortcuts.toggle_palette", &self.shortcuts.toggle_palette),
+            ("shortcuts.open_settings", &self.shortcuts.open_settings),
+            ("shortcuts.screenshot", &self.shortcuts.screenshot),
+            ("shortcuts.element_scan", &self.shortcuts.element_scan),
+        ];
+        for (field, hotkey) in hotkeys {
+            if let Err(message) = check_hotkey(hotkey) {
+                errors.push(FieldError::new(field, message));
+            }
+        }
+        // `general.hotkey` mirrors the palette shortcut, so only the actions must differ
+        let actions = &hotkeys[1..];
+        for (i, (field, hotkey)) in actions.iter().enumerate() {
+            let duplicate = actions[..i]
+                .iter()
+                .find(|(_, other)| normalize_hotkey(other) == normalize_hotkey(hotkey));
+            if let Some((other, _)) = duplicate {
+                errors.push(FieldError::new(*field, format!("Same shortcut as {}", other)));
+            }
+        }
+
+        if !["compact", "expanded"].contains(&self.general.window_mode.as_str()) {
+            errors.push(FieldError::new(
+                "general.window_mode",
+                "Must be \"compact\" or \"expanded\"",
+            ));
         }
+
+        check_url("expression_polishing.api_url", &self.expression_polishing.api_url, &mut errors);
+        check_url("ai_dialogue.api_url", &self.ai_dialogue.api_url, &mut errors);
+        if self.ai_dialogue.max_history_messages < 0 {
+            errors
+                .push(FieldError::new("ai_dialogue.max_history_messages", "Must not be negative"));
+        }
+
+        let folder = self.screenshot.save_folder.trim();
+        if !folder.is_empty() && !std::path::Path::new(folder).is_absolute() {
+            errors.push(FieldError::new("screenshot.save_folder", "Must be an absolute path"));
+        }
+        let pattern = &self.screenshot.filename_pattern;
+        if pattern.trim().is_empty() {
+            errors.push(FieldError::new("screenshot.filename_pattern", "Must not be empty"));
+        } else if pattern.contains(['/', '\\']) {
+            errors.push(FieldError::new(
+                "screenshot.filename_pattern",
+                "Must not contain path separators",
+            ));
+        }
+        if !["png", "jpeg", "webp", "avif", "bmp"].contains(&self.screenshot.image_format.as_str())
+        {
+            errors.push(FieldError::new(
+                "screenshot.image_format",
+                "Must be \"png\", \"jpeg\", \"webp\", \"avif\" or \"bmp\"",
+            ));
+        }
+        if !(1..=100).contains(&self.screenshot.image_quality) {
+            errors.push(FieldError::new("screenshot.image_quality", "Must be between 1 and 100"));
+        }
+
+        if self.clipboard_history.excluded_apps.iter().any(|app| app.trim().is_empty()) {
+            errors.push(FieldError::new(
+                "clipboard_history.excluded_apps",
+                "Application names must not be empty",
+            ));
+        }
+
+        for folder in &self.launcher.search_folders {
+            if !std::path::Path::new(folder.trim()).is_absolute() {
+                errors.push(FieldError::new(
+                    "launcher.search_folders",
+                    format!("Must be an absolute path: {}", folder),
+                ));
+            }
+        }
+
+        errors
+    }
+}
+
+/// Minimal hotkey check: modifiers and a key joined by `+`
+pub fn check_hotkey_syntax(hotkey: &str) -> Result<(), String> {
+    if hotkey.trim().is_empty() {
+        return Err("Shortcut must not be empty".to_string());
+    }
+    // "Ctrl++" is not supported, the key itself would be empty
+    if hotkey.split('+').any(|part| part.trim().is_empty()) {
+        return Err(format!("Invalid shortcut: {}", hotkey));
+    }
+    Ok(())
+}
+
+fn normalize_hotkey(hotkey: &str) -> String {
+    let mut parts: Vec<String> =
+        hotkey.split('+').map(|part| part.trim().to_ascii_lowercase()).collect();
+    parts.sort();
+    parts.join("+")
+}
+
+fn check_url(field: &str, url: &str, errors: &mut Vec<FieldError>) {
+    let url = url.trim();
+    let rest = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://"));
+    let host = rest.and_then(|rest| rest.split(['/', '?', '#']).next()).unwrap_or_default();
+    if host.is_empty() || url.contains(char::is_whitespace) {
+        errors.push(FieldError::new(field, format!("Invalid URL: {}", url)));
+    }
+}
+
+/// Result of reading a settings file
+#[derive(Debug, Clone)]
+pub struct SettingsLoadReport {
+    pub settings: Settings,
+    /// Schema version of the file if it had to be migrated
+    pub migrated_from: Option<u32>,
+    /// Fields that could not be read and were reset to their defaults
+    pub errors: Vec<FieldError>,
+    /// Where the original file was copied before it was rewritten
+    pub backup_path: Option<String>,
+}
+
+impl SettingsLoadReport {
+    pub fn new(settings: Settings) -> Self {
+        Self { settings, migrated_from: None, errors: Vec::new(), backup_path: None }
+    }
+
+    /// The file was read as-is
+    pub fn is_clean(&self) -> bool {
+        self.migrated_from.is_none() && self.errors.is_empty()
+    }
+}
+
+/// Upgrades a settings file from one schema version to the next
+type SettingsMigration = fn(&mut Map<String, Value>);
+
+/// `MIGRATIONS[i]` upgrades version `i + 1` to `i + 2`
+const MIGRATIONS: &[SettingsMigration] = &[migrate_v1_to_v2];
+
+/// Version 1 files have no `schema_version`. They may spell the image format
+/// `jpg` or in upper case, and use macOS modifier names in shortcuts.
+fn migrate_v1_to_v2(root: &mut Map<String, Value>) {
+    if let Some(Value::String(format)) =
+        root.get_mut("screenshot").and_then(|section| section.get_mut("image_format"))
+    {
+        let lower = format.trim().to_ascii_lowercase();
+        *format = if lower == "jpg" { "jpeg".to_string() } else { lower };
+    }
+
+    let hotkeys = [
+        ("general", "hotkey"),
+        ("shortcuts", "toggle_palette"),
+        ("shortcuts", "open_settings"),
+        ("shortcuts", "screenshot"),
+        ("shortcuts", "element_scan"),
+    ];
+    for (section, field) in hotkeys {
+        if let Some(Value::String(hotkey)) =
+            root.get_mut(section).and_then(|section| section.get_mut(field))
+        {
+            *hotkey = hotkey
+                .split('+')
+                .map(|part| match part.trim().to_ascii_lowercase().as_str() {
+                    "command" | "⌘" => "Cmd".to_string(),
+                    "option" | "opt" | "⌥" => "Alt".to_string(),
+                    _ => part.trim().to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("+");
+        }
+    }
+}
+
+/// Upgrade settings JSON to [`SETTINGS_SCHEMA_VERSION`], returning the original version
+///
+/// Files written by a newer version are left untouched.
+pub fn migrate_settings(value: &mut Value) -> Result<u32, FieldError> {
+    let Value::Object(root) = value else {
+        return Err(FieldError::new("settings", "Expected a JSON object"));
+    };
+
+    let version = match root.get("schema_version") {
+        None => 1,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|version| *version > 0)
+            .ok_or_else(|| FieldError::new("schema_version", "Must be a positive integer"))?,
+    };
+    if version >= SETTINGS_SCHEMA_VERSION {
+        return Ok(version);
+    }
+
+    for migration in &MIGRATIONS[version as usize - 1..] {
+        migration(root);
+    }
+    root.insert("schema_version".to_string(), Value::from(SETTINGS_SCHEMA_VERSION));
+    Ok(version)
+}
+
+/// Read settings JSON, migrating older files
+///
+/// Fields are read one at a time: a field with the wrong type is reset to its
+/// default and reported, instead of discarding the whole file.
+pub fn settings_from_json(json: &str) -> SettingsLoadReport {
+    let mut report = SettingsLoadReport::new(Settings::default());
+
+    let mut value: Value = match serde_json::from_str(json) {
+        Ok(value) => value,
+        Err(e) => {
+            report.errors.push(FieldError::new("settings", e.to_string()));
+            return report;
+        }
+    };
+    match migrate_settings(&mut value) {
+        Ok(version) if version < SETTINGS_SCHEMA_VERSION => report.migrated_from = Some(version),
+        Ok(_) => {}
+        Err(error) => report.errors.push(error),
+    }
+
+    if let Value::Object(file) = value {
+        report.settings = decode_fields(file, &mut report.errors);
+    }
+    report
+}
+
+/// Replace plaintext API keys in settings JSON, returning `None` if there are none
+///
+/// Keys become secret references, so backups never hold them in plaintext.
+/// Files that are not valid JSON have every `api_key` string blanked instead.
+pub fn redact_api_keys(json: &str) -> Option<String> {
+    let Ok(mut value) = serde_json::from_str::<Value>(json) else {
+        return blank_api_key_strings(json);
+    };
+
+    let mut changed = false;
+    for field in API_KEY_FIELDS {
+        let pointer = format!("/{}", field.replace('.', "/"));
+        match value.pointer_mut(&pointer) {
+            Some(Value::String(key)) if !key.is_empty() && parse_secret_ref(key).is_none() => {
+                *key = secret_ref(field);
+                changed = true;
+            }
+            _ => {}
+        }
+    }
+    if !changed {
+        return None;
+    }
+    serde_json::to_string_pretty(&value).ok()
+}
+
+/// Blank the value of every `"api_key": "..."` pair found in malformed JSON
+fn blank_api_key_strings(json: &str) -> Option<String> {
+    const KEY: &str = "\"api_key\"";
+
+    let mut out = String::with_capacity(json.len());
+    let mut rest = json;
+    let mut changed = false;
+    while let Some(found) = rest.find(KEY) {
+        let (head, tail) = rest.split_at(found + KEY.len());
+        out.push_str(head);
+        rest = tail;
+
+        let after_colon = match rest.trim_start().strip_prefix(':') {
+            Some(after_colon) => after_colon.trim_start(),
+            None => continue,
+        };
+        let Some(body) = after_colon.strip_prefix('"') else {
+            continue;
+        };
+        // End of the string literal, skipping escaped characters
+        let mut end = None;
+        let mut escaped = false;
+        for (i, c) in body.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => {
+                    end = Some(i);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let key = &body[..end.unwrap_or(body.len())];
+        if key.is_empty() || parse_secret_ref(key).is_some() {
+            continue;
+        }
+
+        out.push_str(&rest[..rest.len() - body.len()]);
+        rest = &body[key.len()..];
+        changed = true;
+    }
+    out.push_str(rest);
+    changed.then_some(out)
+}
+
+fn decode_fields(file: Map<String, Value>, errors: &mut Vec<FieldError>) -> Settings {
+    let mut merged = match serde_json::to_value(Settings::default()) {
+        Ok(Value::Object(merged)) => merged,
+        _ => return Settings::default(),
+    };
+    let accepts = |merged: &Map<String, Value>| {
+        serde_json::from_value::<Settings>(Value::Object(merged.clone())).err()
+    };
+
+    for (key, value) in file {
+        match (merged.get(&key), value) {
+            (Some(Value::Object(_)), Value::Object(fields)) => {
+                for (field, value) in fields {
+                    let section = match merged.get_mut(&key) {
+                        Some(Value::Object(section)) if section.contains_key(&field) => section,
+                        // Fields that no longer exist
+                        _ => continue,
+                    };
+                    let previous = section.insert(field.clone(), value);
+                    if let Some(e) = accepts(&merged) {
+                        errors.push(FieldError::new(format!("{}.{}", key, field), e.to_string()));
+                        if let (Some(Value::Object(section)), Some(previous)) =
+                            (merged.get_mut(&key), previous)
+                        {
+                            section.insert(field, previous);
+                        }
+                    }
+                }
+            }
+            (Some(_), value) => {
+                let previous = merged.insert(key.clone(), value);
+                if let Some(e) = accepts(&merged) {
+                    errors.push(FieldError::new(key.clone(), e.to_string()));
+                    if let Some(previous) = previous {
+                        merged.insert(key, previous);
+                    }
+                }
+            }
+            (None, _) => {}
+        }
+    }
+
+    serde_json::from_value(Value::Object(merged)).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_api_keys() {
+        let json = r#"{"ai_dialogue": {"api_key": "sk-dialogue"},
+                       "expression_polishing": {"api_key": "secret:expression_polishing.api_key"}}"#;
+        let redacted = redact_api_keys(json).unwrap();
+        assert!(!redacted.contains("sk-dialogue"));
+        let value: Value = serde_json::from_str(&redacted).unwrap();
+        assert_eq!(value["ai_dialogue"]["api_key"], "secret:ai_dialogue.api_key");
+        assert_eq!(value["expression_polishing"]["api_key"], "secret:expression_polishing.api_key");
+        assert_eq!(redact_api_keys(&redacted), None);
+
+        // Truncated files are redacted textually
+        let broken = r#"{"ai_dialogue": {"api_key" : "sk-\"quoted\"", "model": "gpt"#;
+        let redacted = redact_api_keys(broken).unwrap();
+        assert_eq!(redacted, r#"{"ai_dialogue": {"api_key" : "", "model": "gpt"#);
+        assert_eq!(redact_api_keys(r#"{"api_key": "#), None);
+    }
+
+    #[test]
+    fn test_migrates_v1_file() {
+        let json = r#"{
+            "general": {"follow_system_appearance": false, "open_at_login": true,
+                        "show_in_system_tray": true, "hotkey": "Command+Space",
+                        "window_mode": "expanded"},
+            "shortcuts": {"toggle_palette": "Option+Space", "open_settings": "Ctrl+,"},
+            "screenshot": {"save_folder": "", "filename_pattern": "shot",
+                           "image_format": "JPG", "auto_copy_clipboard": false}
+        }"#;
+        let report = settings_from_json(json);
+
+        assert_eq!(report.migrated_from, Some(1));
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        let settings = report.settings;
+        assert_eq!(settings.schema_version, SETTINGS_SCHEMA_VERSION);
+        assert!(settings.general.open_at_login);
+        assert_eq!(settings.general.hotkey, "Cmd+Space");
+        assert_eq!(settings.shortcuts.toggle_palette, "Alt+Space");
+        assert_eq!(settings.shortcuts.screenshot, "Ctrl+4");
+        assert_eq!(settings.screenshot.image_format, "jpeg");
+        assert!(!settings.screenshot.auto_copy_clipboard);
+    }
+
+    #[test]
+    fn test_bad_field_keeps_the_rest() {
+        let mut value = serde_json::to_value(Settings::default()).unwrap();
+        value["shortcuts"]["screenshot"] = Value::from("Ctrl+Shift+S");
+        value["ai_dialogue"]["api_key"] = Value::from("sk-test");
+        value["ai_dialogue"]["max_history_messages"] = Value::from("lots");
+        value["advanced"] = Value::from(3);
+        value["removed_section"] = Value::from(true);
+
+        let report = settings_from_json(&value.to_string());
+        assert_eq!(report.migrated_from, None);
+        let fields: Vec<_> = report.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["advanced", "ai_dialogue.max_history_messages"]);
+        assert_eq!(report.settings.shortcuts.screenshot, "Ctrl+Shift+S");
+        assert_eq!(report.settings.ai_dialogue.api_key, "sk-test");
+        assert_eq!(report.settings.ai_dialogue.max_history_messages, 20);
+    }
+
+    #[test]
+    fn test_unreadable_files() {
+        let report = settings_from_json("{ not json");
+        assert_eq!(report.errors[0].field, "settings");
+
+        let report = settings_from_json(r#"{"schema_version": "two"}"#);
+        assert_eq!(report.errors[0].field, "schema_version");
+
+        // Newer files are read as far as possible and not migrated
+        let report = settings_from_json(r#"{"schema_version": 99}"#);
+        assert!(report.is_clean());
+        assert_eq!(report.settings.schema_version, 99);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(Settings::default().validate(&check_hotkey_syntax).is_empty());
+
+        let mut settings = Settings::default();
+        settings.shortcuts.screenshot = "ctrl+,".to_string();
+        settings.shortcuts.element_scan = "Ctrl+".to_string();
+        settings.ai_dialogue.api_url = "api.openai.com".to_string();
+        settings.screenshot.save_folder = "relative/dir".to_string();
+        settings.screenshot.image_format = "gif".to_string();
+        settings.screenshot.image_quality = 0;
+
+        let fields: Vec<_> =
+            settings.validate(&check_hotkey_syntax).into_iter().map(|error| error.field).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "shortcuts.element_scan",
+                "shortcuts.screenshot",
+                "ai_dialogue.api_url",
+                "screenshot.save_folder",
+                "screenshot.image_format",
+                "screenshot.image_quality",
+            ]
+        );
+
+        let mut settings = Settings::default();
+        settings.screenshot.image_format = "avif".to_string();
+        settings.screenshot.image_quality = 100;
+        assert!(settings.validate(&check_hotkey_syntax).is_empty());
     }
 }
@@ -0,0 +1,124 @@
+#!/usr/bin/env python3
+"""Generate tone_mapping.json, the reference outputs for hdr.rs.
+
+This is a separate double-precision implementation written from the
+published formulas: the Narkowicz ACES fit, Hable's Uncharted 2 curve,
+SMPTE ST 2084 (PQ) and the ITU-R BT.2390 EETF. It does not share code
+with the Rust implementation, so the fixtures catch transcription errors
+in either one. The Rust test allows an off-by-one difference per channel
+for f32 rounding.
+
+Usage: python3 gen_tone_mapping.py > tone_mapping.json
+"""
+
+import json
+
+SCRGB_WHITE_NITS = 80.0
+PQ_MAX_NITS = 10000.0
+
+# SMPTE ST 2084
+M1 = 2610.0 / 16384.0
+M2 = 2523.0 / 4096.0 * 128.0
+C1 = 3424.0 / 4096.0
+C2 = 2413.0 / 4096.0 * 32.0
+C3 = 2392.0 / 4096.0 * 32.0
+
+DISPLAYS = [(1000.0, 200.0), (400.0, 80.0), (600.0, 300.0)]
+
+INPUTS = [
+    [0.0, 0.0, 0.0, 1.0],
+    [0.5, 0.5, 0.5, 1.0],
+    [1.0, 1.0, 1.0, 1.0],
+    [2.5, 2.5, 2.5, 1.0],
+    [6.0, 3.0, 1.0, 1.0],
+    [12.5, 12.5, 12.5, 1.0],
+    [20.0, 4.0, 0.5, 0.5],
+    [0.05, 0.2, 1.5, 1.0],
+]
+
+
+def pq_encode(nits):
+    y = min(max(nits / PQ_MAX_NITS, 0.0), 1.0) ** M1
+    return ((C1 + C2 * y) / (1.0 + C3 * y)) ** M2
+
+
+def pq_decode(signal):
+    e = min(max(signal, 0.0), 1.0) ** (1.0 / M2)
+    return (max(e - C1, 0.0) / (C2 - C3 * e)) ** (1.0 / M1) * PQ_MAX_NITS
+
+
+def aces(x):
+    return (x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14)
+
+
+def hable(x):
+    a, b, c, d, e, f = 0.15, 0.50, 0.10, 0.20, 0.02, 0.30
+    return ((x * (a * x + c * b) + d * e) / (x * (a * x + b) + d * f)) - e / f
+
+
+def bt2390(nits, peak, white):
+    """BT.2390 EETF mapping [0, peak] nits onto [0, white] nits."""
+    source_max = pq_encode(peak)
+    max_lum = pq_encode(white) / source_max
+    e1 = pq_encode(nits) / source_max
+    ks = 1.5 * max_lum - 0.5
+    if ks >= 1.0:
+        e2 = min(e1, 1.0)
+    elif e1 < ks:
+        e2 = e1
+    else:
+        t = min((e1 - ks) / (1.0 - ks), 1.0)
+        e2 = ((2 * t**3 - 3 * t**2 + 1) * ks
+              + (t**3 - 2 * t**2 + t) * (1.0 - ks)
+              + (-2 * t**3 + 3 * t**2) * max_lum)
+    return pq_decode(e2 * source_max)
+
+
+def linear_to_srgb(v):
+    v = min(max(v, 0.0), 1.0)
+    return v * 12.92 if v <= 0.0031308 else 1.055 * v ** (1.0 / 2.4) - 0.055
+
+
+def to_u8(v):
+    # Round half away from zero like Rust's f32::round
+    return int(min(max(v, 0.0), 1.0) * 255.0 + 0.5)
+
+
+def tone_map(operator, peak, white, pixel):
+    white = max(white, 1.0)
+    headroom = max(peak / white, 1.0)
+    rgb = [max(c, 0.0) * SCRGB_WHITE_NITS / white for c in pixel[:3]]
+
+    if operator == "aces":
+        out = [min(aces(c) / aces(headroom), 1.0) for c in rgb]
+    elif operator == "hable":
+        out = [min(hable(c) / hable(headroom), 1.0) for c in rgb]
+    else:
+        m = max(rgb)
+        if m <= 0.0:
+            out = [0.0, 0.0, 0.0]
+        else:
+            ratio = bt2390(m * white, white * headroom, white) / white / m
+            out = [min(c * ratio, 1.0) for c in rgb]
+
+    return [to_u8(linear_to_srgb(c)) for c in out] + [to_u8(pixel[3])]
+
+
+def main():
+    rows = []
+    for operator in ["aces", "hable", "bt2390"]:
+        for peak, white in DISPLAYS:
+            for pixel in INPUTS:
+                fixture = {
+                    "operator": operator,
+                    "peak_luminance": peak,
+                    "sdr_white_level": white,
+                    "input": pixel,
+                    "expected": tone_map(operator, peak, white, pixel),
+                }
+                rows.append("  " + json.dumps(fixture))
+    print("[\n" + ",\n".join(rows) + "\n]")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,22 @@
+use aumate_core_shared::Rectangle;
+use serde::{Deserialize, Serialize};
+
+/// 识别出的一行文本
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecognizedText {
+    pub text: String,
+    /// 文本在图像中的位置 (像素)
+    pub bounds: Rectangle,
+    /// 置信度 (0.0 - 1.0)
+    pub confidence: f32,
+}
+
+/// 按阅读顺序拼接识别结果，每行一条
+pub fn recognized_plain_text(lines: &[RecognizedText]) -> String {
+    lines
+        .iter()
+        .map(|line| line.text.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -1,5 +1,30 @@
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+/// 字段级校验错误
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    /// 字段路径，例如 `shortcuts.screenshot`
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { field: field.into(), message: message.into() }
+    }
+}
+
+impl std::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+fn join_field_errors(errors: &[FieldError]) -> String {
+    errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; ")
+}
+
 /// 领域层错误
 #[derive(Debug, Error)]
 pub enum DomainError {
@@ -29,6 +54,9 @@ pub enum DomainError {
 
     #[error("Domain validation failed: {0}")]
     ValidationFailed(String),
+
+    #[error("Invalid fields: {}", join_field_errors(.0))]
+    InvalidFields(Vec<FieldError>),
 }
 
 /// 基础设施层错误
@@ -61,8 +89,17 @@ pub enum InfrastructureError {
     #[error("Serialization failed: {0}")]
     SerializationFailed(String),
 
+    #[error("Database operation failed: {0}")]
+    DatabaseFailed(String),
+
     #[error("External library error: {0}")]
     ExternalError(String),
+
+    #[error("Network request failed: {0}")]
+    NetworkFailed(String),
+
+    #[error("Request timed out: {0}")]
+    Timeout(String),
 }
 
 /// 应用层错误 (Application Error)
@@ -4,6 +4,8 @@ pub mod errors;
 pub mod result;
 pub mod types;
 
-pub use errors::{ApiError, ApplicationError, DomainError, InfrastructureError, UseCaseError};
+pub use errors::{
+    ApiError, ApplicationError, DomainError, FieldError, InfrastructureError, UseCaseError,
+};
 pub use result::Result;
 pub use types::*;
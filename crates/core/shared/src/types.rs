@@ -3,6 +3,14 @@ use serde::{Deserialize, Serialize};
 /// 时间戳
 pub type Timestamp = std::time::SystemTime;
 
+/// 当前 Unix 时间 (毫秒)
+pub fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
 /// 点坐标
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Point {
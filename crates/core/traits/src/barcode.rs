@@ -0,0 +1,26 @@
+use aumate_core_shared::{InfrastructureError, Rectangle};
+
+pub use aumate_core_domain::barcode::{DecodedBarcode, QrCodeOptions};
+pub use aumate_core_domain::image::Image;
+
+/// 条码识别与 QR 码生成 Port
+///
+/// **实现者**:
+/// - `BarcodeAdapter`
+pub trait BarcodePort: Send + Sync {
+    /// 识别图像中的 QR 码、DataMatrix 和常见一维码
+    ///
+    /// 指定 `region` (图像像素) 时只识别该区域，返回的位置仍相对于整张图像
+    fn decode(
+        &self,
+        image: &Image,
+        region: Option<Rectangle>,
+    ) -> Result<Vec<DecodedBarcode>, InfrastructureError>;
+
+    /// 把文本生成为 QR 码图像
+    fn generate_qr(
+        &self,
+        text: &str,
+        options: &QrCodeOptions,
+    ) -> Result<Image, InfrastructureError>;
+}
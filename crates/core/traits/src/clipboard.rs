@@ -1,21 +1,75 @@
 use async_trait::async_trait;
 use aumate_core_shared::InfrastructureError;
-use std::path::PathBuf;
-
-/// 剪贴板内容
-#[derive(Debug, Clone)]
-pub enum ClipboardContent {
-    Text(String),
-    Image(Vec<u8>),
-    Files(Vec<PathBuf>),
-}
+
+pub use aumate_core_domain::clipboard::{ClipboardContent, ClipboardImage, ClipboardImageFormat};
 
 /// 剪贴板内容类型
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ClipboardType {
     Text,
     Image,
     Files,
+    Html,
+    Rtf,
+    /// 其他格式，以 MIME 类型 (或平台原生名称) 表示
+    Custom(String),
+}
+
+impl ClipboardType {
+    /// 由 MIME 类型得到内容类型
+    pub fn from_mime(mime: &str) -> Self {
+        match mime.trim().to_ascii_lowercase().as_str() {
+            "text/plain" => Self::Text,
+            "text/html" => Self::Html,
+            "text/rtf" | "application/rtf" => Self::Rtf,
+            "text/uri-list" => Self::Files,
+            m if m.starts_with("image/") => Self::Image,
+            _ => Self::Custom(mime.trim().to_string()),
+        }
+    }
+
+    /// 解析类型名 (`text`、`html` 等) 或 MIME 类型
+    pub fn parse(name: &str) -> Self {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "text" => Self::Text,
+            "image" => Self::Image,
+            "files" => Self::Files,
+            "html" => Self::Html,
+            "rtf" => Self::Rtf,
+            _ => Self::from_mime(name),
+        }
+    }
+}
+
+impl std::fmt::Display for ClipboardType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Image => write!(f, "image"),
+            Self::Files => write!(f, "files"),
+            Self::Html => write!(f, "html"),
+            Self::Rtf => write!(f, "rtf"),
+            Self::Custom(mime) => write!(f, "{}", mime),
+        }
+    }
+}
+
+/// 剪贴板变化事件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardChange {
+    /// 监听开始后的变化序号，从 1 开始
+    pub sequence: u64,
+    /// 变化后剪贴板上的内容类型，无法读取时为空
+    pub types: Vec<ClipboardType>,
+}
+
+/// 剪贴板变化事件流
+///
+/// 丢弃时停止监听
+#[async_trait]
+pub trait ClipboardChangeStream: Send {
+    /// 等待下一次变化，监听结束时返回 None
+    async fn next(&mut self) -> Option<ClipboardChange>;
 }
 
 /// 剪贴板 Port
@@ -31,12 +85,43 @@ pub trait ClipboardPort: Send + Sync {
     /// 读取剪贴板内容
     async fn read(&self) -> Result<ClipboardContent, InfrastructureError>;
 
+    /// 以指定格式读取剪贴板内容
+    async fn read_format(
+        &self,
+        format: ClipboardType,
+    ) -> Result<ClipboardContent, InfrastructureError>;
+
     /// 写入剪贴板
     async fn write(&self, content: ClipboardContent) -> Result<(), InfrastructureError>;
 
     /// 清空剪贴板
     async fn clear(&self) -> Result<(), InfrastructureError>;
 
-    /// 检查剪贴板内容类型
+    /// 剪贴板上实际存在的内容类型
     async fn get_available_types(&self) -> Result<Vec<ClipboardType>, InfrastructureError>;
+
+    /// 监听剪贴板变化
+    ///
+    /// 不支持变化通知的实现返回 `PlatformNotSupported`，调用方应改为轮询
+    async fn watch(&self) -> Result<Box<dyn ClipboardChangeStream>, InfrastructureError> {
+        Err(InfrastructureError::PlatformNotSupported)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clipboard_type_names() {
+        assert_eq!(ClipboardType::from_mime("text/html"), ClipboardType::Html);
+        assert_eq!(ClipboardType::from_mime("image/png"), ClipboardType::Image);
+        assert_eq!(ClipboardType::parse("RTF"), ClipboardType::Rtf);
+        assert_eq!(
+            ClipboardType::parse("application/x-test"),
+            ClipboardType::Custom("application/x-test".to_string())
+        );
+        assert_eq!(ClipboardType::Files.to_string(), "files");
+        assert_eq!(ClipboardType::Custom("text/csv".to_string()).to_string(), "text/csv");
+    }
 }
@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use aumate_core_shared::InfrastructureError;
+
+pub use aumate_core_domain::clipboard::ClipboardContent;
+pub use aumate_core_domain::clipboard_history::{
+    ClipboardHistoryEntry, ClipboardHistoryQuery, ClipboardHistoryRetention,
+};
+
+/// 剪贴板历史 Port
+///
+/// 负责持久化剪贴板历史，内容相同的条目只保存一份
+///
+/// **实现者**:
+/// - `SqliteClipboardHistoryAdapter`
+#[async_trait]
+pub trait ClipboardHistoryPort: Send + Sync {
+    /// 记录一次复制
+    ///
+    /// 已存在相同内容时不新增条目，而是更新其使用时间和次数。
+    /// 自定义格式等无法记录的内容返回 `None`
+    async fn record(
+        &self,
+        content: &ClipboardContent,
+        source_app: Option<&str>,
+    ) -> Result<Option<ClipboardHistoryEntry>, InfrastructureError>;
+
+    /// 查询条目，置顶条目在前，其余按最近使用时间排序
+    async fn list(
+        &self,
+        query: &ClipboardHistoryQuery,
+    ) -> Result<Vec<ClipboardHistoryEntry>, InfrastructureError>;
+
+    /// 获取条目
+    async fn get(&self, id: i64) -> Result<Option<ClipboardHistoryEntry>, InfrastructureError>;
+
+    /// 读取条目内容
+    async fn get_content(&self, id: i64) -> Result<Option<ClipboardContent>, InfrastructureError>;
+
+    /// 标记条目被再次使用
+    async fn touch(&self, id: i64) -> Result<(), InfrastructureError>;
+
+    /// 设置是否置顶，条目不存在时返回 `false`
+    async fn set_pinned(&self, id: i64, pinned: bool) -> Result<bool, InfrastructureError>;
+
+    /// 删除条目，条目不存在时返回 `false`
+    async fn delete(&self, id: i64) -> Result<bool, InfrastructureError>;
+
+    /// 清空历史，返回删除的条目数
+    async fn clear(&self, keep_pinned: bool) -> Result<usize, InfrastructureError>;
+
+    /// 按保留策略删除旧条目，返回删除的条目数
+    async fn apply_retention(
+        &self,
+        retention: &ClipboardHistoryRetention,
+    ) -> Result<usize, InfrastructureError>;
+}
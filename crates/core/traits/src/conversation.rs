@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+use aumate_core_shared::InfrastructureError;
+
+pub use aumate_core_domain::conversation::{
+    Conversation, ConversationMessage, ConversationQuery, NewConversationMessage,
+};
+
+/// AI 对话存储 Port
+///
+/// **实现者**:
+/// - `SqliteConversationAdapter`
+#[async_trait]
+pub trait ConversationRepositoryPort: Send + Sync {
+    /// 创建空对话
+    async fn create(&self, title: &str) -> Result<Conversation, InfrastructureError>;
+
+    /// 查询对话，按最近更新时间排序
+    async fn list(
+        &self,
+        query: &ConversationQuery,
+    ) -> Result<Vec<Conversation>, InfrastructureError>;
+
+    /// 获取对话
+    async fn get(&self, id: i64) -> Result<Option<Conversation>, InfrastructureError>;
+
+    /// 按时间顺序读取对话的全部消息
+    async fn messages(&self, id: i64) -> Result<Vec<ConversationMessage>, InfrastructureError>;
+
+    /// 追加消息并更新对话的时间和模型
+    ///
+    /// 对话仍是默认标题时，用第一条用户消息生成标题。对话不存在时返回 `None`
+    async fn append(
+        &self,
+        conversation_id: i64,
+        message: NewConversationMessage,
+    ) -> Result<Option<ConversationMessage>, InfrastructureError>;
+
+    /// 重命名，对话不存在时返回 `false`
+    async fn rename(&self, id: i64, title: &str) -> Result<bool, InfrastructureError>;
+
+    /// 删除对话及其消息，对话不存在时返回 `false`
+    async fn delete(&self, id: i64) -> Result<bool, InfrastructureError>;
+
+    /// 删除全部对话，返回删除的数量
+    async fn clear(&self) -> Result<usize, InfrastructureError>;
+}
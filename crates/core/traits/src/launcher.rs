@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use aumate_core_shared::InfrastructureError;
+use std::collections::HashMap;
+
+pub use aumate_core_domain::launcher::{LaunchIcon, LaunchItem, LaunchUsage, LauncherIndexStats};
+pub use aumate_core_domain::settings::LauncherSettings;
+
+/// 启动器索引 Port
+///
+/// 索引已安装的应用和设置中的文件夹
+///
+/// **实现者**:
+/// - `FileSystemLauncherIndexAdapter`
+#[async_trait]
+pub trait LauncherIndexPort: Send + Sync {
+    /// 增量刷新索引，只重新解析新增或修改过的条目
+    async fn refresh(
+        &self,
+        settings: &LauncherSettings,
+    ) -> Result<LauncherIndexStats, InfrastructureError>;
+
+    /// 索引中的全部条目
+    async fn items(&self) -> Result<Vec<LaunchItem>, InfrastructureError>;
+
+    /// 按 ID 查找条目
+    async fn get(&self, id: &str) -> Result<Option<LaunchItem>, InfrastructureError>;
+
+    /// 读取条目的图标，没有图标时返回 None
+    async fn icon(&self, id: &str) -> Result<Option<LaunchIcon>, InfrastructureError>;
+}
+
+/// 启动记录 Port，用于 frecency 排序
+///
+/// **实现者**:
+/// - `JsonLaunchUsageAdapter`
+#[async_trait]
+pub trait LaunchUsagePort: Send + Sync {
+    /// 全部条目的启动记录，按条目 ID 索引
+    async fn usage(&self) -> Result<HashMap<String, LaunchUsage>, InfrastructureError>;
+
+    /// 记录一次启动
+    async fn record(&self, id: &str, now: i64) -> Result<(), InfrastructureError>;
+}
+
+/// 启动应用或打开文件 Port
+///
+/// **实现者**:
+/// - `SystemLauncherAdapter`
+#[async_trait]
+pub trait LauncherPort: Send + Sync {
+    /// 启动条目，不等待进程退出
+    async fn launch(&self, item: &LaunchItem) -> Result<(), InfrastructureError>;
+}
@@ -1,29 +1,47 @@
 // Port 接口定义
 // 这些接口在 Domain 层定义，在 Infrastructure 层实现
 
+pub mod barcode;
 pub mod clipboard;
+pub mod clipboard_history;
+pub mod conversation;
 pub mod element_scanner;
 pub mod global_shortcut;
 pub mod hotkey;
+pub mod launcher;
+pub mod llm;
+pub mod measurement;
 pub mod page;
 pub mod platform;
 pub mod screenshot;
+pub mod screenshot_library;
 pub mod scroll;
+pub mod secret;
 pub mod settings;
 pub mod storage;
+pub mod text_recognition;
 pub mod window;
 
 // Re-export for convenience
+pub use barcode::BarcodePort;
 pub use clipboard::ClipboardPort;
+pub use clipboard_history::ClipboardHistoryPort;
+pub use conversation::ConversationRepositoryPort;
 pub use element_scanner::{ElementScannerPort, ElementType, ScannableElement};
 pub use global_shortcut::GlobalShortcutPort;
 pub use hotkey::{HotkeyListenerPort, InputEventHandler, InputSimulationPort};
+pub use launcher::{LaunchUsagePort, LauncherIndexPort, LauncherPort};
+pub use llm::{ChatCompletionStream, LlmPort};
+pub use measurement::EdgeDetectionPort;
 pub use page::PageManagementPort;
 pub use platform::PlatformInfoPort;
 pub use screenshot::{ImageProcessingPort, ScreenCapturePort};
+pub use screenshot_library::ScreenshotLibraryPort;
 pub use scroll::ScrollCapturePort;
+pub use secret::SecretStorePort;
 pub use settings::SettingsStoragePort;
 pub use storage::{CachePort, FileSystemPort};
+pub use text_recognition::TextRecognitionPort;
 pub use window::{
     MonitorInfo, UIAutomationPort, VibrancyEffect, WindowLayout, WindowLayoutPort, WindowListPort,
     WindowManagementPort, WindowVibrancyPort,
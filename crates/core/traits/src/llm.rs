@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use aumate_core_shared::InfrastructureError;
+
+pub use aumate_core_domain::llm::{ChatCompletionRequest, ChatMessage, ChatRole, LlmEndpoint};
+
+/// 聊天补全的增量文本流
+///
+/// 丢弃时取消请求
+#[async_trait]
+pub trait ChatCompletionStream: Send {
+    /// 等待下一段文本，回复结束时返回 None
+    async fn next(&mut self) -> Option<Result<String, InfrastructureError>>;
+}
+
+/// 大语言模型 Port
+///
+/// **实现者**:
+/// - `OpenAiCompatibleLlmAdapter`
+#[async_trait]
+pub trait LlmPort: Send + Sync {
+    /// 发送流式聊天补全请求
+    ///
+    /// 收到响应头后返回文本流，此前的连接失败、超时和服务端错误由实现者负责重试
+    async fn stream_chat(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<Box<dyn ChatCompletionStream>, InfrastructureError>;
+}
@@ -0,0 +1,13 @@
+use aumate_core_shared::InfrastructureError;
+
+pub use aumate_core_domain::image::Image;
+pub use aumate_core_domain::measurement::EdgeMap;
+
+/// 边缘检测 Port，用于测量截图中的 UI 间距
+///
+/// **实现者**:
+/// - `CannyEdgeDetectionAdapter`
+pub trait EdgeDetectionPort: Send + Sync {
+    /// 检测图像中的边缘，返回与图像尺寸相同的边缘图
+    fn detect_edges(&self, image: &Image) -> Result<EdgeMap, InfrastructureError>;
+}
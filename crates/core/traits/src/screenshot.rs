@@ -2,7 +2,10 @@ use async_trait::async_trait;
 use aumate_core_shared::{InfrastructureError, MonitorId, Point, Rectangle, WindowId};
 
 // 从 domain 导入核心类型
+pub use aumate_core_domain::annotation::AnnotationDocument;
+pub use aumate_core_domain::hdr::{HdrImage, HdrInfo, HdrScreenshot, ToneMappingOperator};
 pub use aumate_core_domain::image::Image;
+pub use aumate_core_domain::redaction::{RedactionReport, RedactionRequest};
 pub use aumate_core_domain::screenshot::{CaptureMetadata, CaptureRegion, Screenshot};
 
 /// 捕获目标
@@ -50,6 +53,7 @@ pub enum ImageFormat {
     Jpeg,
     WebP,
     Bmp,
+    Avif,
 }
 
 impl ImageFormat {
@@ -59,6 +63,7 @@ impl ImageFormat {
             "jpeg" | "jpg" => Ok(Self::Jpeg),
             "webp" => Ok(Self::WebP),
             "bmp" => Ok(Self::Bmp),
+            "avif" => Ok(Self::Avif),
             _ => {
                 Err(InfrastructureError::ExternalError(format!("Unsupported image format: {}", s)))
             }
@@ -71,6 +76,7 @@ impl ImageFormat {
             Self::Jpeg => "jpg",
             Self::WebP => "webp",
             Self::Bmp => "bmp",
+            Self::Avif => "avif",
         }
     }
 
@@ -80,6 +86,7 @@ impl ImageFormat {
             Self::Jpeg => "image/jpeg",
             Self::WebP => "image/webp",
             Self::Bmp => "image/bmp",
+            Self::Avif => "image/avif",
         }
     }
 }
@@ -113,6 +120,8 @@ pub struct Monitor {
     pub rect: Rectangle,
     pub scale_factor: f64,
     pub is_primary: bool,
+    /// 平台能报告时的 HDR 信息
+    pub hdr_info: Option<HdrInfo>,
 }
 
 /// 屏幕捕获 Port
@@ -132,6 +141,18 @@ pub trait ScreenCapturePort: Send + Sync {
         options: CaptureOptions,
     ) -> Result<Screenshot, InfrastructureError>;
 
+    /// 以 scRGB 浮点格式捕获，保留 SDR 白以上的高光
+    ///
+    /// 只在平台提供高位深桌面数据时可用，其他平台返回 `PlatformNotSupported`
+    async fn capture_hdr(
+        &self,
+        target: CaptureTarget,
+        options: CaptureOptions,
+    ) -> Result<HdrScreenshot, InfrastructureError> {
+        let _ = (target, options);
+        Err(InfrastructureError::PlatformNotSupported)
+    }
+
     /// 获取所有可用监视器
     async fn get_monitors(&self) -> Result<Vec<Monitor>, InfrastructureError>;
 
@@ -152,6 +173,18 @@ pub trait ImageProcessingPort: Send + Sync {
     /// 编码图像为指定格式
     fn encode(&self, image: &Image, format: ImageFormat) -> Result<Vec<u8>, InfrastructureError>;
 
+    /// 按指定质量 (1-100) 编码图像，`None` 使用格式的默认质量
+    ///
+    /// 质量只影响有损格式，默认实现忽略质量
+    fn encode_with_quality(
+        &self,
+        image: &Image,
+        format: ImageFormat,
+        _quality: Option<u8>,
+    ) -> Result<Vec<u8>, InfrastructureError> {
+        self.encode(image, format)
+    }
+
     /// 解码图像数据
     fn decode(&self, data: &[u8]) -> Result<Image, InfrastructureError>;
 
@@ -170,6 +203,28 @@ pub trait ImageProcessingPort: Send + Sync {
         algorithm: HdrCorrectionAlgorithm,
     ) -> Result<Image, InfrastructureError>;
 
+    /// 按监视器的峰值亮度和 SDR 白把浮点 HDR 图像色调映射为 8 位 sRGB
+    fn tone_map(
+        &self,
+        image: &HdrImage,
+        operator: ToneMappingOperator,
+        hdr_info: &HdrInfo,
+    ) -> Result<Image, InfrastructureError>;
+
     /// 调整图像大小
     fn resize(&self, image: &Image, width: u32, height: u32) -> Result<Image, InfrastructureError>;
+
+    /// 按 z-order 把标注文档绘制到图像上，返回新图像
+    fn render_annotations(
+        &self,
+        image: &Image,
+        document: &AnnotationDocument,
+    ) -> Result<Image, InfrastructureError>;
+
+    /// 遮挡手动指定的区域和 OCR 文本中的敏感内容，返回新图像和遮挡报告
+    fn redact(
+        &self,
+        image: &Image,
+        request: &RedactionRequest,
+    ) -> Result<(Image, RedactionReport), InfrastructureError>;
 }
@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use aumate_core_shared::InfrastructureError;
+
+pub use aumate_core_domain::screenshot_library::{
+    NewScreenshotRecord, ScreenshotLibraryQuery, ScreenshotRecord,
+};
+
+/// 截图库 Port
+///
+/// 负责保存截图文件、缩略图及其元数据
+///
+/// **实现者**:
+/// - `SqliteScreenshotLibraryAdapter`
+#[async_trait]
+pub trait ScreenshotLibraryPort: Send + Sync {
+    /// 保存编码后的截图和 PNG 缩略图，返回新条目
+    async fn add(
+        &self,
+        record: &NewScreenshotRecord,
+        data: &[u8],
+        thumbnail: &[u8],
+    ) -> Result<ScreenshotRecord, InfrastructureError>;
+
+    /// 查询截图，按捕获时间倒序
+    async fn list(
+        &self,
+        query: &ScreenshotLibraryQuery,
+    ) -> Result<Vec<ScreenshotRecord>, InfrastructureError>;
+
+    /// 获取条目
+    async fn get(&self, id: i64) -> Result<Option<ScreenshotRecord>, InfrastructureError>;
+
+    /// 读取截图文件内容
+    async fn read_image(&self, id: i64) -> Result<Option<Vec<u8>>, InfrastructureError>;
+
+    /// 读取 PNG 缩略图
+    async fn thumbnail(&self, id: i64) -> Result<Option<Vec<u8>>, InfrastructureError>;
+
+    /// 替换标签，条目不存在时返回 `false`
+    async fn set_tags(&self, id: i64, tags: &[String]) -> Result<bool, InfrastructureError>;
+
+    /// 设置备注，条目不存在时返回 `false`
+    async fn set_note(&self, id: i64, note: Option<&str>) -> Result<bool, InfrastructureError>;
+
+    /// 保存 OCR 文本，条目不存在时返回 `false`
+    async fn set_ocr_text(&self, id: i64, text: &str) -> Result<bool, InfrastructureError>;
+
+    /// 删除条目及其文件，条目不存在时返回 `false`
+    async fn delete(&self, id: i64) -> Result<bool, InfrastructureError>;
+}
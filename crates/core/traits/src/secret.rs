@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+use aumate_core_shared::InfrastructureError;
+
+/// 密钥存储 Port
+///
+/// 保存 API Key 等敏感信息，设置文件中只保存引用 (见 `aumate_core_domain::secret`)
+///
+/// **实现者**:
+/// - `EncryptedFileSecretStore`
+#[async_trait]
+pub trait SecretStorePort: Send + Sync {
+    /// 读取密钥，不存在时返回 None
+    async fn get(&self, name: &str) -> Result<Option<String>, InfrastructureError>;
+
+    /// 保存密钥，覆盖同名密钥
+    async fn set(&self, name: &str, value: &str) -> Result<(), InfrastructureError>;
+
+    /// 删除密钥，返回是否存在
+    async fn delete(&self, name: &str) -> Result<bool, InfrastructureError>;
+}
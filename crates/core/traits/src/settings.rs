@@ -1,6 +1,6 @@
 // Settings Storage Port
 use async_trait::async_trait;
-use aumate_core_domain::settings::Settings;
+use aumate_core_domain::settings::{Settings, SettingsLoadReport};
 use aumate_core_shared::InfrastructureError;
 
 /// Settings storage port
@@ -11,6 +11,18 @@ pub trait SettingsStoragePort: Send + Sync {
     /// Load settings from storage
     async fn load(&self) -> Result<Settings, InfrastructureError>;
 
+    /// Load settings, also reporting migrations and fields that were reset
+    async fn load_with_report(&self) -> Result<SettingsLoadReport, InfrastructureError> {
+        Ok(SettingsLoadReport::new(self.load().await?))
+    }
+
     /// Save settings to storage
     async fn save(&self, settings: Settings) -> Result<(), InfrastructureError>;
+
+    /// Replace plaintext API keys left in settings backups
+    ///
+    /// Called once the keys have moved to the secret store.
+    async fn scrub_backups(&self) -> Result<(), InfrastructureError> {
+        Ok(())
+    }
 }
@@ -17,6 +17,10 @@ pub trait FileSystemPort: Send + Sync {
     /// 写入文件
     async fn write(&self, path: &Path, content: &[u8]) -> Result<(), InfrastructureError>;
 
+    /// 原子写入文件：先写入同目录下的临时文件再重命名，
+    /// 中途失败不会留下半个文件
+    async fn write_atomic(&self, path: &Path, content: &[u8]) -> Result<(), InfrastructureError>;
+
     /// 删除文件
     async fn delete(&self, path: &Path) -> Result<(), InfrastructureError>;
 
@@ -37,6 +41,9 @@ pub trait FileSystemPort: Send + Sync {
 
     /// 获取应用配置目录
     fn get_app_config_dir(&self) -> Result<PathBuf, InfrastructureError>;
+
+    /// 获取用户图片目录
+    fn get_pictures_dir(&self) -> Result<PathBuf, InfrastructureError>;
 }
 
 /// 缓存 Port
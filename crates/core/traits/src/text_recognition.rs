@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use aumate_core_shared::InfrastructureError;
+
+pub use aumate_core_domain::image::Image;
+pub use aumate_core_domain::text_recognition::RecognizedText;
+
+/// 文本识别 (OCR) Port
+///
+/// **实现者**:
+/// - `TrOcrTextRecognitionAdapter` (需要 `ocr` feature)
+#[async_trait]
+pub trait TextRecognitionPort: Send + Sync {
+    /// 识别图像中的文本，按阅读顺序返回每一行及其位置
+    async fn recognize(&self, image: &Image) -> Result<Vec<RecognizedText>, InfrastructureError>;
+}
@@ -65,8 +65,14 @@ pub enum DrawWindowStyle {
 }
 
 /// 窗口配置
+///
+/// 位置和尺寸为屏幕物理像素
 #[derive(Debug, Clone)]
 pub struct WindowConfig {
+    /// 窗口标签，同时作为 `WindowId`
+    pub label: String,
+    /// 前端页面路径，如 `pages/pin.html`
+    pub url: String,
     pub title: String,
     pub width: u32,
     pub height: u32,
@@ -74,6 +80,9 @@ pub struct WindowConfig {
     pub y: i32,
     pub resizable: bool,
     pub decorations: bool,
+    pub always_on_top: bool,
+    pub transparent: bool,
+    pub skip_taskbar: bool,
 }
 
 /// 窗口管理 Port
@@ -115,6 +124,30 @@ pub trait WindowManagementPort: Send + Sync {
 
     /// 关闭窗口
     async fn close_window(&self, id: WindowId) -> Result<(), InfrastructureError>;
+
+    /// 设置窗口位置和大小 (物理像素)
+    async fn set_window_bounds(
+        &self,
+        id: WindowId,
+        bounds: Rectangle,
+    ) -> Result<(), InfrastructureError>;
+
+    /// 设置鼠标穿透
+    async fn set_click_through(
+        &self,
+        id: WindowId,
+        enable: bool,
+    ) -> Result<(), InfrastructureError>;
+
+    /// 显示或隐藏窗口
+    async fn set_window_visible(
+        &self,
+        id: WindowId,
+        visible: bool,
+    ) -> Result<(), InfrastructureError>;
+
+    /// 所有显示器的工作区 (不含任务栏和菜单栏，物理像素)
+    async fn get_work_areas(&self) -> Result<Vec<Rectangle>, InfrastructureError>;
 }
 
 /// UI 元素
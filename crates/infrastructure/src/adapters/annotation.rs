@@ -0,0 +1,141 @@
+// 截图标注渲染 (基于 aumate::annotate)
+use aumate::annotate::{self, AnnotationRenderer};
+use aumate_core_domain::annotation::{
+    Annotation, AnnotationColor, AnnotationDocument, AnnotationPoint, AnnotationShape,
+    AnnotationStyle,
+};
+use aumate_core_domain::image::{ColorFormat, Image, ImageMetadata, ImageSource};
+use aumate_core_shared::InfrastructureError;
+
+/// 把标注绘制到图像上，文字使用系统字体
+pub fn render_annotations(
+    image: &Image,
+    document: &AnnotationDocument,
+) -> Result<Image, InfrastructureError> {
+    document.validate().map_err(|e| InfrastructureError::ImageProcessingFailed(e.to_string()))?;
+
+    let mut buffer = to_rgba_image(image)?;
+    AnnotationRenderer::new()
+        .with_system_fonts()
+        .render(&mut buffer, &to_render_document(document))
+        .map_err(|e| InfrastructureError::ImageProcessingFailed(e.to_string()))?;
+    from_rgba_image(buffer)
+}
+
+pub(super) fn to_rgba_image(image: &Image) -> Result<image::RgbaImage, InfrastructureError> {
+    let rgba = image.to_rgba().map_err(InfrastructureError::ImageProcessingFailed)?;
+    image::RgbaImage::from_raw(rgba.width, rgba.height, rgba.data).ok_or_else(|| {
+        InfrastructureError::ImageProcessingFailed("Invalid image buffer".to_string())
+    })
+}
+
+pub(super) fn from_rgba_image(buffer: image::RgbaImage) -> Result<Image, InfrastructureError> {
+    let (width, height) = buffer.dimensions();
+    let metadata = ImageMetadata::new(ImageSource::Other);
+    Image::with_metadata(buffer.into_raw(), width, height, ColorFormat::RGBA, metadata)
+        .map_err(|e| InfrastructureError::ImageProcessingFailed(e.to_string()))
+}
+
+fn to_render_document(document: &AnnotationDocument) -> annotate::AnnotationDocument {
+    annotate::AnnotationDocument::new(
+        document.annotations.iter().map(to_render_annotation).collect(),
+    )
+}
+
+fn to_render_annotation(annotation: &Annotation) -> annotate::Annotation {
+    annotate::Annotation {
+        id: annotation.id.clone(),
+        z_index: annotation.z_index,
+        style: to_render_style(&annotation.style),
+        shape: to_render_shape(&annotation.shape),
+    }
+}
+
+fn to_render_style(style: &AnnotationStyle) -> annotate::Style {
+    annotate::Style {
+        stroke: to_render_color(style.stroke),
+        stroke_width: style.stroke_width,
+        fill: style.fill.map(to_render_color),
+        opacity: style.opacity,
+    }
+}
+
+pub(super) fn to_render_color(color: AnnotationColor) -> annotate::Color {
+    annotate::Color::rgba(color.r, color.g, color.b, color.a)
+}
+
+fn to_render_point(point: AnnotationPoint) -> annotate::Point {
+    annotate::Point::new(point.x, point.y)
+}
+
+fn to_render_shape(shape: &AnnotationShape) -> annotate::Shape {
+    use annotate::Shape;
+
+    match shape.clone() {
+        AnnotationShape::Rectangle { x, y, width, height, corner_radius } => {
+            Shape::Rectangle { x, y, width, height, corner_radius }
+        }
+        AnnotationShape::Ellipse { x, y, width, height } => Shape::Ellipse { x, y, width, height },
+        AnnotationShape::Line { from, to } => {
+            Shape::Line { from: to_render_point(from), to: to_render_point(to) }
+        }
+        AnnotationShape::Arrow { from, to } => {
+            Shape::Arrow { from: to_render_point(from), to: to_render_point(to) }
+        }
+        AnnotationShape::Freehand { points } => {
+            Shape::Freehand { points: points.into_iter().map(to_render_point).collect() }
+        }
+        AnnotationShape::Text { x, y, content, font_size, background } => {
+            Shape::Text { x, y, content, font_size, background: background.map(to_render_color) }
+        }
+        AnnotationShape::Step { x, y, number, radius } => Shape::Step { x, y, number, radius },
+        AnnotationShape::Mosaic { x, y, width, height, block_size } => {
+            Shape::Mosaic { x, y, width, height, block_size }
+        }
+        AnnotationShape::Blur { x, y, width, height, radius } => {
+            Shape::Blur { x, y, width, height, radius }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_document_keeps_json_layout() {
+        // 两边的 JSON 结构一致，N-API 和编辑器可以直接传同一份文档
+        let json = r##"{"annotations": [
+            {"type": "arrow", "id": "a", "z_index": 2, "from": {"x": 1, "y": 2}, "to": {"x": 30, "y": 4},
+             "style": {"stroke": "#1971C2", "stroke_width": 3, "fill": null, "opacity": 0.5}},
+            {"type": "text", "x": 4, "y": 4, "content": "hi", "font_size": 14, "background": "#FFFFFF"},
+            {"type": "mosaic", "x": 0, "y": 0, "width": 8, "height": 8, "block_size": 4}
+        ]}"##;
+        let document = AnnotationDocument::from_json(json).unwrap();
+        let expected = annotate::AnnotationDocument::from_json(json).unwrap();
+        assert_eq!(to_render_document(&document), expected);
+    }
+
+    #[test]
+    fn test_render_annotations() {
+        let image = Image::new(vec![255; 16 * 16 * 4], 16, 16, ColorFormat::RGBA).unwrap();
+        let document = AnnotationDocument::new(vec![
+            Annotation::new(AnnotationShape::Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: 8.0,
+                height: 8.0,
+                corner_radius: 0.0,
+            })
+            .with_style(AnnotationStyle {
+                stroke_width: 0.0,
+                fill: Some(AnnotationColor::rgb(0, 0, 255)),
+                ..AnnotationStyle::default()
+            }),
+        ]);
+
+        let rendered = render_annotations(&image, &document).unwrap();
+        assert_eq!(&rendered.data[..4], &[0, 0, 255, 255]);
+        assert_eq!(&rendered.data[rendered.data.len() - 4..], &[255, 255, 255, 255]);
+    }
+}
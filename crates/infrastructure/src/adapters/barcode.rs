@@ -0,0 +1,143 @@
+// 条码识别与 QR 码生成适配器 (基于 aumate::barcode)
+use super::annotation::{from_rgba_image, to_rgba_image};
+use aumate::barcode::{self, Barcode, QrOptions};
+use aumate_core_domain::barcode::{BarcodeFormat, QrErrorCorrection};
+use aumate_core_shared::{InfrastructureError, Point, Rectangle};
+use aumate_core_traits::barcode::{BarcodePort, DecodedBarcode, Image, QrCodeOptions};
+
+fn barcode_error(e: aumate::error::AumateError) -> InfrastructureError {
+    InfrastructureError::ImageProcessingFailed(e.to_string())
+}
+
+/// 条码适配器
+///
+/// 识别基于 rxing，支持 QR、DataMatrix、EAN/UPC、Code 128/39/93 等
+#[derive(Debug, Default)]
+pub struct BarcodeAdapter;
+
+impl BarcodeAdapter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl BarcodePort for BarcodeAdapter {
+    fn decode(
+        &self,
+        image: &Image,
+        region: Option<Rectangle>,
+    ) -> Result<Vec<DecodedBarcode>, InfrastructureError> {
+        let buffer = to_rgba_image(image)?;
+        let barcodes = match region {
+            Some(region) => {
+                let image_bounds =
+                    Rectangle::from_bounds(0, 0, buffer.width() as i32, buffer.height() as i32);
+                let region = region.clip_rect(&image_bounds);
+                if region.max_x() <= region.min_x() || region.max_y() <= region.min_y() {
+                    return Err(InfrastructureError::ImageProcessingFailed(
+                        "Scan region is outside the image".to_string(),
+                    ));
+                }
+                barcode::scan_region(
+                    &buffer,
+                    region.min_x() as u32,
+                    region.min_y() as u32,
+                    region.width(),
+                    region.height(),
+                )
+            }
+            None => barcode::scan(&buffer),
+        }
+        .map_err(barcode_error)?;
+
+        Ok(barcodes.into_iter().map(to_decoded_barcode).collect())
+    }
+
+    fn generate_qr(
+        &self,
+        text: &str,
+        options: &QrCodeOptions,
+    ) -> Result<Image, InfrastructureError> {
+        let options = QrOptions::new()
+            .with_module_size(options.module_size)
+            .with_quiet_zone(options.quiet_zone)
+            .with_error_correction(to_render_error_correction(options.error_correction));
+        from_rgba_image(barcode::generate_qr(text, &options).map_err(barcode_error)?)
+    }
+}
+
+fn to_decoded_barcode(barcode: Barcode) -> DecodedBarcode {
+    let bounds = barcode.bounds;
+    DecodedBarcode {
+        format: from_render_format(barcode.format),
+        text: barcode.text,
+        bounds: Rectangle::from_bounds(
+            bounds.x as i32,
+            bounds.y as i32,
+            (bounds.x + bounds.width) as i32,
+            (bounds.y + bounds.height) as i32,
+        ),
+        points: barcode
+            .points
+            .into_iter()
+            .map(|(x, y)| Point::new(x.round() as i32, y.round() as i32))
+            .collect(),
+    }
+}
+
+fn from_render_format(format: barcode::BarcodeFormat) -> BarcodeFormat {
+    use barcode::BarcodeFormat as Render;
+    match format {
+        Render::QrCode => BarcodeFormat::QrCode,
+        Render::MicroQrCode => BarcodeFormat::MicroQrCode,
+        Render::DataMatrix => BarcodeFormat::DataMatrix,
+        Render::Aztec => BarcodeFormat::Aztec,
+        Render::Pdf417 => BarcodeFormat::Pdf417,
+        Render::MaxiCode => BarcodeFormat::MaxiCode,
+        Render::Ean8 => BarcodeFormat::Ean8,
+        Render::Ean13 => BarcodeFormat::Ean13,
+        Render::UpcA => BarcodeFormat::UpcA,
+        Render::UpcE => BarcodeFormat::UpcE,
+        Render::Code39 => BarcodeFormat::Code39,
+        Render::Code93 => BarcodeFormat::Code93,
+        Render::Code128 => BarcodeFormat::Code128,
+        Render::Codabar => BarcodeFormat::Codabar,
+        Render::Itf => BarcodeFormat::Itf,
+        Render::Rss14 => BarcodeFormat::Rss14,
+        Render::RssExpanded => BarcodeFormat::RssExpanded,
+        Render::Other => BarcodeFormat::Other,
+    }
+}
+
+fn to_render_error_correction(level: QrErrorCorrection) -> barcode::QrErrorCorrection {
+    match level {
+        QrErrorCorrection::Low => barcode::QrErrorCorrection::Low,
+        QrErrorCorrection::Medium => barcode::QrErrorCorrection::Medium,
+        QrErrorCorrection::Quartile => barcode::QrErrorCorrection::Quartile,
+        QrErrorCorrection::High => barcode::QrErrorCorrection::High,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_qr_decodes() {
+        let adapter = BarcodeAdapter::new();
+        let options = QrCodeOptions { module_size: 4, ..Default::default() };
+        let image = adapter.generate_qr("https://example.com/login?code=42", &options).unwrap();
+
+        let barcodes = adapter.decode(&image, None).unwrap();
+        assert_eq!(barcodes.len(), 1);
+        assert_eq!(barcodes[0].format, BarcodeFormat::QrCode);
+        assert_eq!(barcodes[0].text, "https://example.com/login?code=42");
+        assert!(barcodes[0].bounds.width() > 0);
+
+        // 区域超出图像时裁剪到图像内
+        let region = Rectangle::from_bounds(-10, -10, 1000, 1000);
+        assert_eq!(adapter.decode(&image, Some(region)).unwrap().len(), 1);
+        let outside = Rectangle::from_bounds(2000, 2000, 2100, 2100);
+        assert!(adapter.decode(&image, Some(outside)).is_err());
+    }
+}
@@ -1,8 +1,11 @@
-// 剪贴板适配器 (使用 arboard)
+// 剪贴板适配器 (使用 arboard，富文本和自定义格式使用 aumate::clipboard)
 use arboard::{Clipboard, ImageData};
 use async_trait::async_trait;
 use aumate_core_shared::InfrastructureError;
-use aumate_core_traits::clipboard::{ClipboardContent, ClipboardPort, ClipboardType};
+use aumate_core_traits::clipboard::{
+    ClipboardChange, ClipboardChangeStream, ClipboardContent, ClipboardImage, ClipboardImageFormat,
+    ClipboardPort, ClipboardType,
+};
 use std::borrow::Cow;
 
 /// 剪贴板适配器
@@ -23,29 +26,114 @@ impl Default for ClipboardAdapter {
     }
 }
 
+/// 基于 aumate 监听器的变化事件流 (X11 上使用 XFixes 通知，其他平台轮询)
+struct WatcherChangeStream {
+    watcher: aumate::clipboard::ClipboardWatcher,
+}
+
+#[async_trait]
+impl ClipboardChangeStream for WatcherChangeStream {
+    async fn next(&mut self) -> Option<ClipboardChange> {
+        let change = self.watcher.recv().await?;
+        let mut types: Vec<ClipboardType> = Vec::new();
+        for ty in change.formats.iter().map(|mime| ClipboardType::from_mime(mime)) {
+            if !types.contains(&ty) {
+                types.push(ty);
+            }
+        }
+        Some(ClipboardChange { sequence: change.sequence, types })
+    }
+}
+
+/// `read` 时的类型优先级，其余 (自定义) 类型排在最后
+const READ_PRIORITY: [ClipboardType; 5] = [
+    // 文件管理器复制时通常同时提供路径文本，所以文件列表优先
+    ClipboardType::Files,
+    ClipboardType::Image,
+    ClipboardType::Html,
+    ClipboardType::Rtf,
+    ClipboardType::Text,
+];
+
+fn clipboard_error(e: impl std::fmt::Display) -> InfrastructureError {
+    InfrastructureError::ClipboardFailed(e.to_string())
+}
+
+/// 剪贴板上实际存在的类型
+///
+/// 优先查询平台报告的格式列表；查询失败时 (例如纯 Wayland 会话) 逐一尝试读取
+fn available_types(clipboard: &mut Clipboard) -> Vec<ClipboardType> {
+    let mut types: Vec<ClipboardType> = Vec::new();
+    match aumate::clipboard::available_formats() {
+        Ok(formats) => {
+            for ty in formats.iter().map(|mime| ClipboardType::from_mime(mime)) {
+                if !types.contains(&ty) {
+                    types.push(ty);
+                }
+            }
+        }
+        Err(e) => {
+            log::warn!("Failed to list clipboard formats, probing instead: {}", e);
+            if clipboard.get_text().is_ok() {
+                types.push(ClipboardType::Text);
+            }
+            if clipboard.get_image().is_ok() {
+                types.push(ClipboardType::Image);
+            }
+            if clipboard.get().file_list().is_ok_and(|paths| !paths.is_empty()) {
+                types.push(ClipboardType::Files);
+            }
+        }
+    }
+    types
+}
+
+/// 以指定类型读取，剪贴板上没有该类型时返回 None
+fn read_type(
+    clipboard: &mut Clipboard,
+    ty: &ClipboardType,
+) -> Result<Option<ClipboardContent>, InfrastructureError> {
+    let content = match ty {
+        ClipboardType::Text => clipboard.get_text().ok().map(ClipboardContent::Text),
+        ClipboardType::Image => match clipboard.get_image() {
+            Ok(image) => Some(ClipboardContent::Image(to_clipboard_image(image)?)),
+            Err(_) => None,
+        },
+        ClipboardType::Files => {
+            clipboard.get().file_list().ok().filter(|p| !p.is_empty()).map(ClipboardContent::Files)
+        }
+        ClipboardType::Html => match clipboard.get().html() {
+            Ok(html) => Some(ClipboardContent::Html { html, alt_text: clipboard.get_text().ok() }),
+            Err(_) => None,
+        },
+        ClipboardType::Rtf => match aumate::clipboard::get_rtf() {
+            Ok(rtf) => Some(ClipboardContent::Rtf { rtf, alt_text: clipboard.get_text().ok() }),
+            Err(_) => None,
+        },
+        ClipboardType::Custom(mime) => aumate::clipboard::get_data(mime)
+            .ok()
+            .map(|data| ClipboardContent::Custom { mime: mime.clone(), data }),
+    };
+    Ok(content)
+}
+
 #[async_trait]
 impl ClipboardPort for ClipboardAdapter {
     async fn read(&self) -> Result<ClipboardContent, InfrastructureError> {
         log::info!("ClipboardAdapter: reading clipboard");
 
         tokio::task::spawn_blocking(|| {
-            let mut clipboard = Clipboard::new()
-                .map_err(|e| InfrastructureError::ClipboardFailed(e.to_string()))?;
-
-            // 1. 尝试读取图像
-            if let Ok(image) = clipboard.get_image() {
-                let width = image.width;
-                let height = image.height;
-                let data = image.bytes.into_owned();
+            let mut clipboard = Clipboard::new().map_err(clipboard_error)?;
 
-                log::info!("Read image from clipboard: {}x{}", width, height);
-                return Ok(ClipboardContent::Image(data));
-            }
+            let mut types = available_types(&mut clipboard);
+            types
+                .sort_by_key(|ty| READ_PRIORITY.iter().position(|p| p == ty).unwrap_or(usize::MAX));
 
-            // 2. 尝试读取文本
-            if let Ok(text) = clipboard.get_text() {
-                log::info!("Read text from clipboard: {} chars", text.len());
-                return Ok(ClipboardContent::Text(text));
+            for ty in &types {
+                if let Some(content) = read_type(&mut clipboard, ty)? {
+                    log::info!("Read {} from clipboard", ty);
+                    return Ok(content);
+                }
             }
 
             Err(InfrastructureError::ClipboardFailed(
@@ -53,137 +141,129 @@ impl ClipboardPort for ClipboardAdapter {
             ))
         })
         .await
-        .map_err(|e| InfrastructureError::ClipboardFailed(e.to_string()))?
+        .map_err(clipboard_error)?
+    }
+
+    async fn read_format(
+        &self,
+        format: ClipboardType,
+    ) -> Result<ClipboardContent, InfrastructureError> {
+        log::info!("ClipboardAdapter: reading clipboard as {}", format);
+
+        tokio::task::spawn_blocking(move || {
+            let mut clipboard = Clipboard::new().map_err(clipboard_error)?;
+            read_type(&mut clipboard, &format)?.ok_or_else(|| {
+                InfrastructureError::ClipboardFailed(format!("Clipboard has no {} content", format))
+            })
+        })
+        .await
+        .map_err(clipboard_error)?
     }
 
     async fn write(&self, content: ClipboardContent) -> Result<(), InfrastructureError> {
         log::info!("ClipboardAdapter: writing to clipboard");
 
         tokio::task::spawn_blocking(move || {
-            let mut clipboard = Clipboard::new()
-                .map_err(|e| InfrastructureError::ClipboardFailed(e.to_string()))?;
+            let mut clipboard = Clipboard::new().map_err(clipboard_error)?;
 
             match content {
-                ClipboardContent::Image(data) => {
-                    // arboard 需要知道图像的宽高和格式
-                    // 这里假设是 RGBA 格式，需要从外部传入宽高信息
-                    // 暂时返回错误，需要改进接口
-                    log::warn!("Image write not fully implemented - need width/height info");
-                    Err(InfrastructureError::ClipboardFailed(
-                        "Image write needs width/height information".to_string(),
-                    ))
+                ClipboardContent::Image(image) => {
+                    let (width, height) = (image.width, image.height);
+                    clipboard.set_image(to_image_data(image)?).map_err(clipboard_error)?;
+                    log::info!("Image {}x{} written to clipboard", width, height);
                 }
                 ClipboardContent::Text(text) => {
-                    clipboard
-                        .set_text(text)
-                        .map_err(|e| InfrastructureError::ClipboardFailed(e.to_string()))?;
+                    clipboard.set_text(text).map_err(clipboard_error)?;
                     log::info!("Text written to clipboard");
-                    Ok(())
                 }
-                ClipboardContent::Files(_paths) => {
-                    log::warn!("File list write not yet implemented");
-                    Err(InfrastructureError::ClipboardFailed(
-                        "File write not yet implemented".to_string(),
-                    ))
+                ClipboardContent::Files(paths) => {
+                    if paths.is_empty() {
+                        return Err(InfrastructureError::ClipboardFailed(
+                            "File list is empty".to_string(),
+                        ));
+                    }
+                    clipboard.set().file_list(&paths).map_err(clipboard_error)?;
+                    log::info!("{} files written to clipboard", paths.len());
+                }
+                ClipboardContent::Html { html, alt_text } => {
+                    clipboard.set_html(html, alt_text).map_err(clipboard_error)?;
+                    log::info!("HTML written to clipboard");
+                }
+                ClipboardContent::Rtf { rtf, alt_text } => {
+                    aumate::clipboard::set_rtf(&rtf, alt_text.as_deref())
+                        .map_err(clipboard_error)?;
+                    log::info!("RTF written to clipboard");
+                }
+                ClipboardContent::Custom { mime, data } => {
+                    aumate::clipboard::set_data(&[(mime.as_str(), data.as_slice())])
+                        .map_err(clipboard_error)?;
+                    log::info!("{} ({} bytes) written to clipboard", mime, data.len());
                 }
             }
+            Ok(())
         })
         .await
-        .map_err(|e| InfrastructureError::ClipboardFailed(e.to_string()))?
+        .map_err(clipboard_error)?
     }
 
     async fn clear(&self) -> Result<(), InfrastructureError> {
         log::info!("ClipboardAdapter: clearing clipboard");
 
         tokio::task::spawn_blocking(|| {
-            let mut clipboard = Clipboard::new()
-                .map_err(|e| InfrastructureError::ClipboardFailed(e.to_string()))?;
+            let mut clipboard = Clipboard::new().map_err(clipboard_error)?;
 
-            clipboard.clear().map_err(|e| InfrastructureError::ClipboardFailed(e.to_string()))?;
+            clipboard.clear().map_err(clipboard_error)?;
 
             log::info!("Clipboard cleared");
             Ok(())
         })
         .await
-        .map_err(|e| InfrastructureError::ClipboardFailed(e.to_string()))?
+        .map_err(clipboard_error)?
     }
 
     async fn get_available_types(&self) -> Result<Vec<ClipboardType>, InfrastructureError> {
         log::info!("ClipboardAdapter: getting available clipboard types");
 
         tokio::task::spawn_blocking(|| {
-            let mut clipboard = Clipboard::new()
-                .map_err(|e| InfrastructureError::ClipboardFailed(e.to_string()))?;
-
-            let mut types = Vec::new();
-
-            // 检查是否有文本
-            if clipboard.get_text().is_ok() {
-                types.push(ClipboardType::Text);
-            }
-
-            // 检查是否有图像
-            if clipboard.get_image().is_ok() {
-                types.push(ClipboardType::Image);
-            }
-
-            Ok(types)
+            let mut clipboard = Clipboard::new().map_err(clipboard_error)?;
+            Ok(available_types(&mut clipboard))
         })
         .await
-        .map_err(|e| InfrastructureError::ClipboardFailed(e.to_string()))?
+        .map_err(clipboard_error)?
     }
-}
-
-// 辅助方法：写入图像到剪贴板
-impl ClipboardAdapter {
-    /// 写入 RGBA 图像到剪贴板
-    pub async fn write_image_rgba(
-        &self,
-        data: Vec<u8>,
-        width: usize,
-        height: usize,
-    ) -> Result<(), InfrastructureError> {
-        log::info!("ClipboardAdapter: writing RGBA image {}x{} to clipboard", width, height);
 
-        tokio::task::spawn_blocking(move || {
-            let mut clipboard = Clipboard::new()
-                .map_err(|e| InfrastructureError::ClipboardFailed(e.to_string()))?;
-
-            let image_data = ImageData { width, height, bytes: Cow::Owned(data) };
-
-            clipboard
-                .set_image(image_data)
-                .map_err(|e| InfrastructureError::ClipboardFailed(e.to_string()))?;
+    async fn watch(&self) -> Result<Box<dyn ClipboardChangeStream>, InfrastructureError> {
+        log::info!("ClipboardAdapter: watching clipboard");
 
-            log::info!("RGBA image written to clipboard");
-            Ok(())
-        })
-        .await
-        .map_err(|e| InfrastructureError::ClipboardFailed(e.to_string()))?
+        let watcher = tokio::task::spawn_blocking(aumate::clipboard::watch)
+            .await
+            .map_err(clipboard_error)?
+            .map_err(clipboard_error)?;
+        log::info!("Watching clipboard with {:?}", watcher.backend());
+        Ok(Box::new(WatcherChangeStream { watcher }))
     }
+}
 
-    /// 从剪贴板读取图像（返回 RGBA 数据和尺寸）
-    pub async fn read_image_rgba(&self) -> Result<(Vec<u8>, usize, usize), InfrastructureError> {
-        log::info!("ClipboardAdapter: reading RGBA image from clipboard");
-
-        tokio::task::spawn_blocking(|| {
-            let mut clipboard = Clipboard::new()
-                .map_err(|e| InfrastructureError::ClipboardFailed(e.to_string()))?;
-
-            let image = clipboard
-                .get_image()
-                .map_err(|e| InfrastructureError::ClipboardFailed(e.to_string()))?;
-
-            let width = image.width;
-            let height = image.height;
-            let data = image.bytes.into_owned();
+/// arboard 图像 (RGBA) 转为剪贴板图像
+fn to_clipboard_image(image: ImageData<'_>) -> Result<ClipboardImage, InfrastructureError> {
+    ClipboardImage::rgba(image.bytes.into_owned(), image.width as u32, image.height as u32)
+        .map_err(InfrastructureError::ClipboardFailed)
+}
 
-            log::info!("Read RGBA image {}x{} from clipboard", width, height);
-            Ok((data, width, height))
-        })
-        .await
-        .map_err(|e| InfrastructureError::ClipboardFailed(e.to_string()))?
-    }
+/// 剪贴板图像转为 arboard 需要的 RGBA 数据，PNG 在此解码
+fn to_image_data(image: ClipboardImage) -> Result<ImageData<'static>, InfrastructureError> {
+    let (width, height, bytes) = match image.format {
+        ClipboardImageFormat::Rgba8 => (image.width, image.height, image.data),
+        ClipboardImageFormat::Png => {
+            let decoded = image::load_from_memory_with_format(&image.data, image::ImageFormat::Png)
+                .map_err(|e| {
+                    InfrastructureError::ClipboardFailed(format!("Failed to decode PNG: {}", e))
+                })?
+                .to_rgba8();
+            (decoded.width(), decoded.height(), decoded.into_raw())
+        }
+    };
+    Ok(ImageData { width: width as usize, height: height as usize, bytes: Cow::Owned(bytes) })
 }
 
 #[cfg(test)]
@@ -209,6 +289,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_png_image_data_roundtrip() {
+        let rgba = image::RgbaImage::from_fn(3, 2, |x, y| image::Rgba([x as u8, y as u8, 7, 255]));
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(rgba.clone())
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let data = to_image_data(ClipboardImage::png(png).unwrap()).unwrap();
+        assert_eq!((data.width, data.height), (3, 2));
+        assert_eq!(data.bytes.as_ref(), rgba.as_raw().as_slice());
+
+        let image = to_clipboard_image(data).unwrap();
+        assert_eq!(image.format, ClipboardImageFormat::Rgba8);
+        assert_eq!(image.data, rgba.into_raw());
+    }
+
     #[tokio::test]
     async fn test_clipboard_clear() {
         let adapter = ClipboardAdapter::new();
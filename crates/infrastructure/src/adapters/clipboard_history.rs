@@ -0,0 +1,591 @@
+// Clipboard History Adapter
+use super::sqlite::{SqliteDatabase, like_pattern};
+use async_trait::async_trait;
+use aumate_core_domain::clipboard::{ClipboardImage, ClipboardImageFormat};
+use aumate_core_domain::clipboard_history::{
+    ClipboardHistoryKind, clipboard_preview, clipboard_search_text,
+};
+use aumate_core_shared::{InfrastructureError, now_ms};
+use aumate_core_traits::clipboard_history::{
+    ClipboardContent, ClipboardHistoryEntry, ClipboardHistoryPort, ClipboardHistoryQuery,
+    ClipboardHistoryRetention,
+};
+use rusqlite::types::Value;
+use rusqlite::{Connection, OptionalExtension, Row, params, params_from_iter};
+use sha2::{Digest, Sha256};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS clipboard_entries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,
+    content_hash TEXT NOT NULL UNIQUE,
+    preview TEXT NOT NULL,
+    search_text TEXT NOT NULL,
+    payload BLOB NOT NULL,
+    alt_text TEXT,
+    byte_size INTEGER NOT NULL,
+    image_width INTEGER,
+    image_height INTEGER,
+    source_app TEXT,
+    pinned INTEGER NOT NULL DEFAULT 0,
+    use_count INTEGER NOT NULL DEFAULT 1,
+    created_at INTEGER NOT NULL,
+    last_used_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS clipboard_entries_last_used ON clipboard_entries(pinned, last_used_at);
+
+-- trigram 分词支持中文等无空格文本的子串搜索
+CREATE VIRTUAL TABLE IF NOT EXISTS clipboard_fts USING fts5(
+    search_text,
+    content = 'clipboard_entries',
+    content_rowid = 'id',
+    tokenize = 'trigram'
+);
+CREATE TRIGGER IF NOT EXISTS clipboard_entries_ai AFTER INSERT ON clipboard_entries BEGIN
+    INSERT INTO clipboard_fts(rowid, search_text) VALUES (new.id, new.search_text);
+END;
+CREATE TRIGGER IF NOT EXISTS clipboard_entries_ad AFTER DELETE ON clipboard_entries BEGIN
+    INSERT INTO clipboard_fts(clipboard_fts, rowid, search_text)
+    VALUES ('delete', old.id, old.search_text);
+END;
+";
+
+const ENTRY_COLUMNS: &str = "id, kind, preview, content_hash, source_app, pinned, use_count, \
+                             byte_size, image_width, image_height, created_at, last_used_at";
+
+/// trigram 分词无法匹配更短的关键字，改用 LIKE
+const MIN_FTS_TERM_CHARS: usize = 3;
+
+const DATABASE_NAME: &str = "Clipboard history";
+
+/// SQLite 剪贴板历史适配器
+///
+/// 文本、HTML 和 RTF 按原样保存，图像保存为 PNG，文件列表保存为 JSON
+pub struct SqliteClipboardHistoryAdapter {
+    db: SqliteDatabase,
+}
+
+impl SqliteClipboardHistoryAdapter {
+    /// 默认数据库路径 (~/.aumate/clipboard_history.db)
+    pub fn default_path() -> PathBuf {
+        let home = dirs::home_dir().expect("Could not find home directory");
+        home.join(".aumate").join("clipboard_history.db")
+    }
+
+    /// 打开 (或创建) 数据库文件
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, InfrastructureError> {
+        Ok(Self { db: SqliteDatabase::open(path, DATABASE_NAME, SCHEMA)? })
+    }
+
+    /// 内存数据库，用于测试
+    pub fn in_memory() -> Result<Self, InfrastructureError> {
+        Ok(Self { db: SqliteDatabase::in_memory(DATABASE_NAME, SCHEMA)? })
+    }
+}
+
+/// 内容哈希，同一图像无论以 RGBA 还是 PNG 形式出现都得到相同结果
+pub fn clipboard_content_hash(
+    content: &ClipboardContent,
+) -> Result<Option<String>, InfrastructureError> {
+    let Some(kind) = ClipboardHistoryKind::of(content) else {
+        return Ok(None);
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(kind.as_str().as_bytes());
+    hasher.update([0]);
+    match content {
+        ClipboardContent::Text(text) => hasher.update(text.as_bytes()),
+        ClipboardContent::Image(image) => {
+            let rgba = image_rgba(image)?;
+            hasher.update(rgba.width().to_le_bytes());
+            hasher.update(rgba.height().to_le_bytes());
+            hasher.update(rgba.as_raw());
+        }
+        ClipboardContent::Files(paths) => {
+            for path in paths {
+                hasher.update(path.to_string_lossy().as_bytes());
+                hasher.update([0]);
+            }
+        }
+        ClipboardContent::Html { html: markup, alt_text }
+        | ClipboardContent::Rtf { rtf: markup, alt_text } => {
+            hasher.update(markup.as_bytes());
+            hasher.update([0]);
+            hasher.update(alt_text.as_deref().unwrap_or_default().as_bytes());
+        }
+        ClipboardContent::Custom { .. } => return Ok(None),
+    }
+    let digest = hasher.finalize();
+    Ok(Some(digest.iter().map(|b| format!("{:02x}", b)).collect()))
+}
+
+fn image_rgba(image: &ClipboardImage) -> Result<image::RgbaImage, InfrastructureError> {
+    match image.format {
+        ClipboardImageFormat::Rgba8 => image::RgbaImage::from_raw(
+            image.width,
+            image.height,
+            image.data.clone(),
+        )
+        .ok_or_else(|| {
+            InfrastructureError::ImageProcessingFailed("Invalid RGBA image data".to_string())
+        }),
+        ClipboardImageFormat::Png => {
+            image::load_from_memory_with_format(&image.data, image::ImageFormat::Png)
+                .map(|decoded| decoded.to_rgba8())
+                .map_err(|e| {
+                    InfrastructureError::ImageProcessingFailed(format!(
+                        "Failed to decode PNG: {}",
+                        e
+                    ))
+                })
+        }
+    }
+}
+
+/// 内容序列化为 (payload, alt_text)
+fn encode_payload(
+    content: &ClipboardContent,
+) -> Result<(Vec<u8>, Option<String>), InfrastructureError> {
+    Ok(match content {
+        ClipboardContent::Text(text) => (text.as_bytes().to_vec(), None),
+        ClipboardContent::Image(image) if image.is_png() => (image.data.clone(), None),
+        ClipboardContent::Image(image) => {
+            let mut png = Vec::new();
+            image_rgba(image)?
+                .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+                .map_err(|e| {
+                    InfrastructureError::ImageProcessingFailed(format!(
+                        "Failed to encode PNG: {}",
+                        e
+                    ))
+                })?;
+            (png, None)
+        }
+        ClipboardContent::Files(paths) => {
+            let paths: Vec<String> =
+                paths.iter().map(|path| path.to_string_lossy().into_owned()).collect();
+            let json = serde_json::to_vec(&paths)
+                .map_err(|e| InfrastructureError::SerializationFailed(e.to_string()))?;
+            (json, None)
+        }
+        ClipboardContent::Html { html, alt_text } => (html.as_bytes().to_vec(), alt_text.clone()),
+        ClipboardContent::Rtf { rtf, alt_text } => (rtf.as_bytes().to_vec(), alt_text.clone()),
+        ClipboardContent::Custom { mime, .. } => {
+            return Err(InfrastructureError::ClipboardFailed(format!(
+                "{} is not recorded in history",
+                mime
+            )));
+        }
+    })
+}
+
+fn decode_payload(
+    kind: ClipboardHistoryKind,
+    payload: Vec<u8>,
+    alt_text: Option<String>,
+) -> Result<ClipboardContent, InfrastructureError> {
+    let text = |bytes: Vec<u8>| String::from_utf8_lossy(&bytes).into_owned();
+    Ok(match kind {
+        ClipboardHistoryKind::Text => ClipboardContent::Text(text(payload)),
+        ClipboardHistoryKind::Image => ClipboardContent::Image(
+            ClipboardImage::png(payload).map_err(InfrastructureError::ImageProcessingFailed)?,
+        ),
+        ClipboardHistoryKind::Files => {
+            let paths: Vec<String> = serde_json::from_slice(&payload)
+                .map_err(|e| InfrastructureError::SerializationFailed(e.to_string()))?;
+            ClipboardContent::Files(paths.into_iter().map(PathBuf::from).collect())
+        }
+        ClipboardHistoryKind::Html => ClipboardContent::Html { html: text(payload), alt_text },
+        ClipboardHistoryKind::Rtf => ClipboardContent::Rtf { rtf: text(payload), alt_text },
+    })
+}
+
+fn parse_kind(kind: &str) -> rusqlite::Result<ClipboardHistoryKind> {
+    ClipboardHistoryKind::parse(kind).ok_or_else(|| {
+        rusqlite::Error::FromSqlConversionFailure(
+            0,
+            rusqlite::types::Type::Text,
+            format!("unknown entry kind: {}", kind).into(),
+        )
+    })
+}
+
+fn entry_from_row(row: &Row<'_>) -> rusqlite::Result<ClipboardHistoryEntry> {
+    Ok(ClipboardHistoryEntry {
+        id: row.get(0)?,
+        kind: parse_kind(&row.get::<_, String>(1)?)?,
+        preview: row.get(2)?,
+        content_hash: row.get(3)?,
+        source_app: row.get(4)?,
+        pinned: row.get(5)?,
+        use_count: row.get(6)?,
+        byte_size: row.get::<_, i64>(7)? as u64,
+        image_width: row.get(8)?,
+        image_height: row.get(9)?,
+        created_at: row.get(10)?,
+        last_used_at: row.get(11)?,
+    })
+}
+
+fn get_entry(conn: &Connection, id: i64) -> rusqlite::Result<Option<ClipboardHistoryEntry>> {
+    conn.query_row(
+        &format!("SELECT {} FROM clipboard_entries WHERE id = ?1", ENTRY_COLUMNS),
+        [id],
+        entry_from_row,
+    )
+    .optional()
+}
+
+/// FTS5 查询串，每个关键字作为短语匹配
+fn fts_query(terms: &[&str]) -> String {
+    terms
+        .iter()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[async_trait]
+impl ClipboardHistoryPort for SqliteClipboardHistoryAdapter {
+    async fn record(
+        &self,
+        content: &ClipboardContent,
+        source_app: Option<&str>,
+    ) -> Result<Option<ClipboardHistoryEntry>, InfrastructureError> {
+        let (Some(kind), Some(hash)) =
+            (ClipboardHistoryKind::of(content), clipboard_content_hash(content)?)
+        else {
+            return Ok(None);
+        };
+        let (payload, alt_text) = encode_payload(content)?;
+        let (width, height) = match content {
+            ClipboardContent::Image(image) => (Some(image.width), Some(image.height)),
+            _ => (None, None),
+        };
+        let preview = clipboard_preview(content);
+        let search_text = clipboard_search_text(content);
+        let source_app = source_app.map(str::to_string);
+        let now = now_ms();
+
+        self.db
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT INTO clipboard_entries (kind, content_hash, preview, search_text,
+                         payload, alt_text, byte_size, image_width, image_height, source_app,
+                         created_at, last_used_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?11)
+                     ON CONFLICT(content_hash) DO UPDATE SET
+                         last_used_at = excluded.last_used_at,
+                         use_count = use_count + 1,
+                         source_app = COALESCE(excluded.source_app, source_app)",
+                    params![
+                        kind.as_str(),
+                        hash,
+                        preview,
+                        search_text,
+                        payload,
+                        alt_text,
+                        payload.len() as i64,
+                        width,
+                        height,
+                        source_app,
+                        now,
+                    ],
+                )?;
+
+                conn.query_row(
+                    &format!(
+                        "SELECT {} FROM clipboard_entries WHERE content_hash = ?1",
+                        ENTRY_COLUMNS
+                    ),
+                    [&hash],
+                    entry_from_row,
+                )
+                .optional()
+            })
+            .await
+    }
+
+    async fn list(
+        &self,
+        query: &ClipboardHistoryQuery,
+    ) -> Result<Vec<ClipboardHistoryEntry>, InfrastructureError> {
+        let mut sql = format!("SELECT {} FROM clipboard_entries WHERE 1 = 1", ENTRY_COLUMNS);
+        let mut values: Vec<Value> = Vec::new();
+
+        if let Some(kind) = query.kind {
+            sql.push_str(" AND kind = ?");
+            values.push(Value::Text(kind.as_str().to_string()));
+        }
+        if query.pinned_only {
+            sql.push_str(" AND pinned = 1");
+        }
+
+        let terms: Vec<&str> =
+            query.search.as_deref().unwrap_or_default().split_whitespace().collect();
+        if terms.iter().all(|term| term.chars().count() >= MIN_FTS_TERM_CHARS) {
+            if !terms.is_empty() {
+                sql.push_str(
+                    " AND id IN (SELECT rowid FROM clipboard_fts WHERE clipboard_fts MATCH ?)",
+                );
+                values.push(Value::Text(fts_query(&terms)));
+            }
+        } else {
+            for term in terms {
+                sql.push_str(" AND search_text LIKE ? ESCAPE '\\'");
+                values.push(Value::Text(like_pattern(term)));
+            }
+        }
+
+        sql.push_str(" ORDER BY pinned DESC, last_used_at DESC, id DESC LIMIT ? OFFSET ?");
+        values.push(Value::Integer(query.limit as i64));
+        values.push(Value::Integer(query.offset as i64));
+
+        self.db
+            .call(move |conn| {
+                conn.prepare(&sql)?
+                    .query_map(params_from_iter(values), entry_from_row)?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .await
+    }
+
+    async fn get(&self, id: i64) -> Result<Option<ClipboardHistoryEntry>, InfrastructureError> {
+        self.db.call(move |conn| get_entry(conn, id)).await
+    }
+
+    async fn get_content(&self, id: i64) -> Result<Option<ClipboardContent>, InfrastructureError> {
+        let row = self
+            .db
+            .call(move |conn| {
+                conn.query_row(
+                    "SELECT kind, payload, alt_text FROM clipboard_entries WHERE id = ?1",
+                    [id],
+                    |row| {
+                        Ok((
+                            parse_kind(&row.get::<_, String>(0)?)?,
+                            row.get::<_, Vec<u8>>(1)?,
+                            row.get::<_, Option<String>>(2)?,
+                        ))
+                    },
+                )
+                .optional()
+            })
+            .await?;
+
+        row.map(|(kind, payload, alt_text)| decode_payload(kind, payload, alt_text)).transpose()
+    }
+
+    async fn touch(&self, id: i64) -> Result<(), InfrastructureError> {
+        let now = now_ms();
+        self.db
+            .call(move |conn| {
+                conn.execute(
+                    "UPDATE clipboard_entries SET last_used_at = ?1, use_count = use_count + 1
+                     WHERE id = ?2",
+                    params![now, id],
+                )
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn set_pinned(&self, id: i64, pinned: bool) -> Result<bool, InfrastructureError> {
+        let updated = self
+            .db
+            .call(move |conn| {
+                conn.execute(
+                    "UPDATE clipboard_entries SET pinned = ?1 WHERE id = ?2",
+                    params![pinned, id],
+                )
+            })
+            .await?;
+        Ok(updated > 0)
+    }
+
+    async fn delete(&self, id: i64) -> Result<bool, InfrastructureError> {
+        let deleted = self
+            .db
+            .call(move |conn| conn.execute("DELETE FROM clipboard_entries WHERE id = ?1", [id]))
+            .await?;
+        Ok(deleted > 0)
+    }
+
+    async fn clear(&self, keep_pinned: bool) -> Result<usize, InfrastructureError> {
+        let sql = if keep_pinned {
+            "DELETE FROM clipboard_entries WHERE pinned = 0"
+        } else {
+            "DELETE FROM clipboard_entries"
+        };
+        self.db.call(move |conn| conn.execute(sql, [])).await
+    }
+
+    async fn apply_retention(
+        &self,
+        retention: &ClipboardHistoryRetention,
+    ) -> Result<usize, InfrastructureError> {
+        let cutoff = retention.cutoff(now_ms());
+        let max_entries = retention.max_entries;
+
+        self.db
+            .call(move |conn| {
+                let mut deleted = 0;
+                if let Some(cutoff) = cutoff {
+                    deleted += conn.execute(
+                        "DELETE FROM clipboard_entries WHERE pinned = 0 AND last_used_at < ?1",
+                        [cutoff],
+                    )?;
+                }
+                if max_entries > 0 {
+                    deleted += conn.execute(
+                        "DELETE FROM clipboard_entries WHERE pinned = 0 AND id NOT IN (
+                             SELECT id FROM clipboard_entries WHERE pinned = 0
+                             ORDER BY last_used_at DESC, id DESC LIMIT ?1
+                         )",
+                        [max_entries as i64],
+                    )?;
+                }
+                Ok(deleted)
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adapter() -> SqliteClipboardHistoryAdapter {
+        SqliteClipboardHistoryAdapter::in_memory().unwrap()
+    }
+
+    async fn record_text(adapter: &SqliteClipboardHistoryAdapter, text: &str) -> i64 {
+        adapter.record(&ClipboardContent::text(text), None).await.unwrap().unwrap().id
+    }
+
+    #[tokio::test]
+    async fn test_record_deduplicates_by_content() {
+        let adapter = adapter();
+        let first = adapter.record(&ClipboardContent::text("hello"), Some("Editor")).await.unwrap();
+        let again = adapter.record(&ClipboardContent::text("hello"), None).await.unwrap();
+        let (first, again) = (first.unwrap(), again.unwrap());
+
+        assert_eq!(first.id, again.id);
+        assert_eq!(again.use_count, 2);
+        assert_eq!(again.source_app.as_deref(), Some("Editor"));
+        assert_eq!(adapter.list(&ClipboardHistoryQuery::default()).await.unwrap().len(), 1);
+
+        let custom = ClipboardContent::custom("application/x-test", vec![1]);
+        assert!(adapter.record(&custom, None).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_content_roundtrip() {
+        let adapter = adapter();
+
+        let image = ClipboardImage::rgba(vec![7; 2 * 2 * 4], 2, 2).unwrap();
+        let entry =
+            adapter.record(&ClipboardContent::image(image.clone()), None).await.unwrap().unwrap();
+        assert_eq!((entry.image_width, entry.image_height), (Some(2), Some(2)));
+        let stored = adapter.get_content(entry.id).await.unwrap().unwrap();
+        let stored = stored.as_image().unwrap();
+        assert!(stored.is_png());
+        // PNG 形式的同一图像不会重复记录
+        let again =
+            adapter.record(&ClipboardContent::image(stored.clone()), None).await.unwrap().unwrap();
+        assert_eq!(again.id, entry.id);
+
+        let files = ClipboardContent::files(vec![PathBuf::from("/tmp/a b.txt")]);
+        let entry = adapter.record(&files, None).await.unwrap().unwrap();
+        let stored = adapter.get_content(entry.id).await.unwrap().unwrap();
+        assert_eq!(stored.as_files().unwrap(), files.as_files().unwrap());
+
+        let html = ClipboardContent::html("<b>x</b>", Some("x".to_string()));
+        let entry = adapter.record(&html, None).await.unwrap().unwrap();
+        match adapter.get_content(entry.id).await.unwrap().unwrap() {
+            ClipboardContent::Html { html, alt_text } => {
+                assert_eq!((html.as_str(), alt_text.as_deref()), ("<b>x</b>", Some("x")))
+            }
+            other => panic!("Expected HTML, got {:?}", other),
+        }
+
+        assert!(adapter.get_content(9999).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_search() {
+        let adapter = adapter();
+        record_text(&adapter, "cargo build --release").await;
+        record_text(&adapter, "剪贴板历史记录").await;
+        record_text(&adapter, "git push origin main").await;
+
+        let search = |text: &str| ClipboardHistoryQuery::default().with_search(text);
+        let previews = |entries: Vec<ClipboardHistoryEntry>| {
+            entries.into_iter().map(|entry| entry.preview).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            previews(adapter.list(&search("RELEASE cargo")).await.unwrap()),
+            vec!["cargo build --release"]
+        );
+        assert_eq!(
+            previews(adapter.list(&search("历史记录")).await.unwrap()),
+            vec!["剪贴板历史记录"]
+        );
+        // 短关键字走 LIKE
+        assert_eq!(
+            previews(adapter.list(&search("gi")).await.unwrap()),
+            vec!["git push origin main"]
+        );
+        assert_eq!(previews(adapter.list(&search("100%")).await.unwrap()), Vec::<String>::new());
+        assert!(adapter.list(&search("\"quoted\"")).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pin_delete_and_retention() {
+        let adapter = adapter();
+        let ids: Vec<i64> = {
+            let mut ids = Vec::new();
+            for text in ["one", "two", "three", "four"] {
+                ids.push(record_text(&adapter, text).await);
+            }
+            ids
+        };
+
+        assert!(adapter.set_pinned(ids[0], true).await.unwrap());
+        assert!(!adapter.set_pinned(9999, true).await.unwrap());
+        let listed = adapter.list(&ClipboardHistoryQuery::default()).await.unwrap();
+        assert_eq!(listed[0].id, ids[0]);
+        let pinned =
+            adapter.list(&ClipboardHistoryQuery::default().with_pinned_only(true)).await.unwrap();
+        assert_eq!(pinned.len(), 1);
+
+        assert!(adapter.delete(ids[1]).await.unwrap());
+        assert!(!adapter.delete(ids[1]).await.unwrap());
+        assert!(
+            adapter
+                .list(&ClipboardHistoryQuery::default().with_search("two"))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // 只保留最近的一个未置顶条目，置顶条目不受影响
+        let retention = ClipboardHistoryRetention { max_entries: 1, max_age_days: 0 };
+        assert_eq!(adapter.apply_retention(&retention).await.unwrap(), 1);
+        let remaining: Vec<i64> = adapter
+            .list(&ClipboardHistoryQuery::default())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.id)
+            .collect();
+        assert_eq!(remaining, vec![ids[0], ids[3]]);
+
+        assert_eq!(adapter.clear(true).await.unwrap(), 1);
+        assert_eq!(adapter.clear(false).await.unwrap(), 1);
+    }
+}
@@ -0,0 +1,338 @@
+// AI 对话存储适配器
+use super::sqlite::{SqliteDatabase, like_pattern};
+use async_trait::async_trait;
+use aumate_core_domain::conversation::{DEFAULT_CONVERSATION_TITLE, conversation_title};
+use aumate_core_domain::llm::ChatRole;
+use aumate_core_shared::{InfrastructureError, now_ms};
+use aumate_core_traits::conversation::{
+    Conversation, ConversationMessage, ConversationQuery, ConversationRepositoryPort,
+    NewConversationMessage,
+};
+use rusqlite::types::Value;
+use rusqlite::{Connection, OptionalExtension, Row, params, params_from_iter};
+use std::path::{Path, PathBuf};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS conversations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    title TEXT NOT NULL,
+    model TEXT,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS conversations_updated ON conversations(updated_at);
+
+CREATE TABLE IF NOT EXISTS conversation_messages (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    conversation_id INTEGER NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    role TEXT NOT NULL,
+    content TEXT NOT NULL,
+    model TEXT,
+    created_at INTEGER NOT NULL,
+    duration_ms INTEGER
+);
+CREATE INDEX IF NOT EXISTS conversation_messages_conversation
+    ON conversation_messages(conversation_id, id);
+";
+
+const CONVERSATION_COLUMNS: &str = "c.id, c.title, c.model, \
+     (SELECT COUNT(*) FROM conversation_messages m WHERE m.conversation_id = c.id), \
+     c.created_at, c.updated_at";
+
+const MESSAGE_COLUMNS: &str = "id, conversation_id, role, content, model, created_at, duration_ms";
+
+const DATABASE_NAME: &str = "Conversations";
+
+fn parse_role(role: &str) -> rusqlite::Result<ChatRole> {
+    ChatRole::parse(role).ok_or_else(|| {
+        rusqlite::Error::FromSqlConversionFailure(
+            0,
+            rusqlite::types::Type::Text,
+            format!("unknown message role: {}", role).into(),
+        )
+    })
+}
+
+fn conversation_from_row(row: &Row<'_>) -> rusqlite::Result<Conversation> {
+    Ok(Conversation {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        model: row.get(2)?,
+        message_count: row.get(3)?,
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+    })
+}
+
+fn message_from_row(row: &Row<'_>) -> rusqlite::Result<ConversationMessage> {
+    Ok(ConversationMessage {
+        id: row.get(0)?,
+        conversation_id: row.get(1)?,
+        role: parse_role(&row.get::<_, String>(2)?)?,
+        content: row.get(3)?,
+        model: row.get(4)?,
+        created_at: row.get(5)?,
+        duration_ms: row.get::<_, Option<i64>>(6)?.map(|ms| ms as u64),
+    })
+}
+
+fn get_conversation(conn: &Connection, id: i64) -> rusqlite::Result<Option<Conversation>> {
+    conn.query_row(
+        &format!("SELECT {} FROM conversations c WHERE c.id = ?1", CONVERSATION_COLUMNS),
+        [id],
+        conversation_from_row,
+    )
+    .optional()
+}
+
+/// SQLite AI 对话存储
+pub struct SqliteConversationAdapter {
+    db: SqliteDatabase,
+}
+
+impl SqliteConversationAdapter {
+    /// 默认数据库路径 (~/.aumate/conversations.db)
+    pub fn default_path() -> PathBuf {
+        let home = dirs::home_dir().expect("Could not find home directory");
+        home.join(".aumate").join("conversations.db")
+    }
+
+    /// 打开 (或创建) 数据库文件
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, InfrastructureError> {
+        Ok(Self { db: SqliteDatabase::open(path, DATABASE_NAME, SCHEMA)? })
+    }
+
+    /// 内存数据库，用于测试
+    pub fn in_memory() -> Result<Self, InfrastructureError> {
+        Ok(Self { db: SqliteDatabase::in_memory(DATABASE_NAME, SCHEMA)? })
+    }
+}
+
+#[async_trait]
+impl ConversationRepositoryPort for SqliteConversationAdapter {
+    async fn create(&self, title: &str) -> Result<Conversation, InfrastructureError> {
+        let title = title.to_string();
+        let now = now_ms();
+        self.db
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT INTO conversations (title, created_at, updated_at) VALUES (?1, ?2, ?2)",
+                    params![title, now],
+                )?;
+                conn.query_row(
+                    &format!(
+                        "SELECT {} FROM conversations c WHERE c.id = ?1",
+                        CONVERSATION_COLUMNS
+                    ),
+                    [conn.last_insert_rowid()],
+                    conversation_from_row,
+                )
+            })
+            .await
+    }
+
+    async fn list(
+        &self,
+        query: &ConversationQuery,
+    ) -> Result<Vec<Conversation>, InfrastructureError> {
+        let mut sql = format!("SELECT {} FROM conversations c WHERE 1 = 1", CONVERSATION_COLUMNS);
+        let mut values: Vec<Value> = Vec::new();
+
+        for term in query.search.as_deref().unwrap_or_default().split_whitespace() {
+            sql.push_str(
+                " AND (c.title LIKE ? ESCAPE '\\' OR c.id IN (
+                     SELECT conversation_id FROM conversation_messages
+                     WHERE content LIKE ? ESCAPE '\\'))",
+            );
+            let pattern = like_pattern(term);
+            values.push(Value::Text(pattern.clone()));
+            values.push(Value::Text(pattern));
+        }
+
+        sql.push_str(" ORDER BY c.updated_at DESC, c.id DESC LIMIT ? OFFSET ?");
+        values.push(Value::Integer(query.limit as i64));
+        values.push(Value::Integer(query.offset as i64));
+
+        self.db
+            .call(move |conn| {
+                conn.prepare(&sql)?
+                    .query_map(params_from_iter(values), conversation_from_row)?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .await
+    }
+
+    async fn get(&self, id: i64) -> Result<Option<Conversation>, InfrastructureError> {
+        self.db.call(move |conn| get_conversation(conn, id)).await
+    }
+
+    async fn messages(&self, id: i64) -> Result<Vec<ConversationMessage>, InfrastructureError> {
+        self.db
+            .call(move |conn| {
+                conn.prepare(&format!(
+                    "SELECT {} FROM conversation_messages WHERE conversation_id = ?1 ORDER BY id",
+                    MESSAGE_COLUMNS
+                ))?
+                .query_map([id], message_from_row)?
+                .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .await
+    }
+
+    async fn append(
+        &self,
+        conversation_id: i64,
+        message: NewConversationMessage,
+    ) -> Result<Option<ConversationMessage>, InfrastructureError> {
+        let now = now_ms();
+        self.db
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+
+                let Some(conversation) = get_conversation(&tx, conversation_id)? else {
+                    return Ok(None);
+                };
+                tx.execute(
+                    "INSERT INTO conversation_messages
+                         (conversation_id, role, content, model, created_at, duration_ms)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![
+                        conversation_id,
+                        message.role.as_str(),
+                        message.content,
+                        message.model,
+                        now,
+                        message.duration_ms.map(|ms| ms as i64),
+                    ],
+                )?;
+                let id = tx.last_insert_rowid();
+
+                let title = if conversation.title == DEFAULT_CONVERSATION_TITLE
+                    && message.role == ChatRole::User
+                {
+                    conversation_title(&message.content)
+                } else {
+                    conversation.title
+                };
+                tx.execute(
+                    "UPDATE conversations SET title = ?1, model = COALESCE(?2, model), updated_at = ?3
+                     WHERE id = ?4",
+                    params![title, message.model, now, conversation_id],
+                )?;
+
+                let stored = tx.query_row(
+                    &format!("SELECT {} FROM conversation_messages WHERE id = ?1", MESSAGE_COLUMNS),
+                    [id],
+                    message_from_row,
+                )?;
+                tx.commit()?;
+                Ok(Some(stored))
+            })
+            .await
+    }
+
+    async fn rename(&self, id: i64, title: &str) -> Result<bool, InfrastructureError> {
+        let title = title.to_string();
+        let updated = self
+            .db
+            .call(move |conn| {
+                conn.execute(
+                    "UPDATE conversations SET title = ?1 WHERE id = ?2",
+                    params![title, id],
+                )
+            })
+            .await?;
+        Ok(updated > 0)
+    }
+
+    async fn delete(&self, id: i64) -> Result<bool, InfrastructureError> {
+        let deleted = self
+            .db
+            .call(move |conn| conn.execute("DELETE FROM conversations WHERE id = ?1", [id]))
+            .await?;
+        Ok(deleted > 0)
+    }
+
+    async fn clear(&self) -> Result<usize, InfrastructureError> {
+        self.db.call(|conn| conn.execute("DELETE FROM conversations", [])).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adapter() -> SqliteConversationAdapter {
+        SqliteConversationAdapter::in_memory().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_append_and_resume() {
+        let adapter = adapter();
+        let conversation = adapter.create(DEFAULT_CONVERSATION_TITLE).await.unwrap();
+        assert_eq!(conversation.message_count, 0);
+
+        adapter
+            .append(conversation.id, NewConversationMessage::user("How do I\nrebase?"))
+            .await
+            .unwrap()
+            .unwrap();
+        let reply = adapter
+            .append(
+                conversation.id,
+                NewConversationMessage::assistant("git rebase -i", "gpt-4", 1200),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(reply.role, ChatRole::Assistant);
+        assert_eq!(reply.duration_ms, Some(1200));
+
+        let stored = adapter.get(conversation.id).await.unwrap().unwrap();
+        assert_eq!(stored.title, "How do I rebase?");
+        assert_eq!(stored.model.as_deref(), Some("gpt-4"));
+        assert_eq!(stored.message_count, 2);
+        assert!(stored.updated_at >= conversation.updated_at);
+
+        let messages = adapter.messages(conversation.id).await.unwrap();
+        let contents: Vec<_> = messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["How do I\nrebase?", "git rebase -i"]);
+
+        // 手动改过的标题不会被覆盖
+        assert!(adapter.rename(conversation.id, "Git").await.unwrap());
+        adapter.append(conversation.id, NewConversationMessage::user("more")).await.unwrap();
+        assert_eq!(adapter.get(conversation.id).await.unwrap().unwrap().title, "Git");
+
+        assert!(adapter.append(9999, NewConversationMessage::user("x")).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_search_and_delete() {
+        let adapter = adapter();
+        let rust = adapter.create("Rust lifetimes").await.unwrap();
+        let cooking = adapter.create("Dinner").await.unwrap();
+        adapter.append(cooking.id, NewConversationMessage::user("100% rye bread")).await.unwrap();
+
+        let titles = |conversations: Vec<Conversation>| {
+            conversations.into_iter().map(|c| c.title).collect::<Vec<_>>()
+        };
+        let search = |text: &str| ConversationQuery::default().with_search(text);
+
+        assert_eq!(
+            titles(adapter.list(&ConversationQuery::default()).await.unwrap()),
+            vec!["Dinner", "Rust lifetimes"]
+        );
+        assert_eq!(
+            titles(adapter.list(&search("LIFETIMES")).await.unwrap()),
+            vec!["Rust lifetimes"]
+        );
+        assert_eq!(titles(adapter.list(&search("rye 100%")).await.unwrap()), vec!["Dinner"]);
+        assert!(adapter.list(&search("rye rust")).await.unwrap().is_empty());
+
+        assert!(adapter.delete(cooking.id).await.unwrap());
+        assert!(!adapter.delete(cooking.id).await.unwrap());
+        assert!(adapter.messages(cooking.id).await.unwrap().is_empty());
+        assert_eq!(adapter.clear().await.unwrap(), 1);
+        assert!(adapter.get(rust.id).await.unwrap().is_none());
+    }
+}
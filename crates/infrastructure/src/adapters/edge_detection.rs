@@ -0,0 +1,80 @@
+// 边缘检测适配器 (基于 imageproc 的 Canny 算法)
+use super::annotation::to_rgba_image;
+use aumate_core_shared::InfrastructureError;
+use aumate_core_traits::measurement::{EdgeDetectionPort, EdgeMap, Image};
+
+/// 默认低阈值，UI 中浅色分隔线的梯度也能被连接起来
+const DEFAULT_LOW_THRESHOLD: f32 = 20.0;
+/// 默认高阈值
+const DEFAULT_HIGH_THRESHOLD: f32 = 40.0;
+
+/// Canny 边缘检测适配器
+pub struct CannyEdgeDetectionAdapter {
+    low_threshold: f32,
+    high_threshold: f32,
+}
+
+impl CannyEdgeDetectionAdapter {
+    pub fn new() -> Self {
+        Self { low_threshold: DEFAULT_LOW_THRESHOLD, high_threshold: DEFAULT_HIGH_THRESHOLD }
+    }
+
+    /// 设置 Canny 的低/高阈值，阈值越低检测到的边缘越多
+    pub fn with_thresholds(mut self, low: f32, high: f32) -> Self {
+        self.low_threshold = low;
+        self.high_threshold = high;
+        self
+    }
+}
+
+impl Default for CannyEdgeDetectionAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EdgeDetectionPort for CannyEdgeDetectionAdapter {
+    fn detect_edges(&self, image: &Image) -> Result<EdgeMap, InfrastructureError> {
+        if !(0.0..=self.high_threshold).contains(&self.low_threshold) {
+            return Err(InfrastructureError::ImageProcessingFailed(format!(
+                "Invalid Canny thresholds: {} / {}",
+                self.low_threshold, self.high_threshold
+            )));
+        }
+
+        let gray = image::imageops::grayscale(&to_rgba_image(image)?);
+        let edges = imageproc::edges::canny(&gray, self.low_threshold, self.high_threshold);
+        let (width, height) = edges.dimensions();
+        EdgeMap::new(width, height, edges.into_raw().into_iter().map(|v| v > 0).collect())
+            .map_err(|e| InfrastructureError::ImageProcessingFailed(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aumate_core_domain::image::ColorFormat;
+
+    #[test]
+    fn test_detect_edges() {
+        // 白底上 x=20..40 的黑色竖条
+        let mut data = vec![255u8; 60 * 30 * 4];
+        for y in 0..30 {
+            for x in 20..40 {
+                let offset = (y * 60 + x) * 4;
+                data[offset..offset + 3].copy_from_slice(&[0, 0, 0]);
+            }
+        }
+        let image = Image::new(data, 60, 30, ColorFormat::RGBA).unwrap();
+
+        let edges = CannyEdgeDetectionAdapter::new().detect_edges(&image).unwrap();
+        assert_eq!((edges.width(), edges.height()), (60, 30));
+        assert!((18..=21).any(|x| edges.is_edge(x, 15)));
+        assert!((38..=41).any(|x| edges.is_edge(x, 15)));
+        assert!(!edges.is_edge(10, 15));
+        assert!(!edges.is_edge(30, 15));
+
+        let invalid = CannyEdgeDetectionAdapter::new().with_thresholds(50.0, 10.0);
+        assert!(invalid.detect_edges(&image).is_err());
+    }
+}
@@ -1,14 +1,33 @@
+use aumate_core_domain::hdr::ToneMapper;
+use aumate_core_domain::image::{ColorFormat as DomainColorFormat, ImageMetadata, ImageSource};
 use aumate_core_shared::InfrastructureError;
 use aumate_core_shared::Point;
 use aumate_core_traits::ImageProcessingPort;
-use aumate_core_traits::screenshot::{ColorFormat, HdrCorrectionAlgorithm, Image, ImageFormat};
+use aumate_core_traits::screenshot::{
+    AnnotationDocument, ColorFormat, HdrCorrectionAlgorithm, HdrImage, HdrInfo, Image, ImageFormat,
+    RedactionReport, RedactionRequest, ToneMappingOperator,
+};
 
+use image::codecs::avif::AvifEncoder;
 use image::codecs::jpeg::JpegEncoder;
 use image::codecs::png::{CompressionType, FilterType, PngEncoder};
 use image::codecs::webp::WebPEncoder;
 use image::{DynamicImage, ImageBuffer, Rgba};
 use rayon::prelude::*;
 
+/// 未指定质量时的 JPEG 质量
+const DEFAULT_JPEG_QUALITY: u8 = 80;
+
+/// 未指定质量时的 AVIF 质量
+const DEFAULT_AVIF_QUALITY: u8 = 70;
+
+/// AVIF 编码速度 (1-10)，截图保存需要在一秒内完成
+const AVIF_SPEED: u8 = 8;
+
+fn clamp_quality(quality: Option<u8>, default: u8) -> u8 {
+    quality.unwrap_or(default).clamp(1, 100)
+}
+
 /// 图像处理适配器
 ///
 /// 实现 `ImageProcessingPort` trait，提供图像编码、解码、叠加等功能
@@ -109,6 +128,18 @@ impl ImageProcessingPort for ImageProcessingAdapter {
     ///
     /// **复用**: `app-utils::encode_image`
     fn encode(&self, image: &Image, format: ImageFormat) -> Result<Vec<u8>, InfrastructureError> {
+        self.encode_with_quality(image, format, None)
+    }
+
+    /// 按指定质量编码图像
+    ///
+    /// 质量用于 JPEG、AVIF 和 WebP；未指定质量时 WebP 使用无损编码
+    fn encode_with_quality(
+        &self,
+        image: &Image,
+        format: ImageFormat,
+        quality: Option<u8>,
+    ) -> Result<Vec<u8>, InfrastructureError> {
         let dynamic_image = Self::domain_to_dynamic(image)?;
 
         // 复用 app-utils 的编码逻辑
@@ -117,7 +148,10 @@ impl ImageProcessingPort for ImageProcessingAdapter {
         match format {
             ImageFormat::Jpeg => {
                 dynamic_image
-                    .write_with_encoder(JpegEncoder::new_with_quality(&mut buf, 80))
+                    .write_with_encoder(JpegEncoder::new_with_quality(
+                        &mut buf,
+                        clamp_quality(quality, DEFAULT_JPEG_QUALITY),
+                    ))
                     .map_err(|e| {
                         InfrastructureError::ImageProcessingFailed(format!(
                             "JPEG encoding failed: {}",
@@ -125,16 +159,31 @@ impl ImageProcessingPort for ImageProcessingAdapter {
                         ))
                     })?;
             }
-            ImageFormat::WebP => {
-                dynamic_image.write_with_encoder(WebPEncoder::new_lossless(&mut buf)).map_err(
-                    |e| {
-                        InfrastructureError::ImageProcessingFailed(format!(
-                            "WebP encoding failed: {}",
-                            e
-                        ))
-                    },
-                )?;
-            }
+            ImageFormat::WebP => match quality {
+                // image crate 只有无损 WebP 编码器，有损编码使用 libwebp
+                Some(quality) => {
+                    let rgba = dynamic_image.to_rgba8();
+                    let encoded = webp::Encoder::from_rgba(&rgba, rgba.width(), rgba.height())
+                        .encode_simple(false, quality.clamp(1, 100) as f32)
+                        .map_err(|e| {
+                            InfrastructureError::ImageProcessingFailed(format!(
+                                "WebP encoding failed: {:?}",
+                                e
+                            ))
+                        })?;
+                    buf.extend_from_slice(&encoded);
+                }
+                None => {
+                    dynamic_image.write_with_encoder(WebPEncoder::new_lossless(&mut buf)).map_err(
+                        |e| {
+                            InfrastructureError::ImageProcessingFailed(format!(
+                                "WebP encoding failed: {}",
+                                e
+                            ))
+                        },
+                    )?;
+                }
+            },
             ImageFormat::Png => {
                 dynamic_image
                     .write_with_encoder(PngEncoder::new_with_quality(
@@ -159,6 +208,21 @@ impl ImageProcessingPort for ImageProcessingAdapter {
                         ))
                     })?;
             }
+            ImageFormat::Avif => {
+                // AVIF 编码器只接受 8 位 RGB(A)
+                DynamicImage::ImageRgba8(dynamic_image.to_rgba8())
+                    .write_with_encoder(AvifEncoder::new_with_speed_quality(
+                        &mut buf,
+                        AVIF_SPEED,
+                        clamp_quality(quality, DEFAULT_AVIF_QUALITY),
+                    ))
+                    .map_err(|e| {
+                        InfrastructureError::ImageProcessingFailed(format!(
+                            "AVIF encoding failed: {}",
+                            e
+                        ))
+                    })?;
+            }
         }
 
         Ok(buf)
@@ -230,6 +294,36 @@ impl ImageProcessingPort for ImageProcessingAdapter {
         }
     }
 
+    /// 逐像素色调映射 (并行)
+    fn tone_map(
+        &self,
+        image: &HdrImage,
+        operator: ToneMappingOperator,
+        hdr_info: &HdrInfo,
+    ) -> Result<Image, InfrastructureError> {
+        log::info!(
+            "ImageProcessingAdapter: tone_map, operator={:?}, peak={} nits, white={} nits",
+            operator,
+            hdr_info.peak_luminance,
+            hdr_info.sdr_white_level
+        );
+
+        let mapper = ToneMapper::new(operator, hdr_info);
+        let mut data = vec![0u8; image.data.len()];
+        data.par_chunks_mut(4)
+            .zip(image.data.par_chunks(4))
+            .for_each(|(dst, src)| dst.copy_from_slice(&mapper.map_pixel(src)));
+
+        Image::with_metadata(
+            data,
+            image.width,
+            image.height,
+            DomainColorFormat::RGBA,
+            ImageMetadata::new(ImageSource::Screenshot),
+        )
+        .map_err(InfrastructureError::ImageProcessingFailed)
+    }
+
     fn resize(&self, image: &Image, width: u32, height: u32) -> Result<Image, InfrastructureError> {
         let dynamic_image = Self::domain_to_dynamic(image)?;
 
@@ -238,6 +332,24 @@ impl ImageProcessingPort for ImageProcessingAdapter {
 
         Ok(Self::dynamic_to_domain(&resized))
     }
+
+    /// 绘制标注 (tiny-skia + fontdue)
+    fn render_annotations(
+        &self,
+        image: &Image,
+        document: &AnnotationDocument,
+    ) -> Result<Image, InfrastructureError> {
+        super::annotation::render_annotations(image, document)
+    }
+
+    /// 遮挡敏感区域 (复用标注的马赛克、模糊和矩形绘制)
+    fn redact(
+        &self,
+        image: &Image,
+        request: &RedactionRequest,
+    ) -> Result<(Image, RedactionReport), InfrastructureError> {
+        super::redaction::redact(image, request)
+    }
 }
 
 // HDR 校正内部方法
@@ -426,6 +538,25 @@ mod tests {
         assert_eq!(decoded.height, 10);
     }
 
+    #[test]
+    fn test_encode_webp_with_quality() {
+        let adapter = ImageProcessingAdapter::new();
+
+        // 噪声图像，无损编码无法压缩
+        let data: Vec<u8> =
+            (0..64u32 * 64 * 4).map(|i| (i.wrapping_mul(2654435761) >> 24) as u8).collect();
+        let image = Image::new(data, 64, 64, DomainColorFormat::RGBA).unwrap();
+
+        let lossless = adapter.encode(&image, ImageFormat::WebP).unwrap();
+        let lossy = adapter.encode_with_quality(&image, ImageFormat::WebP, Some(20)).unwrap();
+        assert_eq!(&lossy[0..4], b"RIFF");
+        assert_eq!(&lossy[8..12], b"WEBP");
+        assert!(lossy.len() < lossless.len());
+
+        let decoded = adapter.decode(&lossy).unwrap();
+        assert_eq!((decoded.width, decoded.height), (64, 64));
+    }
+
     #[test]
     fn test_resize() {
         let adapter = ImageProcessingAdapter::new();
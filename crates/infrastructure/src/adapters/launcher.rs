@@ -0,0 +1,727 @@
+// 应用和文件启动器
+//
+// Linux 索引 XDG `.desktop` 文件，macOS 索引 `.app`，Windows 索引开始菜单中的快捷方式，
+// 另外索引设置中的文件夹
+use async_trait::async_trait;
+use aumate_core_domain::launcher::{
+    LaunchIcon, LaunchItem, LaunchItemKind, LaunchUsage, LauncherIndexStats, desktop_exec_args,
+    parse_desktop_entry,
+};
+use aumate_core_domain::settings::LauncherSettings;
+use aumate_core_shared::InfrastructureError;
+use aumate_core_traits::launcher::{LaunchUsagePort, LauncherIndexPort, LauncherPort};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// 应用目录的最大递归深度
+const APPLICATION_MAX_DEPTH: usize = 3;
+
+/// 设置中的文件夹最多索引的条目数
+const MAX_FOLDER_ITEMS: usize = 20_000;
+
+/// 超过该大小的图标不读取
+const MAX_ICON_BYTES: u64 = 2 * 1024 * 1024;
+
+/// 图标主题中按优先顺序查找的尺寸
+const ICON_SIZES: [&str; 8] =
+    ["48x48", "64x64", "128x128", "256x256", "scalable", "32x32", "24x24", "16x16"];
+
+const ICON_EXTENSIONS: [&str; 2] = ["png", "svg"];
+
+/// 未设置 `$TERMINAL` 时按顺序查找的终端模拟器及其执行命令的参数
+const TERMINAL_EMULATORS: [(&str, &[&str]); 9] = [
+    ("x-terminal-emulator", &["-e"]),
+    ("gnome-terminal", &["--"]),
+    ("konsole", &["-e"]),
+    ("xfce4-terminal", &["-x"]),
+    ("alacritty", &["-e"]),
+    ("kitty", &[]),
+    ("foot", &[]),
+    ("wezterm", &["start", "--"]),
+    ("xterm", &["-e"]),
+];
+
+fn launcher_error(message: impl std::fmt::Display) -> InfrastructureError {
+    InfrastructureError::PlatformOperationFailed(format!("Launcher: {}", message))
+}
+
+/// XDG 数据目录，优先级从高到低
+fn xdg_data_dirs() -> Vec<PathBuf> {
+    let home = dirs::home_dir().unwrap_or_default();
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .unwrap_or_else(|| home.join(".local/share"));
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.trim().is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+
+    let mut dirs = vec![data_home];
+    dirs.extend(data_dirs.split(':').map(PathBuf::from).filter(|dir| dir.is_absolute()));
+    for flatpak in
+        [home.join(".local/share/flatpak/exports/share"), "/var/lib/flatpak/exports/share".into()]
+    {
+        if !dirs.contains(&flatpak) {
+            dirs.push(flatpak);
+        }
+    }
+    dirs
+}
+
+/// 当前平台的应用目录
+fn default_application_dirs() -> Vec<PathBuf> {
+    let home = dirs::home_dir().unwrap_or_default();
+    if cfg!(target_os = "macos") {
+        vec!["/Applications".into(), "/System/Applications".into(), home.join("Applications")]
+    } else if cfg!(windows) {
+        ["APPDATA", "PROGRAMDATA"]
+            .into_iter()
+            .filter_map(std::env::var_os)
+            .map(|dir| PathBuf::from(dir).join(r"Microsoft\Windows\Start Menu\Programs"))
+            .collect()
+    } else {
+        xdg_data_dirs().into_iter().map(|dir| dir.join("applications")).collect()
+    }
+}
+
+/// 图标主题目录
+fn default_icon_dirs() -> Vec<PathBuf> {
+    if cfg!(any(target_os = "macos", windows)) {
+        return Vec::new();
+    }
+    let mut dirs: Vec<PathBuf> = xdg_data_dirs().into_iter().map(|dir| dir.join("icons")).collect();
+    dirs.insert(1, dirs::home_dir().unwrap_or_default().join(".icons"));
+    dirs.push("/usr/share/pixmaps".into());
+    dirs
+}
+
+/// 按名称在图标主题中查找图标文件，`icon` 为绝对路径时直接使用
+fn resolve_icon(icon: &str, icon_dirs: &[PathBuf]) -> Option<PathBuf> {
+    let path = Path::new(icon);
+    if path.is_absolute() {
+        return path.is_file().then(|| path.to_path_buf());
+    }
+    for dir in icon_dirs {
+        for size in ICON_SIZES {
+            for extension in ICON_EXTENSIONS {
+                let candidate = dir
+                    .join("hicolor")
+                    .join(size)
+                    .join("apps")
+                    .join(format!("{}.{}", icon, extension));
+                if candidate.is_file() {
+                    return Some(candidate);
+                }
+            }
+        }
+        // pixmaps 等不分尺寸的目录
+        for extension in ICON_EXTENSIONS {
+            let candidate = dir.join(format!("{}.{}", icon, extension));
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+    }
+    None
+}
+
+fn icon_mime_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "png" => Some("image/png"),
+        "svg" => Some("image/svg+xml"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "ico" => Some("image/x-icon"),
+        _ => None,
+    }
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+/// 索引过的入口文件，修改时间不变时复用上次的解析结果
+struct IndexedEntry {
+    modified: Option<SystemTime>,
+    item: Option<LaunchItem>,
+}
+
+#[derive(Default)]
+struct LauncherIndex {
+    entries: HashMap<PathBuf, IndexedEntry>,
+    items: Vec<LaunchItem>,
+}
+
+/// 一次索引刷新
+struct Scanner<'a> {
+    icon_dirs: &'a [PathBuf],
+    previous: HashMap<PathBuf, IndexedEntry>,
+    index: LauncherIndex,
+    seen_ids: HashSet<String>,
+    updated: usize,
+    folder_items: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(icon_dirs: &'a [PathBuf], previous: HashMap<PathBuf, IndexedEntry>) -> Self {
+        Self {
+            icon_dirs,
+            previous,
+            index: LauncherIndex::default(),
+            seen_ids: HashSet::new(),
+            updated: 0,
+            folder_items: 0,
+        }
+    }
+
+    /// 复用或重新生成条目。ID 已存在时 (高优先级目录中的同名应用) 跳过
+    fn add(&mut self, path: &Path, build: impl FnOnce(&Self) -> Option<LaunchItem>) {
+        let modified = std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+        let item = match self.previous.remove(path) {
+            Some(entry) if entry.modified.is_some() && entry.modified == modified => entry.item,
+            _ => {
+                let item = build(self);
+                if item.is_some() {
+                    self.updated += 1;
+                }
+                item
+            }
+        };
+        match &item {
+            Some(item) if self.seen_ids.insert(item.id.clone()) => {
+                self.index.items.push(item.clone())
+            }
+            _ => {}
+        }
+        self.index.entries.insert(path.to_path_buf(), IndexedEntry { modified, item });
+    }
+
+    fn scan_applications(&mut self, root: &Path, dir: &Path, depth: usize) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+        paths.sort();
+
+        for path in paths {
+            let extension = path.extension().map(|ext| ext.to_string_lossy().to_ascii_lowercase());
+            match extension.as_deref() {
+                Some("desktop") if path.is_file() => {
+                    // Desktop file ID: 相对路径中的 `/` 替换为 `-`
+                    let relative = path.strip_prefix(root).unwrap_or(&path);
+                    let id = relative.to_string_lossy().replace(['/', '\\'], "-");
+                    self.add(&path, |scanner| scanner.desktop_item(&id, &path));
+                }
+                Some("app") if path.is_dir() => {
+                    self.add(&path, |_| Some(Self::bundle_item(&path)));
+                }
+                Some("lnk") | Some("url") if path.is_file() => {
+                    self.add(&path, |_| Some(Self::bundle_item(&path)));
+                }
+                _ if path.is_dir() && depth < APPLICATION_MAX_DEPTH => {
+                    self.scan_applications(root, &path, depth + 1);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn desktop_item(&self, desktop_id: &str, path: &Path) -> Option<LaunchItem> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let entry = parse_desktop_entry(&content)?;
+
+        let command = desktop_exec_args(&entry.exec);
+        let mut keywords = entry.keywords;
+        keywords.extend(entry.generic_name.clone());
+
+        Some(LaunchItem {
+            id: format!("app:{}", desktop_id),
+            kind: LaunchItemKind::Application,
+            name: entry.name,
+            description: entry.comment.or(entry.generic_name),
+            keywords,
+            path: path.to_string_lossy().into_owned(),
+            command,
+            terminal: entry.terminal,
+            icon: entry
+                .icon
+                .and_then(|icon| resolve_icon(&icon, self.icon_dirs))
+                .map(|icon| icon.to_string_lossy().into_owned()),
+        })
+    }
+
+    /// `.app` 或快捷方式，由系统打开
+    fn bundle_item(path: &Path) -> LaunchItem {
+        LaunchItem {
+            id: format!("app:{}", path.to_string_lossy()),
+            kind: LaunchItemKind::Application,
+            name: file_stem(path),
+            description: None,
+            keywords: Vec::new(),
+            path: path.to_string_lossy().into_owned(),
+            command: Vec::new(),
+            terminal: false,
+            icon: None,
+        }
+    }
+
+    fn scan_folder(&mut self, dir: &Path, depth: usize, max_depth: usize) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .flatten()
+            .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+            .map(|entry| entry.path())
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            if self.folder_items >= MAX_FOLDER_ITEMS {
+                return;
+            }
+            self.folder_items += 1;
+
+            let is_dir = path.is_dir();
+            let kind = if is_dir { LaunchItemKind::Folder } else { LaunchItemKind::File };
+            self.add(&path, |_| {
+                Some(LaunchItem {
+                    id: format!("file:{}", path.to_string_lossy()),
+                    kind,
+                    name: path.file_name()?.to_string_lossy().into_owned(),
+                    description: Some(dir.to_string_lossy().into_owned()),
+                    keywords: Vec::new(),
+                    path: path.to_string_lossy().into_owned(),
+                    command: Vec::new(),
+                    terminal: false,
+                    icon: None,
+                })
+            });
+            if is_dir && depth < max_depth {
+                self.scan_folder(&path, depth + 1, max_depth);
+            }
+        }
+    }
+
+    fn finish(self) -> (LauncherIndex, LauncherIndexStats) {
+        let removed = self.previous.values().filter(|entry| entry.item.is_some()).count();
+        let stats =
+            LauncherIndexStats { total: self.index.items.len(), updated: self.updated, removed };
+        (self.index, stats)
+    }
+}
+
+/// 基于文件系统的启动器索引
+///
+/// 索引保存在内存中，刷新时按修改时间只重新解析变化的文件
+pub struct FileSystemLauncherIndexAdapter {
+    application_dirs: Vec<PathBuf>,
+    icon_dirs: Vec<PathBuf>,
+    index: Mutex<LauncherIndex>,
+    /// 同一时间只进行一次刷新
+    refresh_lock: tokio::sync::Mutex<()>,
+}
+
+impl FileSystemLauncherIndexAdapter {
+    pub fn new() -> Self {
+        Self {
+            application_dirs: default_application_dirs(),
+            icon_dirs: default_icon_dirs(),
+            index: Mutex::new(LauncherIndex::default()),
+            refresh_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// 替换应用目录，靠前的目录优先
+    pub fn with_application_dirs(mut self, dirs: Vec<PathBuf>) -> Self {
+        self.application_dirs = dirs;
+        self
+    }
+
+    /// 替换图标主题目录
+    pub fn with_icon_dirs(mut self, dirs: Vec<PathBuf>) -> Self {
+        self.icon_dirs = dirs;
+        self
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LauncherIndex> {
+        self.index.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for FileSystemLauncherIndexAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl LauncherIndexPort for FileSystemLauncherIndexAdapter {
+    async fn refresh(
+        &self,
+        settings: &LauncherSettings,
+    ) -> Result<LauncherIndexStats, InfrastructureError> {
+        let _guard = self.refresh_lock.lock().await;
+
+        let previous = std::mem::take(&mut self.lock().entries);
+        let application_dirs =
+            if settings.index_applications { self.application_dirs.clone() } else { Vec::new() };
+        let icon_dirs = self.icon_dirs.clone();
+        let folders: Vec<PathBuf> = settings
+            .search_folders
+            .iter()
+            .map(|folder| PathBuf::from(folder.trim()))
+            .filter(|folder| folder.is_absolute())
+            .collect();
+        let max_depth = settings.max_depth;
+
+        let scan = tokio::task::spawn_blocking(move || {
+            let mut scanner = Scanner::new(&icon_dirs, previous);
+            for dir in &application_dirs {
+                scanner.scan_applications(dir, dir, 0);
+            }
+            for folder in &folders {
+                scanner.scan_folder(folder, 0, max_depth);
+            }
+            scanner.finish()
+        })
+        .await;
+
+        let (index, stats) = scan.map_err(launcher_error)?;
+        *self.lock() = index;
+        log::info!(
+            "Launcher index refreshed: {} items, {} updated, {} removed",
+            stats.total,
+            stats.updated,
+            stats.removed
+        );
+        Ok(stats)
+    }
+
+    async fn items(&self) -> Result<Vec<LaunchItem>, InfrastructureError> {
+        Ok(self.lock().items.clone())
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<LaunchItem>, InfrastructureError> {
+        Ok(self.lock().items.iter().find(|item| item.id == id).cloned())
+    }
+
+    async fn icon(&self, id: &str) -> Result<Option<LaunchIcon>, InfrastructureError> {
+        let Some(path) = self.get(id).await?.and_then(|item| item.icon).map(PathBuf::from) else {
+            return Ok(None);
+        };
+        let Some(mime_type) = icon_mime_type(&path) else {
+            return Ok(None);
+        };
+        let metadata = tokio::fs::metadata(&path).await?;
+        if metadata.len() > MAX_ICON_BYTES {
+            return Ok(None);
+        }
+        let data = tokio::fs::read(&path).await?;
+        Ok(Some(LaunchIcon { mime_type: mime_type.to_string(), data }))
+    }
+}
+
+/// 启动记录保存在 JSON 文件中
+pub struct JsonLaunchUsageAdapter {
+    path: PathBuf,
+    usage: tokio::sync::Mutex<Option<HashMap<String, LaunchUsage>>>,
+}
+
+impl JsonLaunchUsageAdapter {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, usage: tokio::sync::Mutex::new(None) }
+    }
+
+    /// `~/.aumate/launcher_usage.json`
+    pub fn default_path() -> PathBuf {
+        dirs::home_dir().unwrap_or_default().join(".aumate").join("launcher_usage.json")
+    }
+
+    async fn load(&self) -> HashMap<String, LaunchUsage> {
+        match tokio::fs::read_to_string(&self.path).await {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("Failed to parse launcher usage {:?}: {}", self.path, e);
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl LaunchUsagePort for JsonLaunchUsageAdapter {
+    async fn usage(&self) -> Result<HashMap<String, LaunchUsage>, InfrastructureError> {
+        let mut usage = self.usage.lock().await;
+        if usage.is_none() {
+            *usage = Some(self.load().await);
+        }
+        Ok(usage.clone().unwrap_or_default())
+    }
+
+    async fn record(&self, id: &str, now: i64) -> Result<(), InfrastructureError> {
+        let mut usage = self.usage.lock().await;
+        if usage.is_none() {
+            *usage = Some(self.load().await);
+        }
+        let usage = usage.get_or_insert_with(HashMap::new);
+        usage.entry(id.to_string()).or_default().record(now);
+
+        let json = serde_json::to_string(usage)
+            .map_err(|e| InfrastructureError::SerializationFailed(e.to_string()))?;
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&self.path, json).await?;
+        Ok(())
+    }
+}
+
+/// 启动应用或用系统默认程序打开文件
+pub struct SystemLauncherAdapter;
+
+impl SystemLauncherAdapter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 用系统默认程序打开路径
+    ///
+    /// Windows 上直接调用 `ShellExecuteW`，路径不经过 cmd.exe 解析，
+    /// 文件名中的 `&`、`|`、`^` 等字符不会被当作命令执行
+    #[cfg(windows)]
+    fn open_path(path: &str) -> Result<(), InfrastructureError> {
+        use windows::Win32::UI::Shell::ShellExecuteW;
+        use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+        use windows::core::{HSTRING, PCWSTR, w};
+
+        let file = HSTRING::from(path);
+        let result = unsafe {
+            ShellExecuteW(None, w!("open"), &file, PCWSTR::null(), PCWSTR::null(), SW_SHOWNORMAL)
+        };
+        // 返回值不大于 32 表示失败
+        if result.0 as isize <= 32 {
+            return Err(launcher_error(format!(
+                "Failed to open {}: ShellExecute error {}",
+                path, result.0 as isize
+            )));
+        }
+        Ok(())
+    }
+
+    /// 用系统默认程序打开路径
+    #[cfg(not(windows))]
+    fn open_path(path: &str) -> Result<(), InfrastructureError> {
+        let program = if cfg!(target_os = "macos") { "open" } else { "xdg-open" };
+        let mut command = Command::new(program);
+        command.arg(path);
+        spawn_detached(command, path)
+    }
+}
+
+impl Default for SystemLauncherAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl LauncherPort for SystemLauncherAdapter {
+    async fn launch(&self, item: &LaunchItem) -> Result<(), InfrastructureError> {
+        log::info!("Launching {} ({})", item.name, item.id);
+
+        let args = if item.terminal {
+            let mut args = terminal_command()?;
+            args.extend(item.command.iter().cloned());
+            args
+        } else {
+            item.command.clone()
+        };
+        let Some((program, args)) = args.split_first() else {
+            return Self::open_path(&item.path);
+        };
+        let mut command = Command::new(program);
+        command.args(args);
+        spawn_detached(command, &item.name)
+    }
+}
+
+/// 在用户主目录中启动进程，不等待其退出
+fn spawn_detached(mut command: Command, name: &str) -> Result<(), InfrastructureError> {
+    if let Some(home) = dirs::home_dir() {
+        command.current_dir(home);
+    }
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| launcher_error(format!("Failed to launch {}: {}", name, e)))?;
+
+    // 回收子进程，避免留下僵尸进程
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+/// 运行终端应用的命令前缀，优先使用 `$TERMINAL`
+fn terminal_command() -> Result<Vec<String>, InfrastructureError> {
+    let terminal = std::env::var("TERMINAL").ok();
+    terminal_command_from(terminal.as_deref(), |name| find_executable(name).is_some())
+}
+
+fn terminal_command_from(
+    terminal: Option<&str>,
+    is_available: impl Fn(&str) -> bool,
+) -> Result<Vec<String>, InfrastructureError> {
+    if let Some(terminal) = terminal.filter(|terminal| !terminal.trim().is_empty()) {
+        let mut command: Vec<String> = terminal.split_whitespace().map(String::from).collect();
+        command.push("-e".to_string());
+        return Ok(command);
+    }
+
+    TERMINAL_EMULATORS
+        .iter()
+        .find(|(name, _)| is_available(name))
+        .map(|(name, args)| {
+            std::iter::once(*name).chain(args.iter().copied()).map(String::from).collect()
+        })
+        .ok_or_else(|| {
+            launcher_error("No terminal emulator found, set $TERMINAL to run terminal applications")
+        })
+}
+
+/// 在 `PATH` 中查找可执行文件
+fn find_executable(name: &str) -> Option<PathBuf> {
+    if name.contains(std::path::MAIN_SEPARATOR) {
+        let path = PathBuf::from(name);
+        return path.is_file().then_some(path);
+    }
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("aumate-launcher-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn desktop(name: &str, icon: &str) -> String {
+        format!(
+            "[Desktop Entry]\nType=Application\nName={}\nExec={} %F\nIcon={}\n",
+            name,
+            name.to_lowercase(),
+            icon
+        )
+    }
+
+    #[test]
+    fn test_terminal_command() {
+        let command = terminal_command_from(Some("wezterm start"), |_| false).unwrap();
+        assert_eq!(command, vec!["wezterm", "start", "-e"]);
+
+        let command = terminal_command_from(None, |name| name == "konsole" || name == "xterm");
+        assert_eq!(command.unwrap(), vec!["konsole", "-e"]);
+        let command = terminal_command_from(Some(" "), |name| name == "kitty").unwrap();
+        assert_eq!(command, vec!["kitty"]);
+
+        assert!(terminal_command_from(None, |_| false).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_index_desktop_entries() {
+        let dir = temp_dir("desktop");
+        let local = dir.join("local/applications");
+        let system = dir.join("system/applications");
+        write(&local.join("editor.desktop"), &desktop("Local Editor", "editor"));
+        write(&system.join("editor.desktop"), &desktop("System Editor", "editor"));
+        write(&system.join("vendor/tool.desktop"), &desktop("Tool", "/missing/tool.png"));
+        write(
+            &system.join("hidden.desktop"),
+            "[Desktop Entry]\nType=Application\nName=H\nExec=h\nHidden=true\n",
+        );
+        write(&dir.join("icons/hicolor/48x48/apps/editor.png"), "png");
+
+        let index = FileSystemLauncherIndexAdapter::new()
+            .with_application_dirs(vec![local, system.clone()])
+            .with_icon_dirs(vec![dir.join("icons")]);
+        let settings = LauncherSettings::default();
+        let stats = index.refresh(&settings).await.unwrap();
+        assert_eq!((stats.total, stats.updated), (2, 3));
+
+        // 高优先级目录中的同名应用覆盖系统目录中的
+        let editor = index.get("app:editor.desktop").await.unwrap().unwrap();
+        assert_eq!(editor.name, "Local Editor");
+        assert_eq!(editor.command, vec!["local", "editor"]);
+        let icon = index.icon("app:editor.desktop").await.unwrap().unwrap();
+        assert_eq!((icon.mime_type.as_str(), icon.data.as_slice()), ("image/png", &b"png"[..]));
+
+        let tool = index.get("app:vendor-tool.desktop").await.unwrap().unwrap();
+        assert_eq!(tool.icon, None);
+
+        // 只重新解析变化的文件
+        let stats = index.refresh(&settings).await.unwrap();
+        assert_eq!((stats.total, stats.updated, stats.removed), (2, 0, 0));
+        std::fs::remove_file(system.join("vendor/tool.desktop")).unwrap();
+        let stats = index.refresh(&settings).await.unwrap();
+        assert_eq!((stats.total, stats.updated, stats.removed), (1, 0, 1));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_index_folders_and_usage() {
+        let dir = temp_dir("folders");
+        write(&dir.join("docs/report.md"), "");
+        write(&dir.join("docs/archive/2023/old.md"), "");
+        write(&dir.join("docs/.hidden"), "");
+
+        let index = FileSystemLauncherIndexAdapter::new().with_application_dirs(Vec::new());
+        let settings = LauncherSettings {
+            index_applications: false,
+            search_folders: vec![dir.join("docs").to_string_lossy().into_owned()],
+            max_depth: 1,
+        };
+        index.refresh(&settings).await.unwrap();
+        let mut names: Vec<_> =
+            index.items().await.unwrap().into_iter().map(|item| (item.name, item.kind)).collect();
+        names.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            names,
+            vec![
+                ("2023".to_string(), LaunchItemKind::Folder),
+                ("archive".to_string(), LaunchItemKind::Folder),
+                ("report.md".to_string(), LaunchItemKind::File),
+            ]
+        );
+
+        let usage = JsonLaunchUsageAdapter::new(dir.join("usage.json"));
+        usage.record("file:a", 1).await.unwrap();
+        usage.record("file:a", 2).await.unwrap();
+        let reopened = JsonLaunchUsageAdapter::new(dir.join("usage.json"));
+        assert_eq!(
+            reopened.usage().await.unwrap()["file:a"],
+            LaunchUsage { count: 2, last_used: 2 }
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
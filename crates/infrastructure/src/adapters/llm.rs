@@ -0,0 +1,497 @@
+// OpenAI 兼容的大语言模型适配器
+use async_trait::async_trait;
+use aumate_core_shared::InfrastructureError;
+use aumate_core_traits::llm::{ChatCompletionRequest, ChatCompletionStream, LlmPort};
+use reqwest::StatusCode;
+use reqwest::header::{CONTENT_TYPE, RETRY_AFTER};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// 流结束标记
+const DONE_MARKER: &str = "[DONE]";
+
+/// 服务端要求的重试等待时间上限
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(10);
+
+/// OpenAI 兼容接口 (`/chat/completions`) 的流式客户端
+///
+/// 收到响应头之前的连接失败、超时、429 和 5xx 会按指数退避重试；
+/// 开始输出后不再重试，以免重复输出
+pub struct OpenAiCompatibleLlmAdapter {
+    client: reqwest::Client,
+    /// 等待响应头的超时
+    response_timeout: Duration,
+    /// 两段数据之间的最长间隔
+    idle_timeout: Duration,
+    max_retries: u32,
+    retry_delay: Duration,
+}
+
+impl OpenAiCompatibleLlmAdapter {
+    pub fn new() -> Self {
+        Self {
+            client: build_client(Duration::from_secs(10)),
+            response_timeout: Duration::from_secs(60),
+            idle_timeout: Duration::from_secs(60),
+            max_retries: 2,
+            retry_delay: Duration::from_millis(500),
+        }
+    }
+
+    /// 建立连接的超时
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.client = build_client(timeout);
+        self
+    }
+
+    pub fn with_response_timeout(mut self, timeout: Duration) -> Self {
+        self.response_timeout = timeout;
+        self
+    }
+
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    /// 首次请求失败后的最大重试次数
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// 首次重试前的等待时间，之后每次翻倍
+    pub fn with_retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// 发送一次请求
+    ///
+    /// 失败时除错误外还返回是否值得重试，以及服务端要求的最短等待时间
+    async fn send(
+        &self,
+        request: &ChatCompletionRequest,
+        body: &[u8],
+    ) -> Result<reqwest::Response, (InfrastructureError, Option<Duration>)> {
+        let send = self
+            .client
+            .post(request.endpoint.chat_completions_url())
+            .bearer_auth(&request.endpoint.api_key)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.to_vec())
+            .send();
+
+        let response = match tokio::time::timeout(self.response_timeout, send).await {
+            Err(_) => {
+                let error = InfrastructureError::Timeout("No response from the API".to_string());
+                return Err((error, Some(Duration::ZERO)));
+            }
+            Ok(Err(e)) => {
+                let retry = (e.is_connect() || e.is_timeout()).then_some(Duration::ZERO);
+                return Err((InfrastructureError::NetworkFailed(e.to_string()), retry));
+            }
+            Ok(Ok(response)) => response,
+        };
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let retry = is_retryable(status).then(|| retry_after(&response).unwrap_or_default());
+        let text = response.text().await.unwrap_or_default();
+        Err((InfrastructureError::NetworkFailed(api_error_message(status, &text)), retry))
+    }
+}
+
+impl Default for OpenAiCompatibleLlmAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl LlmPort for OpenAiCompatibleLlmAdapter {
+    async fn stream_chat(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<Box<dyn ChatCompletionStream>, InfrastructureError> {
+        let body = serde_json::to_vec(&serde_json::json!({
+            "model": request.endpoint.model,
+            "stream": true,
+            "messages": request.messages,
+        }))
+        .map_err(|e| InfrastructureError::SerializationFailed(e.to_string()))?;
+
+        let mut attempt = 0;
+        loop {
+            match self.send(&request, &body).await {
+                Ok(response) => {
+                    return Ok(Box::new(SseChatStream::new(response, self.idle_timeout)));
+                }
+                Err((error, Some(wait))) if attempt < self.max_retries => {
+                    let delay = wait.min(MAX_RETRY_AFTER).max(self.retry_delay * 2u32.pow(attempt));
+                    attempt += 1;
+                    log::warn!(
+                        "LLM request failed ({}), retry {}/{} in {:?}",
+                        error,
+                        attempt,
+                        self.max_retries,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err((error, _)) => return Err(error),
+            }
+        }
+    }
+}
+
+fn build_client(connect_timeout: Duration) -> reqwest::Client {
+    reqwest::Client::builder().connect_timeout(connect_timeout).build().unwrap_or_else(|e| {
+        log::error!("Failed to build HTTP client: {}", e);
+        reqwest::Client::new()
+    })
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status.is_server_error()
+}
+
+/// `Retry-After` 头 (秒数形式)
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    let seconds = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim().parse().ok()?;
+    Some(Duration::from_secs(seconds))
+}
+
+/// 优先使用响应中的 `error.message`
+fn api_error_message(status: StatusCode, body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|json| json["error"]["message"].as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("API error: {}", status.as_u16()))
+}
+
+/// Server-Sent Events 解析器
+///
+/// 数据可能在任意位置被分块，包括 UTF-8 字符中间
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// 追加数据，返回已完整接收的事件数据
+    fn feed(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            self.line(&line[..end], &mut events);
+        }
+        events
+    }
+
+    /// 连接关闭时处理剩余数据
+    fn finish(&mut self) -> Vec<String> {
+        let mut events = Vec::new();
+        let line = std::mem::take(&mut self.buffer);
+        self.line(&line, &mut events);
+        self.line(b"", &mut events);
+        events
+    }
+
+    fn line(&mut self, line: &[u8], events: &mut Vec<String>) {
+        let line = String::from_utf8_lossy(line);
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+
+        if line.is_empty() {
+            if !self.data.is_empty() {
+                events.push(self.data.join("\n"));
+                self.data.clear();
+            }
+        } else if let Some(value) = line.strip_prefix("data:") {
+            self.data.push(value.strip_prefix(' ').unwrap_or(value).to_string());
+        }
+        // 注释 (`:`开头) 和 event/id/retry 字段不需要处理
+    }
+}
+
+/// 解析一条事件数据中的增量文本
+fn parse_delta(data: &str) -> Result<Option<String>, InfrastructureError> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(data) else {
+        log::debug!("Skipping invalid SSE data: {}", data);
+        return Ok(None);
+    };
+    if let Some(message) = json["error"]["message"].as_str() {
+        return Err(InfrastructureError::NetworkFailed(message.to_string()));
+    }
+    Ok(json["choices"][0]["delta"]["content"]
+        .as_str()
+        .filter(|content| !content.is_empty())
+        .map(str::to_string))
+}
+
+/// 基于 SSE 响应的增量文本流
+struct SseChatStream {
+    response: reqwest::Response,
+    parser: SseParser,
+    pending: VecDeque<Result<String, InfrastructureError>>,
+    finished: bool,
+    idle_timeout: Duration,
+}
+
+impl SseChatStream {
+    fn new(response: reqwest::Response, idle_timeout: Duration) -> Self {
+        Self {
+            response,
+            parser: SseParser::default(),
+            pending: VecDeque::new(),
+            finished: false,
+            idle_timeout,
+        }
+    }
+
+    fn push_events(&mut self, events: Vec<String>) {
+        for data in events {
+            if data.trim() == DONE_MARKER {
+                self.finished = true;
+                return;
+            }
+            match parse_delta(&data) {
+                Ok(Some(content)) => self.pending.push_back(Ok(content)),
+                Ok(None) => {}
+                Err(e) => {
+                    self.pending.push_back(Err(e));
+                    self.finished = true;
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ChatCompletionStream for SseChatStream {
+    async fn next(&mut self) -> Option<Result<String, InfrastructureError>> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some(item);
+            }
+            if self.finished {
+                return None;
+            }
+
+            match tokio::time::timeout(self.idle_timeout, self.response.chunk()).await {
+                Err(_) => {
+                    self.finished = true;
+                    let error = InfrastructureError::Timeout("The API stopped responding".into());
+                    return Some(Err(error));
+                }
+                Ok(Err(e)) => {
+                    self.finished = true;
+                    return Some(Err(InfrastructureError::NetworkFailed(e.to_string())));
+                }
+                Ok(Ok(Some(chunk))) => {
+                    let events = self.parser.feed(&chunk);
+                    self.push_events(events);
+                }
+                Ok(Ok(None)) => {
+                    let events = self.parser.finish();
+                    self.push_events(events);
+                    self.finished = true;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aumate_core_traits::llm::{ChatMessage, LlmEndpoint};
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// 响应片段及发送前的等待时间
+    type MockResponse = Vec<(Duration, Vec<u8>)>;
+
+    fn sse_headers() -> (Duration, Vec<u8>) {
+        let headers =
+            "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n";
+        (Duration::ZERO, headers.as_bytes().to_vec())
+    }
+
+    fn json_response(status: &str, body: &str) -> MockResponse {
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        vec![(Duration::ZERO, response.into_bytes())]
+    }
+
+    /// 按顺序为每个连接返回一个响应，返回接口地址和收到的请求
+    async fn mock_server(responses: Vec<MockResponse>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let received = requests.clone();
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let request = read_request(&mut socket).await;
+                received.lock().unwrap().push(request);
+                for (delay, part) in response {
+                    tokio::time::sleep(delay).await;
+                    if socket.write_all(&part).await.is_err() {
+                        break;
+                    }
+                }
+                let _ = socket.shutdown().await;
+            }
+        });
+
+        (url, requests)
+    }
+
+    async fn read_request(socket: &mut tokio::net::TcpStream) -> String {
+        let mut data = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            data.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&data).to_string();
+            let Some(header_end) = text.find("\r\n\r\n") else {
+                if n == 0 {
+                    return text;
+                }
+                continue;
+            };
+            let content_length = text[..header_end]
+                .lines()
+                .find_map(|line| {
+                    line.to_ascii_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|v| v.trim().to_string())
+                })
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(0);
+            if n == 0 || data.len() >= header_end + 4 + content_length {
+                return text;
+            }
+        }
+    }
+
+    fn request(api_url: &str) -> ChatCompletionRequest {
+        ChatCompletionRequest {
+            endpoint: LlmEndpoint {
+                api_url: api_url.to_string(),
+                api_key: "sk-test".to_string(),
+                model: "test-model".to_string(),
+            },
+            messages: vec![ChatMessage::system("Be brief."), ChatMessage::user("Hi")],
+        }
+    }
+
+    async fn collect(
+        stream: &mut Box<dyn ChatCompletionStream>,
+    ) -> (Vec<String>, Option<InfrastructureError>) {
+        let mut tokens = Vec::new();
+        while let Some(item) = stream.next().await {
+            match item {
+                Ok(token) => tokens.push(token),
+                Err(e) => return (tokens, Some(e)),
+            }
+        }
+        (tokens, None)
+    }
+
+    #[tokio::test]
+    async fn test_streams_tokens() {
+        let hello = "data: {\"choices\":[{\"delta\":{\"content\":\"你好\"}}]}\n\n".as_bytes();
+        // 在 “你” 的 UTF-8 编码中间分块
+        let split = hello.iter().position(|&b| b >= 0x80).unwrap() + 1;
+        let response = vec![
+            sse_headers(),
+            (Duration::ZERO, b": keep-alive\n\n".to_vec()),
+            (Duration::ZERO, hello[..split].to_vec()),
+            (Duration::from_millis(20), hello[split..].to_vec()),
+            (
+                Duration::ZERO,
+                b"data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\r\n\r\ndata: {\"choices\":[{\"delta\":{\"content\":\" world\"}}]}\n\ndata: [DONE]\n\n".to_vec(),
+            ),
+        ];
+        let (url, requests) = mock_server(vec![response]).await;
+
+        let adapter = OpenAiCompatibleLlmAdapter::new();
+        let mut stream = adapter.stream_chat(request(&url)).await.unwrap();
+        let (tokens, error) = collect(&mut stream).await;
+        assert!(error.is_none());
+        assert_eq!(tokens, vec!["你好", " world"]);
+
+        let requests = requests.lock().unwrap();
+        let sent = requests[0].to_ascii_lowercase();
+        assert!(sent.starts_with("post /v1/chat/completions "));
+        assert!(sent.contains("authorization: bearer sk-test"));
+        let body: serde_json::Value =
+            serde_json::from_str(&requests[0][requests[0].find("\r\n\r\n").unwrap() + 4..])
+                .unwrap();
+        assert_eq!(body["model"], "test-model");
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["messages"][1], serde_json::json!({"role": "user", "content": "Hi"}));
+    }
+
+    #[tokio::test]
+    async fn test_retries_only_transient_errors() {
+        let done = vec![
+            sse_headers(),
+            (
+                Duration::ZERO,
+                b"data: {\"choices\":[{\"delta\":{\"content\":\"ok\"}}]}\n\n".to_vec(),
+            ),
+        ];
+        let (url, requests) =
+            mock_server(vec![json_response("503 Service Unavailable", "busy"), done]).await;
+        let adapter = OpenAiCompatibleLlmAdapter::new().with_retry_delay(Duration::from_millis(10));
+        let mut stream = adapter.stream_chat(request(&url)).await.unwrap();
+        assert_eq!(collect(&mut stream).await.0, vec!["ok"]);
+        assert_eq!(requests.lock().unwrap().len(), 2);
+
+        // 认证失败不重试，并返回接口给出的错误信息
+        let unauthorized =
+            json_response("401 Unauthorized", r#"{"error":{"message":"Invalid API key"}}"#);
+        let (url, requests) = mock_server(vec![unauthorized.clone(), unauthorized]).await;
+        let Err(InfrastructureError::NetworkFailed(message)) =
+            adapter.stream_chat(request(&url)).await
+        else {
+            panic!("expected an API error");
+        };
+        assert_eq!(message, "Invalid API key");
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_idle_timeout() {
+        let response = vec![
+            sse_headers(),
+            (Duration::ZERO, b"data: {\"choices\":[{\"delta\":{\"content\":\"a\"}}]}\n\n".to_vec()),
+            (Duration::from_secs(5), b"data: [DONE]\n\n".to_vec()),
+        ];
+        let (url, _) = mock_server(vec![response]).await;
+
+        let adapter =
+            OpenAiCompatibleLlmAdapter::new().with_idle_timeout(Duration::from_millis(100));
+        let mut stream = adapter.stream_chat(request(&url)).await.unwrap();
+        let (tokens, error) = collect(&mut stream).await;
+        assert_eq!(tokens, vec!["a"]);
+        assert!(matches!(error, Some(InfrastructureError::Timeout(_))));
+    }
+}
@@ -0,0 +1,157 @@
+// In-memory Clipboard Adapter
+use async_trait::async_trait;
+use aumate_core_shared::InfrastructureError;
+use aumate_core_traits::clipboard::{
+    ClipboardChange, ClipboardChangeStream, ClipboardContent, ClipboardPort, ClipboardType,
+};
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+
+#[derive(Default)]
+struct MemoryClipboardState {
+    content: Option<ClipboardContent>,
+    /// 内容之外额外声明的格式，例如密码管理器的敏感标记
+    extra_types: Vec<ClipboardType>,
+}
+
+/// 内存剪贴板适配器
+///
+/// 不访问系统剪贴板，用于测试和无图形环境
+pub struct InMemoryClipboardAdapter {
+    state: Mutex<MemoryClipboardState>,
+    /// 每次修改后广播新的内容类型
+    changes: broadcast::Sender<Vec<ClipboardType>>,
+}
+
+impl Default for InMemoryClipboardAdapter {
+    fn default() -> Self {
+        Self {
+            state: Mutex::new(MemoryClipboardState::default()),
+            changes: broadcast::channel(64).0,
+        }
+    }
+}
+
+impl InMemoryClipboardAdapter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_content(content: ClipboardContent) -> Self {
+        let adapter = Self::new();
+        adapter.set(content, Vec::new());
+        adapter
+    }
+
+    /// 模拟其他应用复制内容，同时声明额外的格式
+    pub fn set(&self, content: ClipboardContent, extra_types: Vec<ClipboardType>) {
+        let types = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            state.content = Some(content);
+            state.extra_types = extra_types;
+            state.types()
+        };
+        // 没有监听者时发送失败，忽略即可
+        let _ = self.changes.send(types);
+    }
+
+    /// 当前内容
+    pub fn content(&self) -> Option<ClipboardContent> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).content.clone()
+    }
+}
+
+impl MemoryClipboardState {
+    fn types(&self) -> Vec<ClipboardType> {
+        let Some(content) = &self.content else {
+            return Vec::new();
+        };
+
+        let mut types = vec![content_type(content)];
+        if content.plain_text().is_some() && !types.contains(&ClipboardType::Text) {
+            types.push(ClipboardType::Text);
+        }
+        types.extend(self.extra_types.iter().cloned());
+        types
+    }
+}
+
+/// 内存剪贴板的变化事件流
+struct MemoryChangeStream {
+    receiver: broadcast::Receiver<Vec<ClipboardType>>,
+    sequence: u64,
+}
+
+#[async_trait]
+impl ClipboardChangeStream for MemoryChangeStream {
+    async fn next(&mut self) -> Option<ClipboardChange> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(types) => {
+                    self.sequence += 1;
+                    return Some(ClipboardChange { sequence: self.sequence, types });
+                }
+                // 来不及处理的变化只计数
+                Err(broadcast::error::RecvError::Lagged(skipped)) => self.sequence += skipped,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+fn content_type(content: &ClipboardContent) -> ClipboardType {
+    match content {
+        ClipboardContent::Text(_) => ClipboardType::Text,
+        ClipboardContent::Image(_) => ClipboardType::Image,
+        ClipboardContent::Files(_) => ClipboardType::Files,
+        ClipboardContent::Html { .. } => ClipboardType::Html,
+        ClipboardContent::Rtf { .. } => ClipboardType::Rtf,
+        ClipboardContent::Custom { mime, .. } => ClipboardType::Custom(mime.clone()),
+    }
+}
+
+fn empty() -> InfrastructureError {
+    InfrastructureError::ClipboardFailed("Clipboard is empty".to_string())
+}
+
+#[async_trait]
+impl ClipboardPort for InMemoryClipboardAdapter {
+    async fn read(&self) -> Result<ClipboardContent, InfrastructureError> {
+        self.content().ok_or_else(empty)
+    }
+
+    async fn read_format(
+        &self,
+        format: ClipboardType,
+    ) -> Result<ClipboardContent, InfrastructureError> {
+        let content = self.content().ok_or_else(empty)?;
+        if content_type(&content) == format {
+            return Ok(content);
+        }
+        match (format, content.plain_text()) {
+            (ClipboardType::Text, Some(text)) => Ok(ClipboardContent::text(text)),
+            (format, _) => {
+                Err(InfrastructureError::ClipboardFailed(format!("Clipboard has no {}", format)))
+            }
+        }
+    }
+
+    async fn write(&self, content: ClipboardContent) -> Result<(), InfrastructureError> {
+        self.set(content, Vec::new());
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<(), InfrastructureError> {
+        *self.state.lock().unwrap_or_else(|e| e.into_inner()) = MemoryClipboardState::default();
+        let _ = self.changes.send(Vec::new());
+        Ok(())
+    }
+
+    async fn get_available_types(&self) -> Result<Vec<ClipboardType>, InfrastructureError> {
+        Ok(self.state.lock().unwrap_or_else(|e| e.into_inner()).types())
+    }
+
+    async fn watch(&self) -> Result<Box<dyn ClipboardChangeStream>, InfrastructureError> {
+        Ok(Box::new(MemoryChangeStream { receiver: self.changes.subscribe(), sequence: 0 }))
+    }
+}
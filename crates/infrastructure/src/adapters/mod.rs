@@ -1,15 +1,29 @@
 // 适配器模块
 
+pub mod annotation;
+pub mod barcode;
 pub mod clipboard;
+pub mod clipboard_history;
+pub mod conversation;
+pub mod edge_detection;
 pub mod element_scanner;
 pub mod global_shortcut;
 pub mod hotkey;
 pub mod image;
+pub mod launcher;
+pub mod llm;
+pub mod memory_clipboard;
 pub mod page;
+pub mod redaction;
 pub mod screen_capture;
+pub mod screenshot_library;
 pub mod scroll;
+pub mod secret_store;
 pub mod settings;
+pub(crate) mod sqlite;
 pub mod storage;
+#[cfg(feature = "ocr")]
+pub mod text_recognition;
 pub mod ui_automation;
 pub mod window;
 pub mod window_layout;
@@ -17,16 +31,27 @@ pub mod window_list;
 pub mod window_vibrancy;
 
 // Re-export
+pub use barcode::BarcodeAdapter;
 pub use clipboard::ClipboardAdapter;
+pub use clipboard_history::SqliteClipboardHistoryAdapter;
+pub use conversation::SqliteConversationAdapter;
+pub use edge_detection::CannyEdgeDetectionAdapter;
 pub use element_scanner::ElementScannerAdapter;
 pub use global_shortcut::GlobalShortcutAdapter;
 pub use hotkey::{HotkeyListenerAdapter, InputSimulationAdapter};
 pub use image::ImageProcessingAdapter;
+pub use launcher::{FileSystemLauncherIndexAdapter, JsonLaunchUsageAdapter, SystemLauncherAdapter};
+pub use llm::OpenAiCompatibleLlmAdapter;
+pub use memory_clipboard::InMemoryClipboardAdapter;
 pub use page::PageManagementAdapter;
 pub use screen_capture::ScreenCaptureAdapter;
+pub use screenshot_library::SqliteScreenshotLibraryAdapter;
 pub use scroll::ScrollCaptureAdapter;
+pub use secret_store::{EncryptedFileSecretStore, KeyFile, MasterKeyProvider, SystemKeyring};
 pub use settings::FileSystemSettingsAdapter;
 pub use storage::{FileSystemAdapter, MemoryCacheAdapter};
+#[cfg(feature = "ocr")]
+pub use text_recognition::TrOcrTextRecognitionAdapter;
 pub use ui_automation::UIAutomationAdapter;
 pub use window::WindowManagementAdapter;
 pub use window_layout::WindowLayoutAdapter;
@@ -0,0 +1,143 @@
+// 截图隐私遮挡 (基于 aumate::annotate::Redactor)
+use super::annotation::{from_rgba_image, to_render_color, to_rgba_image};
+use aumate::annotate::{self, Redactor};
+use aumate_core_domain::image::Image;
+use aumate_core_domain::redaction::{
+    RedactedItem, RedactionMethod, RedactionReason, RedactionReport, RedactionRequest,
+    SensitiveKind,
+};
+use aumate_core_shared::{InfrastructureError, Rectangle};
+
+/// 遮挡手动区域和 OCR 文本中的敏感内容
+pub fn redact(
+    image: &Image,
+    request: &RedactionRequest,
+) -> Result<(Image, RedactionReport), InfrastructureError> {
+    request
+        .method
+        .validate()
+        .map_err(|e| InfrastructureError::ImageProcessingFailed(e.to_string()))?;
+
+    let detectors: Vec<annotate::SensitiveKind> =
+        request.detectors.iter().copied().map(to_render_kind).collect();
+    let redactor = Redactor::new()
+        .with_method(to_render_method(request.method))
+        .with_detectors(&detectors)
+        .with_redact_all_text(request.redact_all_text);
+
+    let regions: Vec<annotate::Region> = request.regions.iter().map(to_render_region).collect();
+    let lines: Vec<annotate::TextLine> = request
+        .text
+        .iter()
+        .map(|line| annotate::TextLine {
+            text: line.text.clone(),
+            bounds: to_render_region(&line.bounds),
+        })
+        .collect();
+
+    let mut buffer = to_rgba_image(image)?;
+    let image_bounds = Rectangle::from_bounds(0, 0, buffer.width() as i32, buffer.height() as i32);
+    let report = redactor
+        .redact(&mut buffer, &regions, &lines)
+        .map_err(|e| InfrastructureError::ImageProcessingFailed(e.to_string()))?;
+
+    let report = RedactionReport {
+        items: report
+            .items
+            .into_iter()
+            .map(|item| RedactedItem {
+                reason: from_render_reason(item.reason),
+                bounds: from_render_region(item.bounds).clip_rect(&image_bounds),
+                text: item.text,
+            })
+            .collect(),
+    };
+    Ok((from_rgba_image(buffer)?, report))
+}
+
+fn to_render_method(method: RedactionMethod) -> annotate::RedactionMethod {
+    match method {
+        RedactionMethod::Pixelate { block_size } => {
+            annotate::RedactionMethod::Pixelate { block_size }
+        }
+        RedactionMethod::Blur { radius } => annotate::RedactionMethod::Blur { radius },
+        RedactionMethod::Fill { color } => {
+            annotate::RedactionMethod::Fill { color: to_render_color(color) }
+        }
+    }
+}
+
+fn to_render_kind(kind: SensitiveKind) -> annotate::SensitiveKind {
+    match kind {
+        SensitiveKind::Email => annotate::SensitiveKind::Email,
+        SensitiveKind::Token => annotate::SensitiveKind::Token,
+        SensitiveKind::IpAddress => annotate::SensitiveKind::IpAddress,
+        SensitiveKind::CreditCard => annotate::SensitiveKind::CreditCard,
+    }
+}
+
+fn from_render_reason(reason: annotate::RedactionReason) -> RedactionReason {
+    match reason {
+        annotate::RedactionReason::Manual => RedactionReason::Manual,
+        annotate::RedactionReason::Text => RedactionReason::Text,
+        annotate::RedactionReason::Email => RedactionReason::Email,
+        annotate::RedactionReason::Token => RedactionReason::Token,
+        annotate::RedactionReason::IpAddress => RedactionReason::IpAddress,
+        annotate::RedactionReason::CreditCard => RedactionReason::CreditCard,
+    }
+}
+
+fn to_render_region(rect: &Rectangle) -> annotate::Region {
+    annotate::Region::new(
+        rect.min_x() as f32,
+        rect.min_y() as f32,
+        rect.width() as f32,
+        rect.height() as f32,
+    )
+}
+
+/// 向外取整，保证报告里的矩形覆盖实际遮挡的像素
+fn from_render_region(region: annotate::Region) -> Rectangle {
+    Rectangle::from_bounds(
+        region.x.floor() as i32,
+        region.y.floor() as i32,
+        (region.x + region.width).ceil() as i32,
+        (region.y + region.height).ceil() as i32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aumate_core_domain::annotation::AnnotationColor;
+    use aumate_core_domain::image::ColorFormat;
+    use aumate_core_domain::text_recognition::RecognizedText;
+
+    #[test]
+    fn test_redact() {
+        let image = Image::new(vec![255; 200 * 20 * 4], 200, 20, ColorFormat::RGBA).unwrap();
+        let line = RecognizedText {
+            // 20 个字符，每个 10 像素
+            text: "mail: bob@example.io".to_string(),
+            bounds: Rectangle::from_xywh(0, 0, 200, 20).unwrap(),
+            confidence: 0.9,
+        };
+        let request =
+            RedactionRequest::new(RedactionMethod::Fill { color: AnnotationColor::rgb(0, 0, 0) })
+                .with_text(vec![line]);
+
+        let (redacted, report) = redact(&image, &request).unwrap();
+        assert_eq!(report.count(RedactionReason::Email), 1);
+        let item = &report.items[0];
+        assert_eq!(item.text.as_deref(), Some("bo**********io"));
+        // 邮箱从第 6 个字符开始，加 2 像素边距，并裁剪到图像内
+        assert!(item.bounds.equals(58, 0, 200, 20));
+
+        let pixel = |x: usize, y: usize| &redacted.data[(y * 200 + x) * 4..(y * 200 + x) * 4 + 4];
+        assert_eq!(pixel(100, 10), [0, 0, 0, 255]);
+        assert_eq!(pixel(20, 10), [255, 255, 255, 255]);
+
+        let invalid = RedactionRequest::new(RedactionMethod::Pixelate { block_size: 0 });
+        assert!(redact(&image, &invalid).is_err());
+    }
+}
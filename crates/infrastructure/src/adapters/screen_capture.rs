@@ -1,23 +1,26 @@
-// 屏幕捕获适配器 (macOS)
+// 屏幕捕获适配器 (macOS、Windows)
 use async_trait::async_trait;
 use aumate_core_shared::{InfrastructureError, MonitorId, Rectangle, WindowId};
 use aumate_core_traits::ScreenCapturePort;
 use aumate_core_traits::screenshot::{
-    CaptureMetadata, CaptureOptions, CaptureTarget, ColorFormat, Image, Monitor, Screenshot,
+    CaptureMetadata, CaptureOptions, CaptureTarget, ColorFormat, HdrInfo, HdrScreenshot, Image,
+    Monitor, Screenshot,
 };
 use std::sync::Arc;
 
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "macos", windows))]
 use xcap::Monitor as XCapMonitor;
 
-/// macOS 屏幕捕获适配器
+/// 基于 xcap 的屏幕捕获适配器
+///
+/// Windows 上还能通过 DXGI 桌面复制以浮点格式捕获 HDR 显示器
 pub struct ScreenCaptureAdapter {
     // 可以存储一些配置或缓存
 }
 
 impl ScreenCaptureAdapter {
     pub fn new() -> Self {
-        log::info!("Creating ScreenCaptureAdapter (macOS)");
+        log::info!("Creating ScreenCaptureAdapter");
         Self {}
     }
 }
@@ -37,26 +40,45 @@ impl ScreenCapturePort for ScreenCaptureAdapter {
     ) -> Result<Screenshot, InfrastructureError> {
         log::info!("ScreenCaptureAdapter: capturing with target={:?}", target);
 
-        #[cfg(target_os = "macos")]
+        #[cfg(any(target_os = "macos", windows))]
         {
-            self.capture_macos(target).await
+            self.capture_xcap(target).await
         }
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(not(any(target_os = "macos", windows)))]
         {
             Err(InfrastructureError::PlatformNotSupported)
         }
     }
 
+    async fn capture_hdr(
+        &self,
+        target: CaptureTarget,
+        _options: CaptureOptions,
+    ) -> Result<HdrScreenshot, InfrastructureError> {
+        log::info!("ScreenCaptureAdapter: capturing HDR with target={:?}", target);
+
+        #[cfg(windows)]
+        {
+            self.capture_hdr_windows(target).await
+        }
+
+        #[cfg(not(windows))]
+        {
+            let _ = target;
+            Err(InfrastructureError::PlatformNotSupported)
+        }
+    }
+
     async fn get_monitors(&self) -> Result<Vec<Monitor>, InfrastructureError> {
         log::info!("ScreenCaptureAdapter: getting monitors");
 
-        #[cfg(target_os = "macos")]
+        #[cfg(any(target_os = "macos", windows))]
         {
-            self.get_monitors_macos().await
+            self.get_monitors_xcap().await
         }
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(not(any(target_os = "macos", windows)))]
         {
             Err(InfrastructureError::PlatformNotSupported)
         }
@@ -81,16 +103,93 @@ impl ScreenCapturePort for ScreenCaptureAdapter {
     }
 }
 
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "macos", windows))]
 impl ScreenCaptureAdapter {
-    async fn capture_macos(
-        &self,
-        target: CaptureTarget,
-    ) -> Result<Screenshot, InfrastructureError> {
+    async fn capture_xcap(&self, target: CaptureTarget) -> Result<Screenshot, InfrastructureError> {
         // 保存 target 的克隆用于后续使用
         let target_clone = target.clone();
 
         // 1. 确定要捕获的监视器
+        let xcap_monitor = self.select_monitor(&target)?;
+
+        // 2. 捕获屏幕
+        let xcap_image = xcap_monitor
+            .capture_image()
+            .map_err(|e| InfrastructureError::CaptureFailed(e.to_string()))?;
+
+        // 3. 转换为我们的 Image 格式
+        let width = xcap_image.width();
+        let height = xcap_image.height();
+        let raw_data = xcap_image.into_raw();
+
+        // xcap 返回的是 RGBA 格式
+        use aumate_core_domain::image::{
+            ColorFormat as DomainColorFormat, ImageMetadata, ImageSource,
+        };
+
+        let image = Image::with_metadata(
+            raw_data,
+            width,
+            height,
+            DomainColorFormat::RGBA,
+            ImageMetadata::new(ImageSource::Screenshot),
+        )
+        .map_err(|e| InfrastructureError::ImageProcessingFailed(e))?;
+
+        // 4. 构建元数据
+        let monitor_id =
+            xcap_monitor.id().map_err(|e| InfrastructureError::CaptureFailed(e.to_string()))?;
+        let metadata = Self::capture_metadata(&target_clone, monitor_id);
+
+        // 5. 如果是区域捕获，裁剪图像
+        let final_image = if let CaptureTarget::Region(region) = target_clone {
+            self.crop_image(image, region)?
+        } else {
+            image
+        };
+
+        Ok(Screenshot::new(final_image, metadata))
+    }
+
+    /// 以 scRGB 浮点格式捕获，区域捕获与 8 位捕获使用同样的裁剪坐标
+    #[cfg(windows)]
+    async fn capture_hdr_windows(
+        &self,
+        target: CaptureTarget,
+    ) -> Result<HdrScreenshot, InfrastructureError> {
+        use crate::platform::windows::hdr_capture;
+
+        let monitor_id = self
+            .select_monitor(&target)?
+            .id()
+            .map_err(|e| InfrastructureError::CaptureFailed(e.to_string()))?;
+        let image = tokio::task::spawn_blocking(move || hdr_capture::capture(monitor_id))
+            .await
+            .map_err(|e| InfrastructureError::CaptureFailed(e.to_string()))??;
+
+        let image = match &target {
+            CaptureTarget::Region(region) => {
+                if region.min_x() < 0 || region.min_y() < 0 {
+                    return Err(InfrastructureError::CaptureFailed(
+                        "Crop region out of bounds".to_string(),
+                    ));
+                }
+                image
+                    .crop(
+                        region.min_x() as u32,
+                        region.min_y() as u32,
+                        region.width(),
+                        region.height(),
+                    )
+                    .map_err(InfrastructureError::CaptureFailed)?
+            }
+            _ => image,
+        };
+        Ok(HdrScreenshot::new(image, Self::capture_metadata(&target, monitor_id)))
+    }
+
+    /// 捕获目标所在的监视器
+    fn select_monitor(&self, target: &CaptureTarget) -> Result<XCapMonitor, InfrastructureError> {
         let xcap_monitor = match target {
             CaptureTarget::CurrentMonitor => {
                 let monitors = XCapMonitor::all()
@@ -115,7 +214,7 @@ impl ScreenCaptureAdapter {
                 // 对于区域捕获，使用包含该区域的监视器
                 let monitors = XCapMonitor::all()
                     .map_err(|e| InfrastructureError::CaptureFailed(e.to_string()))?;
-                let monitor = self.find_monitor_for_region(&monitors, &region)?;
+                let monitor = self.find_monitor_for_region(&monitors, region)?;
                 monitor
             }
             CaptureTarget::AllMonitors => {
@@ -133,43 +232,13 @@ impl ScreenCaptureAdapter {
                 ));
             }
         };
+        Ok(xcap_monitor)
+    }
 
-        // 2. 捕获屏幕
-        let xcap_image = xcap_monitor
-            .capture_image()
-            .map_err(|e| InfrastructureError::CaptureFailed(e.to_string()))?;
-
-        // 3. 转换为我们的 Image 格式
-        let width = xcap_image.width();
-        let height = xcap_image.height();
-        let raw_data = xcap_image.into_raw();
-
-        // xcap 返回的是 RGBA 格式
-        use aumate_core_domain::image::{
-            ColorFormat as DomainColorFormat, ImageMetadata, ImageSource,
-        };
-
-        let image = Image::with_metadata(
-            raw_data,
-            width,
-            height,
-            DomainColorFormat::RGBA,
-            ImageMetadata::new(ImageSource::Screenshot),
-        )
-        .map_err(|e| InfrastructureError::ImageProcessingFailed(e))?;
-
-        // 4. 构建元数据
-        let monitor_id =
-            xcap_monitor.id().map_err(|e| InfrastructureError::CaptureFailed(e.to_string()))?;
-
-        let capture_region = match &target_clone {
-            CaptureTarget::Region(r) => Some(r.clone()),
-            _ => None,
-        };
-
+    fn capture_metadata(target: &CaptureTarget, monitor_id: u32) -> CaptureMetadata {
         use aumate_core_domain::screenshot::CaptureTarget as DomainCaptureTarget;
 
-        let domain_target = match &target_clone {
+        let domain_target = match target {
             CaptureTarget::CurrentMonitor => DomainCaptureTarget::CurrentMonitor,
             CaptureTarget::Monitor(m) => DomainCaptureTarget::Monitor { id: m.value().to_string() },
             CaptureTarget::Region(r) => DomainCaptureTarget::Region {
@@ -181,21 +250,19 @@ impl ScreenCaptureAdapter {
             _ => DomainCaptureTarget::CurrentMonitor,
         };
 
-        let metadata = CaptureMetadata::new(domain_target)
-            .with_monitor_id(monitor_id.to_string())
-            .with_cursor_visible(true);
-
-        // 5. 如果是区域捕获，裁剪图像
-        let final_image = if let CaptureTarget::Region(region) = target_clone {
-            self.crop_image(image, region)?
-        } else {
-            image
+        // 记录截图时的前台窗口，供截图库搜索
+        let (window_title, app_name) = match active_win_pos_rs::get_active_window() {
+            Ok(window) => (Some(window.title), Some(window.app_name)),
+            Err(_) => (None, None),
         };
 
-        Ok(Screenshot::new(final_image, metadata))
+        CaptureMetadata::new(domain_target)
+            .with_monitor_id(monitor_id.to_string())
+            .with_window(window_title, app_name)
+            .with_cursor_visible(true)
     }
 
-    async fn get_monitors_macos(&self) -> Result<Vec<Monitor>, InfrastructureError> {
+    async fn get_monitors_xcap(&self) -> Result<Vec<Monitor>, InfrastructureError> {
         let xcap_monitors =
             XCapMonitor::all().map_err(|e| InfrastructureError::CaptureFailed(e.to_string()))?;
 
@@ -215,7 +282,8 @@ impl ScreenCaptureAdapter {
                     name: m.name().unwrap_or_else(|_| "Unknown".to_string()),
                     rect: bounds,
                     is_primary: m.is_primary().unwrap_or(false),
-                    scale_factor: 1.0,
+                    scale_factor: Self::scale_factor(&m),
+                    hdr_info: Self::hdr_info(&m),
                 })
             })
             .collect();
@@ -223,6 +291,28 @@ impl ScreenCaptureAdapter {
         monitors
     }
 
+    /// 显示器缩放比例，读取失败或无效时为 1.0
+    fn scale_factor(monitor: &XCapMonitor) -> f64 {
+        match monitor.scale_factor() {
+            Ok(scale) if scale.is_finite() && scale > 0.0 => scale as f64,
+            _ => 1.0,
+        }
+    }
+
+    /// xcap 不提供 HDR 元数据，Windows 上从 DXGI 读取
+    fn hdr_info(monitor: &XCapMonitor) -> Option<HdrInfo> {
+        #[cfg(windows)]
+        {
+            crate::platform::windows::hdr_capture::hdr_info(monitor.id().ok()?)
+        }
+
+        #[cfg(not(windows))]
+        {
+            let _ = monitor;
+            None
+        }
+    }
+
     fn find_monitor_for_region(
         &self,
         monitors: &[XCapMonitor],
@@ -308,7 +398,7 @@ mod tests {
     use super::*;
 
     #[tokio::test]
-    #[cfg(target_os = "macos")]
+    #[cfg(any(target_os = "macos", windows))]
     async fn test_get_monitors() {
         let adapter = ScreenCaptureAdapter::new();
         let result = adapter.get_monitors().await;
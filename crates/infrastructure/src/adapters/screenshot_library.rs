@@ -0,0 +1,531 @@
+// 截图库适配器
+use super::sqlite::{SqliteDatabase, like_pattern};
+use super::storage::FileSystemAdapter;
+use async_trait::async_trait;
+use aumate_core_domain::screenshot::CaptureRegion;
+use aumate_core_domain::screenshot_library::ScreenshotLibraryRetention;
+use aumate_core_shared::{InfrastructureError, now_ms};
+use aumate_core_traits::FileSystemPort;
+use aumate_core_traits::screenshot_library::{
+    NewScreenshotRecord, ScreenshotLibraryPort, ScreenshotLibraryQuery, ScreenshotRecord,
+};
+use rusqlite::types::Value;
+use rusqlite::{Connection, OptionalExtension, Row, params, params_from_iter};
+use std::path::{Path, PathBuf};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS screenshots (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    file_path TEXT NOT NULL,
+    format TEXT NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    byte_size INTEGER NOT NULL,
+    monitor_id TEXT,
+    window_title TEXT,
+    app_name TEXT,
+    region_x INTEGER,
+    region_y INTEGER,
+    region_width INTEGER,
+    region_height INTEGER,
+    tags TEXT NOT NULL DEFAULT '[]',
+    note TEXT,
+    ocr_text TEXT,
+    thumbnail BLOB NOT NULL,
+    captured_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS screenshots_captured ON screenshots(captured_at);
+";
+
+const RECORD_COLUMNS: &str = "id, file_path, format, width, height, byte_size, monitor_id, \
+     window_title, app_name, region_x, region_y, region_width, region_height, tags, note, \
+     ocr_text, captured_at";
+
+/// 参与搜索的文本
+const SEARCH_TEXT: &str = "(COALESCE(window_title, '') || ' ' || COALESCE(app_name, '') || ' ' || \
+     tags || ' ' || COALESCE(note, '') || ' ' || COALESCE(ocr_text, ''))";
+
+const DATABASE_NAME: &str = "Screenshot library";
+
+fn io_error(action: &str, path: &Path, e: std::io::Error) -> InfrastructureError {
+    InfrastructureError::FileOperationFailed(format!(
+        "Failed to {} {}: {}",
+        action,
+        path.display(),
+        e
+    ))
+}
+
+fn record_from_row(row: &Row<'_>) -> rusqlite::Result<ScreenshotRecord> {
+    let region = match (
+        row.get::<_, Option<i32>>(9)?,
+        row.get::<_, Option<i32>>(10)?,
+        row.get::<_, Option<u32>>(11)?,
+        row.get::<_, Option<u32>>(12)?,
+    ) {
+        (Some(x), Some(y), Some(width), Some(height)) => {
+            Some(CaptureRegion { x, y, width, height })
+        }
+        _ => None,
+    };
+    let tags: String = row.get(13)?;
+
+    Ok(ScreenshotRecord {
+        id: row.get(0)?,
+        file_path: row.get(1)?,
+        format: row.get(2)?,
+        width: row.get(3)?,
+        height: row.get(4)?,
+        byte_size: row.get::<_, i64>(5)? as u64,
+        monitor_id: row.get(6)?,
+        window_title: row.get(7)?,
+        app_name: row.get(8)?,
+        region,
+        tags: serde_json::from_str(&tags).unwrap_or_default(),
+        note: row.get(14)?,
+        ocr_text: row.get(15)?,
+        captured_at: row.get(16)?,
+    })
+}
+
+fn get_record(conn: &Connection, id: i64) -> rusqlite::Result<Option<ScreenshotRecord>> {
+    conn.query_row(
+        &format!("SELECT {} FROM screenshots WHERE id = ?1", RECORD_COLUMNS),
+        [id],
+        record_from_row,
+    )
+    .optional()
+}
+
+/// 删除截图文件，文件已不存在时忽略
+async fn remove_file(path: &Path) -> Result<(), InfrastructureError> {
+    match tokio::fs::remove_file(path).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(io_error("delete", path, e)),
+    }
+}
+
+/// SQLite 截图库
+///
+/// 截图文件以 `{id}.{format}` 保存在截图目录中，元数据和 PNG 缩略图保存在数据库里。
+/// 每次加入截图后按保留策略删除最旧的截图
+pub struct SqliteScreenshotLibraryAdapter {
+    db: SqliteDatabase,
+    images_dir: PathBuf,
+    files: FileSystemAdapter,
+    retention: ScreenshotLibraryRetention,
+}
+
+impl SqliteScreenshotLibraryAdapter {
+    /// 默认数据库路径 (~/.aumate/screenshots.db)
+    pub fn default_path() -> PathBuf {
+        let home = dirs::home_dir().expect("Could not find home directory");
+        home.join(".aumate").join("screenshots.db")
+    }
+
+    /// 默认截图目录 (~/.aumate/screenshots)
+    pub fn default_images_dir() -> PathBuf {
+        let home = dirs::home_dir().expect("Could not find home directory");
+        home.join(".aumate").join("screenshots")
+    }
+
+    /// 打开 (或创建) 数据库文件和截图目录
+    pub fn open(
+        path: impl AsRef<Path>,
+        images_dir: impl Into<PathBuf>,
+    ) -> Result<Self, InfrastructureError> {
+        Self::with_database(SqliteDatabase::open(path, DATABASE_NAME, SCHEMA)?, images_dir.into())
+    }
+
+    /// 内存数据库，截图文件仍写入 `images_dir`，用于测试
+    pub fn in_memory(images_dir: impl Into<PathBuf>) -> Result<Self, InfrastructureError> {
+        Self::with_database(SqliteDatabase::in_memory(DATABASE_NAME, SCHEMA)?, images_dir.into())
+    }
+
+    fn with_database(db: SqliteDatabase, images_dir: PathBuf) -> Result<Self, InfrastructureError> {
+        std::fs::create_dir_all(&images_dir).map_err(|e| io_error("create", &images_dir, e))?;
+        Ok(Self {
+            db,
+            images_dir,
+            files: FileSystemAdapter::new(),
+            retention: ScreenshotLibraryRetention::default(),
+        })
+    }
+
+    /// 设置保留策略
+    pub fn with_retention(mut self, retention: ScreenshotLibraryRetention) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    async fn file_path(&self, id: i64) -> Result<Option<PathBuf>, InfrastructureError> {
+        let path = self
+            .db
+            .call(move |conn| {
+                conn.query_row("SELECT file_path FROM screenshots WHERE id = ?1", [id], |row| {
+                    row.get::<_, String>(0)
+                })
+                .optional()
+            })
+            .await?;
+        Ok(path.map(PathBuf::from))
+    }
+
+    /// 删除截图文件和条目，文件已不存在时只删除条目
+    async fn remove(&self, id: i64, path: &Path) -> Result<bool, InfrastructureError> {
+        remove_file(path).await?;
+        let deleted = self
+            .db
+            .call(move |conn| conn.execute("DELETE FROM screenshots WHERE id = ?1", [id]))
+            .await?;
+        Ok(deleted > 0)
+    }
+
+    /// 按保留策略从最旧的截图开始删除，`keep_id` (刚加入的截图) 总是保留，返回删除数量
+    async fn apply_retention(&self, keep_id: i64) -> Result<usize, InfrastructureError> {
+        let retention = self.retention;
+        let cutoff = retention.cutoff(now_ms());
+        if retention.max_entries == 0 && cutoff.is_none() && retention.max_total_bytes == 0 {
+            return Ok(0);
+        }
+
+        let rows = self
+            .db
+            .call(|conn| {
+                conn.prepare(
+                    "SELECT id, file_path, byte_size, captured_at FROM screenshots
+                     WHERE file_path != '' ORDER BY captured_at DESC, id DESC",
+                )?
+                .query_map([], |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, i64>(2)? as u64,
+                        row.get::<_, i64>(3)?,
+                    ))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .await?;
+
+        let mut kept = 0;
+        let mut total_bytes = 0;
+        let mut deleted = 0;
+        for (id, path, byte_size, captured_at) in rows {
+            let expired = (retention.max_entries > 0 && kept >= retention.max_entries)
+                || cutoff.is_some_and(|cutoff| captured_at < cutoff)
+                || (retention.max_total_bytes > 0
+                    && total_bytes + byte_size > retention.max_total_bytes);
+            if id == keep_id || !expired {
+                kept += 1;
+                total_bytes += byte_size;
+            } else if self.remove(id, Path::new(&path)).await? {
+                deleted += 1;
+            }
+        }
+        Ok(deleted)
+    }
+}
+
+#[async_trait]
+impl ScreenshotLibraryPort for SqliteScreenshotLibraryAdapter {
+    async fn add(
+        &self,
+        record: &NewScreenshotRecord,
+        data: &[u8],
+        thumbnail: &[u8],
+    ) -> Result<ScreenshotRecord, InfrastructureError> {
+        let metadata = &record.capture_metadata;
+        let region = metadata.region.clone();
+        let tags = serde_json::to_string(&record.tags)
+            .map_err(|e| InfrastructureError::SerializationFailed(e.to_string()))?;
+        let (format, width, height, byte_size) =
+            (record.format.clone(), record.width, record.height, data.len() as i64);
+        let (monitor_id, window_title, app_name) =
+            (metadata.monitor_id.clone(), metadata.window_title.clone(), metadata.app_name.clone());
+        let thumbnail = thumbnail.to_vec();
+        let captured_at = record.captured_at();
+
+        let id = self
+            .db
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT INTO screenshots (file_path, format, width, height, byte_size,
+                         monitor_id, window_title, app_name, region_x, region_y, region_width,
+                         region_height, tags, thumbnail, captured_at)
+                     VALUES ('', ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+                    params![
+                        format,
+                        width,
+                        height,
+                        byte_size,
+                        monitor_id,
+                        window_title,
+                        app_name,
+                        region.as_ref().map(|r| r.x),
+                        region.as_ref().map(|r| r.y),
+                        region.as_ref().map(|r| r.width),
+                        region.as_ref().map(|r| r.height),
+                        tags,
+                        thumbnail,
+                        captured_at,
+                    ],
+                )?;
+                Ok(conn.last_insert_rowid())
+            })
+            .await?;
+
+        // 文件写入完成前 file_path 为空，查询时跳过这类条目
+        let path = self.images_dir.join(format!("{}.{}", id, record.format));
+        let stored = match self.files.write_atomic(&path, data).await {
+            Ok(()) => {
+                let file_path = path.to_string_lossy().into_owned();
+                self.db
+                    .call(move |conn| {
+                        conn.execute(
+                            "UPDATE screenshots SET file_path = ?1 WHERE id = ?2",
+                            params![file_path, id],
+                        )
+                    })
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = stored {
+            let _ = self
+                .db
+                .call(move |conn| conn.execute("DELETE FROM screenshots WHERE id = ?1", [id]))
+                .await;
+            let _ = remove_file(&path).await;
+            return Err(e);
+        }
+
+        if let Err(e) = self.apply_retention(id).await {
+            log::warn!("SqliteScreenshotLibraryAdapter: failed to apply retention: {}", e);
+        }
+        self.db
+            .call(move |conn| {
+                conn.query_row(
+                    &format!("SELECT {} FROM screenshots WHERE id = ?1", RECORD_COLUMNS),
+                    [id],
+                    record_from_row,
+                )
+            })
+            .await
+    }
+
+    async fn list(
+        &self,
+        query: &ScreenshotLibraryQuery,
+    ) -> Result<Vec<ScreenshotRecord>, InfrastructureError> {
+        let mut sql = format!("SELECT {} FROM screenshots WHERE file_path != ''", RECORD_COLUMNS);
+        let mut values: Vec<Value> = Vec::new();
+
+        if let Some(tag) = &query.tag {
+            sql.push_str(
+                " AND EXISTS (SELECT 1 FROM json_each(screenshots.tags)
+                     WHERE value = ? COLLATE NOCASE)",
+            );
+            values.push(Value::Text(tag.clone()));
+        }
+        for term in query.search.as_deref().unwrap_or_default().split_whitespace() {
+            sql.push_str(&format!(" AND {} LIKE ? ESCAPE '\\'", SEARCH_TEXT));
+            values.push(Value::Text(like_pattern(term)));
+        }
+
+        sql.push_str(" ORDER BY captured_at DESC, id DESC LIMIT ? OFFSET ?");
+        values.push(Value::Integer(query.limit as i64));
+        values.push(Value::Integer(query.offset as i64));
+
+        self.db
+            .call(move |conn| {
+                conn.prepare(&sql)?
+                    .query_map(params_from_iter(values), record_from_row)?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .await
+    }
+
+    async fn get(&self, id: i64) -> Result<Option<ScreenshotRecord>, InfrastructureError> {
+        self.db.call(move |conn| get_record(conn, id)).await
+    }
+
+    async fn read_image(&self, id: i64) -> Result<Option<Vec<u8>>, InfrastructureError> {
+        let Some(path) = self.file_path(id).await? else {
+            return Ok(None);
+        };
+        match tokio::fs::read(&path).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error("read", &path, e)),
+        }
+    }
+
+    async fn thumbnail(&self, id: i64) -> Result<Option<Vec<u8>>, InfrastructureError> {
+        self.db
+            .call(move |conn| {
+                conn.query_row("SELECT thumbnail FROM screenshots WHERE id = ?1", [id], |row| {
+                    row.get(0)
+                })
+                .optional()
+            })
+            .await
+    }
+
+    async fn set_tags(&self, id: i64, tags: &[String]) -> Result<bool, InfrastructureError> {
+        let tags = serde_json::to_string(tags)
+            .map_err(|e| InfrastructureError::SerializationFailed(e.to_string()))?;
+        let updated = self
+            .db
+            .call(move |conn| {
+                conn.execute("UPDATE screenshots SET tags = ?1 WHERE id = ?2", params![tags, id])
+            })
+            .await?;
+        Ok(updated > 0)
+    }
+
+    async fn set_note(&self, id: i64, note: Option<&str>) -> Result<bool, InfrastructureError> {
+        let note = note.map(str::to_string);
+        let updated = self
+            .db
+            .call(move |conn| {
+                conn.execute("UPDATE screenshots SET note = ?1 WHERE id = ?2", params![note, id])
+            })
+            .await?;
+        Ok(updated > 0)
+    }
+
+    async fn set_ocr_text(&self, id: i64, text: &str) -> Result<bool, InfrastructureError> {
+        let text = text.to_string();
+        let updated = self
+            .db
+            .call(move |conn| {
+                conn.execute(
+                    "UPDATE screenshots SET ocr_text = ?1 WHERE id = ?2",
+                    params![text, id],
+                )
+            })
+            .await?;
+        Ok(updated > 0)
+    }
+
+    async fn delete(&self, id: i64) -> Result<bool, InfrastructureError> {
+        let Some(path) = self.file_path(id).await? else {
+            return Ok(false);
+        };
+        self.remove(id, &path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aumate_core_domain::screenshot::{CaptureMetadata, CaptureTarget};
+    use std::time::SystemTime;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "aumate-screenshot-library-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn new_record(title: &str) -> NewScreenshotRecord {
+        let metadata = CaptureMetadata::new(CaptureTarget::FocusedWindow)
+            .with_monitor_id("1".to_string())
+            .with_region(10, 20, 300, 200)
+            .with_window(Some(title.to_string()), Some("browser".to_string()));
+        NewScreenshotRecord::new("png", 300, 200, metadata)
+    }
+
+    #[tokio::test]
+    async fn test_add_read_and_delete() {
+        let dir = temp_dir("files");
+        let library = SqliteScreenshotLibraryAdapter::in_memory(&dir).unwrap();
+
+        let record = library.add(&new_record("Settings"), b"image", b"thumb").await.unwrap();
+        assert_eq!(record.file_path, dir.join(format!("{}.png", record.id)).to_string_lossy());
+        assert_eq!(record.region, Some(CaptureRegion { x: 10, y: 20, width: 300, height: 200 }));
+        assert_eq!(record.byte_size, 5);
+        assert_eq!(library.read_image(record.id).await.unwrap().unwrap(), b"image");
+        assert_eq!(library.thumbnail(record.id).await.unwrap().unwrap(), b"thumb");
+
+        assert!(library.delete(record.id).await.unwrap());
+        assert!(!Path::new(&record.file_path).exists());
+        assert!(library.get(record.id).await.unwrap().is_none());
+        assert!(!library.delete(record.id).await.unwrap());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_search_tags_and_ocr_text() {
+        let dir = temp_dir("search");
+        let library = SqliteScreenshotLibraryAdapter::in_memory(&dir).unwrap();
+
+        let login = library.add(&new_record("Login page"), b"a", b"a").await.unwrap();
+        let settings = library.add(&new_record("Settings"), b"b", b"b").await.unwrap();
+        library.set_tags(login.id, &["Bug".to_string(), "auth".to_string()]).await.unwrap();
+        library.set_note(settings.id, Some("dark mode 50% contrast")).await.unwrap();
+        library.set_ocr_text(settings.id, "Enable two-factor authentication").await.unwrap();
+
+        let ids = |records: Vec<ScreenshotRecord>| -> Vec<i64> {
+            records.into_iter().map(|record| record.id).collect()
+        };
+        let search = |text: &str| ScreenshotLibraryQuery::default().with_search(text);
+
+        assert_eq!(
+            ids(library.list(&ScreenshotLibraryQuery::default()).await.unwrap()),
+            [settings.id, login.id]
+        );
+        assert_eq!(ids(library.list(&search("login")).await.unwrap()), [login.id]);
+        assert_eq!(ids(library.list(&search("two-factor")).await.unwrap()), [settings.id]);
+        assert_eq!(ids(library.list(&search("50%")).await.unwrap()), [settings.id]);
+        assert_eq!(ids(library.list(&search("auth")).await.unwrap()), [settings.id, login.id]);
+
+        let tagged = ScreenshotLibraryQuery::default().with_tag("bug");
+        assert_eq!(ids(library.list(&tagged).await.unwrap()), [login.id]);
+        assert_eq!(library.get(login.id).await.unwrap().unwrap().tags, ["Bug", "auth"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_retention_prunes_oldest() {
+        let dir = temp_dir("retention");
+        let retention =
+            ScreenshotLibraryRetention { max_entries: 3, max_age_days: 7, max_total_bytes: 10 };
+        let library =
+            SqliteScreenshotLibraryAdapter::in_memory(&dir).unwrap().with_retention(retention);
+        let ids = |records: Vec<ScreenshotRecord>| -> Vec<i64> {
+            records.into_iter().map(|record| record.id).collect()
+        };
+
+        // 超过保留天数
+        let mut old = new_record("Old");
+        old.capture_metadata.capture_time =
+            SystemTime::now() - std::time::Duration::from_secs(8 * 24 * 60 * 60);
+        let old = library.add(&old, b"1", b"t").await.unwrap();
+        let first = library.add(&new_record("First"), b"22", b"t").await.unwrap();
+        assert!(library.get(old.id).await.unwrap().is_none());
+        assert!(!Path::new(&old.file_path).exists());
+
+        // 超过数量
+        let second = library.add(&new_record("Second"), b"33", b"t").await.unwrap();
+        let third = library.add(&new_record("Third"), b"44", b"t").await.unwrap();
+        let fourth = library.add(&new_record("Fourth"), b"55", b"t").await.unwrap();
+        let all = ScreenshotLibraryQuery::default();
+        assert_eq!(ids(library.list(&all).await.unwrap()), [fourth.id, third.id, second.id]);
+        assert!(!Path::new(&first.file_path).exists());
+
+        // 超过总大小，刚加入的截图即使单独超限也保留
+        let large = library.add(&new_record("Large"), b"0123456789ab", b"t").await.unwrap();
+        assert_eq!(ids(library.list(&all).await.unwrap()), [large.id]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
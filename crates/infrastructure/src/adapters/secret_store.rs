@@ -0,0 +1,533 @@
+// 加密文件密钥存储
+use async_trait::async_trait;
+use aumate_core_shared::InfrastructureError;
+use aumate_core_traits::secret::SecretStorePort;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use ring::aead::{Aad, CHACHA20_POLY1305, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+/// 主密钥长度 (ChaCha20-Poly1305)
+const KEY_LEN: usize = 32;
+
+/// 密钥文件格式版本
+const SECRET_FILE_VERSION: u32 = 1;
+
+fn secret_error(message: impl std::fmt::Display) -> InfrastructureError {
+    InfrastructureError::FileOperationFailed(format!("Secret store: {}", message))
+}
+
+fn keyring_error(message: impl std::fmt::Display) -> InfrastructureError {
+    InfrastructureError::PlatformOperationFailed(format!("Keyring: {}", message))
+}
+
+/// 主密钥来源
+///
+/// 默认使用本地密钥文件 [`KeyFile`]，也可以换成系统钥匙串 [`SystemKeyring`]
+/// 或其他实现
+pub trait MasterKeyProvider: Send + Sync {
+    /// 读取主密钥，尚未创建时返回 None
+    fn load(&self) -> Result<Option<Vec<u8>>, InfrastructureError>;
+
+    /// 保存新生成的主密钥
+    fn store(&self, key: &[u8]) -> Result<(), InfrastructureError>;
+}
+
+/// 本地密钥文件，内容为 Base64 编码的主密钥
+///
+/// Unix 上权限为 0600；Windows 上只依赖用户目录的默认 ACL，
+/// 系统钥匙串可用时应优先使用 [`SystemKeyring`]
+pub struct KeyFile {
+    path: PathBuf,
+}
+
+impl KeyFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// `~/.aumate/secrets.key`
+    pub fn default_path() -> PathBuf {
+        dirs::home_dir().unwrap_or_default().join(".aumate").join("secrets.key")
+    }
+}
+
+impl MasterKeyProvider for KeyFile {
+    fn load(&self) -> Result<Option<Vec<u8>>, InfrastructureError> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let encoded = std::fs::read_to_string(&self.path).map_err(secret_error)?;
+        BASE64.decode(encoded.trim()).map(Some).map_err(secret_error)
+    }
+
+    fn store(&self, key: &[u8]) -> Result<(), InfrastructureError> {
+        write_private(&self.path, BASE64.encode(key).as_bytes()).map_err(secret_error)
+    }
+}
+
+/// 系统钥匙串
+///
+/// Linux 通过 `secret-tool` 使用 Secret Service，macOS 通过 `security` 使用钥匙串，
+/// Windows 使用凭据管理器，其他平台返回 `PlatformNotSupported`。
+/// 主密钥只通过标准输入传给外部工具，不会出现在命令行参数中
+pub struct SystemKeyring {
+    service: String,
+    account: String,
+}
+
+impl SystemKeyring {
+    pub fn new(service: impl Into<String>, account: impl Into<String>) -> Self {
+        Self { service: service.into(), account: account.into() }
+    }
+
+    /// 当前系统是否有可用的钥匙串工具
+    pub fn is_available() -> bool {
+        if cfg!(windows) {
+            return true;
+        }
+        let tool = if cfg!(target_os = "macos") { "security" } else { "secret-tool" };
+        if !cfg!(any(target_os = "linux", target_os = "macos")) {
+            return false;
+        }
+        Command::new(tool)
+            .arg("--help")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok()
+    }
+
+    fn run(
+        command: &mut Command,
+        stdin: Option<&[u8]>,
+    ) -> Result<Option<String>, InfrastructureError> {
+        let mut child = command
+            .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(keyring_error)?;
+        if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+            pipe.write_all(input).map_err(secret_error)?;
+        }
+        let output = child.wait_with_output().map_err(secret_error)?;
+        // 查询不到时两个工具都以非零状态退出
+        Ok(output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string()))
+    }
+
+    /// 凭据管理器中的目标名
+    #[cfg(windows)]
+    fn target(&self) -> String {
+        format!("{}/{}", self.service, self.account)
+    }
+}
+
+/// `security -i` 命令中的带引号参数
+fn security_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Windows 凭据管理器，凭据只有当前用户可以读取
+#[cfg(windows)]
+mod credential_manager {
+    use super::keyring_error;
+    use aumate_core_shared::InfrastructureError;
+    use windows::Win32::Foundation::ERROR_NOT_FOUND;
+    use windows::Win32::Security::Credentials::{
+        CRED_PERSIST_LOCAL_MACHINE, CRED_TYPE_GENERIC, CREDENTIALW, CredFree, CredReadW, CredWriteW,
+    };
+    use windows::core::{HSTRING, PWSTR};
+
+    pub fn read(target: &str) -> Result<Option<Vec<u8>>, InfrastructureError> {
+        let target = HSTRING::from(target);
+        let mut credential: *mut CREDENTIALW = std::ptr::null_mut();
+        if let Err(e) = unsafe { CredReadW(&target, CRED_TYPE_GENERIC, None, &mut credential) } {
+            if e.code() == ERROR_NOT_FOUND.to_hresult() {
+                return Ok(None);
+            }
+            return Err(keyring_error(e));
+        }
+        let blob = unsafe {
+            let credential = &*credential;
+            let blob = std::slice::from_raw_parts(
+                credential.CredentialBlob,
+                credential.CredentialBlobSize as usize,
+            )
+            .to_vec();
+            CredFree(credential as *const CREDENTIALW as *const _);
+            blob
+        };
+        Ok(Some(blob))
+    }
+
+    pub fn write(target: &str, blob: &[u8]) -> Result<(), InfrastructureError> {
+        let mut target: Vec<u16> = target.encode_utf16().chain(std::iter::once(0)).collect();
+        let mut blob = blob.to_vec();
+        let credential = CREDENTIALW {
+            Type: CRED_TYPE_GENERIC,
+            TargetName: PWSTR(target.as_mut_ptr()),
+            CredentialBlobSize: blob.len() as u32,
+            CredentialBlob: blob.as_mut_ptr(),
+            Persist: CRED_PERSIST_LOCAL_MACHINE,
+            ..Default::default()
+        };
+        unsafe { CredWriteW(&credential, 0) }.map_err(keyring_error)
+    }
+}
+
+impl MasterKeyProvider for SystemKeyring {
+    fn load(&self) -> Result<Option<Vec<u8>>, InfrastructureError> {
+        #[cfg(windows)]
+        return credential_manager::read(&self.target());
+
+        #[allow(unreachable_code)]
+        let mut command = if cfg!(target_os = "macos") {
+            let mut command = Command::new("security");
+            command.args(["find-generic-password", "-s", &self.service, "-a", &self.account, "-w"]);
+            command
+        } else if cfg!(target_os = "linux") {
+            let mut command = Command::new("secret-tool");
+            command.args(["lookup", "service", &self.service, "account", &self.account]);
+            command
+        } else {
+            return Err(InfrastructureError::PlatformNotSupported);
+        };
+
+        match Self::run(&mut command, None)? {
+            Some(encoded) if !encoded.is_empty() => {
+                BASE64.decode(encoded).map(Some).map_err(secret_error)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn store(&self, key: &[u8]) -> Result<(), InfrastructureError> {
+        #[cfg(windows)]
+        return credential_manager::write(&self.target(), key);
+
+        #[allow(unreachable_code)]
+        let encoded = BASE64.encode(key);
+        let stored = if cfg!(target_os = "macos") {
+            // 交互模式从标准输入读取命令，`-w` 的值不会出现在进程参数中。
+            // 交互模式下命令失败时退出状态仍为 0，因此写入后再读回校验
+            let input = format!(
+                "add-generic-password -U -s {} -a {} -w {}\n",
+                security_quote(&self.service),
+                security_quote(&self.account),
+                encoded
+            );
+            let mut command = Command::new("security");
+            command.arg("-i");
+            Self::run(&mut command, Some(input.as_bytes()))?;
+            (self.load()?.as_deref() == Some(key)).then_some(String::new())
+        } else if cfg!(target_os = "linux") {
+            let mut command = Command::new("secret-tool");
+            command.args([
+                "store",
+                "--label=Aumate secret store key",
+                "service",
+                &self.service,
+                "account",
+                &self.account,
+            ]);
+            Self::run(&mut command, Some(encoded.as_bytes()))?
+        } else {
+            return Err(InfrastructureError::PlatformNotSupported);
+        };
+
+        stored.map(|_| ()).ok_or_else(|| {
+            InfrastructureError::PlatformOperationFailed(
+                "Keyring: failed to store the master key".to_string(),
+            )
+        })
+    }
+}
+
+/// 密钥文件内容，每个密钥为 Base64 编码的 `nonce || 密文 || tag`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SecretFile {
+    version: u32,
+    secrets: BTreeMap<String, String>,
+}
+
+struct SecretFileStore {
+    path: PathBuf,
+    key_provider: Arc<dyn MasterKeyProvider>,
+    /// 已加载的主密钥，同时保护文件读写
+    key: Mutex<Option<Vec<u8>>>,
+}
+
+impl SecretFileStore {
+    /// 读取主密钥，第一次使用时生成
+    fn master_key(&self, cached: &mut Option<Vec<u8>>) -> Result<LessSafeKey, InfrastructureError> {
+        let key = match cached {
+            Some(key) => key.clone(),
+            None => {
+                let key = match self.key_provider.load()? {
+                    Some(key) => key,
+                    None if !self.read()?.secrets.is_empty() => {
+                        return Err(secret_error(
+                            "master key is missing, existing secrets cannot be decrypted",
+                        ));
+                    }
+                    None => {
+                        let mut key = vec![0u8; KEY_LEN];
+                        SystemRandom::new()
+                            .fill(&mut key)
+                            .map_err(|_| secret_error("failed to generate a key"))?;
+                        self.key_provider.store(&key)?;
+                        log::info!("Created a new secret store master key");
+                        key
+                    }
+                };
+                *cached = Some(key.clone());
+                key
+            }
+        };
+
+        let key = UnboundKey::new(&CHACHA20_POLY1305, &key)
+            .map_err(|_| secret_error("invalid master key"))?;
+        Ok(LessSafeKey::new(key))
+    }
+
+    fn read(&self) -> Result<SecretFile, InfrastructureError> {
+        if !self.path.exists() {
+            return Ok(SecretFile { version: SECRET_FILE_VERSION, ..Default::default() });
+        }
+        let content = std::fs::read_to_string(&self.path).map_err(secret_error)?;
+        serde_json::from_str(&content).map_err(secret_error)
+    }
+
+    fn write(&self, file: &SecretFile) -> Result<(), InfrastructureError> {
+        let content = serde_json::to_vec_pretty(file).map_err(secret_error)?;
+        let temp_path = self.path.with_extension("json.tmp");
+        write_private(&temp_path, &content).map_err(secret_error)?;
+        std::fs::rename(&temp_path, &self.path).map_err(secret_error)
+    }
+
+    fn get(&self, name: &str) -> Result<Option<String>, InfrastructureError> {
+        let mut cached = self.key.lock().unwrap_or_else(|e| e.into_inner());
+        let file = self.read()?;
+        let Some(encoded) = file.secrets.get(name) else {
+            return Ok(None);
+        };
+        let key = self.master_key(&mut cached)?;
+        decrypt(&key, name, encoded).map(Some)
+    }
+
+    fn set(&self, name: &str, value: &str) -> Result<(), InfrastructureError> {
+        let mut cached = self.key.lock().unwrap_or_else(|e| e.into_inner());
+        let key = self.master_key(&mut cached)?;
+        let mut file = self.read()?;
+        file.version = SECRET_FILE_VERSION;
+        file.secrets.insert(name.to_string(), encrypt(&key, name, value)?);
+        self.write(&file)
+    }
+
+    fn delete(&self, name: &str) -> Result<bool, InfrastructureError> {
+        let _guard = self.key.lock().unwrap_or_else(|e| e.into_inner());
+        let mut file = self.read()?;
+        if file.secrets.remove(name).is_none() {
+            return Ok(false);
+        }
+        self.write(&file)?;
+        Ok(true)
+    }
+}
+
+/// 加密密钥存储
+///
+/// 密钥以 ChaCha20-Poly1305 加密后保存在 JSON 文件中，密钥名作为附加数据，
+/// 因此密文不能被挪到其他名称下使用。主密钥由 [`MasterKeyProvider`] 提供
+pub struct EncryptedFileSecretStore {
+    inner: Arc<SecretFileStore>,
+}
+
+impl EncryptedFileSecretStore {
+    pub fn new(path: PathBuf, key_provider: Arc<dyn MasterKeyProvider>) -> Self {
+        Self { inner: Arc::new(SecretFileStore { path, key_provider, key: Mutex::new(None) }) }
+    }
+
+    /// `~/.aumate/secrets.json`
+    ///
+    /// Windows 上主密钥保存在凭据管理器中，其他平台以及已有密钥文件时
+    /// 保存在 `~/.aumate/secrets.key`
+    pub fn open_default() -> Self {
+        let key_file = KeyFile::default_path();
+        let key_provider: Arc<dyn MasterKeyProvider> = if cfg!(windows) && !key_file.exists() {
+            Arc::new(SystemKeyring::new("aumate", "secret-store"))
+        } else {
+            Arc::new(KeyFile::new(key_file))
+        };
+        Self::new(Self::default_path(), key_provider)
+    }
+
+    pub fn default_path() -> PathBuf {
+        dirs::home_dir().unwrap_or_default().join(".aumate").join("secrets.json")
+    }
+
+    async fn run<T, F>(&self, f: F) -> Result<T, InfrastructureError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&SecretFileStore) -> Result<T, InfrastructureError> + Send + 'static,
+    {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || f(&inner)).await.map_err(secret_error)?
+    }
+}
+
+#[async_trait]
+impl SecretStorePort for EncryptedFileSecretStore {
+    async fn get(&self, name: &str) -> Result<Option<String>, InfrastructureError> {
+        let name = name.to_string();
+        self.run(move |store| store.get(&name)).await
+    }
+
+    async fn set(&self, name: &str, value: &str) -> Result<(), InfrastructureError> {
+        log::info!("SecretStore: storing {}", name);
+        let (name, value) = (name.to_string(), value.to_string());
+        self.run(move |store| store.set(&name, &value)).await
+    }
+
+    async fn delete(&self, name: &str) -> Result<bool, InfrastructureError> {
+        log::info!("SecretStore: deleting {}", name);
+        let name = name.to_string();
+        self.run(move |store| store.delete(&name)).await
+    }
+}
+
+fn encrypt(key: &LessSafeKey, name: &str, value: &str) -> Result<String, InfrastructureError> {
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new().fill(&mut nonce).map_err(|_| secret_error("failed to generate a nonce"))?;
+
+    let mut data = value.as_bytes().to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(name.as_bytes()),
+        &mut data,
+    )
+    .map_err(|_| secret_error("encryption failed"))?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&data);
+    Ok(BASE64.encode(sealed))
+}
+
+fn decrypt(key: &LessSafeKey, name: &str, encoded: &str) -> Result<String, InfrastructureError> {
+    let sealed = BASE64.decode(encoded).map_err(secret_error)?;
+    if sealed.len() < NONCE_LEN {
+        return Err(secret_error(format!("{} is corrupted", name)));
+    }
+    let (nonce, data) = sealed.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| secret_error("bad nonce"))?;
+
+    let mut data = data.to_vec();
+    let plain = key
+        .open_in_place(nonce, Aad::from(name.as_bytes()), &mut data)
+        .map_err(|_| secret_error(format!("{} cannot be decrypted", name)))?;
+    String::from_utf8(plain.to_vec()).map_err(secret_error)
+}
+
+/// 写入只有当前用户可读写的文件
+fn write_private(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(data)?;
+    file.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("aumate-secrets-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn store(dir: &Path) -> EncryptedFileSecretStore {
+        EncryptedFileSecretStore::new(
+            dir.join("secrets.json"),
+            Arc::new(KeyFile::new(dir.join("secrets.key"))),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_roundtrip() {
+        let dir = temp_dir("roundtrip");
+        let secrets = store(&dir);
+
+        assert_eq!(secrets.get("ai_dialogue.api_key").await.unwrap(), None);
+        secrets.set("ai_dialogue.api_key", "sk-secret-value").await.unwrap();
+        assert_eq!(
+            secrets.get("ai_dialogue.api_key").await.unwrap().as_deref(),
+            Some("sk-secret-value")
+        );
+
+        // 文件中没有明文，重新打开后仍可读取
+        let content = std::fs::read_to_string(dir.join("secrets.json")).unwrap();
+        assert!(!content.contains("sk-secret-value"));
+        let reopened = store(&dir);
+        assert_eq!(
+            reopened.get("ai_dialogue.api_key").await.unwrap().as_deref(),
+            Some("sk-secret-value")
+        );
+
+        assert!(reopened.delete("ai_dialogue.api_key").await.unwrap());
+        assert!(!reopened.delete("ai_dialogue.api_key").await.unwrap());
+        assert_eq!(reopened.get("ai_dialogue.api_key").await.unwrap(), None);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(dir.join("secrets.key")).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_tampering_is_detected() {
+        let dir = temp_dir("tamper");
+        let secrets = store(&dir);
+        secrets.set("a", "first").await.unwrap();
+        secrets.set("b", "second").await.unwrap();
+
+        // 把 a 的密文复制到 b 名下
+        let path = dir.join("secrets.json");
+        let mut file: SecretFile =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let a = file.secrets["a"].clone();
+        file.secrets.insert("b".to_string(), a);
+        std::fs::write(&path, serde_json::to_string(&file).unwrap()).unwrap();
+        assert!(secrets.get("b").await.is_err());
+
+        // 丢失主密钥时不会生成新密钥覆盖
+        std::fs::remove_file(dir.join("secrets.key")).unwrap();
+        assert!(store(&dir).get("a").await.is_err());
+        assert!(store(&dir).set("c", "third").await.is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,27 +1,50 @@
 // Settings Storage Adapter
 use async_trait::async_trait;
-use aumate_core_domain::settings::Settings;
-use aumate_core_shared::InfrastructureError;
+use aumate_core_domain::settings::{
+    Settings, SettingsLoadReport, redact_api_keys, settings_from_json,
+};
+use aumate_core_shared::{FieldError, InfrastructureError};
 use aumate_core_traits::settings::SettingsStoragePort;
 use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::fs;
 
+/// Problems found the last time the settings file had to be repaired
+#[derive(Debug, Clone, Default)]
+struct Recovery {
+    migrated_from: Option<u32>,
+    errors: Vec<FieldError>,
+    backup_path: Option<String>,
+}
+
 /// File system based settings storage adapter
+///
+/// Older files are migrated and fields that cannot be read are reset to their
+/// defaults. In both cases the original file is backed up next to it before
+/// being rewritten, with API keys replaced by secret references.
 pub struct FileSystemSettingsAdapter {
     settings_path: PathBuf,
+    /// Serializes reads and writes so a repair is only done once
+    io_lock: tokio::sync::Mutex<()>,
+    /// Reported by every load until settings are saved again
+    recovery: Mutex<Option<Recovery>>,
 }
 
 impl FileSystemSettingsAdapter {
     /// Create a new settings adapter with default path (~/.aumate/settings.json)
     pub fn new() -> Self {
         let home = dirs::home_dir().expect("Could not find home directory");
-        let settings_path = home.join(".aumate").join("settings.json");
-        Self { settings_path }
+        Self::with_path(home.join(".aumate").join("settings.json"))
     }
 
     /// Create with custom path
     pub fn with_path(path: PathBuf) -> Self {
-        Self { settings_path: path }
+        Self {
+            settings_path: path,
+            io_lock: tokio::sync::Mutex::new(()),
+            recovery: Mutex::new(None),
+        }
     }
 
     /// Ensure the settings directory exists
@@ -33,6 +56,46 @@ impl FileSystemSettingsAdapter {
         }
         Ok(())
     }
+
+    /// Write `content` to `settings.<label>-<unix ms>.json` without plaintext API keys
+    async fn backup(&self, label: &str, content: &str) -> Result<PathBuf, InfrastructureError> {
+        let stamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+        let backup_path =
+            self.settings_path.with_file_name(format!("{}.{}-{}.json", self.stem(), label, stamp));
+
+        let content = redact_api_keys(content).unwrap_or_else(|| content.to_string());
+        fs::write(&backup_path, content).await.map_err(|e| {
+            InfrastructureError::IoFailed(format!("Failed to back up settings: {}", e))
+        })?;
+        Ok(backup_path)
+    }
+
+    fn stem(&self) -> String {
+        self.settings_path.file_stem().unwrap_or_default().to_string_lossy().into_owned()
+    }
+
+    /// Write through a temporary file so a crash never leaves a truncated file
+    async fn write(&self, settings: &Settings) -> Result<(), InfrastructureError> {
+        self.ensure_settings_dir().await?;
+
+        let content = serde_json::to_string_pretty(settings).map_err(|e| {
+            InfrastructureError::SerializationFailed(format!("Failed to serialize settings: {}", e))
+        })?;
+
+        let temp_path = self.settings_path.with_extension("json.tmp");
+        fs::write(&temp_path, content).await.map_err(|e| {
+            InfrastructureError::IoFailed(format!("Failed to write settings: {}", e))
+        })?;
+        fs::rename(&temp_path, &self.settings_path).await.map_err(|e| {
+            InfrastructureError::IoFailed(format!("Failed to write settings: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    fn recovery(&self) -> std::sync::MutexGuard<'_, Option<Recovery>> {
+        self.recovery.lock().unwrap_or_else(|e| e.into_inner())
+    }
 }
 
 impl Default for FileSystemSettingsAdapter {
@@ -44,38 +107,189 @@ impl Default for FileSystemSettingsAdapter {
 #[async_trait]
 impl SettingsStoragePort for FileSystemSettingsAdapter {
     async fn load(&self) -> Result<Settings, InfrastructureError> {
+        Ok(self.load_with_report().await?.settings)
+    }
+
+    async fn load_with_report(&self) -> Result<SettingsLoadReport, InfrastructureError> {
+        let _guard = self.io_lock.lock().await;
         log::info!("Loading settings from {:?}", self.settings_path);
 
         if !self.settings_path.exists() {
             log::info!("Settings file not found, returning default settings");
-            return Ok(Settings::default());
+            return Ok(SettingsLoadReport::new(Settings::default()));
         }
 
         let content = fs::read_to_string(&self.settings_path).await.map_err(|e| {
             InfrastructureError::IoFailed(format!("Failed to read settings: {}", e))
         })?;
 
-        let settings: Settings = serde_json::from_str(&content).unwrap_or_else(|e| {
-            log::warn!("Failed to parse settings: {}, using default", e);
-            Settings::default()
-        });
+        let mut report = settings_from_json(&content);
+        if !report.is_clean() {
+            let label = match report.migrated_from {
+                Some(version) if report.errors.is_empty() => format!("v{}", version),
+                _ => "invalid".to_string(),
+            };
+            let backup_path = self.backup(&label, &content).await?;
+            for error in &report.errors {
+                log::warn!("Invalid setting {}, using default", error);
+            }
+            log::warn!("Settings repaired, original backed up to {:?}", backup_path);
 
-        Ok(settings)
+            self.write(&report.settings).await?;
+            *self.recovery() = Some(Recovery {
+                migrated_from: report.migrated_from,
+                errors: report.errors.clone(),
+                backup_path: Some(backup_path.to_string_lossy().into_owned()),
+            });
+        }
+
+        if let Some(recovery) = self.recovery().clone() {
+            report.migrated_from = recovery.migrated_from;
+            report.errors = recovery.errors;
+            report.backup_path = recovery.backup_path;
+        }
+        Ok(report)
     }
 
     async fn save(&self, settings: Settings) -> Result<(), InfrastructureError> {
+        let _guard = self.io_lock.lock().await;
         log::info!("Saving settings to {:?}", self.settings_path);
 
-        self.ensure_settings_dir().await?;
+        self.write(&settings).await?;
+        *self.recovery() = None;
 
-        let content = serde_json::to_string_pretty(&settings).map_err(|e| {
-            InfrastructureError::SerializationFailed(format!("Failed to serialize settings: {}", e))
-        })?;
+        Ok(())
+    }
 
-        fs::write(&self.settings_path, content).await.map_err(|e| {
-            InfrastructureError::IoFailed(format!("Failed to write settings: {}", e))
-        })?;
+    async fn scrub_backups(&self) -> Result<(), InfrastructureError> {
+        let _guard = self.io_lock.lock().await;
+        let Some(dir) = self.settings_path.parent() else {
+            return Ok(());
+        };
+        let io_error = |e: std::io::Error| {
+            InfrastructureError::IoFailed(format!("Failed to scrub backups: {}", e))
+        };
 
+        let prefix = format!("{}.", self.stem());
+        let settings_name = self.settings_path.file_name().unwrap_or_default();
+        let mut entries = match fs::read_dir(dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(io_error(e)),
+        };
+        while let Some(entry) = entries.next_entry().await.map_err(io_error)? {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if entry.file_name() == settings_name
+                || !name.starts_with(&prefix)
+                || !name.ends_with(".json")
+            {
+                continue;
+            }
+            let path = entry.path();
+            let content = fs::read_to_string(&path).await.map_err(io_error)?;
+            if let Some(redacted) = redact_api_keys(&content) {
+                log::info!("Removing plaintext API keys from {:?}", path);
+                fs::write(&path, redacted).await.map_err(io_error)?;
+            }
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_settings_path(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("aumate-settings-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("settings.json")
+    }
+
+    #[tokio::test]
+    async fn test_invalid_field_is_backed_up_and_repaired() {
+        let path = temp_settings_path("invalid");
+        let mut value = serde_json::to_value(Settings::default()).unwrap();
+        value["ai_dialogue"]["api_key"] = "sk-keep".into();
+        value["general"]["open_at_login"] = "yes".into();
+        let original = value.to_string();
+        std::fs::write(&path, &original).unwrap();
+
+        let adapter = FileSystemSettingsAdapter::with_path(path.clone());
+        let report = adapter.load_with_report().await.unwrap();
+        assert_eq!(report.settings.ai_dialogue.api_key, "sk-keep");
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].field, "general.open_at_login");
+
+        let backup = PathBuf::from(report.backup_path.unwrap());
+        assert!(backup.file_name().unwrap().to_string_lossy().starts_with("settings.invalid-"));
+        let mut expected = value.clone();
+        expected["ai_dialogue"]["api_key"] = "secret:ai_dialogue.api_key".into();
+        let backed_up: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&backup).unwrap()).unwrap();
+        assert_eq!(backed_up, expected);
+
+        // The repaired file reads cleanly, the problems stay reported until the next save
+        let settings: Settings =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(settings.ai_dialogue.api_key, "sk-keep");
+        assert_eq!(adapter.load_with_report().await.unwrap().errors.len(), 1);
+        adapter.save(settings).await.unwrap();
+        assert!(adapter.load_with_report().await.unwrap().is_clean());
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_old_file_is_migrated() {
+        let path = temp_settings_path("migrate");
+        let mut value = serde_json::to_value(Settings::default()).unwrap();
+        value.as_object_mut().unwrap().remove("schema_version");
+        value["screenshot"]["image_format"] = "jpg".into();
+        std::fs::write(&path, value.to_string()).unwrap();
+
+        let adapter = FileSystemSettingsAdapter::with_path(path.clone());
+        let report = adapter.load_with_report().await.unwrap();
+        assert_eq!(report.migrated_from, Some(1));
+        assert!(report.errors.is_empty());
+        assert_eq!(report.settings.screenshot.image_format, "jpeg");
+        assert!(report.backup_path.unwrap().contains("settings.v1-"));
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_backups_never_keep_plaintext_api_keys() {
+        let path = temp_settings_path("secrets");
+        let dir = path.parent().unwrap().to_path_buf();
+        let mut value = serde_json::to_value(Settings::default()).unwrap();
+        value.as_object_mut().unwrap().remove("schema_version");
+        value["ai_dialogue"]["api_key"] = "sk-plaintext-9876".into();
+        std::fs::write(&path, value.to_string()).unwrap();
+        // Left behind by a build that copied files verbatim
+        std::fs::write(dir.join("settings.invalid-1.json"), value.to_string()).unwrap();
+
+        let adapter = FileSystemSettingsAdapter::with_path(path.clone());
+        let report = adapter.load_with_report().await.unwrap();
+        assert_eq!(report.migrated_from, Some(1));
+        assert_eq!(report.settings.ai_dialogue.api_key, "sk-plaintext-9876");
+
+        // What GetSettingsUseCase does after moving the key to the secret store
+        let mut settings = report.settings;
+        settings.ai_dialogue.api_key = "secret:ai_dialogue.api_key".to_string();
+        adapter.save(settings).await.unwrap();
+        adapter.scrub_backups().await.unwrap();
+
+        let mut files = 0;
+        for entry in std::fs::read_dir(&dir).unwrap() {
+            let content = std::fs::read_to_string(entry.unwrap().path()).unwrap();
+            assert!(!content.contains("sk-plaintext-9876"));
+            files += 1;
+        }
+        assert_eq!(files, 3);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
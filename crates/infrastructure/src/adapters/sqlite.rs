@@ -0,0 +1,112 @@
+// SQLite 适配器共用的连接管理
+use aumate_core_shared::InfrastructureError;
+use rusqlite::Connection;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// 带数据库名前缀的 `DatabaseFailed`
+pub fn db_error(name: &str, e: impl std::fmt::Display) -> InfrastructureError {
+    InfrastructureError::DatabaseFailed(format!("{}: {}", name, e))
+}
+
+/// 子串匹配的 LIKE 模式，需配合 `ESCAPE '\'` 使用
+pub fn like_pattern(term: &str) -> String {
+    let escaped = term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// 共享的 SQLite 连接
+///
+/// 打开时启用外键并执行建表语句，所有查询都在阻塞线程池中执行，不会阻塞异步运行时
+#[derive(Clone)]
+pub struct SqliteDatabase {
+    conn: Arc<Mutex<Connection>>,
+    name: &'static str,
+}
+
+impl SqliteDatabase {
+    /// 打开 (或创建) 数据库文件，使用 WAL 模式
+    pub fn open(
+        path: impl AsRef<Path>,
+        name: &'static str,
+        schema: &str,
+    ) -> Result<Self, InfrastructureError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                InfrastructureError::IoFailed(format!(
+                    "Failed to create {}: {}",
+                    parent.display(),
+                    e
+                ))
+            })?;
+        }
+        let conn = Connection::open(path).map_err(|e| db_error(name, e))?;
+        conn.pragma_update(None, "journal_mode", "WAL").map_err(|e| db_error(name, e))?;
+        Self::with_connection(conn, name, schema)
+    }
+
+    /// 内存数据库，用于测试
+    pub fn in_memory(name: &'static str, schema: &str) -> Result<Self, InfrastructureError> {
+        Self::with_connection(
+            Connection::open_in_memory().map_err(|e| db_error(name, e))?,
+            name,
+            schema,
+        )
+    }
+
+    fn with_connection(
+        conn: Connection,
+        name: &'static str,
+        schema: &str,
+    ) -> Result<Self, InfrastructureError> {
+        conn.pragma_update(None, "foreign_keys", true).map_err(|e| db_error(name, e))?;
+        conn.execute_batch(schema).map_err(|e| db_error(name, e))?;
+        Ok(Self { conn: Arc::new(Mutex::new(conn)), name })
+    }
+
+    /// 在阻塞线程池中持有连接执行 `f`
+    pub async fn call<T, F>(&self, f: F) -> Result<T, InfrastructureError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> rusqlite::Result<T> + Send + 'static,
+    {
+        let conn = Arc::clone(&self.conn);
+        let name = self.name;
+        tokio::task::spawn_blocking(move || {
+            let mut conn =
+                conn.lock().map_err(|e| db_error(name, format!("connection poisoned: {}", e)))?;
+            f(&mut conn).map_err(|e| db_error(name, e))
+        })
+        .await
+        .map_err(|e| db_error(name, format!("query task failed: {}", e)))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_call_reports_database_name() {
+        let db = SqliteDatabase::in_memory("Test", "CREATE TABLE items (id INTEGER)").unwrap();
+        let count = db
+            .call(|conn| {
+                conn.execute("INSERT INTO items (id) VALUES (1)", [])?;
+                conn.query_row("SELECT COUNT(*) FROM items", [], |row| row.get::<_, i64>(0))
+            })
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+
+        let err = db.call(|conn| conn.execute("SELECT * FROM missing", [])).await.unwrap_err();
+        assert!(
+            matches!(err, InfrastructureError::DatabaseFailed(message) if message.starts_with("Test: "))
+        );
+    }
+
+    #[test]
+    fn test_like_pattern_escapes_wildcards() {
+        assert_eq!(like_pattern("50%_a\\b"), "%50\\%\\_a\\\\b%");
+    }
+}
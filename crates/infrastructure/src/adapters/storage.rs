@@ -73,6 +73,29 @@ impl FileSystemPort for FileSystemAdapter {
         })
     }
 
+    /// 原子写入文件
+    ///
+    /// 临时文件和目标文件在同一目录，保证 rename 不跨文件系统
+    async fn write_atomic(&self, path: &Path, content: &[u8]) -> Result<(), InfrastructureError> {
+        let file_name = path.file_name().ok_or_else(|| {
+            InfrastructureError::FileOperationFailed(format!("Invalid file path {:?}", path))
+        })?;
+        let mut temp_name = std::ffi::OsString::from(".");
+        temp_name.push(file_name);
+        temp_name.push(format!(".{}.tmp", std::process::id()));
+        let temp_path = path.with_file_name(temp_name);
+
+        self.write(&temp_path, content).await?;
+        if let Err(e) = fs::rename(&temp_path, path).await {
+            let _ = fs::remove_file(&temp_path).await;
+            return Err(InfrastructureError::FileOperationFailed(format!(
+                "Failed to move file {:?} to {:?}: {}",
+                temp_path, path, e
+            )));
+        }
+        Ok(())
+    }
+
     /// 删除文件
     async fn delete(&self, path: &Path) -> Result<(), InfrastructureError> {
         fs::remove_file(path).await.map_err(|e| {
@@ -160,6 +183,15 @@ impl FileSystemPort for FileSystemAdapter {
             InfrastructureError::FileOperationFailed("App config directory not set".to_string())
         })
     }
+
+    /// 获取用户图片目录，没有时使用 `~/Pictures`
+    fn get_pictures_dir(&self) -> Result<PathBuf, InfrastructureError> {
+        dirs::picture_dir()
+            .or_else(|| dirs::home_dir().map(|home| home.join("Pictures")))
+            .ok_or_else(|| {
+                InfrastructureError::FileOperationFailed("Pictures directory not found".to_string())
+            })
+    }
 }
 
 /// 缓存条目（内部使用）
@@ -317,6 +349,29 @@ mod tests {
         let _ = adapter.delete(&dest).await;
     }
 
+    #[tokio::test]
+    async fn test_file_system_adapter_write_atomic() {
+        let adapter = FileSystemAdapter::new();
+        let dir = std::env::temp_dir().join("test_aumate_write_atomic");
+        let test_file = dir.join("atomic.txt");
+
+        // 父目录不存在时自动创建，已存在的文件被替换
+        adapter.write_atomic(&test_file, b"first").await.unwrap();
+        adapter.write_atomic(&test_file, b"second").await.unwrap();
+        assert_eq!(adapter.read(&test_file).await.unwrap(), b"second");
+
+        // 不留下临时文件
+        let mut entries = fs::read_dir(&dir).await.unwrap();
+        let mut count = 0;
+        while entries.next_entry().await.unwrap().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 1);
+
+        // 清理
+        adapter.remove_dir(&dir).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_memory_cache_basic() {
         let mut cache = MemoryCacheAdapter::<String>::new();
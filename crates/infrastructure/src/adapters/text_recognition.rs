@@ -0,0 +1,89 @@
+// 文本识别适配器 (TrOCR)
+use async_trait::async_trait;
+use aumate::ocr::{ModelManager, ModelType, OcrEngine, OcrModelVariant};
+use aumate_core_shared::{InfrastructureError, Rectangle};
+use aumate_core_traits::text_recognition::{Image, RecognizedText, TextRecognitionPort};
+use std::sync::{Arc, Mutex};
+
+fn ocr_error(e: impl std::fmt::Display) -> InfrastructureError {
+    InfrastructureError::ExternalError(format!("OCR: {}", e))
+}
+
+/// 基于 TrOCR 的文本识别适配器
+///
+/// 模型需要事先通过模型管理器下载，第一次识别时加载并保留在内存中
+pub struct TrOcrTextRecognitionAdapter {
+    variant: OcrModelVariant,
+    engine: Arc<Mutex<Option<OcrEngine>>>,
+}
+
+impl TrOcrTextRecognitionAdapter {
+    pub fn new() -> Self {
+        Self { variant: OcrModelVariant::default(), engine: Arc::new(Mutex::new(None)) }
+    }
+
+    pub fn with_variant(mut self, variant: OcrModelVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// 模型是否已下载
+    pub fn is_available(&self) -> bool {
+        ModelManager::new()
+            .map(|manager| manager.is_downloaded(ModelType::TrOCR, self.variant.model_id()))
+            .unwrap_or(false)
+    }
+}
+
+impl Default for TrOcrTextRecognitionAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl TextRecognitionPort for TrOcrTextRecognitionAdapter {
+    async fn recognize(&self, image: &Image) -> Result<Vec<RecognizedText>, InfrastructureError> {
+        let rgba = image.to_rgba().map_err(InfrastructureError::ImageProcessingFailed)?;
+        let buffer =
+            image::RgbaImage::from_raw(rgba.width, rgba.height, rgba.data).ok_or_else(|| {
+                InfrastructureError::ImageProcessingFailed("Invalid image buffer".to_string())
+            })?;
+        let dynamic = image::DynamicImage::ImageRgba8(buffer);
+        let engine = self.engine.clone();
+        let variant = self.variant;
+
+        // 模型推理是 CPU/GPU 密集操作，不能阻塞异步运行时
+        tokio::task::spawn_blocking(move || {
+            let mut guard =
+                engine.lock().map_err(|e| ocr_error(format!("engine poisoned: {}", e)))?;
+            let engine = match guard.as_mut() {
+                Some(engine) => engine,
+                None => {
+                    let manager = ModelManager::new().map_err(ocr_error)?;
+                    let mut engine = OcrEngine::new();
+                    engine.load_variant(variant, &manager).map_err(ocr_error)?;
+                    guard.insert(engine)
+                }
+            };
+
+            let result = engine.recognize(&dynamic).map_err(ocr_error)?;
+            result
+                .lines
+                .into_iter()
+                .map(|line| {
+                    let bounds = Rectangle::from_xywh(
+                        line.bounds.x as i32,
+                        line.bounds.y as i32,
+                        line.bounds.width.max(1),
+                        line.bounds.height.max(1),
+                    )
+                    .map_err(ocr_error)?;
+                    Ok(RecognizedText { text: line.text, bounds, confidence: line.confidence })
+                })
+                .collect()
+        })
+        .await
+        .map_err(|e| ocr_error(format!("recognition task failed: {}", e)))?
+    }
+}
@@ -67,6 +67,16 @@ impl UIAutomationPort for UIAutomationAdapter {
 
 // 辅助方法
 impl UIAutomationAdapter {
+    /// 初始化 UI 自动化，已初始化时直接返回
+    ///
+    /// 与 `init_ui_elements` 相同，但可以通过共享引用调用
+    pub async fn init(&self) -> Result<(), InfrastructureError> {
+        log::info!("UIAutomationAdapter: init");
+
+        let mut elements = self.elements.lock().await;
+        elements.init().map_err(|e| InfrastructureError::PlatformOperationFailed(e))
+    }
+
     /// 清除 UI 元素缓存
     pub async fn clear_cache(&self) {
         log::info!("UIAutomationAdapter: clearing cache");
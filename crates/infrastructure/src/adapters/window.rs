@@ -1,22 +1,49 @@
 // 窗口管理适配器
 use async_trait::async_trait;
-use aumate_core_shared::{InfrastructureError, WindowId};
+use aumate_core_shared::{InfrastructureError, Rectangle, WindowId};
 use aumate_core_traits::WindowManagementPort;
 use aumate_core_traits::window::{
-    DragOperation, DrawWindowStyle, ResizeOperation, Window, WindowConfig,
+    DragOperation, DrawWindowStyle, ResizeOperation, ResizeSide, Window, WindowConfig, WindowState,
+};
+use tauri::window::ResizeDirection;
+use tauri::{
+    AppHandle, Manager, PhysicalPosition, PhysicalSize, WebviewUrl, WebviewWindow,
+    WebviewWindowBuilder,
 };
 
 /// 窗口管理适配器
 ///
-/// 封装 Tauri 窗口管理功能
+/// 封装 Tauri 窗口管理功能，需要通过 `with_app_handle` 提供 App Handle
 pub struct WindowManagementAdapter {
-    // 可以存储 Tauri App Handle 或其他状态
+    app_handle: Option<AppHandle>,
 }
 
 impl WindowManagementAdapter {
     pub fn new() -> Self {
         log::info!("Creating WindowManagementAdapter");
-        Self {}
+        Self { app_handle: None }
+    }
+
+    pub fn with_app_handle(mut self, app_handle: AppHandle) -> Self {
+        self.app_handle = Some(app_handle);
+        self
+    }
+
+    fn app_handle(&self) -> Result<&AppHandle, InfrastructureError> {
+        self.app_handle.as_ref().ok_or_else(|| {
+            InfrastructureError::PlatformOperationFailed(
+                "WindowManagementAdapter has no App Handle".to_string(),
+            )
+        })
+    }
+
+    fn webview_window(&self, id: &WindowId) -> Result<WebviewWindow, InfrastructureError> {
+        self.app_handle()?.get_webview_window(id.as_str()).ok_or_else(|| {
+            InfrastructureError::PlatformOperationFailed(format!(
+                "Window not found: {}",
+                id.as_str()
+            ))
+        })
     }
 }
 
@@ -26,15 +53,49 @@ impl Default for WindowManagementAdapter {
     }
 }
 
+fn platform_error(action: &str, error: tauri::Error) -> InfrastructureError {
+    InfrastructureError::PlatformOperationFailed(format!("Failed to {}: {}", action, error))
+}
+
+fn resize_direction(side: ResizeSide) -> ResizeDirection {
+    match side {
+        ResizeSide::Top => ResizeDirection::North,
+        ResizeSide::Bottom => ResizeDirection::South,
+        ResizeSide::Left => ResizeDirection::West,
+        ResizeSide::Right => ResizeDirection::East,
+        ResizeSide::TopLeft => ResizeDirection::NorthWest,
+        ResizeSide::TopRight => ResizeDirection::NorthEast,
+        ResizeSide::BottomLeft => ResizeDirection::SouthWest,
+        ResizeSide::BottomRight => ResizeDirection::SouthEast,
+    }
+}
+
 #[async_trait]
 impl WindowManagementPort for WindowManagementAdapter {
     async fn get_window(&self, id: WindowId) -> Result<Window, InfrastructureError> {
         log::info!("WindowManagementAdapter: getting window {}", id.as_str());
 
-        // TODO: 实现真实的窗口查询
-        Err(InfrastructureError::PlatformOperationFailed(
-            "get_window not yet implemented - needs Tauri App Handle".to_string(),
-        ))
+        let window = self.webview_window(&id)?;
+        let position = window.outer_position().map_err(|e| platform_error("get position", e))?;
+        let size = window.outer_size().map_err(|e| platform_error("get size", e))?;
+        let rect = Rectangle::from_bounds(
+            position.x,
+            position.y,
+            position.x + size.width as i32,
+            position.y + size.height as i32,
+        );
+
+        let state = if window.is_fullscreen().unwrap_or(false) {
+            WindowState::Fullscreen
+        } else if window.is_minimized().unwrap_or(false) {
+            WindowState::Minimized
+        } else if window.is_maximized().unwrap_or(false) {
+            WindowState::Maximized
+        } else {
+            WindowState::Normal
+        };
+
+        Ok(Window { title: window.title().unwrap_or_default(), id, rect, state })
     }
 
     async fn set_always_on_top(
@@ -44,7 +105,9 @@ impl WindowManagementPort for WindowManagementAdapter {
     ) -> Result<(), InfrastructureError> {
         log::info!("WindowManagementAdapter: set_always_on_top {} = {}", id.as_str(), enable);
 
-        Ok(())
+        self.webview_window(&id)?
+            .set_always_on_top(enable)
+            .map_err(|e| platform_error("set always on top", e))
     }
 
     async fn start_drag(&self, operation: DragOperation) -> Result<(), InfrastructureError> {
@@ -53,7 +116,9 @@ impl WindowManagementPort for WindowManagementAdapter {
             operation.window_id.as_str()
         );
 
-        Ok(())
+        self.webview_window(&operation.window_id)?
+            .start_dragging()
+            .map_err(|e| platform_error("start dragging", e))
     }
 
     async fn start_resize(&self, operation: ResizeOperation) -> Result<(), InfrastructureError> {
@@ -62,7 +127,9 @@ impl WindowManagementPort for WindowManagementAdapter {
             operation.window_id.as_str()
         );
 
-        Ok(())
+        self.webview_window(&operation.window_id)?
+            .start_resize_dragging(resize_direction(operation.side))
+            .map_err(|e| platform_error("start resizing", e))
     }
 
     async fn set_draw_style(
@@ -87,17 +154,108 @@ impl WindowManagementPort for WindowManagementAdapter {
         &self,
         config: WindowConfig,
     ) -> Result<WindowId, InfrastructureError> {
-        log::info!("WindowManagementAdapter: creating window {}", config.title);
+        log::info!("WindowManagementAdapter: creating window {} ({})", config.label, config.title);
+
+        let app_handle = self.app_handle()?;
+        let id = WindowId::new(config.label.clone());
 
-        // TODO: 实现真实的窗口创建
-        // 暂时返回一个占位符 ID
-        Ok(WindowId::new("placeholder".to_string()))
+        // 窗口已存在时直接显示
+        if let Some(window) = app_handle.get_webview_window(&config.label) {
+            window.show().map_err(|e| platform_error("show window", e))?;
+            return Ok(id);
+        }
+
+        // 先隐藏创建，按物理像素设置位置和大小后再显示，避免闪烁
+        let window = WebviewWindowBuilder::new(
+            app_handle,
+            &config.label,
+            WebviewUrl::App(config.url.into()),
+        )
+        .title(&config.title)
+        .resizable(config.resizable)
+        .decorations(config.decorations)
+        .always_on_top(config.always_on_top)
+        .transparent(config.transparent)
+        .skip_taskbar(config.skip_taskbar)
+        .shadow(config.decorations)
+        .visible(false)
+        .build()
+        .map_err(|e| platform_error("create window", e))?;
+
+        window
+            .set_size(PhysicalSize::new(config.width.max(1), config.height.max(1)))
+            .map_err(|e| platform_error("set size", e))?;
+        window
+            .set_position(PhysicalPosition::new(config.x, config.y))
+            .map_err(|e| platform_error("set position", e))?;
+        window.show().map_err(|e| platform_error("show window", e))?;
+
+        Ok(id)
     }
 
     async fn close_window(&self, id: WindowId) -> Result<(), InfrastructureError> {
         log::info!("WindowManagementAdapter: closing window {}", id.as_str());
 
-        Ok(())
+        self.webview_window(&id)?.close().map_err(|e| platform_error("close window", e))
+    }
+
+    async fn set_window_bounds(
+        &self,
+        id: WindowId,
+        bounds: Rectangle,
+    ) -> Result<(), InfrastructureError> {
+        let window = self.webview_window(&id)?;
+        window
+            .set_position(PhysicalPosition::new(bounds.min_x(), bounds.min_y()))
+            .map_err(|e| platform_error("set position", e))?;
+        window
+            .set_size(PhysicalSize::new(bounds.width(), bounds.height()))
+            .map_err(|e| platform_error("set size", e))
+    }
+
+    async fn set_click_through(
+        &self,
+        id: WindowId,
+        enable: bool,
+    ) -> Result<(), InfrastructureError> {
+        log::info!("WindowManagementAdapter: set_click_through {} = {}", id.as_str(), enable);
+
+        self.webview_window(&id)?
+            .set_ignore_cursor_events(enable)
+            .map_err(|e| platform_error("set click-through", e))
+    }
+
+    async fn set_window_visible(
+        &self,
+        id: WindowId,
+        visible: bool,
+    ) -> Result<(), InfrastructureError> {
+        let window = self.webview_window(&id)?;
+        if visible {
+            window.show().map_err(|e| platform_error("show window", e))
+        } else {
+            window.hide().map_err(|e| platform_error("hide window", e))
+        }
+    }
+
+    async fn get_work_areas(&self) -> Result<Vec<Rectangle>, InfrastructureError> {
+        let monitors = self
+            .app_handle()?
+            .available_monitors()
+            .map_err(|e| platform_error("get monitors", e))?;
+
+        Ok(monitors
+            .iter()
+            .map(|monitor| {
+                let area = monitor.work_area();
+                Rectangle::from_bounds(
+                    area.position.x,
+                    area.position.y,
+                    area.position.x + area.size.width as i32,
+                    area.position.y + area.size.height as i32,
+                )
+            })
+            .collect())
     }
 }
 
@@ -0,0 +1,299 @@
+#![cfg(target_os = "windows")]
+
+// HDR 显示器的浮点捕获
+//
+// 通过 DXGI 桌面复制以 `R16G16B16A16_FLOAT` (scRGB) 格式捕获，
+// 峰值亮度来自 `IDXGIOutput6::GetDesc1`，SDR 白来自 DisplayConfig
+
+use aumate_core_domain::hdr::{HdrImage, HdrInfo, SCRGB_WHITE_NITS};
+use aumate_core_shared::InfrastructureError;
+use windows::Win32::Devices::Display::{
+    DISPLAYCONFIG_DEVICE_INFO_GET_SDR_WHITE_LEVEL, DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
+    DISPLAYCONFIG_DEVICE_INFO_HEADER, DISPLAYCONFIG_MODE_INFO, DISPLAYCONFIG_PATH_INFO,
+    DISPLAYCONFIG_SDR_WHITE_LEVEL, DISPLAYCONFIG_SOURCE_DEVICE_NAME, DisplayConfigGetDeviceInfo,
+    GetDisplayConfigBufferSizes, QDC_ONLY_ACTIVE_PATHS, QueryDisplayConfig,
+};
+use windows::Win32::Foundation::{ERROR_SUCCESS, HMODULE};
+use windows::Win32::Graphics::Direct3D::D3D_DRIVER_TYPE_UNKNOWN;
+use windows::Win32::Graphics::Direct3D11::{
+    D3D11_CPU_ACCESS_READ, D3D11_CREATE_DEVICE_BGRA_SUPPORT, D3D11_MAP_READ,
+    D3D11_MAPPED_SUBRESOURCE, D3D11_SDK_VERSION, D3D11_TEXTURE2D_DESC, D3D11_USAGE_STAGING,
+    D3D11CreateDevice, ID3D11Device, ID3D11DeviceContext, ID3D11Texture2D,
+};
+use windows::Win32::Graphics::Dxgi::Common::{
+    DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020, DXGI_FORMAT_R16G16B16A16_FLOAT,
+    DXGI_MODE_ROTATION_IDENTITY, DXGI_MODE_ROTATION_UNSPECIFIED,
+};
+use windows::Win32::Graphics::Dxgi::{
+    CreateDXGIFactory1, DXGI_ERROR_WAIT_TIMEOUT, DXGI_OUTDUPL_FRAME_INFO, DXGI_OUTPUT_DESC1,
+    IDXGIAdapter1, IDXGIFactory1, IDXGIOutput5, IDXGIOutput6, IDXGIResource,
+};
+use windows::core::Interface;
+
+/// 等待桌面复制返回第一帧的时间
+const ACQUIRE_TIMEOUT_MS: u32 = 500;
+
+/// 最多等待的帧数，第一帧可能还没有桌面图像
+const MAX_ACQUIRE_ATTEMPTS: u32 = 4;
+
+fn hdr_error(message: impl std::fmt::Display) -> InfrastructureError {
+    InfrastructureError::CaptureFailed(format!("HDR capture: {}", message))
+}
+
+/// 与 `HMONITOR` 对应的 DXGI 输出及其所属的适配器
+struct Output {
+    adapter: IDXGIAdapter1,
+    output: IDXGIOutput6,
+    desc: DXGI_OUTPUT_DESC1,
+}
+
+fn find_output(monitor: u32) -> Result<Output, InfrastructureError> {
+    let factory: IDXGIFactory1 = unsafe { CreateDXGIFactory1() }.map_err(hdr_error)?;
+    let mut adapter_index = 0;
+    while let Ok(adapter) = unsafe { factory.EnumAdapters1(adapter_index) } {
+        adapter_index += 1;
+        let mut output_index = 0;
+        while let Ok(output) = unsafe { adapter.EnumOutputs(output_index) } {
+            output_index += 1;
+            let Ok(output) = output.cast::<IDXGIOutput6>() else {
+                continue;
+            };
+            let desc = unsafe { output.GetDesc1() }.map_err(hdr_error)?;
+            // xcap 的监视器 ID 就是 HMONITOR
+            if desc.Monitor.0 as u32 == monitor {
+                return Ok(Output { adapter: adapter.clone(), output, desc });
+            }
+        }
+    }
+    Err(hdr_error(format!("no DXGI output for monitor {}", monitor)))
+}
+
+/// 监视器的 HDR 信息，找不到对应的 DXGI 输出时返回 None
+pub fn hdr_info(monitor: u32) -> Option<HdrInfo> {
+    let desc = match find_output(monitor) {
+        Ok(output) => output.desc,
+        Err(e) => {
+            log::debug!("{}", e);
+            return None;
+        }
+    };
+    if desc.ColorSpace != DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020 {
+        return Some(HdrInfo::default());
+    }
+
+    let sdr_white_level = sdr_white_level(&desc.DeviceName).unwrap_or(SCRGB_WHITE_NITS);
+    Some(HdrInfo {
+        is_hdr_enabled: true,
+        peak_luminance: desc.MaxLuminance,
+        min_luminance: desc.MinLuminance,
+        max_full_frame_luminance: desc.MaxFullFrameLuminance,
+        sdr_white_level,
+    })
+}
+
+/// GDI 设备名为 `device_name` 的显示源当前的 SDR 白 (nits)
+fn sdr_white_level(device_name: &[u16; 32]) -> Option<f32> {
+    let mut path_count = 0;
+    let mut mode_count = 0;
+    let status = unsafe {
+        GetDisplayConfigBufferSizes(QDC_ONLY_ACTIVE_PATHS, &mut path_count, &mut mode_count)
+    };
+    if status != ERROR_SUCCESS {
+        return None;
+    }
+    let mut paths = vec![DISPLAYCONFIG_PATH_INFO::default(); path_count as usize];
+    let mut modes = vec![DISPLAYCONFIG_MODE_INFO::default(); mode_count as usize];
+    let status = unsafe {
+        QueryDisplayConfig(
+            QDC_ONLY_ACTIVE_PATHS,
+            &mut path_count,
+            paths.as_mut_ptr(),
+            &mut mode_count,
+            modes.as_mut_ptr(),
+            None,
+        )
+    };
+    if status != ERROR_SUCCESS {
+        return None;
+    }
+
+    paths.iter().take(path_count as usize).find_map(|path| {
+        let mut source = DISPLAYCONFIG_SOURCE_DEVICE_NAME {
+            header: DISPLAYCONFIG_DEVICE_INFO_HEADER {
+                r#type: DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
+                size: size_of::<DISPLAYCONFIG_SOURCE_DEVICE_NAME>() as u32,
+                adapterId: path.sourceInfo.adapterId,
+                id: path.sourceInfo.id,
+            },
+            ..Default::default()
+        };
+        if unsafe { DisplayConfigGetDeviceInfo(&mut source.header) } != 0
+            || &source.viewGdiDeviceName != device_name
+        {
+            return None;
+        }
+
+        let mut white = DISPLAYCONFIG_SDR_WHITE_LEVEL {
+            header: DISPLAYCONFIG_DEVICE_INFO_HEADER {
+                r#type: DISPLAYCONFIG_DEVICE_INFO_GET_SDR_WHITE_LEVEL,
+                size: size_of::<DISPLAYCONFIG_SDR_WHITE_LEVEL>() as u32,
+                adapterId: path.targetInfo.adapterId,
+                id: path.targetInfo.id,
+            },
+            SDRWhiteLevel: 0,
+        };
+        if unsafe { DisplayConfigGetDeviceInfo(&mut white.header) } != 0 {
+            return None;
+        }
+        // SDRWhiteLevel 以 1000 表示 80 nits
+        Some(white.SDRWhiteLevel as f32 / 1000.0 * SCRGB_WHITE_NITS)
+    })
+}
+
+/// 以 scRGB 浮点格式捕获整个监视器
+///
+/// 旋转的显示器返回 `PlatformNotSupported`，由调用方改用 8 位捕获
+pub fn capture(monitor: u32) -> Result<HdrImage, InfrastructureError> {
+    let Output { adapter, output, desc } = find_output(monitor)?;
+    if desc.Rotation != DXGI_MODE_ROTATION_IDENTITY
+        && desc.Rotation != DXGI_MODE_ROTATION_UNSPECIFIED
+    {
+        log::debug!("HDR capture: rotated output {:?} is not supported", desc.Rotation);
+        return Err(InfrastructureError::PlatformNotSupported);
+    }
+
+    let mut device: Option<ID3D11Device> = None;
+    let mut context: Option<ID3D11DeviceContext> = None;
+    unsafe {
+        D3D11CreateDevice(
+            &adapter,
+            D3D_DRIVER_TYPE_UNKNOWN,
+            HMODULE::default(),
+            D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+            None,
+            D3D11_SDK_VERSION,
+            Some(&mut device),
+            None,
+            Some(&mut context),
+        )
+    }
+    .map_err(hdr_error)?;
+    let device = device.ok_or_else(|| hdr_error("no D3D11 device"))?;
+    let context = context.ok_or_else(|| hdr_error("no D3D11 device context"))?;
+
+    let output: IDXGIOutput5 = output.cast().map_err(hdr_error)?;
+    let duplication =
+        unsafe { output.DuplicateOutput1(&device, 0, &[DXGI_FORMAT_R16G16B16A16_FLOAT]) }
+            .map_err(hdr_error)?;
+
+    // 新建的桌面复制第一帧通常就带有完整桌面，少数驱动要多等几帧
+    let mut texture = None;
+    for _ in 0..MAX_ACQUIRE_ATTEMPTS {
+        let mut frame_info = DXGI_OUTDUPL_FRAME_INFO::default();
+        let mut resource: Option<IDXGIResource> = None;
+        match unsafe {
+            duplication.AcquireNextFrame(ACQUIRE_TIMEOUT_MS, &mut frame_info, &mut resource)
+        } {
+            Ok(()) => {}
+            // 桌面没有变化时超时，继续等待
+            Err(e) if e.code() == DXGI_ERROR_WAIT_TIMEOUT => continue,
+            Err(e) => return Err(hdr_error(e)),
+        }
+        if frame_info.LastPresentTime != 0 {
+            let frame: ID3D11Texture2D = resource
+                .ok_or_else(|| hdr_error("no desktop resource"))?
+                .cast()
+                .map_err(hdr_error)?;
+            texture = Some(copy_to_staging(&device, &context, &frame)?);
+        }
+        unsafe { duplication.ReleaseFrame() }.map_err(hdr_error)?;
+        if texture.is_some() {
+            break;
+        }
+    }
+    let (staging, desc) = texture.ok_or_else(|| hdr_error("no desktop frame"))?;
+
+    read_pixels(&context, &staging, &desc)
+}
+
+/// 复制到 CPU 可读的纹理，帧释放后仍可读取
+fn copy_to_staging(
+    device: &ID3D11Device,
+    context: &ID3D11DeviceContext,
+    frame: &ID3D11Texture2D,
+) -> Result<(ID3D11Texture2D, D3D11_TEXTURE2D_DESC), InfrastructureError> {
+    let mut desc = D3D11_TEXTURE2D_DESC::default();
+    unsafe { frame.GetDesc(&mut desc) };
+    if desc.Format != DXGI_FORMAT_R16G16B16A16_FLOAT {
+        return Err(hdr_error(format!("unexpected desktop format {:?}", desc.Format)));
+    }
+    desc.Usage = D3D11_USAGE_STAGING;
+    desc.BindFlags = 0;
+    desc.CPUAccessFlags = D3D11_CPU_ACCESS_READ.0 as u32;
+    desc.MiscFlags = 0;
+
+    let mut staging = None;
+    unsafe { device.CreateTexture2D(&desc, None, Some(&mut staging)) }.map_err(hdr_error)?;
+    let staging = staging.ok_or_else(|| hdr_error("no staging texture"))?;
+    unsafe { context.CopyResource(&staging, frame) };
+    Ok((staging, desc))
+}
+
+fn read_pixels(
+    context: &ID3D11DeviceContext,
+    staging: &ID3D11Texture2D,
+    desc: &D3D11_TEXTURE2D_DESC,
+) -> Result<HdrImage, InfrastructureError> {
+    let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+    unsafe { context.Map(staging, 0, D3D11_MAP_READ, 0, Some(&mut mapped)) }.map_err(hdr_error)?;
+
+    let (width, height) = (desc.Width as usize, desc.Height as usize);
+    let mut data = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        // 每行 width * 4 个 f16，行间距为 RowPitch 字节
+        let row = unsafe {
+            std::slice::from_raw_parts(
+                (mapped.pData as *const u8).add(y * mapped.RowPitch as usize) as *const u16,
+                width * 4,
+            )
+        };
+        data.extend(row.iter().map(|&bits| f16_to_f32(bits)));
+    }
+    unsafe { context.Unmap(staging, 0) };
+
+    HdrImage::new(data, desc.Width, desc.Height).map_err(hdr_error)
+}
+
+/// IEEE 754 半精度转单精度
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = ((bits & 0x8000) as u32) << 16;
+    let exponent = ((bits >> 10) & 0x1f) as u32;
+    let mantissa = (bits & 0x03ff) as u32;
+    let value = match exponent {
+        0 => {
+            // 非规格化数
+            let magnitude = mantissa as f32 / (1 << 24) as f32;
+            return if sign != 0 { -magnitude } else { magnitude };
+        }
+        0x1f => sign | 0x7f80_0000 | (mantissa << 13),
+        _ => sign | ((exponent + 127 - 15) << 23) | (mantissa << 13),
+    };
+    f32::from_bits(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f16_to_f32() {
+        assert_eq!(f16_to_f32(0x0000), 0.0);
+        assert_eq!(f16_to_f32(0x3c00), 1.0);
+        assert_eq!(f16_to_f32(0xc000), -2.0);
+        assert_eq!(f16_to_f32(0x4a40), 12.5);
+        assert_eq!(f16_to_f32(0x7bff), 65504.0);
+        assert_eq!(f16_to_f32(0x0001), 2f32.powi(-24));
+        assert!(f16_to_f32(0x7c00).is_infinite());
+        assert!(f16_to_f32(0x7e00).is_nan());
+    }
+}
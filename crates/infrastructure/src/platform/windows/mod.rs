@@ -1,6 +1,7 @@
 // Windows 平台特定代码
 
 pub mod element_scanner;
+pub mod hdr_capture;
 pub mod ui_automation;
 
 pub use ui_automation::*;
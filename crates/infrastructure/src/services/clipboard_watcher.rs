@@ -0,0 +1,321 @@
+/// 剪贴板监听服务
+///
+/// 收到剪贴板变化通知 (不支持通知时定时轮询) 后读取剪贴板，内容变化时记录到剪贴板历史。
+/// 来自密码管理器的内容 (按前台应用或敏感格式标记识别) 不会被记录
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use aumate_core_domain::settings::ClipboardHistorySettings;
+use aumate_core_shared::InfrastructureError;
+use aumate_core_traits::clipboard::{ClipboardChangeStream, ClipboardPort, ClipboardType};
+use aumate_core_traits::clipboard_history::{ClipboardHistoryEntry, ClipboardHistoryPort};
+use aumate_core_traits::window::WindowListPort;
+
+use crate::adapters::clipboard_history::clipboard_content_hash;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct ClipboardWatcherService {
+    clipboard: Arc<dyn ClipboardPort>,
+    history: Arc<dyn ClipboardHistoryPort>,
+    windows: Option<Arc<dyn WindowListPort>>,
+    settings: RwLock<ClipboardHistorySettings>,
+    poll_interval: Duration,
+    /// 上次看到的内容哈希，用于判断剪贴板是否变化
+    last_hash: Mutex<Option<String>>,
+    running: AtomicBool,
+}
+
+impl ClipboardWatcherService {
+    pub fn new(clipboard: Arc<dyn ClipboardPort>, history: Arc<dyn ClipboardHistoryPort>) -> Self {
+        Self {
+            clipboard,
+            history,
+            windows: None,
+            settings: RwLock::new(ClipboardHistorySettings::default()),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            last_hash: Mutex::new(None),
+            running: AtomicBool::new(false),
+        }
+    }
+
+    /// 用前台窗口识别内容来源
+    pub fn with_window_list(mut self, windows: Arc<dyn WindowListPort>) -> Self {
+        self.windows = Some(windows);
+        self
+    }
+
+    pub fn with_settings(self, settings: ClipboardHistorySettings) -> Self {
+        self.update_settings(settings);
+        self
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// 更新设置，下次轮询时生效
+    pub fn update_settings(&self, settings: ClipboardHistorySettings) {
+        *self.settings.write().unwrap_or_else(|e| e.into_inner()) = settings;
+    }
+
+    fn settings(&self) -> ClipboardHistorySettings {
+        self.settings.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// 检查一次剪贴板，有新内容时记录并返回对应条目
+    pub async fn poll_once(&self) -> Result<Option<ClipboardHistoryEntry>, InfrastructureError> {
+        let settings = self.settings();
+        if !settings.enabled {
+            return Ok(None);
+        }
+
+        // 剪贴板为空或暂时无法读取时跳过
+        let Ok(content) = self.clipboard.read().await else {
+            return Ok(None);
+        };
+        let Some(hash) = clipboard_content_hash(&content)? else {
+            return Ok(None);
+        };
+        {
+            let mut last_hash = self.last_hash.lock().unwrap_or_else(|e| e.into_inner());
+            if last_hash.as_deref() == Some(hash.as_str()) {
+                return Ok(None);
+            }
+            *last_hash = Some(hash);
+        }
+
+        let exclusions = settings.exclusions();
+        let formats: Vec<String> = self
+            .clipboard
+            .get_available_types()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|format| match format {
+                ClipboardType::Custom(name) => Some(name),
+                _ => None,
+            })
+            .collect();
+        if exclusions.has_sensitive_format(&formats) {
+            log::debug!("ClipboardWatcherService: skipping content marked as sensitive");
+            return Ok(None);
+        }
+
+        let source = match &self.windows {
+            Some(windows) => windows.get_active_window().await.ok().flatten(),
+            None => None,
+        };
+        let excluded = source.as_ref().filter(|window| {
+            exclusions.is_excluded_app(&window.app_name)
+                || exclusions.is_excluded_app(&window.process_name)
+        });
+        if let Some(window) = excluded {
+            log::debug!("ClipboardWatcherService: skipping content from {}", window.app_name);
+            return Ok(None);
+        }
+
+        let source_app = source.map(|window| window.app_name).filter(|name| !name.is_empty());
+        let entry = self.history.record(&content, source_app.as_deref()).await?;
+        self.history.apply_retention(&settings.retention()).await?;
+        Ok(entry)
+    }
+
+    /// 持续监听直到调用 [`stop`](Self::stop)，重复调用时直接返回
+    pub async fn run(self: Arc<Self>) {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let mut changes = match self.clipboard.watch().await {
+            Ok(changes) => Some(changes),
+            Err(e) => {
+                log::info!(
+                    "ClipboardWatcherService: change notifications unavailable ({}), polling",
+                    e
+                );
+                None
+            }
+        };
+        log::info!("ClipboardWatcherService: started");
+
+        if let Err(e) = self.history.apply_retention(&self.settings().retention()).await {
+            log::warn!("ClipboardWatcherService: failed to apply retention: {}", e);
+        }
+        while self.running.load(Ordering::SeqCst) {
+            if let Err(e) = self.poll_once().await {
+                log::warn!("ClipboardWatcherService: failed to record clipboard: {}", e);
+            }
+            self.wait_for_change(&mut changes).await;
+        }
+        log::info!("ClipboardWatcherService: stopped");
+    }
+
+    /// 等到剪贴板可能已变化或服务已停止
+    async fn wait_for_change(&self, changes: &mut Option<Box<dyn ClipboardChangeStream>>) {
+        while self.running.load(Ordering::SeqCst) {
+            let Some(stream) = changes.as_mut() else {
+                tokio::time::sleep(self.poll_interval).await;
+                return;
+            };
+            // 超时只用于定期检查是否已停止
+            match tokio::time::timeout(self.poll_interval, stream.next()).await {
+                Ok(Some(_)) => return,
+                Ok(None) => {
+                    log::warn!("ClipboardWatcherService: change stream ended, polling");
+                    *changes = None;
+                }
+                Err(_) => {}
+            }
+        }
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::clipboard_history::SqliteClipboardHistoryAdapter;
+    use crate::adapters::memory_clipboard::InMemoryClipboardAdapter;
+    use async_trait::async_trait;
+    use aumate_core_shared::Rectangle;
+    use aumate_core_traits::clipboard::ClipboardContent;
+    use aumate_core_traits::clipboard_history::ClipboardHistoryQuery;
+    use aumate_core_traits::window::WindowInfo;
+
+    struct MockWindows {
+        app_name: Mutex<String>,
+    }
+
+    #[async_trait]
+    impl WindowListPort for MockWindows {
+        async fn get_window_list(&self) -> Result<Vec<WindowInfo>, InfrastructureError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_active_window(&self) -> Result<Option<WindowInfo>, InfrastructureError> {
+            let app_name = self.app_name.lock().unwrap().clone();
+            Ok(Some(WindowInfo {
+                id: "1".to_string(),
+                window_id: 1,
+                title: String::new(),
+                process_name: format!("{}.exe", app_name),
+                app_name,
+                process_path: String::new(),
+                icon: None,
+                bounds: Rectangle::new(0, 0, 100, 100).unwrap(),
+            }))
+        }
+
+        async fn switch_to_window(&self, _window_id: u32) -> Result<(), InfrastructureError> {
+            Ok(())
+        }
+
+        async fn close_desktop_window(&self, _window_id: u32) -> Result<(), InfrastructureError> {
+            Ok(())
+        }
+    }
+
+    fn watcher() -> (
+        Arc<InMemoryClipboardAdapter>,
+        Arc<SqliteClipboardHistoryAdapter>,
+        Arc<MockWindows>,
+        ClipboardWatcherService,
+    ) {
+        let clipboard = Arc::new(InMemoryClipboardAdapter::new());
+        let history = Arc::new(SqliteClipboardHistoryAdapter::in_memory().unwrap());
+        let windows = Arc::new(MockWindows { app_name: Mutex::new("Editor".to_string()) });
+        let watcher = ClipboardWatcherService::new(clipboard.clone(), history.clone())
+            .with_window_list(windows.clone());
+        (clipboard, history, windows, watcher)
+    }
+
+    #[tokio::test]
+    async fn test_records_changes_once() {
+        let (clipboard, history, _, watcher) = watcher();
+        assert!(watcher.poll_once().await.unwrap().is_none());
+
+        clipboard.set(ClipboardContent::text("first"), Vec::new());
+        let entry = watcher.poll_once().await.unwrap().unwrap();
+        assert_eq!(entry.source_app.as_deref(), Some("Editor"));
+        assert!(watcher.poll_once().await.unwrap().is_none());
+
+        clipboard.set(ClipboardContent::text("second"), Vec::new());
+        watcher.poll_once().await.unwrap().unwrap();
+        // 再次复制旧内容时更新已有条目
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        clipboard.set(ClipboardContent::text("first"), Vec::new());
+        let again = watcher.poll_once().await.unwrap().unwrap();
+        assert_eq!((again.id, again.use_count), (entry.id, 2));
+
+        let entries = history.list(&ClipboardHistoryQuery::default()).await.unwrap();
+        assert_eq!(
+            entries.iter().map(|e| e.preview.as_str()).collect::<Vec<_>>(),
+            vec!["first", "second"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_skips_password_managers() {
+        let (clipboard, history, windows, watcher) = watcher();
+
+        let marker = ClipboardType::Custom("x-kde-passwordManagerHint".to_string());
+        clipboard.set(ClipboardContent::text("hunter2"), vec![marker]);
+        assert!(watcher.poll_once().await.unwrap().is_none());
+
+        *windows.app_name.lock().unwrap() = "KeePassXC".to_string();
+        clipboard.set(ClipboardContent::text("correct horse"), Vec::new());
+        assert!(watcher.poll_once().await.unwrap().is_none());
+
+        assert!(history.list(&ClipboardHistoryQuery::default()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_applies_settings() {
+        let (clipboard, history, _, watcher) = watcher();
+        let watcher = watcher
+            .with_settings(ClipboardHistorySettings { max_entries: 2, ..Default::default() });
+
+        for text in ["a", "b", "c"] {
+            clipboard.set(ClipboardContent::text(text), Vec::new());
+            watcher.poll_once().await.unwrap();
+        }
+        assert_eq!(history.list(&ClipboardHistoryQuery::default()).await.unwrap().len(), 2);
+
+        watcher.update_settings(ClipboardHistorySettings { enabled: false, ..Default::default() });
+        clipboard.set(ClipboardContent::text("d"), Vec::new());
+        assert!(watcher.poll_once().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_run_records_on_change() {
+        let (clipboard, history, _, watcher) = watcher();
+        let watcher = Arc::new(watcher.with_poll_interval(Duration::from_secs(60)));
+        let task = tokio::spawn(watcher.clone().run());
+
+        // 轮询间隔很长，只有变化通知能触发记录
+        let mut entries = Vec::new();
+        for _ in 0..100 {
+            clipboard.set(ClipboardContent::text("notified"), Vec::new());
+            entries = history.list(&ClipboardHistoryQuery::default()).await.unwrap();
+            if !entries.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].preview, "notified");
+
+        watcher.stop();
+        task.abort();
+    }
+}
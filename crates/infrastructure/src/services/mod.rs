@@ -2,6 +2,7 @@
 //
 // 这些服务被适配器使用，封装了实际的实现逻辑
 
+pub mod clipboard_watcher;
 pub mod device_events;
 pub mod input_simulation;
 pub mod key_listener;
@@ -9,6 +10,7 @@ pub mod mouse_listener;
 pub mod page;
 pub mod scroll;
 
+pub use clipboard_watcher::ClipboardWatcherService;
 pub use device_events::DeviceEventHandlerService;
 pub use input_simulation::EnigoManager;
 pub use key_listener::ListenKeyService;
@@ -0,0 +1,31 @@
+//! Screenshot annotation rendering
+//!
+//! Draws arrows, rectangles, ellipses, pen strokes, text, numbered steps,
+//! mosaic and blur onto an image, so annotated evidence can be produced
+//! without the screenshot editor's webview. [`Redactor`] builds on it to hide
+//! sensitive regions before screenshots are shared.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use aumate::annotate::{Annotation, AnnotationDocument, Point, Shape, render_annotations};
+//!
+//! let mut image = image::open("screenshot.png").unwrap().to_rgba8();
+//! let document = AnnotationDocument::new(vec![Annotation::new(Shape::Arrow {
+//!     from: Point::new(40.0, 40.0),
+//!     to: Point::new(200.0, 120.0),
+//! })]);
+//! render_annotations(&mut image, &document).unwrap();
+//! image.save("annotated.png").unwrap();
+//! ```
+
+mod model;
+mod redact;
+mod render;
+
+pub use model::{Annotation, AnnotationDocument, Color, Point, Shape, Style};
+pub use redact::{
+    RedactedItem, RedactionMethod, RedactionReason, RedactionReport, Redactor, Region,
+    SensitiveKind, SensitiveMatch, TextLine, detect_sensitive, mask_text,
+};
+pub use render::{AnnotationRenderer, render_annotations};
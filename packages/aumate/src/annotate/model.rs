@@ -0,0 +1,259 @@
+//! Annotation document types
+//!
+//! The JSON layout matches `aumate_core_domain::annotation`, so documents
+//! produced by the screenshot editor can be rendered here unchanged.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// RGBA color, serialized as `"#RRGGBB"` or `"#RRGGBBAA"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::rgba(r, g, b, 255)
+    }
+
+    /// Parse `#RGB`, `#RRGGBB` or `#RRGGBBAA` (the leading `#` is optional)
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim().trim_start_matches('#');
+        if !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        match hex.len() {
+            3 => {
+                let short = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|v| v * 17);
+                Some(Self::rgb(short(0)?, short(1)?, short(2)?))
+            }
+            6 => Some(Self::rgb(channel(0)?, channel(2)?, channel(4)?)),
+            8 => Some(Self::rgba(channel(0)?, channel(2)?, channel(4)?, channel(6)?)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.a == 255 {
+            write!(f, "#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+        } else {
+            write!(f, "#{:02X}{:02X}{:02X}{:02X}", self.r, self.g, self.b, self.a)
+        }
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::from_hex(&value).ok_or_else(|| format!("Invalid color: {}", value))
+    }
+}
+
+impl From<Color> for String {
+    fn from(color: Color) -> Self {
+        color.to_string()
+    }
+}
+
+/// A point in image pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point {
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+}
+
+/// How a shape is stroked and filled
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Style {
+    /// Outline color; also the text color and the step badge color
+    pub stroke: Color,
+    pub stroke_width: f32,
+    /// Fill color for closed shapes
+    pub fill: Option<Color>,
+    /// Opacity applied to the whole shape, 0.0-1.0
+    pub opacity: f32,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self { stroke: Color::rgb(0xE0, 0x31, 0x31), stroke_width: 4.0, fill: None, opacity: 1.0 }
+    }
+}
+
+/// Annotation geometry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Shape {
+    Rectangle {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        #[serde(default)]
+        corner_radius: f32,
+    },
+    Ellipse {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    },
+    Line {
+        from: Point,
+        to: Point,
+    },
+    Arrow {
+        from: Point,
+        to: Point,
+    },
+    /// Pen stroke through the given points
+    Freehand {
+        points: Vec<Point>,
+    },
+    /// Text whose top-left corner is at (x, y); `\n` starts a new line
+    Text {
+        x: f32,
+        y: f32,
+        content: String,
+        font_size: f32,
+        #[serde(default)]
+        background: Option<Color>,
+    },
+    /// Numbered step badge centered at (x, y)
+    Step {
+        x: f32,
+        y: f32,
+        number: u32,
+        radius: f32,
+    },
+    /// Pixelate a region with square blocks
+    Mosaic {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        block_size: u32,
+    },
+    /// Gaussian-blur a region
+    Blur {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        radius: f32,
+    },
+}
+
+/// A single annotation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    #[serde(default)]
+    pub id: String,
+    /// Higher values are drawn on top; ties keep document order
+    #[serde(default)]
+    pub z_index: i32,
+    #[serde(default)]
+    pub style: Style,
+    #[serde(flatten)]
+    pub shape: Shape,
+}
+
+impl Annotation {
+    pub fn new(shape: Shape) -> Self {
+        Self { id: String::new(), z_index: 0, style: Style::default(), shape }
+    }
+
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+}
+
+/// A set of annotations to draw onto an image
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnnotationDocument {
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+}
+
+impl AnnotationDocument {
+    pub fn new(annotations: Vec<Annotation>) -> Self {
+        Self { annotations }
+    }
+
+    /// Parse a document from JSON
+    pub fn from_json(json: &str) -> crate::error::Result<Self> {
+        serde_json::from_str(json).map_err(|e| {
+            crate::error::AumateError::Other(format!("Invalid annotation document: {}", e))
+        })
+    }
+
+    /// Annotations in drawing order (bottom first)
+    pub fn render_order(&self) -> Vec<&Annotation> {
+        let mut ordered: Vec<&Annotation> = self.annotations.iter().collect();
+        ordered.sort_by_key(|annotation| annotation.z_index);
+        ordered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_hex() {
+        assert_eq!(Color::from_hex("#f00"), Some(Color::rgb(255, 0, 0)));
+        assert_eq!(Color::from_hex("00FF0080"), Some(Color::rgba(0, 255, 0, 128)));
+        assert_eq!(Color::from_hex("#12345"), None);
+        assert_eq!(Color::rgba(1, 2, 3, 4).to_string(), "#01020304");
+        assert_eq!(Color::rgb(1, 2, 3).to_string(), "#010203");
+    }
+
+    #[test]
+    fn test_document_json() {
+        let json = r##"{
+            "annotations": [
+                {"type": "arrow", "from": {"x": 1, "y": 2}, "to": {"x": 30, "y": 40}, "z_index": 2},
+                {"type": "rectangle", "x": 0, "y": 0, "width": 10, "height": 10,
+                 "style": {"stroke": "#00ff00", "fill": "#0000FF80"}}
+            ]
+        }"##;
+        let document = AnnotationDocument::from_json(json).unwrap();
+        let rect = &document.annotations[1];
+        assert_eq!(rect.style.stroke, Color::rgb(0, 255, 0));
+        assert_eq!(rect.style.fill, Some(Color::rgba(0, 0, 255, 128)));
+        assert_eq!(rect.style.stroke_width, Style::default().stroke_width);
+
+        let order: Vec<_> = document.render_order().iter().map(|a| a.z_index).collect();
+        assert_eq!(order, [0, 2]);
+
+        let round_trip: AnnotationDocument =
+            serde_json::from_str(&serde_json::to_string(&document).unwrap()).unwrap();
+        assert_eq!(round_trip, document);
+    }
+}
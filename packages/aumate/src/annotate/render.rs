@@ -0,0 +1,695 @@
+//! Annotation rasterizer built on tiny-skia (shapes) and fontdue (text)
+
+use super::model::{Annotation, AnnotationDocument, Color, Point, Shape, Style};
+use crate::error::{AumateError, Result};
+use fontdue::{Font, FontSettings};
+use image::{Rgba, RgbaImage};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+use tiny_skia::{
+    ColorU8, FillRule, LineCap, LineJoin, Paint, Path, PathBuilder, Pixmap, PixmapPaint,
+    PremultipliedColorU8, Rect, Stroke, Transform,
+};
+
+/// Fonts loaded by `AnnotationRenderer::with_system_fonts`, in fallback order.
+/// Latin fonts come first, CJK fonts cover characters the first ones lack.
+const SYSTEM_FONT_PATHS: &[&str] = &[
+    // macOS
+    "/System/Library/Fonts/Helvetica.ttc",
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+    // Windows
+    "C:\\Windows\\Fonts\\segoeui.ttf",
+    "C:\\Windows\\Fonts\\arial.ttf",
+    "C:\\Windows\\Fonts\\msyh.ttc",
+    // Linux
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/TTF/DejaVuSans.ttf",
+    "/usr/share/fonts/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/truetype/liberation/LiberationSans-Regular.ttf",
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+];
+
+static SYSTEM_FONTS: Lazy<Vec<Arc<Font>>> = Lazy::new(|| {
+    SYSTEM_FONT_PATHS
+        .iter()
+        .filter_map(|path| std::fs::read(path).ok())
+        .filter_map(|data| Font::from_bytes(data, FontSettings::default()).ok())
+        .map(Arc::new)
+        .collect()
+});
+
+/// Bezier control point offset for a quarter circle
+const KAPPA: f32 = 0.552_284_8;
+
+/// Draws annotation documents onto RGBA images
+///
+/// Shapes need no setup. Text and step badges need at least one font, added
+/// with [`with_font`](Self::with_font) or [`with_system_fonts`](Self::with_system_fonts).
+#[derive(Clone, Default)]
+pub struct AnnotationRenderer {
+    fonts: Vec<Arc<Font>>,
+}
+
+impl AnnotationRenderer {
+    /// Create a renderer without fonts
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a TrueType/OpenType font; earlier fonts take precedence
+    pub fn with_font(mut self, data: &[u8]) -> Result<Self> {
+        let font = Font::from_bytes(data, FontSettings::default())
+            .map_err(|e| AumateError::Other(format!("Failed to load font: {}", e)))?;
+        self.fonts.push(Arc::new(font));
+        Ok(self)
+    }
+
+    /// Add the platform's default UI fonts (loaded once per process)
+    pub fn with_system_fonts(mut self) -> Self {
+        self.fonts.extend(SYSTEM_FONTS.iter().cloned());
+        self
+    }
+
+    /// Whether text and step annotations can be drawn
+    pub fn has_fonts(&self) -> bool {
+        !self.fonts.is_empty()
+    }
+
+    /// Draw every annotation in `document` onto `image`, in z-order
+    pub fn render(&self, image: &mut RgbaImage, document: &AnnotationDocument) -> Result<()> {
+        let mut pixmap = to_pixmap(image)?;
+        for annotation in document.render_order() {
+            self.draw(&mut pixmap, annotation)?;
+        }
+        write_back(&pixmap, image);
+        Ok(())
+    }
+
+    fn draw(&self, pixmap: &mut Pixmap, annotation: &Annotation) -> Result<()> {
+        let style = &annotation.style;
+        match &annotation.shape {
+            Shape::Rectangle { x, y, width, height, corner_radius } => {
+                if let Some(path) = rounded_rect(*x, *y, *width, *height, *corner_radius) {
+                    draw_closed(pixmap, &path, style);
+                }
+            }
+            Shape::Ellipse { x, y, width, height } => {
+                if let Some(path) =
+                    normalized_rect(*x, *y, *width, *height).and_then(PathBuilder::from_oval)
+                {
+                    draw_closed(pixmap, &path, style);
+                }
+            }
+            Shape::Line { from, to } => {
+                draw_polyline(pixmap, &[*from, *to], style, LineCap::Round);
+            }
+            Shape::Arrow { from, to } => draw_arrow(pixmap, *from, *to, style),
+            Shape::Freehand { points } => draw_freehand(pixmap, points, style),
+            Shape::Text { x, y, content, font_size, background } => {
+                let layout = self.layout_text(content, *font_size)?;
+                if let Some(background) = background {
+                    let pad = font_size * 0.25;
+                    if let Some(rect) = Rect::from_xywh(
+                        x - pad,
+                        y - pad,
+                        layout.width + pad * 2.0,
+                        layout.height + pad * 2.0,
+                    ) {
+                        let paint = paint(*background, style.opacity);
+                        pixmap.fill_rect(rect, &paint, Transform::identity(), None);
+                    }
+                }
+                draw_glyphs(pixmap, &layout, *x, *y, style.stroke, style.opacity);
+            }
+            Shape::Step { x, y, number, radius } => {
+                self.draw_step(pixmap, *x, *y, *number, *radius, style)?;
+            }
+            Shape::Mosaic { x, y, width, height, block_size } => {
+                if let Some(region) = pixel_region(pixmap, *x, *y, *width, *height) {
+                    pixelate(pixmap, region, (*block_size).max(1));
+                }
+            }
+            Shape::Blur { x, y, width, height, radius } => {
+                if let Some(region) = pixel_region(pixmap, *x, *y, *width, *height) {
+                    blur(pixmap, region, *radius);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn draw_step(
+        &self,
+        pixmap: &mut Pixmap,
+        x: f32,
+        y: f32,
+        number: u32,
+        radius: f32,
+        style: &Style,
+    ) -> Result<()> {
+        let badge = style.fill.unwrap_or(style.stroke);
+        if let Some(circle) = PathBuilder::from_circle(x, y, radius.max(1.0)) {
+            pixmap.fill_path(
+                &circle,
+                &paint(badge, style.opacity),
+                FillRule::Winding,
+                Transform::identity(),
+                None,
+            );
+        }
+
+        let layout = self.layout_text(&number.to_string(), radius * 1.2)?;
+        let Some((left, top, right, bottom)) = layout.ink_bounds() else {
+            return Ok(());
+        };
+        // Dark digits on light badges, white digits otherwise
+        let luminance = 0.299 * badge.r as f32 + 0.587 * badge.g as f32 + 0.114 * badge.b as f32;
+        let text_color =
+            if luminance > 160.0 { Color::rgb(0, 0, 0) } else { Color::rgb(255, 255, 255) };
+        let origin_x = x - (left + right) / 2.0;
+        let origin_y = y - (top + bottom) / 2.0;
+        draw_glyphs(pixmap, &layout, origin_x, origin_y, text_color, style.opacity);
+        Ok(())
+    }
+
+    /// Lay out `content` with its first line box's top-left corner at the origin
+    fn layout_text(&self, content: &str, font_size: f32) -> Result<TextLayout> {
+        let primary = self.fonts.first().ok_or_else(|| {
+            AumateError::Other("No font available for text annotations".to_string())
+        })?;
+        let font_size = font_size.max(1.0);
+        let (ascent, line_height) = match primary.horizontal_line_metrics(font_size) {
+            Some(metrics) => (metrics.ascent, metrics.new_line_size),
+            None => (font_size * 0.8, font_size * 1.2),
+        };
+
+        let mut layout = TextLayout::default();
+        for (line_index, line) in content.lines().enumerate() {
+            let baseline = ascent + line_index as f32 * line_height;
+            let mut pen_x = 0.0f32;
+            for ch in line.chars() {
+                let font = self.font_for(ch);
+                let (metrics, coverage) = font.rasterize(ch, font_size);
+                if metrics.width > 0 && metrics.height > 0 {
+                    layout.glyphs.push(Glyph {
+                        x: (pen_x + metrics.xmin as f32).round(),
+                        y: (baseline - metrics.height as f32 - metrics.ymin as f32).round(),
+                        width: metrics.width as u32,
+                        height: metrics.height as u32,
+                        coverage,
+                    });
+                }
+                pen_x += metrics.advance_width;
+            }
+            layout.width = layout.width.max(pen_x);
+            layout.height += line_height;
+        }
+        Ok(layout)
+    }
+
+    /// First font that has a glyph for `ch`, falling back to the primary font
+    fn font_for(&self, ch: char) -> &Font {
+        self.fonts.iter().find(|font| font.lookup_glyph_index(ch) != 0).unwrap_or(&self.fonts[0])
+    }
+}
+
+/// Draw `document` onto `image` using the system fonts
+pub fn render_annotations(image: &mut RgbaImage, document: &AnnotationDocument) -> Result<()> {
+    AnnotationRenderer::new().with_system_fonts().render(image, document)
+}
+
+#[derive(Default)]
+struct TextLayout {
+    glyphs: Vec<Glyph>,
+    width: f32,
+    height: f32,
+}
+
+impl TextLayout {
+    /// Bounding box of the drawn pixels as (left, top, right, bottom)
+    fn ink_bounds(&self) -> Option<(f32, f32, f32, f32)> {
+        self.glyphs.iter().fold(None, |bounds, glyph| {
+            let (l, t, r, b) =
+                (glyph.x, glyph.y, glyph.x + glyph.width as f32, glyph.y + glyph.height as f32);
+            Some(match bounds {
+                None => (l, t, r, b),
+                Some((bl, bt, br, bb)) => (bl.min(l), bt.min(t), br.max(r), bb.max(b)),
+            })
+        })
+    }
+}
+
+struct Glyph {
+    x: f32,
+    y: f32,
+    width: u32,
+    height: u32,
+    coverage: Vec<u8>,
+}
+
+fn to_pixmap(image: &RgbaImage) -> Result<Pixmap> {
+    let mut pixmap = Pixmap::new(image.width(), image.height())
+        .ok_or_else(|| AumateError::Other("Cannot annotate an empty image".to_string()))?;
+    for (dst, src) in pixmap.pixels_mut().iter_mut().zip(image.pixels()) {
+        *dst = ColorU8::from_rgba(src[0], src[1], src[2], src[3]).premultiply();
+    }
+    Ok(pixmap)
+}
+
+fn write_back(pixmap: &Pixmap, image: &mut RgbaImage) {
+    for (dst, src) in image.pixels_mut().zip(pixmap.pixels()) {
+        let color = src.demultiply();
+        *dst = Rgba([color.red(), color.green(), color.blue(), color.alpha()]);
+    }
+}
+
+fn paint(color: Color, opacity: f32) -> Paint<'static> {
+    let mut paint = Paint::default();
+    let alpha = (color.a as f32 * opacity.clamp(0.0, 1.0)).round() as u8;
+    paint.set_color_rgba8(color.r, color.g, color.b, alpha);
+    paint.anti_alias = true;
+    paint
+}
+
+fn stroke(width: f32, line_cap: LineCap) -> Stroke {
+    Stroke { width, line_cap, line_join: LineJoin::Round, ..Stroke::default() }
+}
+
+/// Rect with non-negative size, accepting shapes dragged up or left
+fn normalized_rect(x: f32, y: f32, width: f32, height: f32) -> Option<Rect> {
+    Rect::from_ltrb(x.min(x + width), y.min(y + height), x.max(x + width), y.max(y + height))
+}
+
+fn rounded_rect(x: f32, y: f32, width: f32, height: f32, radius: f32) -> Option<Path> {
+    let rect = normalized_rect(x, y, width, height)?;
+    let r = radius.min(rect.width() / 2.0).min(rect.height() / 2.0);
+    if r.is_nan() || r <= 0.0 {
+        return Some(PathBuilder::from_rect(rect));
+    }
+
+    let (l, t, rt, b) = (rect.left(), rect.top(), rect.right(), rect.bottom());
+    let k = r * (1.0 - KAPPA);
+    let mut pb = PathBuilder::new();
+    pb.move_to(l + r, t);
+    pb.line_to(rt - r, t);
+    pb.cubic_to(rt - k, t, rt, t + k, rt, t + r);
+    pb.line_to(rt, b - r);
+    pb.cubic_to(rt, b - k, rt - k, b, rt - r, b);
+    pb.line_to(l + r, b);
+    pb.cubic_to(l + k, b, l, b - k, l, b - r);
+    pb.line_to(l, t + r);
+    pb.cubic_to(l, t + k, l + k, t, l + r, t);
+    pb.close();
+    pb.finish()
+}
+
+fn draw_closed(pixmap: &mut Pixmap, path: &Path, style: &Style) {
+    if let Some(fill) = style.fill {
+        pixmap.fill_path(
+            path,
+            &paint(fill, style.opacity),
+            FillRule::Winding,
+            Transform::identity(),
+            None,
+        );
+    }
+    if style.stroke_width > 0.0 {
+        pixmap.stroke_path(
+            path,
+            &paint(style.stroke, style.opacity),
+            &stroke(style.stroke_width, LineCap::Round),
+            Transform::identity(),
+            None,
+        );
+    }
+}
+
+fn draw_polyline(pixmap: &mut Pixmap, points: &[Point], style: &Style, line_cap: LineCap) {
+    if style.stroke_width <= 0.0 {
+        return;
+    }
+    let mut pb = PathBuilder::new();
+    let mut points = points.iter();
+    let Some(first) = points.next() else {
+        return;
+    };
+    pb.move_to(first.x, first.y);
+    for point in points {
+        pb.line_to(point.x, point.y);
+    }
+    if let Some(path) = pb.finish() {
+        pixmap.stroke_path(
+            &path,
+            &paint(style.stroke, style.opacity),
+            &stroke(style.stroke_width, line_cap),
+            Transform::identity(),
+            None,
+        );
+    }
+}
+
+fn draw_freehand(pixmap: &mut Pixmap, points: &[Point], style: &Style) {
+    match points {
+        [] => {}
+        // A single click leaves a dot
+        [point] => {
+            if let Some(dot) =
+                PathBuilder::from_circle(point.x, point.y, (style.stroke_width / 2.0).max(0.5))
+            {
+                pixmap.fill_path(
+                    &dot,
+                    &paint(style.stroke, style.opacity),
+                    FillRule::Winding,
+                    Transform::identity(),
+                    None,
+                );
+            }
+        }
+        _ => draw_polyline(pixmap, points, style, LineCap::Round),
+    }
+}
+
+fn draw_arrow(pixmap: &mut Pixmap, from: Point, to: Point, style: &Style) {
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    let length = dx.hypot(dy);
+    if length < 1e-3 {
+        return;
+    }
+    let (ux, uy) = (dx / length, dy / length);
+    let head_length = (style.stroke_width * 4.0).max(10.0).min(length);
+    let half_width = head_length * 0.5;
+    let base = Point::new(to.x - ux * head_length, to.y - uy * head_length);
+
+    // The shaft stops at the head's base so translucent arrows don't double up
+    if length > head_length {
+        draw_polyline(pixmap, &[from, base], style, LineCap::Butt);
+    }
+
+    let mut pb = PathBuilder::new();
+    pb.move_to(to.x, to.y);
+    pb.line_to(base.x - uy * half_width, base.y + ux * half_width);
+    pb.line_to(base.x + uy * half_width, base.y - ux * half_width);
+    pb.close();
+    if let Some(head) = pb.finish() {
+        pixmap.fill_path(
+            &head,
+            &paint(style.stroke, style.opacity),
+            FillRule::Winding,
+            Transform::identity(),
+            None,
+        );
+    }
+}
+
+fn draw_glyphs(
+    pixmap: &mut Pixmap,
+    layout: &TextLayout,
+    origin_x: f32,
+    origin_y: f32,
+    color: Color,
+    opacity: f32,
+) {
+    let alpha = color.a as f32 * opacity.clamp(0.0, 1.0) / 255.0;
+    for glyph in &layout.glyphs {
+        let Some(mut tile) = Pixmap::new(glyph.width, glyph.height) else {
+            continue;
+        };
+        for (dst, &coverage) in tile.pixels_mut().iter_mut().zip(&glyph.coverage) {
+            let a = (coverage as f32 * alpha).round() as u8;
+            *dst = ColorU8::from_rgba(color.r, color.g, color.b, a).premultiply();
+        }
+        pixmap.draw_pixmap(
+            (origin_x + glyph.x).round() as i32,
+            (origin_y + glyph.y).round() as i32,
+            tile.as_ref(),
+            &PixmapPaint::default(),
+            Transform::identity(),
+            None,
+        );
+    }
+}
+
+/// Integer pixel bounds (x0, y0, x1, y1) of a region, clipped to the image
+fn pixel_region(
+    pixmap: &Pixmap,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+) -> Option<(u32, u32, u32, u32)> {
+    let rect = normalized_rect(x, y, width, height)?;
+    let clamp_x = |v: f32| v.clamp(0.0, pixmap.width() as f32) as u32;
+    let clamp_y = |v: f32| v.clamp(0.0, pixmap.height() as f32) as u32;
+    let (x0, x1) = (clamp_x(rect.left().floor()), clamp_x(rect.right().ceil()));
+    let (y0, y1) = (clamp_y(rect.top().floor()), clamp_y(rect.bottom().ceil()));
+    (x0 < x1 && y0 < y1).then_some((x0, y0, x1, y1))
+}
+
+fn pixelate(pixmap: &mut Pixmap, (x0, y0, x1, y1): (u32, u32, u32, u32), block_size: u32) {
+    let stride = pixmap.width() as usize;
+    let pixels = pixmap.pixels_mut();
+    for block_y in (y0..y1).step_by(block_size as usize) {
+        for block_x in (x0..x1).step_by(block_size as usize) {
+            let (bx1, by1) = ((block_x + block_size).min(x1), (block_y + block_size).min(y1));
+            let indices = || {
+                (block_y..by1).flat_map(move |py| {
+                    (block_x..bx1).map(move |px| py as usize * stride + px as usize)
+                })
+            };
+
+            let mut sum = [0u32; 4];
+            let mut count = 0u32;
+            for index in indices() {
+                let p = pixels[index];
+                sum[0] += p.red() as u32;
+                sum[1] += p.green() as u32;
+                sum[2] += p.blue() as u32;
+                sum[3] += p.alpha() as u32;
+                count += 1;
+            }
+            let avg = |v: u32| ((v + count / 2) / count) as u8;
+            // Averages of premultiplied channels still satisfy color <= alpha
+            let Some(color) =
+                PremultipliedColorU8::from_rgba(avg(sum[0]), avg(sum[1]), avg(sum[2]), avg(sum[3]))
+            else {
+                continue;
+            };
+            for index in indices() {
+                pixels[index] = color;
+            }
+        }
+    }
+}
+
+fn blur(pixmap: &mut Pixmap, (x0, y0, x1, y1): (u32, u32, u32, u32), sigma: f32) {
+    if sigma.is_nan() || sigma <= 0.0 {
+        return;
+    }
+    let stride = pixmap.width() as usize;
+    let index = |x: u32, y: u32| y as usize * stride + x as usize;
+
+    let region = RgbaImage::from_fn(x1 - x0, y1 - y0, |x, y| {
+        let color = pixmap.pixels()[index(x0 + x, y0 + y)].demultiply();
+        Rgba([color.red(), color.green(), color.blue(), color.alpha()])
+    });
+    let blurred = image::imageops::blur(&region, sigma);
+
+    let pixels = pixmap.pixels_mut();
+    for (x, y, pixel) in blurred.enumerate_pixels() {
+        pixels[index(x0 + x, y0 + y)] =
+            ColorU8::from_rgba(pixel[0], pixel[1], pixel[2], pixel[3]).premultiply();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Set to regenerate the golden images after an intended rendering change
+    const UPDATE_GOLDEN_ENV: &str = "AUMATE_UPDATE_GOLDEN";
+
+    fn test_image() -> RgbaImage {
+        RgbaImage::from_fn(96, 64, |x, y| {
+            let checker = if (x / 8 + y / 8) % 2 == 0 { 40 } else { 0 };
+            Rgba([(x * 2) as u8 + checker, (y * 3) as u8 + checker, 160, 255])
+        })
+    }
+
+    fn style(stroke: &str, stroke_width: f32, fill: Option<&str>) -> Style {
+        Style {
+            stroke: Color::from_hex(stroke).unwrap(),
+            stroke_width,
+            fill: fill.and_then(Color::from_hex),
+            opacity: 1.0,
+        }
+    }
+
+    fn render(annotations: Vec<Annotation>) -> RgbaImage {
+        let mut image = test_image();
+        AnnotationRenderer::new()
+            .render(&mut image, &AnnotationDocument::new(annotations))
+            .unwrap();
+        image
+    }
+
+    /// Compare with `testdata/<name>`, allowing small anti-aliasing differences
+    fn assert_golden(name: &str, actual: &RgbaImage) {
+        let path =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/annotate/testdata").join(name);
+        if std::env::var_os(UPDATE_GOLDEN_ENV).is_some() {
+            actual.save(&path).unwrap();
+            return;
+        }
+
+        let expected = image::open(&path)
+            .unwrap_or_else(|e| {
+                panic!("Missing golden image {:?} ({}), run with {}=1", path, e, UPDATE_GOLDEN_ENV)
+            })
+            .to_rgba8();
+        assert_eq!(expected.dimensions(), actual.dimensions(), "{}: size differs", name);
+
+        let mismatched = expected
+            .pixels()
+            .zip(actual.pixels())
+            .filter(|(e, a)| e.0.iter().zip(a.0.iter()).any(|(e, a)| e.abs_diff(*a) > 2))
+            .count();
+        let total = (actual.width() * actual.height()) as usize;
+        assert!(
+            mismatched * 200 <= total,
+            "{}: {} of {} pixels differ from the golden image",
+            name,
+            mismatched,
+            total
+        );
+    }
+
+    #[test]
+    fn test_golden_shapes() {
+        let image = render(vec![
+            Annotation::new(Shape::Rectangle {
+                x: 6.0,
+                y: 6.0,
+                width: 36.0,
+                height: 24.0,
+                corner_radius: 6.0,
+            })
+            .with_style(style("#E03131", 3.0, Some("#FFD43B80"))),
+            Annotation::new(Shape::Ellipse { x: 52.0, y: 6.0, width: 36.0, height: 24.0 })
+                .with_style(style("#1971C2", 2.0, None)),
+            Annotation::new(Shape::Line {
+                from: Point::new(6.0, 40.0),
+                to: Point::new(42.0, 58.0),
+            })
+            .with_style(style("#2F9E44", 2.0, None)),
+            Annotation::new(Shape::Arrow {
+                from: Point::new(52.0, 58.0),
+                to: Point::new(90.0, 38.0),
+            })
+            .with_style(style("#E03131", 3.0, None)),
+            Annotation::new(Shape::Freehand {
+                points: vec![
+                    Point::new(20.0, 36.0),
+                    Point::new(26.0, 44.0),
+                    Point::new(34.0, 38.0),
+                    Point::new(40.0, 46.0),
+                ],
+            })
+            .with_style(Style { opacity: 0.5, ..style("#000000", 4.0, None) }),
+        ]);
+        assert_golden("shapes.png", &image);
+    }
+
+    #[test]
+    fn test_golden_redaction() {
+        let image = render(vec![
+            Annotation::new(Shape::Mosaic {
+                x: 4.0,
+                y: 4.0,
+                width: 40.0,
+                height: 56.0,
+                block_size: 6,
+            }),
+            Annotation::new(Shape::Blur {
+                x: 52.0,
+                y: 4.0,
+                width: 40.0,
+                height: 56.0,
+                radius: 3.0,
+            }),
+        ]);
+        assert_golden("redaction.png", &image);
+    }
+
+    #[test]
+    fn test_golden_z_order() {
+        // The first rectangle has the higher z_index, so it ends up on top
+        let image = render(vec![
+            Annotation::new(Shape::Rectangle {
+                x: 10.0,
+                y: 10.0,
+                width: 40.0,
+                height: 30.0,
+                corner_radius: 0.0,
+            })
+            .with_style(style("#000000", 0.0, Some("#1971C2")))
+            .with_z_index(1),
+            Annotation::new(Shape::Rectangle {
+                x: 30.0,
+                y: 20.0,
+                width: 40.0,
+                height: 30.0,
+                corner_radius: 0.0,
+            })
+            .with_style(style("#000000", 0.0, Some("#E03131"))),
+            Annotation::new(Shape::Mosaic {
+                x: 60.0,
+                y: 40.0,
+                width: 30.0,
+                height: 20.0,
+                block_size: 10,
+            })
+            .with_z_index(2),
+        ]);
+        assert_eq!(image.get_pixel(40, 30), &Rgba([0x19, 0x71, 0xC2, 255]));
+        assert_golden("z_order.png", &image);
+    }
+
+    #[test]
+    fn test_text_requires_font() {
+        let mut image = test_image();
+        let document = AnnotationDocument::new(vec![Annotation::new(Shape::Step {
+            x: 20.0,
+            y: 20.0,
+            number: 1,
+            radius: 10.0,
+        })]);
+        assert!(AnnotationRenderer::new().render(&mut image, &document).is_err());
+    }
+
+    #[test]
+    fn test_text_with_system_font() {
+        let renderer = AnnotationRenderer::new().with_system_fonts();
+        if !renderer.has_fonts() {
+            return;
+        }
+        let mut image = test_image();
+        let document = AnnotationDocument::new(vec![
+            Annotation::new(Shape::Text {
+                x: 4.0,
+                y: 4.0,
+                content: "Hi\n42".to_string(),
+                font_size: 16.0,
+                background: Some(Color::rgb(255, 255, 255)),
+            })
+            .with_style(style("#000000", 1.0, None)),
+        ]);
+        renderer.render(&mut image, &document).unwrap();
+
+        // The background covers the text box and the glyphs are dark
+        assert_eq!(image.get_pixel(2, 2), &Rgba([255, 255, 255, 255]));
+        assert!(image.pixels().any(|p| p[0] < 64 && p[1] < 64 && p[2] < 64));
+    }
+}
@@ -0,0 +1,26 @@
+//! Barcode scanning and QR code generation
+//!
+//! [`scan`] finds every QR code, DataMatrix and common 1D barcode (EAN/UPC,
+//! Code 128/39/93, ITF, Codabar) in an image, so codes shown on screen during
+//! login flows or 2FA setup can be read without a phone. [`generate_qr`]
+//! renders text as a QR code image.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use aumate::barcode::{QrOptions, generate_qr, scan};
+//!
+//! let screenshot = image::open("screenshot.png").unwrap().to_rgba8();
+//! for barcode in scan(&screenshot).unwrap() {
+//!     println!("{} at {:?}: {}", barcode.format.name(), barcode.bounds, barcode.text);
+//! }
+//!
+//! let qr = generate_qr("https://example.com", &QrOptions::default()).unwrap();
+//! qr.save("qr.png").unwrap();
+//! ```
+
+mod qr;
+mod scan;
+
+pub use qr::{MAX_MODULE_SIZE, QrErrorCorrection, QrOptions, generate_qr};
+pub use scan::{Barcode, BarcodeBounds, BarcodeFormat, scan, scan_region};
@@ -0,0 +1,149 @@
+//! QR code rendering on top of the qrcode crate
+
+use image::{Rgba, RgbaImage};
+use qrcode::{Color, EcLevel, QrCode};
+
+use crate::error::{AumateError, Result};
+
+/// Largest supported module size, keeps generated images reasonably small
+pub const MAX_MODULE_SIZE: u32 = 64;
+
+/// How much of the code can be damaged and still be read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QrErrorCorrection {
+    /// About 7%
+    Low,
+    /// About 15%
+    #[default]
+    Medium,
+    /// About 25%
+    Quartile,
+    /// About 30%
+    High,
+}
+
+impl QrErrorCorrection {
+    fn level(self) -> EcLevel {
+        match self {
+            Self::Low => EcLevel::L,
+            Self::Medium => EcLevel::M,
+            Self::Quartile => EcLevel::Q,
+            Self::High => EcLevel::H,
+        }
+    }
+}
+
+/// QR code rendering options
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrOptions {
+    /// Pixels per module (default 8)
+    pub module_size: u32,
+    /// Light modules around the code, scanners expect 4 (default 4)
+    pub quiet_zone: u32,
+    pub error_correction: QrErrorCorrection,
+    pub dark: Rgba<u8>,
+    pub light: Rgba<u8>,
+}
+
+impl Default for QrOptions {
+    fn default() -> Self {
+        Self {
+            module_size: 8,
+            quiet_zone: 4,
+            error_correction: QrErrorCorrection::default(),
+            dark: Rgba([0, 0, 0, 255]),
+            light: Rgba([255, 255, 255, 255]),
+        }
+    }
+}
+
+impl QrOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_module_size(mut self, module_size: u32) -> Self {
+        self.module_size = module_size;
+        self
+    }
+
+    pub fn with_quiet_zone(mut self, quiet_zone: u32) -> Self {
+        self.quiet_zone = quiet_zone;
+        self
+    }
+
+    pub fn with_error_correction(mut self, error_correction: QrErrorCorrection) -> Self {
+        self.error_correction = error_correction;
+        self
+    }
+
+    pub fn with_colors(mut self, dark: Rgba<u8>, light: Rgba<u8>) -> Self {
+        self.dark = dark;
+        self.light = light;
+        self
+    }
+}
+
+/// Render `text` as a QR code
+///
+/// The smallest QR version that fits the text at the requested error
+/// correction level is used. The image is square, `(modules + 2 *
+/// quiet_zone) * module_size` pixels wide.
+pub fn generate_qr(text: &str, options: &QrOptions) -> Result<RgbaImage> {
+    if text.is_empty() {
+        return Err(AumateError::Barcode("QR code text must not be empty".to_string()));
+    }
+    if options.module_size == 0 || options.module_size > MAX_MODULE_SIZE {
+        return Err(AumateError::Barcode(format!(
+            "Module size must be between 1 and {}",
+            MAX_MODULE_SIZE
+        )));
+    }
+
+    let code = QrCode::with_error_correction_level(text, options.error_correction.level())
+        .map_err(|e| AumateError::Barcode(format!("Failed to encode QR code: {}", e)))?;
+    let modules = code.width() as i64;
+    let colors = code.to_colors();
+
+    let quiet_zone = options.quiet_zone as i64;
+    let size = (modules as u32 + options.quiet_zone * 2) * options.module_size;
+    Ok(RgbaImage::from_fn(size, size, |x, y| {
+        let column = (x / options.module_size) as i64 - quiet_zone;
+        let row = (y / options.module_size) as i64 - quiet_zone;
+        let inside = (0..modules).contains(&column) && (0..modules).contains(&row);
+        if inside && colors[(row * modules + column) as usize] == Color::Dark {
+            options.dark
+        } else {
+            options.light
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::barcode::{BarcodeFormat, scan};
+
+    #[test]
+    fn test_generate_qr() {
+        // "hello" fits version 1, 21 modules
+        let image = generate_qr("hello", &QrOptions::new().with_module_size(4)).unwrap();
+        assert_eq!(image.dimensions(), (116, 116));
+        assert_eq!(*image.get_pixel(0, 0), Rgba([255, 255, 255, 255]));
+        // Top-left finder pattern starts right after the quiet zone
+        assert_eq!(*image.get_pixel(16, 16), Rgba([0, 0, 0, 255]));
+
+        assert!(generate_qr("", &QrOptions::default()).is_err());
+        assert!(generate_qr("hello", &QrOptions::new().with_module_size(0)).is_err());
+    }
+
+    #[test]
+    fn test_generated_qr_scans_back() {
+        let text = "https://example.com/share?id=42&lang=zh-CN";
+        let options = QrOptions::new().with_error_correction(QrErrorCorrection::High);
+        let barcodes = scan(&generate_qr(text, &options).unwrap()).unwrap();
+        assert_eq!(barcodes.len(), 1);
+        assert_eq!(barcodes[0].format, BarcodeFormat::QrCode);
+        assert_eq!(barcodes[0].text, text);
+    }
+}
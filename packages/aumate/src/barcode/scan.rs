@@ -0,0 +1,306 @@
+//! Barcode detection on top of rxing
+
+use image::{GrayImage, RgbaImage, imageops};
+use rxing::{BarcodeFormat as RxingFormat, Exceptions, RXingResult};
+
+use crate::error::{AumateError, Result};
+
+/// Symbology of a detected barcode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BarcodeFormat {
+    QrCode,
+    MicroQrCode,
+    DataMatrix,
+    Aztec,
+    Pdf417,
+    MaxiCode,
+    Ean8,
+    Ean13,
+    UpcA,
+    UpcE,
+    Code39,
+    Code93,
+    Code128,
+    Codabar,
+    Itf,
+    Rss14,
+    RssExpanded,
+    /// Any other symbology supported by the detector
+    Other,
+}
+
+impl BarcodeFormat {
+    /// Stable snake_case name, e.g. `"qr_code"`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::QrCode => "qr_code",
+            Self::MicroQrCode => "micro_qr_code",
+            Self::DataMatrix => "data_matrix",
+            Self::Aztec => "aztec",
+            Self::Pdf417 => "pdf_417",
+            Self::MaxiCode => "maxi_code",
+            Self::Ean8 => "ean_8",
+            Self::Ean13 => "ean_13",
+            Self::UpcA => "upc_a",
+            Self::UpcE => "upc_e",
+            Self::Code39 => "code_39",
+            Self::Code93 => "code_93",
+            Self::Code128 => "code_128",
+            Self::Codabar => "codabar",
+            Self::Itf => "itf",
+            Self::Rss14 => "rss_14",
+            Self::RssExpanded => "rss_expanded",
+            Self::Other => "other",
+        }
+    }
+
+    /// 1D barcodes are located by a scan line rather than four corners
+    pub fn is_linear(&self) -> bool {
+        matches!(
+            self,
+            Self::Ean8
+                | Self::Ean13
+                | Self::UpcA
+                | Self::UpcE
+                | Self::Code39
+                | Self::Code93
+                | Self::Code128
+                | Self::Codabar
+                | Self::Itf
+                | Self::Rss14
+                | Self::RssExpanded
+        )
+    }
+
+    fn from_rxing(format: &RxingFormat) -> Self {
+        match format {
+            RxingFormat::QR_CODE => Self::QrCode,
+            RxingFormat::MICRO_QR_CODE => Self::MicroQrCode,
+            RxingFormat::DATA_MATRIX => Self::DataMatrix,
+            RxingFormat::AZTEC => Self::Aztec,
+            RxingFormat::PDF_417 => Self::Pdf417,
+            RxingFormat::MAXICODE => Self::MaxiCode,
+            RxingFormat::EAN_8 => Self::Ean8,
+            RxingFormat::EAN_13 => Self::Ean13,
+            RxingFormat::UPC_A => Self::UpcA,
+            RxingFormat::UPC_E => Self::UpcE,
+            RxingFormat::CODE_39 => Self::Code39,
+            RxingFormat::CODE_93 => Self::Code93,
+            RxingFormat::CODE_128 => Self::Code128,
+            RxingFormat::CODABAR => Self::Codabar,
+            RxingFormat::ITF => Self::Itf,
+            RxingFormat::RSS_14 => Self::Rss14,
+            RxingFormat::RSS_EXPANDED => Self::RssExpanded,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Axis-aligned box in image pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarcodeBounds {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A decoded barcode
+#[derive(Debug, Clone, PartialEq)]
+pub struct Barcode {
+    pub format: BarcodeFormat,
+    /// Decoded payload
+    pub text: String,
+    /// Points reported by the detector in image pixels: finder patterns or
+    /// corners for 2D codes, the two ends of the scan line for 1D barcodes
+    pub points: Vec<(f32, f32)>,
+    /// Bounding box of `points`, at least one pixel high for 1D barcodes
+    pub bounds: BarcodeBounds,
+}
+
+impl Barcode {
+    fn from_rxing(result: &RXingResult, width: u32, height: u32) -> Self {
+        let points: Vec<(f32, f32)> = result.getPoints().iter().map(|p| (p.x, p.y)).collect();
+        Self {
+            format: BarcodeFormat::from_rxing(result.getBarcodeFormat()),
+            text: result.getText().to_string(),
+            bounds: bounds_of(&points, width, height),
+            points,
+        }
+    }
+
+    fn offset(mut self, x: u32, y: u32) -> Self {
+        for point in &mut self.points {
+            point.0 += x as f32;
+            point.1 += y as f32;
+        }
+        self.bounds.x += x;
+        self.bounds.y += y;
+        self
+    }
+}
+
+fn bounds_of(points: &[(f32, f32)], width: u32, height: u32) -> BarcodeBounds {
+    if points.is_empty() {
+        return BarcodeBounds { x: 0, y: 0, width: 0, height: 0 };
+    }
+
+    let clamp = |v: f32, max: u32| (v.max(0.0) as u32).min(max);
+    let (min_x, max_x) =
+        points.iter().fold((f32::MAX, f32::MIN), |(min, max), p| (min.min(p.0), max.max(p.0)));
+    let (min_y, max_y) =
+        points.iter().fold((f32::MAX, f32::MIN), |(min, max), p| (min.min(p.1), max.max(p.1)));
+
+    let x = clamp(min_x.floor(), width.saturating_sub(1));
+    let y = clamp(min_y.floor(), height.saturating_sub(1));
+    BarcodeBounds {
+        x,
+        y,
+        width: clamp(max_x.ceil(), width).saturating_sub(x).max(1),
+        height: clamp(max_y.ceil(), height).saturating_sub(y).max(1),
+    }
+}
+
+/// Find and decode every barcode in an image
+///
+/// Results are sorted top to bottom, then left to right. When nothing is
+/// found the inverted image is tried as well, so light-on-dark codes in
+/// dark themes are recognized.
+pub fn scan(image: &RgbaImage) -> Result<Vec<Barcode>> {
+    let mut gray = imageops::grayscale(image);
+    let barcodes = scan_luma(&gray)?;
+    if !barcodes.is_empty() {
+        return Ok(barcodes);
+    }
+    imageops::invert(&mut gray);
+    scan_luma(&gray)
+}
+
+/// Scan only the `width` x `height` region at `(x, y)`
+///
+/// Positions in the results are relative to the whole image.
+pub fn scan_region(
+    image: &RgbaImage,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> Result<Vec<Barcode>> {
+    let outside = x as u64 + width as u64 > image.width() as u64
+        || y as u64 + height as u64 > image.height() as u64;
+    if width == 0 || height == 0 || outside {
+        return Err(AumateError::Barcode(format!(
+            "Region {}x{} at ({}, {}) is outside the {}x{} image",
+            width,
+            height,
+            x,
+            y,
+            image.width(),
+            image.height()
+        )));
+    }
+
+    let region = imageops::crop_imm(image, x, y, width, height).to_image();
+    Ok(scan(&region)?.into_iter().map(|barcode| barcode.offset(x, y)).collect())
+}
+
+fn scan_luma(image: &GrayImage) -> Result<Vec<Barcode>> {
+    let (width, height) = image.dimensions();
+    if width == 0 || height == 0 {
+        return Ok(Vec::new());
+    }
+
+    let results =
+        match rxing::helpers::detect_multiple_in_luma(image.as_raw().clone(), width, height) {
+            Ok(results) => results,
+            Err(Exceptions::NotFoundException(_)) => return Ok(Vec::new()),
+            Err(e) => return Err(AumateError::Barcode(e.to_string())),
+        };
+
+    let mut barcodes: Vec<Barcode> =
+        results.iter().map(|result| Barcode::from_rxing(result, width, height)).collect();
+    barcodes.sort_by_key(|barcode| (barcode.bounds.y, barcode.bounds.x));
+    Ok(barcodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> RgbaImage {
+        let path =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/barcode/testdata").join(name);
+        image::open(&path)
+            .unwrap_or_else(|e| panic!("Missing fixture {:?}: {}", path, e))
+            .to_rgba8()
+    }
+
+    fn contains(outer: BarcodeBounds, x: u32, y: u32) -> bool {
+        x >= outer.x && x < outer.x + outer.width && y >= outer.y && y < outer.y + outer.height
+    }
+
+    #[test]
+    fn test_scan_fixtures() {
+        // 37 modules of 6 pixels with a 4 module quiet zone
+        let barcodes = scan(&fixture("qr.png")).unwrap();
+        assert_eq!(barcodes.len(), 1);
+        let qr = &barcodes[0];
+        assert_eq!(qr.format, BarcodeFormat::QrCode);
+        assert_eq!(
+            qr.text,
+            "otpauth://totp/Aumate:alice@example.com?secret=JBSWY3DPEHPK3PXP&issuer=Aumate"
+        );
+        assert!(contains(qr.bounds, 135, 135));
+        // Corners are module centers, so bounds may end half a pixel past the symbol
+        assert!(qr.bounds.x >= 24 && qr.bounds.x + qr.bounds.width <= 247);
+
+        let barcodes = scan(&fixture("datamatrix.png")).unwrap();
+        assert_eq!(barcodes.len(), 1);
+        assert_eq!(barcodes[0].format, BarcodeFormat::DataMatrix);
+        assert_eq!(barcodes[0].text, "AUMATE-42");
+
+        let barcodes = scan(&fixture("ean13.png")).unwrap();
+        assert_eq!(barcodes.len(), 1);
+        let ean = &barcodes[0];
+        assert_eq!(ean.format, BarcodeFormat::Ean13);
+        assert!(ean.format.is_linear());
+        assert_eq!(ean.text, "4006381333931");
+        // Scan line across the bars, 10 module quiet zone of 3 pixels each side
+        assert!(ean.bounds.x >= 25 && ean.bounds.x + ean.bounds.width <= 320);
+        assert!(ean.bounds.y >= 30 && ean.bounds.y < 120);
+    }
+
+    #[test]
+    fn test_scan_region_offsets_positions() {
+        let qr = fixture("qr.png");
+        let ean = fixture("ean13.png");
+        let mut canvas = RgbaImage::from_pixel(800, 600, image::Rgba([255, 255, 255, 255]));
+        imageops::overlay(&mut canvas, &qr, 400, 250);
+        imageops::overlay(&mut canvas, &ean, 20, 20);
+
+        let all = scan(&canvas).unwrap();
+        let formats: Vec<_> = all.iter().map(|barcode| barcode.format).collect();
+        assert_eq!(formats, [BarcodeFormat::Ean13, BarcodeFormat::QrCode]);
+
+        let barcodes = scan_region(&canvas, 380, 230, 320, 320).unwrap();
+        assert_eq!(barcodes.len(), 1);
+        assert!(contains(barcodes[0].bounds, 535, 385));
+        assert!(barcodes[0].points.iter().all(|&(x, y)| x >= 400.0 && y >= 250.0));
+
+        assert!(scan_region(&canvas, 700, 0, 200, 10).is_err());
+        assert!(scan_region(&canvas, 0, 0, 0, 10).is_err());
+    }
+
+    #[test]
+    fn test_scan_inverted_and_blank() {
+        let mut qr = fixture("qr.png");
+        imageops::invert(&mut qr);
+        let barcodes = scan(&qr).unwrap();
+        assert_eq!(barcodes.len(), 1);
+        assert_eq!(barcodes[0].format, BarcodeFormat::QrCode);
+
+        let blank = RgbaImage::from_pixel(64, 64, image::Rgba([255, 255, 255, 255]));
+        assert!(scan(&blank).unwrap().is_empty());
+    }
+}
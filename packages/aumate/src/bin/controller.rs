@@ -0,0 +1,26 @@
+//! Aumate control panel
+//!
+//! Standalone window for managing models and trying out the ML features.
+//! Tabs are included for the features the binary is built with:
+//!
+//! ```sh
+//! cargo run -p aumate --bin aumate-controller --features gui,stt,ocr
+//! ```
+
+use aumate::error::Result;
+use aumate::gui::{Controller, run_control_panel};
+
+fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    #[allow(unused_mut)]
+    let mut controller = Controller::new();
+
+    #[cfg(feature = "stt")]
+    controller.add_feature(Box::new(aumate::stt::SttFeature::new()));
+
+    #[cfg(feature = "ocr")]
+    controller.add_feature(Box::new(aumate::ocr::OcrFeature::new()));
+
+    run_control_panel(controller)
+}
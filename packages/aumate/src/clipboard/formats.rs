@@ -0,0 +1,118 @@
+//! Clipboard format names
+//!
+//! Formats are identified by MIME type. Each platform stores them under its own
+//! names (X11 targets, Windows clipboard formats, macOS pasteboard types), which
+//! are translated at the platform boundary using the tables below.
+
+/// Plain text
+pub const TEXT: &str = "text/plain";
+/// HTML markup
+pub const HTML: &str = "text/html";
+/// Rich Text Format
+pub const RTF: &str = "text/rtf";
+/// PNG image
+pub const PNG: &str = "image/png";
+/// List of file URIs
+pub const URI_LIST: &str = "text/uri-list";
+
+/// Native names of well-known formats, preferred name first
+#[cfg(target_os = "linux")]
+const NATIVE_NAMES: &[(&str, &[&str])] = &[
+    (TEXT, &["UTF8_STRING", "text/plain;charset=utf-8", "STRING", "TEXT", "text/plain"]),
+    (HTML, &["text/html"]),
+    (RTF, &["text/rtf", "application/rtf", "text/richtext"]),
+    (PNG, &["image/png"]),
+    (URI_LIST, &["text/uri-list"]),
+];
+
+#[cfg(target_os = "windows")]
+const NATIVE_NAMES: &[(&str, &[&str])] = &[
+    (TEXT, &["CF_UNICODETEXT", "CF_TEXT", "CF_OEMTEXT"]),
+    (HTML, &["HTML Format"]),
+    (RTF, &["Rich Text Format"]),
+    (PNG, &["PNG"]),
+    ("image/bmp", &["CF_DIBV5", "CF_DIB", "CF_BITMAP"]),
+    (URI_LIST, &["CF_HDROP"]),
+];
+
+#[cfg(target_os = "macos")]
+const NATIVE_NAMES: &[(&str, &[&str])] = &[
+    (TEXT, &["public.utf8-plain-text", "NSStringPboardType"]),
+    (HTML, &["public.html", "Apple HTML pasteboard type"]),
+    (RTF, &["public.rtf", "NSRTFPboardType"]),
+    (PNG, &["public.png"]),
+    ("image/tiff", &["public.tiff", "NeXT TIFF v4.0 pasteboard type"]),
+    (URI_LIST, &["public.file-url"]),
+];
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+const NATIVE_NAMES: &[(&str, &[&str])] = &[];
+
+/// X11 targets that describe the selection rather than hold data
+#[cfg(target_os = "linux")]
+const META_NAMES: &[&str] =
+    &["TARGETS", "TIMESTAMP", "MULTIPLE", "SAVE_TARGETS", "DELETE", "INSERT_SELECTION"];
+
+#[cfg(not(target_os = "linux"))]
+const META_NAMES: &[&str] = &[];
+
+/// Native names under which a format may be stored, preferred name first
+///
+/// Formats without an entry (custom MIME types) are stored under their own name.
+pub(crate) fn native_names(mime: &str) -> Vec<&str> {
+    let mime = mime.trim();
+    NATIVE_NAMES
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(mime))
+        .map(|(_, names)| names.to_vec())
+        .unwrap_or_else(|| vec![mime])
+}
+
+/// MIME type of a native format name, or `None` for entries that hold no data
+pub(crate) fn to_mime(native: &str) -> Option<String> {
+    if META_NAMES.contains(&native) {
+        return None;
+    }
+    let mime = NATIVE_NAMES
+        .iter()
+        .find(|(_, names)| names.iter().any(|name| name.eq_ignore_ascii_case(native)))
+        .map_or(native, |(mime, _)| mime);
+    Some(mime.to_string())
+}
+
+/// Translate native format names to MIME types, dropping duplicates
+pub(crate) fn to_mime_list<I, S>(natives: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut formats: Vec<String> = Vec::new();
+    for mime in natives.into_iter().filter_map(|name| to_mime(name.as_ref())) {
+        if !formats.contains(&mime) {
+            formats.push(mime);
+        }
+    }
+    formats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_formats_keep_their_name() {
+        assert_eq!(native_names("application/x-aumate"), vec!["application/x-aumate"]);
+        assert_eq!(to_mime("application/x-aumate").as_deref(), Some("application/x-aumate"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_x11_targets() {
+        assert_eq!(native_names(TEXT)[0], "UTF8_STRING");
+        assert_eq!(native_names("TEXT/RTF")[0], "text/rtf");
+
+        let targets =
+            ["TARGETS", "UTF8_STRING", "STRING", "text/html", "application/rtf", "TIMESTAMP"];
+        assert_eq!(to_mime_list(targets), vec![TEXT, HTML, RTF]);
+    }
+}
@@ -0,0 +1,558 @@
+//! X11 clipboard access through the selection protocol
+//!
+//! Reading asks the selection owner for its TARGETS, picks the best native name
+//! for the requested format and converts the selection into a property on a
+//! hidden window. Writing takes ownership of CLIPBOARD and serves requests from
+//! a background thread until another client takes it over. Transfers larger
+//! than the server's maximum request size use the INCR protocol in both
+//! directions. Changes are observed through XFixes selection notifications.
+
+use super::formats;
+use crate::error::{AumateError, Result};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use x11rb::connection::{Connection, RequestConnection};
+use x11rb::protocol::Event;
+use x11rb::protocol::xfixes::{self, ConnectionExt as _, SelectionEventMask};
+use x11rb::protocol::xproto::{
+    Atom, AtomEnum, ChangeWindowAttributesAux, ConnectionExt as _, CreateWindowAux, EventMask,
+    PropMode, Property, PropertyNotifyEvent, SELECTION_NOTIFY_EVENT, SelectionNotifyEvent,
+    SelectionRequestEvent, Window, WindowClass,
+};
+use x11rb::rust_connection::RustConnection;
+use x11rb::wrapper::ConnectionExt as _;
+
+/// How long to wait for the selection owner to answer, and for each INCR chunk
+const TRANSFER_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Largest chunk sent per property change during an INCR transfer, further
+/// limited by the server's maximum request size
+const INCR_CHUNK_BYTES: usize = 256 * 1024;
+
+/// Connection reused by [`change_token`], which watchers poll frequently
+static TOKEN_CLIPBOARD: Mutex<Option<X11Clipboard>> = Mutex::new(None);
+
+fn x11_error(e: impl std::fmt::Display) -> AumateError {
+    AumateError::Clipboard(format!("X11 clipboard error: {}", e))
+}
+
+/// A connection with a hidden window to receive selection data on
+struct X11Clipboard {
+    conn: RustConnection,
+    window: Window,
+    clipboard: Atom,
+    targets: Atom,
+    incr: Atom,
+    property: Atom,
+}
+
+impl X11Clipboard {
+    fn connect() -> Result<Self> {
+        let (conn, screen_num) = x11rb::connect(None).map_err(x11_error)?;
+        let screen = &conn.setup().roots[screen_num];
+        let window = conn.generate_id().map_err(x11_error)?;
+        conn.create_window(
+            x11rb::COPY_DEPTH_FROM_PARENT,
+            window,
+            screen.root,
+            0,
+            0,
+            1,
+            1,
+            0,
+            WindowClass::INPUT_OUTPUT,
+            screen.root_visual,
+            &CreateWindowAux::new().event_mask(EventMask::PROPERTY_CHANGE),
+        )
+        .map_err(x11_error)?;
+
+        let mut clipboard = Self { conn, window, clipboard: 0, targets: 0, incr: 0, property: 0 };
+        clipboard.clipboard = clipboard.atom("CLIPBOARD")?;
+        clipboard.targets = clipboard.atom("TARGETS")?;
+        clipboard.incr = clipboard.atom("INCR")?;
+        clipboard.property = clipboard.atom("AUMATE_CLIPBOARD")?;
+        Ok(clipboard)
+    }
+
+    fn atom(&self, name: &str) -> Result<Atom> {
+        Ok(self
+            .conn
+            .intern_atom(false, name.as_bytes())
+            .map_err(x11_error)?
+            .reply()
+            .map_err(x11_error)?
+            .atom)
+    }
+
+    fn atom_name(&self, atom: Atom) -> Result<String> {
+        let reply = self.conn.get_atom_name(atom).map_err(x11_error)?.reply().map_err(x11_error)?;
+        Ok(String::from_utf8_lossy(&reply.name).into_owned())
+    }
+
+    /// Native names of everything the selection owner offers
+    fn target_names(&self) -> Result<Vec<String>> {
+        let Some(data) = self.convert(self.targets)? else {
+            return Ok(Vec::new());
+        };
+        data.chunks_exact(4)
+            .map(|atom| u32::from_ne_bytes([atom[0], atom[1], atom[2], atom[3]]))
+            .filter(|&atom| atom != x11rb::NONE)
+            .map(|atom| self.atom_name(atom))
+            .collect()
+    }
+
+    /// Convert the selection to `target`, returning `None` if the owner refuses
+    fn convert(&self, target: Atom) -> Result<Option<Vec<u8>>> {
+        self.conn
+            .convert_selection(
+                self.window,
+                self.clipboard,
+                target,
+                self.property,
+                x11rb::CURRENT_TIME,
+            )
+            .map_err(x11_error)?;
+        self.conn.flush().map_err(x11_error)?;
+
+        let deadline = Instant::now() + TRANSFER_TIMEOUT;
+        loop {
+            if let Event::SelectionNotify(event) = self.next_event(deadline)? {
+                if event.requestor != self.window || event.target != target {
+                    continue;
+                }
+                if event.property == x11rb::NONE {
+                    return Ok(None);
+                }
+                return self.read_property().map(Some);
+            }
+        }
+    }
+
+    /// Read (and delete) the transfer property, following INCR if needed
+    fn read_property(&self) -> Result<Vec<u8>> {
+        let reply = self.take_property()?;
+        if reply.0 != self.incr {
+            return Ok(reply.1);
+        }
+
+        // INCR: the owner sends chunks, each time we delete the property
+        receive_incr(TRANSFER_TIMEOUT, |deadline| self.next_incr_chunk(deadline))
+    }
+
+    /// Wait for the owner to store the next INCR chunk and take it
+    fn next_incr_chunk(&self, deadline: Instant) -> Result<Vec<u8>> {
+        loop {
+            if let Event::PropertyNotify(event) = self.next_event(deadline)? {
+                if event.window == self.window
+                    && event.atom == self.property
+                    && event.state == Property::NEW_VALUE
+                {
+                    return self.take_property().map(|(_, chunk)| chunk);
+                }
+            }
+        }
+    }
+
+    fn take_property(&self) -> Result<(Atom, Vec<u8>)> {
+        let reply = self
+            .conn
+            .get_property(true, self.window, self.property, AtomEnum::ANY, 0, u32::MAX / 4)
+            .map_err(x11_error)?
+            .reply()
+            .map_err(x11_error)?;
+        self.conn.flush().map_err(x11_error)?;
+        Ok((reply.type_, reply.value))
+    }
+
+    fn next_event(&self, deadline: Instant) -> Result<Event> {
+        loop {
+            if let Some(event) = self.conn.poll_for_event().map_err(x11_error)? {
+                return Ok(event);
+            }
+            if Instant::now() >= deadline {
+                return Err(AumateError::Clipboard(
+                    "Timed out waiting for the clipboard owner".to_string(),
+                ));
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+}
+
+/// Collect INCR chunks until the owner sends the empty final chunk
+///
+/// `next_chunk` waits for a chunk until the given deadline, which restarts after
+/// every chunk: a slow transfer succeeds as long as the owner keeps sending.
+fn receive_incr(
+    timeout: Duration,
+    mut next_chunk: impl FnMut(Instant) -> Result<Vec<u8>>,
+) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    loop {
+        let chunk = next_chunk(Instant::now() + timeout)?;
+        if chunk.is_empty() {
+            return Ok(data);
+        }
+        data.extend_from_slice(&chunk);
+    }
+}
+
+/// Fingerprint of the clipboard contents, for change polling
+///
+/// X11 has no change counter, so this hashes the selection owner, its targets
+/// and the text (owners may keep the selection while replacing its contents).
+/// The connection is kept between calls and reopened after an error.
+pub(super) fn change_token() -> Result<u64> {
+    let mut cached =
+        TOKEN_CLIPBOARD.lock().map_err(|e| AumateError::Clipboard(format!("Lock error: {}", e)))?;
+    let clipboard = match cached.take() {
+        Some(clipboard) => clipboard,
+        None => X11Clipboard::connect()?,
+    };
+    let token = clipboard_token(&clipboard)?;
+    *cached = Some(clipboard);
+    Ok(token)
+}
+
+fn clipboard_token(clipboard: &X11Clipboard) -> Result<u64> {
+    let owner = clipboard
+        .conn
+        .get_selection_owner(clipboard.clipboard)
+        .map_err(x11_error)?
+        .reply()
+        .map_err(x11_error)?
+        .owner;
+
+    let mut hasher = DefaultHasher::new();
+    owner.hash(&mut hasher);
+    if owner != x11rb::NONE {
+        let targets = clipboard.target_names()?;
+        targets.hash(&mut hasher);
+        let text_target = formats::native_names(formats::TEXT)
+            .into_iter()
+            .find(|name| targets.iter().any(|target| target == name));
+        if let Some(name) = text_target {
+            clipboard.convert(clipboard.atom(name)?)?.hash(&mut hasher);
+        }
+    }
+    Ok(hasher.finish())
+}
+
+/// Clipboard owner changes reported by the XFixes extension
+pub(super) struct SelectionEvents {
+    clipboard: X11Clipboard,
+}
+
+impl SelectionEvents {
+    /// Start listening, failing if the server lacks XFixes
+    pub(super) fn subscribe() -> Result<Self> {
+        let clipboard = X11Clipboard::connect()?;
+        let conn = &clipboard.conn;
+        if conn.extension_information(xfixes::X11_EXTENSION_NAME).map_err(x11_error)?.is_none() {
+            return Err(AumateError::Clipboard("XFixes extension is not available".to_string()));
+        }
+        // The version must be negotiated before any other XFixes request
+        conn.xfixes_query_version(5, 0).map_err(x11_error)?.reply().map_err(x11_error)?;
+        conn.xfixes_select_selection_input(
+            clipboard.window,
+            clipboard.clipboard,
+            SelectionEventMask::SET_SELECTION_OWNER
+                | SelectionEventMask::SELECTION_WINDOW_DESTROY
+                | SelectionEventMask::SELECTION_CLIENT_CLOSE,
+        )
+        .map_err(x11_error)?;
+        conn.flush().map_err(x11_error)?;
+        Ok(Self { clipboard })
+    }
+
+    /// Wait up to `timeout` for the clipboard to change
+    pub(super) fn wait(&self, timeout: Duration) -> Result<bool> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            while let Some(event) = self.clipboard.conn.poll_for_event().map_err(x11_error)? {
+                match event {
+                    Event::XfixesSelectionNotify(event)
+                        if event.selection == self.clipboard.clipboard =>
+                    {
+                        return Ok(true);
+                    }
+                    _ => {}
+                }
+            }
+            if Instant::now() >= deadline {
+                return Ok(false);
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+}
+
+/// MIME types of everything on the clipboard
+pub(super) fn available_formats() -> Result<Vec<String>> {
+    let clipboard = X11Clipboard::connect()?;
+    Ok(formats::to_mime_list(clipboard.target_names()?))
+}
+
+/// Raw data of a format
+pub(super) fn get_data(mime: &str) -> Result<Vec<u8>> {
+    let clipboard = X11Clipboard::connect()?;
+    let offered = clipboard.target_names()?;
+
+    for name in formats::native_names(mime) {
+        if !offered.iter().any(|target| target == name) {
+            continue;
+        }
+        if let Some(data) = clipboard.convert(clipboard.atom(name)?)? {
+            return Ok(data);
+        }
+    }
+    Err(AumateError::Clipboard(format!("Clipboard has no {} data", mime)))
+}
+
+/// Take ownership of the clipboard and offer the given formats
+pub(super) fn set_data(items: Vec<(String, Vec<u8>)>) -> Result<()> {
+    let clipboard = X11Clipboard::connect()?;
+
+    // Every native name of every format, pointing at the data to serve
+    let mut offers: Vec<(Atom, usize)> = Vec::new();
+    for (index, (mime, _)) in items.iter().enumerate() {
+        for name in formats::native_names(mime) {
+            let atom = clipboard.atom(name)?;
+            if !offers.iter().any(|&(offered, _)| offered == atom) {
+                offers.push((atom, index));
+            }
+        }
+    }
+
+    clipboard
+        .conn
+        .set_selection_owner(clipboard.window, clipboard.clipboard, x11rb::CURRENT_TIME)
+        .map_err(x11_error)?;
+    let owner = clipboard
+        .conn
+        .get_selection_owner(clipboard.clipboard)
+        .map_err(x11_error)?
+        .reply()
+        .map_err(x11_error)?
+        .owner;
+    if owner != clipboard.window {
+        return Err(AumateError::Clipboard("Failed to take clipboard ownership".to_string()));
+    }
+
+    // The previous server, if any, exits once it sees it lost the selection
+    let data: Vec<Vec<u8>> = items.into_iter().map(|(_, data)| data).collect();
+    std::thread::spawn(move || {
+        if let Err(e) = serve(&clipboard, &offers, &data) {
+            log::warn!("Clipboard server stopped: {}", e);
+        }
+    });
+    Ok(())
+}
+
+/// An outgoing INCR transfer, advanced each time the requestor deletes the property
+struct IncrTransfer {
+    requestor: Window,
+    property: Atom,
+    target: Atom,
+    index: usize,
+    offset: usize,
+}
+
+/// Answer selection requests until another client owns the clipboard
+///
+/// A failed request is refused and logged without stopping the server, and
+/// unfinished INCR transfers are dropped when the selection is lost.
+fn serve(clipboard: &X11Clipboard, offers: &[(Atom, usize)], data: &[Vec<u8>]) -> Result<()> {
+    let conn = &clipboard.conn;
+    let max_bytes = conn.maximum_request_bytes().saturating_sub(64);
+    let mut transfers: Vec<IncrTransfer> = Vec::new();
+
+    loop {
+        match conn.wait_for_event().map_err(x11_error)? {
+            Event::SelectionClear(event) if event.selection == clipboard.clipboard => {
+                return Ok(());
+            }
+            Event::SelectionRequest(request) => {
+                let property = answer(clipboard, offers, data, max_bytes, &request, &mut transfers)
+                    .unwrap_or_else(|e| {
+                        log::warn!("Failed to answer clipboard request: {}", e);
+                        x11rb::NONE
+                    });
+                if let Err(e) = notify(conn, &request, property) {
+                    log::warn!("Failed to notify clipboard requestor: {}", e);
+                }
+            }
+            Event::PropertyNotify(event) if event.state == Property::DELETE => {
+                let chunk_bytes = max_bytes.min(INCR_CHUNK_BYTES);
+                if let Err(e) = continue_transfer(conn, data, chunk_bytes, &event, &mut transfers) {
+                    log::warn!("Clipboard INCR transfer failed: {}", e);
+                    transfers.retain(|t| (t.requestor, t.property) != (event.window, event.atom));
+                }
+            }
+            // Usually a requestor window that went away mid-transfer
+            Event::Error(e) => {
+                log::debug!("Clipboard server X11 error: {:?}", e.error_kind);
+                transfers.retain(|transfer| transfer.requestor != e.bad_value);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Store the requested data on the requestor, returning the property to report
+fn answer(
+    clipboard: &X11Clipboard,
+    offers: &[(Atom, usize)],
+    data: &[Vec<u8>],
+    max_bytes: usize,
+    request: &SelectionRequestEvent,
+    transfers: &mut Vec<IncrTransfer>,
+) -> Result<Atom> {
+    let conn = &clipboard.conn;
+    // Obsolete clients pass no property and expect the target to be used
+    let property = if request.property == x11rb::NONE { request.target } else { request.property };
+
+    if request.target == clipboard.targets {
+        let mut atoms = vec![clipboard.targets];
+        atoms.extend(offers.iter().map(|&(atom, _)| atom));
+        conn.change_property32(
+            PropMode::REPLACE,
+            request.requestor,
+            property,
+            AtomEnum::ATOM,
+            &atoms,
+        )
+        .map_err(x11_error)?;
+        return Ok(property);
+    }
+
+    let Some(&(atom, index)) = offers.iter().find(|&&(atom, _)| atom == request.target) else {
+        return Ok(x11rb::NONE);
+    };
+    let bytes = &data[index];
+    if bytes.len() <= max_bytes {
+        conn.change_property8(PropMode::REPLACE, request.requestor, property, atom, bytes)
+            .map_err(x11_error)?;
+        return Ok(property);
+    }
+
+    // INCR: announce the size, then send chunks as the requestor deletes the property
+    let size = u32::try_from(bytes.len()).unwrap_or(u32::MAX);
+    conn.change_window_attributes(
+        request.requestor,
+        &ChangeWindowAttributesAux::new().event_mask(EventMask::PROPERTY_CHANGE),
+    )
+    .map_err(x11_error)?;
+    conn.change_property32(PropMode::REPLACE, request.requestor, property, clipboard.incr, &[size])
+        .map_err(x11_error)?;
+    transfers.retain(|t| (t.requestor, t.property) != (request.requestor, property));
+    transfers.push(IncrTransfer {
+        requestor: request.requestor,
+        property,
+        target: atom,
+        index,
+        offset: 0,
+    });
+    Ok(property)
+}
+
+/// Send the next INCR chunk, or the closing empty chunk once all data is sent
+fn continue_transfer(
+    conn: &RustConnection,
+    data: &[Vec<u8>],
+    chunk_bytes: usize,
+    event: &PropertyNotifyEvent,
+    transfers: &mut Vec<IncrTransfer>,
+) -> Result<()> {
+    let Some(position) =
+        transfers.iter().position(|t| t.requestor == event.window && t.property == event.atom)
+    else {
+        return Ok(());
+    };
+    let transfer = &mut transfers[position];
+    let bytes = &data[transfer.index];
+    let end = bytes.len().min(transfer.offset + chunk_bytes);
+    let chunk = &bytes[transfer.offset..end];
+    conn.change_property8(
+        PropMode::REPLACE,
+        transfer.requestor,
+        transfer.property,
+        transfer.target,
+        chunk,
+    )
+    .map_err(x11_error)?;
+    transfer.offset = end;
+
+    if chunk.is_empty() {
+        let requestor = transfer.requestor;
+        transfers.swap_remove(position);
+        if !transfers.iter().any(|t| t.requestor == requestor) {
+            conn.change_window_attributes(
+                requestor,
+                &ChangeWindowAttributesAux::new().event_mask(EventMask::NO_EVENT),
+            )
+            .map_err(x11_error)?;
+        }
+    }
+    conn.flush().map_err(x11_error)
+}
+
+fn notify(conn: &RustConnection, request: &SelectionRequestEvent, property: Atom) -> Result<()> {
+    let event = SelectionNotifyEvent {
+        response_type: SELECTION_NOTIFY_EVENT,
+        sequence: 0,
+        time: request.time,
+        requestor: request.requestor,
+        selection: request.selection,
+        target: request.target,
+        property,
+    };
+    conn.send_event(false, request.requestor, EventMask::NO_EVENT, event).map_err(x11_error)?;
+    conn.flush().map_err(x11_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Chunk source that takes `delay` to deliver each chunk, failing like
+    /// `next_event` when the deadline passes first
+    fn slow_chunks(
+        chunks: Vec<Vec<u8>>,
+        delay: Duration,
+    ) -> impl FnMut(Instant) -> Result<Vec<u8>> {
+        let mut chunks = chunks.into_iter();
+        move |deadline| {
+            std::thread::sleep(delay);
+            if Instant::now() >= deadline {
+                return Err(AumateError::Clipboard("timed out".to_string()));
+            }
+            Ok(chunks.next().unwrap_or_default())
+        }
+    }
+
+    #[test]
+    fn test_receive_incr_restarts_deadline_per_chunk() {
+        let chunks = vec![vec![1u8; 4], vec![2u8; 4], vec![3u8; 4], vec![4u8; 4], vec![5u8; 4]];
+        let start = Instant::now();
+        let data = receive_incr(
+            Duration::from_millis(100),
+            slow_chunks(chunks, Duration::from_millis(40)),
+        )
+        .unwrap();
+        // The whole transfer takes longer than a single timeout
+        assert!(start.elapsed() > Duration::from_millis(100));
+        assert_eq!(data.len(), 20);
+        assert_eq!(&data[16..], &[5u8; 4]);
+    }
+
+    #[test]
+    fn test_receive_incr_times_out_when_owner_stalls() {
+        let chunks = vec![vec![1u8; 4]];
+        let result =
+            receive_incr(Duration::from_millis(20), slow_chunks(chunks, Duration::from_millis(40)));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,125 @@
+//! macOS clipboard access through `NSPasteboard`
+//!
+//! Custom formats are stored as pasteboard types named after their MIME type.
+
+use super::formats;
+use crate::error::{AumateError, Result};
+use objc::runtime::{BOOL, NO, Object};
+use objc::{class, msg_send, sel, sel_impl};
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+type Id = *mut Object;
+
+/// Autorelease pool drained when dropped
+struct AutoreleasePool(Id);
+
+impl AutoreleasePool {
+    fn new() -> Self {
+        Self(unsafe { msg_send![class!(NSAutoreleasePool), new] })
+    }
+}
+
+impl Drop for AutoreleasePool {
+    fn drop(&mut self) {
+        unsafe {
+            let _: () = msg_send![self.0, drain];
+        }
+    }
+}
+
+fn pasteboard() -> Id {
+    unsafe { msg_send![class!(NSPasteboard), generalPasteboard] }
+}
+
+fn ns_string(s: &str) -> Result<Id> {
+    let c_string = CString::new(s)
+        .map_err(|_| AumateError::Clipboard(format!("Invalid pasteboard type: {}", s)))?;
+    Ok(unsafe { msg_send![class!(NSString), stringWithUTF8String: c_string.as_ptr()] })
+}
+
+fn from_ns_string(s: Id) -> String {
+    unsafe {
+        let ptr: *const c_char = msg_send![s, UTF8String];
+        if ptr.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(ptr).to_string_lossy().into_owned()
+        }
+    }
+}
+
+fn type_names(pasteboard: Id) -> Vec<String> {
+    unsafe {
+        let types: Id = msg_send![pasteboard, types];
+        if types.is_null() {
+            return Vec::new();
+        }
+        let count: usize = msg_send![types, count];
+        (0..count)
+            .map(|i| {
+                let name: Id = msg_send![types, objectAtIndex: i];
+                from_ns_string(name)
+            })
+            .collect()
+    }
+}
+
+/// Change counter of the general pasteboard
+pub(super) fn change_token() -> Result<u64> {
+    let _pool = AutoreleasePool::new();
+    let count: isize = unsafe { msg_send![pasteboard(), changeCount] };
+    Ok(count as u64)
+}
+
+/// MIME types of everything on the clipboard
+pub(super) fn available_formats() -> Result<Vec<String>> {
+    let _pool = AutoreleasePool::new();
+    Ok(formats::to_mime_list(type_names(pasteboard())))
+}
+
+/// Raw data of a format
+pub(super) fn get_data(mime: &str) -> Result<Vec<u8>> {
+    let _pool = AutoreleasePool::new();
+    let pasteboard = pasteboard();
+
+    for name in formats::native_names(mime) {
+        let data: Id = unsafe { msg_send![pasteboard, dataForType: ns_string(name)?] };
+        if data.is_null() {
+            continue;
+        }
+        let bytes = unsafe {
+            let length: usize = msg_send![data, length];
+            let ptr: *const u8 = msg_send![data, bytes];
+            if ptr.is_null() {
+                Vec::new()
+            } else {
+                std::slice::from_raw_parts(ptr, length).to_vec()
+            }
+        };
+        return Ok(bytes);
+    }
+    Err(AumateError::Clipboard(format!("Clipboard has no {} data", mime)))
+}
+
+/// Replace the clipboard contents with the given formats
+pub(super) fn set_data(items: Vec<(String, Vec<u8>)>) -> Result<()> {
+    let _pool = AutoreleasePool::new();
+    let pasteboard = pasteboard();
+    unsafe {
+        let _: isize = msg_send![pasteboard, clearContents];
+    }
+
+    for (mime, data) in items {
+        let name = ns_string(formats::native_names(&mime)[0])?;
+        let written: BOOL = unsafe {
+            let data: Id =
+                msg_send![class!(NSData), dataWithBytes: data.as_ptr() length: data.len()];
+            msg_send![pasteboard, setData: data forType: name]
+        };
+        if written == NO {
+            return Err(AumateError::Clipboard(format!("Failed to write {} to clipboard", mime)));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,418 @@
+//! Clipboard operations module
+//!
+//! Provides clipboard text, image, file list and rich format operations for
+//! desktop automation.
+//!
+//! Rich formats are identified by MIME type (see [`formats`]). HTML goes through
+//! arboard on every platform; RTF and custom MIME types use the platform
+//! clipboard directly (X11 selections, Windows clipboard formats, `NSPasteboard`).
+//!
+//! Changes can be observed with [`watch`].
+
+pub mod formats;
+mod watch;
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "windows")]
+mod windows;
+
+#[cfg(target_os = "linux")]
+use linux as native;
+#[cfg(target_os = "macos")]
+use macos as native;
+#[cfg(target_os = "windows")]
+use windows as native;
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod native {
+    use crate::error::{AumateError, Result};
+
+    fn unsupported() -> AumateError {
+        AumateError::Clipboard("Rich clipboard formats are not supported on this platform".into())
+    }
+
+    pub(super) fn available_formats() -> Result<Vec<String>> {
+        Err(unsupported())
+    }
+
+    pub(super) fn get_data(_mime: &str) -> Result<Vec<u8>> {
+        Err(unsupported())
+    }
+
+    pub(super) fn set_data(_items: Vec<(String, Vec<u8>)>) -> Result<()> {
+        Err(unsupported())
+    }
+
+    pub(super) fn change_token() -> Result<u64> {
+        Err(unsupported())
+    }
+}
+
+pub use watch::{
+    ClipboardChange, ClipboardWatchHandle, ClipboardWatcher, WatchBackend, WatchOptions, watch,
+    watch_with,
+};
+
+use crate::error::{AumateError, Result};
+use arboard::Clipboard;
+use image::ImageEncoder;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Raw clipboard image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardImage {
+    pub width: u32,
+    pub height: u32,
+    /// RGBA pixels, 8 bits per channel
+    pub rgba: Vec<u8>,
+}
+
+impl ClipboardImage {
+    /// Encode the image as PNG
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        if self.rgba.len() != self.width as usize * self.height as usize * 4 {
+            return Err(AumateError::Clipboard(
+                "Failed to create image from clipboard data".to_string(),
+            ));
+        }
+
+        let mut png_data = Vec::new();
+        let encoder = image::codecs::png::PngEncoder::new(&mut png_data);
+        encoder
+            .write_image(&self.rgba, self.width, self.height, image::ExtendedColorType::Rgba8)
+            .map_err(|e| AumateError::Clipboard(format!("Failed to encode image as PNG: {}", e)))?;
+        Ok(png_data)
+    }
+}
+
+// Thread-safe clipboard instance
+static CLIPBOARD: Mutex<Option<Clipboard>> = Mutex::new(None);
+
+fn get_or_init_clipboard() -> Result<std::sync::MutexGuard<'static, Option<Clipboard>>> {
+    let mut guard =
+        CLIPBOARD.lock().map_err(|e| AumateError::Clipboard(format!("Lock error: {}", e)))?;
+
+    if guard.is_none() {
+        let clipboard = Clipboard::new().map_err(|e| {
+            AumateError::Clipboard(format!("Failed to initialize clipboard: {}", e))
+        })?;
+        *guard = Some(clipboard);
+    }
+
+    Ok(guard)
+}
+
+/// Get text from clipboard
+pub fn get_text() -> Result<String> {
+    let mut guard = get_or_init_clipboard()?;
+    let clipboard = guard
+        .as_mut()
+        .ok_or_else(|| AumateError::Clipboard("Clipboard not initialized".to_string()))?;
+
+    clipboard
+        .get_text()
+        .map_err(|e| AumateError::Clipboard(format!("Failed to get clipboard text: {}", e)))
+}
+
+/// Set text to clipboard
+pub fn set_text(text: &str) -> Result<()> {
+    let mut guard = get_or_init_clipboard()?;
+    let clipboard = guard
+        .as_mut()
+        .ok_or_else(|| AumateError::Clipboard("Clipboard not initialized".to_string()))?;
+
+    clipboard
+        .set_text(text.to_string())
+        .map_err(|e| AumateError::Clipboard(format!("Failed to set clipboard text: {}", e)))
+}
+
+/// Get image from clipboard (returns PNG-encoded buffer)
+pub fn get_image() -> Result<Vec<u8>> {
+    get_image_raw()?.to_png()
+}
+
+/// Get image from clipboard as raw RGBA pixels
+pub fn get_image_raw() -> Result<ClipboardImage> {
+    let mut guard = get_or_init_clipboard()?;
+    let clipboard = guard
+        .as_mut()
+        .ok_or_else(|| AumateError::Clipboard("Clipboard not initialized".to_string()))?;
+
+    let image_data = clipboard
+        .get_image()
+        .map_err(|e| AumateError::Clipboard(format!("Failed to get clipboard image: {}", e)))?;
+
+    Ok(ClipboardImage {
+        width: image_data.width as u32,
+        height: image_data.height as u32,
+        rgba: image_data.bytes.into_owned(),
+    })
+}
+
+/// Set image to clipboard (accepts PNG-encoded buffer)
+pub fn set_image(image_buffer: &[u8]) -> Result<()> {
+    let mut guard = get_or_init_clipboard()?;
+    let clipboard = guard
+        .as_mut()
+        .ok_or_else(|| AumateError::Clipboard("Clipboard not initialized".to_string()))?;
+
+    // Decode PNG buffer
+    let img = image::load_from_memory(image_buffer)
+        .map_err(|e| AumateError::Clipboard(format!("Failed to decode image: {}", e)))?;
+
+    let rgba = img.to_rgba8();
+    let (width, height) = rgba.dimensions();
+
+    // Convert to arboard ImageData
+    let image_data = arboard::ImageData {
+        width: width as usize,
+        height: height as usize,
+        bytes: rgba.into_raw().into(),
+    };
+
+    clipboard
+        .set_image(image_data)
+        .map_err(|e| AumateError::Clipboard(format!("Failed to set clipboard image: {}", e)))
+}
+
+/// Set image to clipboard from raw RGBA data
+pub fn set_image_raw(width: u32, height: u32, rgba_data: Vec<u8>) -> Result<()> {
+    if width == 0 || height == 0 || rgba_data.len() != width as usize * height as usize * 4 {
+        return Err(AumateError::Clipboard(format!(
+            "Invalid RGBA data for {}x{} image: {} bytes",
+            width,
+            height,
+            rgba_data.len()
+        )));
+    }
+
+    let mut guard = get_or_init_clipboard()?;
+    let clipboard = guard
+        .as_mut()
+        .ok_or_else(|| AumateError::Clipboard("Clipboard not initialized".to_string()))?;
+
+    let image_data = arboard::ImageData {
+        width: width as usize,
+        height: height as usize,
+        bytes: rgba_data.into(),
+    };
+
+    clipboard
+        .set_image(image_data)
+        .map_err(|e| AumateError::Clipboard(format!("Failed to set clipboard image: {}", e)))
+}
+
+/// Get the list of files on the clipboard (e.g. copied in a file manager)
+pub fn get_files() -> Result<Vec<PathBuf>> {
+    let mut guard = get_or_init_clipboard()?;
+    let clipboard = guard
+        .as_mut()
+        .ok_or_else(|| AumateError::Clipboard("Clipboard not initialized".to_string()))?;
+
+    clipboard
+        .get()
+        .file_list()
+        .map_err(|e| AumateError::Clipboard(format!("Failed to get clipboard files: {}", e)))
+}
+
+/// Put a list of files on the clipboard so they can be pasted in a file manager
+pub fn set_files(paths: &[impl AsRef<Path>]) -> Result<()> {
+    if paths.is_empty() {
+        return Err(AumateError::Clipboard("File list is empty".to_string()));
+    }
+
+    let mut guard = get_or_init_clipboard()?;
+    let clipboard = guard
+        .as_mut()
+        .ok_or_else(|| AumateError::Clipboard("Clipboard not initialized".to_string()))?;
+
+    clipboard
+        .set()
+        .file_list(paths)
+        .map_err(|e| AumateError::Clipboard(format!("Failed to set clipboard files: {}", e)))
+}
+
+/// Get HTML from clipboard
+pub fn get_html() -> Result<String> {
+    let mut guard = get_or_init_clipboard()?;
+    let clipboard = guard
+        .as_mut()
+        .ok_or_else(|| AumateError::Clipboard("Clipboard not initialized".to_string()))?;
+
+    clipboard
+        .get()
+        .html()
+        .map_err(|e| AumateError::Clipboard(format!("Failed to get clipboard HTML: {}", e)))
+}
+
+/// Set HTML to clipboard, with optional plain text for applications without HTML support
+pub fn set_html(html: &str, alt_text: Option<&str>) -> Result<()> {
+    let mut guard = get_or_init_clipboard()?;
+    let clipboard = guard
+        .as_mut()
+        .ok_or_else(|| AumateError::Clipboard("Clipboard not initialized".to_string()))?;
+
+    clipboard
+        .set_html(html, alt_text)
+        .map_err(|e| AumateError::Clipboard(format!("Failed to set clipboard HTML: {}", e)))
+}
+
+/// Get Rich Text Format data from clipboard
+pub fn get_rtf() -> Result<String> {
+    let data = get_data(formats::RTF)?;
+    Ok(String::from_utf8_lossy(&data).into_owned())
+}
+
+/// Set Rich Text Format data to clipboard, with optional plain-text fallback
+pub fn set_rtf(rtf: &str, alt_text: Option<&str>) -> Result<()> {
+    let mut items = vec![(formats::RTF, rtf.as_bytes())];
+    if let Some(text) = alt_text {
+        items.push((formats::TEXT, text.as_bytes()));
+    }
+    set_data(&items)
+}
+
+/// List the formats on the clipboard as MIME types
+///
+/// Well-known native formats are reported under their MIME type (e.g. X11
+/// `UTF8_STRING` as `text/plain`); anything else keeps its native name.
+pub fn available_formats() -> Result<Vec<String>> {
+    native::available_formats()
+}
+
+/// Get the raw data of a format by MIME type (e.g. `text/rtf` or `application/x-myapp`)
+pub fn get_data(mime: &str) -> Result<Vec<u8>> {
+    native::get_data(mime)
+}
+
+/// Replace the clipboard contents with one or more formats given as `(mime, data)`
+///
+/// All formats are offered together, so pasting applications pick the richest
+/// one they understand.
+pub fn set_data(items: &[(&str, &[u8])]) -> Result<()> {
+    if items.is_empty() {
+        return Err(AumateError::Clipboard("No clipboard data to set".to_string()));
+    }
+    native::set_data(
+        items.iter().map(|&(mime, data)| (mime.trim().to_string(), data.to_vec())).collect(),
+    )
+}
+
+/// Clear clipboard
+pub fn clear() -> Result<()> {
+    let mut guard = get_or_init_clipboard()?;
+    let clipboard = guard
+        .as_mut()
+        .ok_or_else(|| AumateError::Clipboard("Clipboard not initialized".to_string()))?;
+
+    clipboard
+        .clear()
+        .map_err(|e| AumateError::Clipboard(format!("Failed to clear clipboard: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Helper to check if display server is available
+    fn has_display() -> bool {
+        // Check for X11 or Wayland display
+        std::env::var("DISPLAY").is_ok() || std::env::var("WAYLAND_DISPLAY").is_ok()
+    }
+
+    #[test]
+    fn test_clipboard_text() {
+        if !has_display() {
+            eprintln!("Skipping clipboard test: no display server available");
+            return;
+        }
+
+        let test_text = "Hello, clipboard!";
+        set_text(test_text).unwrap();
+
+        let retrieved = get_text().unwrap();
+        assert_eq!(retrieved, test_text);
+    }
+
+    #[test]
+    fn test_clipboard_image_png_roundtrip() {
+        let image = ClipboardImage {
+            width: 2,
+            height: 2,
+            rgba: vec![255, 0, 0, 255, 0, 255, 0, 128, 0, 0, 255, 0, 1, 2, 3, 4],
+        };
+        let png = image.to_png().unwrap();
+        let decoded = image::load_from_memory(&png).unwrap().to_rgba8();
+        assert_eq!(decoded.dimensions(), (2, 2));
+        assert_eq!(decoded.into_raw(), image.rgba);
+
+        let truncated = ClipboardImage { rgba: vec![0; 3], ..image };
+        assert!(truncated.to_png().is_err());
+    }
+
+    #[test]
+    fn test_clipboard_files() {
+        if !has_display() {
+            eprintln!("Skipping clipboard test: no display server available");
+            return;
+        }
+
+        let path = std::env::temp_dir().join("aumate clipboard test.txt");
+        set_files(&[&path]).unwrap();
+        assert_eq!(get_files().unwrap(), vec![path]);
+        assert!(set_files(&[] as &[&Path]).is_err());
+    }
+
+    #[test]
+    fn test_clipboard_custom_formats() {
+        if !has_display() {
+            eprintln!("Skipping clipboard test: no display server available");
+            return;
+        }
+
+        let custom = "application/x-aumate-test";
+        set_data(&[
+            (formats::RTF, br"{\rtf1 hello}"),
+            (custom, &[0, 1, 2]),
+            (formats::TEXT, b"hello"),
+        ])
+        .unwrap();
+
+        let available = available_formats().unwrap();
+        assert!(available.iter().any(|f| f == formats::RTF));
+        assert!(available.iter().any(|f| f == custom));
+        assert_eq!(get_data(custom).unwrap(), vec![0, 1, 2]);
+        assert_eq!(get_rtf().unwrap(), r"{\rtf1 hello}");
+        assert_eq!(get_text().unwrap(), "hello");
+        assert!(set_data(&[]).is_err());
+    }
+
+    #[test]
+    fn test_clipboard_html() {
+        if !has_display() {
+            eprintln!("Skipping clipboard test: no display server available");
+            return;
+        }
+
+        set_html("<b>bold</b>", Some("bold")).unwrap();
+        assert!(get_html().unwrap().contains("<b>bold</b>"));
+        assert_eq!(get_text().unwrap(), "bold");
+    }
+
+    #[test]
+    fn test_clear_clipboard() {
+        if !has_display() {
+            eprintln!("Skipping clipboard test: no display server available");
+            return;
+        }
+
+        set_text("test").unwrap();
+        clear().unwrap();
+        // Getting clipboard might fail or return empty string after clear
+        let _ = get_text();
+    }
+}
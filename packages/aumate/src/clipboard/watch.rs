@@ -0,0 +1,269 @@
+//! Clipboard change notifications
+//!
+//! [`watch`] starts a background thread that reports every clipboard change.
+//! On X11 the thread sleeps until the XFixes extension announces a new
+//! selection owner. Elsewhere, or when XFixes is unavailable, it polls a cheap
+//! change token: `GetClipboardSequenceNumber` on Windows, the pasteboard
+//! `changeCount` on macOS and a content fingerprint on X11.
+
+use super::native;
+use crate::error::Result;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// How changes are detected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchBackend {
+    /// X11 XFixes selection notifications
+    XFixes,
+    /// Periodic comparison of a change token
+    Polling,
+}
+
+/// A clipboard change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardChange {
+    /// Number of changes seen by this watcher, starting at 1
+    pub sequence: u64,
+    /// MIME types on the clipboard after the change (empty if they could not be read)
+    pub formats: Vec<String>,
+}
+
+/// Options for [`watch_with`]
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// Polling interval, also the longest delay before a stopped watcher exits
+    pub poll_interval: Duration,
+    /// Poll even where change notifications are available
+    pub force_polling: bool,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self { poll_interval: Duration::from_millis(250), force_polling: false }
+    }
+}
+
+impl WatchOptions {
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn with_force_polling(mut self, force_polling: bool) -> Self {
+        self.force_polling = force_polling;
+        self
+    }
+}
+
+/// Stops a [`ClipboardWatcher`] from another thread
+#[derive(Debug, Clone)]
+pub struct ClipboardWatchHandle {
+    stopped: Arc<AtomicBool>,
+}
+
+impl ClipboardWatchHandle {
+    /// Stop watching; pending changes can still be received
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
+}
+
+/// Receiver of clipboard changes
+///
+/// Changes are queued until received, so none are lost between calls. The
+/// background thread exits when the watcher is stopped or dropped.
+pub struct ClipboardWatcher {
+    receiver: mpsc::UnboundedReceiver<ClipboardChange>,
+    handle: ClipboardWatchHandle,
+    backend: WatchBackend,
+}
+
+impl ClipboardWatcher {
+    /// Wait for the next change, `None` once the watcher has stopped
+    pub async fn recv(&mut self) -> Option<ClipboardChange> {
+        self.receiver.recv().await
+    }
+
+    /// Poll for the next change, for use in hand-written futures and streams
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<ClipboardChange>> {
+        self.receiver.poll_recv(cx)
+    }
+
+    /// Block the current thread until the next change
+    ///
+    /// Must not be called from within an async runtime.
+    pub fn blocking_recv(&mut self) -> Option<ClipboardChange> {
+        self.receiver.blocking_recv()
+    }
+
+    /// Next queued change, without waiting
+    pub fn try_recv(&mut self) -> Option<ClipboardChange> {
+        self.receiver.try_recv().ok()
+    }
+
+    pub fn backend(&self) -> WatchBackend {
+        self.backend
+    }
+
+    pub fn handle(&self) -> ClipboardWatchHandle {
+        self.handle.clone()
+    }
+
+    pub fn stop(&self) {
+        self.handle.stop();
+    }
+}
+
+impl Drop for ClipboardWatcher {
+    fn drop(&mut self) {
+        self.handle.stop();
+    }
+}
+
+/// Watch the clipboard with default options
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example() -> aumate::error::Result<()> {
+/// let mut watcher = aumate::clipboard::watch()?;
+/// while let Some(change) = watcher.recv().await {
+///     println!("clipboard changed: {:?}", change.formats);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn watch() -> Result<ClipboardWatcher> {
+    watch_with(WatchOptions::default())
+}
+
+/// Watch the clipboard
+pub fn watch_with(options: WatchOptions) -> Result<ClipboardWatcher> {
+    #[cfg(target_os = "linux")]
+    if !options.force_polling {
+        match native::SelectionEvents::subscribe() {
+            Ok(events) => {
+                let interval = options.poll_interval;
+                return Ok(spawn(WatchBackend::XFixes, move |stopped, notify| {
+                    while !stopped.load(Ordering::SeqCst) {
+                        match events.wait(interval) {
+                            Ok(true) => {
+                                if !notify() {
+                                    return;
+                                }
+                            }
+                            Ok(false) => {}
+                            Err(e) => {
+                                log::warn!("Clipboard watcher stopped: {}", e);
+                                return;
+                            }
+                        }
+                    }
+                }));
+            }
+            Err(e) => log::debug!("XFixes unavailable, polling the clipboard: {}", e),
+        }
+    }
+
+    Ok(spawn_polling(options.poll_interval, native::change_token))
+}
+
+/// Poll `token` and report whenever it changes
+fn spawn_polling<F>(interval: Duration, mut token: F) -> ClipboardWatcher
+where
+    F: FnMut() -> Result<u64> + Send + 'static,
+{
+    spawn(WatchBackend::Polling, move |stopped, notify| {
+        // The clipboard may be temporarily locked by another application
+        let mut last = token().ok();
+        while !stopped.load(Ordering::SeqCst) {
+            std::thread::sleep(interval);
+            let current = match token() {
+                Ok(current) => current,
+                Err(e) => {
+                    log::trace!("Failed to read clipboard change token: {}", e);
+                    continue;
+                }
+            };
+            if last != Some(current) {
+                last = Some(current);
+                if !notify() {
+                    return;
+                }
+            }
+        }
+    })
+}
+
+/// Run `detect` on a background thread; it calls `notify` for each change and
+/// stops when `notify` returns `false` (the watcher was dropped)
+fn spawn<F>(backend: WatchBackend, detect: F) -> ClipboardWatcher
+where
+    F: FnOnce(&AtomicBool, &mut dyn FnMut() -> bool) + Send + 'static,
+{
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let stopped = Arc::new(AtomicBool::new(false));
+    let handle = ClipboardWatchHandle { stopped: stopped.clone() };
+
+    std::thread::spawn(move || {
+        let mut sequence = 0;
+        let mut notify = || {
+            sequence += 1;
+            let formats = super::available_formats().unwrap_or_default();
+            sender.send(ClipboardChange { sequence, formats }).is_ok()
+        };
+        detect(&stopped, &mut notify);
+    });
+
+    ClipboardWatcher { receiver, handle, backend }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU64;
+
+    #[test]
+    fn test_polling_reports_each_change() {
+        let counter = Arc::new(AtomicU64::new(0));
+        let token = {
+            let counter = counter.clone();
+            move || Ok(counter.load(Ordering::SeqCst))
+        };
+        let mut watcher = spawn_polling(Duration::from_millis(5), token);
+        assert_eq!(watcher.backend(), WatchBackend::Polling);
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(watcher.try_recv().is_none());
+
+        counter.store(1, Ordering::SeqCst);
+        assert_eq!(watcher.blocking_recv().map(|change| change.sequence), Some(1));
+        counter.store(2, Ordering::SeqCst);
+        assert_eq!(watcher.blocking_recv().map(|change| change.sequence), Some(2));
+
+        watcher.stop();
+        assert!(watcher.handle().is_stopped());
+        // The thread exits and closes the channel
+        while watcher.blocking_recv().is_some() {}
+    }
+
+    #[tokio::test]
+    async fn test_recv_async() {
+        let counter = Arc::new(AtomicU64::new(0));
+        let token = {
+            let counter = counter.clone();
+            move || Ok(counter.fetch_add(1, Ordering::SeqCst) / 3)
+        };
+        let mut watcher = spawn_polling(Duration::from_millis(1), token);
+        let change = tokio::time::timeout(Duration::from_secs(5), watcher.recv()).await.unwrap();
+        assert_eq!(change.unwrap().sequence, 1);
+    }
+}
@@ -0,0 +1,173 @@
+//! Windows clipboard access through clipboard formats
+//!
+//! Custom formats are registered under their MIME type. Text is stored as
+//! `CF_UNICODETEXT` and HTML in the `CF_HTML` envelope other applications expect.
+
+use super::formats;
+use crate::error::{AumateError, Result};
+use clipboard_win::{Clipboard, raw};
+
+const CF_TEXT: u32 = 1;
+const CF_BITMAP: u32 = 2;
+const CF_OEMTEXT: u32 = 7;
+const CF_DIB: u32 = 8;
+const CF_UNICODETEXT: u32 = 13;
+const CF_HDROP: u32 = 15;
+const CF_LOCALE: u32 = 16;
+const CF_DIBV5: u32 = 17;
+
+fn win_error(e: impl std::fmt::Display) -> AumateError {
+    AumateError::Clipboard(format!("Windows clipboard error: {}", e))
+}
+
+fn open() -> Result<Clipboard> {
+    Clipboard::new_attempts(10).map_err(win_error)
+}
+
+fn format_name(format: u32) -> Option<String> {
+    let name = match format {
+        CF_TEXT => "CF_TEXT",
+        CF_BITMAP => "CF_BITMAP",
+        CF_OEMTEXT => "CF_OEMTEXT",
+        CF_DIB => "CF_DIB",
+        CF_UNICODETEXT => "CF_UNICODETEXT",
+        CF_HDROP => "CF_HDROP",
+        CF_DIBV5 => "CF_DIBV5",
+        // Implied by the text formats
+        CF_LOCALE => return None,
+        _ => return raw::format_name_big(format),
+    };
+    Some(name.to_string())
+}
+
+/// Clipboard format id for a native name
+fn format_id(name: &str) -> Option<u32> {
+    match name {
+        "CF_UNICODETEXT" => Some(CF_UNICODETEXT),
+        "CF_TEXT" | "CF_OEMTEXT" | "CF_BITMAP" | "CF_DIB" | "CF_DIBV5" | "CF_HDROP" => None,
+        _ => raw::register_format(name).map(|id| id.get()),
+    }
+}
+
+/// Clipboard sequence number, incremented by the system on every change
+pub(super) fn change_token() -> Result<u64> {
+    raw::seq_num().map(|seq| u64::from(seq.get())).ok_or_else(|| {
+        AumateError::Clipboard("Clipboard sequence number is not available".to_string())
+    })
+}
+
+/// MIME types of everything on the clipboard
+pub(super) fn available_formats() -> Result<Vec<String>> {
+    let _clipboard = open()?;
+    Ok(formats::to_mime_list(raw::EnumFormats::new().filter_map(format_name)))
+}
+
+/// Raw data of a format
+///
+/// Predefined formats other than text (bitmaps, file drops) have dedicated
+/// accessors and are not returned here.
+pub(super) fn get_data(mime: &str) -> Result<Vec<u8>> {
+    let _clipboard = open()?;
+
+    for name in formats::native_names(mime) {
+        let Some(format) = format_id(name) else {
+            continue;
+        };
+        if !raw::is_format_avail(format) {
+            continue;
+        }
+
+        let mut data = Vec::new();
+        raw::get_vec(format, &mut data).map_err(win_error)?;
+        return Ok(match format {
+            CF_UNICODETEXT => decode_utf16(&data).into_bytes(),
+            _ if name == "HTML Format" => extract_cf_html(&data),
+            _ => data,
+        });
+    }
+    Err(AumateError::Clipboard(format!("Clipboard has no {} data", mime)))
+}
+
+/// Replace the clipboard contents with the given formats
+pub(super) fn set_data(items: Vec<(String, Vec<u8>)>) -> Result<()> {
+    let _clipboard = open()?;
+    raw::empty().map_err(win_error)?;
+
+    for (mime, data) in items {
+        let name = formats::native_names(&mime)[0];
+        let format = format_id(name)
+            .ok_or_else(|| AumateError::Clipboard(format!("Cannot write {} as raw data", mime)))?;
+        let data = match format {
+            CF_UNICODETEXT => encode_utf16(&String::from_utf8_lossy(&data)),
+            _ if name == "HTML Format" => wrap_cf_html(&String::from_utf8_lossy(&data)),
+            _ => data,
+        };
+        raw::set_without_clear(format, &data).map_err(win_error)?;
+    }
+    Ok(())
+}
+
+fn decode_utf16(data: &[u8]) -> String {
+    let units: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .take_while(|&unit| unit != 0)
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+fn encode_utf16(text: &str) -> Vec<u8> {
+    text.encode_utf16().chain(std::iter::once(0)).flat_map(u16::to_le_bytes).collect()
+}
+
+/// Wrap an HTML fragment in the `CF_HTML` header
+fn wrap_cf_html(html: &str) -> Vec<u8> {
+    const HEADER_LEN: usize = 105;
+    const PREFIX: &str = "<html><body>\r\n<!--StartFragment-->";
+    const SUFFIX: &str = "<!--EndFragment-->\r\n</body></html>";
+
+    let start_html = HEADER_LEN;
+    let start_fragment = start_html + PREFIX.len();
+    let end_fragment = start_fragment + html.len();
+    let end_html = end_fragment + SUFFIX.len();
+
+    let header = format!(
+        "Version:0.9\r\nStartHTML:{:010}\r\nEndHTML:{:010}\r\nStartFragment:{:010}\r\nEndFragment:{:010}\r\n",
+        start_html, end_html, start_fragment, end_fragment
+    );
+    debug_assert_eq!(header.len(), HEADER_LEN);
+    format!("{}{}{}{}", header, PREFIX, html, SUFFIX).into_bytes()
+}
+
+/// Extract the fragment from `CF_HTML` data
+fn extract_cf_html(data: &[u8]) -> Vec<u8> {
+    let text = String::from_utf8_lossy(data);
+    let offset = |key: &str| -> Option<usize> {
+        let line = text.lines().find_map(|line| line.strip_prefix(key))?;
+        line.trim().parse().ok()
+    };
+    match (offset("StartFragment:"), offset("EndFragment:")) {
+        (Some(start), Some(end)) if start <= end && end <= data.len() => data[start..end].to_vec(),
+        _ => data.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cf_html_roundtrip() {
+        let html = "<b>bold</b> text";
+        let wrapped = wrap_cf_html(html);
+        assert!(
+            String::from_utf8_lossy(&wrapped).starts_with("Version:0.9\r\nStartHTML:0000000105")
+        );
+        assert_eq!(extract_cf_html(&wrapped), html.as_bytes());
+    }
+
+    #[test]
+    fn test_utf16_roundtrip() {
+        assert_eq!(decode_utf16(&encode_utf16("héllo ✓")), "héllo ✓");
+    }
+}
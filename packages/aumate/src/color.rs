@@ -0,0 +1,303 @@
+//! Color picking
+//!
+//! Text formats (HEX/RGB/HSL/OKLCH) for [`PixelColor`] and a [`ColorPicker`]
+//! session that captures every monitor once, so moving the cursor only reads
+//! from memory and the magnifier can be sampled at interactive rates.
+
+use image::RgbaImage;
+
+use crate::error::{AumateError, Result};
+use crate::screen::{MonitorImage, PixelColor, capture_monitor_images};
+
+/// Default magnifier radius, the grid is `2 * radius + 1` pixels wide
+pub const DEFAULT_MAGNIFIER_RADIUS: u32 = 7;
+
+/// Largest supported magnifier radius
+pub const MAX_MAGNIFIER_RADIUS: u32 = 32;
+
+/// Colors below this OKLCH chroma are treated as gray, their hue is 0
+const ACHROMATIC_CHROMA: f64 = 1e-4;
+
+/// A color as copyable text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorFormats {
+    /// `#RRGGBB`
+    pub hex: String,
+    /// `rgb(255, 0, 0)`
+    pub rgb: String,
+    /// `hsl(0, 100%, 50%)`
+    pub hsl: String,
+    /// `oklch(62.8% 0.258 29.2)`
+    pub oklch: String,
+}
+
+impl PixelColor {
+    /// Convert to CSS rgb string (e.g., "rgb(255, 0, 0)")
+    pub fn to_rgb_string(&self) -> String {
+        format!("rgb({}, {}, {})", self.r, self.g, self.b)
+    }
+
+    /// Convert to HSL, hue in degrees, saturation and lightness in 0..=1
+    pub fn to_hsl(&self) -> (f64, f64, f64) {
+        let [r, g, b] = [self.r, self.g, self.b].map(|c| c as f64 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+        let l = (max + min) / 2.0;
+        if delta == 0.0 {
+            return (0.0, 0.0, l);
+        }
+
+        let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        (h, s, l)
+    }
+
+    /// Convert to CSS hsl string (e.g., "hsl(0, 100%, 50%)")
+    pub fn to_hsl_string(&self) -> String {
+        let (h, s, l) = self.to_hsl();
+        format!(
+            "hsl({}, {}%, {}%)",
+            h.round() as u32 % 360,
+            (s * 100.0).round(),
+            (l * 100.0).round()
+        )
+    }
+
+    /// Convert to OKLCH via OKLab, lightness in 0..=1 and hue in degrees
+    pub fn to_oklch(&self) -> (f64, f64, f64) {
+        let [r, g, b] = [self.r, self.g, self.b].map(srgb_to_linear);
+
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+        let lightness = 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s;
+        let a = 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s;
+        let b = 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s;
+
+        let chroma = a.hypot(b);
+        let hue = if chroma < ACHROMATIC_CHROMA {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        };
+        (lightness, chroma, hue)
+    }
+
+    /// Convert to CSS oklch string (e.g., "oklch(62.8% 0.258 29.2)")
+    pub fn to_oklch_string(&self) -> String {
+        let (l, c, h) = self.to_oklch();
+        format!("oklch({:.1}% {:.3} {:.1})", l * 100.0, c, h)
+    }
+
+    /// All text formats at once
+    pub fn formats(&self) -> ColorFormats {
+        ColorFormats {
+            hex: self.to_hex(),
+            rgb: self.to_rgb_string(),
+            hsl: self.to_hsl_string(),
+            oklch: self.to_oklch_string(),
+        }
+    }
+}
+
+fn srgb_to_linear(c: u8) -> f64 {
+    let c = c as f64 / 255.0;
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+/// Pixel grid around the cursor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Magnifier {
+    pub radius: u32,
+    /// Grid width and height, `2 * radius + 1`
+    pub size: u32,
+    /// Row-major RGBA, pixels outside every monitor are fully transparent
+    pub pixels: Vec<u8>,
+}
+
+impl Magnifier {
+    /// Color of a grid cell
+    pub fn color_at(&self, column: u32, row: u32) -> Option<PixelColor> {
+        if column >= self.size || row >= self.size {
+            return None;
+        }
+        let offset = ((row * self.size + column) * 4) as usize;
+        let p = &self.pixels[offset..offset + 4];
+        Some(PixelColor::new(p[0], p[1], p[2], p[3]))
+    }
+}
+
+/// Color picking session over one capture per monitor
+///
+/// Coordinates are screen coordinates as reported by the monitors. On HiDPI
+/// screens the capture has more pixels than the monitor bounds, positions
+/// are mapped by the ratio between the two.
+#[derive(Debug, Clone, Default)]
+pub struct ColorPicker {
+    captures: Vec<MonitorImage>,
+}
+
+impl ColorPicker {
+    /// Capture all monitors and start a session
+    pub fn new() -> Result<Self> {
+        let captures = capture_monitor_images()?;
+        if captures.is_empty() {
+            return Err(AumateError::Screen("No monitors found".to_string()));
+        }
+        Ok(Self { captures })
+    }
+
+    /// Start a session over existing captures
+    pub fn from_captures(captures: Vec<MonitorImage>) -> Self {
+        Self { captures }
+    }
+
+    /// Color at screen position `(x, y)`, None outside every monitor
+    pub fn color_at(&self, x: i32, y: i32) -> Option<PixelColor> {
+        let (image, px, py) = self.locate(x, y)?;
+        pixel_at(image, px, py)
+    }
+
+    /// Magnifier grid centered on screen position `(x, y)`
+    pub fn magnifier(&self, x: i32, y: i32, radius: u32) -> Result<Magnifier> {
+        if radius > MAX_MAGNIFIER_RADIUS {
+            return Err(AumateError::Other(format!(
+                "Magnifier radius must not exceed {}",
+                MAX_MAGNIFIER_RADIUS
+            )));
+        }
+        let (image, cx, cy) = self.locate(x, y).ok_or_else(|| {
+            AumateError::Screen(format!("Position ({}, {}) is outside every monitor", x, y))
+        })?;
+
+        let size = radius * 2 + 1;
+        let radius = radius as i64;
+        let mut pixels = Vec::with_capacity((size * size * 4) as usize);
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                let color =
+                    pixel_at(image, cx + dx, cy + dy).unwrap_or(PixelColor::new(0, 0, 0, 0));
+                pixels.extend_from_slice(&[color.r, color.g, color.b, color.a]);
+            }
+        }
+        Ok(Magnifier { radius: radius as u32, size, pixels })
+    }
+
+    /// Find the capture containing `(x, y)` and map to its pixel coordinates
+    fn locate(&self, x: i32, y: i32) -> Option<(&RgbaImage, i64, i64)> {
+        self.captures.iter().find_map(|capture| {
+            let dx = x as i64 - capture.x as i64;
+            let dy = y as i64 - capture.y as i64;
+            if dx < 0 || dy < 0 || dx >= capture.width as i64 || dy >= capture.height as i64 {
+                return None;
+            }
+            let scale_x = capture.image.width() as f64 / capture.width as f64;
+            let scale_y = capture.image.height() as f64 / capture.height as f64;
+            Some((
+                &capture.image,
+                (dx as f64 * scale_x).floor() as i64,
+                (dy as f64 * scale_y).floor() as i64,
+            ))
+        })
+    }
+}
+
+fn pixel_at(image: &RgbaImage, x: i64, y: i64) -> Option<PixelColor> {
+    if x < 0 || y < 0 || x >= image.width() as i64 || y >= image.height() as i64 {
+        return None;
+    }
+    let [r, g, b, a] = image.get_pixel(x as u32, y as u32).0;
+    Some(PixelColor::new(r, g, b, a))
+}
+
+/// Recently picked colors, newest first and without duplicates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorHistory {
+    colors: Vec<PixelColor>,
+    capacity: usize,
+}
+
+impl ColorHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self { colors: Vec::new(), capacity: capacity.max(1) }
+    }
+
+    /// Add a color to the front, moving it there if already present
+    pub fn push(&mut self, color: PixelColor) {
+        self.colors.retain(|c| *c != color);
+        self.colors.insert(0, color);
+        self.colors.truncate(self.capacity);
+    }
+
+    pub fn colors(&self) -> &[PixelColor] {
+        &self.colors
+    }
+
+    pub fn clear(&mut self) {
+        self.colors.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_formats() {
+        let red = PixelColor::new(255, 0, 0, 255).formats();
+        assert_eq!(red.hex, "#FF0000");
+        assert_eq!(red.rgb, "rgb(255, 0, 0)");
+        assert_eq!(red.hsl, "hsl(0, 100%, 50%)");
+        assert_eq!(red.oklch, "oklch(62.8% 0.258 29.2)");
+
+        let gray = PixelColor::new(128, 128, 128, 255);
+        assert_eq!(gray.to_hsl_string(), "hsl(0, 0%, 50%)");
+        assert_eq!(gray.to_oklch().2, 0.0);
+    }
+
+    #[test]
+    fn test_picker_maps_hidpi_capture() {
+        // 100x50 monitor left of the primary one, captured at 2x
+        let image = RgbaImage::from_fn(200, 100, |x, y| image::Rgba([x as u8, y as u8, 0, 255]));
+        let picker = ColorPicker::from_captures(vec![MonitorImage {
+            x: -100,
+            y: 0,
+            width: 100,
+            height: 50,
+            image,
+        }]);
+
+        assert_eq!(picker.color_at(-90, 5), Some(PixelColor::new(20, 10, 0, 255)));
+        assert_eq!(picker.color_at(0, 0), None);
+
+        let magnifier = picker.magnifier(-100, 0, 1).unwrap();
+        assert_eq!(magnifier.size, 3);
+        assert_eq!(magnifier.color_at(0, 0), Some(PixelColor::new(0, 0, 0, 0)));
+        assert_eq!(magnifier.color_at(2, 2), Some(PixelColor::new(1, 1, 0, 255)));
+        assert!(picker.magnifier(-90, 5, MAX_MAGNIFIER_RADIUS + 1).is_err());
+        assert!(picker.magnifier(10, 5, 1).is_err());
+    }
+
+    #[test]
+    fn test_color_history() {
+        let red = PixelColor::new(255, 0, 0, 255);
+        let blue = PixelColor::new(0, 0, 255, 255);
+
+        let mut history = ColorHistory::new(2);
+        history.push(red);
+        history.push(blue);
+        history.push(red);
+        assert_eq!(history.colors(), &[red, blue]);
+
+        history.clear();
+        assert!(history.colors().is_empty());
+    }
+}
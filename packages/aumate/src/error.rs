@@ -43,6 +43,16 @@ pub enum AumateError {
     #[error("ML error: {0}")]
     Ml(String),
 
+    /// Barcode scanning and generation errors
+    #[cfg(feature = "barcode")]
+    #[error("Barcode error: {0}")]
+    Barcode(String),
+
+    /// Visual comparison errors
+    #[cfg(feature = "visual")]
+    #[error("Visual diff error: {0}")]
+    Visual(String),
+
     /// Generic errors
     #[error("{0}")]
     Other(String),
@@ -0,0 +1,394 @@
+//! Controller framework for the control panel
+//!
+//! Each feature (STT, OCR, ...) implements [`ControllerFeature`] and is shown
+//! as one tab. [`Controller`] owns the features, keeps them sorted by tab
+//! order and tracks which tab is selected, so the panel itself only has to
+//! forward frames to it.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::error::Result;
+
+/// Tab metadata for a controller feature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TabInfo {
+    /// Unique tab identifier
+    pub id: &'static str,
+    /// Label shown in the tab bar
+    pub label: &'static str,
+    /// Sort order, lower values come first
+    pub order: i32,
+}
+
+impl TabInfo {
+    pub fn new(id: &'static str, label: &'static str, order: i32) -> Self {
+        Self { id, label, order }
+    }
+}
+
+/// Per-frame context shared with features
+#[derive(Debug, Default)]
+pub struct ControllerContext {
+    repaint_requested: bool,
+}
+
+impl ControllerContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask the panel to draw another frame, e.g. after a background task finished
+    pub fn request_repaint(&mut self) {
+        self.repaint_requested = true;
+    }
+
+    pub fn repaint_requested(&self) -> bool {
+        self.repaint_requested
+    }
+
+    /// Return and clear the pending repaint request
+    pub fn take_repaint_request(&mut self) -> bool {
+        std::mem::take(&mut self.repaint_requested)
+    }
+}
+
+/// A feature shown as a tab in the control panel
+pub trait ControllerFeature {
+    /// Unique feature identifier
+    fn id(&self) -> &'static str;
+
+    /// Tab label and position
+    fn tab_info(&self) -> TabInfo;
+
+    /// Called once when the feature is registered with a running panel
+    fn initialize(&mut self, _ctx: &mut ControllerContext) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called every frame for every feature, including hidden tabs
+    fn update(&mut self, _ctx: &mut ControllerContext) {}
+
+    /// Draw the tab content, only called for the selected tab
+    fn render(&mut self, ui: &mut egui::Ui, ctx: &mut ControllerContext);
+
+    /// Called once when the panel closes
+    fn shutdown(&mut self) {}
+}
+
+/// Result slot for work running on a background thread
+///
+/// The UI thread hands [`AsyncTask::callback`] to the worker and polls
+/// [`AsyncTask::take`] each frame, so heavy operations like model loading
+/// never block rendering.
+pub struct AsyncTask<T> {
+    result: Arc<Mutex<Option<T>>>,
+}
+
+impl<T: Send + 'static> AsyncTask<T> {
+    pub fn new() -> Self {
+        Self { result: Arc::new(Mutex::new(None)) }
+    }
+
+    /// Callback that stores the result, to be moved into the worker thread
+    pub fn callback(&self) -> impl FnOnce(T) + Send + 'static {
+        let result = Arc::clone(&self.result);
+        move |value| {
+            *result.lock().unwrap() = Some(value);
+        }
+    }
+
+    /// Whether the worker has delivered a result that was not taken yet
+    pub fn is_ready(&self) -> bool {
+        self.result.lock().unwrap().is_some()
+    }
+
+    /// Take the result if the worker has finished
+    pub fn take(&self) -> Option<T> {
+        self.result.lock().unwrap().take()
+    }
+}
+
+impl<T: Send + 'static> Default for AsyncTask<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Owns the features and the tab selection of the control panel
+#[derive(Default)]
+pub struct Controller {
+    features: Vec<Box<dyn ControllerFeature>>,
+    /// Selected feature id, the first tab when unset
+    selected: Option<&'static str>,
+    context: ControllerContext,
+    initialized: bool,
+    /// Features whose `initialize` failed, by id
+    errors: HashMap<&'static str, String>,
+}
+
+impl Controller {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_feature(mut self, feature: impl ControllerFeature + 'static) -> Self {
+        self.add_feature(Box::new(feature));
+        self
+    }
+
+    /// Register a feature, keeping tabs sorted by order
+    ///
+    /// A feature whose id is already registered is ignored. Features added
+    /// after [`Controller::initialize`] are initialized right away.
+    pub fn add_feature(&mut self, mut feature: Box<dyn ControllerFeature>) {
+        let id = feature.id();
+        if self.features.iter().any(|f| f.id() == id) {
+            log::warn!("Controller feature '{}' is already registered", id);
+            return;
+        }
+
+        if self.initialized {
+            self.initialize_feature(feature.as_mut());
+        }
+
+        let order = feature.tab_info().order;
+        let index = self.features.partition_point(|f| f.tab_info().order <= order);
+        self.features.insert(index, feature);
+    }
+
+    /// Tabs in display order
+    pub fn tabs(&self) -> Vec<TabInfo> {
+        self.features.iter().map(|f| f.tab_info()).collect()
+    }
+
+    pub fn selected_tab(&self) -> Option<TabInfo> {
+        self.features.get(self.selected_index()).map(|f| f.tab_info())
+    }
+
+    /// Select a tab by feature id, returns false if there is no such tab
+    pub fn select(&mut self, id: &str) -> bool {
+        match self.features.iter().find(|f| f.id() == id) {
+            Some(feature) => {
+                self.selected = Some(feature.id());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected.and_then(|id| self.features.iter().position(|f| f.id() == id)).unwrap_or(0)
+    }
+
+    /// Initialization error of a feature, shown in place of its tab content
+    pub fn error(&self, id: &str) -> Option<&str> {
+        self.errors.get(id).map(String::as_str)
+    }
+
+    pub fn context_mut(&mut self) -> &mut ControllerContext {
+        &mut self.context
+    }
+
+    /// Initialize all registered features
+    ///
+    /// A failing feature keeps its tab but shows the error instead of its content.
+    pub fn initialize(&mut self) {
+        if self.initialized {
+            return;
+        }
+        self.initialized = true;
+
+        let mut features = std::mem::take(&mut self.features);
+        for feature in &mut features {
+            self.initialize_feature(feature.as_mut());
+        }
+        self.features = features;
+    }
+
+    fn initialize_feature(&mut self, feature: &mut dyn ControllerFeature) {
+        if let Err(e) = feature.initialize(&mut self.context) {
+            log::error!("Failed to initialize controller feature '{}': {}", feature.id(), e);
+            self.errors.insert(feature.id(), e.to_string());
+        }
+    }
+
+    /// Run `update` on every working feature
+    pub fn update(&mut self) {
+        for feature in &mut self.features {
+            if !self.errors.contains_key(feature.id()) {
+                feature.update(&mut self.context);
+            }
+        }
+    }
+
+    /// Draw one frame: tab bar plus the selected tab
+    pub fn show(&mut self, ctx: &egui::Context) {
+        self.update();
+
+        let selected = self.selected_index();
+        egui::TopBottomPanel::top("controller_tabs").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                for (index, feature) in self.features.iter().enumerate() {
+                    if ui.selectable_label(index == selected, feature.tab_info().label).clicked() {
+                        self.selected = Some(feature.id());
+                    }
+                }
+            });
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let Some(feature) = self.features.get_mut(selected) else {
+                ui.label("No features enabled");
+                return;
+            };
+
+            if let Some(error) = self.errors.get(feature.id()) {
+                ui.colored_label(egui::Color32::RED, format!("Failed to initialize: {}", error));
+                return;
+            }
+
+            egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                feature.render(ui, &mut self.context);
+            });
+        });
+
+        if self.context.take_repaint_request() {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Shut down all features, called once when the panel closes
+    pub fn shutdown(&mut self) {
+        for feature in &mut self.features {
+            feature.shutdown();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AumateError;
+    use std::thread;
+
+    #[derive(Default)]
+    struct Counters {
+        updates: usize,
+        renders: usize,
+        shutdowns: usize,
+    }
+
+    struct TestFeature {
+        tab: TabInfo,
+        fail_init: bool,
+        counters: Arc<Mutex<Counters>>,
+    }
+
+    impl TestFeature {
+        fn new(id: &'static str, order: i32) -> Self {
+            Self {
+                tab: TabInfo::new(id, id, order),
+                fail_init: false,
+                counters: Arc::new(Mutex::new(Counters::default())),
+            }
+        }
+    }
+
+    impl ControllerFeature for TestFeature {
+        fn id(&self) -> &'static str {
+            self.tab.id
+        }
+
+        fn tab_info(&self) -> TabInfo {
+            self.tab
+        }
+
+        fn initialize(&mut self, _ctx: &mut ControllerContext) -> Result<()> {
+            if self.fail_init { Err(AumateError::Gui("no device".to_string())) } else { Ok(()) }
+        }
+
+        fn update(&mut self, ctx: &mut ControllerContext) {
+            self.counters.lock().unwrap().updates += 1;
+            ctx.request_repaint();
+        }
+
+        fn render(&mut self, ui: &mut egui::Ui, _ctx: &mut ControllerContext) {
+            self.counters.lock().unwrap().renders += 1;
+            ui.label(self.tab.label);
+        }
+
+        fn shutdown(&mut self) {
+            self.counters.lock().unwrap().shutdowns += 1;
+        }
+    }
+
+    fn run_frame(controller: &mut Controller) {
+        let ctx = egui::Context::default();
+        let _ = ctx.run(egui::RawInput::default(), |ctx| controller.show(ctx));
+    }
+
+    #[test]
+    fn test_async_task() {
+        let task = AsyncTask::new();
+        assert!(!task.is_ready());
+        assert_eq!(task.take(), None);
+
+        let callback = task.callback();
+        thread::spawn(move || callback(42)).join().unwrap();
+        assert!(task.is_ready());
+        assert_eq!(task.take(), Some(42));
+        assert_eq!(task.take(), None);
+    }
+
+    #[test]
+    fn test_tabs_sorted_and_selection_kept() {
+        let mut controller = Controller::new()
+            .with_feature(TestFeature::new("ocr", 50))
+            .with_feature(TestFeature::new("stt", 40));
+        let ids: Vec<_> = controller.tabs().iter().map(|t| t.id).collect();
+        assert_eq!(ids, ["stt", "ocr"]);
+        assert_eq!(controller.selected_tab().unwrap().id, "stt");
+
+        assert!(controller.select("ocr"));
+        assert!(!controller.select("missing"));
+
+        // Inserting a tab before the selection keeps the same tab selected
+        controller.add_feature(Box::new(TestFeature::new("clipboard", 30)));
+        assert_eq!(controller.selected_tab().unwrap().id, "ocr");
+
+        // Duplicate ids are ignored
+        controller.add_feature(Box::new(TestFeature::new("ocr", 10)));
+        assert_eq!(controller.tabs().len(), 3);
+    }
+
+    #[test]
+    fn test_failed_feature_is_disabled() {
+        let mut broken = TestFeature::new("stt", 40);
+        broken.fail_init = true;
+        let broken_counters = Arc::clone(&broken.counters);
+        let working = TestFeature::new("ocr", 50);
+        let working_counters = Arc::clone(&working.counters);
+
+        let mut controller = Controller::new().with_feature(broken).with_feature(working);
+        controller.initialize();
+        assert_eq!(controller.error("stt"), Some("GUI error: no device"));
+        assert_eq!(controller.error("ocr"), None);
+
+        // A feature that failed to initialize is neither updated nor rendered
+        run_frame(&mut controller);
+        assert_eq!(broken_counters.lock().unwrap().updates, 0);
+        assert_eq!(broken_counters.lock().unwrap().renders, 0);
+
+        controller.select("ocr");
+        run_frame(&mut controller);
+        assert_eq!(working_counters.lock().unwrap().updates, 2);
+        assert_eq!(working_counters.lock().unwrap().renders, 1);
+        // The repaint request was handed over to egui
+        assert!(!controller.context_mut().repaint_requested());
+
+        controller.shutdown();
+        assert_eq!(broken_counters.lock().unwrap().shutdowns, 1);
+        assert_eq!(working_counters.lock().unwrap().shutdowns, 1);
+    }
+}
@@ -0,0 +1,11 @@
+//! Control panel GUI
+//!
+//! A small egui window with one tab per feature. Features implement
+//! [`ControllerFeature`]; the STT and OCR tabs live next to their modules
+//! (`stt::SttFeature`, `ocr::OcrFeature`).
+
+pub mod controller;
+mod panel;
+
+pub use controller::{AsyncTask, Controller, ControllerContext, ControllerFeature, TabInfo};
+pub use panel::run_control_panel;
@@ -0,0 +1,246 @@
+//! Control panel window
+//!
+//! Runs a [`Controller`] in a winit window rendered with egui on wgpu.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use winit::application::ApplicationHandler;
+use winit::dpi::LogicalSize;
+use winit::event::WindowEvent;
+use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
+use winit::window::{Window, WindowId};
+
+use crate::error::{AumateError, Result};
+use crate::gui::controller::Controller;
+
+/// Upper bound between frames while idle
+///
+/// Downloads report progress through shared state without waking the UI,
+/// so the panel redraws at least this often to keep progress bars moving.
+const IDLE_REPAINT_INTERVAL: Duration = Duration::from_millis(250);
+
+const PANEL_TITLE: &str = "Aumate Controller";
+const PANEL_SIZE: LogicalSize<f64> = LogicalSize::new(640.0, 720.0);
+
+/// Open the control panel and block until it is closed
+///
+/// Must be called from the main thread.
+pub fn run_control_panel(controller: Controller) -> Result<()> {
+    let event_loop = EventLoop::new().map_err(|e| AumateError::Gui(e.to_string()))?;
+    let mut app = PanelApp { controller, surface: None, error: None };
+    event_loop.run_app(&mut app).map_err(|e| AumateError::Gui(e.to_string()))?;
+
+    app.controller.shutdown();
+    match app.error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+struct PanelApp {
+    controller: Controller,
+    surface: Option<PanelSurface>,
+    /// Fatal error that stopped the event loop
+    error: Option<AumateError>,
+}
+
+impl ApplicationHandler for PanelApp {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.surface.is_some() {
+            return;
+        }
+
+        match PanelSurface::new(event_loop) {
+            Ok(surface) => {
+                self.controller.initialize();
+                surface.window.request_redraw();
+                self.surface = Some(surface);
+            }
+            Err(e) => {
+                log::error!("Failed to open control panel: {}", e);
+                self.error = Some(e);
+                event_loop.exit();
+            }
+        }
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
+        let Some(surface) = self.surface.as_mut() else {
+            return;
+        };
+
+        let response = surface.egui_state.on_window_event(&surface.window, &event);
+        if response.repaint {
+            surface.window.request_redraw();
+        }
+
+        match event {
+            WindowEvent::CloseRequested => event_loop.exit(),
+            WindowEvent::Resized(size) => {
+                surface.resize(size.width, size.height);
+                surface.window.request_redraw();
+            }
+            WindowEvent::RedrawRequested => {
+                let repaint_delay = surface.redraw(&mut self.controller);
+                if repaint_delay.is_zero() {
+                    surface.window.request_redraw();
+                } else {
+                    let delay = repaint_delay.min(IDLE_REPAINT_INTERVAL);
+                    event_loop.set_control_flow(ControlFlow::WaitUntil(Instant::now() + delay));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        if let (Some(surface), ControlFlow::WaitUntil(deadline)) =
+            (&self.surface, event_loop.control_flow())
+        {
+            if Instant::now() >= deadline {
+                surface.window.request_redraw();
+                event_loop.set_control_flow(ControlFlow::Wait);
+            }
+        }
+    }
+}
+
+/// Window, GPU surface and egui renderer
+struct PanelSurface {
+    window: Arc<Window>,
+    surface: wgpu::Surface<'static>,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    config: wgpu::SurfaceConfiguration,
+    egui_ctx: egui::Context,
+    egui_state: egui_winit::State,
+    renderer: egui_wgpu::Renderer,
+}
+
+impl PanelSurface {
+    fn new(event_loop: &ActiveEventLoop) -> Result<Self> {
+        let attributes =
+            Window::default_attributes().with_title(PANEL_TITLE).with_inner_size(PANEL_SIZE);
+        let window = Arc::new(
+            event_loop.create_window(attributes).map_err(|e| AumateError::Gui(e.to_string()))?,
+        );
+
+        let instance = wgpu::Instance::default();
+        let surface = instance
+            .create_surface(Arc::clone(&window))
+            .map_err(|e| AumateError::Gui(format!("Failed to create surface: {}", e)))?;
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::LowPower,
+            compatible_surface: Some(&surface),
+            force_fallback_adapter: false,
+        }))
+        .map_err(|e| AumateError::Gui(format!("No suitable GPU adapter: {}", e)))?;
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default()))
+                .map_err(|e| AumateError::Gui(format!("Failed to create device: {}", e)))?;
+
+        let size = window.inner_size();
+        let config = surface
+            .get_default_config(&adapter, size.width.max(1), size.height.max(1))
+            .ok_or_else(|| AumateError::Gui("Surface is not supported by adapter".to_string()))?;
+        surface.configure(&device, &config);
+
+        let egui_ctx = egui::Context::default();
+        let egui_state = egui_winit::State::new(
+            egui_ctx.clone(),
+            egui::ViewportId::ROOT,
+            &window,
+            Some(window.scale_factor() as f32),
+            window.theme(),
+            Some(device.limits().max_texture_dimension_2d as usize),
+        );
+        let renderer =
+            egui_wgpu::Renderer::new(&device, config.format, egui_wgpu::RendererOptions::default());
+
+        Ok(Self { window, surface, device, queue, config, egui_ctx, egui_state, renderer })
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        self.config.width = width;
+        self.config.height = height;
+        self.surface.configure(&self.device, &self.config);
+    }
+
+    /// Draw one frame, returns how long egui wants to wait before the next one
+    fn redraw(&mut self, controller: &mut Controller) -> Duration {
+        let raw_input = self.egui_state.take_egui_input(&self.window);
+        let output = self.egui_ctx.run(raw_input, |ctx| controller.show(ctx));
+        self.egui_state.handle_platform_output(&self.window, output.platform_output);
+
+        let repaint_delay = output
+            .viewport_output
+            .get(&egui::ViewportId::ROOT)
+            .map_or(Duration::MAX, |viewport| viewport.repaint_delay);
+
+        let frame = match self.surface.get_current_texture() {
+            Ok(frame) => frame,
+            Err(wgpu::SurfaceError::Outdated | wgpu::SurfaceError::Lost) => {
+                self.surface.configure(&self.device, &self.config);
+                return Duration::ZERO;
+            }
+            Err(e) => {
+                log::warn!("Failed to acquire frame: {}", e);
+                return repaint_delay;
+            }
+        };
+        let view = frame.texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let primitives = self.egui_ctx.tessellate(output.shapes, output.pixels_per_point);
+        let screen = egui_wgpu::ScreenDescriptor {
+            size_in_pixels: [self.config.width, self.config.height],
+            pixels_per_point: output.pixels_per_point,
+        };
+        for (id, delta) in &output.textures_delta.set {
+            self.renderer.update_texture(&self.device, &self.queue, *id, delta);
+        }
+
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("controller_encoder"),
+        });
+        let commands = self.renderer.update_buffers(
+            &self.device,
+            &self.queue,
+            &mut encoder,
+            &primitives,
+            &screen,
+        );
+        {
+            let mut pass = encoder
+                .begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("controller_pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &view,
+                        resolve_target: None,
+                        depth_slice: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                })
+                .forget_lifetime();
+            self.renderer.render(&mut pass, &primitives, &screen);
+        }
+        self.queue.submit(commands.into_iter().chain([encoder.finish()]));
+        self.window.pre_present_notify();
+        frame.present();
+
+        for id in &output.textures_delta.free {
+            self.renderer.free_texture(id);
+        }
+
+        repaint_delay
+    }
+}
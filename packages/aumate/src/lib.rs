@@ -6,14 +6,22 @@
 //! - Clipboard text and image operations
 //! - Window management
 //! - Image template matching
+//! - On-screen text matching
+//! - Barcode scanning and QR code generation
+//! - Screenshot comparison for visual regression tests
 //!
 //! # Features
 //!
 //! - `input` - Mouse and keyboard control (enabled by default)
-//! - `screen` - Screen capture operations (enabled by default)
+//! - `screen` - Screen capture, pixel operations and color picking (enabled by default)
 //! - `clipboard` - Clipboard operations (enabled by default)
 //! - `window` - Window management (enabled by default)
 //! - `image_match` - Image template matching
+//! - `text_match` - Find and click text on screen with OCR
+//! - `annotate` - Draw annotations (arrows, text, mosaic, ...) onto images
+//! - `barcode` - Scan QR codes, DataMatrix and 1D barcodes, generate QR codes
+//! - `visual` - Compare screenshots and keep baseline directories for visual regression tests
+//! - `gui` - Control panel window (`aumate-controller`) with STT and OCR tabs
 //!
 //! # Example
 //!
@@ -37,6 +45,9 @@ pub mod input;
 #[cfg(feature = "screen")]
 pub mod screen;
 
+#[cfg(feature = "screen")]
+pub mod color;
+
 #[cfg(feature = "clipboard")]
 pub mod clipboard;
 
@@ -58,6 +69,21 @@ pub mod ocr;
 #[cfg(feature = "image_match")]
 pub mod image_match;
 
+#[cfg(feature = "text_match")]
+pub mod text_match;
+
+#[cfg(feature = "annotate")]
+pub mod annotate;
+
+#[cfg(feature = "barcode")]
+pub mod barcode;
+
+#[cfg(feature = "visual")]
+pub mod visual;
+
+#[cfg(feature = "gui")]
+pub mod gui;
+
 /// Prelude module for convenient imports
 pub mod prelude {
     pub use crate::error::{AumateError, Result};
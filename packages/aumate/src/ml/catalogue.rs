@@ -0,0 +1,195 @@
+//! Model catalogues
+//!
+//! A catalogue lists the models the [`ModelManager`](super::ModelManager) knows how
+//! to download and manage. The built-in catalogue covers Whisper, TrOCR and
+//! Silero VAD; applications can register additional catalogues for third-party
+//! models, either in code or from a JSON file.
+
+use super::model::{
+    ModelType, TROCR_FILES, TROCR_MODELS, VAD_MODEL_FILE, VAD_MODEL_ID, VAD_MODEL_SIZE,
+    VAD_MODEL_URL, WHISPER_FILES, WHISPER_MODELS, format_size,
+};
+use crate::error::{AumateError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Static description of a downloadable model
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelSpec {
+    /// Model identifier, unique per model type (e.g., "whisper-tiny")
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Description
+    #[serde(default)]
+    pub description: String,
+    /// Model type
+    pub model_type: ModelType,
+    /// Size in bytes (approximate)
+    #[serde(default)]
+    pub size_bytes: u64,
+    /// HuggingFace repository ID (empty for direct-download models)
+    #[serde(default)]
+    pub repo_id: String,
+    /// Direct download URL for models not hosted in a HuggingFace repository
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Files that make up the model
+    pub files: Vec<String>,
+}
+
+/// Source of model specifications
+pub trait ModelCatalogue: Send + Sync {
+    /// Catalogue name, used in logs
+    fn name(&self) -> &str;
+
+    /// All models provided by this catalogue
+    fn models(&self) -> Vec<ModelSpec>;
+}
+
+/// Catalogue of the models shipped with aumate
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuiltinCatalogue;
+
+impl ModelCatalogue for BuiltinCatalogue {
+    fn name(&self) -> &str {
+        "builtin"
+    }
+
+    fn models(&self) -> Vec<ModelSpec> {
+        let whisper = WHISPER_MODELS.iter().map(|(id, name, repo_id, size)| ModelSpec {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: format!("~{}", format_size(*size)),
+            model_type: ModelType::Whisper,
+            size_bytes: *size,
+            repo_id: repo_id.to_string(),
+            url: None,
+            files: WHISPER_FILES.iter().map(|s| s.to_string()).collect(),
+        });
+
+        let trocr = TROCR_MODELS.iter().map(|(id, name, desc, repo_id, size)| ModelSpec {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: desc.to_string(),
+            model_type: ModelType::TrOCR,
+            size_bytes: *size,
+            repo_id: repo_id.to_string(),
+            url: None,
+            files: TROCR_FILES.iter().map(|s| s.to_string()).collect(),
+        });
+
+        let vad = ModelSpec {
+            id: VAD_MODEL_ID.to_string(),
+            name: "Silero VAD".to_string(),
+            description: "Voice Activity Detection".to_string(),
+            model_type: ModelType::Vad,
+            size_bytes: VAD_MODEL_SIZE,
+            repo_id: String::new(),
+            url: Some(VAD_MODEL_URL.to_string()),
+            files: vec![VAD_MODEL_FILE.to_string()],
+        };
+
+        whisper.chain(trocr).chain(std::iter::once(vad)).collect()
+    }
+}
+
+/// Catalogue of third-party models defined at runtime
+///
+/// The JSON form is an array of [`ModelSpec`] objects:
+///
+/// ```json
+/// [{
+///   "id": "distil-whisper-small.en",
+///   "name": "Distil-Whisper Small (English)",
+///   "model_type": "whisper",
+///   "repo_id": "distil-whisper/distil-small.en",
+///   "files": ["model.safetensors", "config.json", "tokenizer.json"]
+/// }]
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CustomCatalogue {
+    name: String,
+    models: Vec<ModelSpec>,
+}
+
+impl CustomCatalogue {
+    /// Create an empty catalogue
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), models: Vec::new() }
+    }
+
+    /// Add a model to the catalogue
+    pub fn with_model(mut self, spec: ModelSpec) -> Self {
+        self.models.push(spec);
+        self
+    }
+
+    /// Parse a catalogue from a JSON array of model specs
+    pub fn from_json(name: impl Into<String>, json: &str) -> Result<Self> {
+        let models: Vec<ModelSpec> = serde_json::from_str(json)
+            .map_err(|e| AumateError::Ml(format!("Invalid model catalogue: {}", e)))?;
+        for spec in &models {
+            if spec.id.is_empty() || spec.files.is_empty() {
+                return Err(AumateError::Ml(format!(
+                    "Invalid model catalogue entry {:?}: id and files are required",
+                    spec.name
+                )));
+            }
+        }
+        Ok(Self { name: name.into(), models })
+    }
+
+    /// Load a catalogue from a JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        let name = path.display().to_string();
+        Self::from_json(name, &std::fs::read_to_string(path)?)
+    }
+}
+
+impl ModelCatalogue for CustomCatalogue {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn models(&self) -> Vec<ModelSpec> {
+        self.models.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_catalogue() {
+        let models = BuiltinCatalogue.models();
+        assert_eq!(models.len(), WHISPER_MODELS.len() + TROCR_MODELS.len() + 1);
+        let vad = models.iter().find(|m| m.model_type == ModelType::Vad).unwrap();
+        assert_eq!(vad.url.as_deref(), Some(VAD_MODEL_URL));
+    }
+
+    #[test]
+    fn test_custom_catalogue_from_json() {
+        let json = r#"[{
+            "id": "distil-small.en",
+            "name": "Distil-Whisper Small",
+            "model_type": "whisper",
+            "repo_id": "distil-whisper/distil-small.en",
+            "files": ["model.safetensors", "config.json", "tokenizer.json"]
+        }]"#;
+        let catalogue = CustomCatalogue::from_json("test", json).unwrap();
+        let models = catalogue.models();
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].model_type, ModelType::Whisper);
+        assert_eq!(models[0].url, None);
+
+        assert!(
+            CustomCatalogue::from_json(
+                "bad",
+                r#"[{"id": "x", "name": "x", "model_type": "vad", "files": []}]"#
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! Resumable model file transfer
+//!
+//! Downloads files over HTTP with range-request resumption, or copies them
+//! from a local mirror directory.
+
+use super::integrity::HubDigest;
+use super::mirror::FileLocation;
+use crate::error::{AumateError, Result};
+use futures_util::StreamExt;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Path of the partial file kept while a download is in progress
+pub fn partial_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
+/// Fetch a file from `location` into `dest`
+///
+/// `on_progress` receives `(transferred_bytes, total_bytes)`; the total is 0
+/// when the server does not report a length.
+pub async fn fetch_file(
+    client: &reqwest::Client,
+    location: &FileLocation,
+    dest: &Path,
+    on_progress: &mut dyn FnMut(u64, u64),
+) -> Result<()> {
+    match location {
+        FileLocation::Url(url) => download_resumable(client, url, dest, on_progress).await,
+        FileLocation::Path(src) => copy_file(src, dest, on_progress),
+    }
+}
+
+/// Fetch a small optional text file (e.g. a mirror manifest)
+///
+/// Returns `None` if the file does not exist at the location.
+pub async fn fetch_optional_text(
+    client: &reqwest::Client,
+    location: &FileLocation,
+) -> Result<Option<String>> {
+    match location {
+        FileLocation::Url(url) => {
+            let response = client
+                .get(url)
+                .send()
+                .await
+                .map_err(|e| AumateError::Other(format!("Request failed: {}", e)))?;
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+            if !response.status().is_success() {
+                return Err(AumateError::Other(format!(
+                    "Request for {} failed with status: {}",
+                    url,
+                    response.status()
+                )));
+            }
+            let text = response
+                .text()
+                .await
+                .map_err(|e| AumateError::Other(format!("Failed to read {}: {}", url, e)))?;
+            Ok(Some(text))
+        }
+        FileLocation::Path(path) => {
+            if path.is_file() {
+                Ok(Some(std::fs::read_to_string(path)?))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Query the digest the Hugging Face Hub publishes for a `resolve` URL
+///
+/// `client` must not follow redirects: LFS files redirect to a CDN whose
+/// response lacks the Hub headers. Returns `None` if the server is not the Hub
+/// or the request fails, so the download proceeds unverified.
+pub async fn fetch_hub_digest(client: &reqwest::Client, url: &str) -> Option<HubDigest> {
+    let response = match client.head(url).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            log::warn!("Failed to query digest of {}: {}", url, e);
+            return None;
+        }
+    };
+    let header = |name: &str| response.headers().get(name).and_then(|v| v.to_str().ok());
+    // Only the Hub guarantees that ETags are content digests
+    header("x-repo-commit")?;
+    HubDigest::from_etags(header("x-linked-etag"), header("etag"))
+}
+
+/// Download `url` to `dest`, resuming from an existing `.part` file
+async fn download_resumable(
+    client: &reqwest::Client,
+    url: &str,
+    dest: &Path,
+    on_progress: &mut dyn FnMut(u64, u64),
+) -> Result<()> {
+    let temp_path = partial_path(dest);
+    let mut start_pos = if temp_path.exists() {
+        std::fs::metadata(&temp_path).map(|m| m.len()).unwrap_or(0)
+    } else {
+        0
+    };
+
+    let mut request = client.get(url);
+    if start_pos > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", start_pos));
+    }
+
+    let mut response =
+        request.send().await.map_err(|e| AumateError::Other(format!("Download failed: {}", e)))?;
+
+    // The partial file is larger than the remote file, start over
+    if response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        log::warn!("Discarding stale partial download: {:?}", temp_path);
+        std::fs::remove_file(&temp_path)?;
+        start_pos = 0;
+        response = client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| AumateError::Other(format!("Download failed: {}", e)))?;
+    }
+
+    let resumed = response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    if !response.status().is_success() {
+        return Err(AumateError::Other(format!(
+            "Download of {} failed with status: {}",
+            url,
+            response.status()
+        )));
+    }
+
+    // Server ignored the range request and sent the whole file
+    if !resumed {
+        start_pos = 0;
+    } else {
+        log::info!("Resuming download of {} at byte {}", url, start_pos);
+    }
+
+    let total_size = response.content_length().map(|len| len + start_pos).unwrap_or(0);
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(&temp_path)
+        .map_err(|e| AumateError::Other(format!("Failed to open file: {}", e)))?;
+
+    let mut downloaded = start_pos;
+    on_progress(downloaded, total_size);
+
+    let mut stream = response.bytes_stream();
+    while let Some(chunk_result) = stream.next().await {
+        let chunk =
+            chunk_result.map_err(|e| AumateError::Other(format!("Download error: {}", e)))?;
+
+        file.write_all(&chunk).map_err(|e| AumateError::Other(format!("Write error: {}", e)))?;
+
+        downloaded += chunk.len() as u64;
+        on_progress(downloaded, total_size);
+    }
+    file.flush()?;
+    drop(file);
+
+    if total_size > 0 && downloaded != total_size {
+        return Err(AumateError::Other(format!(
+            "Incomplete download of {}: {} of {} bytes",
+            url, downloaded, total_size
+        )));
+    }
+
+    std::fs::rename(&temp_path, dest)
+        .map_err(|e| AumateError::Other(format!("Failed to rename file: {}", e)))?;
+    Ok(())
+}
+
+/// Copy a local file to `dest` with progress reporting
+fn copy_file(src: &Path, dest: &Path, on_progress: &mut dyn FnMut(u64, u64)) -> Result<()> {
+    let mut reader = std::fs::File::open(src)
+        .map_err(|e| AumateError::Other(format!("Failed to open {:?}: {}", src, e)))?;
+    let total_size = reader.metadata()?.len();
+
+    let temp_path = partial_path(dest);
+    let mut writer = std::fs::File::create(&temp_path)?;
+
+    let mut buf = vec![0u8; 256 * 1024];
+    let mut copied = 0u64;
+    on_progress(copied, total_size);
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        writer.write_all(&buf[..n])?;
+        copied += n as u64;
+        on_progress(copied, total_size);
+    }
+    writer.flush()?;
+    drop(writer);
+
+    std::fs::rename(&temp_path, dest)?;
+    Ok(())
+}
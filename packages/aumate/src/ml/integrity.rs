@@ -0,0 +1,313 @@
+//! Model integrity verification
+//!
+//! SHA-256 manifests describing the files of a downloaded or imported model.
+//!
+//! A manifest is *pinned* when its digests were checked against a manifest
+//! published by the mirror or shipped with an import, or against the digests
+//! the Hugging Face Hub publishes for every repository file. Manifests computed
+//! from the files themselves only detect later changes and never count as
+//! verified.
+
+use crate::error::{AumateError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+
+/// File name of the manifest stored inside each model directory
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Digest of a single model file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileDigest {
+    /// Lowercase hex SHA-256 of the file contents
+    pub sha256: String,
+    /// File size in bytes
+    pub size: u64,
+}
+
+/// SHA-256 manifest for a model directory
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelManifest {
+    /// Model identifier (e.g., "whisper-tiny")
+    #[serde(default)]
+    pub model_id: String,
+    /// Digests keyed by file name relative to the model directory
+    pub files: BTreeMap<String, FileDigest>,
+    /// Whether the digests were checked against a published manifest
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+impl ModelManifest {
+    /// Create an empty manifest for a model
+    pub fn new(model_id: impl Into<String>) -> Self {
+        Self { model_id: model_id.into(), files: BTreeMap::new(), pinned: false }
+    }
+
+    /// Mark whether the digests were checked against a published manifest
+    pub fn with_pinned(mut self, pinned: bool) -> Self {
+        self.pinned = pinned;
+        self
+    }
+
+    /// Build a manifest by hashing the given files in `dir`
+    pub fn compute(model_id: &str, dir: &Path, files: &[String]) -> Result<Self> {
+        let mut manifest = Self::new(model_id);
+        for file in files {
+            manifest.files.insert(file.clone(), digest_file(&dir.join(file))?);
+        }
+        Ok(manifest)
+    }
+
+    /// Parse a manifest from JSON
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| AumateError::Ml(format!("Invalid model manifest: {}", e)))
+    }
+
+    /// Load a manifest from a file
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Save the manifest as pretty-printed JSON
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| AumateError::Ml(format!("Failed to serialize manifest: {}", e)))?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Get the expected digest for a file
+    pub fn get(&self, file: &str) -> Option<&FileDigest> {
+        self.files.get(file)
+    }
+
+    /// Check a file against its expected digest, if the manifest lists it
+    pub fn check_file(&self, file: &str, path: &Path) -> Result<()> {
+        let Some(expected) = self.get(file) else {
+            return Ok(());
+        };
+        let actual = digest_file(path)?;
+        if !actual.sha256.eq_ignore_ascii_case(&expected.sha256) {
+            return Err(AumateError::Ml(format!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                file, expected.sha256, actual.sha256
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Digest the Hugging Face Hub publishes for a repository file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HubDigest {
+    /// SHA-256 of a Git LFS file, from the `X-Linked-Etag` header
+    Sha256(String),
+    /// Git blob SHA-1 of a regular file, from the `ETag` header
+    GitBlobSha1(String),
+}
+
+impl HubDigest {
+    /// Parse the digest from the `X-Linked-Etag` (LFS files) or `ETag` header
+    pub fn from_etags(linked_etag: Option<&str>, etag: Option<&str>) -> Option<Self> {
+        if let Some(hash) = linked_etag.and_then(|value| etag_hex(value, 64)) {
+            return Some(Self::Sha256(hash));
+        }
+        etag.and_then(|value| etag_hex(value, 40)).map(Self::GitBlobSha1)
+    }
+
+    /// Check a file against the digest
+    pub fn check_file(&self, file: &str, path: &Path) -> Result<()> {
+        let (expected, actual) = match self {
+            Self::Sha256(expected) => (expected, digest_file(path)?.sha256),
+            Self::GitBlobSha1(expected) => (expected, git_blob_sha1(path)?),
+        };
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(AumateError::Ml(format!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                file, expected, actual
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Lowercase hex value of an ETag with `len` hex digits
+fn etag_hex(value: &str, len: usize) -> Option<String> {
+    let value = value.trim();
+    let value = value.strip_prefix("W/").unwrap_or(value).trim_matches('"');
+    (value.len() == len && value.bytes().all(|b| b.is_ascii_hexdigit()))
+        .then(|| value.to_ascii_lowercase())
+}
+
+/// Git blob SHA-1 of a file, `sha1("blob <size>\0" + contents)`
+pub fn git_blob_sha1(path: &Path) -> Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut context = ring::digest::Context::new(&ring::digest::SHA1_FOR_LEGACY_USE_ONLY);
+    context.update(format!("blob {}\0", file.metadata()?.len()).as_bytes());
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        context.update(&buf[..n]);
+    }
+    Ok(context.finish().as_ref().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Result of verifying a model directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelVerification {
+    /// All files are present and match a pinned manifest
+    Verified,
+    /// All files are present but there is no pinned manifest to check them against
+    Unverified,
+    /// Required files are missing
+    Missing(Vec<String>),
+    /// Files whose contents do not match the manifest
+    Corrupted(Vec<String>),
+}
+
+impl ModelVerification {
+    /// Whether the model can be used (verified or at least complete)
+    pub fn is_usable(&self) -> bool {
+        matches!(self, Self::Verified | Self::Unverified)
+    }
+}
+
+/// Verify the files of a model directory against its manifest
+pub fn verify_dir(dir: &Path, files: &[String]) -> Result<ModelVerification> {
+    let missing: Vec<String> = files.iter().filter(|f| !dir.join(f).is_file()).cloned().collect();
+    if !missing.is_empty() {
+        return Ok(ModelVerification::Missing(missing));
+    }
+
+    let manifest_path = dir.join(MANIFEST_FILE);
+    if !manifest_path.exists() {
+        return Ok(ModelVerification::Unverified);
+    }
+    let manifest = ModelManifest::load(&manifest_path)?;
+
+    let mut corrupted = Vec::new();
+    for file in files {
+        let Some(expected) = manifest.get(file) else {
+            continue;
+        };
+        let actual = digest_file(&dir.join(file))?;
+        if actual.size != expected.size || !actual.sha256.eq_ignore_ascii_case(&expected.sha256) {
+            corrupted.push(file.clone());
+        }
+    }
+
+    if !corrupted.is_empty() {
+        Ok(ModelVerification::Corrupted(corrupted))
+    } else if manifest.pinned {
+        Ok(ModelVerification::Verified)
+    } else {
+        Ok(ModelVerification::Unverified)
+    }
+}
+
+/// Compute the SHA-256 digest and size of a file
+pub fn digest_file(path: &Path) -> Result<FileDigest> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut size = 0u64;
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+        size += n as u64;
+    }
+    Ok(FileDigest { sha256: format!("{:x}", hasher.finalize()), size })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("aumate-integrity-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_digest_file() {
+        let dir = temp_dir("digest");
+        std::fs::write(dir.join("a.txt"), b"hello").unwrap();
+        let digest = digest_file(&dir.join("a.txt")).unwrap();
+        assert_eq!(digest.size, 5);
+        assert_eq!(
+            digest.sha256,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_hub_digest() {
+        let dir = temp_dir("hub");
+        let path = dir.join("config.json");
+        std::fs::write(&path, b"hello").unwrap();
+
+        // `git hash-object` of "hello"
+        assert_eq!(git_blob_sha1(&path).unwrap(), "b6fc4c620b67d95f953a5c1c1230aaab5db5a1b0");
+        let blob =
+            HubDigest::from_etags(None, Some("\"B6FC4C620B67D95F953A5C1C1230AAAB5DB5A1B0\""))
+                .unwrap();
+        assert_eq!(blob, HubDigest::GitBlobSha1("b6fc4c620b67d95f953a5c1c1230aaab5db5a1b0".into()));
+        blob.check_file("config.json", &path).unwrap();
+
+        let lfs = HubDigest::from_etags(
+            Some("\"2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824\""),
+            Some("\"b6fc4c620b67d95f953a5c1c1230aaab5db5a1b0\""),
+        )
+        .unwrap();
+        assert!(matches!(lfs, HubDigest::Sha256(_)));
+        lfs.check_file("config.json", &path).unwrap();
+
+        std::fs::write(&path, b"tampered").unwrap();
+        assert!(lfs.check_file("config.json", &path).is_err());
+        assert!(HubDigest::from_etags(None, Some("\"5d41-abc\"")).is_none());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_verify_dir() {
+        let dir = temp_dir("verify");
+        let files = vec!["a.bin".to_string(), "b.json".to_string()];
+        std::fs::write(dir.join("a.bin"), b"weights").unwrap();
+        assert_eq!(
+            verify_dir(&dir, &files).unwrap(),
+            ModelVerification::Missing(vec!["b.json".to_string()])
+        );
+
+        std::fs::write(dir.join("b.json"), b"{}").unwrap();
+        assert_eq!(verify_dir(&dir, &files).unwrap(), ModelVerification::Unverified);
+
+        // Digests of the files themselves only detect later changes
+        let manifest = ModelManifest::compute("test", &dir, &files).unwrap();
+        manifest.save(&dir.join(MANIFEST_FILE)).unwrap();
+        assert_eq!(verify_dir(&dir, &files).unwrap(), ModelVerification::Unverified);
+
+        manifest.with_pinned(true).save(&dir.join(MANIFEST_FILE)).unwrap();
+        assert_eq!(verify_dir(&dir, &files).unwrap(), ModelVerification::Verified);
+
+        std::fs::write(dir.join("a.bin"), b"tampered").unwrap();
+        assert_eq!(
+            verify_dir(&dir, &files).unwrap(),
+            ModelVerification::Corrupted(vec!["a.bin".to_string()])
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,194 @@
+//! Model download mirrors
+//!
+//! Decides where model files are fetched from: Hugging Face (default),
+//! an HF-compatible mirror base URL, or a local directory for air-gapped machines.
+
+use crate::error::{AumateError, Result};
+use std::path::{Path, PathBuf};
+
+/// Environment variable used to configure the default mirror
+pub const MODEL_MIRROR_ENV: &str = "AUMATE_MODEL_MIRROR";
+
+/// Default Hugging Face endpoint
+pub const HUGGINGFACE_ENDPOINT: &str = "https://huggingface.co";
+
+/// Where model files are fetched from
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ModelMirror {
+    /// Hugging Face Hub (and the upstream URL for direct-download models)
+    #[default]
+    HuggingFace,
+    /// HF-compatible mirror, e.g. `https://hf-mirror.com`
+    ///
+    /// Repository files are fetched from `{base}/{repo_id}/resolve/main/{file}`,
+    /// direct-download models from `{base}/{model_id}/{file}`.
+    BaseUrl(String),
+    /// Local directory laid out like the models directory
+    /// (`{dir}/{model_type}/{model_id}/{file}`)
+    LocalDir(PathBuf),
+}
+
+/// Resolved location of a single model file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileLocation {
+    /// Remote file fetched over HTTP(S)
+    Url(String),
+    /// Local file copied from disk
+    Path(PathBuf),
+}
+
+impl ModelMirror {
+    /// Parse a mirror specification
+    ///
+    /// Accepts `file://` URLs, `http(s)://` base URLs and plain directory paths.
+    /// An empty string or `huggingface` selects the default Hugging Face Hub.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        if spec.is_empty() || spec.eq_ignore_ascii_case("huggingface") {
+            return Ok(Self::HuggingFace);
+        }
+
+        if let Some(path) = spec.strip_prefix("file://") {
+            if path.is_empty() {
+                return Err(AumateError::Other("Empty file:// mirror path".into()));
+            }
+            return Ok(Self::LocalDir(PathBuf::from(path)));
+        }
+
+        if spec.starts_with("http://") || spec.starts_with("https://") {
+            return Ok(Self::BaseUrl(spec.trim_end_matches('/').to_string()));
+        }
+
+        if spec.contains("://") {
+            return Err(AumateError::Other(format!("Unsupported mirror scheme: {}", spec)));
+        }
+
+        Ok(Self::LocalDir(PathBuf::from(spec)))
+    }
+
+    /// Read the mirror from the `AUMATE_MODEL_MIRROR` environment variable
+    pub fn from_env() -> Option<Self> {
+        let spec = std::env::var(MODEL_MIRROR_ENV).ok()?;
+        match Self::parse(&spec) {
+            Ok(mirror) => Some(mirror),
+            Err(e) => {
+                log::warn!("Ignoring invalid {}: {}", MODEL_MIRROR_ENV, e);
+                None
+            }
+        }
+    }
+
+    /// Resolve the location of a model file
+    ///
+    /// `upstream_url` is the canonical URL for models that are not hosted in
+    /// a Hugging Face repository (e.g. the Silero VAD model).
+    pub fn resolve(
+        &self,
+        subdir: &str,
+        model_id: &str,
+        repo_id: &str,
+        upstream_url: Option<&str>,
+        file: &str,
+    ) -> FileLocation {
+        match self {
+            Self::HuggingFace => match upstream_url {
+                Some(url) => FileLocation::Url(url.to_string()),
+                None => FileLocation::Url(hf_resolve_url(HUGGINGFACE_ENDPOINT, repo_id, file)),
+            },
+            Self::BaseUrl(base) => {
+                if repo_id.is_empty() {
+                    FileLocation::Url(format!("{}/{}/{}", base, model_id, file))
+                } else {
+                    FileLocation::Url(hf_resolve_url(base, repo_id, file))
+                }
+            }
+            Self::LocalDir(dir) => FileLocation::Path(local_file(dir, subdir, model_id, file)),
+        }
+    }
+
+    /// Whether this mirror may provide a `manifest.json` next to the model files
+    ///
+    /// Hugging Face repositories do not ship aumate manifests, so only custom
+    /// mirrors are asked for one.
+    pub fn serves_manifest(&self) -> bool {
+        !matches!(self, Self::HuggingFace)
+    }
+
+    /// Whether files of this model resolve to a Hub `resolve` endpoint
+    ///
+    /// Such endpoints publish each file's digest in their response headers.
+    pub fn resolves_from_hub(&self, repo_id: &str, upstream_url: Option<&str>) -> bool {
+        match self {
+            Self::HuggingFace => !repo_id.is_empty() && upstream_url.is_none(),
+            Self::BaseUrl(_) => !repo_id.is_empty(),
+            Self::LocalDir(_) => false,
+        }
+    }
+}
+
+fn hf_resolve_url(base: &str, repo_id: &str, file: &str) -> String {
+    format!("{}/{}/resolve/main/{}", base.trim_end_matches('/'), repo_id, file)
+}
+
+fn local_file(dir: &Path, subdir: &str, model_id: &str, file: &str) -> PathBuf {
+    dir.join(subdir).join(model_id).join(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolves_from_hub() {
+        let mirror = ModelMirror::HuggingFace;
+        assert!(mirror.resolves_from_hub("openai/whisper-tiny", None));
+        assert!(!mirror.resolves_from_hub("", Some("https://example.com/vad.onnx")));
+
+        let mirror = ModelMirror::BaseUrl("https://hf-mirror.com".to_string());
+        assert!(mirror.resolves_from_hub("openai/whisper-tiny", Some("https://example.com/a")));
+        assert!(
+            !ModelMirror::LocalDir(PathBuf::from("/mnt/models")).resolves_from_hub("a/b", None)
+        );
+    }
+
+    #[test]
+    fn test_parse_mirror() {
+        assert_eq!(ModelMirror::parse("").unwrap(), ModelMirror::HuggingFace);
+        assert_eq!(
+            ModelMirror::parse("https://hf-mirror.com/").unwrap(),
+            ModelMirror::BaseUrl("https://hf-mirror.com".to_string())
+        );
+        assert_eq!(
+            ModelMirror::parse("file:///mnt/models").unwrap(),
+            ModelMirror::LocalDir(PathBuf::from("/mnt/models"))
+        );
+        assert_eq!(
+            ModelMirror::parse("/mnt/models").unwrap(),
+            ModelMirror::LocalDir(PathBuf::from("/mnt/models"))
+        );
+        assert!(ModelMirror::parse("ftp://example.com").is_err());
+    }
+
+    #[test]
+    fn test_resolve_locations() {
+        let hf = ModelMirror::HuggingFace;
+        assert_eq!(
+            hf.resolve("whisper", "whisper-tiny", "openai/whisper-tiny", None, "config.json"),
+            FileLocation::Url(
+                "https://huggingface.co/openai/whisper-tiny/resolve/main/config.json".to_string()
+            )
+        );
+
+        let mirror = ModelMirror::BaseUrl("http://127.0.0.1:8080".to_string());
+        assert_eq!(
+            mirror.resolve("vad", "silero-vad", "", Some("https://x/y.onnx"), "silero_vad.onnx"),
+            FileLocation::Url("http://127.0.0.1:8080/silero-vad/silero_vad.onnx".to_string())
+        );
+
+        let local = ModelMirror::LocalDir(PathBuf::from("/media/usb"));
+        assert_eq!(
+            local.resolve("trocr", "trocr-base-printed", "microsoft/x", None, "vocab.json"),
+            FileLocation::Path(PathBuf::from("/media/usb/trocr/trocr-base-printed/vocab.json"))
+        );
+    }
+}
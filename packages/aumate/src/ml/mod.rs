@@ -1,21 +1,28 @@
 //! Machine Learning module
 //!
-//! This module provides shared ML utilities for STT (Whisper) and OCR (TrOCR):
+//! This module provides shared ML utilities for STT (Whisper, VAD) and OCR (TrOCR):
 //! - Device management (CPU, CUDA, Metal)
-//! - Model downloading from Hugging Face Hub
-//! - Model management (download, storage, listing)
+//! - Model downloading from Hugging Face Hub or a configurable mirror
+//! - A single model registry with pluggable catalogues (download, offline import,
+//!   verification, deletion, disk usage)
 //! - Autoregressive text decoding
 
+mod catalogue;
 mod device;
-mod download;
+mod fetch;
+mod integrity;
+mod mirror;
 mod model;
 mod text_decoder;
 
+pub use catalogue::{BuiltinCatalogue, CustomCatalogue, ModelCatalogue, ModelSpec};
 pub use device::{Device, DeviceConfig, device_name, get_device, is_gpu_available};
-pub use download::{ModelDownloader, ModelSource, download_model};
+pub use integrity::{FileDigest, MANIFEST_FILE, ModelManifest, ModelVerification, digest_file};
+pub use mirror::{FileLocation, HUGGINGFACE_ENDPOINT, MODEL_MIRROR_ENV, ModelMirror};
 pub use model::{
-    DownloadProgress, DownloadStatus, ModelInfo, ModelManager, ModelType, TROCR_FILES,
-    TROCR_MODELS, VAD_MODEL_ID, VAD_MODEL_SIZE, VAD_MODEL_URL, WHISPER_FILES, WHISPER_MODELS,
+    DiskUsage, DownloadProgress, DownloadStatus, LegacyMigration, LegacyModel, ModelDiskUsage,
+    ModelInfo, ModelManager, ModelType, TROCR_FILES, TROCR_MODELS, VAD_MODEL_FILE, VAD_MODEL_ID,
+    VAD_MODEL_SIZE, VAD_MODEL_URL, WHISPER_FILES, WHISPER_MODELS,
 };
 pub use text_decoder::{DecodingConfig, TextDecoder};
 
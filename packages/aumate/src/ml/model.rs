@@ -1,16 +1,22 @@
 //! Shared model management for ML modules
 //!
-//! Provides model downloading, storage, and management for Whisper (STT) and TrOCR (OCR).
-
+//! The single model registry used by STT (Whisper + Silero VAD) and OCR (TrOCR):
+//! catalogue, download, offline import, integrity verification, deletion and
+//! disk-usage reporting.
+
+use super::catalogue::{BuiltinCatalogue, ModelCatalogue, ModelSpec};
+use super::fetch;
+use super::integrity::{self, MANIFEST_FILE, ModelManifest, ModelVerification};
+use super::mirror::{FileLocation, ModelMirror};
 use crate::error::{AumateError, Result};
-use futures_util::StreamExt;
-use std::collections::HashMap;
-use std::io::Write;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 /// Model type identifier
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ModelType {
     /// Whisper speech-to-text model
     Whisper,
@@ -29,6 +35,11 @@ impl ModelType {
             Self::Vad => "vad",
         }
     }
+
+    /// Get all model types
+    pub fn all() -> &'static [Self] {
+        &[Self::Whisper, Self::TrOCR, Self::Vad]
+    }
 }
 
 /// Information about a model
@@ -46,6 +57,8 @@ pub struct ModelInfo {
     pub size_bytes: u64,
     /// HuggingFace repository ID (e.g., "openai/whisper-tiny")
     pub repo_id: String,
+    /// Direct download URL for models not hosted in a HuggingFace repository
+    pub url: Option<String>,
     /// Files to download
     pub files: Vec<String>,
     /// Whether the model is downloaded
@@ -57,16 +70,62 @@ pub struct ModelInfo {
 impl ModelInfo {
     /// Get human-readable size string
     pub fn size_display(&self) -> String {
-        if self.size_bytes >= 1_000_000_000 {
-            format!("{:.1} GB", self.size_bytes as f64 / 1_000_000_000.0)
-        } else if self.size_bytes >= 1_000_000 {
-            format!("{} MB", self.size_bytes / 1_000_000)
-        } else {
-            format!("{} KB", self.size_bytes / 1_000)
-        }
+        format_size(self.size_bytes)
     }
 }
 
+/// Disk space used by a single model directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelDiskUsage {
+    /// Model type
+    pub model_type: ModelType,
+    /// Model identifier (directory name)
+    pub model_id: String,
+    /// Bytes used on disk, including partial downloads
+    pub bytes: u64,
+    /// Whether the model is listed in a registered catalogue
+    pub in_catalogue: bool,
+}
+
+/// Disk space used by the models directory
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiskUsage {
+    /// Per-model usage
+    pub models: Vec<ModelDiskUsage>,
+    /// Bytes used by model files left over from the old STT layout
+    pub legacy_bytes: u64,
+    /// Total bytes used
+    pub total_bytes: u64,
+}
+
+/// Model file left over from the old STT model layout
+///
+/// Older releases stored whisper.cpp GGML weights as `~/.aumate/models/{id}.bin`.
+/// The Candle-based engine cannot load them; they are superseded by the
+/// safetensors models under `~/.aumate/models/whisper/{id}/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegacyModel {
+    /// Legacy model identifier (e.g., "whisper-base")
+    pub id: String,
+    /// Path to the legacy file
+    pub path: PathBuf,
+    /// File size in bytes
+    pub size_bytes: u64,
+    /// Catalogue model that replaces it, if any
+    pub replacement: Option<String>,
+}
+
+/// Outcome of migrating the old STT model layout
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LegacyMigration {
+    /// Legacy files that were removed
+    pub removed: Vec<LegacyModel>,
+    /// Bytes reclaimed
+    pub reclaimed_bytes: u64,
+    /// Replacement Whisper models that are not downloaded yet
+    pub to_download: Vec<String>,
+}
+
 /// Download progress information
 #[derive(Debug, Clone)]
 pub struct DownloadProgress {
@@ -196,6 +255,7 @@ pub const VAD_MODEL_URL: &str =
     "https://github.com/snakers4/silero-vad/raw/master/src/silero_vad/data/silero_vad.onnx";
 pub const VAD_MODEL_ID: &str = "silero-vad";
 pub const VAD_MODEL_SIZE: u64 = 2_000_000;
+pub const VAD_MODEL_FILE: &str = "silero_vad.onnx";
 
 // ==================== Model Manager ====================
 
@@ -203,15 +263,82 @@ pub const VAD_MODEL_SIZE: u64 = 2_000_000;
 pub struct ModelManager {
     /// Root models directory
     models_dir: PathBuf,
+    /// Where model files are fetched from
+    mirror: ModelMirror,
+    /// Registered model catalogues, in lookup order
+    catalogues: Vec<Arc<dyn ModelCatalogue>>,
     /// Current downloads in progress
     downloads: Arc<Mutex<HashMap<String, DownloadProgress>>>,
 }
 
 impl ModelManager {
     /// Create a new model manager
+    ///
+    /// The download mirror defaults to Hugging Face and can be overridden with
+    /// the `AUMATE_MODEL_MIRROR` environment variable.
     pub fn new() -> Result<Self> {
         let models_dir = super::get_models_dir()?;
-        Ok(Self { models_dir, downloads: Arc::new(Mutex::new(HashMap::new())) })
+        Ok(Self::with_models_dir(models_dir))
+    }
+
+    /// Create a model manager rooted at a custom models directory
+    pub fn with_models_dir(models_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            models_dir: models_dir.into(),
+            mirror: ModelMirror::from_env().unwrap_or_default(),
+            catalogues: vec![Arc::new(BuiltinCatalogue)],
+            downloads: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Use a specific download mirror
+    pub fn with_mirror(mut self, mirror: ModelMirror) -> Self {
+        self.mirror = mirror;
+        self
+    }
+
+    /// Set the download mirror
+    pub fn set_mirror(&mut self, mirror: ModelMirror) {
+        self.mirror = mirror;
+    }
+
+    /// Get the download mirror
+    pub fn mirror(&self) -> &ModelMirror {
+        &self.mirror
+    }
+
+    /// Register an additional model catalogue
+    pub fn with_catalogue(mut self, catalogue: impl ModelCatalogue + 'static) -> Self {
+        self.add_catalogue(Arc::new(catalogue));
+        self
+    }
+
+    /// Register an additional model catalogue
+    ///
+    /// Catalogues are searched in registration order, so a model ID that is
+    /// already provided by an earlier catalogue (including the built-in one)
+    /// cannot be redefined.
+    pub fn add_catalogue(&mut self, catalogue: Arc<dyn ModelCatalogue>) {
+        log::debug!("Registered model catalogue: {}", catalogue.name());
+        self.catalogues.push(catalogue);
+    }
+
+    /// Get all model specs from the registered catalogues
+    pub fn catalogue(&self) -> Vec<ModelSpec> {
+        let mut seen = HashSet::new();
+        self.catalogues
+            .iter()
+            .flat_map(|c| c.models())
+            .filter(|spec| seen.insert((spec.model_type, spec.id.clone())))
+            .collect()
+    }
+
+    /// Find a model spec in the registered catalogues
+    pub fn find_spec(&self, model_type: ModelType, model_id: &str) -> Option<ModelSpec> {
+        self.catalogues
+            .iter()
+            .flat_map(|c| c.models())
+            .find(|spec| spec.model_type == model_type && spec.id == model_id)
     }
 
     /// Get the root models directory
@@ -231,92 +358,72 @@ impl ModelManager {
 
     /// Check if a model is downloaded
     pub fn is_downloaded(&self, model_type: ModelType, model_id: &str) -> bool {
-        let dir = self.model_dir(model_type, model_id);
-        if !dir.exists() {
-            return false;
-        }
+        self.find_spec(model_type, model_id).is_some_and(|spec| self.spec_is_downloaded(&spec))
+    }
 
-        // Check if all required files exist
-        let files = match model_type {
-            ModelType::Whisper => WHISPER_FILES,
-            ModelType::TrOCR => TROCR_FILES,
-            ModelType::Vad => return dir.join("silero_vad.onnx").exists(),
-        };
+    /// Check if all files of a model spec are present
+    fn spec_is_downloaded(&self, spec: &ModelSpec) -> bool {
+        let dir = self.model_dir(spec.model_type, &spec.id);
+        dir.exists() && spec.files.iter().all(|f| dir.join(f).exists())
+    }
 
-        files.iter().all(|f| dir.join(f).exists())
+    /// Build model info, including download status, for a spec
+    fn model_info(&self, spec: ModelSpec) -> ModelInfo {
+        let is_downloaded = self.spec_is_downloaded(&spec);
+        let local_path =
+            if is_downloaded { Some(self.model_dir(spec.model_type, &spec.id)) } else { None };
+        ModelInfo {
+            id: spec.id,
+            name: spec.name,
+            description: spec.description,
+            model_type: spec.model_type,
+            size_bytes: spec.size_bytes,
+            repo_id: spec.repo_id,
+            url: spec.url,
+            files: spec.files,
+            is_downloaded,
+            local_path,
+        }
     }
 
     /// List all available Whisper models
     pub fn list_whisper_models(&self) -> Vec<ModelInfo> {
-        WHISPER_MODELS
-            .iter()
-            .map(|(id, name, repo_id, size)| {
-                let is_downloaded = self.is_downloaded(ModelType::Whisper, id);
-                let local_path =
-                    if is_downloaded { Some(self.model_dir(ModelType::Whisper, id)) } else { None };
-                ModelInfo {
-                    id: id.to_string(),
-                    name: name.to_string(),
-                    description: format!("~{}", format_size(*size)),
-                    model_type: ModelType::Whisper,
-                    size_bytes: *size,
-                    repo_id: repo_id.to_string(),
-                    files: WHISPER_FILES.iter().map(|s| s.to_string()).collect(),
-                    is_downloaded,
-                    local_path,
-                }
-            })
-            .collect()
+        self.list_models(ModelType::Whisper)
     }
 
     /// List all available TrOCR models
     pub fn list_trocr_models(&self) -> Vec<ModelInfo> {
-        TROCR_MODELS
-            .iter()
-            .map(|(id, name, desc, repo_id, size)| {
-                let is_downloaded = self.is_downloaded(ModelType::TrOCR, id);
-                let local_path =
-                    if is_downloaded { Some(self.model_dir(ModelType::TrOCR, id)) } else { None };
-                ModelInfo {
-                    id: id.to_string(),
-                    name: name.to_string(),
-                    description: desc.to_string(),
-                    model_type: ModelType::TrOCR,
-                    size_bytes: *size,
-                    repo_id: repo_id.to_string(),
-                    files: TROCR_FILES.iter().map(|s| s.to_string()).collect(),
-                    is_downloaded,
-                    local_path,
-                }
-            })
-            .collect()
+        self.list_models(ModelType::TrOCR)
     }
 
     /// List all available models of a specific type
     pub fn list_models(&self, model_type: ModelType) -> Vec<ModelInfo> {
-        match model_type {
-            ModelType::Whisper => self.list_whisper_models(),
-            ModelType::TrOCR => self.list_trocr_models(),
-            ModelType::Vad => vec![self.get_vad_model_info()],
-        }
+        self.catalogue()
+            .into_iter()
+            .filter(|spec| spec.model_type == model_type)
+            .map(|spec| self.model_info(spec))
+            .collect()
+    }
+
+    /// List all available models of every type
+    pub fn list_all_models(&self) -> Vec<ModelInfo> {
+        self.catalogue().into_iter().map(|spec| self.model_info(spec)).collect()
+    }
+
+    /// List downloaded models of a specific type
+    pub fn list_downloaded_models(&self, model_type: ModelType) -> Vec<ModelInfo> {
+        self.list_models(model_type).into_iter().filter(|m| m.is_downloaded).collect()
+    }
+
+    /// Get info for a single model
+    pub fn get_model_info(&self, model_type: ModelType, model_id: &str) -> Option<ModelInfo> {
+        self.find_spec(model_type, model_id).map(|spec| self.model_info(spec))
     }
 
     /// Get VAD model info
     pub fn get_vad_model_info(&self) -> ModelInfo {
-        let is_downloaded = self.is_downloaded(ModelType::Vad, VAD_MODEL_ID);
-        let local_path =
-            if is_downloaded { Some(self.model_dir(ModelType::Vad, VAD_MODEL_ID)) } else { None };
-        ModelInfo {
-            id: VAD_MODEL_ID.to_string(),
-            name: "Silero VAD".to_string(),
-            description: "Voice Activity Detection".to_string(),
-            model_type: ModelType::Vad,
-            size_bytes: VAD_MODEL_SIZE,
-            repo_id: String::new(),
-            files: vec!["silero_vad.onnx".to_string()],
-            is_downloaded,
-            local_path,
-        }
+        self.get_model_info(ModelType::Vad, VAD_MODEL_ID)
+            .expect("Silero VAD is part of the built-in catalogue")
     }
 
     /// Get the path to a downloaded model
@@ -330,38 +437,51 @@ impl ModelManager {
         self.downloads.lock().unwrap().get(model_id).cloned()
     }
 
+    /// Find a model in the catalogue
+    fn find_model(&self, model_type: ModelType, model_id: &str) -> Result<ModelInfo> {
+        self.get_model_info(model_type, model_id)
+            .ok_or_else(|| AumateError::Other(format!("Unknown model: {}", model_id)))
+    }
+
+    /// Resolve where a model file is fetched from with the current mirror
+    fn file_location(&self, model: &ModelInfo, file: &str) -> FileLocation {
+        self.mirror.resolve(
+            model.model_type.subdir(),
+            &model.id,
+            &model.repo_id,
+            model.url.as_deref(),
+            file,
+        )
+    }
+
     /// Download a model (blocking)
+    ///
+    /// Files are fetched from the configured mirror. Interrupted downloads are
+    /// resumed with HTTP range requests, and files that are already present are
+    /// skipped. When the mirror provides a `manifest.json`, every file is checked
+    /// against it and the model is pinned to those digests. Otherwise files served
+    /// by the Hugging Face Hub are checked against the digests it publishes for
+    /// them. If neither is available the written manifest only records the
+    /// downloaded files, and [`ModelManager::verify_model`] reports the model as
+    /// unverified.
     pub fn download_model_sync(
         &self,
         model_type: ModelType,
         model_id: &str,
         progress_callback: Option<Box<dyn Fn(DownloadProgress) + Send>>,
     ) -> Result<PathBuf> {
-        // Special handling for VAD model (direct URL download)
-        if model_type == ModelType::Vad {
-            return self.download_vad_model(progress_callback);
-        }
-
-        // Find the model info
-        let model_info = self
-            .list_models(model_type)
-            .into_iter()
-            .find(|m| m.id == model_id)
-            .ok_or_else(|| AumateError::Other(format!("Unknown model: {}", model_id)))?;
+        let model_info = self.find_model(model_type, model_id)?;
 
         // Create output directory
         let output_dir = self.model_dir(model_type, model_id);
         std::fs::create_dir_all(&output_dir)?;
 
-        let files = &model_info.files;
-        let total_files = files.len();
-
         // Initialize progress
         let progress = DownloadProgress {
             model_id: model_id.to_string(),
             current_file: String::new(),
             file_index: 0,
-            total_files,
+            total_files: model_info.files.len(),
             downloaded_bytes: 0,
             total_bytes: 0,
             status: DownloadStatus::Pending,
@@ -372,224 +492,242 @@ impl ModelManager {
         let rt = tokio::runtime::Runtime::new()
             .map_err(|e| AumateError::Other(format!("Failed to create runtime: {}", e)))?;
 
-        let repo_id = model_info.repo_id.clone();
-        let downloads = self.downloads.clone();
-        let model_id_owned = model_id.to_string();
-
-        let result: Result<PathBuf> = rt.block_on(async {
-            // Use hf-hub to download files
-            let api = hf_hub::api::tokio::Api::new()
-                .map_err(|e| AumateError::Other(format!("Failed to create HF API: {}", e)))?;
-
-            let repo = api.model(repo_id.clone());
+        let result = rt.block_on(self.download_files(
+            &model_info,
+            &output_dir,
+            progress_callback.as_deref(),
+        ));
 
-            for (idx, filename) in files.iter().enumerate() {
-                let file_idx = idx + 1;
-
-                // Update progress for new file
-                {
-                    let mut downloads = downloads.lock().unwrap();
-                    if let Some(p) = downloads.get_mut(&model_id_owned) {
-                        p.current_file = filename.clone();
-                        p.file_index = file_idx;
-                        p.downloaded_bytes = 0;
-                        p.total_bytes = 0;
-                        p.status = DownloadStatus::Downloading;
-                    }
-                }
-
-                if let Some(ref callback) = progress_callback {
-                    callback(DownloadProgress {
-                        model_id: model_id_owned.clone(),
-                        current_file: filename.clone(),
-                        file_index: file_idx,
-                        total_files,
-                        downloaded_bytes: 0,
-                        total_bytes: 0,
-                        status: DownloadStatus::Downloading,
-                    });
-                }
-
-                log::info!(
-                    "Downloading {}/{}: {} from {}",
-                    file_idx,
-                    total_files,
-                    filename,
-                    repo_id
-                );
-
-                // Download file using hf-hub (it handles caching)
-                let cached_path = repo.get(filename).await.map_err(|e| {
-                    AumateError::Other(format!("Failed to download {}: {}", filename, e))
-                })?;
-
-                // Copy to our model directory
-                let dest_path = output_dir.join(filename);
-                std::fs::copy(&cached_path, &dest_path).map_err(|e| {
-                    AumateError::Other(format!("Failed to copy {}: {}", filename, e))
-                })?;
-
-                log::info!("Downloaded: {} -> {:?}", filename, dest_path);
-
-                // Update progress
-                if let Some(ref callback) = progress_callback {
-                    callback(DownloadProgress {
-                        model_id: model_id_owned.clone(),
-                        current_file: filename.clone(),
-                        file_index: file_idx,
-                        total_files,
-                        downloaded_bytes: 1,
-                        total_bytes: 1,
-                        status: DownloadStatus::Downloading,
-                    });
-                }
-            }
-
-            // Mark as completed
-            {
-                let mut downloads = downloads.lock().unwrap();
-                if let Some(p) = downloads.get_mut(&model_id_owned) {
-                    p.status = DownloadStatus::Completed;
-                }
-            }
-
-            if let Some(ref callback) = progress_callback {
-                callback(DownloadProgress {
-                    model_id: model_id_owned.clone(),
-                    current_file: String::new(),
-                    file_index: total_files,
-                    total_files,
-                    downloaded_bytes: 1,
-                    total_bytes: 1,
-                    status: DownloadStatus::Completed,
-                });
-            }
-
-            Ok(output_dir.clone())
-        });
-
-        // Handle error
-        if let Err(ref e) = result {
+        // Update final status
+        {
             let mut downloads = self.downloads.lock().unwrap();
             if let Some(p) = downloads.get_mut(model_id) {
-                p.status = DownloadStatus::Failed(e.to_string());
+                p.status = match result {
+                    Ok(_) => DownloadStatus::Completed,
+                    Err(ref e) => DownloadStatus::Failed(e.to_string()),
+                };
             }
         }
 
+        if let (Ok(_), Some(callback)) = (&result, &progress_callback) {
+            callback(DownloadProgress {
+                model_id: model_id.to_string(),
+                current_file: String::new(),
+                file_index: model_info.files.len(),
+                total_files: model_info.files.len(),
+                downloaded_bytes: 1,
+                total_bytes: 1,
+                status: DownloadStatus::Completed,
+            });
+        }
+
         result
     }
 
-    /// Download VAD model (special case - direct URL)
-    fn download_vad_model(
+    /// Fetch all files of a model into `output_dir`
+    async fn download_files(
         &self,
-        progress_callback: Option<Box<dyn Fn(DownloadProgress) + Send>>,
+        model: &ModelInfo,
+        output_dir: &Path,
+        progress_callback: Option<&(dyn Fn(DownloadProgress) + Send)>,
     ) -> Result<PathBuf> {
-        let output_dir = self.model_dir(ModelType::Vad, VAD_MODEL_ID);
-        std::fs::create_dir_all(&output_dir)?;
-
-        let output_path = output_dir.join("silero_vad.onnx");
-        let temp_path = output_dir.join("silero_vad.onnx.tmp");
-
-        // Initialize progress
-        let progress = DownloadProgress {
-            model_id: VAD_MODEL_ID.to_string(),
-            current_file: "silero_vad.onnx".to_string(),
-            file_index: 1,
-            total_files: 1,
-            downloaded_bytes: 0,
-            total_bytes: VAD_MODEL_SIZE,
-            status: DownloadStatus::Pending,
+        let client = reqwest::Client::new();
+        let total_files = model.files.len();
+
+        // Expected digests published by the mirror, if any
+        let expected = if self.mirror.serves_manifest() {
+            let location = self.file_location(model, MANIFEST_FILE);
+            match fetch::fetch_optional_text(&client, &location).await? {
+                Some(json) => Some(ModelManifest::from_json(&json)?),
+                None => None,
+            }
+        } else {
+            None
         };
-        self.downloads.lock().unwrap().insert(VAD_MODEL_ID.to_string(), progress);
 
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| AumateError::Other(format!("Failed to create runtime: {}", e)))?;
-
-        let downloads = self.downloads.clone();
+        // Without a mirror manifest, fall back to the digests published by the Hub
+        let query_hub = expected.is_none()
+            && self.mirror.resolves_from_hub(&model.repo_id, model.url.as_deref());
+        let hub_client = if query_hub {
+            reqwest::Client::builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .map_err(|e| AumateError::Other(format!("Failed to create client: {}", e)))?
+        } else {
+            client.clone()
+        };
+        let mut hub_verified = query_hub;
 
-        let result = rt.block_on(async {
-            let client = reqwest::Client::new();
-            let response = client
-                .get(VAD_MODEL_URL)
-                .send()
-                .await
-                .map_err(|e| AumateError::Other(format!("Download failed: {}", e)))?;
+        for (idx, filename) in model.files.iter().enumerate() {
+            let file_index = idx + 1;
+            let dest_path = output_dir.join(filename);
+            let location = self.file_location(model, filename);
 
-            if !response.status().is_success() {
-                return Err(AumateError::Other(format!(
-                    "Download failed with status: {}",
-                    response.status()
-                )));
+            let hub_digest = match location {
+                FileLocation::Url(ref url) if query_hub => {
+                    fetch::fetch_hub_digest(&hub_client, url).await
+                }
+                _ => None,
+            };
+            if hub_digest.is_none() {
+                hub_verified = false;
             }
-
-            let total_size = response.content_length().unwrap_or(VAD_MODEL_SIZE);
-
-            {
-                let mut downloads = downloads.lock().unwrap();
-                if let Some(p) = downloads.get_mut(VAD_MODEL_ID) {
-                    p.total_bytes = total_size;
-                    p.status = DownloadStatus::Downloading;
+            let check = |path: &Path| match (&expected, &hub_digest) {
+                (Some(manifest), _) => manifest.check_file(filename, path),
+                (None, Some(digest)) => digest.check_file(filename, path),
+                (None, None) => Ok(()),
+            };
+
+            // Skip files completed by an earlier, interrupted run
+            if dest_path.is_file() {
+                if check(&dest_path).is_ok() {
+                    log::info!("Already present, skipping: {:?}", dest_path);
+                    continue;
                 }
+                std::fs::remove_file(&dest_path)?;
             }
 
-            let mut file = std::fs::File::create(&temp_path)
-                .map_err(|e| AumateError::Other(format!("Failed to create file: {}", e)))?;
+            log::info!(
+                "Downloading {}/{}: {} from {:?}",
+                file_index,
+                total_files,
+                filename,
+                location
+            );
+
+            let mut on_progress = |downloaded: u64, total: u64| {
+                let progress = DownloadProgress {
+                    model_id: model.id.clone(),
+                    current_file: filename.clone(),
+                    file_index,
+                    total_files,
+                    downloaded_bytes: downloaded,
+                    total_bytes: total,
+                    status: DownloadStatus::Downloading,
+                };
+                self.downloads.lock().unwrap().insert(model.id.clone(), progress.clone());
+                if let Some(callback) = progress_callback {
+                    callback(progress);
+                }
+            };
 
-            let mut downloaded: u64 = 0;
-            let mut stream = response.bytes_stream();
+            fetch::fetch_file(&client, &location, &dest_path, &mut on_progress).await?;
 
-            while let Some(chunk_result) = stream.next().await {
-                let chunk = chunk_result
-                    .map_err(|e| AumateError::Other(format!("Download error: {}", e)))?;
+            if let Err(e) = check(&dest_path) {
+                let _ = std::fs::remove_file(&dest_path);
+                return Err(e);
+            }
 
-                file.write_all(&chunk)
-                    .map_err(|e| AumateError::Other(format!("Write error: {}", e)))?;
+            log::info!("Downloaded: {} -> {:?}", filename, dest_path);
+        }
 
-                downloaded += chunk.len() as u64;
+        ModelManifest::compute(&model.id, output_dir, &model.files)?
+            .with_pinned(expected.is_some() || hub_verified)
+            .save(&output_dir.join(MANIFEST_FILE))?;
 
-                {
-                    let mut downloads = downloads.lock().unwrap();
-                    if let Some(p) = downloads.get_mut(VAD_MODEL_ID) {
-                        p.downloaded_bytes = downloaded;
-                    }
-                }
+        Ok(output_dir.to_path_buf())
+    }
 
-                if let Some(ref callback) = progress_callback {
-                    callback(DownloadProgress {
-                        model_id: VAD_MODEL_ID.to_string(),
-                        current_file: "silero_vad.onnx".to_string(),
-                        file_index: 1,
-                        total_files: 1,
-                        downloaded_bytes: downloaded,
-                        total_bytes: total_size,
-                        status: DownloadStatus::Downloading,
-                    });
-                }
+    /// Import a model from a local directory or archive (blocking)
+    ///
+    /// Supports directories and `.tar`, `.tar.gz` or `.tgz` archives, with the
+    /// model files either at the top level or inside a single folder. The model
+    /// ID is taken from a directory's `manifest.json`, or else from the directory
+    /// or archive name. If the source includes a manifest, every file is verified
+    /// against it before the model is installed.
+    pub fn import_model(&self, model_type: ModelType, source: &Path) -> Result<PathBuf> {
+        let model_id = match read_source_manifest(source)? {
+            Some(manifest) if !manifest.model_id.is_empty() => manifest.model_id,
+            _ => source_stem(source)
+                .ok_or_else(|| AumateError::Other(format!("Invalid import path: {:?}", source)))?,
+        };
+        self.import_model_as(model_type, &model_id, source)
+    }
+
+    /// Import a model under an explicit model ID (blocking)
+    ///
+    /// See [`ModelManager::import_model`] for the supported sources.
+    pub fn import_model_as(
+        &self,
+        model_type: ModelType,
+        model_id: &str,
+        source: &Path,
+    ) -> Result<PathBuf> {
+        let model_info = self.find_model(model_type, model_id)?;
+        let type_dir = self.type_dir(model_type);
+        std::fs::create_dir_all(&type_dir)?;
+
+        let extract_dir = type_dir.join(format!(".{}.extract", model_id));
+        let staging_dir = type_dir.join(format!(".{}.import", model_id));
+
+        let result = (|| {
+            let source_dir = if source.is_dir() {
+                source.to_path_buf()
+            } else {
+                extract_archive(source, &extract_dir)?;
+                find_model_root(&extract_dir, &model_info.files)
+            };
+
+            let missing: Vec<&String> =
+                model_info.files.iter().filter(|f| !source_dir.join(f).is_file()).collect();
+            if !missing.is_empty() {
+                return Err(AumateError::Other(format!(
+                    "Cannot import {}: missing {:?}",
+                    model_id, missing
+                )));
             }
 
-            std::fs::rename(&temp_path, &output_path)
-                .map_err(|e| AumateError::Other(format!("Failed to rename file: {}", e)))?;
+            let manifest_path = source_dir.join(MANIFEST_FILE);
+            let expected = if manifest_path.is_file() {
+                Some(ModelManifest::load(&manifest_path)?)
+            } else {
+                None
+            };
+
+            if staging_dir.exists() {
+                std::fs::remove_dir_all(&staging_dir)?;
+            }
+            std::fs::create_dir_all(&staging_dir)?;
 
-            {
-                let mut downloads = downloads.lock().unwrap();
-                if let Some(p) = downloads.get_mut(VAD_MODEL_ID) {
-                    p.status = DownloadStatus::Completed;
+            for file in &model_info.files {
+                let dest = staging_dir.join(file);
+                std::fs::copy(source_dir.join(file), &dest)?;
+                if let Some(ref manifest) = expected {
+                    manifest.check_file(file, &dest)?;
                 }
             }
 
-            Ok(output_dir.clone())
-        });
+            ModelManifest::compute(model_id, &staging_dir, &model_info.files)?
+                .with_pinned(expected.is_some())
+                .save(&staging_dir.join(MANIFEST_FILE))?;
 
-        if let Err(ref e) = result {
-            let mut downloads = self.downloads.lock().unwrap();
-            if let Some(p) = downloads.get_mut(VAD_MODEL_ID) {
-                p.status = DownloadStatus::Failed(e.to_string());
+            let output_dir = self.model_dir(model_type, model_id);
+            if output_dir.exists() {
+                std::fs::remove_dir_all(&output_dir)?;
             }
+            std::fs::rename(&staging_dir, &output_dir)?;
+            log::info!("Imported {} from {:?} -> {:?}", model_id, source, output_dir);
+            Ok(output_dir)
+        })();
+
+        let _ = std::fs::remove_dir_all(&extract_dir);
+        if result.is_err() {
+            let _ = std::fs::remove_dir_all(&staging_dir);
         }
-
         result
     }
 
+    /// Verify a model's files against its SHA-256 manifest
+    ///
+    /// Models not checked against a manifest published by the mirror or the
+    /// import source, or against the Hub's digests, are reported as
+    /// [`ModelVerification::Unverified`] unless they have changed
+    /// since they were installed.
+    pub fn verify_model(&self, model_type: ModelType, model_id: &str) -> Result<ModelVerification> {
+        let model_info = self.find_model(model_type, model_id)?;
+        integrity::verify_dir(&self.model_dir(model_type, model_id), &model_info.files)
+    }
+
     /// Delete a downloaded model
     pub fn delete_model(&self, model_type: ModelType, model_id: &str) -> Result<()> {
         let path = self.model_dir(model_type, model_id);
@@ -598,6 +736,95 @@ impl ModelManager {
         }
         Ok(())
     }
+
+    /// Get the disk space used by a single model
+    pub fn model_disk_usage(&self, model_type: ModelType, model_id: &str) -> u64 {
+        dir_size(&self.model_dir(model_type, model_id))
+    }
+
+    /// Report the disk space used by all models
+    ///
+    /// Includes model directories that are no longer in any catalogue, as well
+    /// as files left over from the old STT layout.
+    pub fn disk_usage(&self) -> DiskUsage {
+        let catalogue: HashSet<(ModelType, String)> =
+            self.catalogue().into_iter().map(|spec| (spec.model_type, spec.id)).collect();
+
+        let mut models = Vec::new();
+        for &model_type in ModelType::all() {
+            let Ok(entries) = std::fs::read_dir(self.type_dir(model_type)) else {
+                continue;
+            };
+            for entry in entries.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                let Some(model_id) = path.file_name().and_then(|n| n.to_str()) else {
+                    continue;
+                };
+                if !path.is_dir() || model_id.starts_with('.') {
+                    continue;
+                }
+                models.push(ModelDiskUsage {
+                    model_type,
+                    model_id: model_id.to_string(),
+                    bytes: dir_size(&path),
+                    in_catalogue: catalogue.contains(&(model_type, model_id.to_string())),
+                });
+            }
+        }
+        models.sort_by_key(|m| std::cmp::Reverse(m.bytes));
+
+        let legacy_bytes: u64 = self.find_legacy_stt_models().iter().map(|m| m.size_bytes).sum();
+        let total_bytes = models.iter().map(|m| m.bytes).sum::<u64>() + legacy_bytes;
+        DiskUsage { models, legacy_bytes, total_bytes }
+    }
+
+    /// Find model files left over from the old STT layout
+    pub fn find_legacy_stt_models(&self) -> Vec<LegacyModel> {
+        let Ok(entries) = std::fs::read_dir(&self.models_dir) else {
+            return Vec::new();
+        };
+
+        let mut legacy: Vec<LegacyModel> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|path| path.is_file())
+            .filter_map(|path| {
+                let name = path.file_name()?.to_str()?;
+                let id = name.strip_suffix(".bin").or_else(|| name.strip_suffix(".bin.tmp"))?;
+                if !id.starts_with("whisper-") {
+                    return None;
+                }
+                let replacement = self.find_spec(ModelType::Whisper, id).map(|spec| spec.id);
+                let size_bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                Some(LegacyModel { id: id.to_string(), path, size_bytes, replacement })
+            })
+            .collect();
+        legacy.sort_by(|a, b| a.path.cmp(&b.path));
+        legacy
+    }
+
+    /// Migrate from the old STT layout by removing its unusable GGML files
+    ///
+    /// Returns the removed files and the replacement Whisper models that still
+    /// need to be downloaded with [`ModelManager::download_model_sync`].
+    pub fn migrate_legacy_stt_models(&self) -> Result<LegacyMigration> {
+        let mut migration = LegacyMigration::default();
+        for legacy in self.find_legacy_stt_models() {
+            std::fs::remove_file(&legacy.path)?;
+            log::info!("Removed legacy STT model: {:?}", legacy.path);
+
+            if let Some(ref replacement) = legacy.replacement {
+                if !self.is_downloaded(ModelType::Whisper, replacement)
+                    && !migration.to_download.contains(replacement)
+                {
+                    migration.to_download.push(replacement.clone());
+                }
+            }
+            migration.reclaimed_bytes += legacy.size_bytes;
+            migration.removed.push(legacy);
+        }
+        Ok(migration)
+    }
 }
 
 impl Default for ModelManager {
@@ -606,8 +833,74 @@ impl Default for ModelManager {
     }
 }
 
+/// Total size of all files under a directory
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| match e.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&e.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Read `manifest.json` from an import source directory, if present
+fn read_source_manifest(source: &Path) -> Result<Option<ModelManifest>> {
+    let path = source.join(MANIFEST_FILE);
+    if source.is_dir() && path.is_file() { ModelManifest::load(&path).map(Some) } else { Ok(None) }
+}
+
+/// Derive a model ID from a directory or archive name
+fn source_stem(source: &Path) -> Option<String> {
+    let name = source.file_name()?.to_str()?;
+    let stem =
+        [".tar.gz", ".tgz", ".tar"].iter().find_map(|ext| name.strip_suffix(ext)).unwrap_or(name);
+    if stem.is_empty() { None } else { Some(stem.to_string()) }
+}
+
+/// Extract a `.tar`, `.tar.gz` or `.tgz` archive into `dest`
+fn extract_archive(archive: &Path, dest: &Path) -> Result<()> {
+    let name = archive.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    let file = std::fs::File::open(archive)
+        .map_err(|e| AumateError::Other(format!("Failed to open {:?}: {}", archive, e)))?;
+
+    if dest.exists() {
+        std::fs::remove_dir_all(dest)?;
+    }
+    std::fs::create_dir_all(dest)?;
+
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        tar::Archive::new(flate2::read::GzDecoder::new(file)).unpack(dest)?;
+    } else if name.ends_with(".tar") {
+        tar::Archive::new(file).unpack(dest)?;
+    } else {
+        return Err(AumateError::Other(format!("Unsupported model archive: {:?}", archive)));
+    }
+    Ok(())
+}
+
+/// Locate the model files in an extracted archive
+///
+/// Archives often wrap the files in a single top-level folder.
+fn find_model_root(dir: &Path, files: &[String]) -> PathBuf {
+    if files.iter().any(|f| dir.join(f).is_file()) {
+        return dir.to_path_buf();
+    }
+    let entries: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|rd| rd.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+        .unwrap_or_default();
+    match entries.as_slice() {
+        [only] if only.is_dir() => only.clone(),
+        _ => dir.to_path_buf(),
+    }
+}
+
 /// Format bytes as human-readable size
-fn format_size(bytes: u64) -> String {
+pub(crate) fn format_size(bytes: u64) -> String {
     if bytes >= 1_000_000_000 {
         format!("{:.1} GB", bytes as f64 / 1_000_000_000.0)
     } else if bytes >= 1_000_000 {
@@ -620,6 +913,7 @@ fn format_size(bytes: u64) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ml::CustomCatalogue;
 
     #[test]
     fn test_model_info_size_display() {
@@ -630,6 +924,7 @@ mod tests {
             model_type: ModelType::Whisper,
             size_bytes: 142_000_000,
             repo_id: "test/model".to_string(),
+            url: None,
             files: vec![],
             is_downloaded: false,
             local_path: None,
@@ -659,4 +954,450 @@ mod tests {
         assert_eq!(ModelType::TrOCR.subdir(), "trocr");
         assert_eq!(ModelType::Vad.subdir(), "vad");
     }
+
+    #[test]
+    fn test_source_stem() {
+        assert_eq!(source_stem(Path::new("/tmp/whisper-tiny")).as_deref(), Some("whisper-tiny"));
+        assert_eq!(source_stem(Path::new("silero-vad.tar.gz")).as_deref(), Some("silero-vad"));
+        assert_eq!(
+            source_stem(Path::new("trocr-base-printed.tgz")).as_deref(),
+            Some("trocr-base-printed")
+        );
+    }
+
+    /// Minimal HTTP/1.1 file server standing in for Hugging Face or a mirror
+    struct TestServer {
+        base_url: String,
+        range_requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl TestServer {
+        fn start(files: HashMap<String, Vec<u8>>) -> Self {
+            Self::start_with_headers(files, HashMap::new())
+        }
+
+        /// Serve `files`, adding `headers[path]` to full responses for that path
+        fn start_with_headers(
+            files: HashMap<String, Vec<u8>>,
+            headers: HashMap<String, String>,
+        ) -> Self {
+            use std::io::{BufRead, BufReader, Write};
+
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            let base_url = format!("http://{}", listener.local_addr().unwrap());
+            let range_requests = Arc::new(Mutex::new(Vec::new()));
+            let ranges = range_requests.clone();
+
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(mut stream) = stream else { break };
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                    let mut request_line = String::new();
+                    if reader.read_line(&mut request_line).is_err() {
+                        continue;
+                    }
+                    let head = request_line.starts_with("HEAD ");
+                    let path = request_line.split_whitespace().nth(1).unwrap_or("/").to_string();
+
+                    let mut range_start = None;
+                    loop {
+                        let mut line = String::new();
+                        if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                            break;
+                        }
+                        let lower = line.to_ascii_lowercase();
+                        if let Some(value) = lower.strip_prefix("range: bytes=") {
+                            ranges.lock().unwrap().push(value.trim().to_string());
+                            range_start = value.trim().trim_end_matches('-').parse::<usize>().ok();
+                        }
+                    }
+
+                    let response = match files.get(&path) {
+                        None => b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec(),
+                        Some(body) => match range_start {
+                            Some(start) if start < body.len() => {
+                                let mut r = format!(
+                                    "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {}-{}/{}\r\nConnection: close\r\n\r\n",
+                                    body.len() - start,
+                                    start,
+                                    body.len() - 1,
+                                    body.len()
+                                )
+                                .into_bytes();
+                                r.extend_from_slice(&body[start..]);
+                                r
+                            }
+                            Some(_) => b"HTTP/1.1 416 Range Not Satisfiable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec(),
+                            None => {
+                                let mut r = format!(
+                                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n",
+                                    body.len(),
+                                    headers.get(&path).map(String::as_str).unwrap_or("")
+                                )
+                                .into_bytes();
+                                if !head {
+                                    r.extend_from_slice(body);
+                                }
+                                r
+                            }
+                        },
+                    };
+                    let _ = stream.write_all(&response);
+                }
+            });
+
+            Self { base_url, range_requests }
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("aumate-models-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn whisper_files() -> Vec<(String, Vec<u8>)> {
+        WHISPER_FILES
+            .iter()
+            .map(|f| (f.to_string(), format!("contents of {}", f).into_bytes()))
+            .collect()
+    }
+
+    fn manifest_json(model_id: &str, files: &[(String, Vec<u8>)]) -> Vec<u8> {
+        use sha2::{Digest, Sha256};
+        let mut manifest = ModelManifest::new(model_id);
+        for (name, body) in files {
+            manifest.files.insert(
+                name.clone(),
+                integrity::FileDigest {
+                    sha256: format!("{:x}", Sha256::digest(body)),
+                    size: body.len() as u64,
+                },
+            );
+        }
+        serde_json::to_vec(&manifest).unwrap()
+    }
+
+    #[test]
+    fn test_download_from_mirror_with_manifest() {
+        let files = whisper_files();
+        let mut served = HashMap::new();
+        for (name, body) in &files {
+            served.insert(format!("/openai/whisper-tiny/resolve/main/{}", name), body.clone());
+        }
+        served.insert(
+            "/openai/whisper-tiny/resolve/main/manifest.json".to_string(),
+            manifest_json("whisper-tiny", &files),
+        );
+        let server = TestServer::start(served);
+
+        let dir = temp_dir("mirror");
+        let manager = ModelManager::with_models_dir(&dir)
+            .with_mirror(ModelMirror::parse(&server.base_url).unwrap());
+
+        let path = manager.download_model_sync(ModelType::Whisper, "whisper-tiny", None).unwrap();
+        for (name, body) in &files {
+            assert_eq!(&std::fs::read(path.join(name)).unwrap(), body);
+        }
+        assert!(manager.is_downloaded(ModelType::Whisper, "whisper-tiny"));
+        assert_eq!(
+            manager.verify_model(ModelType::Whisper, "whisper-tiny").unwrap(),
+            ModelVerification::Verified
+        );
+        assert_eq!(
+            manager.get_download_progress("whisper-tiny").unwrap().status,
+            DownloadStatus::Completed
+        );
+
+        std::fs::write(path.join("config.json"), b"corrupted").unwrap();
+        assert_eq!(
+            manager.verify_model(ModelType::Whisper, "whisper-tiny").unwrap(),
+            ModelVerification::Corrupted(vec!["config.json".to_string()])
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Serve the whisper files like the Hub, with `config.json` stored in Git
+    /// and the other files in LFS
+    fn hub_server(files: &[(String, Vec<u8>)], lfs_digest: impl Fn(&[u8]) -> String) -> TestServer {
+        use sha2::{Digest, Sha256};
+        let mut served = HashMap::new();
+        let mut headers = HashMap::new();
+        for (name, body) in files {
+            let path = format!("/openai/whisper-tiny/resolve/main/{}", name);
+            let digest = if name == "config.json" {
+                let mut blob = format!("blob {}\0", body.len()).into_bytes();
+                blob.extend_from_slice(body);
+                let sha1 = ring::digest::digest(&ring::digest::SHA1_FOR_LEGACY_USE_ONLY, &blob);
+                let hex: String = sha1.as_ref().iter().map(|b| format!("{:02x}", b)).collect();
+                format!("ETag: \"{}\"\r\n", hex)
+            } else {
+                format!(
+                    "ETag: \"{:x}\"\r\nX-Linked-Etag: \"{}\"\r\n",
+                    Sha256::digest(name.as_bytes()),
+                    lfs_digest(body)
+                )
+            };
+            headers.insert(path.clone(), format!("X-Repo-Commit: abc123\r\n{}", digest));
+            served.insert(path, body.clone());
+        }
+        TestServer::start_with_headers(served, headers)
+    }
+
+    #[test]
+    fn test_download_verified_by_hub_digests() {
+        use sha2::{Digest, Sha256};
+        let files = whisper_files();
+        let server = hub_server(&files, |body| format!("{:x}", Sha256::digest(body)));
+
+        let dir = temp_dir("hub-digests");
+        let manager = ModelManager::with_models_dir(&dir)
+            .with_mirror(ModelMirror::BaseUrl(server.base_url.clone()));
+
+        manager.download_model_sync(ModelType::Whisper, "whisper-tiny", None).unwrap();
+        assert_eq!(
+            manager.verify_model(ModelType::Whisper, "whisper-tiny").unwrap(),
+            ModelVerification::Verified
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_download_rejects_hub_digest_mismatch() {
+        let files = whisper_files();
+        let server = hub_server(&files, |_| "0".repeat(64));
+
+        let dir = temp_dir("hub-mismatch");
+        let manager = ModelManager::with_models_dir(&dir)
+            .with_mirror(ModelMirror::BaseUrl(server.base_url.clone()));
+
+        let err =
+            manager.download_model_sync(ModelType::Whisper, "whisper-tiny", None).unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"));
+        assert!(!manager.is_downloaded(ModelType::Whisper, "whisper-tiny"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_download_rejects_checksum_mismatch() {
+        let body = b"not the real model".to_vec();
+        let mut served = HashMap::new();
+        served.insert("/silero-vad/silero_vad.onnx".to_string(), body);
+        served.insert(
+            "/silero-vad/manifest.json".to_string(),
+            manifest_json("silero-vad", &[(VAD_MODEL_FILE.to_string(), b"expected".to_vec())]),
+        );
+        let server = TestServer::start(served);
+
+        let dir = temp_dir("mismatch");
+        let manager = ModelManager::with_models_dir(&dir)
+            .with_mirror(ModelMirror::BaseUrl(server.base_url.clone()));
+
+        let err = manager.download_model_sync(ModelType::Vad, VAD_MODEL_ID, None).unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"));
+        assert!(!manager.is_downloaded(ModelType::Vad, VAD_MODEL_ID));
+        assert!(matches!(
+            manager.get_download_progress(VAD_MODEL_ID).unwrap().status,
+            DownloadStatus::Failed(_)
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_download_resumes_partial_file() {
+        let body: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let mut served = HashMap::new();
+        served.insert("/silero-vad/silero_vad.onnx".to_string(), body.clone());
+        let server = TestServer::start(served);
+
+        let dir = temp_dir("resume");
+        let manager = ModelManager::with_models_dir(&dir)
+            .with_mirror(ModelMirror::BaseUrl(server.base_url.clone()));
+
+        let model_dir = manager.model_dir(ModelType::Vad, VAD_MODEL_ID);
+        std::fs::create_dir_all(&model_dir).unwrap();
+        let dest = model_dir.join(VAD_MODEL_FILE);
+        std::fs::write(fetch::partial_path(&dest), &body[..4000]).unwrap();
+
+        let last = Arc::new(Mutex::new(None));
+        let last_clone = last.clone();
+        let callback: Box<dyn Fn(DownloadProgress) + Send> = Box::new(move |p| {
+            if p.status == DownloadStatus::Downloading {
+                *last_clone.lock().unwrap() = Some((p.downloaded_bytes, p.total_bytes));
+            }
+        });
+
+        manager.download_model_sync(ModelType::Vad, VAD_MODEL_ID, Some(callback)).unwrap();
+
+        assert_eq!(std::fs::read(&dest).unwrap(), body);
+        assert!(!fetch::partial_path(&dest).exists());
+        assert_eq!(server.range_requests.lock().unwrap().as_slice(), ["4000-"]);
+        assert_eq!(*last.lock().unwrap(), Some((10_000, 10_000)));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_download_from_local_directory_mirror() {
+        let source = temp_dir("local-mirror-src");
+        let vad_dir = source.join("vad").join(VAD_MODEL_ID);
+        std::fs::create_dir_all(&vad_dir).unwrap();
+        std::fs::write(vad_dir.join(VAD_MODEL_FILE), b"onnx").unwrap();
+
+        let dir = temp_dir("local-mirror");
+        let manager = ModelManager::with_models_dir(&dir)
+            .with_mirror(ModelMirror::parse(&format!("file://{}", source.display())).unwrap());
+
+        manager.download_model_sync(ModelType::Vad, VAD_MODEL_ID, None).unwrap();
+        assert!(manager.is_downloaded(ModelType::Vad, VAD_MODEL_ID));
+        // The mirror publishes no manifest, so nothing pins the downloaded bytes
+        assert_eq!(
+            manager.verify_model(ModelType::Vad, VAD_MODEL_ID).unwrap(),
+            ModelVerification::Unverified
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::remove_dir_all(&source).unwrap();
+    }
+
+    #[test]
+    fn test_import_model_from_directory() {
+        let source = temp_dir("import-src").join("whisper-tiny");
+        std::fs::create_dir_all(&source).unwrap();
+        let files = whisper_files();
+        for (name, body) in &files {
+            std::fs::write(source.join(name), body).unwrap();
+        }
+
+        let dir = temp_dir("import-dir");
+        let manager = ModelManager::with_models_dir(&dir);
+
+        let path = manager.import_model(ModelType::Whisper, &source).unwrap();
+        assert_eq!(path, manager.model_dir(ModelType::Whisper, "whisper-tiny"));
+        assert!(manager.is_downloaded(ModelType::Whisper, "whisper-tiny"));
+        assert_eq!(
+            manager.verify_model(ModelType::Whisper, "whisper-tiny").unwrap(),
+            ModelVerification::Unverified
+        );
+
+        // A manifest that does not match the files must abort the import
+        std::fs::write(
+            source.join(MANIFEST_FILE),
+            manifest_json("whisper-base", &[("config.json".to_string(), b"other".to_vec())]),
+        )
+        .unwrap();
+        assert!(manager.import_model(ModelType::Whisper, &source).is_err());
+        assert!(!manager.is_downloaded(ModelType::Whisper, "whisper-base"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::remove_dir_all(source.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_import_model_from_archive() {
+        let source = temp_dir("import-archive-src");
+        let archive_path = source.join("trocr-base-printed.tar.gz");
+        let files: Vec<(String, Vec<u8>)> =
+            TROCR_FILES.iter().map(|f| (f.to_string(), f.as_bytes().to_vec())).collect();
+
+        {
+            let file = std::fs::File::create(&archive_path).unwrap();
+            let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::fast());
+            let mut builder = tar::Builder::new(encoder);
+            let mut entries = files.clone();
+            entries.push((MANIFEST_FILE.to_string(), manifest_json("trocr-base-printed", &files)));
+            for (name, body) in &entries {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(body.len() as u64);
+                header.set_mode(0o644);
+                header.set_cksum();
+                builder
+                    .append_data(
+                        &mut header,
+                        format!("trocr-base-printed/{}", name),
+                        body.as_slice(),
+                    )
+                    .unwrap();
+            }
+            builder.into_inner().unwrap().finish().unwrap();
+        }
+
+        let dir = temp_dir("import-archive");
+        let manager = ModelManager::with_models_dir(&dir);
+
+        manager.import_model(ModelType::TrOCR, &archive_path).unwrap();
+        assert!(manager.is_downloaded(ModelType::TrOCR, "trocr-base-printed"));
+        assert_eq!(
+            manager.verify_model(ModelType::TrOCR, "trocr-base-printed").unwrap(),
+            ModelVerification::Verified
+        );
+        assert!(!manager.type_dir(ModelType::TrOCR).join(".trocr-base-printed.extract").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::remove_dir_all(&source).unwrap();
+    }
+
+    #[test]
+    fn test_custom_catalogue_models() {
+        let dir = temp_dir("catalogue");
+        let spec = ModelSpec {
+            id: "distil-small.en".to_string(),
+            name: "Distil-Whisper Small".to_string(),
+            description: String::new(),
+            model_type: ModelType::Whisper,
+            size_bytes: 0,
+            repo_id: "distil-whisper/distil-small.en".to_string(),
+            url: None,
+            files: vec!["model.safetensors".to_string()],
+        };
+        let manager = ModelManager::with_models_dir(&dir)
+            .with_catalogue(CustomCatalogue::new("test").with_model(spec));
+
+        let whisper = manager.list_whisper_models();
+        assert_eq!(whisper.len(), WHISPER_MODELS.len() + 1);
+        assert!(!manager.is_downloaded(ModelType::Whisper, "distil-small.en"));
+
+        let model_dir = manager.model_dir(ModelType::Whisper, "distil-small.en");
+        std::fs::create_dir_all(&model_dir).unwrap();
+        std::fs::write(model_dir.join("model.safetensors"), vec![0u8; 100]).unwrap();
+        assert!(manager.is_downloaded(ModelType::Whisper, "distil-small.en"));
+        assert_eq!(manager.list_downloaded_models(ModelType::Whisper).len(), 1);
+        assert!(manager.get_model_info(ModelType::TrOCR, "distil-small.en").is_none());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_disk_usage_and_legacy_migration() {
+        let dir = temp_dir("disk-usage");
+        let manager = ModelManager::with_models_dir(&dir);
+
+        let vad_dir = manager.model_dir(ModelType::Vad, VAD_MODEL_ID);
+        std::fs::create_dir_all(&vad_dir).unwrap();
+        std::fs::write(vad_dir.join(VAD_MODEL_FILE), vec![0u8; 300]).unwrap();
+        let orphan_dir = manager.model_dir(ModelType::TrOCR, "removed-model");
+        std::fs::create_dir_all(&orphan_dir).unwrap();
+        std::fs::write(orphan_dir.join("model.safetensors"), vec![0u8; 50]).unwrap();
+
+        // Old STT layout: GGML weights directly in the models directory
+        std::fs::write(dir.join("whisper-base.bin"), vec![0u8; 1000]).unwrap();
+        std::fs::write(dir.join("whisper-small.bin.tmp"), vec![0u8; 200]).unwrap();
+
+        let usage = manager.disk_usage();
+        assert_eq!(usage.legacy_bytes, 1200);
+        assert_eq!(usage.total_bytes, 1550);
+        assert_eq!(usage.models.len(), 2);
+        assert!(usage.models.iter().any(|m| m.model_id == "removed-model" && !m.in_catalogue));
+        assert_eq!(manager.model_disk_usage(ModelType::Vad, VAD_MODEL_ID), 300);
+
+        let legacy = manager.find_legacy_stt_models();
+        assert_eq!(legacy.len(), 2);
+        assert_eq!(legacy[0].replacement.as_deref(), Some("whisper-base"));
+
+        let migration = manager.migrate_legacy_stt_models().unwrap();
+        assert_eq!(migration.reclaimed_bytes, 1200);
+        assert_eq!(migration.to_download, vec!["whisper-base", "whisper-small"]);
+        assert!(manager.find_legacy_stt_models().is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
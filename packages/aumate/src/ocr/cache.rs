@@ -0,0 +1,130 @@
+//! Recognition cache for OCR
+//!
+//! Recognizing a text line is by far the most expensive part of OCR. Lines are
+//! keyed by a hash of their pixels, so repeated recognition of an unchanged
+//! screen (or of lines that merely moved) skips the decoder entirely.
+
+use image::DynamicImage;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+
+/// Default number of cached lines
+pub const DEFAULT_CACHE_CAPACITY: usize = 512;
+
+/// Hash the pixels and dimensions of an image
+pub fn image_hash(image: &DynamicImage) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    image.width().hash(&mut hasher);
+    image.height().hash(&mut hasher);
+    image.color().channel_count().hash(&mut hasher);
+    image.as_bytes().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Least-recently-inserted cache keyed by image hash
+#[derive(Debug, Clone)]
+pub(crate) struct RecognitionCache<V> {
+    capacity: usize,
+    entries: HashMap<u64, V>,
+    order: VecDeque<u64>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<V: Clone> RecognitionCache<V> {
+    /// Create a cache holding at most `capacity` entries (0 disables caching)
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, entries: HashMap::new(), order: VecDeque::new(), hits: 0, misses: 0 }
+    }
+
+    /// Look up a cached value
+    pub fn get(&mut self, key: u64) -> Option<V> {
+        match self.entries.get(&key) {
+            Some(value) => {
+                self.hits += 1;
+                Some(value.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Store a value, evicting the oldest entry when full
+    pub fn insert(&mut self, key: u64, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.insert(key, value).is_none() {
+            self.order.push_back(key);
+        }
+        while self.entries.len() > self.capacity {
+            match self.order.pop_front() {
+                Some(oldest) => {
+                    self.entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Change the capacity, evicting entries as needed
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.entries.len() > capacity {
+            match self.order.pop_front() {
+                Some(oldest) => {
+                    self.entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Remove all entries and reset statistics
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /// Cache hits and misses since the last clear
+    pub fn stats(&self) -> (u64, u64) {
+        (self.hits, self.misses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_hash_changes_with_pixels() {
+        let a = DynamicImage::new_rgb8(8, 8);
+        let mut b = a.to_rgb8();
+        assert_eq!(image_hash(&a), image_hash(&DynamicImage::ImageRgb8(b.clone())));
+        b.put_pixel(3, 3, image::Rgb([1, 2, 3]));
+        assert_ne!(image_hash(&a), image_hash(&DynamicImage::ImageRgb8(b)));
+        assert_ne!(image_hash(&a), image_hash(&DynamicImage::new_rgb8(4, 16)));
+    }
+
+    #[test]
+    fn test_cache_evicts_oldest() {
+        let mut cache = RecognitionCache::new(2);
+        cache.insert(1, "a");
+        cache.insert(2, "b");
+        cache.insert(3, "c");
+        assert_eq!(cache.entries.len(), 2);
+        assert_eq!(cache.get(1), None);
+        assert_eq!(cache.get(3), Some("c"));
+        assert_eq!(cache.stats(), (1, 1));
+
+        cache.set_capacity(0);
+        assert!(cache.entries.is_empty());
+        cache.insert(4, "d");
+        assert_eq!(cache.get(4), None);
+    }
+}
@@ -1,10 +1,14 @@
 //! TrOCR-based OCR engine
 //!
 //! Provides optical character recognition using TrOCR model via Candle ML framework.
+//! Text lines are located by the layout analyzer and recognized one at a time, so
+//! results carry line and word geometry along with decoder confidence.
 
 use super::OcrModelVariant;
+use super::cache::{DEFAULT_CACHE_CAPACITY, RecognitionCache, image_hash};
+use super::layout::{LayoutAnalyzer, LayoutLine, TextRect};
 use crate::error::{AumateError, Result};
-use crate::ml::{Device, DeviceConfig, get_device};
+use crate::ml::{Device, DeviceConfig, ModelManager, ModelType, get_device};
 use candle_core::{DType, Tensor};
 use candle_nn::Activation;
 use candle_nn::VarBuilder;
@@ -48,15 +52,64 @@ impl TrOCRModel {
     }
 }
 
+/// A recognized word
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrWord {
+    /// Word text
+    pub text: String,
+    /// Bounding box in image coordinates
+    pub bounds: TextRect,
+    /// Confidence score (0.0 to 1.0), the geometric mean of token probabilities
+    pub confidence: f32,
+}
+
+/// A recognized text line
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrLine {
+    /// Line text
+    pub text: String,
+    /// Bounding box in image coordinates
+    pub bounds: TextRect,
+    /// Confidence score (0.0 to 1.0), the geometric mean of token probabilities
+    pub confidence: f32,
+    /// Words in reading order
+    pub words: Vec<OcrWord>,
+    /// Index of the text block (column or paragraph) the line belongs to
+    pub block: usize,
+}
+
 /// Result of OCR recognition
 #[derive(Debug, Clone)]
 pub struct OcrResult {
-    /// Recognized text
+    /// Recognized text, one line per detected text line
     pub text: String,
     /// Recognition duration in milliseconds
     pub duration_ms: u64,
     /// Confidence score (0.0 to 1.0) if available
     pub confidence: Option<f32>,
+    /// Recognized lines with their geometry, in reading order
+    pub lines: Vec<OcrLine>,
+}
+
+impl OcrResult {
+    /// Iterate over all recognized words
+    pub fn words(&self) -> impl Iterator<Item = &OcrWord> {
+        self.lines.iter().flat_map(|line| line.words.iter())
+    }
+}
+
+/// Decoded token with its log-probability
+#[derive(Debug, Clone, Copy)]
+struct ScoredToken {
+    id: u32,
+    log_prob: f32,
+}
+
+/// Convert the mean log-probability of tokens into a confidence score
+fn confidence_of<'a>(tokens: impl IntoIterator<Item = &'a ScoredToken>) -> Option<f32> {
+    let (sum, count) =
+        tokens.into_iter().fold((0.0f32, 0usize), |(sum, n), t| (sum + t.log_prob, n + 1));
+    (count > 0).then(|| (sum / count as f32).exp())
 }
 
 /// TrOCR-based OCR engine
@@ -75,6 +128,10 @@ pub struct OcrEngine {
     image_size: usize,
     /// Whether to use KV cache for decoding
     use_cache: bool,
+    /// Text line detector
+    layout: LayoutAnalyzer,
+    /// Decoded tokens of recently recognized line images
+    cache: RecognitionCache<Vec<ScoredToken>>,
 }
 
 impl OcrEngine {
@@ -89,6 +146,8 @@ impl OcrEngine {
             variant: None,
             image_size: 384,  // Default TrOCR image size
             use_cache: false, // Default to no cache (TrOCR base models don't use it)
+            layout: LayoutAnalyzer::new(),
+            cache: RecognitionCache::new(DEFAULT_CACHE_CAPACITY),
         }
     }
 
@@ -103,6 +162,8 @@ impl OcrEngine {
             variant: None,
             image_size: 384,
             use_cache: false,
+            layout: LayoutAnalyzer::new(),
+            cache: RecognitionCache::new(DEFAULT_CACHE_CAPACITY),
         })
     }
 
@@ -211,6 +272,8 @@ impl OcrEngine {
         self.model = Some(TrOCRModel { model });
         self.tokenizer = Some(tokenizer);
         self.model_path = Some(model_dir.to_path_buf());
+        self.variant = None;
+        self.cache.clear();
 
         log::info!(
             "TrOCR model loaded successfully on {:?} (use_cache={})",
@@ -220,12 +283,24 @@ impl OcrEngine {
         Ok(())
     }
 
+    /// Load a downloaded model variant from the model manager
+    pub fn load_variant(&mut self, variant: OcrModelVariant, manager: &ModelManager) -> Result<()> {
+        let model_dir =
+            manager.get_model_path(ModelType::TrOCR, variant.model_id()).ok_or_else(|| {
+                AumateError::Ml(format!("OCR model {} is not downloaded", variant.model_id()))
+            })?;
+        self.load_model(&model_dir)?;
+        self.variant = Some(variant);
+        Ok(())
+    }
+
     /// Unload the current model
     pub fn unload_model(&mut self) {
         self.model = None;
         self.tokenizer = None;
         self.model_path = None;
         self.variant = None;
+        self.cache.clear();
         log::info!("TrOCR model unloaded");
     }
 
@@ -279,7 +354,30 @@ impl OcrEngine {
         &self.device
     }
 
-    /// Recognize text from an image (supports multi-line text)
+    /// Replace the text line detector
+    pub fn set_layout_analyzer(&mut self, layout: LayoutAnalyzer) {
+        self.layout = layout;
+    }
+
+    /// Set how many recognized line images are cached (0 disables the cache)
+    pub fn set_cache_capacity(&mut self, capacity: usize) {
+        self.cache.set_capacity(capacity);
+    }
+
+    /// Drop all cached line recognitions
+    pub fn clear_cache(&mut self) {
+        self.cache.clear();
+    }
+
+    /// Line cache hits and misses since the cache was last cleared
+    pub fn cache_stats(&self) -> (u64, u64) {
+        self.cache.stats()
+    }
+
+    /// Recognize text from an image (supports multi-line and multi-column text)
+    ///
+    /// Each detected line is recognized separately. If no lines are found the
+    /// whole image is treated as a single line.
     pub fn recognize(&mut self, image: &image::DynamicImage) -> Result<OcrResult> {
         let tokenizer = self
             .tokenizer
@@ -287,27 +385,43 @@ impl OcrEngine {
             .ok_or_else(|| AumateError::Other("No tokenizer loaded".to_string()))?;
 
         let start_time = Instant::now();
+        let (width, height) = (image.width(), image.height());
+
+        let mut layout = self.layout.analyze(image);
+        if layout.is_empty() {
+            let bounds = TextRect::new(0, 0, width, height);
+            layout.push(LayoutLine { bounds, words: vec![bounds], block: 0 });
+        } else {
+            log::info!("Detected {} text lines in {}x{} image", layout.len(), width, height);
+        }
 
-        // Detect text lines in the image
-        let line_images = self.detect_text_lines(image);
-
-        let text = if line_images.len() > 1 {
-            // Multi-line: process each line separately and join with newlines
-            log::info!("Detected {} text lines, processing each separately", line_images.len());
-            let mut line_results = Vec::new();
-            for (i, line_image) in line_images.iter().enumerate() {
-                let image_tensor = self.preprocess_image(line_image)?;
-                let line_text = self.decode_image(&image_tensor, &tokenizer)?;
-                if !line_text.is_empty() {
-                    log::info!("Line {}: \"{}\"", i + 1, line_text);
-                    line_results.push(line_text);
+        let mut lines = Vec::with_capacity(layout.len());
+        for detected in layout {
+            let padding = (detected.bounds.height / 4).max(2);
+            let crop = detected.bounds.pad(padding, width, height);
+            let line_image = image.crop_imm(crop.x, crop.y, crop.width, crop.height);
+
+            let key = image_hash(&line_image);
+            let tokens = match self.cache.get(key) {
+                Some(tokens) => tokens,
+                None => {
+                    let image_tensor = self.preprocess_image(&line_image)?;
+                    let tokens = self.decode_image(&image_tensor, &tokenizer)?;
+                    self.cache.insert(key, tokens.clone());
+                    tokens
                 }
+            };
+            if let Some(line) = Self::build_line(&tokenizer, &tokens, &detected)? {
+                log::debug!("Line {:?} ({:.2}): \"{}\"", line.bounds, line.confidence, line.text);
+                lines.push(line);
             }
-            line_results.join("\n")
+        }
+
+        let text = lines.iter().map(|l| l.text.as_str()).collect::<Vec<_>>().join("\n");
+        let confidence = if lines.is_empty() {
+            None
         } else {
-            // Single line or no clear line detection: process whole image
-            let image_tensor = self.preprocess_image(image)?;
-            self.decode_image(&image_tensor, &tokenizer)?
+            Some(lines.iter().map(|l| l.confidence).sum::<f32>() / lines.len() as f32)
         };
 
         let duration_ms = start_time.elapsed().as_millis() as u64;
@@ -315,184 +429,70 @@ impl OcrEngine {
         log::info!(
             "OCR completed in {}ms: \"{}\"",
             duration_ms,
-            if text.len() > 50 { format!("{}...", &text[..50]) } else { text.clone() }
-        );
-
-        Ok(OcrResult { text, duration_ms, confidence: None })
-    }
-
-    /// Detect text lines in an image using horizontal projection analysis
-    ///
-    /// Returns a vector of cropped line images. If only one line is detected
-    /// or detection fails, returns the original image.
-    fn detect_text_lines(&self, image: &image::DynamicImage) -> Vec<image::DynamicImage> {
-        let gray = image.to_luma8();
-        let (width, height) = gray.dimensions();
-
-        if height < 20 || width < 20 {
-            return vec![image.clone()];
-        }
-
-        // Apply adaptive binarization to handle complex backgrounds
-        // This helps detect text lines regardless of background color
-        let binary = self.adaptive_binarize(&gray);
-
-        // Calculate horizontal projection (count of "text" pixels per row)
-        let mut projection: Vec<u32> = Vec::with_capacity(height as usize);
-        for y in 0..height {
-            let mut row_sum: u32 = 0;
-            for x in 0..width {
-                // In binary image, 0 = text (foreground), 255 = background
-                if binary.get_pixel(x, y).0[0] == 0 {
-                    row_sum += 1;
-                }
-            }
-            projection.push(row_sum);
-        }
-
-        // Find threshold for detecting gaps between lines
-        // A row is considered a "gap" if it has very few text pixels
-        let max_projection = *projection.iter().max().unwrap_or(&1);
-        let gap_threshold = max_projection / 10; // Rows with <10% of max are gaps
-
-        // Find line boundaries (runs of non-gap rows)
-        let mut lines: Vec<(u32, u32)> = Vec::new(); // (start_y, end_y)
-        let mut in_line = false;
-        let mut line_start = 0u32;
-        let min_line_height = (height / 30).max(3); // Minimum line height to avoid noise
-        let min_gap_height = (height / 50).max(2); // Minimum gap height
-
-        let mut gap_start = 0u32;
-        let mut in_gap = false;
-
-        for (y, &proj) in projection.iter().enumerate() {
-            let y = y as u32;
-            if proj > gap_threshold {
-                // This row has text
-                if in_gap && in_line {
-                    // Check if gap was significant enough
-                    let gap_height = y - gap_start;
-                    if gap_height >= min_gap_height {
-                        // End previous line at gap start
-                        let line_height = gap_start - line_start;
-                        if line_height >= min_line_height {
-                            lines.push((line_start, gap_start));
-                        }
-                        line_start = y;
-                    }
-                }
-                if !in_line {
-                    line_start = y;
-                    in_line = true;
-                }
-                in_gap = false;
+            if text.chars().count() > 50 {
+                format!("{}...", text.chars().take(50).collect::<String>())
             } else {
-                // This row is a potential gap
-                if in_line && !in_gap {
-                    gap_start = y;
-                    in_gap = true;
-                }
+                text.clone()
             }
-        }
+        );
 
-        // Don't forget the last line
-        if in_line {
-            let end_y = if in_gap { gap_start } else { height };
-            let line_height = end_y - line_start;
-            if line_height >= min_line_height {
-                lines.push((line_start, end_y));
-            }
-        }
+        Ok(OcrResult { text, duration_ms, confidence, lines })
+    }
 
-        // If we detected less than 2 lines, return original image
-        if lines.len() < 2 {
-            return vec![image.clone()];
+    /// Assemble a recognized line from decoded tokens and detected geometry
+    fn build_line(
+        tokenizer: &Tokenizer,
+        tokens: &[ScoredToken],
+        detected: &LayoutLine,
+    ) -> Result<Option<OcrLine>> {
+        let ids: Vec<u32> = tokens.iter().map(|t| t.id).collect();
+        let text = tokenizer
+            .decode(&ids, true)
+            .map_err(|e| AumateError::Other(format!("Failed to decode tokens: {}", e)))?
+            .trim()
+            .to_string();
+        if text.is_empty() {
+            return Ok(None);
         }
 
-        // Crop each detected line with some vertical padding
-        let padding = (height / 40).max(2);
-        let mut line_images = Vec::new();
-
-        for (start_y, end_y) in lines {
-            let padded_start = start_y.saturating_sub(padding);
-            let padded_end = (end_y + padding).min(height);
-            let crop_height = padded_end - padded_start;
-
-            if crop_height > 0 {
-                let cropped = image.crop_imm(0, padded_start, width, crop_height);
-                line_images.push(cropped);
+        // Byte-level BPE marks tokens that start a new word with a leading 'Ġ' (space)
+        let mut groups: Vec<Vec<ScoredToken>> = Vec::new();
+        for token in tokens {
+            let starts_word =
+                tokenizer.id_to_token(token.id).is_some_and(|t| t.starts_with('\u{0120}'));
+            match groups.last_mut() {
+                Some(group) if !starts_word => group.push(*token),
+                _ => groups.push(vec![*token]),
             }
         }
 
-        if line_images.is_empty() {
-            vec![image.clone()]
-        } else {
-            log::info!(
-                "Text line detection: found {} lines in {}x{} image",
-                line_images.len(),
-                width,
-                height
-            );
-            line_images
-        }
-    }
-
-    /// Apply adaptive binarization to handle various backgrounds
-    ///
-    /// Uses local mean thresholding to separate text from background,
-    /// works for both dark-on-light and light-on-dark text.
-    fn adaptive_binarize(&self, gray: &image::GrayImage) -> image::GrayImage {
-        let (width, height) = gray.dimensions();
-
-        // Window size for local thresholding (adapt to image size)
-        let window_size = ((width.min(height) / 15) as usize).clamp(15, 51);
-        let half_window = window_size / 2;
-
-        // Create integral image for fast mean calculation
-        let mut integral: Vec<Vec<u64>> =
-            vec![vec![0; (width + 1) as usize]; (height + 1) as usize];
-        for y in 0..height {
-            for x in 0..width {
-                let pixel = gray.get_pixel(x, y).0[0] as u64;
-                integral[(y + 1) as usize][(x + 1) as usize] = pixel
-                    + integral[y as usize][(x + 1) as usize]
-                    + integral[(y + 1) as usize][x as usize]
-                    - integral[y as usize][x as usize];
+        let mut words = Vec::with_capacity(groups.len());
+        for group in &groups {
+            let ids: Vec<u32> = group.iter().map(|t| t.id).collect();
+            let word = tokenizer
+                .decode(&ids, true)
+                .map_err(|e| AumateError::Other(format!("Failed to decode tokens: {}", e)))?
+                .trim()
+                .to_string();
+            if !word.is_empty() {
+                words.push((word, confidence_of(group).unwrap_or(0.0)));
             }
         }
 
-        let mut binary = image::GrayImage::new(width, height);
-
-        for y in 0..height {
-            for x in 0..width {
-                // Calculate local mean using integral image
-                let x1 = (x as i32 - half_window as i32).max(0) as usize;
-                let y1 = (y as i32 - half_window as i32).max(0) as usize;
-                let x2 = ((x as usize + half_window + 1).min(width as usize)).min(width as usize);
-                let y2 = ((y as usize + half_window + 1).min(height as usize)).min(height as usize);
-
-                let area = ((x2 - x1) * (y2 - y1)) as u64;
-                // Reorder to avoid underflow: (a + d) - b - c instead of a - b - c + d
-                let sum = (integral[y2][x2] + integral[y1][x1])
-                    .saturating_sub(integral[y1][x2])
-                    .saturating_sub(integral[y2][x1]);
-                let local_mean = (sum / area.max(1)) as u8;
-
-                let pixel = gray.get_pixel(x, y).0[0];
-
-                // Adaptive threshold with bias towards detecting text
-                // Text is darker than local mean (dark text) or lighter (light text)
-                let threshold_bias = 15u8;
-                let lower_bound = local_mean.saturating_sub(threshold_bias);
-                let upper_bound = local_mean.saturating_add(threshold_bias);
-                // Text pixels deviate significantly from local mean
-                let is_text = pixel < lower_bound || pixel > upper_bound;
-
-                binary.put_pixel(x, y, image::Luma([if is_text { 0 } else { 255 }]));
-            }
-        }
+        let boxes = word_boxes(&words, detected);
+        let words = words
+            .into_iter()
+            .zip(boxes)
+            .map(|((text, confidence), bounds)| OcrWord { text, bounds, confidence })
+            .collect();
 
-        binary
+        Ok(Some(OcrLine {
+            text,
+            bounds: detected.bounds,
+            confidence: confidence_of(tokens).unwrap_or(0.0),
+            words,
+            block: detected.block,
+        }))
     }
 
     /// Recognize text from image bytes (PNG, JPEG, etc.)
@@ -568,8 +568,11 @@ impl OcrEngine {
         Ok(tensor)
     }
 
-    /// Decode image to text using autoregressive generation
-    fn decode_image(&mut self, image: &Tensor, tokenizer: &Tokenizer) -> Result<String> {
+    /// Decode image to tokens using autoregressive generation
+    ///
+    /// Returns the generated tokens (without start and end tokens) with the
+    /// log-probability the decoder assigned to each.
+    fn decode_image(&mut self, image: &Tensor, tokenizer: &Tokenizer) -> Result<Vec<ScoredToken>> {
         let model =
             self.model.as_mut().ok_or_else(|| AumateError::Other("No model loaded".to_string()))?;
 
@@ -586,6 +589,7 @@ impl OcrEngine {
 
         // Start with decoder start token
         let mut token_ids: Vec<u32> = vec![decoder_start_token_id];
+        let mut scored = Vec::new();
         let max_tokens = 512;
 
         // Autoregressive decoding loop (following official Candle example pattern)
@@ -612,16 +616,18 @@ impl OcrEngine {
                 .squeeze(1)
                 .map_err(|e| AumateError::Ml(format!("Failed to squeeze: {}", e)))?;
 
-            // Greedy decoding: take argmax
-            // last_logits has shape [1, vocab_size], argmax on dim 1 gives [1]
-            // We need to squeeze or index to get a scalar
-            let next_token = last_logits
-                .argmax(candle_core::D::Minus1)
-                .map_err(|e| AumateError::Ml(format!("Failed to argmax: {}", e)))?
+            // Greedy decoding: take the most likely token and keep its log-probability
+            // last_logits has shape [1, vocab_size]
+            let log_probs = candle_nn::ops::log_softmax(&last_logits, candle_core::D::Minus1)
+                .map_err(|e| AumateError::Ml(format!("Failed to compute log-softmax: {}", e)))?
                 .squeeze(0)
-                .map_err(|e| AumateError::Ml(format!("Failed to squeeze argmax result: {}", e)))?
-                .to_scalar::<u32>()
-                .map_err(|e| AumateError::Ml(format!("Failed to get scalar: {}", e)))?;
+                .map_err(|e| AumateError::Ml(format!("Failed to squeeze log-probs: {}", e)))?
+                .to_vec1::<f32>()
+                .map_err(|e| AumateError::Ml(format!("Failed to read log-probs: {}", e)))?;
+            let (next_token, log_prob) =
+                log_probs.iter().enumerate().fold((0u32, f32::NEG_INFINITY), |best, (id, &lp)| {
+                    if lp > best.1 { (id as u32, lp) } else { best }
+                });
 
             // Check for end of sequence
             if next_token == eos_token_id {
@@ -629,20 +635,10 @@ impl OcrEngine {
             }
 
             token_ids.push(next_token);
+            scored.push(ScoredToken { id: next_token, log_prob });
         }
 
-        // Decode tokens to text (skip decoder start token)
-        let text_tokens: Vec<u32> = token_ids
-            .into_iter()
-            .skip(1) // Skip decoder start token
-            .filter(|&t| t != eos_token_id)
-            .collect();
-
-        let text = tokenizer
-            .decode(&text_tokens, true)
-            .map_err(|e| AumateError::Other(format!("Failed to decode tokens: {}", e)))?;
-
-        Ok(text.trim().to_string())
+        Ok(scored)
     }
 }
 
@@ -652,6 +648,33 @@ impl Default for OcrEngine {
     }
 }
 
+/// Assign bounding boxes to recognized words
+///
+/// When the recognizer produced as many words as the layout analyzer found,
+/// they are paired in order. Otherwise the line box is divided in proportion
+/// to the character counts of the words, counting one character per space.
+fn word_boxes(words: &[(String, f32)], line: &LayoutLine) -> Vec<TextRect> {
+    if words.len() == line.words.len() {
+        return line.words.clone();
+    }
+
+    let chars: Vec<usize> = words.iter().map(|(w, _)| w.chars().count().max(1)).collect();
+    let total = chars.iter().sum::<usize>() + words.len().saturating_sub(1);
+    let bounds = line.bounds;
+    let unit = bounds.width as f32 / total.max(1) as f32;
+
+    let mut offset = 0usize;
+    chars
+        .iter()
+        .map(|&n| {
+            let x = bounds.x + (offset as f32 * unit).round() as u32;
+            let right = bounds.x + ((offset + n) as f32 * unit).round() as u32;
+            offset += n + 1;
+            TextRect::new(x, bounds.y, right.saturating_sub(x).max(1), bounds.height)
+        })
+        .collect()
+}
+
 /// JSON config for TrOCR models (partial, for deserialization)
 #[derive(Debug, serde::Deserialize)]
 struct TrOCRConfigJson {
@@ -717,74 +740,36 @@ mod tests {
     }
 
     #[test]
-    fn test_detect_text_lines_single_line() {
-        use image::{DynamicImage, Rgb, RgbImage};
-
-        let engine = OcrEngine::new();
-
-        // Create a simple white image with a single black line of "text" in the middle
-        let mut img = RgbImage::new(200, 50);
-        // Fill with white
-        for pixel in img.pixels_mut() {
-            *pixel = Rgb([255, 255, 255]);
-        }
-        // Add a "text line" (dark pixels) in the middle
-        for x in 20..180 {
-            for y in 20..30 {
-                img.put_pixel(x, y, Rgb([0, 0, 0]));
-            }
-        }
-
-        let dynamic_img = DynamicImage::ImageRgb8(img);
-        let lines = engine.detect_text_lines(&dynamic_img);
-
-        // Should return original image for single line
-        assert_eq!(lines.len(), 1);
+    fn test_word_boxes_pair_with_layout() {
+        let line = LayoutLine {
+            bounds: TextRect::new(10, 5, 100, 12),
+            words: vec![TextRect::new(10, 5, 40, 12), TextRect::new(60, 5, 50, 12)],
+            block: 0,
+        };
+        let words = vec![("Hello".to_string(), 0.9), ("world".to_string(), 0.8)];
+        assert_eq!(word_boxes(&words, &line), line.words);
     }
 
     #[test]
-    fn test_detect_text_lines_multi_line() {
-        use image::{DynamicImage, Rgb, RgbImage};
-
-        let engine = OcrEngine::new();
-
-        // Create an image with two distinct text lines
-        let mut img = RgbImage::new(200, 100);
-        // Fill with white
-        for pixel in img.pixels_mut() {
-            *pixel = Rgb([255, 255, 255]);
-        }
-        // First text line (y: 15-25)
-        for x in 20..180 {
-            for y in 15..25 {
-                img.put_pixel(x, y, Rgb([0, 0, 0]));
-            }
-        }
-        // Second text line (y: 60-70)
-        for x in 20..180 {
-            for y in 60..70 {
-                img.put_pixel(x, y, Rgb([0, 0, 0]));
-            }
-        }
-
-        let dynamic_img = DynamicImage::ImageRgb8(img);
-        let lines = engine.detect_text_lines(&dynamic_img);
-
-        // Should detect 2 lines
-        assert_eq!(lines.len(), 2);
+    fn test_word_boxes_split_proportionally() {
+        let line = LayoutLine {
+            bounds: TextRect::new(0, 0, 110, 10),
+            words: vec![TextRect::new(0, 0, 110, 10)],
+            block: 0,
+        };
+        // 4 + 1 + 6 = 11 character cells of 10px
+        let words = vec![("Save".to_string(), 0.9), ("report".to_string(), 0.9)];
+        assert_eq!(
+            word_boxes(&words, &line),
+            vec![TextRect::new(0, 0, 40, 10), TextRect::new(50, 0, 60, 10)]
+        );
     }
 
     #[test]
-    fn test_detect_text_lines_small_image() {
-        use image::{DynamicImage, RgbImage};
-
-        let engine = OcrEngine::new();
-
-        // Very small image should return original
-        let img = RgbImage::new(10, 10);
-        let dynamic_img = DynamicImage::ImageRgb8(img);
-        let lines = engine.detect_text_lines(&dynamic_img);
-
-        assert_eq!(lines.len(), 1);
+    fn test_confidence_of() {
+        let tokens = [ScoredToken { id: 1, log_prob: 0.0 }, ScoredToken { id: 2, log_prob: -2.0 }];
+        let confidence = confidence_of(&tokens).unwrap();
+        assert!((confidence - (-1.0f32).exp()).abs() < 1e-6);
+        assert_eq!(confidence_of(&[]), None);
     }
 }
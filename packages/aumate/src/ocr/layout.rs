@@ -0,0 +1,639 @@
+//! Text layout analysis for OCR
+//!
+//! Finds text lines and words in an image with a connected-component analyzer:
+//! adaptive binarization, 8-connected component labeling, grouping of glyphs into
+//! words and words into lines, and recursive XY-cut segmentation into text blocks
+//! so that multi-column layouts are read column by column.
+
+use image::{DynamicImage, GrayImage};
+
+/// Axis-aligned rectangle in image coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextRect {
+    /// X coordinate (left edge)
+    pub x: u32,
+    /// Y coordinate (top edge)
+    pub y: u32,
+    /// Width
+    pub width: u32,
+    /// Height
+    pub height: u32,
+}
+
+impl TextRect {
+    /// Create a new rectangle
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self { x, y, width, height }
+    }
+
+    /// Right edge x coordinate (exclusive)
+    pub fn right(&self) -> u32 {
+        self.x + self.width
+    }
+
+    /// Bottom edge y coordinate (exclusive)
+    pub fn bottom(&self) -> u32 {
+        self.y + self.height
+    }
+
+    /// Center point
+    pub fn center(&self) -> (u32, u32) {
+        (self.x + self.width / 2, self.y + self.height / 2)
+    }
+
+    /// Area in pixels
+    pub fn area(&self) -> u32 {
+        self.width * self.height
+    }
+
+    /// Smallest rectangle containing both rectangles
+    pub fn union(&self, other: &TextRect) -> TextRect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        TextRect::new(
+            x,
+            y,
+            self.right().max(other.right()) - x,
+            self.bottom().max(other.bottom()) - y,
+        )
+    }
+
+    /// Check if a point lies inside the rectangle
+    pub fn contains(&self, x: u32, y: u32) -> bool {
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+
+    /// Offset the rectangle by a translation
+    pub fn translate(&self, dx: u32, dy: u32) -> TextRect {
+        TextRect::new(self.x + dx, self.y + dy, self.width, self.height)
+    }
+
+    /// Expand the rectangle by `pad` pixels on each side, clamped to the image
+    pub fn pad(&self, pad: u32, max_width: u32, max_height: u32) -> TextRect {
+        let x = self.x.saturating_sub(pad);
+        let y = self.y.saturating_sub(pad);
+        let right = (self.right() + pad).min(max_width);
+        let bottom = (self.bottom() + pad).min(max_height);
+        TextRect::new(x, y, right.saturating_sub(x), bottom.saturating_sub(y))
+    }
+
+    fn vertical_overlap(&self, other: &TextRect) -> u32 {
+        self.bottom().min(other.bottom()).saturating_sub(self.y.max(other.y))
+    }
+
+    fn horizontal_overlap(&self, other: &TextRect) -> u32 {
+        self.right().min(other.right()).saturating_sub(self.x.max(other.x))
+    }
+
+    fn horizontal_gap(&self, other: &TextRect) -> u32 {
+        if self.right() <= other.x {
+            other.x - self.right()
+        } else if other.right() <= self.x {
+            self.x - other.right()
+        } else {
+            0
+        }
+    }
+
+    fn vertical_gap(&self, other: &TextRect) -> u32 {
+        if self.bottom() <= other.y {
+            other.y - self.bottom()
+        } else if other.bottom() <= self.y {
+            self.y - other.bottom()
+        } else {
+            0
+        }
+    }
+}
+
+/// A detected text line with its word boxes
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutLine {
+    /// Bounding box of the line
+    pub bounds: TextRect,
+    /// Bounding boxes of the words, left to right
+    pub words: Vec<TextRect>,
+    /// Index of the text block (column or paragraph) the line belongs to
+    pub block: usize,
+}
+
+/// Configuration for layout analysis
+#[derive(Debug, Clone)]
+pub struct LayoutConfig {
+    /// Components with fewer pixels are treated as noise (default: 3)
+    pub min_component_pixels: usize,
+    /// Maximum gap between glyphs of a word, relative to the median glyph height (default: 0.45)
+    pub word_gap_ratio: f32,
+    /// Maximum gap between words of a line, relative to the line height (default: 1.8)
+    pub line_gap_ratio: f32,
+    /// Minimum whitespace needed to split text blocks, relative to the median line height (default: 1.2)
+    pub block_gap_ratio: f32,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            min_component_pixels: 3,
+            word_gap_ratio: 0.45,
+            line_gap_ratio: 1.8,
+            block_gap_ratio: 1.2,
+        }
+    }
+}
+
+/// Connected-component text layout analyzer
+#[derive(Debug, Clone, Default)]
+pub struct LayoutAnalyzer {
+    config: LayoutConfig,
+}
+
+impl LayoutAnalyzer {
+    /// Create an analyzer with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an analyzer with a custom configuration
+    pub fn with_config(config: LayoutConfig) -> Self {
+        Self { config }
+    }
+
+    /// Detect text lines in reading order
+    ///
+    /// Lines are ordered block by block (top to bottom, columns left to right)
+    /// and top to bottom within each block.
+    pub fn analyze(&self, image: &DynamicImage) -> Vec<LayoutLine> {
+        let gray = image.to_luma8();
+        let (width, height) = gray.dimensions();
+        if width < 4 || height < 4 {
+            return Vec::new();
+        }
+
+        let binary = adaptive_binarize(&gray);
+        let glyphs = self.glyph_boxes(&binary);
+        if glyphs.is_empty() {
+            return Vec::new();
+        }
+
+        let median_height = median(glyphs.iter().map(|g| g.height)).max(1);
+        let words = self.group_words(&glyphs, median_height);
+        let lines = self.group_lines(&words);
+
+        let line_height = median(lines.iter().map(|(b, _)| b.height)).max(1);
+        let min_gap = (line_height as f32 * self.config.block_gap_ratio).round() as u32;
+        let mut order = Vec::with_capacity(lines.len());
+        let mut blocks = Vec::with_capacity(lines.len());
+        let mut block = 0;
+        let bounds: Vec<TextRect> = lines.iter().map(|(b, _)| *b).collect();
+        xy_cut(
+            (0..lines.len()).collect(),
+            &bounds,
+            min_gap.max(2),
+            &mut order,
+            &mut blocks,
+            &mut block,
+        );
+
+        order
+            .into_iter()
+            .zip(blocks)
+            .map(|(idx, block)| {
+                let (bounds, words) = &lines[idx];
+                LayoutLine { bounds: *bounds, words: words.clone(), block }
+            })
+            .collect()
+    }
+
+    /// Label connected components and keep the ones that look like glyphs
+    fn glyph_boxes(&self, binary: &GrayImage) -> Vec<TextRect> {
+        let components = connected_components(binary);
+        if components.is_empty() {
+            return Vec::new();
+        }
+
+        let median_height = median(components.iter().map(|(r, _)| r.height)).max(1);
+        let (width, height) = binary.dimensions();
+
+        components
+            .into_iter()
+            .filter(|(rect, pixels)| {
+                if *pixels < self.config.min_component_pixels {
+                    return false;
+                }
+                // Frames and panels: large, mostly hollow components
+                let large = rect.height > median_height * 4 && rect.width > median_height * 4;
+                let sparse = (*pixels as f32) < rect.area() as f32 * 0.08;
+                if large && sparse {
+                    return false;
+                }
+                // Separators and rules: very thin and long
+                let rule = rect.height <= 2 && rect.width > median_height * 8;
+                let bar = rect.width <= 2 && rect.height > median_height * 8;
+                // Background regions spanning the whole image
+                let background = rect.width >= width * 9 / 10 && rect.height >= height * 9 / 10;
+                !(rule || bar || background)
+            })
+            .map(|(rect, _)| rect)
+            .collect()
+    }
+
+    /// Merge glyphs into words
+    fn group_words(&self, glyphs: &[TextRect], median_height: u32) -> Vec<TextRect> {
+        let max_gap = (median_height as f32 * self.config.word_gap_ratio).ceil() as u32;
+        let stack_gap = (median_height / 2).max(1);
+
+        let mut sorted: Vec<TextRect> = glyphs.to_vec();
+        sorted.sort_by_key(|r| r.x);
+        let mut sets = DisjointSet::new(sorted.len());
+
+        for i in 0..sorted.len() {
+            let a = sorted[i];
+            for (j, b) in sorted.iter().enumerate().skip(i + 1) {
+                if b.x > a.right() + max_gap {
+                    break;
+                }
+                let min_height = a.height.min(b.height).max(1);
+                let same_row =
+                    a.vertical_overlap(b) * 2 >= min_height && a.horizontal_gap(b) <= max_gap;
+                // Dots and accents stacked above or below a glyph
+                let min_width = a.width.min(b.width).max(1);
+                let stacked =
+                    a.horizontal_overlap(b) * 2 >= min_width && a.vertical_gap(b) <= stack_gap;
+                if same_row || stacked {
+                    sets.union(i, j);
+                }
+            }
+        }
+
+        sets.groups()
+            .into_iter()
+            .map(|group| bounding_box(group.iter().map(|&i| &sorted[i])))
+            .collect()
+    }
+
+    /// Merge words into lines, returning each line's box and sorted word boxes
+    fn group_lines(&self, words: &[TextRect]) -> Vec<(TextRect, Vec<TextRect>)> {
+        let mut sorted: Vec<TextRect> = words.to_vec();
+        sorted.sort_by_key(|r| r.x);
+        let mut sets = DisjointSet::new(sorted.len());
+
+        for i in 0..sorted.len() {
+            let a = sorted[i];
+            for (j, b) in sorted.iter().enumerate().skip(i + 1) {
+                let height = a.height.max(b.height);
+                let max_gap = (height as f32 * self.config.line_gap_ratio).ceil() as u32;
+                if b.x > a.right() + max_gap * 2 {
+                    break;
+                }
+                let min_height = a.height.min(b.height).max(1);
+                if a.vertical_overlap(b) * 2 >= min_height && a.horizontal_gap(b) <= max_gap {
+                    sets.union(i, j);
+                }
+            }
+        }
+
+        sets.groups()
+            .into_iter()
+            .map(|group| {
+                let mut line_words: Vec<TextRect> = group.iter().map(|&i| sorted[i]).collect();
+                line_words.sort_by_key(|r| r.x);
+                (bounding_box(line_words.iter()), line_words)
+            })
+            .collect()
+    }
+}
+
+/// Recursive XY-cut: split boxes at the widest whitespace gap until no gap is
+/// large enough, emitting each leaf as a text block in reading order
+fn xy_cut(
+    indices: Vec<usize>,
+    bounds: &[TextRect],
+    min_gap: u32,
+    order: &mut Vec<usize>,
+    blocks: &mut Vec<usize>,
+    block: &mut usize,
+) {
+    if indices.len() > 1 {
+        let rows = split_by_projection(&indices, bounds, min_gap, |r| (r.y, r.bottom()));
+        let cols = split_by_projection(&indices, bounds, min_gap, |r| (r.x, r.right()));
+
+        // Prefer the split with the wider gap; columns break ties so that
+        // side-by-side text is not read across
+        let split = match (rows, cols) {
+            (Some((r, r_gap)), Some((c, c_gap))) => Some(if r_gap > c_gap { r } else { c }),
+            (Some((r, _)), None) => Some(r),
+            (None, Some((c, _))) => Some(c),
+            (None, None) => None,
+        };
+
+        if let Some(groups) = split {
+            for group in groups {
+                xy_cut(group, bounds, min_gap, order, blocks, block);
+            }
+            return;
+        }
+    }
+
+    let mut leaf = indices;
+    leaf.sort_by_key(|&i| (bounds[i].y, bounds[i].x));
+    for i in leaf {
+        order.push(i);
+        blocks.push(*block);
+    }
+    *block += 1;
+}
+
+/// Split boxes along one axis at gaps of at least `min_gap`
+///
+/// Returns the groups in axis order and the widest gap, or `None` if no gap qualifies.
+fn split_by_projection(
+    indices: &[usize],
+    bounds: &[TextRect],
+    min_gap: u32,
+    span: impl Fn(&TextRect) -> (u32, u32),
+) -> Option<(Vec<Vec<usize>>, u32)> {
+    let mut sorted = indices.to_vec();
+    sorted.sort_by_key(|&i| span(&bounds[i]).0);
+
+    let mut groups = vec![Vec::new()];
+    let mut end = span(&bounds[sorted[0]]).1;
+    let mut widest = 0;
+    for i in sorted {
+        let (start, stop) = span(&bounds[i]);
+        if start >= end + min_gap {
+            widest = widest.max(start - end);
+            groups.push(Vec::new());
+        }
+        groups.last_mut().unwrap().push(i);
+        end = end.max(stop);
+    }
+
+    if groups.len() > 1 { Some((groups, widest)) } else { None }
+}
+
+/// Apply adaptive binarization to handle various backgrounds
+///
+/// Uses local mean thresholding to separate text from background. Polarity is
+/// decided from a wider neighbourhood, so dark-on-light and light-on-dark text
+/// both work without the gaps between glyphs turning into foreground.
+/// Foreground pixels are 0, background pixels are 255.
+fn adaptive_binarize(gray: &GrayImage) -> GrayImage {
+    let (width, height) = gray.dimensions();
+    let (w, h) = (width as usize, height as usize);
+
+    // Window size for local thresholding (adapt to image size)
+    let window_size = ((width.min(height) / 15) as usize).clamp(15, 51);
+    let half_window = window_size / 2;
+    // The background dominates a window several times larger than a glyph
+    let half_polarity_window = half_window * 4;
+
+    // Integral image for fast mean calculation
+    let mut integral = vec![0u64; (w + 1) * (h + 1)];
+    for y in 0..h {
+        let mut row_sum = 0u64;
+        for x in 0..w {
+            row_sum += gray.get_pixel(x as u32, y as u32).0[0] as u64;
+            integral[(y + 1) * (w + 1) + x + 1] = integral[y * (w + 1) + x + 1] + row_sum;
+        }
+    }
+
+    let window_mean = |x: usize, y: usize, half: usize| -> u8 {
+        let x1 = x.saturating_sub(half);
+        let y1 = y.saturating_sub(half);
+        let x2 = (x + half + 1).min(w);
+        let y2 = (y + half + 1).min(h);
+
+        let area = ((x2 - x1) * (y2 - y1)) as u64;
+        let sum = (integral[y2 * (w + 1) + x2] + integral[y1 * (w + 1) + x1])
+            .saturating_sub(integral[y1 * (w + 1) + x2])
+            .saturating_sub(integral[y2 * (w + 1) + x1]);
+        (sum / area.max(1)) as u8
+    };
+
+    let threshold_bias = 15u8;
+    let mut binary = GrayImage::new(width, height);
+    for y in 0..h {
+        for x in 0..w {
+            let local_mean = window_mean(x, y, half_window);
+            let light_background = window_mean(x, y, half_polarity_window) >= 128;
+            let pixel = gray.get_pixel(x as u32, y as u32).0[0];
+
+            // Text pixels deviate significantly from the local mean, away from the background
+            let is_text = if light_background {
+                pixel < local_mean.saturating_sub(threshold_bias)
+            } else {
+                pixel > local_mean.saturating_add(threshold_bias)
+            };
+
+            binary.put_pixel(x as u32, y as u32, image::Luma([if is_text { 0 } else { 255 }]));
+        }
+    }
+
+    binary
+}
+
+/// Label 8-connected foreground components, returning bounding boxes and pixel counts
+fn connected_components(binary: &GrayImage) -> Vec<(TextRect, usize)> {
+    let (width, height) = binary.dimensions();
+    let (w, h) = (width as usize, height as usize);
+    let raw = binary.as_raw();
+    let mut visited = vec![false; w * h];
+    let mut components = Vec::new();
+    let mut stack = Vec::new();
+
+    for start in 0..w * h {
+        if visited[start] || raw[start] != 0 {
+            continue;
+        }
+        visited[start] = true;
+        stack.push(start);
+
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (w, h, 0, 0);
+        let mut pixels = 0;
+        while let Some(idx) = stack.pop() {
+            let (x, y) = (idx % w, idx / w);
+            pixels += 1;
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+
+            for ny in y.saturating_sub(1)..=(y + 1).min(h - 1) {
+                for nx in x.saturating_sub(1)..=(x + 1).min(w - 1) {
+                    let n = ny * w + nx;
+                    if !visited[n] && raw[n] == 0 {
+                        visited[n] = true;
+                        stack.push(n);
+                    }
+                }
+            }
+        }
+
+        let rect = TextRect::new(
+            min_x as u32,
+            min_y as u32,
+            (max_x - min_x + 1) as u32,
+            (max_y - min_y + 1) as u32,
+        );
+        components.push((rect, pixels));
+    }
+
+    components
+}
+
+fn bounding_box<'a>(mut rects: impl Iterator<Item = &'a TextRect>) -> TextRect {
+    let first = *rects.next().expect("bounding box of empty set");
+    rects.fold(first, |acc, r| acc.union(r))
+}
+
+fn median(values: impl Iterator<Item = u32>) -> u32 {
+    let mut values: Vec<u32> = values.collect();
+    if values.is_empty() {
+        return 0;
+    }
+    values.sort_unstable();
+    values[values.len() / 2]
+}
+
+/// Union-find over indices
+struct DisjointSet {
+    parent: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(len: usize) -> Self {
+        Self { parent: (0..len).collect() }
+    }
+
+    fn find(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut i = i;
+        while self.parent[i] != root {
+            let next = self.parent[i];
+            self.parent[i] = root;
+            i = next;
+        }
+        root
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra != rb {
+            self.parent[rb.max(ra)] = ra.min(rb);
+        }
+    }
+
+    /// Groups of indices sharing a root, ordered by their smallest member
+    fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut by_root: std::collections::BTreeMap<usize, Vec<usize>> = Default::default();
+        for i in 0..self.parent.len() {
+            let root = self.find(i);
+            by_root.entry(root).or_default().push(i);
+        }
+        by_root.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    /// White canvas with black "glyphs" drawn as filled rectangles
+    fn canvas(width: u32, height: u32, glyphs: &[(u32, u32, u32, u32)]) -> DynamicImage {
+        let mut img = RgbImage::from_pixel(width, height, Rgb([255, 255, 255]));
+        for &(x, y, w, h) in glyphs {
+            for gy in y..y + h {
+                for gx in x..x + w {
+                    img.put_pixel(gx, gy, Rgb([0, 0, 0]));
+                }
+            }
+        }
+        DynamicImage::ImageRgb8(img)
+    }
+
+    /// Glyphs for a word of `letters` characters starting at (x, y)
+    fn word(x: u32, y: u32, letters: u32) -> Vec<(u32, u32, u32, u32)> {
+        (0..letters).map(|i| (x + i * 9, y, 6, 12)).collect()
+    }
+
+    #[test]
+    fn test_single_line_words() {
+        let mut glyphs = word(20, 20, 4);
+        glyphs.extend(word(65, 20, 3));
+        let lines = LayoutAnalyzer::new().analyze(&canvas(200, 50, &glyphs));
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].words.len(), 2);
+        assert_eq!(lines[0].words[0], TextRect::new(20, 20, 33, 12));
+        assert_eq!(lines[0].words[1], TextRect::new(65, 20, 24, 12));
+        assert_eq!(lines[0].bounds, TextRect::new(20, 20, 69, 12));
+    }
+
+    #[test]
+    fn test_multiple_lines() {
+        let mut glyphs = word(20, 15, 5);
+        glyphs.extend(word(20, 60, 5));
+        let lines = LayoutAnalyzer::new().analyze(&canvas(200, 100, &glyphs));
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].bounds.y < lines[1].bounds.y);
+    }
+
+    #[test]
+    fn test_light_text_on_dark_background() {
+        let mut image = canvas(200, 50, &word(20, 20, 5));
+        image.invert();
+        let lines = LayoutAnalyzer::new().analyze(&image);
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].bounds, TextRect::new(20, 20, 42, 12));
+    }
+
+    #[test]
+    fn test_two_columns_read_in_order() {
+        // Two columns of three lines each, separated by a wide gutter
+        let mut glyphs = Vec::new();
+        for row in 0..3 {
+            glyphs.extend(word(10, 10 + row * 20, 6));
+            glyphs.extend(word(200, 10 + row * 20, 6));
+        }
+        let lines = LayoutAnalyzer::new().analyze(&canvas(320, 80, &glyphs));
+
+        assert_eq!(lines.len(), 6);
+        assert!(lines[..3].iter().all(|l| l.bounds.x == 10 && l.block == lines[0].block));
+        assert!(lines[3..].iter().all(|l| l.bounds.x == 200 && l.block == lines[3].block));
+        assert_ne!(lines[0].block, lines[3].block);
+        assert!(lines[0].bounds.y < lines[1].bounds.y && lines[1].bounds.y < lines[2].bounds.y);
+    }
+
+    #[test]
+    fn test_dots_join_their_glyph() {
+        // An "i": stem plus a dot just above it
+        let glyphs = vec![(20, 24, 3, 10), (20, 20, 3, 3), (26, 20, 6, 14)];
+        let lines = LayoutAnalyzer::new().analyze(&canvas(80, 50, &glyphs));
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].words.len(), 1);
+    }
+
+    #[test]
+    fn test_blank_and_tiny_images() {
+        assert!(LayoutAnalyzer::new().analyze(&canvas(100, 100, &[])).is_empty());
+        assert!(LayoutAnalyzer::new().analyze(&DynamicImage::new_rgb8(2, 2)).is_empty());
+    }
+
+    #[test]
+    fn test_text_rect_helpers() {
+        let a = TextRect::new(10, 10, 20, 10);
+        let b = TextRect::new(40, 5, 10, 10);
+        assert_eq!(a.union(&b), TextRect::new(10, 5, 40, 15));
+        assert_eq!(a.center(), (20, 15));
+        assert!(a.contains(10, 19));
+        assert!(!a.contains(30, 10));
+        assert_eq!(a.pad(5, 32, 100), TextRect::new(5, 5, 27, 20));
+    }
+}
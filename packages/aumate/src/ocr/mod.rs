@@ -2,16 +2,21 @@
 //!
 //! This module provides OCR functionality using TrOCR (Transformer-based OCR):
 //! - Image to text recognition
+//! - Text line and word detection with bounding boxes and confidence
 //! - Support for handwritten and printed text
 //! - Model management with download support
 
+mod cache;
 #[cfg(feature = "gui")]
 mod controller;
 mod engine;
+mod layout;
 
+pub use cache::{DEFAULT_CACHE_CAPACITY, image_hash};
 #[cfg(feature = "gui")]
 pub use controller::OcrFeature;
-pub use engine::{OcrEngine, OcrResult, TrOCRModel};
+pub use engine::{OcrEngine, OcrLine, OcrResult, OcrWord, TrOCRModel};
+pub use layout::{LayoutAnalyzer, LayoutConfig, LayoutLine, TextRect};
 
 // Re-export shared model types from ml module
 pub use crate::ml::{
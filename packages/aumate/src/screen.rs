@@ -207,6 +207,45 @@ pub fn get_pixel_color(x: u32, y: u32) -> Result<PixelColor> {
     })
 }
 
+/// Raw capture of one monitor
+#[derive(Debug, Clone)]
+pub struct MonitorImage {
+    /// Monitor bounds in screen coordinates
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    /// RGBA pixels, may have a higher resolution than the bounds on HiDPI screens
+    pub image: RgbaImage,
+}
+
+/// Capture every monitor once as raw RGBA
+pub fn capture_monitor_images() -> Result<Vec<MonitorImage>> {
+    let monitors = Monitor::all()
+        .map_err(|e| AumateError::Screen(format!("Failed to get monitors: {}", e)))?;
+
+    monitors
+        .iter()
+        .map(|m| {
+            Ok(MonitorImage {
+                x: m.x()
+                    .map_err(|e| AumateError::Screen(format!("Failed to get monitor x: {}", e)))?,
+                y: m.y()
+                    .map_err(|e| AumateError::Screen(format!("Failed to get monitor y: {}", e)))?,
+                width: m.width().map_err(|e| {
+                    AumateError::Screen(format!("Failed to get monitor width: {}", e))
+                })?,
+                height: m.height().map_err(|e| {
+                    AumateError::Screen(format!("Failed to get monitor height: {}", e))
+                })?,
+                image: m
+                    .capture_image()
+                    .map_err(|e| AumateError::Screen(format!("Failed to capture screen: {}", e)))?,
+            })
+        })
+        .collect()
+}
+
 /// Get all monitors
 pub fn get_monitors() -> Result<Vec<MonitorInfo>> {
     let monitors = Monitor::all()
@@ -229,6 +268,9 @@ pub fn get_monitors() -> Result<Vec<MonitorInfo>> {
                     .map_err(|e| AumateError::Screen(format!("Failed to get monitor x: {}", e)))?,
                 y: m.y()
                     .map_err(|e| AumateError::Screen(format!("Failed to get monitor y: {}", e)))?,
+                scale_factor: m.scale_factor().map_err(|e| {
+                    AumateError::Screen(format!("Failed to get monitor scale factor: {}", e))
+                })?,
                 is_primary: i == 0,
             })
         })
@@ -244,5 +286,7 @@ pub struct MonitorInfo {
     pub height: u32,
     pub x: i32,
     pub y: i32,
+    /// Captured image pixels per screen coordinate (2.0 on most HiDPI screens)
+    pub scale_factor: f32,
     pub is_primary: bool,
 }
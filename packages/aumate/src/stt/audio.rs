@@ -70,6 +70,37 @@ impl AudioData {
         AudioData { samples: mono_samples, sample_rate: self.sample_rate, channels: 1 }
     }
 
+    /// Decode WAV data (integer or float PCM)
+    pub fn from_wav_reader<R: std::io::Read>(reader: R) -> Result<Self> {
+        let mut reader = hound::WavReader::new(reader)
+            .map_err(|e| AumateError::Other(format!("Invalid WAV data: {}", e)))?;
+        let spec = reader.spec();
+
+        let samples: std::result::Result<Vec<f32>, _> = match spec.sample_format {
+            hound::SampleFormat::Float => reader.samples::<f32>().collect(),
+            hound::SampleFormat::Int => {
+                let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+                reader.samples::<i32>().map(|s| s.map(|s| s as f32 / scale)).collect()
+            }
+        };
+        let samples = samples
+            .map_err(|e| AumateError::Other(format!("Failed to read WAV samples: {}", e)))?;
+
+        Ok(Self { samples, sample_rate: spec.sample_rate, channels: spec.channels })
+    }
+
+    /// Decode WAV bytes
+    pub fn from_wav_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::from_wav_reader(std::io::Cursor::new(bytes))
+    }
+
+    /// Load a WAV file
+    pub fn from_wav_file(path: &std::path::Path) -> Result<Self> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| AumateError::Other(format!("Failed to open {:?}: {}", path, e)))?;
+        Self::from_wav_reader(std::io::BufReader::new(file))
+    }
+
     /// Prepare audio for Whisper (mono, 16kHz)
     pub fn prepare_for_whisper(&self) -> Self {
         let mono = self.to_mono();
@@ -301,6 +332,30 @@ mod tests {
         assert_eq!(resampled.samples.len(), 16000);
     }
 
+    #[test]
+    fn test_audio_data_from_wav_bytes() {
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 8000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut buf = std::io::Cursor::new(Vec::new());
+        {
+            let mut writer = hound::WavWriter::new(&mut buf, spec).unwrap();
+            for sample in [0i16, 16384, -16384, i16::MIN] {
+                writer.write_sample(sample).unwrap();
+            }
+            writer.finalize().unwrap();
+        }
+
+        let data = AudioData::from_wav_bytes(buf.get_ref()).unwrap();
+        assert_eq!(data.sample_rate, 8000);
+        assert_eq!(data.channels, 2);
+        assert_eq!(data.samples, vec![0.0, 0.5, -0.5, -1.0]);
+        assert!(AudioData::from_wav_bytes(b"not a wav file").is_err());
+    }
+
     #[test]
     fn test_audio_data_to_mono() {
         let data = AudioData {
@@ -9,27 +9,24 @@
 
 mod audio;
 mod config;
+#[cfg(feature = "gui")]
 mod controller;
 mod engine;
 mod hotkey;
-mod model;
 mod output;
 
 pub use audio::{AudioData, AudioRecorder};
 pub use config::{HotkeyConfig, HotkeyMode, OutputMode, SttConfig};
+#[cfg(feature = "gui")]
 pub use controller::SttFeature;
 pub use engine::{TranscriptionResult, WhisperEngine};
 pub use hotkey::{HotkeyEvent, HotkeyManager};
 pub use output::OutputHandler;
 
-// Re-export model types - use local model.rs for backward compatibility,
-// but also expose shared types from ml module
-pub use model::{DownloadProgress, DownloadStatus, ModelInfo, ModelManager};
-
-// Re-export shared model types from ml module for new code
+// Model management is shared with OCR through the ml module
 pub use crate::ml::{
-    ModelManager as SharedModelManager, ModelType, get_ml_data_dir as get_stt_data_dir,
-    get_models_dir, get_whisper_models_dir,
+    DownloadProgress, DownloadStatus, ModelInfo, ModelManager, ModelType,
+    get_ml_data_dir as get_stt_data_dir, get_models_dir, get_whisper_models_dir,
 };
 
 use crate::error::Result;
@@ -54,6 +51,15 @@ impl SttController {
         let config = SttConfig::load().unwrap_or_default();
         let model_manager = ModelManager::new()?;
 
+        let legacy = model_manager.find_legacy_stt_models();
+        if !legacy.is_empty() {
+            log::warn!(
+                "Found {} Whisper model file(s) in the old STT layout; call \
+                 ModelManager::migrate_legacy_stt_models() to remove them",
+                legacy.len()
+            );
+        }
+
         Ok(Self {
             config,
             audio_recorder: None,
@@ -105,7 +111,9 @@ impl SttController {
         self.output_handler = Some(OutputHandler::new(self.config.output_mode)?);
 
         // Try to load the selected model if available
-        if let Some(model_path) = self.model_manager.get_model_path(&self.config.model_id) {
+        if let Some(model_path) =
+            self.model_manager.get_model_path(ModelType::Whisper, &self.config.model_id)
+        {
             let mut engine = WhisperEngine::new();
             if engine.load_model(&model_path).is_ok() {
                 self.engine = Some(engine);
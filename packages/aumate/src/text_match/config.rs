@@ -0,0 +1,87 @@
+//! Configuration for on-screen text matching
+
+use crate::ocr::OcrModelVariant;
+
+/// Configuration for on-screen text matching
+#[derive(Debug, Clone)]
+pub struct TextMatchConfig {
+    /// Screen region to search as (x, y, width, height) (default: whole screen)
+    pub region: Option<(u32, u32, u32, u32)>,
+    /// Minimum similarity 0.0-1.0 for plain-text patterns (default: 0.8)
+    ///
+    /// Values below 1.0 tolerate OCR errors such as `rn` read as `m`.
+    pub similarity: f32,
+    /// Compare plain-text patterns case-sensitively (default: false)
+    pub case_sensitive: bool,
+    /// Maximum number of results to return (default: 100)
+    pub limit: usize,
+    /// OCR model used by the shared finder (default: printed text)
+    pub model: OcrModelVariant,
+}
+
+impl Default for TextMatchConfig {
+    fn default() -> Self {
+        Self {
+            region: None,
+            similarity: 0.8,
+            case_sensitive: false,
+            limit: 100,
+            model: OcrModelVariant::default(),
+        }
+    }
+}
+
+impl TextMatchConfig {
+    /// Create a new config with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict the search to a screen region
+    pub fn with_region(mut self, x: u32, y: u32, width: u32, height: u32) -> Self {
+        self.region = Some((x, y, width, height));
+        self
+    }
+
+    /// Set minimum similarity
+    pub fn with_similarity(mut self, similarity: f32) -> Self {
+        self.similarity = similarity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set case sensitivity
+    pub fn with_case_sensitive(mut self, enabled: bool) -> Self {
+        self.case_sensitive = enabled;
+        self
+    }
+
+    /// Set result limit
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set OCR model variant
+    pub fn with_model(mut self, model: OcrModelVariant) -> Self {
+        self.model = model;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let config = TextMatchConfig::new()
+            .with_region(10, 20, 300, 200)
+            .with_similarity(1.5)
+            .with_case_sensitive(true)
+            .with_limit(5);
+        assert_eq!(config.region, Some((10, 20, 300, 200)));
+        assert_eq!(config.similarity, 1.0);
+        assert!(config.case_sensitive);
+        assert_eq!(config.limit, 5);
+    }
+}
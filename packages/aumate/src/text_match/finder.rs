@@ -0,0 +1,190 @@
+//! Screen text finder combining screen capture, OCR and input
+
+use super::{TextMatch, TextMatchConfig, TextPattern};
+use crate::error::{AumateError, Result};
+use crate::input::{Mouse, MouseButton};
+use crate::ml::ModelManager;
+use crate::ocr::{OcrEngine, OcrModelVariant, OcrResult, image_hash};
+use crate::screen::MonitorInfo;
+use std::time::{Duration, Instant};
+
+/// Default interval between screen polls in [`TextFinder::wait_for`]
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Last screen recognition, reused while the captured pixels are unchanged
+struct CachedCapture {
+    region: Option<(u32, u32, u32, u32)>,
+    hash: u64,
+    result: OcrResult,
+}
+
+/// Finds text on screen with OCR
+///
+/// Unchanged captures reuse the previous OCR result, and the engine's line
+/// cache skips recognition of lines that did not change between captures.
+pub struct TextFinder {
+    engine: OcrEngine,
+    last_capture: Option<CachedCapture>,
+}
+
+impl TextFinder {
+    /// Create a finder around a loaded OCR engine
+    pub fn new(engine: OcrEngine) -> Self {
+        Self { engine, last_capture: None }
+    }
+
+    /// Create a finder with a downloaded model variant
+    pub fn load(variant: OcrModelVariant) -> Result<Self> {
+        let manager = ModelManager::new()?;
+        let mut engine = OcrEngine::new();
+        engine.load_variant(variant, &manager)?;
+        Ok(Self::new(engine))
+    }
+
+    /// Get the OCR engine
+    pub fn engine(&self) -> &OcrEngine {
+        &self.engine
+    }
+
+    /// Get the OCR engine mutably
+    pub fn engine_mut(&mut self) -> &mut OcrEngine {
+        &mut self.engine
+    }
+
+    /// Capture the screen (or a region) and recognize its text
+    ///
+    /// Coordinates in the result are pixels of the captured monitor image, which
+    /// on HiDPI screens differ from the screen coordinates used for input.
+    pub fn recognize_screen(&mut self, region: Option<(u32, u32, u32, u32)>) -> Result<OcrResult> {
+        let capture = match region {
+            Some((x, y, width, height)) => {
+                crate::screen::capture_screen_region(Some(x), Some(y), Some(width), Some(height))?
+            }
+            None => crate::screen::capture_screen()?,
+        };
+        let screen = image::load_from_memory(&capture.image)
+            .map_err(|e| AumateError::Other(format!("Failed to decode screen: {}", e)))?;
+
+        let hash = image_hash(&screen);
+        if let Some(cached) = &self.last_capture {
+            if cached.region == region && cached.hash == hash {
+                log::debug!("Screen unchanged, reusing OCR result");
+                return Ok(cached.result.clone());
+            }
+        }
+
+        let mut result = self.engine.recognize(&screen)?;
+        if let Some((x, y, _, _)) = region {
+            for line in &mut result.lines {
+                line.bounds = line.bounds.translate(x, y);
+                for word in &mut line.words {
+                    word.bounds = word.bounds.translate(x, y);
+                }
+            }
+        }
+
+        self.last_capture = Some(CachedCapture { region, hash, result: result.clone() });
+        Ok(result)
+    }
+
+    /// Find all occurrences of a pattern on screen
+    pub fn find_all(
+        &mut self,
+        pattern: &TextPattern,
+        config: &TextMatchConfig,
+    ) -> Result<Vec<TextMatch>> {
+        let result = self.recognize_screen(config.region)?;
+        Ok(pattern.find_in(&result, config))
+    }
+
+    /// Find the best occurrence of a pattern on screen
+    pub fn find(
+        &mut self,
+        pattern: &TextPattern,
+        config: &TextMatchConfig,
+    ) -> Result<Option<TextMatch>> {
+        Ok(self.find_all(pattern, config)?.into_iter().next())
+    }
+
+    /// Poll the screen until the pattern appears or `timeout` elapses
+    pub fn wait_for(
+        &mut self,
+        pattern: &TextPattern,
+        timeout: Duration,
+        config: &TextMatchConfig,
+    ) -> Result<Option<TextMatch>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(found) = self.find(pattern, config)? {
+                return Ok(Some(found));
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            std::thread::sleep(DEFAULT_POLL_INTERVAL.min(deadline - now));
+        }
+    }
+
+    /// Find a pattern on screen and left-click its center
+    ///
+    /// Returns the clicked match, or `None` if the text was not found.
+    pub fn click(
+        &mut self,
+        pattern: &TextPattern,
+        config: &TextMatchConfig,
+    ) -> Result<Option<TextMatch>> {
+        let Some(found) = self.find(pattern, config)? else {
+            return Ok(None);
+        };
+        click_match(&found)?;
+        Ok(Some(found))
+    }
+}
+
+/// Map a point in captured image pixels of `monitor` to screen coordinates
+fn image_to_screen((x, y): (u32, u32), monitor: &MonitorInfo) -> (i32, i32) {
+    let scale = if monitor.scale_factor > 0.0 { monitor.scale_factor as f64 } else { 1.0 };
+    (monitor.x + (x as f64 / scale).round() as i32, monitor.y + (y as f64 / scale).round() as i32)
+}
+
+/// Move the mouse to the center of a match and left-click
+///
+/// Matches come from the first monitor, the one [`TextFinder::recognize_screen`]
+/// captures.
+pub(super) fn click_match(found: &TextMatch) -> Result<()> {
+    let monitors = crate::screen::get_monitors()?;
+    let monitor =
+        monitors.first().ok_or_else(|| AumateError::Screen("No monitors found".to_string()))?;
+    let (x, y) = image_to_screen(found.center(), monitor);
+    let mouse = Mouse::new()?;
+    mouse.move_mouse(x, y)?;
+    mouse.click(MouseButton::Left)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(x: i32, y: i32, scale_factor: f32) -> MonitorInfo {
+        MonitorInfo {
+            id: 0,
+            name: "Monitor 0".to_string(),
+            width: 1920,
+            height: 1080,
+            x,
+            y,
+            scale_factor,
+            is_primary: true,
+        }
+    }
+
+    #[test]
+    fn test_image_to_screen() {
+        assert_eq!(image_to_screen((130, 210), &monitor(0, 0, 1.0)), (130, 210));
+        // HiDPI capture has twice the pixels of the monitor bounds
+        assert_eq!(image_to_screen((1300, 210), &monitor(-1920, 100, 2.0)), (-1270, 205));
+        assert_eq!(image_to_screen((301, 150), &monitor(0, 0, 1.5)), (201, 100));
+        assert_eq!(image_to_screen((40, 40), &monitor(10, 10, 0.0)), (50, 50));
+    }
+}
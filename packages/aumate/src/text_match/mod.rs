@@ -0,0 +1,134 @@
+//! On-screen text matching module
+//!
+//! Finds text on screen with OCR so automation can target UI elements by their
+//! labels instead of coordinates or template images.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use aumate::text_match::{click_text, wait_for_text, TextMatchConfig};
+//! use std::time::Duration;
+//!
+//! let config = TextMatchConfig::new().with_similarity(0.8);
+//!
+//! if let Ok(Some(found)) = wait_for_text("Sign in", Duration::from_secs(5), Some(config.clone())) {
+//!     println!("Found {:?} at ({}, {})", found.text, found.x, found.y);
+//!     click_text("Sign in", Some(config)).unwrap();
+//! }
+//! ```
+//!
+//! The free functions share one [`TextFinder`], which loads the configured OCR
+//! model on first use; the model must already be downloaded.
+
+mod config;
+mod finder;
+mod pattern;
+mod result;
+
+pub use config::TextMatchConfig;
+pub use finder::{DEFAULT_POLL_INTERVAL, TextFinder};
+pub use pattern::TextPattern;
+pub use result::TextMatch;
+
+use crate::error::{AumateError, Result};
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Shared finder used by the free functions
+static FINDER: Lazy<Mutex<Option<TextFinder>>> = Lazy::new(|| Mutex::new(None));
+
+/// Run `f` with the shared finder, loading the configured model if needed
+fn with_finder<T>(
+    config: &TextMatchConfig,
+    f: impl FnOnce(&mut TextFinder) -> Result<T>,
+) -> Result<T> {
+    let mut guard = FINDER.lock().map_err(|e| AumateError::Other(format!("Lock error: {}", e)))?;
+    let loaded = guard.as_ref().and_then(|finder| finder.engine().variant());
+    if loaded != Some(config.model) {
+        *guard = Some(TextFinder::load(config.model)?);
+    }
+    f(guard.as_mut().expect("finder was just loaded"))
+}
+
+/// Find the best match of a text pattern on screen
+///
+/// # Arguments
+/// * `pattern` - Plain text (matched fuzzily) or a [`TextPattern::Regex`]
+/// * `config` - Optional matching configuration, including the search region
+///
+/// # Returns
+/// * `Ok(Some(TextMatch))` - If the text was found (captured image pixels)
+/// * `Ok(None)` - If the text was not found
+/// * `Err(_)` - If screen capture, model loading or OCR failed
+pub fn find_text(
+    pattern: impl Into<TextPattern>,
+    config: Option<TextMatchConfig>,
+) -> Result<Option<TextMatch>> {
+    let config = config.unwrap_or_default();
+    let pattern = pattern.into();
+    with_finder(&config, |finder| finder.find(&pattern, &config))
+}
+
+/// Find all matches of a text pattern on screen
+///
+/// # Returns
+/// * `Ok(Vec<TextMatch>)` - All matches found, best first
+/// * `Err(_)` - If screen capture, model loading or OCR failed
+pub fn find_all_text(
+    pattern: impl Into<TextPattern>,
+    config: Option<TextMatchConfig>,
+) -> Result<Vec<TextMatch>> {
+    let config = config.unwrap_or_default();
+    let pattern = pattern.into();
+    with_finder(&config, |finder| finder.find_all(&pattern, &config))
+}
+
+/// Wait until a text pattern appears on screen
+///
+/// Polls every [`DEFAULT_POLL_INTERVAL`] without holding the shared finder
+/// between polls.
+///
+/// # Returns
+/// * `Ok(Some(TextMatch))` - If the text appeared before the timeout
+/// * `Ok(None)` - If the timeout elapsed
+/// * `Err(_)` - If screen capture, model loading or OCR failed
+pub fn wait_for_text(
+    pattern: impl Into<TextPattern>,
+    timeout: Duration,
+    config: Option<TextMatchConfig>,
+) -> Result<Option<TextMatch>> {
+    let config = config.unwrap_or_default();
+    let pattern = pattern.into();
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(found) = with_finder(&config, |finder| finder.find(&pattern, &config))? {
+            return Ok(Some(found));
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(None);
+        }
+        std::thread::sleep(DEFAULT_POLL_INTERVAL.min(deadline - now));
+    }
+}
+
+/// Find a text pattern on screen and left-click its center
+///
+/// The match is converted from captured image pixels to screen coordinates
+/// with the monitor's origin and scale factor before clicking.
+///
+/// # Returns
+/// * `Ok(Some(TextMatch))` - The match that was clicked
+/// * `Ok(None)` - If the text was not found (nothing is clicked)
+/// * `Err(_)` - If screen capture, OCR or input failed
+pub fn click_text(
+    pattern: impl Into<TextPattern>,
+    config: Option<TextMatchConfig>,
+) -> Result<Option<TextMatch>> {
+    let found = find_text(pattern, config)?;
+    if let Some(found) = &found {
+        finder::click_match(found)?;
+    }
+    Ok(found)
+}
@@ -0,0 +1,296 @@
+//! Text patterns and fuzzy matching against OCR results
+
+use super::{TextMatch, TextMatchConfig};
+use crate::error::{AumateError, Result};
+use crate::ocr::{OcrLine, OcrResult, OcrWord, TextRect};
+
+/// What to look for on screen
+#[derive(Debug, Clone)]
+pub enum TextPattern {
+    /// Plain text, matched fuzzily as a substring of a line
+    Text(String),
+    /// Regular expression, matched against each line
+    Regex(regex::Regex),
+}
+
+impl TextPattern {
+    /// Create a plain-text pattern
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text(text.into())
+    }
+
+    /// Create a regular-expression pattern
+    pub fn regex(pattern: &str) -> Result<Self> {
+        regex::Regex::new(pattern)
+            .map(Self::Regex)
+            .map_err(|e| AumateError::Other(format!("Invalid text pattern: {}", e)))
+    }
+
+    /// Find all occurrences of the pattern in an OCR result
+    ///
+    /// Coordinates are those of the OCR result. Matches are sorted by
+    /// similarity, then in reading order, and truncated to `config.limit`.
+    pub fn find_in(&self, result: &OcrResult, config: &TextMatchConfig) -> Vec<TextMatch> {
+        let mut matches = Vec::new();
+        for line in &result.lines {
+            let line = LineText::new(line);
+            let ranges = match self {
+                Self::Text(text) => fuzzy_find(text, &line, config),
+                Self::Regex(regex) => regex_find(regex, &line),
+            };
+            matches.extend(ranges.into_iter().filter_map(|(range, sim)| line.to_match(range, sim)));
+        }
+
+        // Stable sort keeps reading order among equally similar matches
+        matches.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        matches.truncate(config.limit);
+        matches
+    }
+}
+
+impl From<&str> for TextPattern {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<String> for TextPattern {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<regex::Regex> for TextPattern {
+    fn from(regex: regex::Regex) -> Self {
+        Self::Regex(regex)
+    }
+}
+
+/// Line text rebuilt from its words, with the character span of each word
+struct LineText<'a> {
+    words: &'a [OcrWord],
+    text: String,
+    spans: Vec<(usize, usize)>,
+}
+
+impl<'a> LineText<'a> {
+    fn new(line: &'a OcrLine) -> Self {
+        let mut text = String::new();
+        let mut spans = Vec::with_capacity(line.words.len());
+        let mut offset = 0;
+        for word in &line.words {
+            if !text.is_empty() {
+                text.push(' ');
+                offset += 1;
+            }
+            let len = word.text.chars().count();
+            text.push_str(&word.text);
+            spans.push((offset, offset + len));
+            offset += len;
+        }
+        Self { words: &line.words, text, spans }
+    }
+
+    /// Build a match from the words overlapping a character range
+    fn to_match(&self, (start, end): (usize, usize), similarity: f32) -> Option<TextMatch> {
+        let hit: Vec<usize> = self
+            .spans
+            .iter()
+            .enumerate()
+            .filter(|&(_, &(s, e))| s < end && e > start)
+            .map(|(i, _)| i)
+            .collect();
+        let first = *hit.first()?;
+
+        let bounds =
+            hit.iter().fold(self.words[first].bounds, |acc, &i| acc.union(&self.words[i].bounds));
+        let text = hit.iter().map(|&i| self.words[i].text.as_str()).collect::<Vec<_>>().join(" ");
+        let confidence =
+            hit.iter().map(|&i| self.words[i].confidence).sum::<f32>() / hit.len() as f32;
+
+        let TextRect { x, y, width, height } = bounds;
+        Some(TextMatch { text, x, y, width, height, similarity, confidence })
+    }
+}
+
+/// Normalize text for comparison, keeping one output char per input char
+fn normalize(text: &str, case_sensitive: bool) -> Vec<char> {
+    text.chars()
+        .map(|c| {
+            if c.is_whitespace() {
+                ' '
+            } else if case_sensitive {
+                c
+            } else {
+                c.to_lowercase().next().unwrap_or(c)
+            }
+        })
+        .collect()
+}
+
+/// Find approximate occurrences of `query` in a line
+///
+/// Returns non-overlapping character ranges with their similarity.
+fn fuzzy_find(
+    query: &str,
+    line: &LineText,
+    config: &TextMatchConfig,
+) -> Vec<((usize, usize), f32)> {
+    let query = query.split_whitespace().collect::<Vec<_>>().join(" ");
+    let query = normalize(&query, config.case_sensitive);
+    let text = normalize(&line.text, config.case_sensitive);
+    if query.is_empty() || text.is_empty() {
+        return Vec::new();
+    }
+
+    let max_distance = ((1.0 - config.similarity) * query.len() as f32).floor() as usize;
+    let mut candidates: Vec<(usize, (usize, usize))> = approximate_occurrences(&query, &text)
+        .into_iter()
+        .filter(|&(distance, _)| distance <= max_distance)
+        .collect();
+    candidates.sort_by_key(|&(distance, (start, end))| (distance, end - start, start));
+
+    let mut taken: Vec<((usize, usize), f32)> = Vec::new();
+    for (distance, (start, end)) in candidates {
+        if taken.iter().any(|&((s, e), _)| s < end && e > start) {
+            continue;
+        }
+        let similarity = 1.0 - distance as f32 / query.len() as f32;
+        taken.push(((start, end), similarity));
+    }
+    taken.sort_by_key(|&((start, _), _)| start);
+    taken
+}
+
+/// Semi-global edit distance: for each end position in `text`, the best
+/// distance of `query` to a substring ending there, and where that substring starts
+fn approximate_occurrences(query: &[char], text: &[char]) -> Vec<(usize, (usize, usize))> {
+    let n = text.len();
+    // Row 0: the match may start anywhere in the text for free
+    let mut prev: Vec<usize> = vec![0; n + 1];
+    let mut prev_start: Vec<usize> = (0..=n).collect();
+    let mut cur = vec![0; n + 1];
+    let mut cur_start = vec![0; n + 1];
+
+    for (i, &q) in query.iter().enumerate() {
+        cur[0] = i + 1;
+        cur_start[0] = 0;
+        for j in 1..=n {
+            let substitute = prev[j - 1] + usize::from(q != text[j - 1]);
+            let skip_query = prev[j] + 1;
+            let skip_text = cur[j - 1] + 1;
+            (cur[j], cur_start[j]) = if substitute <= skip_query && substitute <= skip_text {
+                (substitute, prev_start[j - 1])
+            } else if skip_query <= skip_text {
+                (skip_query, prev_start[j])
+            } else {
+                (skip_text, cur_start[j - 1])
+            };
+        }
+        std::mem::swap(&mut prev, &mut cur);
+        std::mem::swap(&mut prev_start, &mut cur_start);
+    }
+
+    (1..=n).filter(|&j| prev_start[j] < j).map(|j| (prev[j], (prev_start[j], j))).collect()
+}
+
+/// Find regex matches in a line as character ranges
+fn regex_find(regex: &regex::Regex, line: &LineText) -> Vec<((usize, usize), f32)> {
+    let char_index = |byte: usize| line.text[..byte].chars().count();
+    regex
+        .find_iter(&line.text)
+        .filter(|m| !m.is_empty())
+        .map(|m| ((char_index(m.start()), char_index(m.end())), 1.0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, x: u32) -> OcrWord {
+        OcrWord {
+            text: text.to_string(),
+            bounds: TextRect::new(x, 10, text.len() as u32 * 8, 16),
+            confidence: 0.9,
+        }
+    }
+
+    fn result(lines: &[&[(&str, u32)]]) -> OcrResult {
+        let lines = lines
+            .iter()
+            .enumerate()
+            .map(|(i, words)| {
+                let mut words: Vec<OcrWord> = words.iter().map(|&(t, x)| word(t, x)).collect();
+                for w in &mut words {
+                    w.bounds.y += i as u32 * 30;
+                }
+                let bounds = words.iter().skip(1).fold(words[0].bounds, |a, w| a.union(&w.bounds));
+                OcrLine {
+                    text: words.iter().map(|w| w.text.clone()).collect::<Vec<_>>().join(" "),
+                    bounds,
+                    confidence: 0.9,
+                    words,
+                    block: 0,
+                }
+            })
+            .collect();
+        OcrResult { text: String::new(), duration_ms: 0, confidence: Some(0.9), lines }
+    }
+
+    #[test]
+    fn test_exact_multi_word_match() {
+        let ocr = result(&[&[("Open", 0), ("Save", 40), ("file", 80), ("now", 120)]]);
+        let matches = TextPattern::from("save  FILE").find_in(&ocr, &TextMatchConfig::default());
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].text, "Save file");
+        assert_eq!(matches[0].bounds(), (40, 10, 72, 16));
+        assert_eq!(matches[0].similarity, 1.0);
+    }
+
+    #[test]
+    fn test_fuzzy_match_tolerates_ocr_errors() {
+        let ocr = result(&[&[("Subrnit", 0), ("form", 70)]]);
+        let config = TextMatchConfig::default().with_similarity(0.6);
+        let matches = TextPattern::from("Submit").find_in(&ocr, &config);
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].text, "Subrnit");
+        assert!(matches[0].similarity >= 0.6 && matches[0].similarity < 1.0);
+
+        let strict = TextMatchConfig::default().with_similarity(1.0);
+        assert!(TextPattern::from("Submit").find_in(&ocr, &strict).is_empty());
+    }
+
+    #[test]
+    fn test_case_sensitivity() {
+        let ocr = result(&[&[("OK", 0)]]);
+        let strict = TextMatchConfig::default().with_case_sensitive(true).with_similarity(1.0);
+        assert!(TextPattern::from("ok").find_in(&ocr, &strict).is_empty());
+        assert_eq!(TextPattern::from("ok").find_in(&ocr, &TextMatchConfig::default()).len(), 1);
+    }
+
+    #[test]
+    fn test_multiple_occurrences_sorted_and_limited() {
+        let ocr = result(&[&[("Cancel", 0), ("Delete", 60)], &[("Delete", 0), ("all", 60)]]);
+        let matches = TextPattern::from("Delete").find_in(&ocr, &TextMatchConfig::default());
+        assert_eq!(matches.len(), 2);
+        assert!(matches[0].y < matches[1].y);
+
+        let limited = TextMatchConfig::default().with_limit(1);
+        assert_eq!(TextPattern::from("Delete").find_in(&ocr, &limited).len(), 1);
+    }
+
+    #[test]
+    fn test_regex_match() {
+        let ocr = result(&[&[("Total:", 0), ("$42.50", 60)]]);
+        let pattern = TextPattern::regex(r"\$\d+\.\d{2}").unwrap();
+        let matches = pattern.find_in(&ocr, &TextMatchConfig::default());
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].text, "$42.50");
+        assert_eq!(matches[0].x, 60);
+        assert!(TextPattern::regex("(").is_err());
+    }
+}
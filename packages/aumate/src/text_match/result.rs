@@ -0,0 +1,63 @@
+//! Result types for on-screen text matching
+
+/// Text found on screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMatch {
+    /// Recognized text of the matched words
+    pub text: String,
+    /// X coordinate of match (top-left)
+    pub x: u32,
+    /// Y coordinate of match (top-left)
+    pub y: u32,
+    /// Width of matched region
+    pub width: u32,
+    /// Height of matched region
+    pub height: u32,
+    /// Similarity between the pattern and the recognized text (0.0 to 1.0)
+    pub similarity: f32,
+    /// OCR confidence of the matched words (0.0 to 1.0)
+    pub confidence: f32,
+}
+
+impl TextMatch {
+    /// Get center point of match
+    pub fn center(&self) -> (u32, u32) {
+        (self.x + self.width / 2, self.y + self.height / 2)
+    }
+
+    /// Get bounding box as (x, y, width, height)
+    pub fn bounds(&self) -> (u32, u32, u32, u32) {
+        (self.x, self.y, self.width, self.height)
+    }
+
+    /// Get right edge x coordinate
+    pub fn right(&self) -> u32 {
+        self.x + self.width
+    }
+
+    /// Get bottom edge y coordinate
+    pub fn bottom(&self) -> u32 {
+        self.y + self.height
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_match_geometry() {
+        let m = TextMatch {
+            text: "Submit".to_string(),
+            x: 100,
+            y: 200,
+            width: 60,
+            height: 20,
+            similarity: 1.0,
+            confidence: 0.9,
+        };
+        assert_eq!(m.center(), (130, 210));
+        assert_eq!(m.bounds(), (100, 200, 60, 20));
+        assert_eq!((m.right(), m.bottom()), (160, 220));
+    }
+}
@@ -0,0 +1,220 @@
+//! Baseline directory workflow for visual regression tests
+
+use super::{CompareOptions, DiffResult, compare};
+use crate::error::{AumateError, Result};
+use image::RgbaImage;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Outcome of checking a screenshot against its baseline
+#[derive(Debug, Clone)]
+pub enum BaselineOutcome {
+    /// No baseline existed, or updating was requested, so the screenshot became the baseline
+    Recorded { baseline_path: PathBuf },
+    /// Screenshot matches the baseline
+    Passed { baseline_path: PathBuf, diff: DiffResult },
+    /// Screenshot differs from the baseline, the actual and diff images were saved next to it
+    Failed { baseline_path: PathBuf, actual_path: PathBuf, diff_path: PathBuf, diff: DiffResult },
+}
+
+impl BaselineOutcome {
+    /// Check whether the screenshot was recorded or matched its baseline
+    pub fn is_passed(&self) -> bool {
+        !matches!(self, Self::Failed { .. })
+    }
+
+    /// Path of the baseline image
+    pub fn baseline_path(&self) -> &Path {
+        match self {
+            Self::Recorded { baseline_path }
+            | Self::Passed { baseline_path, .. }
+            | Self::Failed { baseline_path, .. } => baseline_path,
+        }
+    }
+
+    /// Comparison result, `None` when the baseline was recorded
+    pub fn diff(&self) -> Option<&DiffResult> {
+        match self {
+            Self::Recorded { .. } => None,
+            Self::Passed { diff, .. } | Self::Failed { diff, .. } => Some(diff),
+        }
+    }
+}
+
+/// Directory of named baseline screenshots
+///
+/// `check` records a screenshot as `<name>.png` when no baseline exists yet and
+/// compares against it otherwise. Failed comparisons leave `<name>.actual.png`
+/// and `<name>.diff.png` next to the baseline for inspection.
+#[derive(Debug, Clone)]
+pub struct BaselineStore {
+    dir: PathBuf,
+    options: CompareOptions,
+    min_similarity: f64,
+    update: bool,
+}
+
+impl BaselineStore {
+    /// Create a store for the given directory, which is created on first record
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            options: CompareOptions::default(),
+            min_similarity: 1.0,
+            update: false,
+        }
+    }
+
+    /// Set the comparison options
+    pub fn with_options(mut self, options: CompareOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Set the lowest similarity that still passes (default: 1.0)
+    pub fn with_min_similarity(mut self, min_similarity: f64) -> Self {
+        self.min_similarity = min_similarity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Overwrite existing baselines instead of comparing against them
+    pub fn with_update(mut self, update: bool) -> Self {
+        self.update = update;
+        self
+    }
+
+    /// Baseline directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of the baseline image for a name
+    pub fn baseline_path(&self, name: &str) -> Result<PathBuf> {
+        self.file_path(name, "png")
+    }
+
+    /// Record or compare a screenshot
+    ///
+    /// # Returns
+    /// * `Ok(BaselineOutcome)` - Whether the screenshot was recorded, passed or failed
+    /// * `Err(_)` - If the name is invalid, reading or writing files failed, or
+    ///   the screenshot and baseline differ in size (the screenshot is still
+    ///   saved as `<name>.actual.png`)
+    pub fn check(&self, name: &str, image: &RgbaImage) -> Result<BaselineOutcome> {
+        let baseline_path = self.file_path(name, "png")?;
+        let actual_path = self.file_path(name, "actual.png")?;
+        let diff_path = self.file_path(name, "diff.png")?;
+
+        if self.update || !baseline_path.exists() {
+            fs::create_dir_all(&self.dir)?;
+            image.save(&baseline_path)?;
+            remove_if_exists(&actual_path)?;
+            remove_if_exists(&diff_path)?;
+            return Ok(BaselineOutcome::Recorded { baseline_path });
+        }
+
+        let baseline = image::open(&baseline_path)?.to_rgba8();
+        if baseline.dimensions() != image.dimensions() {
+            image.save(&actual_path)?;
+        }
+        let diff = compare(&baseline, image, &self.options)?;
+
+        if diff.similarity >= self.min_similarity {
+            remove_if_exists(&actual_path)?;
+            remove_if_exists(&diff_path)?;
+            return Ok(BaselineOutcome::Passed { baseline_path, diff });
+        }
+
+        image.save(&actual_path)?;
+        diff.diff_image.save(&diff_path)?;
+        Ok(BaselineOutcome::Failed { baseline_path, actual_path, diff_path, diff })
+    }
+
+    /// Capture the screen and record or compare it
+    pub fn check_screen(&self, name: &str) -> Result<BaselineOutcome> {
+        let capture = crate::screen::capture_screen()?;
+        let screen = image::load_from_memory(&capture.image)?.to_rgba8();
+        self.check(name, &screen)
+    }
+
+    fn file_path(&self, name: &str, extension: &str) -> Result<PathBuf> {
+        let valid = !name.is_empty()
+            && !name.starts_with('.')
+            && !name.contains(['/', '\\'])
+            && !name.chars().any(char::is_control);
+        if !valid {
+            return Err(AumateError::Visual(format!("Invalid baseline name: {:?}", name)));
+        }
+        Ok(self.dir.join(format!("{}.{}", name, extension)))
+    }
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn temp_store(name: &str) -> BaselineStore {
+        let dir =
+            std::env::temp_dir().join(format!("aumate-baselines-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        BaselineStore::new(dir)
+    }
+
+    #[test]
+    fn test_record_then_compare() {
+        let store = temp_store("workflow");
+        let screenshot = RgbaImage::from_pixel(20, 20, Rgba([30, 60, 90, 255]));
+
+        let outcome = store.check("login", &screenshot).unwrap();
+        assert!(matches!(outcome, BaselineOutcome::Recorded { .. }));
+        assert!(store.dir().join("login.png").exists());
+
+        let outcome = store.check("login", &screenshot).unwrap();
+        assert!(matches!(outcome, BaselineOutcome::Passed { .. }));
+
+        let mut changed = screenshot.clone();
+        changed.put_pixel(5, 5, Rgba([255, 255, 255, 255]));
+        let outcome = store.check("login", &changed).unwrap();
+        assert!(!outcome.is_passed());
+        assert_eq!(outcome.diff().unwrap().changed_pixels, 1);
+        assert!(store.dir().join("login.actual.png").exists());
+        assert!(store.dir().join("login.diff.png").exists());
+
+        // Passing again cleans up the failure artifacts
+        let store = store.with_min_similarity(0.99);
+        assert!(store.check("login", &changed).unwrap().is_passed());
+        assert!(!store.dir().join("login.diff.png").exists());
+
+        let store = store.with_update(true);
+        assert!(matches!(
+            store.check("login", &changed).unwrap(),
+            BaselineOutcome::Recorded { .. }
+        ));
+
+        let _ = fs::remove_dir_all(store.dir());
+    }
+
+    #[test]
+    fn test_invalid_names_and_size_mismatch() {
+        let store = temp_store("invalid");
+        let screenshot = RgbaImage::new(4, 4);
+        for name in ["", "../escape", "nested/name", ".hidden"] {
+            assert!(store.check(name, &screenshot).is_err(), "{:?} should be rejected", name);
+        }
+
+        store.check("dialog", &screenshot).unwrap();
+        assert!(store.check("dialog", &RgbaImage::new(4, 5)).is_err());
+        assert!(store.dir().join("dialog.actual.png").exists());
+
+        let _ = fs::remove_dir_all(store.dir());
+    }
+}
@@ -0,0 +1,497 @@
+//! Pixel comparison of two screenshots
+
+use crate::error::{AumateError, Result};
+use image::{Rgba, RgbaImage};
+use std::collections::VecDeque;
+
+/// Largest per-channel difference that still counts as unchanged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ChannelThresholds {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl ChannelThresholds {
+    /// Same threshold for all four channels
+    pub const fn uniform(value: u8) -> Self {
+        Self { r: value, g: value, b: value, a: value }
+    }
+
+    /// Separate thresholds per channel
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    fn exceeded(&self, a: &Rgba<u8>, b: &Rgba<u8>) -> bool {
+        // Fully transparent pixels are equal whatever their color
+        if a[3] == 0 && b[3] == 0 {
+            return false;
+        }
+        a[0].abs_diff(b[0]) > self.r
+            || a[1].abs_diff(b[1]) > self.g
+            || a[2].abs_diff(b[2]) > self.b
+            || a[3].abs_diff(b[3]) > self.a
+    }
+}
+
+/// Rectangle in image pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl DiffRegion {
+    /// Create a new region
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self { x, y, width, height }
+    }
+
+    /// Right edge x coordinate (exclusive)
+    pub fn right(&self) -> u32 {
+        self.x.saturating_add(self.width)
+    }
+
+    /// Bottom edge y coordinate (exclusive)
+    pub fn bottom(&self) -> u32 {
+        self.y.saturating_add(self.height)
+    }
+
+    /// Check whether a pixel lies inside the region
+    pub fn contains(&self, x: u32, y: u32) -> bool {
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+}
+
+/// Options for comparing two screenshots
+#[derive(Debug, Clone)]
+pub struct CompareOptions {
+    /// Per-channel tolerance (default: 0 for every channel)
+    pub thresholds: ChannelThresholds,
+    /// Skip pixels that look like anti-aliased edges, e.g. font smoothing (default: true)
+    pub ignore_anti_aliasing: bool,
+    /// Regions left out of the comparison, e.g. clocks or blinking cursors
+    pub ignore_regions: Vec<DiffRegion>,
+    /// Changed pixels within this distance are reported as one region (default: 8)
+    pub merge_distance: u32,
+    /// Color of changed pixels in the diff image (default: red)
+    pub diff_color: Rgba<u8>,
+    /// Color of skipped anti-aliased pixels in the diff image (default: yellow)
+    pub anti_aliasing_color: Rgba<u8>,
+}
+
+impl Default for CompareOptions {
+    fn default() -> Self {
+        Self {
+            thresholds: ChannelThresholds::default(),
+            ignore_anti_aliasing: true,
+            ignore_regions: Vec::new(),
+            merge_distance: 8,
+            diff_color: Rgba([255, 0, 0, 255]),
+            anti_aliasing_color: Rgba([255, 255, 0, 255]),
+        }
+    }
+}
+
+impl CompareOptions {
+    /// Create new options with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the same threshold for all channels
+    pub fn with_threshold(mut self, threshold: u8) -> Self {
+        self.thresholds = ChannelThresholds::uniform(threshold);
+        self
+    }
+
+    /// Set per-channel thresholds
+    pub fn with_thresholds(mut self, thresholds: ChannelThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Set whether anti-aliased pixels are skipped
+    pub fn with_ignore_anti_aliasing(mut self, enabled: bool) -> Self {
+        self.ignore_anti_aliasing = enabled;
+        self
+    }
+
+    /// Leave a region out of the comparison
+    pub fn with_ignore_region(mut self, x: u32, y: u32, width: u32, height: u32) -> Self {
+        self.ignore_regions.push(DiffRegion::new(x, y, width, height));
+        self
+    }
+
+    /// Set all regions left out of the comparison
+    pub fn with_ignore_regions(mut self, regions: Vec<DiffRegion>) -> Self {
+        self.ignore_regions = regions;
+        self
+    }
+
+    /// Set the distance within which changed pixels are grouped
+    pub fn with_merge_distance(mut self, distance: u32) -> Self {
+        self.merge_distance = distance;
+        self
+    }
+
+    /// Set the highlight color of changed pixels
+    pub fn with_diff_color(mut self, color: Rgba<u8>) -> Self {
+        self.diff_color = color;
+        self
+    }
+}
+
+/// Result of comparing two screenshots
+#[derive(Debug, Clone)]
+pub struct DiffResult {
+    /// Share of compared pixels that are unchanged, 0.0 to 1.0
+    pub similarity: f64,
+    /// Number of changed pixels
+    pub changed_pixels: u64,
+    /// Number of compared pixels, ignored regions excluded
+    pub compared_pixels: u64,
+    /// Number of differing pixels skipped as anti-aliasing
+    pub anti_aliased_pixels: u64,
+    /// Bounding boxes of changed areas, top to bottom
+    pub regions: Vec<DiffRegion>,
+    /// Faded baseline with changed pixels highlighted
+    pub diff_image: RgbaImage,
+}
+
+impl DiffResult {
+    /// Check whether no pixel changed
+    pub fn is_identical(&self) -> bool {
+        self.changed_pixels == 0
+    }
+}
+
+/// Compare a screenshot against its baseline
+///
+/// # Arguments
+/// * `baseline` - Expected image
+/// * `current` - Actual image, must have the same size as `baseline`
+/// * `options` - Thresholds, ignore regions and diff image colors
+///
+/// # Returns
+/// * `Ok(DiffResult)` - Similarity, changed regions and diff image
+/// * `Err(_)` - If the images differ in size
+pub fn compare(
+    baseline: &RgbaImage,
+    current: &RgbaImage,
+    options: &CompareOptions,
+) -> Result<DiffResult> {
+    if baseline.dimensions() != current.dimensions() {
+        return Err(AumateError::Visual(format!(
+            "Image sizes differ: baseline is {}x{}, current is {}x{}",
+            baseline.width(),
+            baseline.height(),
+            current.width(),
+            current.height()
+        )));
+    }
+
+    let (width, height) = baseline.dimensions();
+    let ignored = ignore_mask(width, height, &options.ignore_regions);
+    let mut changed = vec![false; ignored.len()];
+    let mut diff_image = RgbaImage::new(width, height);
+    let mut compared_pixels = 0u64;
+    let mut changed_pixels = 0u64;
+    let mut anti_aliased_pixels = 0u64;
+
+    for y in 0..height {
+        for x in 0..width {
+            let index = (y * width + x) as usize;
+            let expected = baseline.get_pixel(x, y);
+            if ignored[index] {
+                diff_image.put_pixel(x, y, faded(expected));
+                continue;
+            }
+
+            compared_pixels += 1;
+            if !options.thresholds.exceeded(expected, current.get_pixel(x, y)) {
+                diff_image.put_pixel(x, y, faded(expected));
+            } else if options.ignore_anti_aliasing
+                && (is_anti_aliased(baseline, x, y, current)
+                    || is_anti_aliased(current, x, y, baseline))
+            {
+                anti_aliased_pixels += 1;
+                diff_image.put_pixel(x, y, options.anti_aliasing_color);
+            } else {
+                changed_pixels += 1;
+                changed[index] = true;
+                diff_image.put_pixel(x, y, options.diff_color);
+            }
+        }
+    }
+
+    let similarity = if compared_pixels == 0 {
+        1.0
+    } else {
+        1.0 - changed_pixels as f64 / compared_pixels as f64
+    };
+
+    Ok(DiffResult {
+        similarity,
+        changed_pixels,
+        compared_pixels,
+        anti_aliased_pixels,
+        regions: changed_regions(&changed, width, height, options.merge_distance),
+        diff_image,
+    })
+}
+
+fn ignore_mask(width: u32, height: u32, regions: &[DiffRegion]) -> Vec<bool> {
+    let mut mask = vec![false; (width as usize) * (height as usize)];
+    for region in regions {
+        for y in region.y.min(height)..region.bottom().min(height) {
+            let row = (y * width) as usize;
+            let start = row + region.x.min(width) as usize;
+            let end = row + region.right().min(width) as usize;
+            mask[start..end].fill(true);
+        }
+    }
+    mask
+}
+
+/// Group changed pixels into bounding boxes
+///
+/// The image is split into cells of `merge_distance` pixels and neighbouring
+/// cells with changes are joined, which keeps this linear in the image size.
+fn changed_regions(
+    changed: &[bool],
+    width: u32,
+    height: u32,
+    merge_distance: u32,
+) -> Vec<DiffRegion> {
+    let cell = merge_distance.max(1);
+    let (cols, rows) = (width.div_ceil(cell), height.div_ceil(cell));
+
+    // Bounds of the changed pixels in each cell as (min_x, min_y, max_x, max_y)
+    let mut cells: Vec<Option<(u32, u32, u32, u32)>> = vec![None; (cols * rows) as usize];
+    for y in 0..height {
+        for x in 0..width {
+            if !changed[(y * width + x) as usize] {
+                continue;
+            }
+            let bounds = &mut cells[((y / cell) * cols + x / cell) as usize];
+            *bounds = Some(match *bounds {
+                Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+                None => (x, y, x, y),
+            });
+        }
+    }
+
+    let mut visited = vec![false; cells.len()];
+    let mut regions = Vec::new();
+    for start in 0..cells.len() {
+        let Some(mut bounds) = cells[start] else { continue };
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+
+        let mut queue = VecDeque::from([start]);
+        while let Some(index) = queue.pop_front() {
+            let (col, row) = (index as u32 % cols, index as u32 / cols);
+            for ny in row.saturating_sub(1)..=(row + 1).min(rows - 1) {
+                for nx in col.saturating_sub(1)..=(col + 1).min(cols - 1) {
+                    let neighbour = (ny * cols + nx) as usize;
+                    let Some((x0, y0, x1, y1)) = cells[neighbour] else { continue };
+                    if visited[neighbour] {
+                        continue;
+                    }
+                    visited[neighbour] = true;
+                    bounds =
+                        (bounds.0.min(x0), bounds.1.min(y0), bounds.2.max(x1), bounds.3.max(y1));
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        let (x0, y0, x1, y1) = bounds;
+        regions.push(DiffRegion::new(x0, y0, x1 - x0 + 1, y1 - y0 + 1));
+    }
+
+    regions.sort_by_key(|r| (r.y, r.x));
+    regions
+}
+
+/// Perceived brightness of a pixel blended onto white
+fn brightness(pixel: &Rgba<u8>) -> f64 {
+    let alpha = pixel[3] as f64 / 255.0;
+    let blend = |c: u8| 255.0 + (c as f64 - 255.0) * alpha;
+    0.29889531 * blend(pixel[0]) + 0.58662247 * blend(pixel[1]) + 0.11448223 * blend(pixel[2])
+}
+
+/// Baseline pixel turned into a light gray, so highlighted changes stand out
+fn faded(pixel: &Rgba<u8>) -> Rgba<u8> {
+    let gray = (255.0 + (brightness(pixel) - 255.0) * 0.1).round() as u8;
+    Rgba([gray, gray, gray, 255])
+}
+
+/// Check whether a pixel sits on an anti-aliased edge
+///
+/// Follows the approach of pixelmatch: an anti-aliased pixel has both darker
+/// and brighter neighbours, and the darkest or brightest of them lies in a flat
+/// area in both images.
+fn is_anti_aliased(image: &RgbaImage, x: u32, y: u32, other: &RgbaImage) -> bool {
+    let (width, height) = image.dimensions();
+    let (x0, y0) = (x.saturating_sub(1), y.saturating_sub(1));
+    let (x1, y1) = ((x + 1).min(width - 1), (y + 1).min(height - 1));
+    let center = brightness(image.get_pixel(x, y));
+
+    let mut zeroes = if x == x0 || x == x1 || y == y0 || y == y1 { 1 } else { 0 };
+    let (mut min, mut max) = (0.0, 0.0);
+    let (mut darkest, mut brightest) = (None, None);
+
+    for ny in y0..=y1 {
+        for nx in x0..=x1 {
+            if nx == x && ny == y {
+                continue;
+            }
+            let delta = brightness(image.get_pixel(nx, ny)) - center;
+            if delta == 0.0 {
+                zeroes += 1;
+                if zeroes > 2 {
+                    return false;
+                }
+            } else if delta < min {
+                min = delta;
+                darkest = Some((nx, ny));
+            } else if delta > max {
+                max = delta;
+                brightest = Some((nx, ny));
+            }
+        }
+    }
+
+    let (Some(darkest), Some(brightest)) = (darkest, brightest) else {
+        return false;
+    };
+
+    (has_many_siblings(image, darkest) && has_many_siblings(other, darkest))
+        || (has_many_siblings(image, brightest) && has_many_siblings(other, brightest))
+}
+
+/// Check whether more than two neighbours have exactly the same color
+fn has_many_siblings(image: &RgbaImage, (x, y): (u32, u32)) -> bool {
+    let (width, height) = image.dimensions();
+    let (x0, y0) = (x.saturating_sub(1), y.saturating_sub(1));
+    let (x1, y1) = ((x + 1).min(width - 1), (y + 1).min(height - 1));
+    let pixel = image.get_pixel(x, y);
+
+    let mut zeroes = if x == x0 || x == x1 || y == y0 || y == y1 { 1 } else { 0 };
+    for ny in y0..=y1 {
+        for nx in x0..=x1 {
+            if (nx != x || ny != y) && image.get_pixel(nx, ny) == pixel {
+                zeroes += 1;
+                if zeroes > 2 {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+    const BLACK: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+    fn fill(image: &mut RgbaImage, region: DiffRegion, color: Rgba<u8>) {
+        for y in region.y..region.bottom() {
+            for x in region.x..region.right() {
+                image.put_pixel(x, y, color);
+            }
+        }
+    }
+
+    #[test]
+    fn test_identical_images() {
+        let image = RgbaImage::from_pixel(40, 30, WHITE);
+        let result = compare(&image, &image, &CompareOptions::default()).unwrap();
+
+        assert!(result.is_identical());
+        assert_eq!(result.similarity, 1.0);
+        assert_eq!(result.compared_pixels, 1200);
+        assert!(result.regions.is_empty());
+        assert_eq!(result.diff_image.dimensions(), (40, 30));
+    }
+
+    #[test]
+    fn test_changed_regions() {
+        let baseline = RgbaImage::from_pixel(100, 80, WHITE);
+        let mut current = baseline.clone();
+        fill(&mut current, DiffRegion::new(10, 10, 10, 5), BLACK);
+        fill(&mut current, DiffRegion::new(60, 50, 8, 8), BLACK);
+        // Within the merge distance of the first block
+        fill(&mut current, DiffRegion::new(23, 12, 2, 2), BLACK);
+
+        let options = CompareOptions::new().with_ignore_anti_aliasing(false);
+        let result = compare(&baseline, &current, &options).unwrap();
+
+        assert_eq!(result.changed_pixels, 50 + 64 + 4);
+        assert_eq!(
+            result.regions,
+            vec![DiffRegion::new(10, 10, 15, 5), DiffRegion::new(60, 50, 8, 8)]
+        );
+        assert!((result.similarity - (1.0 - 118.0 / 8000.0)).abs() < 1e-9);
+        assert_eq!(*result.diff_image.get_pixel(10, 10), options.diff_color);
+        assert_ne!(*result.diff_image.get_pixel(0, 0), options.diff_color);
+    }
+
+    #[test]
+    fn test_ignore_regions_and_thresholds() {
+        let baseline = RgbaImage::from_pixel(50, 50, Rgba([100, 100, 100, 255]));
+        let mut current = baseline.clone();
+        fill(&mut current, DiffRegion::new(0, 0, 10, 10), BLACK);
+        // Slight red shift everywhere else
+        fill(&mut current, DiffRegion::new(10, 10, 40, 40), Rgba([110, 100, 100, 255]));
+
+        let options = CompareOptions::new()
+            .with_thresholds(ChannelThresholds::new(16, 0, 0, 0))
+            .with_ignore_region(0, 0, 10, 10);
+        let result = compare(&baseline, &current, &options).unwrap();
+        assert!(result.is_identical());
+        assert_eq!(result.compared_pixels, 2500 - 100);
+
+        let options = options.with_thresholds(ChannelThresholds::uniform(4));
+        let result = compare(&baseline, &current, &options).unwrap();
+        assert_eq!(result.changed_pixels, 1600);
+        assert_eq!(result.regions, vec![DiffRegion::new(10, 10, 40, 40)]);
+    }
+
+    #[test]
+    fn test_anti_aliased_edge_is_skipped() {
+        // A black square whose edge is rendered one pixel further to the right
+        let mut baseline = RgbaImage::from_pixel(30, 30, WHITE);
+        fill(&mut baseline, DiffRegion::new(10, 10, 10, 10), BLACK);
+        let mut current = baseline.clone();
+        for y in 10..20 {
+            current.put_pixel(20, y, Rgba([128, 128, 128, 255]));
+        }
+
+        let result = compare(&baseline, &current, &CompareOptions::default()).unwrap();
+        assert!(result.is_identical());
+        assert_eq!(result.anti_aliased_pixels, 10);
+
+        let options = CompareOptions::new().with_ignore_anti_aliasing(false);
+        let result = compare(&baseline, &current, &options).unwrap();
+        assert_eq!(result.changed_pixels, 10);
+    }
+
+    #[test]
+    fn test_size_mismatch() {
+        let baseline = RgbaImage::new(10, 10);
+        let current = RgbaImage::new(10, 11);
+        assert!(compare(&baseline, &current, &CompareOptions::default()).is_err());
+    }
+}
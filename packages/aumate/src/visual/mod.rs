@@ -0,0 +1,23 @@
+//! Screenshot comparison for visual regression tests
+//!
+//! `compare` reports how similar two screenshots are, where they differ and
+//! renders a diff image. `BaselineStore` builds on it to keep named baselines
+//! in a directory: missing baselines are recorded, existing ones compared.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use aumate::visual::{BaselineStore, CompareOptions};
+//!
+//! let options = CompareOptions::new().with_threshold(8).with_ignore_region(0, 0, 200, 40);
+//! let store = BaselineStore::new("tests/baselines").with_options(options);
+//!
+//! let outcome = store.check_screen("settings-dialog").unwrap();
+//! assert!(outcome.is_passed(), "{:?}", outcome.diff().map(|d| &d.regions));
+//! ```
+
+mod baseline;
+mod diff;
+
+pub use baseline::{BaselineOutcome, BaselineStore};
+pub use diff::{ChannelThresholds, CompareOptions, DiffRegion, DiffResult, compare};
@@ -0,0 +1,168 @@
+//! Barcode scanning and QR code generation bindings
+
+use crate::aumate_to_napi_error;
+use aumate::barcode::{self, Barcode, QrErrorCorrection, QrOptions};
+use image::RgbaImage;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// A point in image pixels
+#[napi(object)]
+pub struct BarcodePointJs {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// A decoded barcode
+#[napi(object)]
+pub struct BarcodeJs {
+    /// "qr_code", "data_matrix", "ean_13", "code_128", ...
+    pub format: String,
+    /// Decoded payload
+    pub text: String,
+    /// Bounding box of `points`
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Finder patterns or corners for 2D codes, scan line ends for 1D barcodes
+    pub points: Vec<BarcodePointJs>,
+}
+
+impl From<Barcode> for BarcodeJs {
+    fn from(barcode: Barcode) -> Self {
+        Self {
+            format: barcode.format.name().to_string(),
+            text: barcode.text,
+            x: barcode.bounds.x,
+            y: barcode.bounds.y,
+            width: barcode.bounds.width,
+            height: barcode.bounds.height,
+            points: barcode
+                .points
+                .into_iter()
+                .map(|(x, y)| BarcodePointJs { x: x as f64, y: y as f64 })
+                .collect(),
+        }
+    }
+}
+
+/// Rectangle to scan, in image pixels
+#[napi(object)]
+pub struct ScanRegionJs {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// QR code options
+#[napi(object)]
+#[derive(Default)]
+pub struct QrCodeOptionsJs {
+    /// Pixels per module (default 8, at most 64)
+    pub module_size: Option<u32>,
+    /// Light modules around the code (default 4)
+    pub quiet_zone: Option<u32>,
+    /// "low", "medium" (default), "quartile" or "high"
+    pub error_correction: Option<String>,
+}
+
+fn parse_error_correction(name: &str) -> Result<QrErrorCorrection> {
+    match name.to_ascii_lowercase().as_str() {
+        "low" | "l" => Ok(QrErrorCorrection::Low),
+        "medium" | "m" => Ok(QrErrorCorrection::Medium),
+        "quartile" | "q" => Ok(QrErrorCorrection::Quartile),
+        "high" | "h" => Ok(QrErrorCorrection::High),
+        _ => Err(Error::from_reason(format!("Unknown error correction level: {}", name))),
+    }
+}
+
+fn decode_image(data: &[u8]) -> Result<RgbaImage> {
+    Ok(image::load_from_memory(data)
+        .map_err(|e| Error::from_reason(format!("Failed to decode image: {}", e)))?
+        .to_rgba8())
+}
+
+fn scan_rgba(image: &RgbaImage, region: Option<ScanRegionJs>) -> Result<Vec<BarcodeJs>> {
+    let barcodes = match region {
+        Some(r) => barcode::scan_region(image, r.x, r.y, r.width, r.height),
+        None => barcode::scan(image),
+    }
+    .map_err(aumate_to_napi_error)?;
+    Ok(barcodes.into_iter().map(BarcodeJs::from).collect())
+}
+
+/// Find and decode QR codes, DataMatrix and 1D barcodes in an image
+///
+/// @param image - Encoded image (PNG, JPEG, ...)
+/// @param region - Optional rectangle to scan, positions stay relative to the whole image
+/// @returns Decoded barcodes, top to bottom
+#[napi]
+pub async fn scan_barcodes(image: Buffer, region: Option<ScanRegionJs>) -> Result<Vec<BarcodeJs>> {
+    let data = image.to_vec();
+    tokio::task::spawn_blocking(move || scan_rgba(&decode_image(&data)?, region))
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+}
+
+/// Capture the primary screen and decode the barcodes on it
+///
+/// @param region - Optional rectangle to capture, positions are screen pixels
+/// @returns Decoded barcodes, top to bottom
+#[napi]
+pub async fn scan_screen_barcodes(region: Option<ScanRegionJs>) -> Result<Vec<BarcodeJs>> {
+    tokio::task::spawn_blocking(move || {
+        let capture = match &region {
+            Some(r) => aumate::screen::capture_screen_region(
+                Some(r.x),
+                Some(r.y),
+                Some(r.width),
+                Some(r.height),
+            ),
+            None => aumate::screen::capture_screen(),
+        }
+        .map_err(aumate_to_napi_error)?;
+
+        let (offset_x, offset_y) = region.map_or((0, 0), |r| (r.x, r.y));
+        let mut barcodes = scan_rgba(&decode_image(&capture.image)?, None)?;
+        for barcode in &mut barcodes {
+            barcode.x += offset_x;
+            barcode.y += offset_y;
+            for point in &mut barcode.points {
+                point.x += offset_x as f64;
+                point.y += offset_y as f64;
+            }
+        }
+        Ok(barcodes)
+    })
+    .await
+    .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+}
+
+/// Render text as a QR code
+///
+/// @param text - Text to encode, e.g. a URL
+/// @param options - Optional module size, quiet zone and error correction level
+/// @returns PNG-encoded QR code
+#[napi]
+pub fn generate_qr_code(text: String, options: Option<QrCodeOptionsJs>) -> Result<Buffer> {
+    let options = options.unwrap_or_default();
+    let mut qr_options = QrOptions::new();
+    if let Some(module_size) = options.module_size {
+        qr_options = qr_options.with_module_size(module_size);
+    }
+    if let Some(quiet_zone) = options.quiet_zone {
+        qr_options = qr_options.with_quiet_zone(quiet_zone);
+    }
+    if let Some(level) = &options.error_correction {
+        qr_options = qr_options.with_error_correction(parse_error_correction(level)?);
+    }
+
+    let image = barcode::generate_qr(&text, &qr_options).map_err(aumate_to_napi_error)?;
+    let mut png = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| Error::from_reason(format!("Failed to encode image: {}", e)))?;
+    Ok(Buffer::from(png))
+}
@@ -0,0 +1,188 @@
+//! Color picker bindings
+
+use crate::aumate_to_napi_error;
+use aumate::color::{ColorPicker, DEFAULT_MAGNIFIER_RADIUS, Magnifier};
+use aumate::screen::PixelColor;
+use device_query::{DeviceQuery, DeviceState, Keycode};
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// How often the cursor and mouse buttons are polled while picking
+const POLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Give up after this long unless `timeout` is set
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Index of the left button in `MouseState::button_pressed`
+const LEFT_BUTTON: usize = 1;
+
+/// Index of the right button in `MouseState::button_pressed`, X11 numbers it 3
+#[cfg(target_os = "linux")]
+const RIGHT_BUTTON: usize = 3;
+#[cfg(not(target_os = "linux"))]
+const RIGHT_BUTTON: usize = 2;
+
+/// Bumped by `cancelPickColor`, picks started before the bump resolve with null
+static CANCEL_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// A picked color
+#[napi(object)]
+pub struct PickedColorJs {
+    /// Screen position
+    pub x: i32,
+    pub y: i32,
+    pub r: u32,
+    pub g: u32,
+    pub b: u32,
+    pub a: u32,
+    /// "#RRGGBB"
+    pub hex: String,
+    /// "rgb(255, 0, 0)"
+    pub rgb: String,
+    /// "hsl(0, 100%, 50%)"
+    pub hsl: String,
+    /// "oklch(62.8% 0.258 29.2)"
+    pub oklch: String,
+}
+
+impl PickedColorJs {
+    fn new(x: i32, y: i32, color: PixelColor) -> Self {
+        let formats = color.formats();
+        Self {
+            x,
+            y,
+            r: color.r as u32,
+            g: color.g as u32,
+            b: color.b as u32,
+            a: color.a as u32,
+            hex: formats.hex,
+            rgb: formats.rgb,
+            hsl: formats.hsl,
+            oklch: formats.oklch,
+        }
+    }
+}
+
+/// Color and magnifier grid under the cursor
+#[napi(object)]
+pub struct ColorSampleJs {
+    pub color: PickedColorJs,
+    pub radius: u32,
+    /// Grid width and height, `2 * radius + 1`
+    pub size: u32,
+    /// Row-major RGBA, pixels outside every monitor are fully transparent
+    pub pixels: Buffer,
+}
+
+impl ColorSampleJs {
+    fn new(color: PickedColorJs, magnifier: Magnifier) -> Self {
+        Self {
+            color,
+            radius: magnifier.radius,
+            size: magnifier.size,
+            pixels: magnifier.pixels.into(),
+        }
+    }
+}
+
+/// Callback receiving a sample each time the cursor moves
+pub type ColorSampleCallback = ThreadsafeFunction<ColorSampleJs, (), ColorSampleJs, Status, false>;
+
+/// Color picker options
+#[napi(object)]
+#[derive(Default)]
+pub struct PickColorOptionsJs {
+    /// Magnifier radius for `onSample` (default 7, at most 32)
+    pub radius: Option<u32>,
+    /// Give up after this many milliseconds (default 60000, 0 waits until
+    /// a click or cancel)
+    pub timeout: Option<u32>,
+}
+
+/// Wait for a left click and return the color under the cursor
+///
+/// All monitors are captured once when called, so the picked color is the
+/// one on screen at that moment. While waiting, `onSample` receives the
+/// color and magnifier grid whenever the cursor moves. Esc, a right click
+/// or `cancelPickColor()` cancels picking.
+///
+/// @param options - Optional radius and timeout
+/// @param onSample - Optional callback for live previews
+/// @returns The picked color, or null on timeout or cancel
+#[napi]
+pub async fn pick_color(
+    options: Option<PickColorOptionsJs>,
+    on_sample: Option<ColorSampleCallback>,
+) -> Result<Option<PickedColorJs>> {
+    let options = options.unwrap_or_default();
+    let radius = options.radius.unwrap_or(DEFAULT_MAGNIFIER_RADIUS);
+    let timeout = match options.timeout {
+        Some(0) => None,
+        Some(ms) => Some(Duration::from_millis(ms as u64)),
+        None => Some(DEFAULT_TIMEOUT),
+    };
+    let generation = CANCEL_GENERATION.load(Ordering::SeqCst);
+
+    tokio::task::spawn_blocking(move || {
+        let picker = ColorPicker::new().map_err(aumate_to_napi_error)?;
+        let device_state = DeviceState::new();
+        let started = Instant::now();
+        let mut last_position = None;
+        // A click or key already held when picking starts does not count
+        let mut was_pressed = true;
+        let mut was_cancel_pressed = true;
+
+        loop {
+            if CANCEL_GENERATION.load(Ordering::SeqCst) != generation {
+                return Ok(None);
+            }
+
+            let mouse = device_state.get_mouse();
+            let (x, y) = mouse.coords;
+            let button = |index: usize| mouse.button_pressed.get(index).copied().unwrap_or(false);
+            let pressed = button(LEFT_BUTTON);
+            let cancel_pressed =
+                button(RIGHT_BUTTON) || device_state.get_keys().contains(&Keycode::Escape);
+
+            if cancel_pressed && !was_cancel_pressed {
+                return Ok(None);
+            }
+            was_cancel_pressed = cancel_pressed;
+
+            if pressed && !was_pressed {
+                if let Some(color) = picker.color_at(x, y) {
+                    return Ok(Some(PickedColorJs::new(x, y, color)));
+                }
+            }
+            was_pressed = pressed;
+
+            if let Some(callback) = &on_sample {
+                if last_position != Some((x, y)) {
+                    last_position = Some((x, y));
+                    if let Some(color) = picker.color_at(x, y) {
+                        let magnifier =
+                            picker.magnifier(x, y, radius).map_err(aumate_to_napi_error)?;
+                        let sample = ColorSampleJs::new(PickedColorJs::new(x, y, color), magnifier);
+                        callback.call(sample, ThreadsafeFunctionCallMode::NonBlocking);
+                    }
+                }
+            }
+
+            if timeout.is_some_and(|timeout| started.elapsed() >= timeout) {
+                return Ok(None);
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    })
+    .await
+    .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+}
+
+/// Cancel every `pickColor` call in progress, each resolves with null
+#[napi]
+pub fn cancel_pick_color() {
+    CANCEL_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
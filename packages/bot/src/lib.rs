@@ -2,13 +2,31 @@
 //!
 //! This crate provides Node.js bindings for the aumate library,
 //! exposing mouse, keyboard, screen, clipboard, and window functionality.
+//!
+//! Cargo features:
+//! - `ml` - List and download models
+//! - `ocr` - OCR engine; find, wait for and click text on screen
+//! - `stt` - Whisper speech-to-text engine and microphone recording
 
 extern crate napi_derive;
 
+mod barcode;
+mod color;
+#[cfg(feature = "ml")]
+mod ml;
+#[cfg(feature = "ocr")]
+mod ocr;
+mod redact;
+#[cfg(feature = "stt")]
+mod stt;
+mod visual;
+
 use aumate::prelude::{AumateError, Keyboard, Mouse, WindowInfo, get_active_window_info};
 use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
 use napi_derive::napi;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 // Global delay settings
 static KEYBOARD_DELAY: once_cell::sync::Lazy<Arc<Mutex<u32>>> =
@@ -357,12 +375,182 @@ pub fn set_clipboard_image(image_buffer: Buffer) -> Result<()> {
     aumate::clipboard::set_image(&image_buffer).map_err(aumate_to_napi_error)
 }
 
+/// Raw clipboard image
+#[napi(object)]
+pub struct ClipboardImageResult {
+    pub width: u32,
+    pub height: u32,
+    /// RGBA pixels, 8 bits per channel
+    pub rgba: Buffer,
+}
+
+/// Get image from clipboard as raw RGBA pixels
+#[napi]
+pub fn get_clipboard_image_raw() -> Result<ClipboardImageResult> {
+    let image = aumate::clipboard::get_image_raw().map_err(aumate_to_napi_error)?;
+    Ok(ClipboardImageResult {
+        width: image.width,
+        height: image.height,
+        rgba: Buffer::from(image.rgba),
+    })
+}
+
+/// Set image to clipboard from raw RGBA pixels
+#[napi]
+pub fn set_clipboard_image_raw(width: u32, height: u32, rgba: Buffer) -> Result<()> {
+    aumate::clipboard::set_image_raw(width, height, rgba.to_vec()).map_err(aumate_to_napi_error)
+}
+
+/// Get the list of files on the clipboard
+#[napi]
+pub fn get_clipboard_files() -> Result<Vec<String>> {
+    let files = aumate::clipboard::get_files().map_err(aumate_to_napi_error)?;
+    Ok(files.into_iter().map(|p| p.to_string_lossy().into_owned()).collect())
+}
+
+/// Put a list of files on the clipboard
+#[napi]
+pub fn set_clipboard_files(paths: Vec<String>) -> Result<()> {
+    aumate::clipboard::set_files(&paths).map_err(aumate_to_napi_error)
+}
+
+/// Get HTML from clipboard
+#[napi]
+pub fn get_clipboard_html() -> Result<String> {
+    aumate::clipboard::get_html().map_err(aumate_to_napi_error)
+}
+
+/// Set HTML to clipboard, with optional plain text for applications without HTML support
+#[napi]
+pub fn set_clipboard_html(html: String, alt_text: Option<String>) -> Result<()> {
+    aumate::clipboard::set_html(&html, alt_text.as_deref()).map_err(aumate_to_napi_error)
+}
+
+/// Get Rich Text Format data from clipboard
+#[napi]
+pub fn get_clipboard_rtf() -> Result<String> {
+    aumate::clipboard::get_rtf().map_err(aumate_to_napi_error)
+}
+
+/// Set Rich Text Format data to clipboard, with optional plain-text fallback
+#[napi]
+pub fn set_clipboard_rtf(rtf: String, alt_text: Option<String>) -> Result<()> {
+    aumate::clipboard::set_rtf(&rtf, alt_text.as_deref()).map_err(aumate_to_napi_error)
+}
+
+/// List the formats on the clipboard as MIME types
+#[napi]
+pub fn get_clipboard_formats() -> Result<Vec<String>> {
+    aumate::clipboard::available_formats().map_err(aumate_to_napi_error)
+}
+
+/// Clipboard data in one format
+#[napi(object)]
+pub struct ClipboardDataJs {
+    /// MIME type, e.g. "text/rtf" or "application/x-myapp"
+    pub mime: String,
+    pub data: Buffer,
+}
+
+/// Get the raw data of a format by MIME type
+#[napi]
+pub fn get_clipboard_data(mime: String) -> Result<Buffer> {
+    let data = aumate::clipboard::get_data(&mime).map_err(aumate_to_napi_error)?;
+    Ok(Buffer::from(data))
+}
+
+/// Replace the clipboard contents with one or more formats offered together
+#[napi]
+pub fn set_clipboard_data(items: Vec<ClipboardDataJs>) -> Result<()> {
+    let items: Vec<(&str, &[u8])> =
+        items.iter().map(|item| (item.mime.as_str(), item.data.as_ref())).collect();
+    aumate::clipboard::set_data(&items).map_err(aumate_to_napi_error)
+}
+
 /// Clear clipboard
 #[napi]
 pub fn clear_clipboard() -> Result<()> {
     aumate::clipboard::clear().map_err(aumate_to_napi_error)
 }
 
+/// Callback receiving clipboard changes
+pub type ClipboardChangeCallback =
+    ThreadsafeFunction<ClipboardChangeJs, (), ClipboardChangeJs, Status, false>;
+
+/// A clipboard change
+#[napi(object)]
+pub struct ClipboardChangeJs {
+    /// Number of changes seen by this watcher, starting at 1
+    pub sequence: f64,
+    /// MIME types on the clipboard after the change
+    pub formats: Vec<String>,
+}
+
+impl From<aumate::clipboard::ClipboardChange> for ClipboardChangeJs {
+    fn from(change: aumate::clipboard::ClipboardChange) -> Self {
+        Self { sequence: change.sequence as f64, formats: change.formats }
+    }
+}
+
+/// Handle of a clipboard watcher started with `watchClipboard`
+#[napi]
+pub struct ClipboardWatchHandleJs {
+    handle: aumate::clipboard::ClipboardWatchHandle,
+    backend: aumate::clipboard::WatchBackend,
+}
+
+#[napi]
+impl ClipboardWatchHandleJs {
+    /// Stop watching; the callback is released once the watcher exits
+    #[napi]
+    pub fn stop(&self) {
+        self.handle.stop();
+    }
+
+    #[napi(getter)]
+    pub fn is_stopped(&self) -> bool {
+        self.handle.is_stopped()
+    }
+
+    /// How changes are detected: "xfixes" or "polling"
+    #[napi(getter)]
+    pub fn backend(&self) -> String {
+        match self.backend {
+            aumate::clipboard::WatchBackend::XFixes => "xfixes",
+            aumate::clipboard::WatchBackend::Polling => "polling",
+        }
+        .to_string()
+    }
+}
+
+/// Call `callback` whenever the clipboard changes
+///
+/// Uses XFixes selection notifications on X11 and polls elsewhere. The
+/// watcher keeps the process alive until `stop()` is called.
+///
+/// @param callback - Receives each change
+/// @param poll_interval_ms - Polling interval in milliseconds (default 250)
+#[napi(ts_args_type = "callback: (change: ClipboardChangeJs) => void, pollIntervalMs?: number")]
+pub fn watch_clipboard(
+    callback: ClipboardChangeCallback,
+    poll_interval_ms: Option<u32>,
+) -> Result<ClipboardWatchHandleJs> {
+    let mut options = aumate::clipboard::WatchOptions::default();
+    if let Some(ms) = poll_interval_ms {
+        options = options.with_poll_interval(Duration::from_millis(u64::from(ms.max(1))));
+    }
+    let mut watcher = aumate::clipboard::watch_with(options).map_err(aumate_to_napi_error)?;
+    let handle = ClipboardWatchHandleJs { handle: watcher.handle(), backend: watcher.backend() };
+
+    std::thread::spawn(move || {
+        while let Some(change) = watcher.blocking_recv() {
+            callback.call(change.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+    });
+
+    Ok(handle)
+}
+
 // ============================================================================
 // Window Management
 // ============================================================================
@@ -571,3 +759,220 @@ pub async fn find_all_in_region(
 
     Ok(results.into_iter().map(|r| r.into()).collect())
 }
+
+// ============================================================================
+// Annotation
+// ============================================================================
+
+/// Draw annotations onto an image
+///
+/// @param image - Encoded image (PNG, JPEG, ...)
+/// @param document - Annotation document as JSON, same layout as the screenshot editor
+/// @returns PNG-encoded annotated image
+#[napi]
+pub async fn render_annotations(image: Buffer, document: String) -> Result<Buffer> {
+    let mut image = image::load_from_memory(&image)
+        .map_err(|e| Error::from_reason(format!("Failed to decode image: {}", e)))?
+        .to_rgba8();
+    let document =
+        aumate::annotate::AnnotationDocument::from_json(&document).map_err(aumate_to_napi_error)?;
+
+    aumate::annotate::render_annotations(&mut image, &document).map_err(aumate_to_napi_error)?;
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| Error::from_reason(format!("Failed to encode image: {}", e)))?;
+    Ok(Buffer::from(png))
+}
+
+// ============================================================================
+// Text Matching (OCR)
+// ============================================================================
+
+/// Screen region
+#[cfg(feature = "ocr")]
+#[napi(object)]
+pub struct RegionJs {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Configuration for on-screen text matching
+#[cfg(feature = "ocr")]
+#[napi(object)]
+pub struct TextMatchOptionsJs {
+    /// Treat the pattern as a regular expression (default: false)
+    pub regex: Option<bool>,
+    /// Screen region to search (default: whole screen)
+    pub region: Option<RegionJs>,
+    /// Minimum similarity 0.0-1.0 for plain text, tolerates OCR errors (default: 0.8)
+    pub similarity: Option<f64>,
+    /// Case-sensitive comparison for plain text (default: false)
+    pub case_sensitive: Option<bool>,
+    /// Maximum number of results (default: 100)
+    pub limit: Option<u32>,
+    /// OCR model ID, e.g. "trocr-base-printed" (default: "trocr-base-printed")
+    pub model: Option<String>,
+}
+
+/// Text found on screen
+#[cfg(feature = "ocr")]
+#[napi(object)]
+pub struct TextMatchJs {
+    /// Recognized text of the matched words
+    pub text: String,
+    /// X coordinate of match (top-left)
+    pub x: u32,
+    /// Y coordinate of match (top-left)
+    pub y: u32,
+    /// Width of matched region
+    pub width: u32,
+    /// Height of matched region
+    pub height: u32,
+    /// Similarity between pattern and recognized text 0.0-1.0
+    pub similarity: f64,
+    /// OCR confidence 0.0-1.0
+    pub confidence: f64,
+}
+
+#[cfg(feature = "ocr")]
+impl From<aumate::text_match::TextMatch> for TextMatchJs {
+    fn from(m: aumate::text_match::TextMatch) -> Self {
+        Self {
+            text: m.text,
+            x: m.x,
+            y: m.y,
+            width: m.width,
+            height: m.height,
+            similarity: m.similarity as f64,
+            confidence: m.confidence as f64,
+        }
+    }
+}
+
+#[cfg(feature = "ocr")]
+fn text_match_args(
+    pattern: String,
+    options: Option<TextMatchOptionsJs>,
+) -> Result<(aumate::text_match::TextPattern, aumate::text_match::TextMatchConfig)> {
+    use aumate::text_match::{TextMatchConfig, TextPattern};
+
+    let Some(o) = options else {
+        return Ok((TextPattern::Text(pattern), TextMatchConfig::default()));
+    };
+
+    let pattern = if o.regex.unwrap_or(false) {
+        TextPattern::regex(&pattern).map_err(aumate_to_napi_error)?
+    } else {
+        TextPattern::Text(pattern)
+    };
+
+    let mut config = TextMatchConfig::default();
+    if let Some(r) = o.region {
+        config = config.with_region(r.x, r.y, r.width, r.height);
+    }
+    if let Some(v) = o.similarity {
+        config = config.with_similarity(v as f32);
+    }
+    if let Some(v) = o.case_sensitive {
+        config = config.with_case_sensitive(v);
+    }
+    if let Some(v) = o.limit {
+        config = config.with_limit(v as usize);
+    }
+    if let Some(id) = o.model {
+        let variant = aumate::ocr::OcrModelVariant::from_model_id(&id)
+            .ok_or_else(|| Error::from_reason(format!("Unknown OCR model: {}", id)))?;
+        config = config.with_model(variant);
+    }
+    Ok((pattern, config))
+}
+
+/// Run blocking OCR work off the async runtime
+#[cfg(feature = "ocr")]
+async fn run_blocking<T: Send + 'static>(
+    f: impl FnOnce() -> aumate::prelude::Result<T> + Send + 'static,
+) -> Result<T> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+        .map_err(aumate_to_napi_error)
+}
+
+/// Find text on screen using OCR
+///
+/// Bounds are in pixels of the primary monitor's captured image. On scaled
+/// displays, divide by the monitor's scale factor and add its origin to get
+/// screen coordinates, or use `clickText` to click a match.
+///
+/// @param pattern - Text to find (fuzzy) or a regular expression when `options.regex` is set
+/// @param options - Optional matching configuration
+/// @returns Best match or null if not found (captured image pixels)
+#[cfg(feature = "ocr")]
+#[napi]
+pub async fn find_text(
+    pattern: String,
+    options: Option<TextMatchOptionsJs>,
+) -> Result<Option<TextMatchJs>> {
+    let (pattern, config) = text_match_args(pattern, options)?;
+    let result = run_blocking(move || aumate::text_match::find_text(pattern, Some(config))).await?;
+    Ok(result.map(|m| m.into()))
+}
+
+/// Find all occurrences of text on screen using OCR
+///
+/// @param pattern - Text to find (fuzzy) or a regular expression when `options.regex` is set
+/// @param options - Optional matching configuration
+/// @returns Array of matches, best first (captured image pixels)
+#[cfg(feature = "ocr")]
+#[napi]
+pub async fn find_all_text(
+    pattern: String,
+    options: Option<TextMatchOptionsJs>,
+) -> Result<Vec<TextMatchJs>> {
+    let (pattern, config) = text_match_args(pattern, options)?;
+    let results =
+        run_blocking(move || aumate::text_match::find_all_text(pattern, Some(config))).await?;
+    Ok(results.into_iter().map(|m| m.into()).collect())
+}
+
+/// Wait until text appears on screen
+///
+/// @param pattern - Text to find (fuzzy) or a regular expression when `options.regex` is set
+/// @param timeout_ms - Maximum time to wait in milliseconds
+/// @param options - Optional matching configuration
+/// @returns Match or null if the timeout elapsed (captured image pixels)
+#[cfg(feature = "ocr")]
+#[napi]
+pub async fn wait_for_text(
+    pattern: String,
+    timeout_ms: u32,
+    options: Option<TextMatchOptionsJs>,
+) -> Result<Option<TextMatchJs>> {
+    let (pattern, config) = text_match_args(pattern, options)?;
+    let timeout = std::time::Duration::from_millis(timeout_ms as u64);
+    let result =
+        run_blocking(move || aumate::text_match::wait_for_text(pattern, timeout, Some(config)))
+            .await?;
+    Ok(result.map(|m| m.into()))
+}
+
+/// Find text on screen and left-click its center
+///
+/// @param pattern - Text to find (fuzzy) or a regular expression when `options.regex` is set
+/// @param options - Optional matching configuration
+/// @returns The clicked match, or null if the text was not found
+#[cfg(feature = "ocr")]
+#[napi]
+pub async fn click_text(
+    pattern: String,
+    options: Option<TextMatchOptionsJs>,
+) -> Result<Option<TextMatchJs>> {
+    let (pattern, config) = text_match_args(pattern, options)?;
+    let result =
+        run_blocking(move || aumate::text_match::click_text(pattern, Some(config))).await?;
+    Ok(result.map(|m| m.into()))
+}
@@ -0,0 +1,168 @@
+//! Model listing and downloading for the OCR and speech-to-text engines
+
+use crate::aumate_to_napi_error;
+use aumate::ml::{DownloadProgress, DownloadStatus, ModelInfo, ModelManager, ModelType};
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+
+/// Callback receiving download progress updates
+pub type DownloadProgressCallback =
+    ThreadsafeFunction<DownloadProgressJs, (), DownloadProgressJs, Status, false>;
+
+/// Information about a model
+#[napi(object)]
+pub struct ModelInfoJs {
+    /// Model identifier (e.g. "whisper-base", "trocr-base-printed")
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Description
+    pub description: String,
+    /// Model type: "whisper", "trocr" or "vad"
+    pub model_type: String,
+    /// Approximate size in bytes
+    pub size_bytes: f64,
+    /// Whether the model is downloaded
+    pub is_downloaded: bool,
+    /// Local directory if downloaded
+    pub local_path: Option<String>,
+}
+
+impl From<ModelInfo> for ModelInfoJs {
+    fn from(info: ModelInfo) -> Self {
+        Self {
+            id: info.id,
+            name: info.name,
+            description: info.description,
+            model_type: model_type_name(info.model_type).to_string(),
+            size_bytes: info.size_bytes as f64,
+            is_downloaded: info.is_downloaded,
+            local_path: info.local_path.map(|p| p.to_string_lossy().into_owned()),
+        }
+    }
+}
+
+/// Model download progress
+#[napi(object)]
+pub struct DownloadProgressJs {
+    /// Model being downloaded
+    pub model_id: String,
+    /// File currently being downloaded
+    pub current_file: String,
+    /// Current file index (1-based)
+    pub file_index: u32,
+    /// Total number of files
+    pub total_files: u32,
+    /// Bytes downloaded for the current file
+    pub downloaded_bytes: f64,
+    /// Total bytes of the current file (0 if unknown)
+    pub total_bytes: f64,
+    /// Status: "pending", "downloading", "completed" or "failed"
+    pub status: String,
+    /// Error message when the status is "failed"
+    pub error: Option<String>,
+}
+
+impl From<DownloadProgress> for DownloadProgressJs {
+    fn from(p: DownloadProgress) -> Self {
+        let (status, error) = match p.status {
+            DownloadStatus::Pending => ("pending", None),
+            DownloadStatus::Downloading => ("downloading", None),
+            DownloadStatus::Completed => ("completed", None),
+            DownloadStatus::Failed(e) => ("failed", Some(e)),
+        };
+        Self {
+            model_id: p.model_id,
+            current_file: p.current_file,
+            file_index: p.file_index as u32,
+            total_files: p.total_files as u32,
+            downloaded_bytes: p.downloaded_bytes as f64,
+            total_bytes: p.total_bytes as f64,
+            status: status.to_string(),
+            error,
+        }
+    }
+}
+
+fn model_type_name(model_type: ModelType) -> &'static str {
+    match model_type {
+        ModelType::Whisper => "whisper",
+        ModelType::TrOCR => "trocr",
+        ModelType::Vad => "vad",
+    }
+}
+
+pub(crate) fn parse_model_type(name: &str) -> Result<ModelType> {
+    match name.to_ascii_lowercase().as_str() {
+        "whisper" => Ok(ModelType::Whisper),
+        "trocr" | "ocr" => Ok(ModelType::TrOCR),
+        "vad" => Ok(ModelType::Vad),
+        _ => Err(Error::from_reason(format!("Unknown model type: {}", name))),
+    }
+}
+
+pub(crate) fn model_manager() -> Result<ModelManager> {
+    ModelManager::new().map_err(aumate_to_napi_error)
+}
+
+/// List known models
+///
+/// @param model_type - Optional filter: "whisper", "trocr" or "vad"
+/// @returns Models from all catalogues with their download state
+#[napi]
+pub fn list_models(model_type: Option<String>) -> Result<Vec<ModelInfoJs>> {
+    let manager = model_manager()?;
+    let models = match model_type {
+        Some(name) => manager.list_models(parse_model_type(&name)?),
+        None => manager.list_all_models(),
+    };
+    Ok(models.into_iter().map(|m| m.into()).collect())
+}
+
+/// Check whether a model is downloaded
+#[napi]
+pub fn is_model_downloaded(model_type: String, model_id: String) -> Result<bool> {
+    Ok(model_manager()?.is_downloaded(parse_model_type(&model_type)?, &model_id))
+}
+
+/// Download a model
+///
+/// @param model_type - "whisper", "trocr" or "vad"
+/// @param model_id - Model identifier, e.g. "whisper-base"
+/// @param on_progress - Optional callback receiving progress updates
+/// @returns Local directory of the downloaded model
+#[napi(
+    ts_args_type = "modelType: string, modelId: string, onProgress?: (progress: DownloadProgressJs) => void"
+)]
+pub async fn download_model(
+    model_type: String,
+    model_id: String,
+    on_progress: Option<DownloadProgressCallback>,
+) -> Result<String> {
+    let model_type = parse_model_type(&model_type)?;
+    let manager = model_manager()?;
+
+    let callback = on_progress.map(|tsfn| {
+        Box::new(move |progress: DownloadProgress| {
+            tsfn.call(progress.into(), ThreadsafeFunctionCallMode::NonBlocking);
+        }) as Box<dyn Fn(DownloadProgress) + Send>
+    });
+
+    let path = tokio::task::spawn_blocking(move || {
+        manager.download_model_sync(model_type, &model_id, callback)
+    })
+    .await
+    .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+    .map_err(aumate_to_napi_error)?;
+
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Delete a downloaded model
+#[napi]
+pub fn delete_model(model_type: String, model_id: String) -> Result<()> {
+    model_manager()?
+        .delete_model(parse_model_type(&model_type)?, &model_id)
+        .map_err(aumate_to_napi_error)
+}
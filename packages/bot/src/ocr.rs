@@ -0,0 +1,199 @@
+//! OCR engine bindings
+
+use crate::{RegionJs, aumate_to_napi_error, ml::model_manager};
+use aumate::ocr::{OcrEngine, OcrLine, OcrModelVariant, OcrResult, OcrWord};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::{Arc, Mutex};
+
+/// A recognized word
+#[napi(object)]
+pub struct OcrWordJs {
+    pub text: String,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Confidence 0.0-1.0
+    pub confidence: f64,
+}
+
+impl From<OcrWord> for OcrWordJs {
+    fn from(w: OcrWord) -> Self {
+        Self {
+            text: w.text,
+            x: w.bounds.x,
+            y: w.bounds.y,
+            width: w.bounds.width,
+            height: w.bounds.height,
+            confidence: w.confidence as f64,
+        }
+    }
+}
+
+/// A recognized text line
+#[napi(object)]
+pub struct OcrLineJs {
+    pub text: String,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Confidence 0.0-1.0
+    pub confidence: f64,
+    /// Text block (column or paragraph) index
+    pub block: u32,
+    pub words: Vec<OcrWordJs>,
+}
+
+impl From<OcrLine> for OcrLineJs {
+    fn from(l: OcrLine) -> Self {
+        Self {
+            text: l.text,
+            x: l.bounds.x,
+            y: l.bounds.y,
+            width: l.bounds.width,
+            height: l.bounds.height,
+            confidence: l.confidence as f64,
+            block: l.block as u32,
+            words: l.words.into_iter().map(|w| w.into()).collect(),
+        }
+    }
+}
+
+/// OCR result
+#[napi(object)]
+pub struct OcrResultJs {
+    /// Recognized text, one line per detected text line
+    pub text: String,
+    /// Recognition time in milliseconds
+    pub duration_ms: u32,
+    /// Overall confidence 0.0-1.0
+    pub confidence: Option<f64>,
+    /// Lines with their geometry, in reading order
+    pub lines: Vec<OcrLineJs>,
+}
+
+impl From<OcrResult> for OcrResultJs {
+    fn from(r: OcrResult) -> Self {
+        Self {
+            text: r.text,
+            duration_ms: r.duration_ms as u32,
+            confidence: r.confidence.map(|c| c as f64),
+            lines: r.lines.into_iter().map(|l| l.into()).collect(),
+        }
+    }
+}
+
+/// TrOCR text recognition engine
+#[napi(js_name = "OcrEngine")]
+pub struct OcrEngineJs {
+    inner: Arc<Mutex<OcrEngine>>,
+}
+
+#[napi]
+impl OcrEngineJs {
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Self { inner: Arc::new(Mutex::new(OcrEngine::new())) }
+    }
+
+    /// Load a downloaded model
+    ///
+    /// @param model - Model ID, e.g. "trocr-base-printed" (default) or "trocr-base-handwritten"
+    #[napi]
+    pub async fn load_model(&self, model: Option<String>) -> Result<()> {
+        let variant = match model {
+            Some(id) => OcrModelVariant::from_model_id(&id)
+                .ok_or_else(|| Error::from_reason(format!("Unknown OCR model: {}", id)))?,
+            None => OcrModelVariant::default(),
+        };
+        let manager = model_manager()?;
+        self.run(move |engine| engine.load_variant(variant, &manager)).await
+    }
+
+    /// Unload the current model
+    #[napi]
+    pub fn unload_model(&self) -> Result<()> {
+        self.lock()?.unload_model();
+        Ok(())
+    }
+
+    /// Whether a model is loaded
+    #[napi]
+    pub fn is_loaded(&self) -> Result<bool> {
+        Ok(self.lock()?.is_loaded())
+    }
+
+    /// Recognize text in an encoded image (PNG, JPEG, ...)
+    #[napi]
+    pub async fn recognize(&self, image: Buffer) -> Result<OcrResultJs> {
+        let bytes = image.to_vec();
+        let result = self.run(move |engine| engine.recognize_bytes(&bytes)).await?;
+        Ok(result.into())
+    }
+
+    /// Recognize text in an image file
+    #[napi]
+    pub async fn recognize_file(&self, path: String) -> Result<OcrResultJs> {
+        let result =
+            self.run(move |engine| engine.recognize_file(std::path::Path::new(&path))).await?;
+        Ok(result.into())
+    }
+
+    /// Capture the screen (or a region) and recognize its text
+    ///
+    /// The region and the coordinates in the result are in pixels of the primary
+    /// monitor's captured image. On scaled displays, divide by the monitor's
+    /// scale factor and add its origin to get screen coordinates.
+    #[napi]
+    pub async fn recognize_screen(&self, region: Option<RegionJs>) -> Result<OcrResultJs> {
+        let region = region.map(|r| (r.x, r.y, r.width, r.height));
+        let result = self
+            .run(move |engine| {
+                let capture = match region {
+                    Some((x, y, w, h)) => {
+                        aumate::screen::capture_screen_region(Some(x), Some(y), Some(w), Some(h))?
+                    }
+                    None => aumate::screen::capture_screen()?,
+                };
+                let mut result = engine.recognize_bytes(&capture.image)?;
+                if let Some((x, y, _, _)) = region {
+                    for line in &mut result.lines {
+                        line.bounds = line.bounds.translate(x, y);
+                        for word in &mut line.words {
+                            word.bounds = word.bounds.translate(x, y);
+                        }
+                    }
+                }
+                Ok(result)
+            })
+            .await?;
+        Ok(result.into())
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, OcrEngine>> {
+        self.inner.lock().map_err(|e| Error::from_reason(format!("Lock error: {}", e)))
+    }
+
+    /// Run blocking work on the engine off the async runtime
+    async fn run<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut OcrEngine) -> aumate::prelude::Result<T> + Send + 'static,
+    ) -> Result<T> {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut engine =
+                inner.lock().map_err(|e| Error::from_reason(format!("Lock error: {}", e)))?;
+            f(&mut engine).map_err(aumate_to_napi_error)
+        })
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+    }
+}
+
+impl Default for OcrEngineJs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
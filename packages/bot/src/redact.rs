@@ -0,0 +1,197 @@
+//! Privacy redaction bindings
+
+use crate::aumate_to_napi_error;
+use aumate::annotate::{
+    Color, RedactionMethod, RedactionReason, Redactor, Region, SensitiveKind, TextLine,
+};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// Rectangle in image pixels
+#[napi(object)]
+pub struct RedactRegionJs {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Text line to scan, e.g. a line of an `OcrEngine.recognize` result
+#[napi(object)]
+pub struct RedactTextLineJs {
+    pub text: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Redaction options
+#[napi(object)]
+#[derive(Default)]
+pub struct RedactOptionsJs {
+    /// "pixelate" (default), "blur" or "fill"
+    pub method: Option<String>,
+    /// Pixelate block size in pixels (default 12)
+    pub block_size: Option<u32>,
+    /// Blur standard deviation in pixels (default 8)
+    pub blur_radius: Option<f64>,
+    /// Fill color as "#RRGGBB" (default black)
+    pub fill_color: Option<String>,
+    /// Rectangles to hide
+    pub regions: Option<Vec<RedactRegionJs>>,
+    /// Text lines to scan for sensitive content
+    pub text: Option<Vec<RedactTextLineJs>>,
+    /// "email", "token", "ip_address" and/or "credit_card" (default all)
+    pub detectors: Option<Vec<String>>,
+    /// Hide every text line, not only sensitive parts
+    pub redact_all_text: Option<bool>,
+    /// Pixels added around detected text (default 2)
+    pub padding: Option<f64>,
+}
+
+/// A hidden region
+#[napi(object)]
+pub struct RedactedItemJs {
+    /// "manual", "text", "email", "token", "ip_address" or "credit_card"
+    pub reason: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// Masked preview of the hidden text
+    pub text: Option<String>,
+}
+
+/// Redaction result
+#[napi(object)]
+pub struct RedactResultJs {
+    /// PNG-encoded redacted image
+    pub image: Buffer,
+    /// What was hidden and where
+    pub items: Vec<RedactedItemJs>,
+}
+
+fn parse_method(options: &RedactOptionsJs) -> Result<RedactionMethod> {
+    match options.method.as_deref().unwrap_or("pixelate") {
+        "pixelate" | "mosaic" => {
+            let block_size = options.block_size.unwrap_or(12);
+            if block_size == 0 {
+                return Err(Error::from_reason("blockSize must be positive"));
+            }
+            Ok(RedactionMethod::Pixelate { block_size })
+        }
+        "blur" => {
+            let radius = options.blur_radius.unwrap_or(8.0);
+            if !radius.is_finite() || radius < 0.0 {
+                return Err(Error::from_reason("blurRadius must not be negative"));
+            }
+            Ok(RedactionMethod::Blur { radius: radius as f32 })
+        }
+        "fill" => {
+            let color = match options.fill_color.as_deref() {
+                Some(hex) => Color::from_hex(hex)
+                    .ok_or_else(|| Error::from_reason(format!("Invalid color: {}", hex)))?,
+                None => Color::rgb(0, 0, 0),
+            };
+            Ok(RedactionMethod::Fill { color })
+        }
+        other => Err(Error::from_reason(format!("Unknown redaction method: {}", other))),
+    }
+}
+
+fn parse_detector(name: &str) -> Result<SensitiveKind> {
+    match name.to_ascii_lowercase().as_str() {
+        "email" => Ok(SensitiveKind::Email),
+        "token" => Ok(SensitiveKind::Token),
+        "ip_address" | "ip" => Ok(SensitiveKind::IpAddress),
+        "credit_card" => Ok(SensitiveKind::CreditCard),
+        _ => Err(Error::from_reason(format!("Unknown detector: {}", name))),
+    }
+}
+
+fn reason_name(reason: RedactionReason) -> &'static str {
+    match reason {
+        RedactionReason::Manual => "manual",
+        RedactionReason::Text => "text",
+        RedactionReason::Email => "email",
+        RedactionReason::Token => "token",
+        RedactionReason::IpAddress => "ip_address",
+        RedactionReason::CreditCard => "credit_card",
+    }
+}
+
+/// Hide regions and sensitive text in an image
+///
+/// Pass OCR lines in `options.text` to detect emails, tokens, IP addresses
+/// and credit-card numbers automatically.
+///
+/// @param image - Encoded image (PNG, JPEG, ...)
+/// @param options - Redaction options
+/// @returns PNG-encoded redacted image and the list of hidden regions
+#[napi]
+pub async fn redact_image(
+    image: Buffer,
+    options: Option<RedactOptionsJs>,
+) -> Result<RedactResultJs> {
+    let options = options.unwrap_or_default();
+
+    let mut redactor = Redactor::new()
+        .with_method(parse_method(&options)?)
+        .with_redact_all_text(options.redact_all_text.unwrap_or(false));
+    if let Some(detectors) = &options.detectors {
+        let detectors =
+            detectors.iter().map(|name| parse_detector(name)).collect::<Result<Vec<_>>>()?;
+        redactor = redactor.with_detectors(&detectors);
+    }
+    if let Some(padding) = options.padding {
+        redactor = redactor.with_padding(padding as f32);
+    }
+
+    let regions: Vec<Region> = options
+        .regions
+        .unwrap_or_default()
+        .into_iter()
+        .map(|r| Region::new(r.x as f32, r.y as f32, r.width as f32, r.height as f32))
+        .collect();
+    let lines: Vec<TextLine> = options
+        .text
+        .unwrap_or_default()
+        .into_iter()
+        .map(|line| TextLine {
+            text: line.text,
+            bounds: Region::new(
+                line.x as f32,
+                line.y as f32,
+                line.width as f32,
+                line.height as f32,
+            ),
+        })
+        .collect();
+
+    let mut image = image::load_from_memory(&image)
+        .map_err(|e| Error::from_reason(format!("Failed to decode image: {}", e)))?
+        .to_rgba8();
+    let report = redactor.redact(&mut image, &regions, &lines).map_err(aumate_to_napi_error)?;
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| Error::from_reason(format!("Failed to encode image: {}", e)))?;
+
+    Ok(RedactResultJs {
+        image: Buffer::from(png),
+        items: report
+            .items
+            .into_iter()
+            .map(|item| RedactedItemJs {
+                reason: reason_name(item.reason).to_string(),
+                x: item.bounds.x as f64,
+                y: item.bounds.y as f64,
+                width: item.bounds.width as f64,
+                height: item.bounds.height as f64,
+                text: item.text,
+            })
+            .collect(),
+    })
+}
@@ -0,0 +1,245 @@
+//! Speech-to-text engine bindings
+
+use crate::{aumate_to_napi_error, ml::model_manager};
+use aumate::ml::ModelType;
+use aumate::stt::{AudioData, AudioRecorder, TranscriptionResult, WhisperEngine};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+
+/// Default Whisper model
+const DEFAULT_WHISPER_MODEL: &str = "whisper-base";
+
+/// Transcription result
+#[napi(object)]
+pub struct TranscriptionResultJs {
+    /// Transcribed text
+    pub text: String,
+    /// Detected or requested language
+    pub language: Option<String>,
+    /// Transcription time in milliseconds
+    pub duration_ms: u32,
+}
+
+impl From<TranscriptionResult> for TranscriptionResultJs {
+    fn from(r: TranscriptionResult) -> Self {
+        Self { text: r.text, language: r.language, duration_ms: r.duration_ms as u32 }
+    }
+}
+
+/// Audio input device
+#[napi(object)]
+pub struct AudioDeviceJs {
+    pub name: String,
+    pub is_default: bool,
+}
+
+/// Microphone recording running on its own thread
+///
+/// Audio streams are not `Send` on every platform, so the recorder lives on
+/// the thread that created it and hands back the audio when asked to stop.
+struct Recording {
+    stop: mpsc::Sender<mpsc::Sender<aumate::prelude::Result<AudioData>>>,
+}
+
+impl Recording {
+    fn start(device: Option<String>) -> Result<Self> {
+        let (started_tx, started_rx) = mpsc::channel();
+        let (stop_tx, stop_rx) =
+            mpsc::channel::<mpsc::Sender<aumate::prelude::Result<AudioData>>>();
+
+        std::thread::spawn(move || {
+            let mut recorder = match AudioRecorder::new() {
+                Ok(recorder) => recorder,
+                Err(e) => {
+                    let _ = started_tx.send(Err(e));
+                    return;
+                }
+            };
+            recorder.set_input_device(device);
+            if let Err(e) = recorder.start_recording() {
+                let _ = started_tx.send(Err(e));
+                return;
+            }
+            let _ = started_tx.send(Ok(()));
+
+            // Record until asked to stop (or the handle is dropped)
+            let reply = stop_rx.recv();
+            let audio = recorder.stop_recording();
+            if let Ok(reply) = reply {
+                let _ = reply.send(audio);
+            }
+        });
+
+        started_rx
+            .recv()
+            .map_err(|_| Error::from_reason("Recording thread exited unexpectedly"))?
+            .map_err(aumate_to_napi_error)?;
+        Ok(Self { stop: stop_tx })
+    }
+
+    fn stop(self) -> Result<AudioData> {
+        let (reply_tx, reply_rx) = mpsc::channel();
+        self.stop
+            .send(reply_tx)
+            .map_err(|_| Error::from_reason("Recording thread exited unexpectedly"))?;
+        reply_rx
+            .recv()
+            .map_err(|_| Error::from_reason("Recording thread exited unexpectedly"))?
+            .map_err(aumate_to_napi_error)
+    }
+}
+
+/// Whisper speech-to-text engine
+#[napi(js_name = "WhisperEngine")]
+pub struct WhisperEngineJs {
+    inner: Arc<Mutex<WhisperEngine>>,
+    recording: Mutex<Option<Recording>>,
+}
+
+#[napi]
+impl WhisperEngineJs {
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Self { inner: Arc::new(Mutex::new(WhisperEngine::new())), recording: Mutex::new(None) }
+    }
+
+    /// Load a downloaded model
+    ///
+    /// @param model - Model ID, e.g. "whisper-base" (default) or "whisper-small"
+    #[napi]
+    pub async fn load_model(&self, model: Option<String>) -> Result<()> {
+        let model_id = model.unwrap_or_else(|| DEFAULT_WHISPER_MODEL.to_string());
+        let path = model_manager()?
+            .get_model_path(ModelType::Whisper, &model_id)
+            .ok_or_else(|| Error::from_reason(format!("Model {} is not downloaded", model_id)))?;
+        self.run(move |engine| engine.load_model(&path)).await
+    }
+
+    /// Unload the current model
+    #[napi]
+    pub fn unload_model(&self) -> Result<()> {
+        self.lock()?.unload_model();
+        Ok(())
+    }
+
+    /// Whether a model is loaded
+    #[napi]
+    pub fn is_loaded(&self) -> Result<bool> {
+        Ok(self.lock()?.is_loaded())
+    }
+
+    /// Set the transcription language (e.g. "en"), or auto-detect when omitted
+    #[napi]
+    pub fn set_language(&self, language: Option<String>) -> Result<()> {
+        self.lock()?.set_language(language);
+        Ok(())
+    }
+
+    /// Transcribe a WAV-encoded buffer
+    #[napi]
+    pub async fn transcribe(&self, wav: Buffer) -> Result<TranscriptionResultJs> {
+        let bytes = wav.to_vec();
+        let result =
+            self.run(move |engine| engine.transcribe(&AudioData::from_wav_bytes(&bytes)?)).await?;
+        Ok(result.into())
+    }
+
+    /// Transcribe a WAV file
+    #[napi]
+    pub async fn transcribe_file(&self, path: String) -> Result<TranscriptionResultJs> {
+        let result = self
+            .run(move |engine| {
+                engine.transcribe(&AudioData::from_wav_file(std::path::Path::new(&path))?)
+            })
+            .await?;
+        Ok(result.into())
+    }
+
+    /// Transcribe raw mono samples in the range [-1.0, 1.0]
+    #[napi]
+    pub async fn transcribe_samples(
+        &self,
+        samples: Float32Array,
+        sample_rate: u32,
+    ) -> Result<TranscriptionResultJs> {
+        let audio = AudioData { samples: samples.to_vec(), sample_rate, channels: 1 };
+        let result = self.run(move |engine| engine.transcribe(&audio)).await?;
+        Ok(result.into())
+    }
+
+    /// Start recording from the microphone
+    ///
+    /// @param device - Input device name (default: system default)
+    #[napi]
+    pub fn start_recording(&self, device: Option<String>) -> Result<()> {
+        let mut recording = self.recording_lock()?;
+        if recording.is_some() {
+            return Err(Error::from_reason("Already recording"));
+        }
+        *recording = Some(Recording::start(device)?);
+        Ok(())
+    }
+
+    /// Stop recording and transcribe what was recorded
+    #[napi]
+    pub async fn stop_recording(&self) -> Result<TranscriptionResultJs> {
+        let recording =
+            self.recording_lock()?.take().ok_or_else(|| Error::from_reason("Not recording"))?;
+        let result = self
+            .run(move |engine| {
+                let audio = recording.stop().map_err(|e| {
+                    aumate::prelude::AumateError::Other(format!("Recording failed: {}", e))
+                })?;
+                engine.transcribe(&audio)
+            })
+            .await?;
+        Ok(result.into())
+    }
+
+    /// Whether the microphone is being recorded
+    #[napi]
+    pub fn is_recording(&self) -> Result<bool> {
+        Ok(self.recording_lock()?.is_some())
+    }
+
+    /// List audio input devices
+    #[napi]
+    pub fn list_input_devices() -> Result<Vec<AudioDeviceJs>> {
+        let devices = AudioRecorder::list_input_devices().map_err(aumate_to_napi_error)?;
+        Ok(devices
+            .into_iter()
+            .map(|d| AudioDeviceJs { name: d.name, is_default: d.is_default })
+            .collect())
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, WhisperEngine>> {
+        self.inner.lock().map_err(|e| Error::from_reason(format!("Lock error: {}", e)))
+    }
+
+    fn recording_lock(&self) -> Result<std::sync::MutexGuard<'_, Option<Recording>>> {
+        self.recording.lock().map_err(|e| Error::from_reason(format!("Lock error: {}", e)))
+    }
+
+    /// Run blocking work on the engine off the async runtime
+    async fn run<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut WhisperEngine) -> aumate::prelude::Result<T> + Send + 'static,
+    ) -> Result<T> {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut engine =
+                inner.lock().map_err(|e| Error::from_reason(format!("Lock error: {}", e)))?;
+            f(&mut engine).map_err(aumate_to_napi_error)
+        })
+        .await
+        .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))?
+    }
+}
+
+impl Default for WhisperEngineJs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,269 @@
+//! Screenshot comparison and baseline directory bindings
+
+use crate::aumate_to_napi_error;
+use aumate::visual::{
+    self, BaselineOutcome, BaselineStore, ChannelThresholds, CompareOptions, DiffRegion, DiffResult,
+};
+use image::RgbaImage;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// Rectangle in image pixels
+#[napi(object)]
+pub struct DiffRegionJs {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl From<DiffRegion> for DiffRegionJs {
+    fn from(r: DiffRegion) -> Self {
+        Self { x: r.x, y: r.y, width: r.width, height: r.height }
+    }
+}
+
+/// Per-channel tolerance, 0-255
+#[napi(object)]
+pub struct ChannelThresholdsJs {
+    pub r: Option<u32>,
+    pub g: Option<u32>,
+    pub b: Option<u32>,
+    pub a: Option<u32>,
+}
+
+/// Options for comparing screenshots
+#[napi(object)]
+#[derive(Default)]
+pub struct CompareOptionsJs {
+    /// Tolerance for every channel, 0-255 (default: 0)
+    pub threshold: Option<u32>,
+    /// Per-channel tolerance, overrides `threshold` for the given channels
+    pub channel_thresholds: Option<ChannelThresholdsJs>,
+    /// Skip anti-aliased edge pixels (default: true)
+    pub ignore_anti_aliasing: Option<bool>,
+    /// Regions left out of the comparison
+    pub ignore_regions: Option<Vec<DiffRegionJs>>,
+    /// Changed pixels within this distance form one region (default: 8)
+    pub merge_distance: Option<u32>,
+}
+
+fn to_channel(value: u32) -> u8 {
+    value.min(255) as u8
+}
+
+fn config_js_to_compare_options(options: Option<CompareOptionsJs>) -> CompareOptions {
+    let Some(o) = options else {
+        return CompareOptions::default();
+    };
+
+    let mut co = CompareOptions::default();
+    if let Some(v) = o.threshold {
+        co.thresholds = ChannelThresholds::uniform(to_channel(v));
+    }
+    if let Some(c) = o.channel_thresholds {
+        let t = co.thresholds;
+        co.thresholds = ChannelThresholds::new(
+            c.r.map_or(t.r, to_channel),
+            c.g.map_or(t.g, to_channel),
+            c.b.map_or(t.b, to_channel),
+            c.a.map_or(t.a, to_channel),
+        );
+    }
+    if let Some(v) = o.ignore_anti_aliasing {
+        co.ignore_anti_aliasing = v;
+    }
+    if let Some(v) = o.ignore_regions {
+        co.ignore_regions =
+            v.into_iter().map(|r| DiffRegion::new(r.x, r.y, r.width, r.height)).collect();
+    }
+    if let Some(v) = o.merge_distance {
+        co.merge_distance = v;
+    }
+    co
+}
+
+/// Result of comparing two screenshots
+#[napi(object)]
+pub struct VisualDiffJs {
+    /// Share of compared pixels that are unchanged, 0.0-1.0
+    pub similarity: f64,
+    /// Number of changed pixels
+    pub changed_pixels: i64,
+    /// Number of compared pixels, ignored regions excluded
+    pub compared_pixels: i64,
+    /// Number of differing pixels skipped as anti-aliasing
+    pub anti_aliased_pixels: i64,
+    /// Bounding boxes of changed areas, top to bottom
+    pub regions: Vec<DiffRegionJs>,
+    /// PNG-encoded diff image with changes highlighted
+    pub diff_image: Buffer,
+}
+
+fn diff_to_js(diff: DiffResult) -> Result<VisualDiffJs> {
+    Ok(VisualDiffJs {
+        similarity: diff.similarity,
+        changed_pixels: diff.changed_pixels as i64,
+        compared_pixels: diff.compared_pixels as i64,
+        anti_aliased_pixels: diff.anti_aliased_pixels as i64,
+        regions: diff.regions.into_iter().map(DiffRegionJs::from).collect(),
+        diff_image: encode_png(&diff.diff_image)?,
+    })
+}
+
+fn decode_image(data: &[u8]) -> Result<RgbaImage> {
+    Ok(image::load_from_memory(data)
+        .map_err(|e| Error::from_reason(format!("Failed to decode image: {}", e)))?
+        .to_rgba8())
+}
+
+fn encode_png(image: &RgbaImage) -> Result<Buffer> {
+    let mut png = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| Error::from_reason(format!("Failed to encode image: {}", e)))?;
+    Ok(Buffer::from(png))
+}
+
+/// Compare a screenshot against its baseline
+///
+/// @param baseline - Encoded expected image (PNG, JPEG, ...)
+/// @param current - Encoded actual image, same size as the baseline
+/// @param options - Optional thresholds, ignore regions and anti-aliasing handling
+/// @returns Similarity, changed regions and a PNG diff image
+#[napi]
+pub async fn compare_images(
+    baseline: Buffer,
+    current: Buffer,
+    options: Option<CompareOptionsJs>,
+) -> Result<VisualDiffJs> {
+    let (baseline, current) = (baseline.to_vec(), current.to_vec());
+    let co = config_js_to_compare_options(options);
+
+    let diff = tokio::task::spawn_blocking(move || {
+        visual::compare(&decode_image(&baseline)?, &decode_image(&current)?, &co)
+            .map_err(aumate_to_napi_error)
+    })
+    .await
+    .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))??;
+
+    diff_to_js(diff)
+}
+
+/// Options for checking a screenshot against a baseline directory
+#[napi(object)]
+#[derive(Default)]
+pub struct BaselineOptionsJs {
+    /// Comparison options
+    pub compare: Option<CompareOptionsJs>,
+    /// Lowest similarity that still passes, 0.0-1.0 (default: 1.0)
+    pub min_similarity: Option<f64>,
+    /// Overwrite the baseline instead of comparing (default: false)
+    pub update: Option<bool>,
+}
+
+/// Outcome of checking a screenshot against its baseline
+#[napi(object)]
+pub struct BaselineResultJs {
+    /// "recorded", "passed" or "failed"
+    pub status: String,
+    /// Whether the screenshot was recorded or matched
+    pub passed: bool,
+    /// Path of the baseline image
+    pub baseline_path: String,
+    /// Path of the saved actual image, set when failed
+    pub actual_path: Option<String>,
+    /// Path of the saved diff image, set when failed
+    pub diff_path: Option<String>,
+    /// Comparison result, missing when the baseline was recorded
+    pub diff: Option<VisualDiffJs>,
+}
+
+fn outcome_to_js(outcome: BaselineOutcome) -> Result<BaselineResultJs> {
+    let path = |p: &std::path::Path| p.to_string_lossy().into_owned();
+    let (status, actual_path, diff_path) = match &outcome {
+        BaselineOutcome::Recorded { .. } => ("recorded", None, None),
+        BaselineOutcome::Passed { .. } => ("passed", None, None),
+        BaselineOutcome::Failed { actual_path, diff_path, .. } => {
+            ("failed", Some(path(actual_path)), Some(path(diff_path)))
+        }
+    };
+    let passed = outcome.is_passed();
+    let baseline_path = path(outcome.baseline_path());
+    let diff = match outcome {
+        BaselineOutcome::Recorded { .. } => None,
+        BaselineOutcome::Passed { diff, .. } | BaselineOutcome::Failed { diff, .. } => {
+            Some(diff_to_js(diff)?)
+        }
+    };
+
+    Ok(BaselineResultJs {
+        status: status.to_string(),
+        passed,
+        baseline_path,
+        actual_path,
+        diff_path,
+        diff,
+    })
+}
+
+fn baseline_store(dir: String, options: Option<BaselineOptionsJs>) -> BaselineStore {
+    let options = options.unwrap_or_default();
+    let mut store = BaselineStore::new(dir)
+        .with_options(config_js_to_compare_options(options.compare))
+        .with_update(options.update.unwrap_or(false));
+    if let Some(v) = options.min_similarity {
+        store = store.with_min_similarity(v);
+    }
+    store
+}
+
+/// Check an image against a baseline directory
+///
+/// Records `<dir>/<name>.png` when it does not exist yet and compares against
+/// it otherwise. Failures leave `<name>.actual.png` and `<name>.diff.png` next to it.
+///
+/// @param dir - Baseline directory
+/// @param name - Baseline name, without extension
+/// @param image - Encoded screenshot (PNG, JPEG, ...)
+/// @param options - Optional comparison options, pass threshold and update flag
+#[napi]
+pub async fn check_baseline(
+    dir: String,
+    name: String,
+    image: Buffer,
+    options: Option<BaselineOptionsJs>,
+) -> Result<BaselineResultJs> {
+    let data = image.to_vec();
+    let store = baseline_store(dir, options);
+
+    let outcome = tokio::task::spawn_blocking(move || {
+        store.check(&name, &decode_image(&data)?).map_err(aumate_to_napi_error)
+    })
+    .await
+    .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))??;
+
+    outcome_to_js(outcome)
+}
+
+/// Capture the primary screen and check it against a baseline directory
+///
+/// @param dir - Baseline directory
+/// @param name - Baseline name, without extension
+/// @param options - Optional comparison options, pass threshold and update flag
+#[napi]
+pub async fn check_screen_baseline(
+    dir: String,
+    name: String,
+    options: Option<BaselineOptionsJs>,
+) -> Result<BaselineResultJs> {
+    let store = baseline_store(dir, options);
+
+    let outcome = tokio::task::spawn_blocking(move || {
+        store.check_screen(&name).map_err(aumate_to_napi_error)
+    })
+    .await
+    .map_err(|e| Error::from_reason(format!("Task failed: {}", e)))??;
+
+    outcome_to_js(outcome)
+}